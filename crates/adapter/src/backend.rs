@@ -15,6 +15,7 @@ use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::fmt;
 use std::time::Duration;
+use unrelated_http_tools::context::ProgressSink;
 
 pub type JsonObject = serde_json::Map<String, Value>;
 
@@ -159,6 +160,8 @@ pub trait Backend: Send + Sync {
     ///   per-session process lifecycles or other session-scoped behavior.
     /// * `name` - The tool name (original name, without server prefix)
     /// * `arguments` - Tool arguments as JSON
+    /// * `timeout` - Remaining call budget (if any)
+    /// * `progress` - Receives progress updates when the client asked for them
     ///
     /// # Returns
     /// The tool result, or an error.
@@ -168,6 +171,7 @@ pub trait Backend: Send + Sync {
         name: &str,
         arguments: Value,
         timeout: Option<Duration>,
+        progress: Option<ProgressSink>,
     ) -> Result<CallToolResult>;
 
     /// List all resources provided by this backend.
@@ -20,7 +20,7 @@ pub use unrelated_http_tools::config::{
 };
 pub use unrelated_openapi_tools::config::{
     ApiServerConfig, AutoDiscoverConfig, HashPolicy, OpenApiOverrideToolConfig,
-    OpenApiOverridesConfig,
+    OpenApiOverridesConfig, SecurityConfig, SecurityCredential,
 };
 
 // NOTE: env-backed deserializers live in `unrelated-env` so they can be shared across crates.
@@ -355,12 +355,12 @@ pub enum ServerConfig {
     #[serde(rename = "openapi")]
     OpenApi {
         #[serde(flatten)]
-        config: ApiServerConfig,
+        config: Box<ApiServerConfig>,
     },
     #[serde(rename = "http")]
     Http {
         #[serde(flatten)]
-        config: HttpServerConfig,
+        config: Box<HttpServerConfig>,
     },
 }
 
@@ -435,14 +435,21 @@ impl AdapterConfig {
                 spec_hash_policy: HashPolicy::Ignore,
                 base_url: None,
                 auth: None,
+                security: SecurityConfig::default(),
                 auto_discover: AutoDiscoverConfig::Enabled(true),
                 endpoints: HashMap::new(),
                 defaults: EndpointDefaults::default(),
                 response_transforms: Vec::new(),
+                xml: Default::default(),
                 response_overrides: Vec::new(),
                 overrides: OpenApiOverridesConfig::default(),
             };
-            servers.insert("default".to_string(), ServerConfig::OpenApi { config: cfg });
+            servers.insert(
+                "default".to_string(),
+                ServerConfig::OpenApi {
+                    config: Box::new(cfg),
+                },
+            );
         }
 
         // 5) Validate: must have at least one config source (unless a config file was explicitly provided).
@@ -464,7 +471,12 @@ impl AdapterConfig {
             )));
         }
 
-        // 7) Clamp tool call timeout to the shared cap (Gateway ↔ Adapter coordination).
+        // 7) Validate transforms (pointer syntax, duplicate targets).
+        transforms
+            .validate()
+            .map_err(|e| AdapterError::Config(format!("Invalid transforms: {e}")))?;
+
+        // 8) Clamp tool call timeout to the shared cap (Gateway ↔ Adapter coordination).
         let cap = crate::timeouts::tool_call_timeout_cap_secs();
         if adapter.call_timeout == 0 {
             return Err(AdapterError::Config("callTimeout must be > 0".to_string()));
@@ -696,10 +708,10 @@ fn expand_server_env_vars(server: ServerConfig) -> Result<ServerConfig> {
             config: expand_mcp_env_vars(config)?,
         }),
         ServerConfig::OpenApi { config } => Ok(ServerConfig::OpenApi {
-            config: expand_api_env_vars(config)?,
+            config: Box::new(expand_api_env_vars(*config)?),
         }),
         ServerConfig::Http { config } => Ok(ServerConfig::Http {
-            config: expand_http_env_vars(config)?,
+            config: Box::new(expand_http_env_vars(*config)?),
         }),
     }
 }
@@ -732,6 +744,12 @@ fn expand_api_env_vars(mut config: ApiServerConfig) -> Result<ApiServerConfig> {
     if let Some(auth) = config.auth {
         config.auth = Some(expand_auth_env_vars(auth)?);
     }
+    config.security.credentials = config
+        .security
+        .credentials
+        .into_iter()
+        .map(|(k, v)| Ok((k, expand_security_credential_env_vars(v)?)))
+        .collect::<Result<HashMap<_, _>>>()?;
     // Expand headers in defaults
     config.defaults.headers = config
         .defaults
@@ -745,6 +763,34 @@ fn expand_api_env_vars(mut config: ApiServerConfig) -> Result<ApiServerConfig> {
     Ok(config)
 }
 
+fn expand_security_credential_env_vars(
+    credential: SecurityCredential,
+) -> Result<SecurityCredential> {
+    Ok(match credential {
+        SecurityCredential::ApiKey { value } => SecurityCredential::ApiKey {
+            value: expand_env_string(&value)?,
+        },
+        SecurityCredential::Bearer { token } => SecurityCredential::Bearer {
+            token: expand_env_string(&token)?,
+        },
+        SecurityCredential::Basic { username, password } => SecurityCredential::Basic {
+            username: expand_env_string(&username)?,
+            password: expand_env_string(&password)?,
+        },
+        SecurityCredential::Oauth2ClientCredentials {
+            client_id,
+            client_secret,
+            scopes,
+            token_url,
+        } => SecurityCredential::Oauth2ClientCredentials {
+            client_id: expand_env_string(&client_id)?,
+            client_secret: expand_env_string(&client_secret)?,
+            scopes,
+            token_url: token_url.map(|u| expand_env_string(&u)).transpose()?,
+        },
+    })
+}
+
 fn expand_openapi_overrides_env_vars(
     mut overrides: OpenApiOverridesConfig,
 ) -> Result<OpenApiOverridesConfig> {
@@ -834,6 +880,36 @@ fn expand_auth_env_vars(auth: AuthConfig) -> Result<AuthConfig> {
             name,
             value: expand_env_string(&value)?,
         },
+        AuthConfig::Sigv4 {
+            access_key_id,
+            secret_access_key,
+            session_token,
+            region,
+            service,
+        } => AuthConfig::Sigv4 {
+            access_key_id: expand_env_string(&access_key_id)?,
+            secret_access_key: expand_env_string(&secret_access_key)?,
+            session_token: session_token
+                .map(|token| expand_env_string(&token))
+                .transpose()?,
+            region: expand_env_string(&region)?,
+            service,
+        },
+        AuthConfig::Hmac {
+            secret,
+            key_id,
+            signature_header,
+            timestamp_header,
+            key_id_header,
+            encoding,
+        } => AuthConfig::Hmac {
+            secret: expand_env_string(&secret)?,
+            key_id: key_id.map(|id| expand_env_string(&id)).transpose()?,
+            signature_header,
+            timestamp_header,
+            key_id_header,
+            encoding,
+        },
     })
 }
 
@@ -15,6 +15,7 @@ use rmcp::model::{CallToolResult, GetPromptResult, ReadResourceResult};
 use serde_json::Value;
 use std::sync::Arc;
 use std::time::Duration;
+use unrelated_http_tools::context::{CallContext, ProgressSink};
 use unrelated_http_tools::runtime::{HttpToolSource, HttpToolsError};
 
 pub struct HttpBackend {
@@ -110,6 +111,7 @@ impl Backend for HttpBackend {
         name: &str,
         arguments: Value,
         timeout: Option<Duration>,
+        progress: Option<ProgressSink>,
     ) -> Result<CallToolResult> {
         let Some(source) = self.source.read().clone() else {
             return Err(AdapterError::Runtime(format!(
@@ -118,8 +120,13 @@ impl Backend for HttpBackend {
             )));
         };
 
-        let fut = source.call_tool(name, arguments);
-        if let Some(t) = timeout.filter(|t| *t > Duration::from_millis(0)) {
+        let timeout = timeout.filter(|t| *t > Duration::from_millis(0));
+        let mut ctx = timeout.map(CallContext::with_timeout).unwrap_or_default();
+        if let Some(sink) = progress {
+            ctx = ctx.with_progress(sink);
+        }
+        let fut = source.call_tool_with_context(name, arguments, &ctx);
+        if let Some(t) = timeout {
             match tokio::time::timeout(t, fut).await {
                 Ok(r) => r.map_err(map_http_tools_error),
                 Err(_) => Err(AdapterError::Runtime(format!(
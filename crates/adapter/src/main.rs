@@ -276,7 +276,7 @@ fn register_backends_from_config(
                 tracing::info!("Creating OpenAPI backend: {}", name);
                 let backend = Arc::new(OpenApiBackend::new(
                     name.clone(),
-                    api_cfg,
+                    *api_cfg,
                     adapter.call_timeout_duration(),
                     adapter.startup_timeout_duration(),
                     adapter.openapi_probe,
@@ -288,7 +288,7 @@ fn register_backends_from_config(
                 tracing::info!("Creating HTTP backend: {}", name);
                 let backend = Arc::new(crate::http_backend::HttpBackend::new(
                     name.clone(),
-                    http_cfg,
+                    *http_cfg,
                     adapter.call_timeout_duration(),
                 ));
                 backend_manager.add_backend(backend);
@@ -321,6 +321,9 @@ async fn refresh_aggregator(
             .into_iter()
             .map(|t| {
                 let mut schema = t.input_schema;
+                if let Err(e) = transforms.validate_against_schema(&t.original_name, &schema) {
+                    tracing::warn!("Backend '{}': {}", backend.name(), e);
+                }
                 transforms.apply_schema_transforms(&t.original_name, &mut schema);
 
                 crate::aggregator::ToolInfo {
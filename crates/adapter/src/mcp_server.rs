@@ -13,10 +13,10 @@ use rmcp::{
     model::{
         AnnotateAble, CallToolRequestParams, CallToolResult, CompleteRequestParams, CompleteResult,
         Content, GetPromptRequestParams, GetPromptResult, Implementation, ListPromptsResult,
-        ListResourcesResult, ListToolsResult, PaginatedRequestParams, Prompt, ProtocolVersion,
-        RawResource, ReadResourceRequestParams, ReadResourceResult, Reference, Resource,
-        ServerCapabilities, ServerInfo, SetLevelRequestParams, SubscribeRequestParams, Tool,
-        UnsubscribeRequestParams,
+        ListResourcesResult, ListToolsResult, PaginatedRequestParams, ProgressNotificationParam,
+        Prompt, ProtocolVersion, RawResource, ReadResourceRequestParams, ReadResourceResult,
+        Reference, Resource, ServerCapabilities, ServerInfo, SetLevelRequestParams,
+        SubscribeRequestParams, Tool, UnsubscribeRequestParams,
     },
     service::{RequestContext, RoleServer},
 };
@@ -24,6 +24,7 @@ use serde_json::Value;
 use std::sync::Arc;
 use std::time::Duration;
 use std::time::Instant;
+use unrelated_http_tools::context::{ProgressSink, ProgressUpdate};
 use unrelated_tool_transforms::TransformPipeline;
 
 fn mcp_session_id_from_context(context: &RequestContext<RoleServer>) -> Option<&str> {
@@ -45,6 +46,31 @@ fn timeout_budget_from_meta(meta: &rmcp::model::Meta) -> Option<Duration> {
     Some(Duration::from_millis(timeout_ms))
 }
 
+/// Forward backend progress updates as `notifications/progress` when the client sent a
+/// `progressToken`.
+fn progress_sink(context: &RequestContext<RoleServer>) -> Option<ProgressSink> {
+    let progress_token = context.meta.get_progress_token()?;
+    let peer = context.peer.clone();
+    let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel::<ProgressUpdate>();
+    tokio::spawn(async move {
+        while let Some(update) = rx.recv().await {
+            let param = ProgressNotificationParam {
+                progress_token: progress_token.clone(),
+                progress: update.progress,
+                total: update.total,
+                message: update.message,
+            };
+            if let Err(e) = peer.notify_progress(param).await {
+                tracing::debug!(error = %e, "failed to send progress notification");
+                break;
+            }
+        }
+    });
+    Some(Arc::new(move |update| {
+        let _ = tx.send(update);
+    }))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -421,11 +447,18 @@ impl ServerHandler for AdapterMcpServer {
         let args_value = serde_json::Value::Object(arguments);
 
         let timeout_budget = timeout_budget_from_meta(&context.meta);
+        let progress = progress_sink(&context);
 
         // Call the tool
         let backend_type = backend.backend_type();
         match backend
-            .call_tool(session_id, &original_tool_name, args_value, timeout_budget)
+            .call_tool(
+                session_id,
+                &original_tool_name,
+                args_value,
+                timeout_budget,
+                progress,
+            )
             .await
         {
             Ok(result) => {
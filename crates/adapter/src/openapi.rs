@@ -16,6 +16,7 @@ use rmcp::model::{CallToolResult, GetPromptResult, ReadResourceResult};
 use serde_json::Value;
 use std::sync::Arc;
 use std::time::Duration;
+use unrelated_http_tools::context::{CallContext, ProgressSink};
 use unrelated_openapi_tools::error::OpenApiToolsError;
 use unrelated_openapi_tools::runtime::OpenApiToolSource;
 
@@ -135,9 +136,15 @@ impl Backend for OpenApiBackend {
         name: &str,
         arguments: Value,
         timeout: Option<Duration>,
+        progress: Option<ProgressSink>,
     ) -> Result<CallToolResult> {
-        let fut = self.source.call_tool(name, arguments);
-        if let Some(t) = timeout.filter(|t| *t > Duration::from_millis(0)) {
+        let timeout = timeout.filter(|t| *t > Duration::from_millis(0));
+        let mut ctx = timeout.map(CallContext::with_timeout).unwrap_or_default();
+        if let Some(sink) = progress {
+            ctx = ctx.with_progress(sink);
+        }
+        let fut = self.source.call_tool_with_context(name, arguments, &ctx);
+        if let Some(t) = timeout {
             match tokio::time::timeout(t, fut).await {
                 Ok(r) => r.map_err(map_openapi_tools_error),
                 Err(_) => Err(AdapterError::Runtime(format!(
@@ -35,6 +35,7 @@ use tokio::process::Command;
 use tokio::sync::Mutex;
 use tokio::sync::mpsc::UnboundedSender;
 use tokio::time::timeout;
+use unrelated_http_tools::context::ProgressSink;
 
 async fn refresh_lists_from_peer(
     peer: &Peer<RoleClient>,
@@ -1241,6 +1242,7 @@ impl Backend for StdioBackend {
         name: &str,
         arguments: Value,
         timeout: Option<Duration>,
+        _progress: Option<ProgressSink>,
     ) -> Result<CallToolResult> {
         let effective_timeout = timeout
             .filter(|t| *t > Duration::from_millis(0))
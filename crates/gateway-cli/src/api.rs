@@ -112,6 +112,9 @@ pub struct ToolPolicy {
     /// Optional per-tool retry policy (Gateway-only).
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub retry: Option<RetryPolicy>,
+    /// Optional response shaping chain for upstream MCP results (Gateway-only; passed through).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub response_transforms: Option<serde_json::Value>,
 }
 
 #[derive(Debug, Clone, Copy, Serialize, Deserialize)]
@@ -148,6 +151,117 @@ pub struct OidcPrincipalBinding {
     pub enabled: bool,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TenantOidcIssuer {
+    pub issuer: String,
+    pub audiences: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub jwks_uri: Option<String>,
+    pub allowed_algs: Vec<String>,
+    pub enabled: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpstreamOAuthClient {
+    pub client_id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub client_secret_ref: Option<String>,
+    #[serde(default)]
+    pub scopes: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub authorization_endpoint: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub token_endpoint: Option<String>,
+    pub enabled: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpstreamOAuthResponse {
+    pub client: UpstreamOAuthClient,
+    #[serde(default)]
+    pub redirect_uri: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpstreamOAuthLink {
+    pub principal: String,
+    pub expires_at_unix: Option<i64>,
+    pub has_refresh_token: bool,
+    pub updated_at_unix: i64,
+}
+
+/// Principal to link: an API key, or an OIDC issuer + subject.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpstreamOAuthLinkRequest {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub api_key_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub oidc_issuer: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub oidc_subject: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpstreamOAuthLinkStart {
+    pub principal: String,
+    pub authorization_url: String,
+    pub expires_at_unix: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct McpSession {
+    pub session_id: String,
+    pub tenant_id: String,
+    pub profile_id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub principal: Option<String>,
+    pub created_at_unix: i64,
+    pub last_seen_at_unix: i64,
+    pub expires_at_unix: i64,
+    pub upstreams: Vec<McpSessionUpstream>,
+    pub open_streams: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct McpSessionUpstream {
+    pub upstream_id: String,
+    pub endpoint_id: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct McpSessionsResponse {
+    sessions: Vec<McpSession>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OidcClaimRule {
+    pub id: String,
+    pub issuer: String,
+    pub profile_id: Option<String>,
+    pub enabled: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    pub conditions: Vec<OidcClaimCondition>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OidcClaimCondition {
+    pub claim: String,
+    pub op: String,
+    pub value: String,
+}
+
 #[derive(Debug, Clone)]
 pub struct ProfileUpsert {
     pub tenant_id: String,
@@ -319,6 +433,57 @@ impl ApiClient {
         Ok(())
     }
 
+    pub async fn list_sessions(
+        &self,
+        tenant_id: Option<&str>,
+        profile_id: Option<&str>,
+    ) -> anyhow::Result<Vec<McpSession>> {
+        let mut url = self.url("/admin/v1/sessions")?;
+        if let Some(tenant_id) = tenant_id {
+            url.query_pairs_mut().append_pair("tenantId", tenant_id);
+        }
+        if let Some(profile_id) = profile_id {
+            url.query_pairs_mut().append_pair("profileId", profile_id);
+        }
+        let resp: McpSessionsResponse = self
+            .auth(self.http.get(url))
+            .send()
+            .await
+            .context("GET /admin/v1/sessions")?
+            .error_for_status()
+            .context("GET /admin/v1/sessions status")?
+            .json()
+            .await
+            .context("parse sessions response")?;
+        Ok(resp.sessions)
+    }
+
+    pub async fn get_session(&self, session_id: &str) -> anyhow::Result<McpSession> {
+        let url = self.url(&format!("/admin/v1/sessions/{session_id}"))?;
+        let session: McpSession = self
+            .auth(self.http.get(url))
+            .send()
+            .await
+            .context("GET /admin/v1/sessions/{session_id}")?
+            .error_for_status()
+            .context("GET /admin/v1/sessions/{session_id} status")?
+            .json()
+            .await
+            .context("parse session response")?;
+        Ok(session)
+    }
+
+    pub async fn terminate_session(&self, session_id: &str) -> anyhow::Result<()> {
+        let url = self.url(&format!("/admin/v1/sessions/{session_id}"))?;
+        self.auth(self.http.delete(url))
+            .send()
+            .await
+            .context("DELETE /admin/v1/sessions/{session_id}")?
+            .error_for_status()
+            .context("DELETE /admin/v1/sessions/{session_id} status")?;
+        Ok(())
+    }
+
     pub async fn create_profile(
         &self,
         profile: ProfileUpsert,
@@ -437,6 +602,8 @@ impl ApiClient {
         &self,
         tenant_id: &str,
         ttl_seconds: Option<u64>,
+        role: Option<&str>,
+        scopes: &[String],
     ) -> anyhow::Result<IssueTenantTokenResponse> {
         let url = self.url("/admin/v1/tenant-tokens")?;
         let resp: IssueTenantTokenResponse = self
@@ -444,6 +611,8 @@ impl ApiClient {
             .json(&IssueTenantTokenRequest {
                 tenant_id: tenant_id.to_string(),
                 ttl_seconds,
+                role: role.map(ToString::to_string),
+                scopes: (!scopes.is_empty()).then(|| scopes.to_vec()),
             })
             .send()
             .await
@@ -456,6 +625,19 @@ impl ApiClient {
         Ok(resp)
     }
 
+    pub async fn revoke_tenant_token(&self, tenant_id: &str, token_id: &str) -> anyhow::Result<()> {
+        let url = self.url(&format!(
+            "/admin/v1/tenants/{tenant_id}/tenant-tokens/{token_id}"
+        ))?;
+        self.auth(self.http.delete(url))
+            .send()
+            .await
+            .context("DELETE /admin/v1/tenants/{tenant_id}/tenant-tokens/{token_id}")?
+            .error_for_status()
+            .context("DELETE /admin/v1/tenants/{tenant_id}/tenant-tokens/{token_id} status")?;
+        Ok(())
+    }
+
     pub async fn list_tool_sources(&self, tenant_id: &str) -> anyhow::Result<Vec<ToolSource>> {
         let url = self.url(&format!("/admin/v1/tenants/{tenant_id}/tool-sources"))?;
         let resp: ToolSourcesResponse = self
@@ -565,8 +747,12 @@ impl ApiClient {
     pub async fn list_oidc_principals(
         &self,
         tenant_id: &str,
+        issuer: Option<&str>,
     ) -> anyhow::Result<Vec<OidcPrincipalBinding>> {
-        let url = self.url(&format!("/admin/v1/tenants/{tenant_id}/oidc-principals"))?;
+        let mut url = self.url(&format!("/admin/v1/tenants/{tenant_id}/oidc-principals"))?;
+        if let Some(issuer) = issuer {
+            url.query_pairs_mut().append_pair("issuer", issuer);
+        }
         let resp: OidcPrincipalsResponse = self
             .auth(self.http.get(url))
             .send()
@@ -584,6 +770,7 @@ impl ApiClient {
         &self,
         tenant_id: &str,
         subject: &str,
+        issuer: Option<&str>,
         profile_id: Option<&str>,
         enabled: bool,
     ) -> anyhow::Result<()> {
@@ -591,6 +778,7 @@ impl ApiClient {
         self.auth(self.http.put(url))
             .json(&PutOidcPrincipalRequest {
                 subject,
+                issuer,
                 profile_id,
                 enabled,
             })
@@ -606,6 +794,7 @@ impl ApiClient {
         &self,
         tenant_id: &str,
         subject: &str,
+        issuer: Option<&str>,
         profile_id: Option<&str>,
     ) -> anyhow::Result<()> {
         let mut url = self.url(&format!(
@@ -614,6 +803,9 @@ impl ApiClient {
         if let Some(pid) = profile_id {
             url.query_pairs_mut().append_pair("profileId", pid);
         }
+        if let Some(issuer) = issuer {
+            url.query_pairs_mut().append_pair("issuer", issuer);
+        }
         self.auth(self.http.delete(url))
             .send()
             .await
@@ -623,8 +815,229 @@ impl ApiClient {
         Ok(())
     }
 
+    pub async fn list_oidc_claim_rules(
+        &self,
+        tenant_id: &str,
+        issuer: Option<&str>,
+    ) -> anyhow::Result<Vec<OidcClaimRule>> {
+        let mut url = self.url(&format!("/admin/v1/tenants/{tenant_id}/oidc-claim-rules"))?;
+        if let Some(issuer) = issuer {
+            url.query_pairs_mut().append_pair("issuer", issuer);
+        }
+        let resp: OidcClaimRulesResponse = self
+            .auth(self.http.get(url))
+            .send()
+            .await
+            .context("GET /admin/v1/tenants/{tenant_id}/oidc-claim-rules")?
+            .error_for_status()
+            .context("GET /admin/v1/tenants/{tenant_id}/oidc-claim-rules status")?
+            .json()
+            .await
+            .context("parse oidc claim rules response")?;
+        Ok(resp.rules)
+    }
+
+    pub async fn put_oidc_claim_rule(
+        &self,
+        tenant_id: &str,
+        rule_id: &str,
+        rule: &OidcClaimRuleUpsert<'_>,
+    ) -> anyhow::Result<OidcClaimRule> {
+        let url = self.url(&format!(
+            "/admin/v1/tenants/{tenant_id}/oidc-claim-rules/{rule_id}"
+        ))?;
+        let rule: OidcClaimRule = self
+            .auth(self.http.put(url))
+            .json(rule)
+            .send()
+            .await
+            .context("PUT /admin/v1/tenants/{tenant_id}/oidc-claim-rules/{rule_id}")?
+            .error_for_status()
+            .context("PUT /admin/v1/tenants/{tenant_id}/oidc-claim-rules/{rule_id} status")?
+            .json()
+            .await
+            .context("parse oidc claim rule response")?;
+        Ok(rule)
+    }
+
+    pub async fn delete_oidc_claim_rule(
+        &self,
+        tenant_id: &str,
+        rule_id: &str,
+        issuer: Option<&str>,
+    ) -> anyhow::Result<()> {
+        let mut url = self.url(&format!(
+            "/admin/v1/tenants/{tenant_id}/oidc-claim-rules/{rule_id}"
+        ))?;
+        if let Some(issuer) = issuer {
+            url.query_pairs_mut().append_pair("issuer", issuer);
+        }
+        self.auth(self.http.delete(url))
+            .send()
+            .await
+            .context("DELETE /admin/v1/tenants/{tenant_id}/oidc-claim-rules/{rule_id}")?
+            .error_for_status()
+            .context("DELETE /admin/v1/tenants/{tenant_id}/oidc-claim-rules/{rule_id} status")?;
+        Ok(())
+    }
+
+    /// Dry-run a sample token (or raw claims) against the tenant's OIDC bindings and claim rules.
+    pub async fn test_oidc_claim_rules(
+        &self,
+        tenant_id: &str,
+        body: &serde_json::Value,
+    ) -> anyhow::Result<serde_json::Value> {
+        let url = self.url(&format!(
+            "/admin/v1/tenants/{tenant_id}/oidc-claim-rules/test"
+        ))?;
+        self.auth(self.http.post(url))
+            .json(body)
+            .send()
+            .await
+            .context("POST /admin/v1/tenants/{tenant_id}/oidc-claim-rules/test")?
+            .error_for_status()
+            .context("POST /admin/v1/tenants/{tenant_id}/oidc-claim-rules/test status")?
+            .json()
+            .await
+            .context("parse oidc claim rules test response")
+    }
+
     // Tenant API (requires a tenant token as this client's bearer).
-    pub async fn list_api_keys(&self) -> anyhow::Result<Vec<ApiKeyMetadata>> {
+    pub async fn list_oidc_issuers(&self) -> anyhow::Result<Vec<TenantOidcIssuer>> {
+        let url = self.url("/tenant/v1/oidc/issuers")?;
+        let resp: OidcIssuersResponse = self
+            .auth(self.http.get(url))
+            .send()
+            .await
+            .context("GET /tenant/v1/oidc/issuers")?
+            .error_for_status()
+            .context("GET /tenant/v1/oidc/issuers status")?
+            .json()
+            .await
+            .context("parse oidc issuers response")?;
+        Ok(resp.issuers)
+    }
+
+    pub async fn put_oidc_issuer(&self, issuer: &TenantOidcIssuer) -> anyhow::Result<()> {
+        let url = self.url("/tenant/v1/oidc/issuers")?;
+        self.auth(self.http.put(url))
+            .json(issuer)
+            .send()
+            .await
+            .context("PUT /tenant/v1/oidc/issuers")?
+            .error_for_status()
+            .context("PUT /tenant/v1/oidc/issuers status")?;
+        Ok(())
+    }
+
+    pub async fn delete_oidc_issuer(&self, issuer: &str) -> anyhow::Result<()> {
+        let mut url = self.url("/tenant/v1/oidc/issuers")?;
+        url.query_pairs_mut().append_pair("issuer", issuer);
+        self.auth(self.http.delete(url))
+            .send()
+            .await
+            .context("DELETE /tenant/v1/oidc/issuers")?
+            .error_for_status()
+            .context("DELETE /tenant/v1/oidc/issuers status")?;
+        Ok(())
+    }
+
+    pub async fn get_upstream_oauth(
+        &self,
+        upstream_id: &str,
+    ) -> anyhow::Result<UpstreamOAuthResponse> {
+        let url = self.url(&format!("/tenant/v1/upstreams/{upstream_id}/oauth"))?;
+        self.auth(self.http.get(url))
+            .send()
+            .await
+            .context("GET /tenant/v1/upstreams/{upstream_id}/oauth")?
+            .error_for_status()
+            .context("GET /tenant/v1/upstreams/{upstream_id}/oauth status")?
+            .json()
+            .await
+            .context("parse upstream oauth response")
+    }
+
+    pub async fn put_upstream_oauth(
+        &self,
+        upstream_id: &str,
+        client: &UpstreamOAuthClient,
+    ) -> anyhow::Result<()> {
+        let url = self.url(&format!("/tenant/v1/upstreams/{upstream_id}/oauth"))?;
+        self.auth(self.http.put(url))
+            .json(client)
+            .send()
+            .await
+            .context("PUT /tenant/v1/upstreams/{upstream_id}/oauth")?
+            .error_for_status()
+            .context("PUT /tenant/v1/upstreams/{upstream_id}/oauth status")?;
+        Ok(())
+    }
+
+    pub async fn delete_upstream_oauth(&self, upstream_id: &str) -> anyhow::Result<()> {
+        let url = self.url(&format!("/tenant/v1/upstreams/{upstream_id}/oauth"))?;
+        self.auth(self.http.delete(url))
+            .send()
+            .await
+            .context("DELETE /tenant/v1/upstreams/{upstream_id}/oauth")?
+            .error_for_status()
+            .context("DELETE /tenant/v1/upstreams/{upstream_id}/oauth status")?;
+        Ok(())
+    }
+
+    pub async fn list_upstream_oauth_links(
+        &self,
+        upstream_id: &str,
+    ) -> anyhow::Result<Vec<UpstreamOAuthLink>> {
+        let url = self.url(&format!("/tenant/v1/upstreams/{upstream_id}/oauth/links"))?;
+        let resp: UpstreamOAuthLinksResponse = self
+            .auth(self.http.get(url))
+            .send()
+            .await
+            .context("GET /tenant/v1/upstreams/{upstream_id}/oauth/links")?
+            .error_for_status()
+            .context("GET /tenant/v1/upstreams/{upstream_id}/oauth/links status")?
+            .json()
+            .await
+            .context("parse upstream oauth links response")?;
+        Ok(resp.links)
+    }
+
+    pub async fn start_upstream_oauth_link(
+        &self,
+        upstream_id: &str,
+        req: &UpstreamOAuthLinkRequest,
+    ) -> anyhow::Result<UpstreamOAuthLinkStart> {
+        let url = self.url(&format!("/tenant/v1/upstreams/{upstream_id}/oauth/links"))?;
+        self.auth(self.http.post(url))
+            .json(req)
+            .send()
+            .await
+            .context("POST /tenant/v1/upstreams/{upstream_id}/oauth/links")?
+            .error_for_status()
+            .context("POST /tenant/v1/upstreams/{upstream_id}/oauth/links status")?
+            .json()
+            .await
+            .context("parse upstream oauth link response")
+    }
+
+    pub async fn delete_upstream_oauth_link(
+        &self,
+        upstream_id: &str,
+        principal: &str,
+    ) -> anyhow::Result<()> {
+        let mut url = self.url(&format!("/tenant/v1/upstreams/{upstream_id}/oauth/links"))?;
+        url.query_pairs_mut().append_pair("principal", principal);
+        self.auth(self.http.delete(url))
+            .send()
+            .await
+            .context("DELETE /tenant/v1/upstreams/{upstream_id}/oauth/links")?
+            .error_for_status()
+            .context("DELETE /tenant/v1/upstreams/{upstream_id}/oauth/links status")?;
+        Ok(())
+    }
+
+    pub async fn list_api_keys(&self) -> anyhow::Result<ApiKeysResponse> {
         let url = self.url("/tenant/v1/api-keys")?;
         let resp: ApiKeysResponse = self
             .auth(self.http.get(url))
@@ -636,13 +1049,16 @@ impl ApiClient {
             .json()
             .await
             .context("parse api keys response")?;
-        Ok(resp.api_keys)
+        Ok(resp)
     }
 
     pub async fn create_api_key(
         &self,
         name: Option<&str>,
         profile_id: Option<&str>,
+        expires_at_unix: Option<i64>,
+        tools: &[String],
+        allowed_cidrs: &[String],
     ) -> anyhow::Result<CreateApiKeyResponse> {
         let url = self.url("/tenant/v1/api-keys")?;
         let resp: CreateApiKeyResponse = self
@@ -650,6 +1066,9 @@ impl ApiClient {
             .json(&CreateApiKeyRequest {
                 name: name.map(ToString::to_string),
                 profile_id: profile_id.map(ToString::to_string),
+                expires_at_unix,
+                tools: tools.to_vec(),
+                allowed_cidrs: allowed_cidrs.to_vec(),
             })
             .send()
             .await
@@ -662,6 +1081,30 @@ impl ApiClient {
         Ok(resp)
     }
 
+    pub async fn rotate_api_key(
+        &self,
+        api_key_id: &str,
+        grace_period_secs: Option<u64>,
+        expires_at_unix: Option<i64>,
+    ) -> anyhow::Result<RotateApiKeyResponse> {
+        let url = self.url(&format!("/tenant/v1/api-keys/{api_key_id}/rotate"))?;
+        let resp: RotateApiKeyResponse = self
+            .auth(self.http.post(url))
+            .json(&RotateApiKeyRequest {
+                grace_period_secs,
+                expires_at_unix,
+            })
+            .send()
+            .await
+            .context("POST /tenant/v1/api-keys/{api_key_id}/rotate")?
+            .error_for_status()
+            .context("POST /tenant/v1/api-keys/{api_key_id}/rotate status")?
+            .json()
+            .await
+            .context("parse rotate api key response")?;
+        Ok(resp)
+    }
+
     pub async fn revoke_api_key(&self, api_key_id: &str) -> anyhow::Result<()> {
         let url = self.url(&format!("/tenant/v1/api-keys/{api_key_id}"))?;
         self.auth(self.http.delete(url))
@@ -804,6 +1247,10 @@ struct IssueTenantTokenRequest {
     tenant_id: String,
     #[serde(default)]
     ttl_seconds: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    role: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    scopes: Option<Vec<String>>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -813,6 +1260,10 @@ pub struct IssueTenantTokenResponse {
     pub tenant_id: String,
     pub token: String,
     pub exp_unix_secs: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub token_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scopes: Option<Vec<String>>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -860,12 +1311,31 @@ pub struct ApiKeyMetadata {
     pub total_tool_calls_attempted: i64,
     pub total_requests_attempted: i64,
     pub created_at_unix: i64,
+    #[serde(default)]
+    pub expires_at_unix: Option<i64>,
+    #[serde(default)]
+    pub tool_allowlist: Vec<String>,
+    #[serde(default)]
+    pub rotated_from_id: Option<String>,
+    #[serde(default)]
+    pub allowed_cidrs: Vec<String>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
-struct ApiKeysResponse {
-    api_keys: Vec<ApiKeyMetadata>,
+pub struct ApiKeysResponse {
+    pub api_keys: Vec<ApiKeyMetadata>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub expiry_warnings: Vec<ApiKeyExpiryWarning>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiKeyExpiryWarning {
+    pub api_key_id: String,
+    pub name: String,
+    pub expires_at_unix: i64,
+    pub expires_in_secs: i64,
 }
 
 #[derive(Debug, Serialize)]
@@ -875,6 +1345,12 @@ struct CreateApiKeyRequest {
     name: Option<String>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     profile_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    expires_at_unix: Option<i64>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    tools: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    allowed_cidrs: Vec<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -885,6 +1361,39 @@ pub struct CreateApiKeyResponse {
     pub secret: String,
     pub prefix: String,
     pub profile_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires_at_unix: Option<i64>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tools: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub allowed_cidrs: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct RotateApiKeyRequest {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    grace_period_secs: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    expires_at_unix: Option<i64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RotateApiKeyResponse {
+    pub ok: bool,
+    pub id: String,
+    pub secret: String,
+    pub prefix: String,
+    pub profile_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires_at_unix: Option<i64>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tools: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub allowed_cidrs: Vec<String>,
+    pub rotated_from_id: String,
+    pub previous_expires_at_unix: i64,
 }
 
 #[derive(Debug, Deserialize)]
@@ -893,10 +1402,44 @@ struct OidcPrincipalsResponse {
     principals: Vec<OidcPrincipalBinding>,
 }
 
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct OidcIssuersResponse {
+    issuers: Vec<TenantOidcIssuer>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct UpstreamOAuthLinksResponse {
+    links: Vec<UpstreamOAuthLink>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct OidcClaimRulesResponse {
+    rules: Vec<OidcClaimRule>,
+}
+
+/// Body for `PUT /admin/v1/tenants/{tenant_id}/oidc-claim-rules/{rule_id}`.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OidcClaimRuleUpsert<'a> {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub issuer: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub profile_id: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<&'a str>,
+    pub conditions: &'a [OidcClaimCondition],
+    pub enabled: bool,
+}
+
 #[derive(Debug, Serialize)]
 #[serde(rename_all = "camelCase")]
 struct PutOidcPrincipalRequest<'a> {
     subject: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    issuer: Option<&'a str>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     profile_id: Option<&'a str>,
     enabled: bool,
@@ -67,6 +67,11 @@ enum Command {
         #[command(subcommand)]
         command: Box<ProfilesCommand>,
     },
+    /// Live MCP data-plane sessions.
+    Sessions {
+        #[command(subcommand)]
+        command: SessionsCommand,
+    },
     McpJson {
         #[command(subcommand)]
         command: McpJsonCommand,
@@ -116,6 +121,17 @@ enum TenantsCommand {
         /// TTL in seconds (default: server default).
         #[arg(long)]
         ttl_seconds: Option<u64>,
+        /// Predefined scope set: `admin` (full access) or `viewer` (read-only).
+        #[arg(long, conflicts_with = "scopes")]
+        role: Option<String>,
+        /// Scope to grant, e.g. `profiles:read` (repeatable). Omit for full access.
+        #[arg(long = "scope")]
+        scopes: Vec<String>,
+    },
+    /// Revoke a tenant token by id (before it expires).
+    RevokeToken {
+        id: String,
+        token_id: String,
     },
     ToolSources {
         tenant_id: String,
@@ -137,9 +153,40 @@ enum TenantsCommand {
     },
     OidcPrincipals {
         tenant_id: String,
+        /// OIDC issuer the bindings belong to (defaults to the Gateway-wide issuer).
+        #[arg(long)]
+        issuer: Option<String>,
         #[command(subcommand)]
         command: TenantOidcPrincipalsCommand,
     },
+    OidcClaimRules {
+        tenant_id: String,
+        /// OIDC issuer the rules belong to (defaults to the Gateway-wide issuer).
+        #[arg(long)]
+        issuer: Option<String>,
+        #[command(subcommand)]
+        command: TenantOidcClaimRulesCommand,
+    },
+    /// Manage the tenant's own OIDC issuers (tenant API).
+    OidcIssuers {
+        tenant_id: String,
+        /// TTL used when issuing an ephemeral tenant token for this operation.
+        #[arg(long)]
+        ttl_seconds: Option<u64>,
+        #[command(subcommand)]
+        command: TenantOidcIssuersCommand,
+    },
+    /// Manage per-principal OAuth account linking for an upstream (tenant API).
+    UpstreamOauth {
+        tenant_id: String,
+        /// Upstream id as seen by the tenant (tenant-owned or global).
+        upstream_id: String,
+        /// TTL used when issuing an ephemeral tenant token for this operation.
+        #[arg(long)]
+        ttl_seconds: Option<u64>,
+        #[command(subcommand)]
+        command: TenantUpstreamOauthCommand,
+    },
 }
 
 #[derive(Subcommand, Debug)]
@@ -186,7 +233,11 @@ struct TenantSecretPutArgs {
 enum TenantApiKeysCommand {
     List,
     Create(TenantApiKeyCreateArgs),
-    Revoke { api_key_id: String },
+    /// Issue a successor key; the old key stays valid for the grace period.
+    Rotate(TenantApiKeyRotateArgs),
+    Revoke {
+        api_key_id: String,
+    },
 }
 
 #[derive(Args, Debug)]
@@ -197,6 +248,39 @@ struct TenantApiKeyCreateArgs {
     /// If set, key is scoped to this profile id (`UUIDv4`). If omitted, key is tenant-wide.
     #[arg(long)]
     profile_id: Option<String>,
+    /// Expire the key after this many seconds.
+    #[arg(long)]
+    expires_in_secs: Option<u64>,
+    /// Restrict the key to a tool (`<source_id>:<original_tool_name>`). Repeatable.
+    #[arg(long = "tool")]
+    tools: Vec<String>,
+    /// Only accept the key from this client IP or CIDR. Repeatable.
+    #[arg(long = "allowed-cidr")]
+    allowed_cidrs: Vec<String>,
+}
+
+#[derive(Args, Debug)]
+struct TenantApiKeyRotateArgs {
+    api_key_id: String,
+    /// How long the old key stays valid (default: 24h; `0` expires it immediately).
+    #[arg(long)]
+    grace_secs: Option<u64>,
+    /// Expire the successor key after this many seconds.
+    #[arg(long)]
+    expires_in_secs: Option<u64>,
+}
+
+fn expires_at_from_now(expires_in_secs: Option<u64>) -> anyhow::Result<Option<i64>> {
+    let Some(secs) = expires_in_secs else {
+        return Ok(None);
+    };
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .context("system clock is before UNIX_EPOCH")?
+        .as_secs();
+    Ok(Some(
+        i64::try_from(now.saturating_add(secs)).unwrap_or(i64::MAX),
+    ))
 }
 
 #[derive(Subcommand, Debug)]
@@ -224,6 +308,155 @@ struct TenantOidcPrincipalDeleteArgs {
     profile_id: Option<String>,
 }
 
+#[derive(Subcommand, Debug)]
+enum TenantOidcIssuersCommand {
+    List,
+    Put(TenantOidcIssuerPutArgs),
+    Delete { issuer: String },
+}
+
+#[derive(Args, Debug)]
+struct TenantOidcIssuerPutArgs {
+    /// Issuer URL (must match the token `iss` claim exactly).
+    issuer: String,
+    /// Accepted `aud` value (repeatable; at least one is required).
+    #[arg(long = "audience", required = true)]
+    audiences: Vec<String>,
+    /// JWKS URL. If omitted, discovered via `<issuer>/.well-known/openid-configuration`.
+    #[arg(long)]
+    jwks_uri: Option<String>,
+    /// Accepted signing algorithm (repeatable; default: RS256).
+    #[arg(long = "alg")]
+    allowed_algs: Vec<String>,
+    #[arg(long, default_value_t = true, action = ArgAction::Set)]
+    enabled: bool,
+}
+
+#[derive(Subcommand, Debug)]
+enum TenantUpstreamOauthCommand {
+    /// Show the OAuth client configuration and the redirect URI to register.
+    Get,
+    Put(TenantUpstreamOauthPutArgs),
+    Delete,
+    /// List linked principals (token metadata only).
+    Links,
+    /// Start linking a principal; prints the authorization URL to open in a browser.
+    Link(TenantUpstreamOauthLinkArgs),
+    /// Remove a principal's linked tokens.
+    Unlink {
+        principal: String,
+    },
+}
+
+#[derive(Args, Debug)]
+struct TenantUpstreamOauthPutArgs {
+    #[arg(long)]
+    client_id: String,
+    /// Name of the tenant secret holding the client secret (omit for public clients).
+    #[arg(long)]
+    client_secret_ref: Option<String>,
+    /// Requested scope (repeatable).
+    #[arg(long = "scope")]
+    scopes: Vec<String>,
+    /// Override the discovered authorization endpoint.
+    #[arg(long)]
+    authorization_endpoint: Option<String>,
+    /// Override the discovered token endpoint.
+    #[arg(long)]
+    token_endpoint: Option<String>,
+    #[arg(long, default_value_t = true, action = ArgAction::Set)]
+    enabled: bool,
+}
+
+#[derive(Args, Debug)]
+struct TenantUpstreamOauthLinkArgs {
+    /// Link on behalf of an API key.
+    #[arg(long, conflicts_with_all = ["oidc_issuer", "oidc_subject"])]
+    api_key_id: Option<String>,
+    /// Link on behalf of an OIDC subject (requires `--oidc-subject`).
+    #[arg(long, requires = "oidc_subject")]
+    oidc_issuer: Option<String>,
+    #[arg(long, requires = "oidc_issuer")]
+    oidc_subject: Option<String>,
+}
+
+#[derive(Subcommand, Debug)]
+enum TenantOidcClaimRulesCommand {
+    List,
+    Put(TenantOidcClaimRulePutArgs),
+    Delete {
+        rule_id: String,
+    },
+    /// Evaluate a sample token (or raw claims) against the tenant's bindings and rules.
+    Test(TenantOidcClaimRulesTestArgs),
+}
+
+#[derive(Args, Debug)]
+struct TenantOidcClaimRulePutArgs {
+    rule_id: String,
+    /// Condition as `<claim> <equals|contains|endsWith> <value>` (repeatable; all must match).
+    ///
+    /// Example: `--condition 'groups contains ml-platform' --condition 'email endsWith @corp.example'`
+    #[arg(long = "condition", required = true)]
+    conditions: Vec<String>,
+    /// If set, the rule grants only this profile id (`UUIDv4`). If omitted, the rule is tenant-wide.
+    #[arg(long)]
+    profile_id: Option<String>,
+    #[arg(long)]
+    description: Option<String>,
+    #[arg(long, default_value_t = true, action = ArgAction::Set)]
+    enabled: bool,
+}
+
+#[derive(Args, Debug)]
+struct TenantOidcClaimRulesTestArgs {
+    /// Sample JWT (validated like a data-plane bearer token).
+    #[arg(
+        long,
+        conflicts_with = "claims_json",
+        required_unless_present = "claims_json"
+    )]
+    token: Option<String>,
+    /// Raw claims JSON object to evaluate instead of a token.
+    #[arg(long)]
+    claims_json: Option<String>,
+    #[arg(long)]
+    profile_id: Option<String>,
+}
+
+fn parse_oidc_claim_condition(raw: &str) -> anyhow::Result<api::OidcClaimCondition> {
+    let mut parts = raw.trim().splitn(3, char::is_whitespace);
+    let (Some(claim), Some(op), Some(value)) = (parts.next(), parts.next(), parts.next()) else {
+        anyhow::bail!("invalid condition '{raw}' (expected '<claim> <op> <value>')");
+    };
+    if !matches!(op, "equals" | "contains" | "endsWith") {
+        anyhow::bail!("invalid condition op '{op}' (allowed: equals|contains|endsWith)");
+    }
+    Ok(api::OidcClaimCondition {
+        claim: claim.to_string(),
+        op: op.to_string(),
+        value: value.trim().to_string(),
+    })
+}
+
+#[derive(Subcommand, Debug)]
+enum SessionsCommand {
+    List {
+        #[arg(long)]
+        tenant_id: Option<String>,
+        #[arg(long)]
+        profile_id: Option<String>,
+    },
+    Get {
+        session_id: String,
+    },
+    /// Terminate a session: its token is rejected, its SSE streams close and its upstream
+    /// sessions are deleted.
+    Terminate {
+        session_id: String,
+    },
+}
+
 #[derive(Subcommand, Debug)]
 enum UpstreamsCommand {
     List,
@@ -510,6 +743,12 @@ async fn run(cli: Cli) -> anyhow::Result<()> {
             let api = api_client(&admin_base, token)?;
             handle_profiles(*command, api, json, &data_base).await
         }
+        Command::Sessions { command } => {
+            let token =
+                resolve_token_parts(token.as_ref(), token_file.as_ref(), token_stdin, &cfg)?;
+            let api = api_client(&admin_base, token)?;
+            handle_sessions(command, api, json).await
+        }
         Command::McpJson { command } => handle_mcp_json(command, &data_base),
     }
 }
@@ -768,8 +1007,16 @@ async fn handle_tenants(
         TenantsCommand::Get { id } => handle_tenants_get(&api, json, &id).await,
         TenantsCommand::Put { id, enabled } => handle_tenants_put(&api, json, &id, enabled).await,
         TenantsCommand::Delete { id } => handle_tenants_delete(&api, json, &id).await,
-        TenantsCommand::IssueToken { id, ttl_seconds } => {
-            handle_tenants_issue_token(&api, json, &id, ttl_seconds).await
+        TenantsCommand::IssueToken {
+            id,
+            ttl_seconds,
+            role,
+            scopes,
+        } => {
+            handle_tenants_issue_token(&api, json, &id, ttl_seconds, role.as_deref(), &scopes).await
+        }
+        TenantsCommand::RevokeToken { id, token_id } => {
+            handle_tenants_revoke_token(&api, json, &id, &token_id).await
         }
         TenantsCommand::ToolSources { tenant_id, command } => {
             handle_tenants_tool_sources(&api, json, &tenant_id, command).await
@@ -782,21 +1029,334 @@ async fn handle_tenants(
             ttl_seconds,
             command,
         } => handle_tenants_api_keys(&api, json, &tenant_id, ttl_seconds, command).await,
-        TenantsCommand::OidcPrincipals { tenant_id, command } => {
-            handle_tenants_oidc_principals(&api, json, &tenant_id, command).await
+        TenantsCommand::OidcPrincipals {
+            tenant_id,
+            issuer,
+            command,
+        } => {
+            handle_tenants_oidc_principals(&api, json, &tenant_id, issuer.as_deref(), command).await
+        }
+        TenantsCommand::OidcClaimRules {
+            tenant_id,
+            issuer,
+            command,
+        } => {
+            handle_tenants_oidc_claim_rules(&api, json, &tenant_id, issuer.as_deref(), command)
+                .await
+        }
+        TenantsCommand::OidcIssuers {
+            tenant_id,
+            ttl_seconds,
+            command,
+        } => handle_tenants_oidc_issuers(&api, json, &tenant_id, ttl_seconds, command).await,
+        TenantsCommand::UpstreamOauth {
+            tenant_id,
+            upstream_id,
+            ttl_seconds,
+            command,
+        } => {
+            handle_tenants_upstream_oauth(
+                &api,
+                json,
+                &tenant_id,
+                &upstream_id,
+                ttl_seconds,
+                command,
+            )
+            .await
+        }
+    }
+}
+
+async fn handle_tenants_upstream_oauth(
+    api: &api::ApiClient,
+    json: bool,
+    tenant_id: &str,
+    upstream_id: &str,
+    ttl_seconds: Option<u64>,
+    command: TenantUpstreamOauthCommand,
+) -> anyhow::Result<()> {
+    // Least privilege: the ephemeral token only needs upstream scopes.
+    let scopes = ["upstreams:read".to_string(), "upstreams:write".to_string()];
+    let token = api
+        .issue_tenant_token(tenant_id, ttl_seconds, None, &scopes)
+        .await?
+        .token;
+    let tenant_api = api.clone_with_token(token);
+
+    match command {
+        TenantUpstreamOauthCommand::Get => {
+            let resp = tenant_api.get_upstream_oauth(upstream_id).await?;
+            if json {
+                println!("{}", serde_json::to_string_pretty(&resp)?);
+                return Ok(());
+            }
+            let c = resp.client;
+            let status = if c.enabled {
+                "enabled".green().to_string()
+            } else {
+                "disabled".red().to_string()
+            };
+            println!("{}  {}", upstream_id.bold(), status);
+            println!("  clientId: {}", c.client_id);
+            if let Some(secret) = &c.client_secret_ref {
+                println!("  clientSecretRef: {}", secret.dimmed());
+            }
+            if !c.scopes.is_empty() {
+                println!("  scopes: {}", c.scopes.join(" ").dimmed());
+            }
+            if let Some(e) = &c.authorization_endpoint {
+                println!("  authorizationEndpoint: {}", e.dimmed());
+            }
+            if let Some(e) = &c.token_endpoint {
+                println!("  tokenEndpoint: {}", e.dimmed());
+            }
+            match &resp.redirect_uri {
+                Some(uri) => println!("  redirectUri: {uri}"),
+                None => println!(
+                    "  redirectUri: {}",
+                    "(unset: configure UNRELATED_GATEWAY_PUBLIC_BASE_URL)".dimmed()
+                ),
+            }
+        }
+        TenantUpstreamOauthCommand::Put(args) => {
+            tenant_api
+                .put_upstream_oauth(
+                    upstream_id,
+                    &api::UpstreamOAuthClient {
+                        client_id: args.client_id,
+                        client_secret_ref: args.client_secret_ref,
+                        scopes: args.scopes,
+                        authorization_endpoint: args.authorization_endpoint,
+                        token_endpoint: args.token_endpoint,
+                        enabled: args.enabled,
+                    },
+                )
+                .await?;
+            if json {
+                println!("{}", serde_json::json!({"ok": true}));
+            } else {
+                println!("{}", "ok".green());
+            }
+        }
+        TenantUpstreamOauthCommand::Delete => {
+            tenant_api.delete_upstream_oauth(upstream_id).await?;
+            if json {
+                println!("{}", serde_json::json!({"ok": true}));
+            } else {
+                println!("{}", "ok".green());
+            }
+        }
+        TenantUpstreamOauthCommand::Links => {
+            let links = tenant_api.list_upstream_oauth_links(upstream_id).await?;
+            if json {
+                println!("{}", serde_json::to_string_pretty(&links)?);
+                return Ok(());
+            }
+            if links.is_empty() {
+                println!("{}", "(no linked accounts)".dimmed());
+                return Ok(());
+            }
+            println!("{}", "linked accounts".bold());
+            for l in links {
+                let refresh = if l.has_refresh_token {
+                    "refreshable".green().to_string()
+                } else {
+                    "no refresh token".yellow().to_string()
+                };
+                let expires = l
+                    .expires_at_unix
+                    .map_or_else(|| "-".to_string(), |e| e.to_string());
+                println!("  {}  {}", l.principal, refresh);
+                println!("    expiresAtUnix: {}", expires.dimmed());
+            }
+        }
+        TenantUpstreamOauthCommand::Link(args) => {
+            let start = tenant_api
+                .start_upstream_oauth_link(
+                    upstream_id,
+                    &api::UpstreamOAuthLinkRequest {
+                        api_key_id: args.api_key_id,
+                        oidc_issuer: args.oidc_issuer,
+                        oidc_subject: args.oidc_subject,
+                    },
+                )
+                .await?;
+            if json {
+                println!("{}", serde_json::to_string_pretty(&start)?);
+            } else {
+                println!("{}", "open this URL to link the account:".bold());
+                println!("  {}", start.authorization_url);
+                println!("  principal: {}", start.principal.dimmed());
+                println!(
+                    "  expiresAtUnix: {}",
+                    start.expires_at_unix.to_string().dimmed()
+                );
+            }
+        }
+        TenantUpstreamOauthCommand::Unlink { principal } => {
+            tenant_api
+                .delete_upstream_oauth_link(upstream_id, &principal)
+                .await?;
+            if json {
+                println!("{}", serde_json::json!({"ok": true}));
+            } else {
+                println!("{}", "ok".green());
+            }
+        }
+    }
+    Ok(())
+}
+
+async fn handle_tenants_oidc_claim_rules(
+    api: &api::ApiClient,
+    json: bool,
+    tenant_id: &str,
+    issuer: Option<&str>,
+    command: TenantOidcClaimRulesCommand,
+) -> anyhow::Result<()> {
+    match command {
+        TenantOidcClaimRulesCommand::List => {
+            let rules = api.list_oidc_claim_rules(tenant_id, issuer).await?;
+            if json {
+                println!("{}", serde_json::to_string_pretty(&rules)?);
+                return Ok(());
+            }
+            if rules.is_empty() {
+                println!("{}", "(no oidc claim rules)".dimmed());
+                return Ok(());
+            }
+            println!("{}", "oidc claim rules".bold());
+            for r in rules {
+                let scope = r.profile_id.as_deref().unwrap_or("(tenant-wide)");
+                let status = if r.enabled {
+                    "enabled".green().to_string()
+                } else {
+                    "disabled".red().to_string()
+                };
+                println!("  {}  {}  {}", r.id, scope.dimmed(), status);
+                for c in r.conditions {
+                    println!("    {} {} {}", c.claim, c.op.dimmed(), c.value);
+                }
+            }
+        }
+        TenantOidcClaimRulesCommand::Put(args) => {
+            let conditions = args
+                .conditions
+                .iter()
+                .map(|c| parse_oidc_claim_condition(c))
+                .collect::<anyhow::Result<Vec<_>>>()?;
+            let rule = api
+                .put_oidc_claim_rule(
+                    tenant_id,
+                    &args.rule_id,
+                    &api::OidcClaimRuleUpsert {
+                        issuer,
+                        profile_id: args.profile_id.as_deref(),
+                        description: args.description.as_deref(),
+                        conditions: &conditions,
+                        enabled: args.enabled,
+                    },
+                )
+                .await?;
+            if json {
+                println!("{}", serde_json::to_string_pretty(&rule)?);
+            } else {
+                println!("{}", "ok".green());
+            }
+        }
+        TenantOidcClaimRulesCommand::Delete { rule_id } => {
+            api.delete_oidc_claim_rule(tenant_id, &rule_id, issuer)
+                .await?;
+            if json {
+                println!("{}", serde_json::json!({"ok": true}));
+            } else {
+                println!("{}", "ok".green());
+            }
+        }
+        TenantOidcClaimRulesCommand::Test(args) => {
+            let mut body = serde_json::Map::new();
+            if let Some(token) = args.token {
+                body.insert("token".to_string(), token.into());
+            }
+            if let Some(raw) = args.claims_json.as_deref() {
+                let claims: serde_json::Value =
+                    serde_json::from_str(raw).context("parse --claims-json")?;
+                body.insert("claims".to_string(), claims);
+            }
+            if let Some(pid) = args.profile_id {
+                body.insert("profileId".to_string(), pid.into());
+            }
+            if let Some(issuer) = issuer {
+                body.insert("issuer".to_string(), issuer.into());
+            }
+            let resp = api
+                .test_oidc_claim_rules(tenant_id, &serde_json::Value::Object(body))
+                .await?;
+            if json {
+                println!("{}", serde_json::to_string_pretty(&resp)?);
+                return Ok(());
+            }
+            let allowed = resp
+                .get("allowed")
+                .and_then(serde_json::Value::as_bool)
+                .unwrap_or(false);
+            let subject = resp
+                .get("subject")
+                .and_then(serde_json::Value::as_str)
+                .unwrap_or("(none)");
+            println!(
+                "{} subject={}",
+                if allowed {
+                    "allowed".green().to_string()
+                } else {
+                    "denied".red().to_string()
+                },
+                subject
+            );
+            if resp
+                .get("principalBound")
+                .and_then(serde_json::Value::as_bool)
+                == Some(true)
+            {
+                println!("  {}", "granted by principal binding".dimmed());
+            }
+            for r in resp
+                .get("rules")
+                .and_then(serde_json::Value::as_array)
+                .into_iter()
+                .flatten()
+            {
+                let id = r
+                    .get("id")
+                    .and_then(serde_json::Value::as_str)
+                    .unwrap_or("?");
+                let grants = r.get("grants").and_then(serde_json::Value::as_bool) == Some(true);
+                let matched = r.get("matched").and_then(serde_json::Value::as_bool) == Some(true);
+                let status = if grants {
+                    "grants".green().to_string()
+                } else if matched {
+                    "matched (not applicable)".yellow().to_string()
+                } else {
+                    "no match".dimmed().to_string()
+                };
+                println!("  {id}  {status}");
+            }
         }
     }
+    Ok(())
 }
 
 async fn handle_tenants_oidc_principals(
     api: &api::ApiClient,
     json: bool,
     tenant_id: &str,
+    issuer: Option<&str>,
     command: TenantOidcPrincipalsCommand,
 ) -> anyhow::Result<()> {
     match command {
         TenantOidcPrincipalsCommand::List => {
-            let principals = api.list_oidc_principals(tenant_id).await?;
+            let principals = api.list_oidc_principals(tenant_id, issuer).await?;
             if json {
                 println!("{}", serde_json::to_string_pretty(&principals)?);
                 return Ok(());
@@ -820,6 +1380,7 @@ async fn handle_tenants_oidc_principals(
             api.put_oidc_principal(
                 tenant_id,
                 &args.subject,
+                issuer,
                 args.profile_id.as_deref(),
                 args.enabled,
             )
@@ -831,7 +1392,73 @@ async fn handle_tenants_oidc_principals(
             }
         }
         TenantOidcPrincipalsCommand::Delete(args) => {
-            api.delete_oidc_principal(tenant_id, &args.subject, args.profile_id.as_deref())
+            api.delete_oidc_principal(tenant_id, &args.subject, issuer, args.profile_id.as_deref())
+                .await?;
+            if json {
+                println!("{}", serde_json::json!({"ok": true}));
+            } else {
+                println!("{}", "ok".green());
+            }
+        }
+    }
+    Ok(())
+}
+
+async fn handle_tenants_oidc_issuers(
+    api: &api::ApiClient,
+    json: bool,
+    tenant_id: &str,
+    ttl_seconds: Option<u64>,
+    command: TenantOidcIssuersCommand,
+) -> anyhow::Result<()> {
+    // Least privilege: the ephemeral token only needs OIDC scopes.
+    let scopes = ["oidc:read".to_string(), "oidc:write".to_string()];
+    let token = api
+        .issue_tenant_token(tenant_id, ttl_seconds, None, &scopes)
+        .await?
+        .token;
+    let tenant_api = api.clone_with_token(token);
+
+    match command {
+        TenantOidcIssuersCommand::List => {
+            let issuers = tenant_api.list_oidc_issuers().await?;
+            if json {
+                println!("{}", serde_json::to_string_pretty(&issuers)?);
+                return Ok(());
+            }
+            if issuers.is_empty() {
+                println!("{}", "(no oidc issuers)".dimmed());
+                return Ok(());
+            }
+            println!("{}", "oidc issuers".bold());
+            for i in issuers {
+                let status = if i.enabled {
+                    "enabled".green().to_string()
+                } else {
+                    "disabled".red().to_string()
+                };
+                println!("  {}  {}", i.issuer, status);
+                println!("    audiences: {}", i.audiences.join(", ").dimmed());
+                println!("    algs: {}", i.allowed_algs.join(", ").dimmed());
+                if let Some(uri) = &i.jwks_uri {
+                    println!("    jwksUri: {}", uri.dimmed());
+                }
+            }
+        }
+        TenantOidcIssuersCommand::Put(args) => {
+            let allowed_algs = if args.allowed_algs.is_empty() {
+                vec!["RS256".to_string()]
+            } else {
+                args.allowed_algs
+            };
+            tenant_api
+                .put_oidc_issuer(&api::TenantOidcIssuer {
+                    issuer: args.issuer,
+                    audiences: args.audiences,
+                    jwks_uri: args.jwks_uri,
+                    allowed_algs,
+                    enabled: args.enabled,
+                })
                 .await?;
             if json {
                 println!("{}", serde_json::json!({"ok": true}));
@@ -839,6 +1466,14 @@ async fn handle_tenants_oidc_principals(
                 println!("{}", "ok".green());
             }
         }
+        TenantOidcIssuersCommand::Delete { issuer } => {
+            tenant_api.delete_oidc_issuer(&issuer).await?;
+            if json {
+                println!("{}", serde_json::json!({"ok": true}));
+            } else {
+                println!("{}", "ok".green());
+            }
+        }
     }
     Ok(())
 }
@@ -907,8 +1542,12 @@ async fn handle_tenants_issue_token(
     json: bool,
     id: &str,
     ttl_seconds: Option<u64>,
+    role: Option<&str>,
+    scopes: &[String],
 ) -> anyhow::Result<()> {
-    let resp = api.issue_tenant_token(id, ttl_seconds).await?;
+    let resp = api
+        .issue_tenant_token(id, ttl_seconds, role, scopes)
+        .await?;
     if json {
         println!("{}", serde_json::to_string_pretty(&resp)?);
         return Ok(());
@@ -916,10 +1555,32 @@ async fn handle_tenants_issue_token(
     println!("{}", "tenant token".bold());
     println!("  tenantId: {}", resp.tenant_id);
     println!("  expUnixSecs: {}", resp.exp_unix_secs);
+    if let Some(token_id) = &resp.token_id {
+        println!("  tokenId: {token_id}");
+    }
+    match &resp.scopes {
+        Some(scopes) => println!("  scopes: {}", scopes.join(", ")),
+        None => println!("  scopes: (full access)"),
+    }
     println!("  token: {}", resp.token);
     Ok(())
 }
 
+async fn handle_tenants_revoke_token(
+    api: &api::ApiClient,
+    json: bool,
+    id: &str,
+    token_id: &str,
+) -> anyhow::Result<()> {
+    api.revoke_tenant_token(id, token_id).await?;
+    if json {
+        println!("{}", serde_json::json!({"ok": true}));
+    } else {
+        println!("{}", "ok".green());
+    }
+    Ok(())
+}
+
 async fn handle_tenants_tool_sources(
     api: &api::ApiClient,
     json: bool,
@@ -1035,16 +1696,31 @@ async fn handle_tenants_api_keys(
     ttl_seconds: Option<u64>,
     command: TenantApiKeysCommand,
 ) -> anyhow::Result<()> {
-    let token = api.issue_tenant_token(tenant_id, ttl_seconds).await?.token;
+    // Least privilege: the ephemeral token only needs API key scopes.
+    let scopes = ["api-keys:read".to_string(), "api-keys:write".to_string()];
+    let token = api
+        .issue_tenant_token(tenant_id, ttl_seconds, None, &scopes)
+        .await?
+        .token;
     let tenant_api = api.clone_with_token(token);
 
     match command {
         TenantApiKeysCommand::List => {
-            let keys = tenant_api.list_api_keys().await?;
+            let resp = tenant_api.list_api_keys().await?;
             if json {
-                println!("{}", serde_json::to_string_pretty(&keys)?);
+                println!("{}", serde_json::to_string_pretty(&resp.api_keys)?);
                 return Ok(());
             }
+            for w in &resp.expiry_warnings {
+                eprintln!(
+                    "{} api key {} ({}) expires in {}s",
+                    "warning:".yellow().bold(),
+                    w.api_key_id,
+                    w.name,
+                    w.expires_in_secs
+                );
+            }
+            let keys = resp.api_keys;
             if keys.is_empty() {
                 println!("{}", "(no api keys)".dimmed());
                 return Ok(());
@@ -1060,6 +1736,18 @@ async fn handle_tenants_api_keys(
                 println!("  {}  {}  {}", k.id, scope.dimmed(), revoked);
                 println!("    name: {}", k.name.dimmed());
                 println!("    prefix: {}", k.prefix.dimmed());
+                if let Some(exp) = k.expires_at_unix {
+                    println!("    expiresAtUnix: {}", exp.to_string().dimmed());
+                }
+                if !k.tool_allowlist.is_empty() {
+                    println!("    tools: {}", k.tool_allowlist.join(", ").dimmed());
+                }
+                if !k.allowed_cidrs.is_empty() {
+                    println!("    allowedCidrs: {}", k.allowed_cidrs.join(", ").dimmed());
+                }
+                if let Some(from) = &k.rotated_from_id {
+                    println!("    rotatedFrom: {}", from.dimmed());
+                }
                 println!(
                     "    toolCallsAttempted: {}",
                     k.total_tool_calls_attempted.to_string().dimmed()
@@ -1068,7 +1756,13 @@ async fn handle_tenants_api_keys(
         }
         TenantApiKeysCommand::Create(args) => {
             let resp = tenant_api
-                .create_api_key(args.name.as_deref(), args.profile_id.as_deref())
+                .create_api_key(
+                    args.name.as_deref(),
+                    args.profile_id.as_deref(),
+                    expires_at_from_now(args.expires_in_secs)?,
+                    &args.tools,
+                    &args.allowed_cidrs,
+                )
                 .await?;
             if json {
                 println!("{}", serde_json::to_string_pretty(&resp)?);
@@ -1081,6 +1775,42 @@ async fn handle_tenants_api_keys(
                 "  profileId: {}",
                 resp.profile_id.as_deref().unwrap_or("(tenant-wide)")
             );
+            if let Some(exp) = resp.expires_at_unix {
+                println!("  expiresAtUnix: {exp}");
+            }
+            if !resp.tools.is_empty() {
+                println!("  tools: {}", resp.tools.join(", "));
+            }
+            if !resp.allowed_cidrs.is_empty() {
+                println!("  allowedCidrs: {}", resp.allowed_cidrs.join(", "));
+            }
+            println!();
+            println!("{}", "Secret (displayed once):".bold());
+            println!("{}", resp.secret);
+        }
+        TenantApiKeysCommand::Rotate(args) => {
+            let resp = tenant_api
+                .rotate_api_key(
+                    &args.api_key_id,
+                    args.grace_secs,
+                    expires_at_from_now(args.expires_in_secs)?,
+                )
+                .await?;
+            if json {
+                println!("{}", serde_json::to_string_pretty(&resp)?);
+                return Ok(());
+            }
+            println!("{}", "api key rotated".green());
+            println!("  id: {}", resp.id);
+            println!("  prefix: {}", resp.prefix);
+            println!("  rotatedFrom: {}", resp.rotated_from_id);
+            println!(
+                "  previous key expires at (unix): {}",
+                resp.previous_expires_at_unix
+            );
+            if let Some(exp) = resp.expires_at_unix {
+                println!("  expiresAtUnix: {exp}");
+            }
             println!();
             println!("{}", "Secret (displayed once):".bold());
             println!("{}", resp.secret);
@@ -1170,6 +1900,70 @@ async fn handle_upstreams(
     Ok(())
 }
 
+async fn handle_sessions(
+    cmd: SessionsCommand,
+    api: api::ApiClient,
+    json: bool,
+) -> anyhow::Result<()> {
+    match cmd {
+        SessionsCommand::List {
+            tenant_id,
+            profile_id,
+        } => {
+            let sessions = api
+                .list_sessions(tenant_id.as_deref(), profile_id.as_deref())
+                .await?;
+            if json {
+                println!("{}", serde_json::to_string_pretty(&sessions)?);
+            } else if sessions.is_empty() {
+                println!("{}", "(no sessions)".dimmed());
+            } else {
+                println!("{}", "sessions".bold());
+                for s in sessions {
+                    println!(
+                        "  {}  tenant={} profile={} principal={} streams={} lastSeenAt={}",
+                        s.session_id,
+                        s.tenant_id,
+                        s.profile_id,
+                        s.principal.as_deref().unwrap_or("-"),
+                        s.open_streams,
+                        s.last_seen_at_unix
+                    );
+                }
+            }
+        }
+        SessionsCommand::Get { session_id } => {
+            let s = api.get_session(&session_id).await?;
+            if json {
+                println!("{}", serde_json::to_string_pretty(&s)?);
+            } else {
+                println!("{}", "session".bold());
+                println!("  id: {}", s.session_id);
+                println!("  tenant: {}", s.tenant_id);
+                println!("  profile: {}", s.profile_id);
+                println!("  principal: {}", s.principal.as_deref().unwrap_or("-"));
+                println!("  createdAtUnix: {}", s.created_at_unix);
+                println!("  lastSeenAtUnix: {}", s.last_seen_at_unix);
+                println!("  expiresAtUnix: {}", s.expires_at_unix);
+                println!("  openStreams: {}", s.open_streams);
+                println!("  upstreams:");
+                for u in s.upstreams {
+                    println!("    - {} ({})", u.upstream_id, u.endpoint_id);
+                }
+            }
+        }
+        SessionsCommand::Terminate { session_id } => {
+            api.terminate_session(&session_id).await?;
+            if json {
+                println!("{}", serde_json::json!({"ok": true}));
+            } else {
+                println!("{}", "ok".green());
+            }
+        }
+    }
+    Ok(())
+}
+
 async fn handle_profiles(
     cmd: ProfilesCommand,
     api: api::ApiClient,
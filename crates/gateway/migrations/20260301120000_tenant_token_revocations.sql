@@ -0,0 +1,16 @@
+-- migrate:up
+-- Mode 3 schema extension: tenant token revocation list.
+--
+-- Tenant tokens are stateless signed tokens; scoped tokens carry a `tokenId` that can be revoked
+-- before expiry. Replicas learn about new revocations via LISTEN/NOTIFY invalidation events.
+
+create table if not exists tenant_token_revocations (
+    tenant_id text not null references tenants(id) on delete cascade,
+    token_id text not null,
+    revoked_at timestamptz not null default now(),
+    primary key (tenant_id, token_id)
+);
+
+-- migrate:down
+
+drop table if exists tenant_token_revocations;
@@ -0,0 +1,23 @@
+-- migrate:up
+-- Mode 3 schema extension: API key expiry, rotation, and per-key tool allowlists.
+--
+-- - `expires_at`: optional hard expiry; expired keys no longer authenticate.
+-- - `tool_allowlist`: optional `source:tool` allowlist that narrows the profile's enabled tools.
+--   Empty => no narrowing.
+-- - `rotated_from_id`: set on keys issued by rotating another key.
+
+alter table api_keys
+    add column if not exists expires_at timestamptz null,
+    add column if not exists tool_allowlist text[] not null default '{}',
+    add column if not exists rotated_from_id uuid null references api_keys(id) on delete set null;
+
+create index if not exists api_keys_tenant_expires_idx on api_keys(tenant_id, expires_at);
+
+-- migrate:down
+
+drop index if exists api_keys_tenant_expires_idx;
+
+alter table api_keys
+    drop column if exists rotated_from_id,
+    drop column if exists tool_allowlist,
+    drop column if exists expires_at;
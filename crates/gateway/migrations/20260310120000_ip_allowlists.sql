@@ -0,0 +1,19 @@
+-- migrate:up
+-- Mode 3 schema extension: IP/CIDR allowlists for data-plane access.
+--
+-- Empty arrays mean "no allowlist configured". Profile-level allowlists live in the profile's
+-- `mcp_settings.security.allowedCidrs` JSON.
+
+alter table tenants
+    add column if not exists allowed_cidrs text[] not null default '{}';
+
+alter table api_keys
+    add column if not exists allowed_cidrs text[] not null default '{}';
+
+-- migrate:down
+
+alter table api_keys
+    drop column if exists allowed_cidrs;
+
+alter table tenants
+    drop column if exists allowed_cidrs;
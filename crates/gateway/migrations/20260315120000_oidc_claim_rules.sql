@@ -0,0 +1,25 @@
+-- migrate:up
+-- Mode 3 schema extension: claims-based OIDC authorization rules.
+--
+-- A rule grants a tenant (`profile_id` NULL) or a single profile when all of its `conditions`
+-- match the claims of a validated JWT from `issuer`. Rules complement `oidc_principals`
+-- (explicit issuer + subject bindings), which are checked first.
+--
+-- `conditions` is a JSON array of `{ "claim": "...", "op": "equals|contains|endsWith", "value": "..." }`.
+
+create table oidc_claim_rules (
+    tenant_id text not null references tenants(id) on delete cascade,
+    issuer text not null,
+    id text not null,
+    profile_id uuid null references profiles(id) on delete cascade,
+    description text null,
+    conditions jsonb not null,
+    enabled boolean not null default true,
+    created_at timestamptz not null default now(),
+    updated_at timestamptz not null default now(),
+    primary key (tenant_id, issuer, id)
+);
+
+-- migrate:down
+
+drop table if exists oidc_claim_rules;
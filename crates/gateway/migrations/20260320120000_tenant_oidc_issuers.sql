@@ -0,0 +1,25 @@
+-- migrate:up
+-- Mode 3 schema extension: per-tenant OIDC issuers.
+--
+-- Tokens presented to a tenant's profiles are routed by their `iss` claim: a matching enabled
+-- row here wins over the Gateway-wide issuer (`UNRELATED_GATEWAY_OIDC_ISSUER`).
+--
+-- - `audiences`: accepted `aud` values (required by the API; at least one).
+-- - `jwks_uri`: optional override; otherwise discovered via `/.well-known/openid-configuration`.
+-- - `allowed_algs`: accepted JWS algorithms.
+
+create table tenant_oidc_issuers (
+    tenant_id text not null references tenants(id) on delete cascade,
+    issuer text not null,
+    audiences text[] not null default '{}',
+    jwks_uri text null,
+    allowed_algs text[] not null default '{RS256}',
+    enabled boolean not null default true,
+    created_at timestamptz not null default now(),
+    updated_at timestamptz not null default now(),
+    primary key (tenant_id, issuer)
+);
+
+-- migrate:down
+
+drop table if exists tenant_oidc_issuers;
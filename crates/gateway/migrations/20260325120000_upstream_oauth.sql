@@ -0,0 +1,65 @@
+-- migrate:up
+-- Mode 3 schema extension: the Gateway as OAuth client to upstream MCP servers.
+--
+-- - `upstream_oauth_clients`: per-tenant OAuth client registration for an upstream. The client
+--   secret lives in the tenant's `secrets` table and is referenced by name.
+-- - `upstream_oauth_tokens`: per-principal tokens (`oidc:<issuer>|<subject>` or `apiKey:<id>`),
+--   encrypted like tenant secrets (XChaCha20-Poly1305; AAD binds tenant + upstream + principal).
+-- - `upstream_oauth_link_states`: pending authorization code flows, keyed by the SHA-256 of the
+--   OAuth `state` parameter. The PKCE verifier is encrypted; rows are single-use and short-lived.
+
+create table upstream_oauth_clients (
+    tenant_id text not null references tenants(id) on delete cascade,
+    upstream_id text not null references upstreams(id) on delete cascade,
+    client_id text not null,
+    client_secret_ref text null,
+    scopes text[] not null default '{}',
+    authorization_endpoint text null,
+    token_endpoint text null,
+    enabled boolean not null default true,
+    created_at timestamptz not null default now(),
+    updated_at timestamptz not null default now(),
+    primary key (tenant_id, upstream_id)
+);
+
+create table upstream_oauth_tokens (
+    tenant_id text not null,
+    upstream_id text not null,
+    principal text not null,
+    kid text not null,
+    nonce bytea not null,
+    ciphertext bytea not null,
+    algo text not null,
+    expires_at timestamptz null,
+    has_refresh_token boolean not null default false,
+    created_at timestamptz not null default now(),
+    updated_at timestamptz not null default now(),
+    primary key (tenant_id, upstream_id, principal),
+    foreign key (tenant_id, upstream_id)
+        references upstream_oauth_clients(tenant_id, upstream_id) on delete cascade
+);
+
+create table upstream_oauth_link_states (
+    state_hash text primary key,
+    tenant_id text not null,
+    upstream_id text not null,
+    principal text not null,
+    kid text not null,
+    nonce bytea not null,
+    ciphertext bytea not null,
+    algo text not null,
+    redirect_uri text not null,
+    expires_at timestamptz not null,
+    created_at timestamptz not null default now(),
+    foreign key (tenant_id, upstream_id)
+        references upstream_oauth_clients(tenant_id, upstream_id) on delete cascade
+);
+
+create index upstream_oauth_link_states_expires_at_idx on upstream_oauth_link_states(expires_at);
+
+-- migrate:down
+
+drop index if exists upstream_oauth_link_states_expires_at_idx;
+drop table if exists upstream_oauth_link_states;
+drop table if exists upstream_oauth_tokens;
+drop table if exists upstream_oauth_clients;
@@ -0,0 +1,26 @@
+-- migrate:up
+-- Mode 3 schema extension: per-session SSE event log for `Last-Event-ID` resumption.
+--
+-- Rows are keyed by the SHA-256 of the session token, so any replica can replay a session's
+-- missed events. Each row records where the event came from (upstream + upstream event id, or a
+-- contract event id) so resumed streams can continue each source from its last logged position.
+
+create table sse_event_log (
+    id bigserial primary key,
+    session_key text not null,
+    upstream_id text null,
+    upstream_event_id text null,
+    contract_event_id bigint null,
+    data text not null,
+    expires_at timestamptz not null,
+    created_at timestamptz not null default now()
+);
+
+create index sse_event_log_session_idx on sse_event_log(session_key, id);
+create index sse_event_log_expires_at_idx on sse_event_log(expires_at);
+
+-- migrate:down
+
+drop index if exists sse_event_log_expires_at_idx;
+drop index if exists sse_event_log_session_idx;
+drop table if exists sse_event_log;
@@ -0,0 +1,28 @@
+-- migrate:up
+-- Mode 3 schema extension: live MCP session registry (list / terminate).
+--
+-- Session tokens stay stateless; this table tracks sessions by the token's `sid` so operators can
+-- see who is connected and terminate a session on every replica. Terminated rows are kept until
+-- the token would have expired so the termination sticks.
+
+create table mcp_sessions (
+    session_id text primary key,
+    tenant_id text not null,
+    profile_id text not null,
+    principal text null,
+    bindings jsonb not null default '[]'::jsonb,
+    open_streams integer not null default 0,
+    created_at timestamptz not null default now(),
+    last_seen_at timestamptz not null default now(),
+    expires_at timestamptz not null,
+    terminated_at timestamptz null
+);
+
+create index mcp_sessions_tenant_profile_idx on mcp_sessions(tenant_id, profile_id);
+create index mcp_sessions_expires_at_idx on mcp_sessions(expires_at);
+
+-- migrate:down
+
+drop index if exists mcp_sessions_expires_at_idx;
+drop index if exists mcp_sessions_tenant_profile_idx;
+drop table if exists mcp_sessions;
@@ -1,17 +1,18 @@
 use crate::audit::{AuditActor, AuditError, HttpAuditEvent};
 use crate::profile_http::{
     DataPlaneAuthSettings, DataPlaneLimitsSettings, NullableString, NullableU64,
-    default_data_plane_auth_mode, resolve_nullable_u64, validate_tool_allowlist,
-    validate_tool_timeout_and_policies,
+    default_data_plane_auth_mode, resolve_nullable_u64, validate_mcp_settings,
+    validate_tool_allowlist, validate_tool_timeout_and_policies, validate_transforms,
 };
 use crate::serde_helpers::default_true;
 use crate::store::{
     AdminProfile, AdminStore, AdminTenant, AdminUpstream, DataPlaneAuthMode, McpProfileSettings,
-    OidcPrincipalBinding, PutProfileDataPlaneAuth, PutProfileFlags, PutProfileInput,
-    PutProfileLimits, TenantSecretMetadata, ToolSourceKind, UpstreamEndpoint,
+    OidcClaimCondition, OidcClaimRule, OidcPrincipalBinding, PutProfileDataPlaneAuth,
+    PutProfileFlags, PutProfileInput, PutProfileLimits, TenantSecretMetadata, ToolSourceKind,
+    UpstreamEndpoint,
 };
 use crate::tenant::{IssueTenantTokenRequest, IssueTenantTokenResponse, now_unix_secs};
-use crate::tenant_token::{TenantSigner, TenantTokenPayloadV1};
+use crate::tenant_token::{TenantSigner, TenantTokenPayloadV1, resolve_issue_scopes};
 use crate::tool_policy::ToolPolicy;
 use axum::{
     Extension, Json, Router,
@@ -43,8 +44,13 @@ pub struct AdminState {
     pub tenant_signer: TenantSigner,
     pub shared_source_ids: Arc<std::collections::HashSet<String>>,
     pub oidc_issuer: Option<String>,
+    /// Used by the claim-rule test endpoint to validate sample tokens.
+    pub oidc: Option<crate::oidc::OidcValidator>,
+    pub tenant_oidc: Arc<crate::tenant_oidc::TenantOidcValidators>,
     pub audit: Arc<dyn crate::audit::AuditSink>,
     pub invalidation: Arc<crate::pg_invalidation::InvalidationDispatcher>,
+    /// Shared MCP data-plane state (used to terminate live sessions).
+    pub mcp_state: Arc<crate::mcp::McpState>,
 }
 
 pub fn router() -> Router {
@@ -80,6 +86,18 @@ pub fn router() -> Router {
             "/admin/v1/tenants/{tenant_id}/oidc-principals/{subject}",
             delete(delete_oidc_principal),
         )
+        .route(
+            "/admin/v1/tenants/{tenant_id}/oidc-claim-rules",
+            get(list_oidc_claim_rules),
+        )
+        .route(
+            "/admin/v1/tenants/{tenant_id}/oidc-claim-rules/test",
+            post(test_oidc_claim_rules),
+        )
+        .route(
+            "/admin/v1/tenants/{tenant_id}/oidc-claim-rules/{rule_id}",
+            put(put_oidc_claim_rule).delete(delete_oidc_claim_rule),
+        )
         .route(
             "/admin/v1/tenants/{tenant_id}/audit/settings",
             get(get_tenant_audit_settings).put(put_tenant_audit_settings),
@@ -118,6 +136,15 @@ pub fn router() -> Router {
             get(get_profile_audit_settings).put(put_profile_audit_settings),
         )
         .route("/admin/v1/tenant-tokens", post(issue_tenant_token))
+        .route(
+            "/admin/v1/tenants/{tenant_id}/tenant-tokens/{token_id}",
+            delete(revoke_tenant_token),
+        )
+        .route("/admin/v1/sessions", get(list_sessions))
+        .route(
+            "/admin/v1/sessions/{session_id}",
+            get(get_session).delete(terminate_session),
+        )
 }
 
 #[derive(Debug, Deserialize)]
@@ -285,6 +312,8 @@ async fn bootstrap_tenant(
     let payload = TenantTokenPayloadV1 {
         tenant_id: tenant_id.to_string(),
         exp_unix_secs: exp,
+        token_id: Some(Uuid::new_v4().to_string()),
+        scopes: None,
     };
     let token = match state.tenant_signer.sign_v1(&payload) {
         Ok(t) => t,
@@ -770,6 +799,19 @@ async fn put_upstream(
     // Outbound safety (SSRF hardening): validate upstream endpoints before storing them.
     let safety = crate::outbound_safety::gateway_outbound_http_safety();
     for ep in &endpoints {
+        if matches!(
+            ep.auth,
+            Some(AuthConfig::Sigv4 { .. } | AuthConfig::Hmac { .. })
+        ) {
+            return (
+                StatusCode::BAD_REQUEST,
+                format!(
+                    "upstream endpoint '{}': request signing auth (sigv4/hmac) is only supported for HTTP and OpenAPI tool sources",
+                    ep.id
+                ),
+            )
+                .into_response();
+        }
         // Upstream endpoint scheme policy: prefer HTTPS by default (dev override supported).
         if let Err(e) = crate::outbound_safety::check_upstream_https_policy(&ep.url) {
             return (
@@ -1003,10 +1045,10 @@ fn resolve_profile_description(
 }
 
 fn validate_oidc_configured_if_needed(
-    oidc_issuer: Option<&str>,
+    oidc_configured: bool,
     mode: DataPlaneAuthMode,
 ) -> Result<(), BoxResponse> {
-    if mode == DataPlaneAuthMode::JwtEveryRequest && oidc_issuer.is_none() {
+    if mode == DataPlaneAuthMode::JwtEveryRequest && !oidc_configured {
         return Err(Box::new(
             (StatusCode::BAD_REQUEST, OIDC_NOT_CONFIGURED_MSG).into_response(),
         ));
@@ -1167,11 +1209,26 @@ async fn admin_put_profile_inner_impl(
     let enabled_tools = req.tools.as_deref().unwrap_or(&[]);
     let data_plane_auth =
         resolve_data_plane_auth_settings(req.data_plane_auth.clone(), existing.as_ref(), is_update);
+    let oidc_configured = crate::tenant_oidc::oidc_configured_for_tenant(
+        oidc_issuer.is_some(),
+        store,
+        &req.tenant_id,
+    )
+    .await
+    .map_err(|e| {
+        Box::new(AdminPutProfileInnerError {
+            resp: (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+            profile_uuid: Some(profile_uuid),
+            profile_id: Some(profile_id.clone()),
+            name: Some(name.clone()),
+            error: AuditError::new("internal_error", e.to_string()),
+        })
+    })?;
     admin_put_profile_validate_oidc(
         profile_uuid,
         profile_id.clone(),
         name.clone(),
-        oidc_issuer,
+        oidc_configured,
         data_plane_auth.mode,
     )?;
 
@@ -1196,7 +1253,17 @@ async fn admin_put_profile_inner_impl(
         enabled_tools,
         tool_call_timeout_secs,
         &tool_policies,
+        &req.transforms,
     )?;
+    if let Err(msg) = validate_mcp_settings(&mcp) {
+        return Err(Box::new(AdminPutProfileInnerError {
+            resp: (StatusCode::BAD_REQUEST, msg.clone()).into_response(),
+            profile_uuid: Some(profile_uuid),
+            profile_id: Some(profile_id),
+            name: Some(name),
+            error: AuditError::new("bad_request", msg),
+        }));
+    }
 
     admin_put_profile_validate_no_self_upstream_loop(
         store,
@@ -1305,10 +1372,10 @@ fn admin_put_profile_validate_oidc(
     profile_uuid: Uuid,
     profile_id: String,
     name: String,
-    oidc_issuer: Option<&str>,
+    oidc_configured: bool,
     mode: DataPlaneAuthMode,
 ) -> AdminPutProfileInnerResult<()> {
-    if let Err(resp) = validate_oidc_configured_if_needed(oidc_issuer, mode) {
+    if let Err(resp) = validate_oidc_configured_if_needed(oidc_configured, mode) {
         let status = resp.status();
         return Err(Box::new(AdminPutProfileInnerError {
             resp: *resp,
@@ -1348,8 +1415,11 @@ fn admin_put_profile_validate_tools(
     enabled_tools: &[String],
     tool_call_timeout_secs: Option<u64>,
     tool_policies: &[ToolPolicy],
+    transforms: &TransformPipeline,
 ) -> AdminPutProfileInnerResult<()> {
-    if let Err(msg) = validate_tool_timeout_and_policies(tool_call_timeout_secs, tool_policies) {
+    if let Err(msg) = validate_tool_timeout_and_policies(tool_call_timeout_secs, tool_policies)
+        .and_then(|()| validate_transforms(transforms))
+    {
         return Err(Box::new(AdminPutProfileInnerError {
             resp: (StatusCode::BAD_REQUEST, msg.clone()).into_response(),
             profile_uuid: Some(profile_uuid),
@@ -1634,6 +1704,11 @@ async fn issue_tenant_token(
         Err(e) => return (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
     }
 
+    let scopes = match resolve_issue_scopes(req.role, req.scopes) {
+        Ok(s) => s,
+        Err(e) => return (StatusCode::BAD_REQUEST, e.to_string()).into_response(),
+    };
+
     let ttl = req.ttl_seconds.unwrap_or(31_536_000);
     let now = match now_unix_secs() {
         Ok(n) => n,
@@ -1644,6 +1719,8 @@ async fn issue_tenant_token(
     let payload = TenantTokenPayloadV1 {
         tenant_id: req.tenant_id.clone(),
         exp_unix_secs: exp,
+        token_id: Some(Uuid::new_v4().to_string()),
+        scopes,
     };
     let token = match state.tenant_signer.sign_v1(&payload) {
         Ok(t) => t,
@@ -1655,10 +1732,140 @@ async fn issue_tenant_token(
         tenant_id: req.tenant_id,
         token,
         exp_unix_secs: exp,
+        token_id: payload.token_id,
+        scopes: payload.scopes,
     })
     .into_response()
 }
 
+async fn revoke_tenant_token(
+    Extension(state): Extension<Arc<AdminState>>,
+    headers: HeaderMap,
+    Path((tenant_id, token_id)): Path<(String, String)>,
+) -> impl IntoResponse {
+    if let Err(resp) = authz(&headers, state.admin_token.as_deref()) {
+        return resp.into_response();
+    }
+    let Some(store) = &state.store else {
+        return (StatusCode::SERVICE_UNAVAILABLE, "Admin store unavailable").into_response();
+    };
+
+    match store.get_tenant(&tenant_id).await {
+        Ok(Some(_)) => {}
+        Ok(None) => return (StatusCode::NOT_FOUND, "tenant not found").into_response(),
+        Err(e) => return (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+    }
+
+    match store.revoke_tenant_token(&tenant_id, &token_id).await {
+        Ok(_) => {
+            state.invalidation.apply_local(
+                &crate::pg_invalidation::InvalidationEvent::TenantTokenRevoked {
+                    tenant_id,
+                    token_id,
+                },
+            );
+            Json(serde_json::json!({ "ok": true })).into_response()
+        }
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SessionsQuery {
+    tenant_id: Option<String>,
+    profile_id: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SessionsResponse {
+    sessions: Vec<crate::session_registry::SessionInfo>,
+}
+
+async fn list_sessions(
+    Extension(state): Extension<Arc<AdminState>>,
+    headers: HeaderMap,
+    Query(q): Query<SessionsQuery>,
+) -> impl IntoResponse {
+    if let Err(resp) = authz(&headers, state.admin_token.as_deref()) {
+        return resp.into_response();
+    }
+    let filter = crate::session_registry::SessionFilter {
+        tenant_id: q.tenant_id,
+        profile_id: q.profile_id,
+    };
+    match state.mcp_state.sessions.list(&filter).await {
+        Ok(sessions) => Json(SessionsResponse {
+            sessions: sessions.iter().map(Into::into).collect(),
+        })
+        .into_response(),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+    }
+}
+
+async fn get_session(
+    Extension(state): Extension<Arc<AdminState>>,
+    headers: HeaderMap,
+    Path(session_id): Path<String>,
+) -> impl IntoResponse {
+    if let Err(resp) = authz(&headers, state.admin_token.as_deref()) {
+        return resp.into_response();
+    }
+    match state.mcp_state.sessions.get(&session_id).await {
+        Ok(Some(r)) => Json(crate::session_registry::SessionInfo::from(&r)).into_response(),
+        Ok(None) => (StatusCode::NOT_FOUND, "session not found").into_response(),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+    }
+}
+
+async fn terminate_session(
+    Extension(state): Extension<Arc<AdminState>>,
+    headers: HeaderMap,
+    Path(session_id): Path<String>,
+) -> impl IntoResponse {
+    if let Err(resp) = authz(&headers, state.admin_token.as_deref()) {
+        return resp.into_response();
+    }
+    let started = Instant::now();
+
+    let record = match crate::mcp::terminate_session(&state.mcp_state, &session_id).await {
+        Ok(Some(r)) => r,
+        Ok(None) => return (StatusCode::NOT_FOUND, "session not found").into_response(),
+        Err(e) => return (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+    };
+    state
+        .invalidation
+        .publish_best_effort(
+            &crate::pg_invalidation::InvalidationEvent::McpSessionTerminated {
+                session_id: session_id.clone(),
+            },
+        )
+        .await;
+
+    state
+        .audit
+        .record(crate::audit::http_event(HttpAuditEvent {
+            tenant_id: record.tenant_id.clone(),
+            actor: AuditActor::default(),
+            action: "admin.session_terminate",
+            http_method: "DELETE",
+            http_route: "/admin/v1/sessions/{session_id}",
+            status_code: 200,
+            ok: true,
+            elapsed: started.elapsed(),
+            meta: serde_json::json!({
+                "session_id": session_id,
+                "profile_id": record.profile_id,
+                "principal": record.principal,
+            }),
+            error: None,
+        }))
+        .await;
+
+    Json(OkResponse { ok: true }).into_response()
+}
+
 #[derive(Debug, Deserialize)]
 #[serde(tag = "type", rename_all = "kebab-case")]
 enum PutToolSourceBody {
@@ -1666,13 +1873,25 @@ enum PutToolSourceBody {
         #[serde(default = "default_true")]
         enabled: bool,
         #[serde(flatten)]
-        config: HttpServerConfig,
+        config: Box<HttpServerConfig>,
     },
     Openapi {
         #[serde(default = "default_true")]
         enabled: bool,
         #[serde(flatten)]
-        config: ApiServerConfig,
+        config: Box<ApiServerConfig>,
+    },
+    Workflow {
+        #[serde(default = "default_true")]
+        enabled: bool,
+        #[serde(flatten)]
+        config: crate::workflow::WorkflowSourceConfig,
+    },
+    Content {
+        #[serde(default = "default_true")]
+        enabled: bool,
+        #[serde(flatten)]
+        config: crate::content_source::ContentSourceConfig,
     },
 }
 
@@ -1707,6 +1926,10 @@ struct SecretsResponse {
 #[serde(rename_all = "camelCase")]
 struct PutOidcPrincipalRequest {
     subject: String,
+    /// Target issuer (Gateway-wide issuer or one of the tenant's issuers). Defaults to the
+    /// Gateway-wide issuer.
+    #[serde(default)]
+    issuer: Option<String>,
     /// If set, the principal is scoped to this profile. If omitted, principal is tenant-wide.
     #[serde(default)]
     profile_id: Option<String>,
@@ -1719,6 +1942,15 @@ struct PutOidcPrincipalRequest {
 struct DeleteOidcPrincipalQuery {
     #[serde(default)]
     profile_id: Option<String>,
+    #[serde(default)]
+    issuer: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct OidcIssuerQuery {
+    #[serde(default)]
+    issuer: Option<String>,
 }
 
 #[derive(Debug, Serialize)]
@@ -1727,6 +1959,61 @@ struct OidcPrincipalsResponse {
     principals: Vec<OidcPrincipalBinding>,
 }
 
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PutOidcClaimRuleRequest {
+    /// Target issuer (Gateway-wide issuer or one of the tenant's issuers). Defaults to the
+    /// Gateway-wide issuer.
+    #[serde(default)]
+    issuer: Option<String>,
+    /// If set, the rule grants only this profile. If omitted, the rule grants the whole tenant.
+    #[serde(default)]
+    profile_id: Option<String>,
+    #[serde(default)]
+    description: Option<String>,
+    conditions: Vec<OidcClaimCondition>,
+    #[serde(default = "default_true")]
+    enabled: bool,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct OidcClaimRulesResponse {
+    rules: Vec<OidcClaimRule>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TestOidcClaimRulesRequest {
+    /// Sample JWT; validated exactly like data-plane bearer tokens.
+    #[serde(default)]
+    token: Option<String>,
+    /// Raw claims to evaluate instead of a token (dry-run without signature validation).
+    #[serde(default)]
+    claims: Option<serde_json::Value>,
+    /// If set, report whether the rules grant this profile. If omitted, any matching rule counts.
+    #[serde(default)]
+    profile_id: Option<String>,
+    /// Issuer whose bindings/rules apply to raw `claims` (defaults to the claims' `iss`, then the
+    /// Gateway-wide issuer). Ignored for tokens, which are routed by their `iss`.
+    #[serde(default)]
+    issuer: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct TestOidcClaimRulesResponse {
+    issuer: String,
+    subject: Option<String>,
+    /// An enabled explicit principal binding (issuer + subject) grants access.
+    principal_bound: bool,
+    /// Some enabled rule grants access.
+    rule_granted: bool,
+    allowed: bool,
+    claims: serde_json::Value,
+    rules: Vec<crate::oidc_rules::RuleEvaluation>,
+}
+
 fn is_valid_source_id(id: &str) -> bool {
     !id.is_empty()
         && !id.contains(':')
@@ -1739,6 +2026,8 @@ fn tool_source_kind_str(k: ToolSourceKind) -> &'static str {
     match k {
         ToolSourceKind::Http => "http",
         ToolSourceKind::Openapi => "openapi",
+        ToolSourceKind::Workflow => "workflow",
+        ToolSourceKind::Content => "content",
     }
 }
 
@@ -1901,6 +2190,25 @@ async fn admin_put_tool_source_inner(
         return outcome;
     }
 
+    if let PutToolSourceBody::Workflow { config, .. } = &body
+        && let Err(e) = config.validate()
+    {
+        return AdminPutToolSourceOutcome::fail(
+            StatusCode::BAD_REQUEST,
+            format!("invalid workflow: {e}"),
+            AuditError::new("bad_request", "invalid workflow"),
+        );
+    }
+    if let PutToolSourceBody::Content { config, .. } = &body
+        && let Err(e) = config.validate()
+    {
+        return AdminPutToolSourceOutcome::fail(
+            StatusCode::BAD_REQUEST,
+            format!("invalid content source: {e}"),
+            AuditError::new("bad_request", "invalid content source"),
+        );
+    }
+
     let (enabled, kind, spec_res) = match body {
         PutToolSourceBody::Http { enabled, config } => (
             enabled,
@@ -1912,6 +2220,16 @@ async fn admin_put_tool_source_inner(
             ToolSourceKind::Openapi,
             serde_json::to_value(&config).map_err(|e| e.to_string()),
         ),
+        PutToolSourceBody::Workflow { enabled, config } => (
+            enabled,
+            ToolSourceKind::Workflow,
+            serde_json::to_value(&config).map_err(|e| e.to_string()),
+        ),
+        PutToolSourceBody::Content { enabled, config } => (
+            enabled,
+            ToolSourceKind::Content,
+            serde_json::to_value(&config).map_err(|e| e.to_string()),
+        ),
     };
     let kind_for_meta = Some(tool_source_kind_str(kind).to_string());
     let enabled_for_meta = Some(enabled);
@@ -2254,6 +2572,7 @@ async fn list_oidc_principals(
     Extension(state): Extension<Arc<AdminState>>,
     headers: HeaderMap,
     Path(tenant_id): Path<String>,
+    Query(q): Query<OidcIssuerQuery>,
 ) -> impl IntoResponse {
     if let Err(resp) = authz(&headers, state.admin_token.as_deref()) {
         return resp.into_response();
@@ -2261,13 +2580,6 @@ async fn list_oidc_principals(
     let Some(store) = &state.store else {
         return (StatusCode::SERVICE_UNAVAILABLE, "Admin store unavailable").into_response();
     };
-    let Some(issuer) = state.oidc_issuer.as_deref() else {
-        return (
-            StatusCode::SERVICE_UNAVAILABLE,
-            "OIDC not configured (set UNRELATED_GATEWAY_OIDC_ISSUER)",
-        )
-            .into_response();
-    };
 
     // Ensure tenant exists.
     match store.get_tenant(&tenant_id).await {
@@ -2275,8 +2587,15 @@ async fn list_oidc_principals(
         Ok(None) => return (StatusCode::NOT_FOUND, "tenant not found").into_response(),
         Err(e) => return (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
     }
+    let issuer =
+        match admin_resolve_oidc_issuer(&state, store.as_ref(), &tenant_id, q.issuer.as_deref())
+            .await
+        {
+            Ok(i) => i,
+            Err(resp) => return resp,
+        };
 
-    match store.list_oidc_principals(&tenant_id, issuer).await {
+    match store.list_oidc_principals(&tenant_id, &issuer).await {
         Ok(principals) => Json(OidcPrincipalsResponse { principals }).into_response(),
         Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
     }
@@ -2294,13 +2613,6 @@ async fn put_oidc_principal(
     let Some(store) = &state.store else {
         return (StatusCode::SERVICE_UNAVAILABLE, "Admin store unavailable").into_response();
     };
-    let Some(issuer) = state.oidc_issuer.as_deref() else {
-        return (
-            StatusCode::SERVICE_UNAVAILABLE,
-            "OIDC not configured (set UNRELATED_GATEWAY_OIDC_ISSUER)",
-        )
-            .into_response();
-    };
 
     let subject = req.subject.trim().to_string();
     if !is_valid_oidc_subject(&subject) {
@@ -2313,6 +2625,13 @@ async fn put_oidc_principal(
         Ok(None) => return (StatusCode::NOT_FOUND, "tenant not found").into_response(),
         Err(e) => return (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
     }
+    let issuer =
+        match admin_resolve_oidc_issuer(&state, store.as_ref(), &tenant_id, req.issuer.as_deref())
+            .await
+        {
+            Ok(i) => i,
+            Err(resp) => return resp,
+        };
 
     if let Some(profile_id) = req.profile_id.as_deref() {
         // Validate UUID and cross-tenant correctness.
@@ -2333,7 +2652,7 @@ async fn put_oidc_principal(
     if let Err(e) = store
         .put_oidc_principal(
             &tenant_id,
-            issuer,
+            &issuer,
             &subject,
             req.profile_id.as_deref(),
             req.enabled,
@@ -2358,13 +2677,6 @@ async fn delete_oidc_principal(
     let Some(store) = &state.store else {
         return (StatusCode::SERVICE_UNAVAILABLE, "Admin store unavailable").into_response();
     };
-    let Some(issuer) = state.oidc_issuer.as_deref() else {
-        return (
-            StatusCode::SERVICE_UNAVAILABLE,
-            "OIDC not configured (set UNRELATED_GATEWAY_OIDC_ISSUER)",
-        )
-            .into_response();
-    };
 
     let subject = subject.trim().to_string();
     if !is_valid_oidc_subject(&subject) {
@@ -2377,9 +2689,16 @@ async fn delete_oidc_principal(
         Ok(None) => return (StatusCode::NOT_FOUND, "tenant not found").into_response(),
         Err(e) => return (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
     }
+    let issuer =
+        match admin_resolve_oidc_issuer(&state, store.as_ref(), &tenant_id, q.issuer.as_deref())
+            .await
+        {
+            Ok(i) => i,
+            Err(resp) => return resp,
+        };
 
     match store
-        .delete_oidc_principal(&tenant_id, issuer, &subject, q.profile_id.as_deref())
+        .delete_oidc_principal(&tenant_id, &issuer, &subject, q.profile_id.as_deref())
         .await
     {
         Ok(0) => (StatusCode::NOT_FOUND, "oidc principal not found").into_response(),
@@ -2388,6 +2707,319 @@ async fn delete_oidc_principal(
     }
 }
 
+/// Resolve the issuer an OIDC binding/rule request targets.
+///
+/// An explicit issuer must be the Gateway-wide issuer or one of the tenant's configured issuers;
+/// otherwise the Gateway-wide issuer is used.
+async fn admin_resolve_oidc_issuer(
+    state: &AdminState,
+    store: &dyn AdminStore,
+    tenant_id: &str,
+    requested: Option<&str>,
+) -> Result<String, Response> {
+    let Some(requested) = requested.map(str::trim).filter(|s| !s.is_empty()) else {
+        return state.oidc_issuer.clone().ok_or_else(|| {
+            (
+                StatusCode::SERVICE_UNAVAILABLE,
+                "OIDC not configured (set UNRELATED_GATEWAY_OIDC_ISSUER or pass a tenant issuer)",
+            )
+                .into_response()
+        });
+    };
+    if state.oidc_issuer.as_deref() == Some(requested) {
+        return Ok(requested.to_string());
+    }
+    match store.list_tenant_oidc_issuers(tenant_id).await {
+        Ok(issuers) if issuers.iter().any(|i| i.issuer == requested) => Ok(requested.to_string()),
+        Ok(_) => Err((StatusCode::BAD_REQUEST, "unknown OIDC issuer for tenant").into_response()),
+        Err(e) => Err((StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response()),
+    }
+}
+
+/// Validate a sample token the same way the data plane does: route by `iss` to an enabled tenant
+/// issuer, else the Gateway-wide issuer.
+async fn admin_validate_sample_token(
+    state: &AdminState,
+    store: &dyn AdminStore,
+    tenant_id: &str,
+    token: &str,
+) -> Result<(String, serde_json::Value), Response> {
+    let bad_token = |msg: String| (StatusCode::BAD_REQUEST, msg).into_response();
+    let iss = crate::oidc::unverified_issuer(token)
+        .ok_or_else(|| bad_token("invalid token: missing iss".to_string()))?;
+
+    let tenant_issuer = store
+        .list_tenant_oidc_issuers(tenant_id)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response())?
+        .into_iter()
+        .find(|i| i.enabled && i.issuer == iss);
+    let validator = match (tenant_issuer, state.oidc.as_ref()) {
+        (Some(cfg), _) => state
+            .tenant_oidc
+            .validator(tenant_id, &cfg)
+            .await
+            .map_err(|e| bad_token(format!("tenant issuer unavailable: {e:#}")))?,
+        (None, Some(global)) if global.issuer() == iss => global.clone(),
+        _ => return Err(bad_token(format!("untrusted token issuer '{iss}'"))),
+    };
+    let claims = validator
+        .validate(token)
+        .await
+        .map_err(|e| bad_token(format!("invalid token: {e:#}")))?;
+    Ok((iss, claims))
+}
+
+/// Rule ids share the tool source id charset; `test` is reserved for the dry-run endpoint.
+fn is_valid_oidc_claim_rule_id(id: &str) -> bool {
+    is_valid_source_id(id) && id != "test"
+}
+
+async fn admin_check_tenant_profile(
+    store: &dyn AdminStore,
+    tenant_id: &str,
+    profile_id: Option<&str>,
+) -> Result<(), Response> {
+    match store.get_tenant(tenant_id).await {
+        Ok(Some(_)) => {}
+        Ok(None) => return Err((StatusCode::NOT_FOUND, "tenant not found").into_response()),
+        Err(e) => return Err((StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response()),
+    }
+    let Some(profile_id) = profile_id else {
+        return Ok(());
+    };
+    if Uuid::parse_str(profile_id)
+        .ok()
+        .and_then(|u| (u.get_version() == Some(Version::Random)).then_some(u))
+        .is_none()
+    {
+        return Err((StatusCode::NOT_FOUND, "profile not found").into_response());
+    }
+    match store.get_profile(profile_id).await {
+        Ok(Some(p)) if p.tenant_id == tenant_id => Ok(()),
+        Ok(_) => Err((StatusCode::NOT_FOUND, "profile not found").into_response()),
+        Err(e) => Err((StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response()),
+    }
+}
+
+async fn list_oidc_claim_rules(
+    Extension(state): Extension<Arc<AdminState>>,
+    headers: HeaderMap,
+    Path(tenant_id): Path<String>,
+    Query(q): Query<OidcIssuerQuery>,
+) -> impl IntoResponse {
+    if let Err(resp) = authz(&headers, state.admin_token.as_deref()) {
+        return resp.into_response();
+    }
+    let Some(store) = &state.store else {
+        return (StatusCode::SERVICE_UNAVAILABLE, "Admin store unavailable").into_response();
+    };
+    if let Err(resp) = admin_check_tenant_profile(store.as_ref(), &tenant_id, None).await {
+        return resp;
+    }
+    let issuer =
+        match admin_resolve_oidc_issuer(&state, store.as_ref(), &tenant_id, q.issuer.as_deref())
+            .await
+        {
+            Ok(i) => i,
+            Err(resp) => return resp,
+        };
+
+    match store.list_oidc_claim_rules(&tenant_id, &issuer).await {
+        Ok(rules) => Json(OidcClaimRulesResponse { rules }).into_response(),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+    }
+}
+
+async fn put_oidc_claim_rule(
+    Extension(state): Extension<Arc<AdminState>>,
+    headers: HeaderMap,
+    Path((tenant_id, rule_id)): Path<(String, String)>,
+    Json(req): Json<PutOidcClaimRuleRequest>,
+) -> impl IntoResponse {
+    if let Err(resp) = authz(&headers, state.admin_token.as_deref()) {
+        return resp.into_response();
+    }
+    let Some(store) = &state.store else {
+        return (StatusCode::SERVICE_UNAVAILABLE, "Admin store unavailable").into_response();
+    };
+
+    if !is_valid_oidc_claim_rule_id(&rule_id) {
+        return (
+            StatusCode::BAD_REQUEST,
+            "invalid rule id (allowed: [A-Za-z0-9_-], 'test' is reserved)",
+        )
+            .into_response();
+    }
+    if let Err(msg) = crate::oidc_rules::validate_conditions(&req.conditions) {
+        return (StatusCode::BAD_REQUEST, msg).into_response();
+    }
+    if let Err(resp) =
+        admin_check_tenant_profile(store.as_ref(), &tenant_id, req.profile_id.as_deref()).await
+    {
+        return resp;
+    }
+    let issuer =
+        match admin_resolve_oidc_issuer(&state, store.as_ref(), &tenant_id, req.issuer.as_deref())
+            .await
+        {
+            Ok(i) => i,
+            Err(resp) => return resp,
+        };
+
+    let rule = OidcClaimRule {
+        id: rule_id,
+        issuer,
+        profile_id: req.profile_id,
+        enabled: req.enabled,
+        description: req
+            .description
+            .map(|d| d.trim().to_string())
+            .filter(|d| !d.is_empty()),
+        conditions: req
+            .conditions
+            .into_iter()
+            .map(|c| OidcClaimCondition {
+                claim: c.claim.trim().to_string(),
+                ..c
+            })
+            .collect(),
+    };
+    if let Err(e) = store.put_oidc_claim_rule(&tenant_id, &rule).await {
+        return (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response();
+    }
+
+    Json(rule).into_response()
+}
+
+async fn delete_oidc_claim_rule(
+    Extension(state): Extension<Arc<AdminState>>,
+    headers: HeaderMap,
+    Path((tenant_id, rule_id)): Path<(String, String)>,
+    Query(q): Query<OidcIssuerQuery>,
+) -> impl IntoResponse {
+    if let Err(resp) = authz(&headers, state.admin_token.as_deref()) {
+        return resp.into_response();
+    }
+    let Some(store) = &state.store else {
+        return (StatusCode::SERVICE_UNAVAILABLE, "Admin store unavailable").into_response();
+    };
+    if let Err(resp) = admin_check_tenant_profile(store.as_ref(), &tenant_id, None).await {
+        return resp;
+    }
+    let issuer =
+        match admin_resolve_oidc_issuer(&state, store.as_ref(), &tenant_id, q.issuer.as_deref())
+            .await
+        {
+            Ok(i) => i,
+            Err(resp) => return resp,
+        };
+
+    match store
+        .delete_oidc_claim_rule(&tenant_id, &issuer, &rule_id)
+        .await
+    {
+        Ok(true) => Json(OkResponse { ok: true }).into_response(),
+        Ok(false) => (StatusCode::NOT_FOUND, "oidc claim rule not found").into_response(),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+    }
+}
+
+/// Dry-run: evaluate a sample token (or raw claims) against the tenant's bindings and rules.
+async fn test_oidc_claim_rules(
+    Extension(state): Extension<Arc<AdminState>>,
+    headers: HeaderMap,
+    Path(tenant_id): Path<String>,
+    Json(req): Json<TestOidcClaimRulesRequest>,
+) -> impl IntoResponse {
+    if let Err(resp) = authz(&headers, state.admin_token.as_deref()) {
+        return resp.into_response();
+    }
+    let Some(store) = &state.store else {
+        return (StatusCode::SERVICE_UNAVAILABLE, "Admin store unavailable").into_response();
+    };
+    if let Err(resp) =
+        admin_check_tenant_profile(store.as_ref(), &tenant_id, req.profile_id.as_deref()).await
+    {
+        return resp;
+    }
+
+    let (issuer, claims) = match (req.token.as_deref().map(str::trim), req.claims) {
+        (Some(token), None) => {
+            match admin_validate_sample_token(&state, store.as_ref(), &tenant_id, token).await {
+                Ok(v) => v,
+                Err(resp) => return resp,
+            }
+        }
+        (None, Some(claims)) if claims.is_object() => {
+            let requested = req.issuer.clone().or_else(|| {
+                claims
+                    .get("iss")
+                    .and_then(serde_json::Value::as_str)
+                    .map(str::to_string)
+            });
+            match admin_resolve_oidc_issuer(
+                &state,
+                store.as_ref(),
+                &tenant_id,
+                requested.as_deref(),
+            )
+            .await
+            {
+                Ok(issuer) => (issuer, claims),
+                Err(resp) => return resp,
+            }
+        }
+        (None, Some(_)) => {
+            return (StatusCode::BAD_REQUEST, "claims must be a JSON object").into_response();
+        }
+        _ => {
+            return (
+                StatusCode::BAD_REQUEST,
+                "provide exactly one of 'token' or 'claims'",
+            )
+                .into_response();
+        }
+    };
+
+    let subject = claims
+        .get("sub")
+        .and_then(serde_json::Value::as_str)
+        .or_else(|| claims.get("oid").and_then(serde_json::Value::as_str))
+        .map(ToString::to_string);
+
+    let principal_bound = match subject.as_deref() {
+        Some(subject) => match store.list_oidc_principals(&tenant_id, &issuer).await {
+            Ok(bindings) => bindings.iter().any(|b| {
+                b.enabled
+                    && b.subject == subject
+                    && (b.profile_id.is_none() || b.profile_id == req.profile_id)
+            }),
+            Err(e) => return (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+        },
+        None => false,
+    };
+
+    let rules = match store.list_oidc_claim_rules(&tenant_id, &issuer).await {
+        Ok(r) => r,
+        Err(e) => return (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+    };
+    let rules = crate::oidc_rules::explain(&rules, req.profile_id.as_deref(), &claims);
+    let rule_granted = rules.iter().any(|r| r.grants);
+
+    Json(TestOidcClaimRulesResponse {
+        issuer,
+        // The data plane requires a subject to bind sessions; without one nothing is allowed.
+        allowed: subject.is_some() && (principal_bound || rule_granted),
+        subject,
+        principal_bound,
+        rule_granted,
+        claims,
+        rules,
+    })
+    .into_response()
+}
+
 fn validate_audit_default_level(level: &str) -> Result<(), &'static str> {
     match level {
         "off" | "summary" | "metadata" | "payload" => Ok(()),
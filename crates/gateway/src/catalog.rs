@@ -5,6 +5,7 @@ use serde_json::Value;
 use std::collections::HashMap;
 use std::sync::Arc;
 use std::time::Duration;
+use unrelated_http_tools::context::CallContext;
 use unrelated_http_tools::runtime::HttpToolSource;
 use unrelated_openapi_tools::runtime::OpenApiToolSource;
 
@@ -52,7 +53,7 @@ impl SharedCatalog {
                     }
                     let source = HttpToolSource::new_with_safety(
                         id.clone(),
-                        config.clone(),
+                        (**config).clone(),
                         default_timeout,
                         safety.clone(),
                     )
@@ -69,7 +70,7 @@ impl SharedCatalog {
                     }
                     let source = OpenApiToolSource::build_with_safety(
                         id.clone(),
-                        config.clone(),
+                        (**config).clone(),
                         default_timeout,
                         startup_timeout,
                         openapi_probe_enabled,
@@ -108,7 +109,7 @@ impl SharedCatalog {
             .map(OpenApiToolSource::list_tools)
     }
 
-    /// Execute a tool call against a local (gateway-native) source.
+    /// Execute a tool call against a local (gateway-native) source within `ctx`'s deadline.
     ///
     /// # Errors
     ///
@@ -118,11 +119,12 @@ impl SharedCatalog {
         source_id: &str,
         tool_name: &str,
         arguments: Value,
+        ctx: &CallContext,
     ) -> anyhow::Result<CallToolResult> {
         if let Some(src) = self.inner.http_sources.get(source_id) {
             return src
                 .clone()
-                .call_tool(tool_name, arguments)
+                .call_tool_with_context(tool_name, arguments, ctx)
                 .await
                 .with_context(|| format!("call local tool '{source_id}:{tool_name}'"));
         }
@@ -130,7 +132,7 @@ impl SharedCatalog {
         if let Some(src) = self.inner.openapi_sources.get(source_id) {
             return src
                 .clone()
-                .call_tool(tool_name, arguments)
+                .call_tool_with_context(tool_name, arguments, ctx)
                 .await
                 .with_context(|| format!("call local tool '{source_id}:{tool_name}'"));
         }
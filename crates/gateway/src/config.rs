@@ -124,7 +124,7 @@ pub enum SharedSourceConfig {
         #[serde(default = "default_true")]
         public: bool,
         #[serde(flatten)]
-        config: http_tools::HttpServerConfig,
+        config: Box<http_tools::HttpServerConfig>,
     },
     /// Gateway-native `OpenAPI` tool source.
     Openapi {
@@ -133,7 +133,7 @@ pub enum SharedSourceConfig {
         #[serde(default = "default_true")]
         public: bool,
         #[serde(flatten)]
-        config: openapi_tools::ApiServerConfig,
+        config: Box<openapi_tools::ApiServerConfig>,
     },
 }
 
@@ -0,0 +1,580 @@
+//! Gateway-defined prompts and resources (Mode 3 tenant tool source kind `content`).
+//!
+//! Prompts carry declared arguments and message templates (`{{argument}}` placeholders).
+//! Resources are static: inline text, a URL fetched under the Gateway's outbound HTTP safety
+//! policy, or a file below the owning tenant's subdirectory of the operator-configured content
+//! directory (`<dir>/<tenant_id>/`), so tenants cannot read each other's files.
+
+use base64::Engine as _;
+use rmcp::model::{
+    AnnotateAble as _, GetPromptResult, Prompt, PromptArgument, PromptMessage, PromptMessageRole,
+    RawResource, ReadResourceResult, Resource, ResourceContents,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::HashSet;
+use std::path::{Component, Path, PathBuf};
+use unrelated_http_tools::safety::OutboundHttpSafety;
+
+const MAX_PROMPTS: usize = 256;
+const MAX_RESOURCES: usize = 256;
+/// Size cap for file resources and URL resources when the safety policy sets no limit.
+const DEFAULT_MAX_RESOURCE_BYTES: usize = 1024 * 1024;
+
+/// Env var naming the directory whose per-tenant subdirectories `file` resources are resolved
+/// against. Unset disables them.
+const CONTENT_DIR_ENV: &str = "UNRELATED_GATEWAY_CONTENT_DIR";
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ContentSourceConfig {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub prompts: Vec<PromptConfig>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub resources: Vec<ResourceConfig>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PromptConfig {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub arguments: Vec<PromptArgumentConfig>,
+    pub messages: Vec<PromptMessageConfig>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PromptArgumentConfig {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub required: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PromptMessageConfig {
+    #[serde(default)]
+    pub role: PromptRole,
+    /// Message text; `{{argument}}` is replaced by the argument value.
+    pub text: String,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PromptRole {
+    #[default]
+    User,
+    Assistant,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ResourceConfig {
+    pub uri: String,
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mime_type: Option<String>,
+    /// Exactly one of `text`, `url` or `file`.
+    #[serde(flatten)]
+    pub content: ResourceContentConfig,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ResourceContentConfig {
+    /// Inline text (e.g. markdown).
+    Text(String),
+    /// Fetched with `GET` on every read (outbound HTTP safety applies).
+    Url(String),
+    /// Path relative to `UNRELATED_GATEWAY_CONTENT_DIR/<tenant_id>`.
+    File(String),
+}
+
+impl ContentSourceConfig {
+    /// Validate names, URIs and templates.
+    ///
+    /// # Errors
+    ///
+    /// Returns a human-readable message describing the first problem found.
+    pub fn validate(&self) -> Result<(), String> {
+        if self.prompts.is_empty() && self.resources.is_empty() {
+            return Err("content source must define at least one prompt or resource".to_string());
+        }
+        if self.prompts.len() > MAX_PROMPTS {
+            return Err(format!(
+                "content source defines more than {MAX_PROMPTS} prompts"
+            ));
+        }
+        if self.resources.len() > MAX_RESOURCES {
+            return Err(format!(
+                "content source defines more than {MAX_RESOURCES} resources"
+            ));
+        }
+
+        let mut names = HashSet::new();
+        for p in &self.prompts {
+            if p.name.trim().is_empty() {
+                return Err("prompt name must not be empty".to_string());
+            }
+            if !names.insert(p.name.as_str()) {
+                return Err(format!("duplicate prompt name '{}'", p.name));
+            }
+            p.validate()
+                .map_err(|e| format!("prompt '{}': {e}", p.name))?;
+        }
+
+        let mut uris = HashSet::new();
+        for r in &self.resources {
+            if r.name.trim().is_empty() {
+                return Err(format!("resource '{}': name must not be empty", r.uri));
+            }
+            if !r.uri.contains(':') {
+                return Err(format!("resource uri '{}' must be an absolute URI", r.uri));
+            }
+            if !uris.insert(r.uri.as_str()) {
+                return Err(format!("duplicate resource uri '{}'", r.uri));
+            }
+            match &r.content {
+                ResourceContentConfig::Text(_) => {}
+                ResourceContentConfig::Url(url) => {
+                    let parsed = reqwest::Url::parse(url)
+                        .map_err(|e| format!("resource '{}': invalid url: {e}", r.uri))?;
+                    if !matches!(parsed.scheme(), "http" | "https") {
+                        return Err(format!("resource '{}': url must be http(s)", r.uri));
+                    }
+                }
+                ResourceContentConfig::File(path) => {
+                    relative_content_path(path)
+                        .map_err(|e| format!("resource '{}': {e}", r.uri))?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    #[must_use]
+    pub fn list_prompts(&self) -> Vec<Prompt> {
+        self.prompts
+            .iter()
+            .map(|p| {
+                let arguments = (!p.arguments.is_empty()).then(|| {
+                    p.arguments
+                        .iter()
+                        .map(|a| PromptArgument {
+                            name: a.name.clone(),
+                            title: None,
+                            description: a.description.clone(),
+                            required: Some(a.required),
+                        })
+                        .collect()
+                });
+                let mut prompt = Prompt::new(p.name.clone(), p.description.clone(), arguments);
+                prompt.title.clone_from(&p.title);
+                prompt
+            })
+            .collect()
+    }
+
+    #[must_use]
+    pub fn list_resources(&self) -> Vec<Resource> {
+        self.resources
+            .iter()
+            .map(|r| {
+                RawResource {
+                    uri: r.uri.clone(),
+                    name: r.name.clone(),
+                    title: r.title.clone(),
+                    description: r.description.clone(),
+                    mime_type: r.mime_type.clone(),
+                    size: match &r.content {
+                        ResourceContentConfig::Text(t) => u32::try_from(t.len()).ok(),
+                        _ => None,
+                    },
+                    icons: None,
+                    meta: None,
+                }
+                .no_annotation()
+            })
+            .collect()
+    }
+
+    /// Render a prompt with the given arguments.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the prompt is unknown or a required argument is missing.
+    pub fn get_prompt(
+        &self,
+        name: &str,
+        arguments: Option<&Map<String, Value>>,
+    ) -> Result<GetPromptResult, String> {
+        let prompt = self
+            .prompts
+            .iter()
+            .find(|p| p.name == name)
+            .ok_or_else(|| format!("unknown prompt: {name}"))?;
+
+        let empty = Map::new();
+        let args = arguments.unwrap_or(&empty);
+        for a in prompt.arguments.iter().filter(|a| a.required) {
+            if args.get(&a.name).is_none_or(Value::is_null) {
+                return Err(format!("missing required argument '{}'", a.name));
+            }
+        }
+
+        let messages = prompt
+            .messages
+            .iter()
+            .map(|m| {
+                let role = match m.role {
+                    PromptRole::User => PromptMessageRole::User,
+                    PromptRole::Assistant => PromptMessageRole::Assistant,
+                };
+                PromptMessage::new_text(role, render_prompt_text(&m.text, args))
+            })
+            .collect();
+        Ok(GetPromptResult {
+            description: prompt.description.clone(),
+            messages,
+        })
+    }
+
+    /// Read a resource by its URI; `file` resources resolve below `tenant_id`'s content directory.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the URI is unknown or the content cannot be loaded.
+    pub async fn read_resource(
+        &self,
+        tenant_id: &str,
+        uri: &str,
+        http: &reqwest::Client,
+        safety: &OutboundHttpSafety,
+    ) -> Result<ReadResourceResult, String> {
+        let resource = self
+            .resources
+            .iter()
+            .find(|r| r.uri == uri)
+            .ok_or_else(|| format!("unknown resource uri: {uri}"))?;
+
+        let (bytes, fetched_mime) = match &resource.content {
+            ResourceContentConfig::Text(text) => {
+                return Ok(ReadResourceResult {
+                    contents: vec![ResourceContents::TextResourceContents {
+                        uri: uri.to_string(),
+                        mime_type: Some(
+                            resource
+                                .mime_type
+                                .clone()
+                                .unwrap_or_else(|| "text/plain".to_string()),
+                        ),
+                        text: text.clone(),
+                        meta: None,
+                    }],
+                });
+            }
+            ResourceContentConfig::Url(url) => fetch_url(url, http, safety).await?,
+            ResourceContentConfig::File(path) => (read_content_file(tenant_id, path).await?, None),
+        };
+
+        let mime_type = resource.mime_type.clone().or(fetched_mime);
+        let contents = match String::from_utf8(bytes) {
+            Ok(text) if mime_type.as_deref().is_none_or(is_textual_mime) => {
+                ResourceContents::TextResourceContents {
+                    uri: uri.to_string(),
+                    mime_type,
+                    text,
+                    meta: None,
+                }
+            }
+            Ok(text) => binary_contents(uri, mime_type, text.as_bytes()),
+            Err(e) => binary_contents(uri, mime_type, e.as_bytes()),
+        };
+        Ok(ReadResourceResult {
+            contents: vec![contents],
+        })
+    }
+}
+
+impl PromptConfig {
+    fn validate(&self) -> Result<(), String> {
+        if self.messages.is_empty() {
+            return Err("at least one message is required".to_string());
+        }
+        let mut args = HashSet::new();
+        for a in &self.arguments {
+            if a.name.trim().is_empty() {
+                return Err("argument name must not be empty".to_string());
+            }
+            if !args.insert(a.name.as_str()) {
+                return Err(format!("duplicate argument '{}'", a.name));
+            }
+        }
+        for m in &self.messages {
+            for placeholder in prompt_placeholders(&m.text)? {
+                if !args.contains(placeholder) {
+                    return Err(format!(
+                        "message references undeclared argument '{placeholder}'"
+                    ));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+fn binary_contents(uri: &str, mime_type: Option<String>, bytes: &[u8]) -> ResourceContents {
+    ResourceContents::BlobResourceContents {
+        uri: uri.to_string(),
+        mime_type,
+        blob: base64::engine::general_purpose::STANDARD.encode(bytes),
+        meta: None,
+    }
+}
+
+fn is_textual_mime(mime: &str) -> bool {
+    let mime = mime.split(';').next().unwrap_or_default().trim();
+    mime.starts_with("text/")
+        || mime.ends_with("+json")
+        || mime.ends_with("+xml")
+        || matches!(
+            mime,
+            "application/json" | "application/xml" | "application/yaml" | "application/x-yaml"
+        )
+}
+
+/// Placeholder names (`{{name}}`) in a prompt message.
+fn prompt_placeholders(text: &str) -> Result<Vec<&str>, String> {
+    let mut out = Vec::new();
+    let mut rest = text;
+    while let Some(start) = rest.find("{{") {
+        let after = &rest[start + 2..];
+        let end = after
+            .find("}}")
+            .ok_or_else(|| "unterminated '{{' placeholder".to_string())?;
+        out.push(after[..end].trim());
+        rest = &after[end + 2..];
+    }
+    Ok(out)
+}
+
+fn render_prompt_text(text: &str, args: &Map<String, Value>) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find("{{") {
+        let after = &rest[start + 2..];
+        let Some(end) = after.find("}}") else {
+            break;
+        };
+        out.push_str(&rest[..start]);
+        match args.get(after[..end].trim()) {
+            None | Some(Value::Null) => {}
+            Some(Value::String(s)) => out.push_str(s),
+            Some(other) => out.push_str(&other.to_string()),
+        }
+        rest = &after[end + 2..];
+    }
+    out.push_str(rest);
+    out
+}
+
+async fn fetch_url(
+    url: &str,
+    http: &reqwest::Client,
+    safety: &OutboundHttpSafety,
+) -> Result<(Vec<u8>, Option<String>), String> {
+    crate::outbound_safety::check_url_allowed(safety, url).await?;
+    let mut resp = http
+        .get(url)
+        .send()
+        .await
+        .map_err(|e| unrelated_http_tools::safety::sanitize_reqwest_error(&e))?;
+    if !resp.status().is_success() {
+        return Err(format!("resource fetch failed: HTTP {}", resp.status()));
+    }
+    let mime = resp
+        .headers()
+        .get(reqwest::header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .map(str::to_string);
+
+    let limit = safety
+        .max_response_bytes
+        .unwrap_or(DEFAULT_MAX_RESOURCE_BYTES);
+    let mut body = Vec::new();
+    while let Some(chunk) = resp
+        .chunk()
+        .await
+        .map_err(|e| unrelated_http_tools::safety::sanitize_reqwest_error(&e))?
+    {
+        if body.len() + chunk.len() > limit {
+            return Err(format!("resource exceeds {limit} bytes"));
+        }
+        body.extend_from_slice(&chunk);
+    }
+    Ok((body, mime))
+}
+
+/// Validate a `file` resource path: relative, no `..`, no root/prefix components.
+fn relative_content_path(path: &str) -> Result<&Path, String> {
+    let p = Path::new(path);
+    if path.is_empty() || p.components().any(|c| !matches!(c, Component::Normal(_))) {
+        return Err(format!(
+            "file path '{path}' must be relative to the content directory (no '..')"
+        ));
+    }
+    Ok(p)
+}
+
+async fn read_content_file(tenant_id: &str, path: &str) -> Result<Vec<u8>, String> {
+    let Some(dir) = std::env::var_os(CONTENT_DIR_ENV) else {
+        return Err(format!(
+            "file resources are disabled ({CONTENT_DIR_ENV} is not set)"
+        ));
+    };
+    read_file_below(&tenant_content_dir(&PathBuf::from(dir), tenant_id)?, path).await
+}
+
+/// The tenant's subdirectory of the content directory.
+fn tenant_content_dir(dir: &Path, tenant_id: &str) -> Result<PathBuf, String> {
+    let mut components = Path::new(tenant_id).components();
+    match (components.next(), components.next()) {
+        (Some(Component::Normal(_)), None) => Ok(dir.join(tenant_id)),
+        _ => Err(format!("tenant '{tenant_id}' has no content directory")),
+    }
+}
+
+async fn read_file_below(dir: &Path, path: &str) -> Result<Vec<u8>, String> {
+    let rel = relative_content_path(path)?;
+    let base = tokio::fs::canonicalize(dir)
+        .await
+        .map_err(|e| format!("content directory unavailable: {e}"))?;
+    let full = tokio::fs::canonicalize(base.join(rel))
+        .await
+        .map_err(|_| format!("file '{path}' not found"))?;
+    // Symlinks must not escape the content directory.
+    if !full.starts_with(&base) {
+        return Err(format!("file '{path}' not found"));
+    }
+    let meta = tokio::fs::metadata(&full)
+        .await
+        .map_err(|_| format!("file '{path}' not found"))?;
+    if meta.len() > DEFAULT_MAX_RESOURCE_BYTES as u64 {
+        return Err(format!(
+            "file '{path}' exceeds {DEFAULT_MAX_RESOURCE_BYTES} bytes"
+        ));
+    }
+    tokio::fs::read(&full)
+        .await
+        .map_err(|e| format!("read file '{path}': {e}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn config(v: Value) -> ContentSourceConfig {
+        serde_json::from_value(v).expect("content config")
+    }
+
+    #[test]
+    fn prompt_renders_arguments_and_requires_required_ones() {
+        let cfg = config(json!({
+            "prompts": [{
+                "name": "incident",
+                "arguments": [
+                    { "name": "service", "required": true },
+                    { "name": "severity" }
+                ],
+                "messages": [
+                    { "text": "Triage {{service}} (sev {{ severity }})." },
+                    { "role": "assistant", "text": "Looking at {{service}}." }
+                ]
+            }]
+        }));
+        cfg.validate().expect("valid");
+
+        let args = json!({ "service": "billing", "severity": 2 });
+        let out = cfg
+            .get_prompt("incident", args.as_object())
+            .expect("prompt");
+        let v = serde_json::to_value(&out).expect("json");
+        assert_eq!(
+            v["messages"][0]["content"]["text"],
+            "Triage billing (sev 2)."
+        );
+        assert_eq!(v["messages"][1]["role"], "assistant");
+
+        let err = cfg.get_prompt("incident", None).unwrap_err();
+        assert!(err.contains("service"), "err={err}");
+    }
+
+    #[test]
+    fn validate_rejects_undeclared_placeholders_and_escaping_files() {
+        let bad_arg = config(json!({
+            "prompts": [{ "name": "p", "messages": [{ "text": "hi {{who}}" }] }]
+        }));
+        assert!(bad_arg.validate().unwrap_err().contains("undeclared"));
+
+        let escaping = config(json!({
+            "resources": [{ "uri": "docs://runbook", "name": "runbook", "file": "../etc/passwd" }]
+        }));
+        assert!(escaping.validate().unwrap_err().contains("relative"));
+
+        let ok = config(json!({
+            "resources": [{ "uri": "docs://runbook", "name": "runbook", "text": "# Runbook" }]
+        }));
+        ok.validate().expect("valid");
+        assert_eq!(ok.list_resources()[0].uri, "docs://runbook");
+    }
+
+    #[tokio::test]
+    async fn file_resources_stay_inside_the_content_dir() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        std::fs::create_dir(dir.path().join("runbooks")).expect("mkdir");
+        std::fs::write(dir.path().join("runbooks/db.md"), "# DB").expect("write");
+
+        let bytes = read_file_below(dir.path(), "runbooks/db.md")
+            .await
+            .expect("read");
+        assert_eq!(bytes, b"# DB");
+        assert!(read_file_below(dir.path(), "../db.md").await.is_err());
+        assert!(
+            read_file_below(dir.path(), "runbooks/missing.md")
+                .await
+                .is_err()
+        );
+    }
+
+    #[tokio::test]
+    async fn file_resources_are_scoped_to_the_owning_tenant() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        for tenant in ["t1", "t2"] {
+            std::fs::create_dir(dir.path().join(tenant)).expect("mkdir");
+        }
+        std::fs::write(dir.path().join("t1/secret.md"), "t1 only").expect("write");
+
+        let t1 = tenant_content_dir(dir.path(), "t1").expect("t1 dir");
+        assert_eq!(
+            read_file_below(&t1, "secret.md").await.expect("read"),
+            b"t1 only"
+        );
+        let t2 = tenant_content_dir(dir.path(), "t2").expect("t2 dir");
+        assert!(read_file_below(&t2, "secret.md").await.is_err());
+        assert!(read_file_below(&t2, "../t1/secret.md").await.is_err());
+        assert!(tenant_content_dir(dir.path(), "..").is_err());
+        assert!(tenant_content_dir(dir.path(), "t1/../t2").is_err());
+    }
+}
@@ -0,0 +1,242 @@
+use axum::http::HeaderMap;
+use ipnet::IpNet;
+use parking_lot::RwLock;
+use std::collections::HashMap;
+use std::net::IpAddr;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// Env var listing proxies whose `X-Forwarded-For` entries are trusted (comma-separated IPs/CIDRs).
+pub const TRUSTED_PROXIES_ENV: &str = "UNRELATED_GATEWAY_TRUSTED_PROXIES";
+
+/// Parse a single allowlist entry: either a CIDR (`10.0.0.0/8`) or a bare IP (host route).
+fn parse_entry(entry: &str) -> Result<IpNet, String> {
+    let entry = entry.trim();
+    if let Ok(net) = entry.parse::<IpNet>() {
+        return Ok(net.trunc());
+    }
+    entry
+        .parse::<IpAddr>()
+        .map(IpNet::from)
+        .map_err(|_| format!("invalid CIDR or IP address: '{entry}'"))
+}
+
+/// Validate an allowlist as configured by tenants (CIDRs or bare IPs, no duplicates).
+pub fn validate_allowlist(entries: &[String]) -> Result<(), String> {
+    let mut seen = std::collections::HashSet::new();
+    for raw in entries {
+        let net = parse_entry(raw)?;
+        if !seen.insert(net) {
+            return Err(format!("duplicate CIDR entry: '{}'", raw.trim()));
+        }
+    }
+    Ok(())
+}
+
+/// Returns true when `ip` is permitted by `allowlist`.
+///
+/// Semantics: empty list => no allowlist configured (allow all). A configured allowlist fails
+/// closed when the client address is unknown. Invalid entries never match (they are rejected on
+/// write).
+pub fn is_allowed(allowlist: &[String], ip: Option<IpAddr>) -> bool {
+    if allowlist.is_empty() {
+        return true;
+    }
+    let Some(ip) = ip.map(|ip| ip.to_canonical()) else {
+        return false;
+    };
+    allowlist
+        .iter()
+        .filter_map(|e| parse_entry(e).ok())
+        .any(|net| net.contains(&ip))
+}
+
+/// Resolves the effective client address from the TCP peer and `X-Forwarded-For`.
+///
+/// `X-Forwarded-For` is only honored when the peer is a trusted proxy. The chain is walked from
+/// right to left, skipping trusted proxies; the first untrusted hop is the client.
+#[derive(Debug, Clone, Default)]
+pub struct TrustedProxies {
+    nets: Vec<IpNet>,
+}
+
+impl TrustedProxies {
+    /// Load from `UNRELATED_GATEWAY_TRUSTED_PROXIES` (unset/empty => no trusted proxies).
+    pub fn from_env() -> anyhow::Result<Self> {
+        let Ok(raw) = std::env::var(TRUSTED_PROXIES_ENV) else {
+            return Ok(Self::default());
+        };
+        let nets = raw
+            .split(',')
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+            .map(parse_entry)
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| anyhow::anyhow!("{TRUSTED_PROXIES_ENV}: {e}"))?;
+        Ok(Self { nets })
+    }
+
+    fn is_trusted(&self, ip: IpAddr) -> bool {
+        let ip = ip.to_canonical();
+        self.nets.iter().any(|n| n.contains(&ip))
+    }
+
+    pub fn client_ip(&self, peer: IpAddr, headers: &HeaderMap) -> IpAddr {
+        let peer = peer.to_canonical();
+        if !self.is_trusted(peer) {
+            return peer;
+        }
+
+        let hops: Vec<&str> = headers
+            .get_all("x-forwarded-for")
+            .iter()
+            .filter_map(|v| v.to_str().ok())
+            .flat_map(|v| v.split(','))
+            .collect();
+
+        let mut client = peer;
+        for hop in hops.iter().rev() {
+            // An unparsable hop ends the trusted chain: fall back to the last known address.
+            let Ok(hop) = hop.trim().parse::<IpAddr>() else {
+                break;
+            };
+            client = hop.to_canonical();
+            if !self.is_trusted(client) {
+                break;
+            }
+        }
+        client
+    }
+}
+
+#[derive(Debug, Clone)]
+struct CachedAllowlist {
+    expires_at: Instant,
+    cidrs: Arc<Vec<String>>,
+}
+
+/// Tenant IP allowlists, so data-plane requests do not query the store every time. Allowlist
+/// writes invalidate the tenant via `pg_invalidation` (`tenant_ip_allowlist`); the TTL bounds a
+/// missed notification.
+#[derive(Clone)]
+pub struct TenantAllowlistCache {
+    ttl: Duration,
+    inner: Arc<RwLock<HashMap<String, CachedAllowlist>>>,
+}
+
+impl TenantAllowlistCache {
+    #[must_use]
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            inner: Arc::new(RwLock::new(HashMap::new())),
+        }
+    }
+
+    #[must_use]
+    pub fn get(&self, tenant_id: &str) -> Option<Arc<Vec<String>>> {
+        let mut map = self.inner.write();
+        let entry = map.get(tenant_id)?;
+        if entry.expires_at <= Instant::now() {
+            map.remove(tenant_id);
+            return None;
+        }
+        Some(entry.cidrs.clone())
+    }
+
+    pub fn put(&self, tenant_id: &str, cidrs: Arc<Vec<String>>) {
+        let expires_at = Instant::now() + self.ttl;
+        self.inner
+            .write()
+            .insert(tenant_id.to_string(), CachedAllowlist { expires_at, cidrs });
+    }
+
+    /// Best-effort cache invalidation for HA deployments.
+    pub fn invalidate_tenant(&self, tenant_id: &str) {
+        self.inner.write().remove(tenant_id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn list(entries: &[&str]) -> Vec<String> {
+        entries.iter().map(ToString::to_string).collect()
+    }
+
+    fn ip(s: &str) -> IpAddr {
+        s.parse().expect("ip")
+    }
+
+    #[test]
+    fn empty_allowlist_allows_everything() {
+        assert!(is_allowed(&[], Some(ip("203.0.113.7"))));
+        assert!(is_allowed(&[], None));
+    }
+
+    #[test]
+    fn allowlist_matches_cidrs_and_bare_ips() {
+        let allow = list(&["10.0.0.0/8", "2001:db8::/32", "198.51.100.4"]);
+        assert!(is_allowed(&allow, Some(ip("10.1.2.3"))));
+        assert!(is_allowed(&allow, Some(ip("2001:db8::1"))));
+        assert!(is_allowed(&allow, Some(ip("198.51.100.4"))));
+        assert!(is_allowed(&allow, Some(ip("::ffff:10.9.9.9"))));
+        assert!(!is_allowed(&allow, Some(ip("198.51.100.5"))));
+        assert!(!is_allowed(&allow, None));
+    }
+
+    #[test]
+    fn validate_rejects_garbage_and_duplicates() {
+        assert!(validate_allowlist(&list(&["10.0.0.0/8", "::1"])).is_ok());
+        assert!(validate_allowlist(&list(&["10.0.0.0/33"])).is_err());
+        assert!(validate_allowlist(&list(&["not-an-ip"])).is_err());
+        assert!(validate_allowlist(&list(&["10.0.0.1/8", "10.0.0.0/8"])).is_err());
+    }
+
+    #[test]
+    fn forwarded_for_is_only_trusted_from_trusted_proxies() {
+        let proxies = TrustedProxies {
+            nets: vec!["10.0.0.0/8".parse().expect("net")],
+        };
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            "x-forwarded-for",
+            "198.51.100.1, 203.0.113.9, 10.0.0.2"
+                .parse()
+                .expect("header"),
+        );
+
+        // Untrusted peer: header ignored.
+        assert_eq!(
+            proxies.client_ip(ip("192.0.2.1"), &headers),
+            ip("192.0.2.1")
+        );
+        // Trusted peer: rightmost untrusted hop wins (the leftmost entry is client-controlled).
+        assert_eq!(
+            proxies.client_ip(ip("10.0.0.1"), &headers),
+            ip("203.0.113.9")
+        );
+        // Trusted peer without header: peer address.
+        assert_eq!(
+            proxies.client_ip(ip("10.0.0.1"), &HeaderMap::new()),
+            ip("10.0.0.1")
+        );
+    }
+
+    #[test]
+    fn tenant_allowlist_cache_expires_and_invalidates_per_tenant() {
+        let cache = TenantAllowlistCache::new(Duration::from_secs(60));
+        cache.put("t1", Arc::new(list(&["10.0.0.0/8"])));
+        cache.put("t2", Arc::new(Vec::new()));
+        assert_eq!(cache.get("t1").as_deref(), Some(&list(&["10.0.0.0/8"])));
+
+        cache.invalidate_tenant("t1");
+        assert!(cache.get("t1").is_none());
+        assert!(cache.get("t2").is_some());
+
+        let expired = TenantAllowlistCache::new(Duration::ZERO);
+        expired.put("t1", Arc::new(Vec::new()));
+        assert!(expired.get("t1").is_none());
+    }
+}
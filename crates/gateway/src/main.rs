@@ -14,26 +14,37 @@ mod audit;
 mod audit_retention;
 mod catalog;
 mod config;
+mod content_source;
 mod contracts;
 mod endpoint_cache;
+mod ip_allowlist;
 mod mcp;
 mod oidc;
+mod oidc_rules;
 mod outbound_safety;
 mod pg_fanout;
 mod pg_invalidation;
 mod pg_store;
 mod profile_http;
+mod protected_resource;
+mod response_pipeline_cache;
 mod secrets_crypto;
 mod serde_helpers;
+mod session_registry;
 mod session_token;
+mod sse_log;
 mod store;
 mod tenant;
 mod tenant_catalog;
+mod tenant_oidc;
 mod tenant_token;
 mod timeouts;
 mod tool_policy;
+mod tool_search;
 mod tools_cache;
 mod transport_limits;
+mod upstream_oauth;
+mod workflow;
 
 const VERSION: &str = env!("CARGO_PKG_VERSION");
 const LICENSE: &str = env!("CARGO_PKG_LICENSE");
@@ -135,9 +146,25 @@ async fn run(args: CliArgs) -> anyhow::Result<()> {
 
     audit_retention::spawn_audit_retention_task(pg_pool.clone(), ct.clone());
 
+    let sse_log: Arc<dyn sse_log::SseEventLog> = match pg_pool.clone() {
+        Some(pool) => Arc::new(sse_log::PgSseEventLog::new(pool)),
+        None => Arc::new(sse_log::MemorySseEventLog::new()),
+    };
+    sse_log::spawn_cleanup_task(sse_log.clone(), ct.clone());
+
+    let sessions = Arc::new(match pg_pool.clone() {
+        Some(pool) => session_registry::SessionRegistry::new(Arc::new(
+            session_registry::PgSessionStore::new(pool),
+        )),
+        None => session_registry::SessionRegistry::in_memory(),
+    });
+    session_registry::spawn_cleanup_task(sessions.clone(), ct.clone());
+
     let http = build_no_redirect_http_client("upstream HTTP client")?;
     let oidc_http = build_no_redirect_http_client("OIDC HTTP client")?;
-    let oidc = oidc::OidcValidator::from_env(oidc_http).await?;
+    let oidc = oidc::OidcValidator::from_env(oidc_http.clone()).await?;
+    let upstream_oauth = Arc::new(upstream_oauth::UpstreamOAuthManager::new(oidc_http.clone()));
+    let tenant_oidc = Arc::new(tenant_oidc::TenantOidcValidators::new(oidc_http));
     let oidc_issuer = oidc.as_ref().map(|o| o.issuer().to_string());
 
     let mcp_state = Arc::new(mcp::McpState {
@@ -145,7 +172,7 @@ async fn run(args: CliArgs) -> anyhow::Result<()> {
         signer: session_token::SessionSigner::new(session_secrets.clone(), session_ttl)
             .context("init session token signer")?,
         http,
-        oidc,
+        oidc: oidc.clone(),
         shutdown: ct.clone(),
         audit: audit.clone(),
         catalog,
@@ -156,9 +183,29 @@ async fn run(args: CliArgs) -> anyhow::Result<()> {
         endpoint_cache: Arc::new(endpoint_cache::UpstreamEndpointCache::new(
             Duration::from_secs(30),
         )),
+        oidc_rules_cache: Arc::new(oidc_rules::OidcClaimRulesCache::new(Duration::from_secs(
+            30,
+        ))),
+        tenant_allowlist_cache: Arc::new(ip_allowlist::TenantAllowlistCache::new(
+            Duration::from_secs(30),
+        )),
+        response_pipelines: Arc::new(response_pipeline_cache::ResponsePipelineCache::new()),
+        trusted_proxies: Arc::new(ip_allowlist::TrustedProxies::from_env()?),
+        tenant_oidc: tenant_oidc.clone(),
+        public_base_url: protected_resource::public_base_url_from_env()?,
+        upstream_oauth,
+        sse_log,
+        sessions,
     });
 
-    let invalidation = build_invalidation_dispatcher(pg_pool.clone(), &mcp_state);
+    let tenant_token_revocations = Arc::new(tenant_token::TenantTokenRevocations::new(
+        Duration::from_secs(30),
+    ));
+    let invalidation = build_invalidation_dispatcher(
+        pg_pool.clone(),
+        &mcp_state,
+        tenant_token_revocations.clone(),
+    );
 
     wire_store_invalidation_publisher(pg_store, invalidation.clone());
 
@@ -172,13 +219,17 @@ async fn run(args: CliArgs) -> anyhow::Result<()> {
         tenant_signer: tenant_token::TenantSigner::new(session_secrets[0].clone()),
         shared_source_ids: shared_source_ids.clone(),
         oidc_issuer: oidc_issuer.clone(),
+        oidc,
+        tenant_oidc,
         audit: audit.clone(),
         invalidation: invalidation.clone(),
+        mcp_state: mcp_state.clone(),
     });
 
     let tenant_state = Arc::new(tenant::TenantState {
         store: admin_state.store.clone(),
         signer: tenant_token::TenantSigner::new(session_secrets[0].clone()),
+        revocations: tenant_token_revocations,
         shared_source_ids,
         mcp_state: mcp_state.clone(),
         audit,
@@ -298,14 +349,22 @@ fn wire_store_invalidation_publisher(
 fn build_invalidation_dispatcher(
     pg_pool: Option<sqlx::PgPool>,
     mcp_state: &Arc<mcp::McpState>,
+    tenant_token_revocations: Arc<tenant_token::TenantTokenRevocations>,
 ) -> Arc<pg_invalidation::InvalidationDispatcher> {
-    Arc::new(pg_invalidation::InvalidationDispatcher::new(
-        pg_pool,
-        mcp_state.tenant_catalog.clone(),
-        mcp_state.tools_cache.clone(),
-        mcp_state.endpoint_cache.clone(),
-        mcp_state.audit.clone(),
-    ))
+    Arc::new(
+        pg_invalidation::InvalidationDispatcher::new(
+            pg_pool,
+            mcp_state.tenant_catalog.clone(),
+            mcp_state.tools_cache.clone(),
+            mcp_state.endpoint_cache.clone(),
+            mcp_state.audit.clone(),
+            tenant_token_revocations,
+            mcp_state.sessions.clone(),
+        )
+        .with_oidc_rules_cache(mcp_state.oidc_rules_cache.clone())
+        .with_tenant_allowlist_cache(mcp_state.tenant_allowlist_cache.clone())
+        .with_tenant_oidc(mcp_state.tenant_oidc.clone()),
+    )
 }
 
 fn start_tool_contract_invalidator(mcp_state: &Arc<mcp::McpState>, ct: CancellationToken) {
@@ -414,7 +473,12 @@ async fn serve_servers(
     admin_app: Router,
 ) -> anyhow::Result<()> {
     let data_ct = ct.clone();
-    let data_server = axum::serve(data_listener, data_app).with_graceful_shutdown(async move {
+    // Connect info provides the peer address for client IP allowlists.
+    let data_server = axum::serve(
+        data_listener,
+        data_app.into_make_service_with_connect_info::<std::net::SocketAddr>(),
+    )
+    .with_graceful_shutdown(async move {
         data_ct.cancelled().await;
     });
 
@@ -467,6 +531,15 @@ fn validate_config(cfg: &config::GatewayConfig) -> anyhow::Result<()> {
         if let Some(tools) = &p.tools {
             validate_tool_allowlist(profile_id, tools)?;
         }
+        profile_http::validate_transforms(&p.transforms)
+            .and_then(|()| {
+                profile_http::validate_tool_timeout_and_policies(
+                    p.tool_call_timeout_secs,
+                    &p.tool_policies,
+                )
+            })
+            .and_then(|()| profile_http::validate_mcp_settings(&p.mcp))
+            .map_err(|e| anyhow::anyhow!("profiles.{profile_id}: {e}"))?;
     }
     Ok(())
 }
@@ -4,7 +4,7 @@ use crate::contracts::ContractTracker;
 use crate::oidc::OidcValidator;
 use crate::session_token::{
     SessionSigner, SessionTokenVerifyError, SessionTokenVerifyErrorKind, TokenAuthV1, TokenOidcV1,
-    TokenPayloadV1, UpstreamSessionBinding,
+    TokenPayloadV1, UpstreamOAuthBinding, UpstreamSessionBinding,
 };
 use crate::store::{
     DataPlaneAuthMode, EffectiveMcpCapabilities, RequestIdNamespacing, SseEventIdNamespacing,
@@ -17,7 +17,7 @@ use crate::{
 };
 use axum::{
     body::Bytes,
-    extract::{DefaultBodyLimit, Path, State},
+    extract::{ConnectInfo, DefaultBodyLimit, Path, State},
     http::{HeaderMap, HeaderValue, StatusCode},
     response::{IntoResponse, Response, Sse},
 };
@@ -36,9 +36,10 @@ use rmcp::{
     },
 };
 use serde::Serialize;
-use std::{collections::HashMap, convert::Infallible, sync::Arc};
+use std::{collections::HashMap, convert::Infallible, net::IpAddr, net::SocketAddr, sync::Arc};
 use tokio_util::sync::CancellationToken;
 use tracing::Instrument as _;
+use unrelated_http_tools::config::AuthConfig;
 use uuid::{Uuid, Version};
 
 mod auth;
@@ -50,7 +51,7 @@ mod tool_call;
 mod upstream;
 use auth::{
     authenticate_api_key_on_initialize, authorize_jwt_request, enforce_data_plane_auth,
-    unauthorized,
+    enforce_ip_allowlists, get_protected_resource_metadata, unauthorized,
 };
 use ids::{make_proxied_request_id, parse_proxied_request_id, resource_collision_urn};
 use surface::{
@@ -175,6 +176,34 @@ pub struct McpState {
     pub contract_fanout: Option<Arc<PgContractFanout>>,
     pub tools_cache: Arc<crate::tools_cache::ToolSurfaceCache>,
     pub endpoint_cache: Arc<crate::endpoint_cache::UpstreamEndpointCache>,
+    /// Enabled OIDC claim rules per tenant + issuer (invalidated on rule writes).
+    pub oidc_rules_cache: Arc<crate::oidc_rules::OidcClaimRulesCache>,
+    pub tenant_allowlist_cache: Arc<crate::ip_allowlist::TenantAllowlistCache>,
+    /// Compiled `mcp.responseTransforms` pipelines (keyed by config contents).
+    pub response_pipelines: Arc<crate::response_pipeline_cache::ResponsePipelineCache>,
+    /// Proxies whose `X-Forwarded-For` is honored when resolving client IPs for allowlists.
+    pub trusted_proxies: Arc<crate::ip_allowlist::TrustedProxies>,
+    /// Validators for tenant-configured OIDC issuers (Mode 3), keyed by tenant + issuer.
+    pub tenant_oidc: Arc<crate::tenant_oidc::TenantOidcValidators>,
+    /// Externally visible data-plane base URL; when set, JWT audiences are bound to profile URLs.
+    pub public_base_url: Option<String>,
+    /// OAuth client runtime for upstreams that require per-principal account linking (Mode 3).
+    pub upstream_oauth: Arc<crate::upstream_oauth::UpstreamOAuthManager>,
+    /// Per-session SSE event log for `Last-Event-ID` replay (`mcp.sseReplay`).
+    pub sse_log: Arc<dyn crate::sse_log::SseEventLog>,
+    /// Live session registry (list/terminate).
+    pub sessions: Arc<crate::session_registry::SessionRegistry>,
+}
+
+/// Effective client IP (peer address, or a trusted `X-Forwarded-For` hop).
+///
+/// `None` when the server was not started with connect info (e.g. in-process tests).
+fn resolve_client_ip(
+    state: &McpState,
+    connect_info: Option<&ConnectInfo<SocketAddr>>,
+    headers: &HeaderMap,
+) -> Option<IpAddr> {
+    connect_info.map(|ci| state.trusted_proxies.client_ip(ci.0.ip(), headers))
 }
 
 pub fn router(state: Arc<McpState>) -> axum::Router {
@@ -185,6 +214,21 @@ pub fn router(state: Arc<McpState>) -> axum::Router {
                 .get(get_mcp)
                 .delete(delete_mcp),
         )
+        .route(
+            &format!(
+                "{}/{{profile_id}}/mcp",
+                crate::protected_resource::WELL_KNOWN_PREFIX
+            ),
+            axum::routing::get(get_protected_resource_metadata),
+        )
+        .route(
+            crate::upstream_oauth::START_PATH,
+            axum::routing::get(crate::upstream_oauth::start),
+        )
+        .route(
+            crate::upstream_oauth::CALLBACK_PATH,
+            axum::routing::get(crate::upstream_oauth::callback),
+        )
         // Hard cap to protect the process from unbounded request bodies.
         .layer(DefaultBodyLimit::max(
             usize::try_from(crate::transport_limits::HARD_MAX_POST_BODY_BYTES)
@@ -210,6 +254,7 @@ pub struct ProfileSurfaceSource {
 async fn post_mcp(
     Path(profile_id): Path<String>,
     State(state): State<Arc<McpState>>,
+    connect_info: Option<axum::Extension<ConnectInfo<SocketAddr>>>,
     headers: HeaderMap,
     body: Bytes,
 ) -> Result<Response, Response> {
@@ -234,6 +279,7 @@ async fn post_mcp(
         .get(HEADER_SESSION_ID)
         .and_then(|v| v.to_str().ok())
         .map(str::to_string);
+    let client_ip = resolve_client_ip(&state, connect_info.as_deref(), &headers);
 
     let span = tracing::info_span!(
         "gateway.mcp.post",
@@ -244,12 +290,13 @@ async fn post_mcp(
     Box::pin(
         async move {
             match session_header {
-                None => handle_initialize(&state, &profile_id, &headers, message).await,
+                None => handle_initialize(&state, &profile_id, &headers, client_ip, message).await,
                 Some(token) => {
                     Box::pin(handle_post_in_session(
                         &state,
                         &profile_id,
                         &headers,
+                        client_ip,
                         token,
                         message,
                     ))
@@ -470,6 +517,7 @@ fn parse_client_jsonrpc_message(
 async fn get_mcp(
     Path(profile_id): Path<String>,
     State(state): State<Arc<McpState>>,
+    connect_info: Option<axum::Extension<ConnectInfo<SocketAddr>>>,
     headers: HeaderMap,
 ) -> Result<Response, Response> {
     if Uuid::parse_str(&profile_id)
@@ -499,15 +547,28 @@ async fn get_mcp(
         .and_then(|v| v.to_str().ok())
         .map(str::to_string);
 
+    let client_ip = resolve_client_ip(&state, connect_info.as_deref(), &headers);
+
     let span = tracing::info_span!("gateway.mcp.get", profile_id = %profile_id);
-    async move { handle_get_stream(&state, &profile_id, &headers, token, last_event_id).await }
-        .instrument(span)
+    async move {
+        handle_get_stream(
+            &state,
+            &profile_id,
+            &headers,
+            client_ip,
+            token,
+            last_event_id,
+        )
         .await
+    }
+    .instrument(span)
+    .await
 }
 
 async fn delete_mcp(
     Path(profile_id): Path<String>,
     State(state): State<Arc<McpState>>,
+    connect_info: Option<axum::Extension<ConnectInfo<SocketAddr>>>,
     headers: HeaderMap,
 ) -> Result<Response, Response> {
     if Uuid::parse_str(&profile_id)
@@ -530,8 +591,10 @@ async fn delete_mcp(
         })?
         .to_string();
 
+    let client_ip = resolve_client_ip(&state, connect_info.as_deref(), &headers);
+
     let span = tracing::info_span!("gateway.mcp.delete", profile_id = %profile_id);
-    async move { handle_delete(&state, &profile_id, &headers, token).await }
+    async move { handle_delete(&state, &profile_id, &headers, client_ip, token).await }
         .instrument(span)
         .await
 }
@@ -540,6 +603,7 @@ async fn handle_initialize(
     state: &McpState,
     profile_id: &str,
     headers: &HeaderMap,
+    client_ip: Option<IpAddr>,
     message: ClientJsonRpcMessage,
 ) -> Result<Response, Response> {
     let (req_id, protocol_version) =
@@ -565,6 +629,7 @@ async fn handle_initialize(
                 Some(authorize_jwt_request(state, &profile, headers).await?),
             ),
         };
+    enforce_ip_allowlists(state, &profile, client_ip, auth.as_ref(), "POST").await?;
 
     tracing::info!(
         profile_id = %profile.id,
@@ -572,8 +637,15 @@ async fn handle_initialize(
         "initialize profile session"
     );
 
-    let (bindings, warnings) =
-        initialize_profile_sources(state, &profile, &message, parse_hop(headers)).await?;
+    let principal = crate::upstream_oauth::principal_key(auth.as_ref(), oidc.as_ref());
+    let (bindings, warnings) = initialize_profile_sources(
+        state,
+        &profile,
+        &message,
+        parse_hop(headers),
+        principal.as_deref(),
+    )
+    .await?;
 
     let mut local_sources: usize = 0;
     for id in &profile.source_ids {
@@ -626,6 +698,22 @@ async fn handle_initialize(
         None
     };
 
+    let sid = crate::session_registry::new_session_id()
+        .map_err(internal_error_response("mint session id"))?;
+    let now = crate::session_registry::now_unix_secs();
+    let record = crate::session_registry::SessionRecord {
+        session_id: sid.clone(),
+        tenant_id: profile.tenant_id.clone(),
+        profile_id: profile.id.clone(),
+        principal,
+        bindings: bindings.clone(),
+        created_at_unix: now,
+        last_seen_at_unix: now,
+        expires_at_unix: now.saturating_add(state.signer.ttl().as_secs()),
+        open_streams: 0,
+        terminated: false,
+    };
+
     let token_payload = TokenPayloadV1 {
         profile_id: profile.id,
         bindings,
@@ -634,11 +722,13 @@ async fn handle_initialize(
         iat: None,
         exp: None,
         proxy_key,
+        sid: Some(sid),
     };
     let token = state
         .signer
         .sign(token_payload)
         .map_err(internal_error_response("sign session token"))?;
+    state.sessions.register(&record).await;
 
     Ok(sse_single_message_with_session_id(
         &response_message,
@@ -683,6 +773,7 @@ async fn initialize_profile_sources(
     profile: &crate::store::Profile,
     init_message: &ClientJsonRpcMessage,
     hop: u32,
+    principal: Option<&str>,
 ) -> Result<(Vec<UpstreamSessionBinding>, Vec<String>), Response> {
     let mut bindings = Vec::<UpstreamSessionBinding>::new();
     let mut warnings = Vec::<String>::new();
@@ -720,6 +811,56 @@ async fn initialize_profile_sources(
             continue;
         }
 
+        // Upstreams with a tenant OAuth client get the caller's linked token instead of the
+        // endpoint's static auth.
+        let oauth_client = state
+            .store
+            .get_upstream_oauth_client(&profile.tenant_id, upstream_id)
+            .await
+            .map_err(internal_error_response("load upstream OAuth client"))?
+            .filter(|c| c.enabled);
+        let mut oauth: Option<(UpstreamOAuthBinding, AuthConfig)> = None;
+        if oauth_client.is_some() {
+            let Some(principal) = principal else {
+                warnings.push(format!(
+                    "Upstream '{upstream_id}' requires account linking, which needs an authenticated profile"
+                ));
+                continue;
+            };
+            match state
+                .upstream_oauth
+                .access_token(
+                    state.store.as_ref(),
+                    &profile.tenant_id,
+                    upstream_id,
+                    principal,
+                )
+                .await
+            {
+                Ok(Some(token)) => {
+                    oauth = Some((
+                        UpstreamOAuthBinding {
+                            tenant_id: profile.tenant_id.clone(),
+                            principal: principal.to_string(),
+                        },
+                        AuthConfig::Bearer { token },
+                    ));
+                }
+                Ok(None) => {
+                    warnings.push(format!(
+                        "Upstream '{upstream_id}' requires account linking; ask a tenant admin for a link URL"
+                    ));
+                    continue;
+                }
+                Err(e) => {
+                    warnings.push(format!(
+                        "Upstream '{upstream_id}' OAuth token unavailable: {e}"
+                    ));
+                    continue;
+                }
+            }
+        }
+
         let upstream_policy = profile.mcp.security.effective_upstream_policy(upstream_id);
         let upstream_init_message =
             upstream::rewrite_upstream_initialize_message(init_message, &upstream_policy);
@@ -731,8 +872,9 @@ async fn initialize_profile_sources(
         let mut initialized: Option<(String, String)> = None; // (endpoint_id, session_id)
         for i in 0..upstream.endpoints.len() {
             let ep = &upstream.endpoints[(start + i) % upstream.endpoints.len()];
-            let headers = upstream::build_upstream_headers(ep.auth.as_ref(), hop + 1);
-            let endpoint_url = upstream::apply_query_auth(&ep.url, ep.auth.as_ref());
+            let auth = oauth.as_ref().map(|(_, a)| a).or(ep.auth.as_ref());
+            let headers = upstream::build_upstream_headers(auth, hop + 1);
+            let endpoint_url = upstream::apply_query_auth(&ep.url, auth);
             match upstream_initialize(&state.http, &endpoint_url, &upstream_init_message, &headers)
                 .await
             {
@@ -749,6 +891,7 @@ async fn initialize_profile_sources(
                 upstream: upstream_id.clone(),
                 endpoint: endpoint_id,
                 session: upstream_session_id,
+                oauth: oauth.map(|(b, _)| b),
             });
         } else if let Some(e) = last_err {
             warnings.push(format!("Upstream '{upstream_id}' initialize failed: {e}"));
@@ -863,6 +1006,64 @@ fn verify_session_token(
     Ok(payload)
 }
 
+/// Reject sessions terminated via the session API (404 per the MCP session rules, so clients
+/// re-initialize).
+async fn ensure_session_live(state: &McpState, payload: &TokenPayloadV1) -> Result<(), Response> {
+    if let Some(sid) = payload.sid.as_deref()
+        && !state.sessions.check_live(sid).await
+    {
+        return Err((
+            StatusCode::NOT_FOUND,
+            "Session terminated; re-initialize required",
+        )
+            .into_response());
+    }
+    Ok(())
+}
+
+/// Terminate a live session: reject its token everywhere, close its SSE streams on this replica
+/// and delete its upstream sessions (best-effort). Other replicas are notified by the caller.
+pub(crate) async fn terminate_session(
+    state: &McpState,
+    session_id: &str,
+) -> anyhow::Result<Option<crate::session_registry::SessionRecord>> {
+    let Some(record) = state.sessions.terminate(session_id).await? else {
+        return Ok(None);
+    };
+    if delete_upstream_sessions(state, &record.profile_id, &record.bindings, 0)
+        .await
+        .is_err()
+    {
+        tracing::warn!(session_id, "failed to delete some upstream sessions");
+    }
+    Ok(Some(record))
+}
+
+async fn delete_upstream_sessions(
+    state: &McpState,
+    profile_id: &str,
+    bindings: &[UpstreamSessionBinding],
+    hop: u32,
+) -> Result<(), Response> {
+    for binding in bindings {
+        if let Some(endpoint) = upstream::resolve_endpoint(state, profile_id, binding).await? {
+            if hop >= upstream::MAX_HOPS {
+                continue;
+            }
+            let endpoint_url = upstream::apply_query_auth(&endpoint.url, endpoint.auth.as_ref());
+            let headers = upstream::build_upstream_headers(endpoint.auth.as_ref(), hop + 1);
+            let _ = streamable_http::delete_session(
+                &state.http,
+                endpoint_url.into(),
+                binding.session.clone().into(),
+                &headers,
+            )
+            .await;
+        }
+    }
+    Ok(())
+}
+
 async fn load_profile_or_404(
     state: &McpState,
     profile_id: &str,
@@ -1056,6 +1257,23 @@ async fn handle_tools_call_in_session(
     message: &mut ClientJsonRpcMessage,
     req_id: &RequestId,
 ) -> Result<Response, Response> {
+    // Tool search meta-tools: search/describe are answered here (they are not tool calls for
+    // limits); `call_tool` is unwrapped into a call of the target tool.
+    if ctx.profile.mcp.tool_search.enabled
+        && let Some(resp) = tool_call::handle_tool_search_meta_call(
+            ctx.state,
+            ctx.profile_id,
+            ctx.profile,
+            ctx.payload,
+            &token,
+            message,
+            ctx.hop,
+        )
+        .await?
+    {
+        return Ok(resp);
+    }
+
     if let Some(auth) = ctx.payload.auth.as_ref() {
         ctx.state
             .store
@@ -1216,6 +1434,7 @@ async fn handle_resource_subscription_in_session(
             route_and_proxy_resource_subscribe(
                 ctx.state,
                 ctx.profile_id,
+                ctx.profile,
                 ctx.payload,
                 token,
                 message,
@@ -1227,6 +1446,7 @@ async fn handle_resource_subscription_in_session(
             route_and_proxy_resource_unsubscribe(
                 ctx.state,
                 ctx.profile_id,
+                ctx.profile,
                 ctx.payload,
                 token,
                 message,
@@ -1278,7 +1498,9 @@ async fn handle_post_in_session_request(
             ))
             .await
         }
-        "resources/list" => aggregate_list_resources(state, profile_id, payload, req_id, hop).await,
+        "resources/list" => {
+            aggregate_list_resources(state, profile_id, profile, payload, req_id, hop).await
+        }
         "resources/subscribe" => {
             handle_resource_subscription_in_session(
                 ctx,
@@ -1299,7 +1521,9 @@ async fn handle_post_in_session_request(
             )
             .await
         }
-        "prompts/list" => aggregate_list_prompts(state, profile_id, payload, req_id, hop).await,
+        "prompts/list" => {
+            aggregate_list_prompts(state, profile_id, profile, payload, req_id, hop).await
+        }
         "completion/complete" => {
             if !effective_caps(profile).completions() {
                 return Err(jsonrpc_error_response(
@@ -1308,15 +1532,19 @@ async fn handle_post_in_session_request(
                     "completions are disabled by profile MCP capability policy".to_string(),
                 ));
             }
-            route_and_proxy_completion_complete(state, profile_id, payload, token, message, hop)
-                .await
+            route_and_proxy_completion_complete(
+                state, profile_id, profile, payload, token, message, hop,
+            )
+            .await
         }
         "tools/call" => handle_tools_call_in_session(ctx, token, message, &req_id).await,
         "resources/read" => {
-            route_and_proxy_resource_read(state, profile_id, payload, token, message, hop).await
+            route_and_proxy_resource_read(state, profile_id, profile, payload, token, message, hop)
+                .await
         }
         "prompts/get" => {
-            route_and_proxy_prompt_get(state, profile_id, payload, token, message, hop).await
+            route_and_proxy_prompt_get(state, profile_id, profile, payload, token, message, hop)
+                .await
         }
         "ping" => {
             let msg = ServerJsonRpcMessage::Response(JsonRpcResponse {
@@ -1338,13 +1566,15 @@ async fn handle_post_in_session(
     state: &McpState,
     profile_id: &str,
     headers: &HeaderMap,
+    client_ip: Option<IpAddr>,
     token: String,
     mut message: ClientJsonRpcMessage,
 ) -> Result<Response, Response> {
     let hop = parse_hop(headers);
     let payload = verify_session_token(&state.signer, &token, profile_id)
         .map_err(|(s, m)| (s, m).into_response())?;
-    let profile = load_profile_or_404(state, profile_id).await?;
+    ensure_session_live(state, &payload).await?;
+    let mut profile = load_profile_or_404(state, profile_id).await?;
     enforce_data_plane_auth(
         state,
         &profile,
@@ -1353,6 +1583,10 @@ async fn handle_post_in_session(
         payload.oidc.as_ref(),
     )
     .await?;
+    enforce_ip_allowlists(state, &profile, client_ip, payload.auth.as_ref(), "POST").await?;
+    if let Some(auth) = &payload.auth {
+        profile.api_key_tool_allowlist.clone_from(&auth.tools);
+    }
 
     if let Some(resp) =
         forward_proxied_response_if_any(state, profile_id, &payload, &mut message, hop).await?
@@ -1382,12 +1616,14 @@ async fn handle_get_stream(
     state: &McpState,
     profile_id: &str,
     headers: &HeaderMap,
+    client_ip: Option<IpAddr>,
     token: String,
     last_event_id: Option<String>,
 ) -> Result<Response, Response> {
     let send_priming = last_event_id.is_none();
     let payload = verify_session_token(&state.signer, &token, profile_id)
         .map_err(|(s, m)| (s, m).into_response())?;
+    ensure_session_live(state, &payload).await?;
 
     let profile = state
         .store
@@ -1404,6 +1640,7 @@ async fn handle_get_stream(
         payload.oidc.as_ref(),
     )
     .await?;
+    enforce_ip_allowlists(state, &profile, client_ip, payload.auth.as_ref(), "GET").await?;
 
     let tenant_limits = match state
         .store
@@ -1423,18 +1660,48 @@ async fn handle_get_stream(
     let limits_shutdown = CancellationToken::new();
 
     // Parse Last-Event-ID:
+    // - If it is a gateway id (`gw.<seq>`, `mcp.sseReplay` only), replay the session event log
+    //   after it and resume each source from its last logged position.
     // - If it looks like an upstream-prefixed id (`<upstream>/<id...>`), resume only that upstream.
     // - If it is numeric, treat it as the durable contract event cursor and do not forward to upstreams.
-    let last = parse_last_event_id(
+    let replay_settings = profile.mcp.sse_replay;
+    let event_log = replay_settings.enabled.then(|| {
+        Arc::new(crate::sse_log::SessionEventLog::new(
+            state.sse_log.clone(),
+            &token,
+            replay_settings,
+        ))
+    });
+    let mut last = parse_last_event_id(
         profile.mcp.namespacing.sse_event_id,
+        replay_settings.enabled,
         last_event_id.as_deref(),
     );
+    let mut replayed = Vec::new();
+    if let (Some(log), Some(after)) = (event_log.as_ref(), last.buffer_after) {
+        match log.resume(after).await {
+            Ok(plan) => {
+                replayed = plan.replay;
+                last.resume_upstreams = plan.upstream_cursors;
+                last.contract_after_id = plan.contract_after;
+            }
+            Err(e) => {
+                tracing::warn!(error = %e, profile_id, "failed to load SSE event log for replay");
+            }
+        }
+    }
 
     // Stored behind an `RwLock` so we can refresh collision counts later without rewiring the
     // upstream SSE stream closures (counts can change when resources are added/removed upstream).
     let collision_counts = Arc::new(parking_lot::RwLock::new(
-        match compute_resource_collision_counts(state, profile_id, &payload, parse_hop(headers))
-            .await
+        match compute_resource_collision_counts(
+            state,
+            profile_id,
+            &profile,
+            &payload,
+            parse_hop(headers),
+        )
+        .await
         {
             Ok(m) => m,
             Err(e) => {
@@ -1448,7 +1715,11 @@ async fn handle_get_stream(
         futures::stream::BoxStream<'static, Result<axum::response::sse::Event, Infallible>>,
     > = Vec::new();
     if send_priming {
-        streams.push(priming_stream());
+        streams.push(priming_stream(if replay_settings.enabled {
+            crate::sse_log::format_event_id(0)
+        } else {
+            "0".to_string()
+        }));
     }
 
     let proxy_key = decode_proxy_key(&payload).map(|v| Arc::from(v.into_boxed_slice()));
@@ -1463,12 +1734,19 @@ async fn handle_get_stream(
             hop: parse_hop(headers),
             limits,
             limits_shutdown: limits_shutdown.clone(),
+            event_log: event_log.clone(),
         })
         .await?,
     );
 
-    if let Some(replay) =
-        contract_replay_stream(state, &profile, profile_id, last.contract_after_id).await
+    if let Some(replay) = contract_replay_stream(
+        state,
+        &profile,
+        profile_id,
+        last.contract_after_id,
+        event_log.clone(),
+    )
+    .await
     {
         streams.push(replay);
     }
@@ -1476,16 +1754,34 @@ async fn handle_get_stream(
     streams.push(contract_notifications_stream(
         &profile,
         state.contracts.subscribe(profile_id),
+        event_log,
     ));
 
-    let merged = futures::stream::select_all(streams);
+    // Logged events the client missed go out first, in their original order.
+    let replayed = futures::stream::iter(replayed.into_iter().map(|evt| {
+        Ok::<_, Infallible>(
+            axum::response::sse::Event::default()
+                .id(crate::sse_log::format_event_id(evt.seq))
+                .data(evt.data),
+        )
+    }));
+    let merged = replayed.chain(futures::stream::select_all(streams));
     // Ensure long-lived streams don't prevent shutdown (e.g. docker stop / SIGTERM),
-    // and close the SSE stream on transport limit violations.
+    // and close the SSE stream on transport limit violations or session termination.
     let shutdown = state.shutdown.clone();
+    let session_stream = match payload.sid.as_deref() {
+        Some(sid) => Some(state.sessions.open_stream(sid).await),
+        None => None,
+    };
     let merged = merged.take_until(async move {
+        let terminated = session_stream.as_ref().map_or_else(
+            CancellationToken::new,
+            crate::session_registry::StreamGuard::token,
+        );
         tokio::select! {
             () = shutdown.cancelled() => {},
             () = limits_shutdown.cancelled() => {},
+            () = terminated.cancelled() => {},
         }
     });
     let mut resp = Sse::new(merged).into_response();
@@ -1498,27 +1794,35 @@ async fn handle_get_stream(
 
 #[derive(Debug, Clone, Default)]
 struct ParsedLastEventId {
-    resume_upstream: Option<String>,
-    resume_upstream_event_id: Option<String>,
+    /// Upstream event id to resume from, per upstream.
+    resume_upstreams: HashMap<String, String>,
     contract_after_id: Option<u64>,
+    /// Gateway event log cursor (`gw.<seq>`).
+    buffer_after: Option<i64>,
 }
 
 fn parse_last_event_id(
     ns: SseEventIdNamespacing,
+    replay_enabled: bool,
     last_event_id: Option<&str>,
 ) -> ParsedLastEventId {
     let Some(id) = last_event_id else {
         return ParsedLastEventId::default();
     };
+    if replay_enabled && let Some(seq) = crate::sse_log::parse_event_id(id) {
+        return ParsedLastEventId {
+            buffer_after: Some(seq),
+            ..Default::default()
+        };
+    }
     if matches!(ns, SseEventIdNamespacing::UpstreamSlash)
         && let Some((upstream, rest)) = id.split_once('/')
         && !upstream.is_empty()
         && !rest.is_empty()
     {
         return ParsedLastEventId {
-            resume_upstream: Some(upstream.to_string()),
-            resume_upstream_event_id: Some(rest.to_string()),
-            contract_after_id: None,
+            resume_upstreams: HashMap::from([(upstream.to_string(), rest.to_string())]),
+            ..Default::default()
         };
     }
     ParsedLastEventId {
@@ -1683,6 +1987,7 @@ struct UpstreamSseMapCtx {
     limits: crate::transport_limits::EffectiveTransportLimits,
     limits_shutdown: CancellationToken,
     audit: Arc<dyn AuditSink>,
+    event_log: Option<Arc<crate::sse_log::SessionEventLog>>,
 }
 
 async fn maybe_block_upstream_server_request(ctx: &UpstreamSseMapCtx, data: &str) -> bool {
@@ -1797,6 +2102,7 @@ async fn map_upstream_sse_event(
 ) -> Option<Result<axum::response::sse::Event, Infallible>> {
     match evt {
         Ok(mut sse) => {
+            let upstream_event_id = sse.id.clone();
             if let Some(id) = sse.id.take() {
                 sse.id = Some(namespace_sse_event_id(ctx.ns_evt, &ctx.upstream_id, id));
             }
@@ -1839,6 +2145,23 @@ async fn map_upstream_sse_event(
                 }
             }
 
+            // Log after rewriting so replays match what the client would have seen. Events with
+            // neither data nor an id carry nothing worth replaying.
+            if let Some(log) = ctx.event_log.as_ref()
+                && (upstream_event_id.is_some() || sse.data.is_some())
+                && let Some(id) = log
+                    .record(
+                        crate::sse_log::EventOrigin::Upstream {
+                            upstream_id: ctx.upstream_id.to_string(),
+                            event_id: upstream_event_id,
+                        },
+                        sse.data.as_deref().unwrap_or_default(),
+                    )
+                    .await
+            {
+                sse.id = Some(id);
+            }
+
             let mut ev = axum::response::sse::Event::default();
             if let Some(id) = sse.id {
                 ev = ev.id(id);
@@ -1867,6 +2190,7 @@ struct OpenUpstreamStreamsInputs<'a> {
     hop: u32,
     limits: crate::transport_limits::EffectiveTransportLimits,
     limits_shutdown: CancellationToken,
+    event_log: Option<Arc<crate::sse_log::SessionEventLog>>,
 }
 
 async fn open_upstream_streams(
@@ -1885,6 +2209,7 @@ async fn open_upstream_streams(
         hop,
         limits,
         limits_shutdown,
+        event_log,
     } = inputs;
     let mut streams: Vec<
         futures::stream::BoxStream<'static, Result<axum::response::sse::Event, Infallible>>,
@@ -1911,11 +2236,7 @@ async fn open_upstream_streams(
             .effective_upstream_policy(binding.upstream.as_str());
         let server_requests_filter = upstream_policy.server_requests.clone();
 
-        let upstream_last = if last.resume_upstream.as_deref() == Some(binding.upstream.as_str()) {
-            last.resume_upstream_event_id.clone()
-        } else {
-            None
-        };
+        let upstream_last = last.resume_upstreams.get(&binding.upstream).cloned();
 
         let upstream = streamable_http::get_stream(
             &state.http,
@@ -1951,6 +2272,7 @@ async fn open_upstream_streams(
             limits,
             limits_shutdown: limits_shutdown.clone(),
             audit: state.audit.clone(),
+            event_log: event_log.clone(),
         });
 
         let mapped = upstream.filter_map(move |evt| {
@@ -1966,11 +2288,12 @@ async fn open_upstream_streams(
 async fn compute_resource_collision_counts(
     state: &McpState,
     profile_id: &str,
+    profile: &crate::store::Profile,
     payload: &TokenPayloadV1,
     hop: u32,
 ) -> Result<HashMap<String, usize>, Response> {
     let per_upstream =
-        upstream::list_resources_all_upstreams(state, profile_id, payload, hop).await?;
+        surface::list_resources_with_content(state, profile_id, profile, payload, hop).await?;
     Ok(count_resource_uris(&per_upstream))
 }
 
@@ -1979,6 +2302,7 @@ async fn contract_replay_stream(
     profile: &crate::store::Profile,
     profile_id: &str,
     after: Option<u64>,
+    event_log: Option<Arc<crate::sse_log::SessionEventLog>>,
 ) -> Option<futures::stream::BoxStream<'static, Result<axum::response::sse::Event, Infallible>>> {
     let (fanout, after) = (state.contract_fanout.as_ref()?, after?);
     let caps = effective_caps(profile);
@@ -1999,13 +2323,16 @@ async fn contract_replay_stream(
                 if !allowed_by_caps || !filter.allows(method) {
                     return None;
                 }
-                let json = list_changed_notification_json(&evt);
-                Some(Ok::<_, Infallible>(
-                    axum::response::sse::Event::default()
-                        .id(evt.event_id.to_string())
-                        .data(json),
-                ))
-            }));
+                Some((evt.event_id, list_changed_notification_json(&evt)))
+            }))
+            .then(move |(event_id, json)| {
+                let event_log = event_log.clone();
+                async move {
+                    Ok::<_, Infallible>(
+                        contract_sse_event(event_log.as_deref(), event_id, json).await,
+                    )
+                }
+            });
             Some(replay.boxed())
         }
         Err(e) => {
@@ -2015,15 +2342,35 @@ async fn contract_replay_stream(
     }
 }
 
+/// A contract notification event, logged for replay when the session has an event log.
+async fn contract_sse_event(
+    event_log: Option<&crate::sse_log::SessionEventLog>,
+    event_id: u64,
+    json: String,
+) -> axum::response::sse::Event {
+    let logged_id = match event_log {
+        Some(log) => {
+            log.record(crate::sse_log::EventOrigin::Contract { event_id }, &json)
+                .await
+        }
+        None => None,
+    };
+    axum::response::sse::Event::default()
+        .id(logged_id.unwrap_or_else(|| event_id.to_string()))
+        .data(json)
+}
+
 fn contract_notifications_stream(
     profile: &crate::store::Profile,
     rx: tokio::sync::broadcast::Receiver<ContractEvent>,
+    event_log: Option<Arc<crate::sse_log::SessionEventLog>>,
 ) -> futures::stream::BoxStream<'static, Result<axum::response::sse::Event, Infallible>> {
     let caps = effective_caps(profile);
     let filter = profile.mcp.notifications.clone();
     let notifications = futures::stream::unfold(rx, move |mut rx| {
         let caps = caps;
         let filter = filter.clone();
+        let event_log = event_log.clone();
         async move {
             loop {
                 match rx.recv().await {
@@ -2040,14 +2387,8 @@ fn contract_notifications_stream(
                             continue;
                         }
                         let json = list_changed_notification_json(&evt);
-                        return Some((
-                            Ok::<_, Infallible>(
-                                axum::response::sse::Event::default()
-                                    .id(evt.event_id.to_string())
-                                    .data(json),
-                            ),
-                            rx,
-                        ));
+                        let ev = contract_sse_event(event_log.as_deref(), evt.event_id, json).await;
+                        return Some((Ok::<_, Infallible>(ev), rx));
                     }
                     Err(tokio::sync::broadcast::error::RecvError::Closed) => return None,
                     Err(tokio::sync::broadcast::error::RecvError::Lagged(_)) => {
@@ -2066,12 +2407,13 @@ fn contract_notifications_stream(
     notifications.boxed()
 }
 
-fn priming_stream()
--> futures::stream::BoxStream<'static, Result<axum::response::sse::Event, Infallible>> {
+fn priming_stream(
+    id: String,
+) -> futures::stream::BoxStream<'static, Result<axum::response::sse::Event, Infallible>> {
     // SSE priming event (SEP-1699): send a first event with an id and empty data ("data:\n").
     // RMCP also sets `retry: 3000`; axum supports `retry()` so we mirror that when possible.
     let ev = axum::response::sse::Event::default()
-        .id("0")
+        .id(id)
         .retry(std::time::Duration::from_millis(SSE_PRIMING_RETRY_MS))
         .data("");
     futures::stream::once(async move { Ok::<_, Infallible>(ev) }).boxed()
@@ -2081,11 +2423,13 @@ async fn handle_delete(
     state: &McpState,
     profile_id: &str,
     headers: &HeaderMap,
+    client_ip: Option<IpAddr>,
     token: String,
 ) -> Result<Response, Response> {
     let hop = parse_hop(headers);
     let payload = verify_session_token(&state.signer, &token, profile_id)
         .map_err(|(s, m)| (s, m).into_response())?;
+    ensure_session_live(state, &payload).await?;
 
     let profile = state
         .store
@@ -2102,32 +2446,25 @@ async fn handle_delete(
         payload.oidc.as_ref(),
     )
     .await?;
+    enforce_ip_allowlists(state, &profile, client_ip, payload.auth.as_ref(), "DELETE").await?;
 
     // Best-effort: invalidate local caches for this session token.
     state.tools_cache.invalidate(&token);
 
-    for binding in &payload.bindings {
-        if let Some(endpoint) = upstream::resolve_endpoint(state, profile_id, binding).await? {
-            if hop >= upstream::MAX_HOPS {
-                continue;
-            }
-            let endpoint_url = upstream::apply_query_auth(&endpoint.url, endpoint.auth.as_ref());
-            let headers = upstream::build_upstream_headers(endpoint.auth.as_ref(), hop + 1);
-            let _ = streamable_http::delete_session(
-                &state.http,
-                endpoint_url.into(),
-                binding.session.clone().into(),
-                &headers,
-            )
-            .await;
-        }
+    if let Some(sid) = payload.sid.as_deref()
+        && let Err(e) = state.sessions.terminate(sid).await
+    {
+        tracing::warn!(error = %e, "failed to mark MCP session terminated");
     }
+
+    delete_upstream_sessions(state, profile_id, &payload.bindings, hop).await?;
     Ok(StatusCode::ACCEPTED.into_response())
 }
 
 async fn route_and_proxy_resource_read(
     state: &McpState,
     profile_id: &str,
+    profile: &crate::store::Profile,
     payload: &TokenPayloadV1,
     _token: String,
     message: &mut ClientJsonRpcMessage,
@@ -2137,9 +2474,27 @@ async fn route_and_proxy_resource_read(
         return Err((StatusCode::BAD_REQUEST, "invalid resources/read request").into_response());
     };
 
-    let (upstream_id, original_uri) = resolve_resource_owner(state, profile_id, payload, &uri, hop)
-        .await
-        .map_err(|e| jsonrpc_error_response(req_id, ErrorCode::INVALID_PARAMS, e.to_string()))?;
+    let (upstream_id, original_uri) =
+        resolve_resource_owner(state, profile_id, profile, payload, &uri, hop)
+            .await
+            .map_err(|e| {
+                jsonrpc_error_response(req_id.clone(), ErrorCode::INVALID_PARAMS, e.to_string())
+            })?;
+
+    // Gateway-defined resources (tenant `content` sources) are served locally.
+    if let Some(source) = surface::content_source_for(state, profile, &upstream_id).await {
+        let result = state
+            .tenant_catalog
+            .read_content_resource(&profile.tenant_id, &source, &original_uri)
+            .await
+            .map_err(|e| jsonrpc_error_response(req_id.clone(), ErrorCode::INTERNAL_ERROR, e))?;
+        let msg = ServerJsonRpcMessage::Response(JsonRpcResponse {
+            jsonrpc: JsonRpcVersion2_0,
+            id: req_id,
+            result: ServerResult::ReadResourceResult(result),
+        });
+        return Ok(sse_single_message(&msg));
+    }
 
     if let Some(param) = as_read_resource_mut(message) {
         param.uri = original_uri;
@@ -2159,6 +2514,7 @@ async fn route_and_proxy_resource_read(
 async fn route_and_proxy_resource_subscribe(
     state: &McpState,
     profile_id: &str,
+    profile: &crate::store::Profile,
     payload: &TokenPayloadV1,
     _token: String,
     message: &mut ClientJsonRpcMessage,
@@ -2172,9 +2528,23 @@ async fn route_and_proxy_resource_subscribe(
             .into_response());
     };
 
-    let (upstream_id, original_uri) = resolve_resource_owner(state, profile_id, payload, &uri, hop)
+    let (upstream_id, original_uri) =
+        resolve_resource_owner(state, profile_id, profile, payload, &uri, hop)
+            .await
+            .map_err(|e| {
+                jsonrpc_error_response(req_id.clone(), ErrorCode::INVALID_PARAMS, e.to_string())
+            })?;
+
+    if surface::content_source_for(state, profile, &upstream_id)
         .await
-        .map_err(|e| jsonrpc_error_response(req_id, ErrorCode::INVALID_PARAMS, e.to_string()))?;
+        .is_some()
+    {
+        return Err(jsonrpc_error_response(
+            req_id,
+            ErrorCode::INVALID_PARAMS,
+            format!("resource '{uri}' is static and does not support subscriptions"),
+        ));
+    }
 
     if let Some(param) = as_subscribe_mut(message) {
         param.uri = original_uri;
@@ -2194,6 +2564,7 @@ async fn route_and_proxy_resource_subscribe(
 async fn route_and_proxy_resource_unsubscribe(
     state: &McpState,
     profile_id: &str,
+    profile: &crate::store::Profile,
     payload: &TokenPayloadV1,
     _token: String,
     message: &mut ClientJsonRpcMessage,
@@ -2207,9 +2578,23 @@ async fn route_and_proxy_resource_unsubscribe(
             .into_response());
     };
 
-    let (upstream_id, original_uri) = resolve_resource_owner(state, profile_id, payload, &uri, hop)
+    let (upstream_id, original_uri) =
+        resolve_resource_owner(state, profile_id, profile, payload, &uri, hop)
+            .await
+            .map_err(|e| {
+                jsonrpc_error_response(req_id.clone(), ErrorCode::INVALID_PARAMS, e.to_string())
+            })?;
+
+    if surface::content_source_for(state, profile, &upstream_id)
         .await
-        .map_err(|e| jsonrpc_error_response(req_id, ErrorCode::INVALID_PARAMS, e.to_string()))?;
+        .is_some()
+    {
+        return Err(jsonrpc_error_response(
+            req_id,
+            ErrorCode::INVALID_PARAMS,
+            format!("resource '{uri}' is static and does not support subscriptions"),
+        ));
+    }
 
     if let Some(param) = as_unsubscribe_mut(message) {
         param.uri = original_uri;
@@ -2229,6 +2614,7 @@ async fn route_and_proxy_resource_unsubscribe(
 async fn route_and_proxy_prompt_get(
     state: &McpState,
     profile_id: &str,
+    profile: &crate::store::Profile,
     payload: &TokenPayloadV1,
     _token: String,
     message: &mut ClientJsonRpcMessage,
@@ -2238,9 +2624,26 @@ async fn route_and_proxy_prompt_get(
         return Err((StatusCode::BAD_REQUEST, "invalid prompts/get request").into_response());
     };
 
-    let (upstream_id, original_name) = resolve_prompt_owner(state, profile_id, payload, &name, hop)
-        .await
-        .map_err(|e| jsonrpc_error_response(req_id, ErrorCode::INVALID_PARAMS, e.to_string()))?;
+    let (upstream_id, original_name) =
+        resolve_prompt_owner(state, profile_id, profile, payload, &name, hop)
+            .await
+            .map_err(|e| {
+                jsonrpc_error_response(req_id.clone(), ErrorCode::INVALID_PARAMS, e.to_string())
+            })?;
+
+    // Gateway-defined prompts (tenant `content` sources) are rendered locally.
+    if let Some(source) = surface::content_source_for(state, profile, &upstream_id).await {
+        let arguments = as_get_prompt_mut(message).and_then(|p| p.arguments.clone());
+        let result = source
+            .get_prompt(&original_name, arguments.as_ref())
+            .map_err(|e| jsonrpc_error_response(req_id.clone(), ErrorCode::INVALID_PARAMS, e))?;
+        let msg = ServerJsonRpcMessage::Response(JsonRpcResponse {
+            jsonrpc: JsonRpcVersion2_0,
+            id: req_id,
+            result: ServerResult::GetPromptResult(result),
+        });
+        return Ok(sse_single_message(&msg));
+    }
 
     if let Some(param) = as_get_prompt_mut(message) {
         param.name = original_name;
@@ -2260,6 +2663,7 @@ async fn route_and_proxy_prompt_get(
 async fn route_and_proxy_completion_complete(
     state: &McpState,
     profile_id: &str,
+    profile: &crate::store::Profile,
     payload: &TokenPayloadV1,
     _token: String,
     message: &mut ClientJsonRpcMessage,
@@ -2276,24 +2680,51 @@ async fn route_and_proxy_completion_complete(
     let (upstream_id, rewritten_ref) = match reference {
         Reference::Prompt(p) => {
             let (upstream_id, original_name) =
-                resolve_prompt_owner(state, profile_id, payload, &p.name, hop)
+                resolve_prompt_owner(state, profile_id, profile, payload, &p.name, hop)
                     .await
                     .map_err(|e| {
-                        jsonrpc_error_response(req_id, ErrorCode::INVALID_PARAMS, e.to_string())
+                        jsonrpc_error_response(
+                            req_id.clone(),
+                            ErrorCode::INVALID_PARAMS,
+                            e.to_string(),
+                        )
                     })?;
             (upstream_id, Reference::for_prompt(original_name))
         }
         Reference::Resource(r) => {
             let (upstream_id, original_uri) =
-                resolve_resource_owner(state, profile_id, payload, &r.uri, hop)
+                resolve_resource_owner(state, profile_id, profile, payload, &r.uri, hop)
                     .await
                     .map_err(|e| {
-                        jsonrpc_error_response(req_id, ErrorCode::INVALID_PARAMS, e.to_string())
+                        jsonrpc_error_response(
+                            req_id.clone(),
+                            ErrorCode::INVALID_PARAMS,
+                            e.to_string(),
+                        )
                     })?;
             (upstream_id, Reference::for_resource(original_uri))
         }
     };
 
+    // Gateway-defined prompts/resources have no completions.
+    if surface::content_source_for(state, profile, &upstream_id)
+        .await
+        .is_some()
+    {
+        let msg = ServerJsonRpcMessage::Response(JsonRpcResponse {
+            jsonrpc: JsonRpcVersion2_0,
+            id: req_id,
+            result: ServerResult::CompleteResult(rmcp::model::CompleteResult {
+                completion: rmcp::model::CompletionInfo {
+                    values: Vec::new(),
+                    total: Some(0),
+                    has_more: Some(false),
+                },
+            }),
+        });
+        return Ok(sse_single_message(&msg));
+    }
+
     if let Some(param) = as_complete_mut(message) {
         param.r#ref = rewritten_ref;
     }
@@ -2632,6 +3063,122 @@ mod tests {
         );
     }
 
+    #[test]
+    fn tools_call_args_validation_enforces_transform_constraints() {
+        let transforms: unrelated_tool_transforms::TransformPipeline =
+            serde_json::from_value(serde_json::json!({
+                "toolOverrides": { "deploy": { "params": {
+                    "environment": { "enum": ["staging"] },
+                    "/options/region": { "pinned": "eu-west-1" }
+                } } }
+            }))
+            .unwrap();
+        let mut schema = serde_json::json!({
+            "type": "object",
+            "properties": {
+                "environment": { "type": "string", "enum": ["staging", "production"] },
+                "options": { "type": "object", "properties": { "region": { "type": "string" } } }
+            },
+            "required": ["environment"]
+        });
+        transforms.apply_schema_transforms("deploy", &mut schema);
+        let tool = rmcp::model::Tool::new(
+            "deploy".to_string(),
+            String::new(),
+            Arc::new(schema.as_object().unwrap().clone()),
+        );
+
+        let validate = |args| super::tool_call::validate_tool_arguments(&tool, &args);
+        assert!(validate(serde_json::json!({ "environment": "staging" })).is_ok());
+        assert!(validate(serde_json::json!({ "environment": "production" })).is_err());
+        assert!(
+            validate(serde_json::json!({
+                "environment": "staging",
+                "options": { "region": "us-east-1" }
+            }))
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn tools_call_args_validation_fails_closed_on_uncompilable_schema() {
+        let schema = serde_json::json!({
+            "type": "object",
+            "properties": {
+                "tag": { "type": "string", "pattern": "^(v" },
+                "environment": { "type": "string", "enum": ["staging"] },
+                "replicas": { "type": "integer", "maximum": 3 }
+            }
+        });
+        let tool = rmcp::model::Tool::new(
+            "deploy".to_string(),
+            String::new(),
+            Arc::new(schema.as_object().unwrap().clone()),
+        );
+
+        for args in [
+            serde_json::json!({ "environment": "staging", "replicas": 1 }),
+            serde_json::json!({ "environment": "production", "replicas": 100 }),
+        ] {
+            let (msg, data) = super::tool_call::validate_tool_arguments(&tool, &args).unwrap_err();
+            assert!(msg.contains("Invalid tool schema"), "message: {msg}");
+            assert_eq!(data["type"], "invalid-tool-schema");
+        }
+    }
+
+    #[tokio::test]
+    async fn workflow_step_reads_the_jsonrpc_response_from_chunked_sse() {
+        let chunks = [
+            ": keep-alive\r\n\r\ndata: {\"jsonrpc\":\"2.0\",\"method\":\"notifications/progress\"}\r\n\r\nda",
+            "ta: {\"jsonrpc\":\"2.0\",\"id\":\"w1\",",
+            "\"result\":{\"content\":[]}}\n\n",
+        ]
+        .map(|c| Ok::<_, std::io::Error>(axum::body::Bytes::from_static(c.as_bytes())));
+        let resp = axum::response::Response::builder()
+            .header(axum::http::header::CONTENT_TYPE, "text/event-stream")
+            .body(axum::body::Body::from_stream(futures::stream::iter(chunks)))
+            .unwrap();
+        let result = super::tool_call::read_tools_call_result(resp)
+            .await
+            .unwrap();
+        assert_eq!(result, serde_json::json!({ "content": [] }));
+
+        let resp = axum::response::Response::builder()
+            .header(axum::http::header::CONTENT_TYPE, "text/event-stream")
+            .body(axum::body::Body::from(
+                "data: {\"jsonrpc\":\"2.0\",\"id\":1,\"error\":{\"code\":-1,\"message\":\"boom\"}}",
+            ))
+            .unwrap();
+        assert_eq!(
+            super::tool_call::read_tools_call_result(resp)
+                .await
+                .unwrap_err(),
+            "boom"
+        );
+    }
+
+    #[test]
+    fn last_event_id_gateway_ids_only_parse_with_replay_enabled() {
+        let ns = SseEventIdNamespacing::UpstreamSlash;
+
+        let last = parse_last_event_id(ns, true, Some("gw.7"));
+        assert_eq!(last.buffer_after, Some(7));
+        assert!(last.resume_upstreams.is_empty());
+
+        let last = parse_last_event_id(ns, false, Some("gw.7"));
+        assert_eq!(last.buffer_after, None);
+
+        let last = parse_last_event_id(ns, true, Some("u1/e3"));
+        assert_eq!(
+            last.resume_upstreams.get("u1").map(String::as_str),
+            Some("e3")
+        );
+        assert_eq!(
+            parse_last_event_id(ns, true, Some("12")).contract_after_id,
+            Some(12)
+        );
+    }
+
     #[test]
     fn upstream_initialize_rewrite_strip_allowlist_and_clientinfo() {
         let caps: ClientCapabilities = serde_json::from_value(serde_json::json!({
@@ -2978,6 +3525,9 @@ mod tests {
         ) -> anyhow::Result<Option<crate::store::TransportLimitsSettings>> {
             Ok(None)
         }
+        async fn get_tenant_allowed_cidrs(&self, _tenant_id: &str) -> anyhow::Result<Vec<String>> {
+            Ok(Vec::new())
+        }
         async fn authenticate_api_key(
             &self,
             _tenant_id: &str,
@@ -3022,6 +3572,78 @@ mod tests {
         ) -> anyhow::Result<bool> {
             Ok(false)
         }
+
+        async fn list_enabled_oidc_claim_rules(
+            &self,
+            _tenant_id: &str,
+            _issuer: &str,
+        ) -> anyhow::Result<Vec<crate::store::OidcClaimRule>> {
+            Ok(Vec::new())
+        }
+
+        async fn get_tenant_oidc_issuer(
+            &self,
+            _tenant_id: &str,
+            _issuer: &str,
+        ) -> anyhow::Result<Option<crate::store::TenantOidcIssuer>> {
+            Ok(None)
+        }
+
+        async fn list_enabled_tenant_oidc_issuers(
+            &self,
+            _tenant_id: &str,
+        ) -> anyhow::Result<Vec<crate::store::TenantOidcIssuer>> {
+            Ok(Vec::new())
+        }
+
+        async fn get_upstream_oauth_client(
+            &self,
+            _tenant_id: &str,
+            _upstream_id: &str,
+        ) -> anyhow::Result<Option<crate::store::UpstreamOAuthClient>> {
+            Ok(None)
+        }
+
+        async fn get_upstream_oauth_token(
+            &self,
+            _tenant_id: &str,
+            _upstream_id: &str,
+            _principal: &str,
+        ) -> anyhow::Result<Option<crate::store::UpstreamOAuthToken>> {
+            Ok(None)
+        }
+
+        async fn put_upstream_oauth_token(
+            &self,
+            _tenant_id: &str,
+            _upstream_id: &str,
+            _principal: &str,
+            _token: &crate::store::UpstreamOAuthToken,
+        ) -> anyhow::Result<()> {
+            Ok(())
+        }
+
+        async fn put_upstream_oauth_link_state(
+            &self,
+            _link: &crate::store::UpstreamOAuthLinkState,
+        ) -> anyhow::Result<()> {
+            Ok(())
+        }
+
+        async fn get_upstream_oauth_link_state(
+            &self,
+
+            _state_hash: &str,
+        ) -> anyhow::Result<Option<crate::store::UpstreamOAuthLinkState>> {
+            Ok(None)
+        }
+
+        async fn take_upstream_oauth_link_state(
+            &self,
+            _state_hash: &str,
+        ) -> anyhow::Result<Option<crate::store::UpstreamOAuthLinkState>> {
+            Ok(None)
+        }
     }
 
     async fn start_server(app: Router) -> (String, tokio::task::JoinHandle<()>) {
@@ -3160,6 +3782,7 @@ mod tests {
             iat: None,
             exp: None,
             proxy_key: None,
+            sid: None,
         };
         let token = signer.sign(payload).expect("token");
 
@@ -3195,6 +3818,7 @@ mod tests {
             source_ids: vec![],
             transforms: unrelated_tool_transforms::TransformPipeline::default(),
             enabled_tools: Vec::new(),
+            api_key_tool_allowlist: Vec::new(),
             data_plane_auth_mode: DataPlaneAuthMode::Disabled,
             accept_x_api_key: false,
             rate_limit_enabled: false,
@@ -3228,6 +3852,23 @@ mod tests {
             endpoint_cache: Arc::new(crate::endpoint_cache::UpstreamEndpointCache::new(
                 Duration::from_secs(60),
             )),
+            oidc_rules_cache: Arc::new(crate::oidc_rules::OidcClaimRulesCache::new(
+                Duration::from_secs(60),
+            )),
+            tenant_allowlist_cache: Arc::new(crate::ip_allowlist::TenantAllowlistCache::new(
+                Duration::ZERO,
+            )),
+            response_pipelines: Arc::default(),
+            trusted_proxies: Arc::default(),
+            tenant_oidc: Arc::new(crate::tenant_oidc::TenantOidcValidators::new(
+                reqwest::Client::default(),
+            )),
+            public_base_url: None,
+            upstream_oauth: Arc::new(crate::upstream_oauth::UpstreamOAuthManager::new(
+                reqwest::Client::default(),
+            )),
+            sse_log: Arc::new(crate::sse_log::MemorySseEventLog::new()),
+            sessions: Arc::new(crate::session_registry::SessionRegistry::in_memory()),
         });
 
         let app = super::router(state);
@@ -3285,6 +3926,7 @@ mod tests {
             source_ids: vec![],
             transforms: unrelated_tool_transforms::TransformPipeline::default(),
             enabled_tools: Vec::new(),
+            api_key_tool_allowlist: Vec::new(),
             data_plane_auth_mode: DataPlaneAuthMode::Disabled,
             accept_x_api_key: false,
             rate_limit_enabled: false,
@@ -3318,6 +3960,23 @@ mod tests {
             endpoint_cache: Arc::new(crate::endpoint_cache::UpstreamEndpointCache::new(
                 Duration::from_secs(60),
             )),
+            oidc_rules_cache: Arc::new(crate::oidc_rules::OidcClaimRulesCache::new(
+                Duration::from_secs(60),
+            )),
+            tenant_allowlist_cache: Arc::new(crate::ip_allowlist::TenantAllowlistCache::new(
+                Duration::ZERO,
+            )),
+            response_pipelines: Arc::default(),
+            trusted_proxies: Arc::default(),
+            tenant_oidc: Arc::new(crate::tenant_oidc::TenantOidcValidators::new(
+                reqwest::Client::default(),
+            )),
+            public_base_url: None,
+            upstream_oauth: Arc::new(crate::upstream_oauth::UpstreamOAuthManager::new(
+                reqwest::Client::default(),
+            )),
+            sse_log: Arc::new(crate::sse_log::MemorySseEventLog::new()),
+            sessions: Arc::new(crate::session_registry::SessionRegistry::in_memory()),
         });
 
         let app = super::router(state);
@@ -3361,6 +4020,7 @@ mod tests {
             source_ids: vec![],
             transforms: unrelated_tool_transforms::TransformPipeline::default(),
             enabled_tools: Vec::new(),
+            api_key_tool_allowlist: Vec::new(),
             data_plane_auth_mode: DataPlaneAuthMode::Disabled,
             accept_x_api_key: false,
             rate_limit_enabled: false,
@@ -3394,6 +4054,23 @@ mod tests {
             endpoint_cache: Arc::new(crate::endpoint_cache::UpstreamEndpointCache::new(
                 Duration::from_secs(60),
             )),
+            oidc_rules_cache: Arc::new(crate::oidc_rules::OidcClaimRulesCache::new(
+                Duration::from_secs(60),
+            )),
+            tenant_allowlist_cache: Arc::new(crate::ip_allowlist::TenantAllowlistCache::new(
+                Duration::ZERO,
+            )),
+            response_pipelines: Arc::default(),
+            trusted_proxies: Arc::default(),
+            tenant_oidc: Arc::new(crate::tenant_oidc::TenantOidcValidators::new(
+                reqwest::Client::default(),
+            )),
+            public_base_url: None,
+            upstream_oauth: Arc::new(crate::upstream_oauth::UpstreamOAuthManager::new(
+                reqwest::Client::default(),
+            )),
+            sse_log: Arc::new(crate::sse_log::MemorySseEventLog::new()),
+            sessions: Arc::new(crate::session_registry::SessionRegistry::in_memory()),
         });
 
         let app = super::router(state);
@@ -3513,6 +4190,23 @@ mod tests {
             endpoint_cache: Arc::new(crate::endpoint_cache::UpstreamEndpointCache::new(
                 Duration::from_secs(60),
             )),
+            oidc_rules_cache: Arc::new(crate::oidc_rules::OidcClaimRulesCache::new(
+                Duration::from_secs(60),
+            )),
+            tenant_allowlist_cache: Arc::new(crate::ip_allowlist::TenantAllowlistCache::new(
+                Duration::ZERO,
+            )),
+            response_pipelines: Arc::default(),
+            trusted_proxies: Arc::default(),
+            tenant_oidc: Arc::new(crate::tenant_oidc::TenantOidcValidators::new(
+                reqwest::Client::default(),
+            )),
+            public_base_url: None,
+            upstream_oauth: Arc::new(crate::upstream_oauth::UpstreamOAuthManager::new(
+                reqwest::Client::default(),
+            )),
+            sse_log: Arc::new(crate::sse_log::MemorySseEventLog::new()),
+            sessions: Arc::new(crate::session_registry::SessionRegistry::in_memory()),
         };
 
         let profile = crate::store::Profile {
@@ -3522,6 +4216,7 @@ mod tests {
             source_ids: vec!["u1".to_string()],
             transforms: unrelated_tool_transforms::TransformPipeline::default(),
             enabled_tools: Vec::new(),
+            api_key_tool_allowlist: Vec::new(),
             data_plane_auth_mode: DataPlaneAuthMode::Disabled,
             accept_x_api_key: false,
             rate_limit_enabled: false,
@@ -3545,7 +4240,7 @@ mod tests {
             request: ClientRequest::InitializeRequest(init),
         });
 
-        let (bindings, warnings) = initialize_profile_sources(&state, &profile, &init_msg, 0)
+        let (bindings, warnings) = initialize_profile_sources(&state, &profile, &init_msg, 0, None)
             .await
             .expect("init ok");
         assert!(warnings.is_empty(), "no upstream should be fully down");
@@ -3625,6 +4320,23 @@ mod tests {
             endpoint_cache: Arc::new(crate::endpoint_cache::UpstreamEndpointCache::new(
                 Duration::from_secs(60),
             )),
+            oidc_rules_cache: Arc::new(crate::oidc_rules::OidcClaimRulesCache::new(
+                Duration::from_secs(60),
+            )),
+            tenant_allowlist_cache: Arc::new(crate::ip_allowlist::TenantAllowlistCache::new(
+                Duration::ZERO,
+            )),
+            response_pipelines: Arc::default(),
+            trusted_proxies: Arc::default(),
+            tenant_oidc: Arc::new(crate::tenant_oidc::TenantOidcValidators::new(
+                reqwest::Client::default(),
+            )),
+            public_base_url: None,
+            upstream_oauth: Arc::new(crate::upstream_oauth::UpstreamOAuthManager::new(
+                reqwest::Client::default(),
+            )),
+            sse_log: Arc::new(crate::sse_log::MemorySseEventLog::new()),
+            sessions: Arc::new(crate::session_registry::SessionRegistry::in_memory()),
         };
 
         let mut mcp = crate::store::McpProfileSettings::default();
@@ -3646,6 +4358,7 @@ mod tests {
             source_ids: vec!["u1".to_string()],
             transforms: unrelated_tool_transforms::TransformPipeline::default(),
             enabled_tools: Vec::new(),
+            api_key_tool_allowlist: Vec::new(),
             data_plane_auth_mode: DataPlaneAuthMode::Disabled,
             accept_x_api_key: false,
             rate_limit_enabled: false,
@@ -3661,6 +4374,7 @@ mod tests {
             upstream: "u1".to_string(),
             endpoint: "e1".to_string(),
             session: "up-session".to_string(),
+            oauth: None,
         }];
 
         let collision_counts = Arc::new(parking_lot::RwLock::new(HashMap::new()));
@@ -3679,6 +4393,7 @@ mod tests {
             hop: 0,
             limits,
             limits_shutdown: CancellationToken::new(),
+            event_log: None,
         })
         .await
         .expect("open streams");
@@ -3744,15 +4459,16 @@ mod tests {
                     id: source_id.to_string(),
                     kind: crate::store::ToolSourceKind::Http,
                     enabled: true,
-                    spec: crate::store::ToolSourceSpec::Http(
+                    spec: crate::store::ToolSourceSpec::Http(Box::new(
                         unrelated_http_tools::config::HttpServerConfig {
                             base_url: "https://example.com".to_string(),
                             auth: None,
                             defaults: unrelated_http_tools::config::EndpointDefaults::default(),
                             response_transforms: vec![],
+                            xml: Default::default(),
                             tools: HashMap::new(),
                         },
-                    ),
+                    )),
                 }));
             }
             Ok(None)
@@ -3772,6 +4488,9 @@ mod tests {
         ) -> anyhow::Result<Option<crate::store::TransportLimitsSettings>> {
             Ok(None)
         }
+        async fn get_tenant_allowed_cidrs(&self, _tenant_id: &str) -> anyhow::Result<Vec<String>> {
+            Ok(Vec::new())
+        }
 
         async fn authenticate_api_key(
             &self,
@@ -3822,6 +4541,78 @@ mod tests {
         ) -> anyhow::Result<bool> {
             Ok(false)
         }
+
+        async fn list_enabled_oidc_claim_rules(
+            &self,
+            _tenant_id: &str,
+            _issuer: &str,
+        ) -> anyhow::Result<Vec<crate::store::OidcClaimRule>> {
+            Ok(Vec::new())
+        }
+
+        async fn get_tenant_oidc_issuer(
+            &self,
+            _tenant_id: &str,
+            _issuer: &str,
+        ) -> anyhow::Result<Option<crate::store::TenantOidcIssuer>> {
+            Ok(None)
+        }
+
+        async fn list_enabled_tenant_oidc_issuers(
+            &self,
+            _tenant_id: &str,
+        ) -> anyhow::Result<Vec<crate::store::TenantOidcIssuer>> {
+            Ok(Vec::new())
+        }
+
+        async fn get_upstream_oauth_client(
+            &self,
+            _tenant_id: &str,
+            _upstream_id: &str,
+        ) -> anyhow::Result<Option<crate::store::UpstreamOAuthClient>> {
+            Ok(None)
+        }
+
+        async fn get_upstream_oauth_token(
+            &self,
+            _tenant_id: &str,
+            _upstream_id: &str,
+            _principal: &str,
+        ) -> anyhow::Result<Option<crate::store::UpstreamOAuthToken>> {
+            Ok(None)
+        }
+
+        async fn put_upstream_oauth_token(
+            &self,
+            _tenant_id: &str,
+            _upstream_id: &str,
+            _principal: &str,
+            _token: &crate::store::UpstreamOAuthToken,
+        ) -> anyhow::Result<()> {
+            Ok(())
+        }
+
+        async fn put_upstream_oauth_link_state(
+            &self,
+            _link: &crate::store::UpstreamOAuthLinkState,
+        ) -> anyhow::Result<()> {
+            Ok(())
+        }
+
+        async fn get_upstream_oauth_link_state(
+            &self,
+
+            _state_hash: &str,
+        ) -> anyhow::Result<Option<crate::store::UpstreamOAuthLinkState>> {
+            Ok(None)
+        }
+
+        async fn take_upstream_oauth_link_state(
+            &self,
+            _state_hash: &str,
+        ) -> anyhow::Result<Option<crate::store::UpstreamOAuthLinkState>> {
+            Ok(None)
+        }
     }
 
     #[tokio::test]
@@ -3881,6 +4672,23 @@ sharedSources:
             endpoint_cache: Arc::new(crate::endpoint_cache::UpstreamEndpointCache::new(
                 Duration::from_secs(60),
             )),
+            oidc_rules_cache: Arc::new(crate::oidc_rules::OidcClaimRulesCache::new(
+                Duration::from_secs(60),
+            )),
+            tenant_allowlist_cache: Arc::new(crate::ip_allowlist::TenantAllowlistCache::new(
+                Duration::ZERO,
+            )),
+            response_pipelines: Arc::default(),
+            trusted_proxies: Arc::default(),
+            tenant_oidc: Arc::new(crate::tenant_oidc::TenantOidcValidators::new(
+                reqwest::Client::default(),
+            )),
+            public_base_url: None,
+            upstream_oauth: Arc::new(crate::upstream_oauth::UpstreamOAuthManager::new(
+                reqwest::Client::default(),
+            )),
+            sse_log: Arc::new(crate::sse_log::MemorySseEventLog::new()),
+            sessions: Arc::new(crate::session_registry::SessionRegistry::in_memory()),
         };
 
         let profile = crate::store::Profile {
@@ -3894,6 +4702,7 @@ sharedSources:
             ],
             transforms: unrelated_tool_transforms::TransformPipeline::default(),
             enabled_tools: Vec::new(),
+            api_key_tool_allowlist: Vec::new(),
             data_plane_auth_mode: DataPlaneAuthMode::Disabled,
             accept_x_api_key: false,
             rate_limit_enabled: false,
@@ -3920,9 +4729,10 @@ sharedSources:
         // We expect this to try resolving exactly one upstream id ("u1"), skipping local sources.
         // It will fail to initialize because endpoint is unreachable, but allow_partial_upstreams
         // should make it return a warning instead of an error.
-        let (_bindings, warnings) = initialize_profile_sources(&state, &profile, &init_msg, 0)
-            .await
-            .expect("init ok");
+        let (_bindings, warnings) =
+            initialize_profile_sources(&state, &profile, &init_msg, 0, None)
+                .await
+                .expect("init ok");
         assert_eq!(calls.load(Ordering::SeqCst), 1);
         assert_eq!(warnings.len(), 1);
         Ok(())
@@ -3981,6 +4791,23 @@ sharedSources:
             endpoint_cache: Arc::new(crate::endpoint_cache::UpstreamEndpointCache::new(
                 Duration::from_secs(60),
             )),
+            oidc_rules_cache: Arc::new(crate::oidc_rules::OidcClaimRulesCache::new(
+                Duration::from_secs(60),
+            )),
+            tenant_allowlist_cache: Arc::new(crate::ip_allowlist::TenantAllowlistCache::new(
+                Duration::ZERO,
+            )),
+            response_pipelines: Arc::default(),
+            trusted_proxies: Arc::default(),
+            tenant_oidc: Arc::new(crate::tenant_oidc::TenantOidcValidators::new(
+                reqwest::Client::default(),
+            )),
+            public_base_url: None,
+            upstream_oauth: Arc::new(crate::upstream_oauth::UpstreamOAuthManager::new(
+                reqwest::Client::default(),
+            )),
+            sse_log: Arc::new(crate::sse_log::MemorySseEventLog::new()),
+            sessions: Arc::new(crate::session_registry::SessionRegistry::in_memory()),
         };
 
         let profile = crate::store::Profile {
@@ -3990,6 +4817,7 @@ sharedSources:
             source_ids: vec!["s1".to_string(), "s2".to_string()],
             transforms: unrelated_tool_transforms::TransformPipeline::default(),
             enabled_tools: Vec::new(),
+            api_key_tool_allowlist: Vec::new(),
             data_plane_auth_mode: DataPlaneAuthMode::Disabled,
             accept_x_api_key: false,
             rate_limit_enabled: false,
@@ -4008,6 +4836,7 @@ sharedSources:
             iat: None,
             exp: None,
             proxy_key: None,
+            sid: None,
         };
 
         let surface =
@@ -4071,6 +4900,23 @@ sharedSources:
             endpoint_cache: Arc::new(crate::endpoint_cache::UpstreamEndpointCache::new(
                 Duration::from_secs(60),
             )),
+            oidc_rules_cache: Arc::new(crate::oidc_rules::OidcClaimRulesCache::new(
+                Duration::from_secs(60),
+            )),
+            tenant_allowlist_cache: Arc::new(crate::ip_allowlist::TenantAllowlistCache::new(
+                Duration::ZERO,
+            )),
+            response_pipelines: Arc::default(),
+            trusted_proxies: Arc::default(),
+            tenant_oidc: Arc::new(crate::tenant_oidc::TenantOidcValidators::new(
+                reqwest::Client::default(),
+            )),
+            public_base_url: None,
+            upstream_oauth: Arc::new(crate::upstream_oauth::UpstreamOAuthManager::new(
+                reqwest::Client::default(),
+            )),
+            sse_log: Arc::new(crate::sse_log::MemorySseEventLog::new()),
+            sessions: Arc::new(crate::session_registry::SessionRegistry::in_memory()),
         };
 
         let profile = crate::store::Profile {
@@ -4080,6 +4926,7 @@ sharedSources:
             source_ids: vec!["s1".to_string()],
             transforms: unrelated_tool_transforms::TransformPipeline::default(),
             enabled_tools: Vec::new(),
+            api_key_tool_allowlist: Vec::new(),
             data_plane_auth_mode: DataPlaneAuthMode::Disabled,
             accept_x_api_key: false,
             rate_limit_enabled: false,
@@ -4098,6 +4945,7 @@ sharedSources:
             iat: None,
             exp: None,
             proxy_key: None,
+            sid: None,
         };
 
         let surface =
@@ -4192,6 +5040,23 @@ sharedSources:
             endpoint_cache: Arc::new(crate::endpoint_cache::UpstreamEndpointCache::new(
                 Duration::from_secs(60),
             )),
+            oidc_rules_cache: Arc::new(crate::oidc_rules::OidcClaimRulesCache::new(
+                Duration::from_secs(60),
+            )),
+            tenant_allowlist_cache: Arc::new(crate::ip_allowlist::TenantAllowlistCache::new(
+                Duration::ZERO,
+            )),
+            response_pipelines: Arc::default(),
+            trusted_proxies: Arc::default(),
+            tenant_oidc: Arc::new(crate::tenant_oidc::TenantOidcValidators::new(
+                reqwest::Client::default(),
+            )),
+            public_base_url: None,
+            upstream_oauth: Arc::new(crate::upstream_oauth::UpstreamOAuthManager::new(
+                reqwest::Client::default(),
+            )),
+            sse_log: Arc::new(crate::sse_log::MemorySseEventLog::new()),
+            sessions: Arc::new(crate::session_registry::SessionRegistry::in_memory()),
         };
 
         let profile = crate::store::Profile {
@@ -4201,6 +5066,7 @@ sharedSources:
             source_ids: vec!["u1".to_string()],
             transforms: unrelated_tool_transforms::TransformPipeline::default(),
             enabled_tools: Vec::new(),
+            api_key_tool_allowlist: Vec::new(),
             data_plane_auth_mode: DataPlaneAuthMode::Disabled,
             accept_x_api_key: false,
             rate_limit_enabled: false,
@@ -4218,6 +5084,7 @@ sharedSources:
                     maximum_interval_ms: None,
                     non_retryable_error_types: vec![],
                 }),
+                response_transforms: None,
             }],
             mcp: crate::store::McpProfileSettings::default(),
         };
@@ -4228,12 +5095,14 @@ sharedSources:
                 upstream: "u1".to_string(),
                 endpoint: "e1".to_string(),
                 session: "s".to_string(),
+                oauth: None,
             }],
             auth: None,
             oidc: None,
             iat: None,
             exp: None,
             proxy_key: None,
+            sid: None,
         };
 
         // Seed tool routing cache so we don't have to build the full tools surface.
@@ -4289,4 +5158,169 @@ sharedSources:
 
         handle.abort();
     }
+
+    #[allow(clippy::too_many_lines)]
+    #[tokio::test]
+    async fn streamed_local_tool_call_is_cancelled_when_the_client_disconnects() {
+        struct ChannelAuditSink(tokio::sync::mpsc::UnboundedSender<crate::audit::AuditEvent>);
+
+        #[async_trait]
+        impl crate::audit::AuditSink for ChannelAuditSink {
+            async fn record(&self, event: crate::audit::AuditEvent) {
+                let _ = self.0.send(event);
+            }
+
+            async fn tenant_default_level(&self, _tenant_id: &str) -> crate::audit::AuditLevel {
+                crate::audit::AuditLevel::Off
+            }
+        }
+
+        let app = Router::new().route(
+            "/slow",
+            get(|| async {
+                tokio::time::sleep(Duration::from_secs(30)).await;
+                "done"
+            }),
+        );
+        let (base, handle) = start_server(app).await;
+        let cfg: GatewayConfig = serde_yaml::from_str(&format!(
+            r"
+tenants: {{}}
+profiles: {{}}
+upstreams: {{}}
+sharedSources:
+  s1:
+    type: http
+    enabled: true
+    public: true
+    baseUrl: {base}
+    tools:
+      slow:
+        method: GET
+        path: /slow
+"
+        ))
+        .expect("valid yaml");
+        let shared = SharedCatalog::from_config(&cfg).await.expect("catalog");
+
+        let (audit_tx, mut audit_rx) = tokio::sync::mpsc::unbounded_channel();
+        let state = McpState {
+            store: Arc::new(TestStore {
+                profiles: HashMap::new(),
+                upstreams: HashMap::new(),
+            }),
+            signer: SessionSigner::new(vec![vec![0u8; 32]], Duration::from_secs(60))
+                .expect("signer"),
+            http: reqwest::Client::default(),
+            oidc: None,
+            shutdown: CancellationToken::new(),
+            audit: Arc::new(ChannelAuditSink(audit_tx)),
+            catalog: Arc::new(shared),
+            tenant_catalog: Arc::new(TenantCatalog::new()),
+            contracts: Arc::new(ContractTracker::new()),
+            contract_fanout: None,
+            tools_cache: Arc::new(crate::tools_cache::ToolSurfaceCache::new(
+                Duration::from_secs(60),
+            )),
+            endpoint_cache: Arc::new(crate::endpoint_cache::UpstreamEndpointCache::new(
+                Duration::from_secs(60),
+            )),
+            oidc_rules_cache: Arc::new(crate::oidc_rules::OidcClaimRulesCache::new(
+                Duration::from_secs(60),
+            )),
+            tenant_allowlist_cache: Arc::new(crate::ip_allowlist::TenantAllowlistCache::new(
+                Duration::ZERO,
+            )),
+            response_pipelines: Arc::default(),
+            trusted_proxies: Arc::default(),
+            tenant_oidc: Arc::new(crate::tenant_oidc::TenantOidcValidators::new(
+                reqwest::Client::default(),
+            )),
+            public_base_url: None,
+            upstream_oauth: Arc::new(crate::upstream_oauth::UpstreamOAuthManager::new(
+                reqwest::Client::default(),
+            )),
+            sse_log: Arc::new(crate::sse_log::MemorySseEventLog::new()),
+            sessions: Arc::new(crate::session_registry::SessionRegistry::in_memory()),
+        };
+
+        let profile = crate::store::Profile {
+            id: "p".to_string(),
+            tenant_id: "t".to_string(),
+            allow_partial_upstreams: true,
+            source_ids: vec!["s1".to_string()],
+            transforms: unrelated_tool_transforms::TransformPipeline::default(),
+            enabled_tools: Vec::new(),
+            api_key_tool_allowlist: Vec::new(),
+            data_plane_auth_mode: DataPlaneAuthMode::Disabled,
+            accept_x_api_key: false,
+            rate_limit_enabled: false,
+            rate_limit_tool_calls_per_minute: None,
+            quota_enabled: false,
+            quota_tool_calls: None,
+            tool_call_timeout_secs: None,
+            tool_policies: vec![],
+            mcp: crate::store::McpProfileSettings::default(),
+        };
+        let payload = TokenPayloadV1 {
+            profile_id: profile.id.clone(),
+            bindings: vec![],
+            auth: None,
+            oidc: None,
+            iat: None,
+            exp: None,
+            proxy_key: None,
+            sid: None,
+        };
+
+        let fp = profile_fingerprint(&profile);
+        let routes = Arc::new(HashMap::from([(
+            "slow".to_string(),
+            ToolRoute {
+                kind: ToolRouteKind::SharedLocal,
+                source_id: "s1".to_string(),
+                original_name: "slow".to_string(),
+            },
+        )]));
+        let surface = CachedToolsSurface {
+            tools: Arc::new(Vec::new()),
+            routes,
+            ambiguous_names: Arc::new(HashSet::new()),
+        };
+        state.tools_cache.put("p", "tok".to_string(), fp, surface);
+
+        let mut msg: ClientJsonRpcMessage = serde_json::from_value(serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "tools/call",
+            "params": { "name": "slow", "arguments": {}, "_meta": { "progressToken": "p1" } }
+        }))
+        .expect("message");
+        let resp = route_and_proxy_tools_call(
+            &state,
+            "p",
+            &profile,
+            &payload,
+            "tok".to_string(),
+            &mut msg,
+            0,
+        )
+        .await
+        .expect("tool call streams");
+        assert_eq!(resp.status(), StatusCode::OK);
+
+        // Dropping the SSE response (client disconnect) cancels the background call.
+        drop(resp);
+        let event = tokio::time::timeout(Duration::from_secs(5), audit_rx.recv())
+            .await
+            .expect("call cancelled before its 30s upstream finished")
+            .expect("audit event");
+        assert!(!event.ok);
+        assert_eq!(
+            event.error_kind.as_deref(),
+            Some("local_tool_call_cancelled")
+        );
+
+        handle.abort();
+    }
 }
@@ -1,7 +1,20 @@
 use super::McpState;
+use crate::protected_resource::{
+    ProtectedResourceMetadata, audience_names_other_resource, bearer_challenge, metadata_url,
+    request_base_url, resource_url,
+};
 use crate::session_token::{TokenAuthV1, TokenOidcV1};
 use crate::store::DataPlaneAuthMode;
-use axum::{http::HeaderMap, http::StatusCode, response::IntoResponse as _, response::Response};
+use axum::{
+    Json,
+    extract::{Path, State},
+    http::HeaderMap,
+    http::StatusCode,
+    response::IntoResponse as _,
+    response::Response,
+};
+use std::net::IpAddr;
+use std::sync::Arc;
 
 fn extract_api_key_secret(headers: &HeaderMap, accept_x_api_key: bool) -> Option<String> {
     if accept_x_api_key && let Some(v) = headers.get("x-api-key").and_then(|h| h.to_str().ok()) {
@@ -36,44 +49,154 @@ pub(super) fn unauthorized(msg: &'static str) -> Response {
     (StatusCode::UNAUTHORIZED, msg).into_response()
 }
 
+/// Route a bearer token to its validator by `iss`: an enabled tenant-configured issuer wins,
+/// otherwise the Gateway-wide issuer (if it matches).
+async fn resolve_jwt_validator(
+    state: &McpState,
+    tenant_id: &str,
+    jwt: &str,
+) -> Result<crate::oidc::OidcValidator, Response> {
+    let Some(iss) = crate::oidc::unverified_issuer(jwt) else {
+        return Err(unauthorized("Unauthorized: invalid bearer token"));
+    };
+
+    let tenant_issuer = match state.tenant_oidc.cached_issuer(tenant_id, &iss) {
+        Some(cfg) => cfg,
+        None => {
+            let cfg = state
+                .store
+                .get_tenant_oidc_issuer(tenant_id, &iss)
+                .await
+                .map_err(super::internal_error_response("load tenant oidc issuer"))?;
+            state.tenant_oidc.put_issuer(tenant_id, &iss, cfg.clone());
+            cfg
+        }
+    };
+    if let Some(cfg) = tenant_issuer.filter(|c| c.enabled) {
+        return state
+            .tenant_oidc
+            .validator(tenant_id, &cfg)
+            .await
+            .map_err(|e| {
+                tracing::warn!(error = %e, tenant_id, issuer = %iss, "tenant oidc issuer unavailable");
+                unauthorized("Unauthorized: invalid bearer token")
+            });
+    }
+
+    match state.oidc.as_ref() {
+        Some(global) if global.issuer() == iss => Ok(global.clone()),
+        _ => Err(unauthorized("Unauthorized: untrusted token issuer")),
+    }
+}
+
+/// Attach the `WWW-Authenticate` challenge (with `resource_metadata`) to a 401 so MCP clients
+/// can discover the authorization server and start the OAuth flow.
+fn with_bearer_challenge(
+    state: &McpState,
+    profile_id: &str,
+    headers: &HeaderMap,
+    mut resp: Response,
+) -> Response {
+    if resp.status() != StatusCode::UNAUTHORIZED {
+        return resp;
+    }
+    let Some(base) = request_base_url(state.public_base_url.as_deref(), headers) else {
+        return resp;
+    };
+    let error = extract_bearer_jwt(headers)
+        .is_some()
+        .then_some("invalid_token");
+    if let Some(v) = bearer_challenge(&metadata_url(&base, profile_id), error) {
+        resp.headers_mut()
+            .insert(axum::http::header::WWW_AUTHENTICATE, v);
+    }
+    resp
+}
+
 pub(super) async fn authorize_jwt_request(
     state: &McpState,
     profile: &crate::store::Profile,
     headers: &HeaderMap,
+) -> Result<TokenOidcV1, Response> {
+    authorize_jwt_request_inner(state, profile, headers)
+        .await
+        .map_err(|resp| with_bearer_challenge(state, &profile.id, headers, resp))
+}
+
+async fn authorize_jwt_request_inner(
+    state: &McpState,
+    profile: &crate::store::Profile,
+    headers: &HeaderMap,
 ) -> Result<TokenOidcV1, Response> {
     let Some(jwt) = extract_bearer_jwt(headers) else {
         return Err(unauthorized("Unauthorized: bearer token is required"));
     };
-    let Some(oidc) = state.oidc.as_ref() else {
-        return Err((
-            StatusCode::INTERNAL_SERVER_ERROR,
-            "OIDC is not configured (missing UNRELATED_GATEWAY_OIDC_ISSUER)",
-        )
-            .into_response());
-    };
+    let oidc = resolve_jwt_validator(state, &profile.tenant_id, &jwt).await?;
+
+    // Audience binding to the profile URL needs a trusted base URL; the `Host` fallback used for
+    // discovery is client-controlled and never widens the accepted audiences.
+    let bound = state
+        .public_base_url
+        .as_deref()
+        .map(|base| (base, resource_url(base, &profile.id)));
+    let resource = bound.as_ref().map(|(_, r)| r.as_str());
 
-    let claims = match oidc.validate(&jwt).await {
+    let claims = match oidc.validate_for_resource(&jwt, resource).await {
         Ok(c) => c,
         Err(e) => {
             tracing::warn!(error = %e, "oidc jwt validation failed");
             return Err(unauthorized("Unauthorized: invalid bearer token"));
         }
     };
+    if let Some((base, resource)) = &bound
+        && audience_names_other_resource(&claims, base, resource)
+    {
+        return Err(unauthorized(
+            "Unauthorized: bearer token audience is bound to another profile",
+        ));
+    }
 
-    // We intentionally avoid claim-based RBAC. We only use an identifier for lookup.
-    // Prefer `sub` (OIDC) and fall back to `oid` (Entra ID).
+    // The session is bound to a stable identifier. Prefer `sub` (OIDC) and fall back to `oid`
+    // (Entra ID).
     let subject = claims
         .get("sub")
         .and_then(serde_json::Value::as_str)
         .or_else(|| claims.get("oid").and_then(serde_json::Value::as_str))
         .ok_or_else(|| unauthorized("Unauthorized: bearer token missing subject"))?;
 
-    let allowed = state
+    let mut allowed = state
         .store
         .is_oidc_principal_allowed(&profile.tenant_id, &profile.id, oidc.issuer(), subject)
         .await
         .map_err(super::internal_error_response("check oidc principal"))?;
 
+    // Explicit principal bindings win; otherwise fall back to claims-based rules.
+    if !allowed {
+        let rules = match state
+            .oidc_rules_cache
+            .get(&profile.tenant_id, oidc.issuer())
+        {
+            Some(rules) => rules,
+            None => {
+                let rules = Arc::new(
+                    state
+                        .store
+                        .list_enabled_oidc_claim_rules(&profile.tenant_id, oidc.issuer())
+                        .await
+                        .map_err(super::internal_error_response("load oidc claim rules"))?,
+                );
+                state
+                    .oidc_rules_cache
+                    .put(&profile.tenant_id, oidc.issuer(), rules.clone());
+                rules
+            }
+        };
+        if let Some(rule) = crate::oidc_rules::find_granting_rule(&rules, &profile.id, &claims) {
+            tracing::debug!(rule_id = %rule.id, subject, "oidc claim rule granted access");
+            allowed = true;
+        }
+    }
+
     if !allowed {
         return Err(unauthorized("Unauthorized"));
     }
@@ -130,6 +253,8 @@ pub(super) async fn authenticate_api_key_on_initialize(
     Ok(TokenAuthV1 {
         tenant_id: api_key.tenant_id,
         api_key_id: api_key.api_key_id,
+        tools: api_key.tool_allowlist,
+        allowed_cidrs: api_key.allowed_cidrs,
     })
 }
 
@@ -174,7 +299,7 @@ async fn enforce_api_key_initialize_only(
         .map_err(super::internal_error_response("check api key active"))?;
 
     if !active {
-        return Err(unauthorized("Unauthorized: API key revoked"));
+        return Err(unauthorized("Unauthorized: API key revoked or expired"));
     }
 
     state
@@ -223,3 +348,133 @@ async fn enforce_api_key_every_request(
 
     Ok(())
 }
+
+/// Enforce client IP allowlists: API key (from the session), profile and tenant.
+///
+/// Every configured allowlist must match. Rejections are audited with
+/// `error_kind = "ip_not_allowed"`.
+pub(super) async fn enforce_ip_allowlists(
+    state: &McpState,
+    profile: &crate::store::Profile,
+    client_ip: Option<IpAddr>,
+    session_auth: Option<&TokenAuthV1>,
+    http_method: &'static str,
+) -> Result<(), Response> {
+    use crate::ip_allowlist::is_allowed;
+
+    let denied_by = if session_auth.is_some_and(|a| !is_allowed(&a.allowed_cidrs, client_ip)) {
+        Some("api_key")
+    } else if !is_allowed(&profile.mcp.security.allowed_cidrs, client_ip) {
+        Some("profile")
+    } else {
+        let tenant_cidrs = match state.tenant_allowlist_cache.get(&profile.tenant_id) {
+            Some(cidrs) => cidrs,
+            None => {
+                let cidrs = Arc::new(
+                    state
+                        .store
+                        .get_tenant_allowed_cidrs(&profile.tenant_id)
+                        .await
+                        .map_err(super::internal_error_response("load tenant ip allowlist"))?,
+                );
+                state
+                    .tenant_allowlist_cache
+                    .put(&profile.tenant_id, cidrs.clone());
+                cidrs
+            }
+        };
+        (!is_allowed(&tenant_cidrs, client_ip)).then_some("tenant")
+    };
+    let Some(denied_by) = denied_by else {
+        return Ok(());
+    };
+
+    tracing::warn!(
+        profile_id = %profile.id,
+        tenant_id = %profile.tenant_id,
+        client_ip = ?client_ip,
+        denied_by,
+        "client ip not allowed"
+    );
+    state
+        .audit
+        .record(crate::audit::AuditEvent {
+            tenant_id: profile.tenant_id.clone(),
+            profile_id: uuid::Uuid::parse_str(&profile.id).ok(),
+            api_key_id: session_auth.and_then(|a| uuid::Uuid::parse_str(&a.api_key_id).ok()),
+            oidc_issuer: None,
+            oidc_subject: None,
+            action: "mcp.ip_rejected".to_string(),
+            http_method: Some(http_method.to_string()),
+            http_route: Some("/{profile_id}/mcp".to_string()),
+            status_code: Some(i32::from(StatusCode::FORBIDDEN.as_u16())),
+            tool_ref: None,
+            tool_name_at_time: None,
+            ok: false,
+            duration_ms: None,
+            error_kind: Some("ip_not_allowed".to_string()),
+            error_message: None,
+            meta: serde_json::json!({
+                "deniedBy": denied_by,
+                "clientIp": client_ip.map(|ip| ip.to_string()),
+            }),
+        })
+        .await;
+
+    Err((StatusCode::FORBIDDEN, "Forbidden: client IP is not allowed").into_response())
+}
+
+/// `GET /.well-known/oauth-protected-resource/{profile_id}/mcp` (RFC 9728).
+///
+/// Served only for JWT-protected profiles. Lists the tenant's enabled issuers, then the
+/// Gateway-wide issuer, as authorization servers.
+pub(super) async fn get_protected_resource_metadata(
+    Path(profile_id): Path<String>,
+    State(state): State<Arc<McpState>>,
+    headers: HeaderMap,
+) -> Result<Response, Response> {
+    let not_found = || (StatusCode::NOT_FOUND, "profile not found").into_response();
+    if uuid::Uuid::parse_str(&profile_id)
+        .ok()
+        .and_then(|u| (u.get_version() == Some(uuid::Version::Random)).then_some(u))
+        .is_none()
+    {
+        return Err(not_found());
+    }
+    let profile = state
+        .store
+        .get_profile(&profile_id)
+        .await
+        .map_err(super::internal_error_response("load profile"))?
+        .ok_or_else(not_found)?;
+    if !matches!(
+        profile.data_plane_auth_mode,
+        DataPlaneAuthMode::JwtEveryRequest
+    ) {
+        return Err(not_found());
+    }
+
+    let base = request_base_url(state.public_base_url.as_deref(), &headers).ok_or_else(|| {
+        (StatusCode::BAD_REQUEST, "missing or invalid Host header").into_response()
+    })?;
+
+    let mut authorization_servers: Vec<String> = state
+        .store
+        .list_enabled_tenant_oidc_issuers(&profile.tenant_id)
+        .await
+        .map_err(super::internal_error_response("load tenant oidc issuers"))?
+        .into_iter()
+        .map(|i| i.issuer)
+        .collect();
+    if let Some(global) = state.oidc.as_ref()
+        && !authorization_servers.iter().any(|i| i == global.issuer())
+    {
+        authorization_servers.push(global.issuer().to_string());
+    }
+
+    Ok(Json(ProtectedResourceMetadata::new(
+        resource_url(&base, &profile.id),
+        authorization_servers,
+    ))
+    .into_response())
+}
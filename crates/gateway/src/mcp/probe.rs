@@ -127,16 +127,16 @@ async fn classify_sources(
             .await
             .unwrap_or(false);
         if is_tenant_local {
-            match Box::pin(state.tenant_catalog.list_tools(
+            match Box::pin(state.tenant_catalog.list_source_tools(
                 state.store.as_ref(),
                 &profile.tenant_id,
                 source_id,
             ))
             .await
             {
-                Ok(Some(tools)) => {
+                Ok(Some((kind, tools))) => {
                     tool_sources.push(surface::ToolSourceTools {
-                        kind: ToolRouteKind::TenantLocal,
+                        kind: surface::tenant_route_kind(kind),
                         source_id: source_id.clone(),
                         tools,
                     });
@@ -439,7 +439,12 @@ pub(crate) async fn probe_profile_surface(
     cleanup_upstream_sessions(state, &upstreams).await;
 
     // Build merged tools list (apply transforms + allowlist + collision prefixing).
-    let tool_surface = surface::merge_tools_surface(&profile.id, profile, tool_sources.clone());
+    let tool_surface = surface::merge_tools_surface(
+        &state.response_pipelines,
+        &profile.id,
+        profile,
+        tool_sources.clone(),
+    );
     let merged_tools = tool_surface.tools;
     let per_source_tool_counts = tool_surface.per_source_tool_counts;
 
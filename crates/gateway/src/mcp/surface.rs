@@ -109,6 +109,7 @@ pub(crate) struct ProbeTool {
 }
 
 pub(super) fn merge_tools_surface(
+    pipelines: &crate::response_pipeline_cache::ResponsePipelineCache,
     profile_id: &str,
     profile: &crate::store::Profile,
     sources: Vec<ToolSourceTools>,
@@ -131,6 +132,17 @@ pub(super) fn merge_tools_surface(
 
             // Schema transforms (param renames + default surface).
             let mut schema = serde_json::Value::Object(tool.input_schema.as_ref().clone());
+            if let Err(e) = profile
+                .transforms
+                .validate_against_schema(&original_name, &schema)
+            {
+                tracing::warn!(
+                    profile_id = %profile_id,
+                    source_id = %source.source_id,
+                    error = %e,
+                    "param transform does not match tool schema"
+                );
+            }
             profile
                 .transforms
                 .apply_schema_transforms(&original_name, &mut schema);
@@ -146,6 +158,46 @@ pub(super) fn merge_tools_surface(
                 continue;
             }
 
+            // Output schema must match shaped upstream results.
+            if source.kind == ToolRouteKind::Upstream && tool.output_schema.is_some() {
+                match super::tool_call::response_pipeline_for(
+                    pipelines,
+                    profile,
+                    &format!("{}:{original_name}", source.source_id),
+                ) {
+                    Ok(Some(pipeline)) => {
+                        if let Some(output_schema) = tool.output_schema.as_ref() {
+                            let mut schema =
+                                serde_json::Value::Object(output_schema.as_ref().clone());
+                            for warning in pipeline.apply_to_schema(&mut schema) {
+                                tracing::debug!(
+                                    profile_id = %profile_id,
+                                    source_id = %source.source_id,
+                                    tool = %original_name,
+                                    warning = %warning,
+                                    "response transforms: output schema widened"
+                                );
+                            }
+                            if let serde_json::Value::Object(obj) = schema {
+                                tool.output_schema = Some(Arc::new(obj));
+                            }
+                        }
+                    }
+                    Ok(None) => {}
+                    Err(e) => {
+                        // Calls fail closed; don't advertise an unshaped schema meanwhile.
+                        tracing::warn!(
+                            profile_id = %profile_id,
+                            source_id = %source.source_id,
+                            tool = %original_name,
+                            error = %e,
+                            "response transforms: dropping output schema"
+                        );
+                        tool.output_schema = None;
+                    }
+                }
+            }
+
             // Tool name transforms.
             tool.name = Cow::Owned(
                 profile
@@ -381,17 +433,9 @@ pub(super) async fn build_tools_surface(
                 tools,
             }),
     );
-    sources.extend(
-        per_tenant_local
-            .into_iter()
-            .map(|(source_id, tools)| ToolSourceTools {
-                kind: ToolRouteKind::TenantLocal,
-                source_id,
-                tools,
-            }),
-    );
+    sources.extend(per_tenant_local);
 
-    let merged = merge_tools_surface(profile_id, profile, sources);
+    let merged = merge_tools_surface(&state.response_pipelines, profile_id, profile, sources);
     Ok(CachedToolsSurface {
         tools: Arc::new(merged.tools),
         routes: Arc::new(merged.routes),
@@ -435,6 +479,14 @@ pub(super) async fn aggregate_list_tools(
     )
     .await;
 
+    // In tool search mode the contract still tracks the aggregated tools (so `list_changed` tells
+    // clients their search results may have changed), but only the meta-tools are listed.
+    let tools = if profile.mcp.tool_search.enabled {
+        crate::tool_search::meta_tools(&profile.mcp.tool_search)
+    } else {
+        tools
+    };
+
     let result = ListToolsResult {
         tools,
         ..Default::default()
@@ -451,12 +503,13 @@ pub(super) async fn aggregate_list_tools(
 pub(super) async fn aggregate_list_resources(
     state: &McpState,
     profile_id: &str,
+    profile: &crate::store::Profile,
     payload: &TokenPayloadV1,
     req_id: rmcp::model::RequestId,
     hop: u32,
 ) -> Result<Response, Response> {
     let per_upstream =
-        super::upstream::list_resources_all_upstreams(state, profile_id, payload, hop).await?;
+        list_resources_with_content(state, profile_id, profile, payload, hop).await?;
     let (merged, _per_source_counts) = merge_resources_with_collisions(per_upstream);
 
     let result = ListResourcesResult {
@@ -483,12 +536,12 @@ pub(super) async fn aggregate_list_resources(
 pub(super) async fn aggregate_list_prompts(
     state: &McpState,
     profile_id: &str,
+    profile: &crate::store::Profile,
     payload: &TokenPayloadV1,
     req_id: rmcp::model::RequestId,
     hop: u32,
 ) -> Result<Response, Response> {
-    let per_upstream =
-        super::upstream::list_prompts_all_upstreams(state, profile_id, payload, hop).await?;
+    let per_upstream = list_prompts_with_content(state, profile_id, profile, payload, hop).await?;
     let (merged, _per_source_counts) = merge_prompts_with_collisions(per_upstream);
 
     let result = ListPromptsResult {
@@ -515,17 +568,19 @@ pub(super) async fn aggregate_list_prompts(
 pub(super) async fn resolve_prompt_owner(
     state: &McpState,
     profile_id: &str,
+    profile: &crate::store::Profile,
     payload: &TokenPayloadV1,
     prompt_name: &str,
     hop: u32,
 ) -> anyhow::Result<(String, String)> {
     if let Some((upstream_id, rest)) = split_prefixed(prompt_name)
-        && payload.bindings.iter().any(|b| b.upstream == upstream_id)
+        && (payload.bindings.iter().any(|b| b.upstream == upstream_id)
+            || profile.source_ids.iter().any(|s| s == upstream_id))
     {
         return Ok((upstream_id.to_string(), rest.to_string()));
     }
 
-    let per_upstream = super::upstream::list_prompts_all_upstreams(state, profile_id, payload, hop)
+    let per_upstream = list_prompts_with_content(state, profile_id, profile, payload, hop)
         .await
         .map_err(|_| anyhow::anyhow!("failed to list prompts"))?;
 
@@ -548,6 +603,7 @@ pub(super) async fn resolve_prompt_owner(
 pub(super) async fn resolve_resource_owner(
     state: &McpState,
     profile_id: &str,
+    profile: &crate::store::Profile,
     payload: &TokenPayloadV1,
     uri: &str,
     hop: u32,
@@ -555,7 +611,7 @@ pub(super) async fn resolve_resource_owner(
     // If this is a gateway collision URN, parse the upstream id from it.
     if super::ids::parse_resource_collision_urn(uri).is_some() {
         // We need to map back to original uri; do that by listing resources and matching exposed uri.
-        let mapping = build_resource_map(state, profile_id, payload, hop).await?;
+        let mapping = build_resource_map(state, profile_id, profile, payload, hop).await?;
         if let Some((u, original)) = mapping.get(uri) {
             return Ok((u.clone(), original.clone()));
         }
@@ -563,7 +619,7 @@ pub(super) async fn resolve_resource_owner(
     }
 
     // Otherwise, resolve by listing resources and finding unique owner.
-    let mapping = build_resource_map(state, profile_id, payload, hop).await?;
+    let mapping = build_resource_map(state, profile_id, profile, payload, hop).await?;
     if let Some((u, original)) = mapping.get(uri) {
         return Ok((u.clone(), original.clone()));
     }
@@ -573,13 +629,13 @@ pub(super) async fn resolve_resource_owner(
 async fn build_resource_map(
     state: &McpState,
     profile_id: &str,
+    profile: &crate::store::Profile,
     payload: &TokenPayloadV1,
     hop: u32,
 ) -> anyhow::Result<HashMap<String, (String, String)>> {
-    let per_upstream =
-        super::upstream::list_resources_all_upstreams(state, profile_id, payload, hop)
-            .await
-            .map_err(|_| anyhow::anyhow!("failed to list resources"))?;
+    let per_upstream = list_resources_with_content(state, profile_id, profile, payload, hop)
+        .await
+        .map_err(|_| anyhow::anyhow!("failed to list resources"))?;
     let counts = count_resource_uris(&per_upstream);
 
     let mut map = HashMap::new();
@@ -597,6 +653,88 @@ async fn build_resource_map(
     Ok(map)
 }
 
+/// Tenant `content` sources attached to the profile (gateway-defined prompts and resources).
+async fn list_content_sources(
+    state: &McpState,
+    profile: &crate::store::Profile,
+) -> Vec<(String, Arc<crate::content_source::ContentSourceConfig>)> {
+    let mut out = Vec::new();
+    for source_id in &profile.source_ids {
+        if state.catalog.is_local_tool_source(source_id) {
+            continue;
+        }
+        if let Some(source) = content_source_for(state, profile, source_id).await {
+            out.push((source_id.clone(), source));
+        }
+    }
+    out
+}
+
+/// Look up a `content` source attached to the profile; `None` for any other owner id.
+pub(super) async fn content_source_for(
+    state: &McpState,
+    profile: &crate::store::Profile,
+    source_id: &str,
+) -> Option<Arc<crate::content_source::ContentSourceConfig>> {
+    if !profile.source_ids.iter().any(|s| s == source_id) {
+        return None;
+    }
+    match Box::pin(state.tenant_catalog.content_source(
+        state.store.as_ref(),
+        &profile.tenant_id,
+        source_id,
+    ))
+    .await
+    {
+        Ok(source) => source,
+        Err(e) => {
+            tracing::warn!(
+                tenant_id = %profile.tenant_id,
+                source_id = %source_id,
+                error = %e,
+                "tenant content source load failed"
+            );
+            None
+        }
+    }
+}
+
+pub(super) async fn list_resources_with_content(
+    state: &McpState,
+    profile_id: &str,
+    profile: &crate::store::Profile,
+    payload: &TokenPayloadV1,
+    hop: u32,
+) -> Result<Vec<(String, Vec<rmcp::model::Resource>)>, Response> {
+    let mut out =
+        super::upstream::list_resources_all_upstreams(state, profile_id, payload, hop).await?;
+    out.extend(
+        list_content_sources(state, profile)
+            .await
+            .into_iter()
+            .map(|(source_id, source)| (source_id, source.list_resources())),
+    );
+    Ok(out)
+}
+
+async fn list_prompts_with_content(
+    state: &McpState,
+    profile_id: &str,
+    profile: &crate::store::Profile,
+    payload: &TokenPayloadV1,
+    hop: u32,
+) -> Result<Vec<(String, Vec<rmcp::model::Prompt>)>, Response> {
+    let mut out =
+        super::upstream::list_prompts_all_upstreams(state, profile_id, payload, hop).await?;
+    out.extend(
+        list_content_sources(state, profile)
+            .await
+            .into_iter()
+            .map(|(source_id, source)| (source_id, source.list_prompts())),
+    );
+    Ok(out)
+}
+
 fn split_prefixed(s: &str) -> Option<(&str, &str)> {
     let (prefix, rest) = s.split_once(':')?;
     if prefix.is_empty() || rest.is_empty() {
@@ -621,7 +759,7 @@ fn list_tools_local_sources(
 async fn list_tools_tenant_sources(
     state: &McpState,
     profile: &crate::store::Profile,
-) -> Vec<(String, Vec<rmcp::model::Tool>)> {
+) -> Vec<ToolSourceTools> {
     let mut out = Vec::new();
     for source_id in &profile.source_ids {
         // Skip shared local sources (handled separately).
@@ -629,14 +767,18 @@ async fn list_tools_tenant_sources(
             continue;
         }
 
-        match Box::pin(state.tenant_catalog.list_tools(
+        match Box::pin(state.tenant_catalog.list_source_tools(
             state.store.as_ref(),
             &profile.tenant_id,
             source_id,
         ))
         .await
         {
-            Ok(Some(tools)) => out.push((source_id.clone(), tools)),
+            Ok(Some((kind, tools))) => out.push(ToolSourceTools {
+                kind: tenant_route_kind(kind),
+                source_id: source_id.clone(),
+                tools,
+            }),
             Ok(None) => {}
             Err(e) => {
                 tracing::warn!(
@@ -651,22 +793,34 @@ async fn list_tools_tenant_sources(
     out
 }
 
+pub(super) fn tenant_route_kind(kind: crate::store::ToolSourceKind) -> ToolRouteKind {
+    match kind {
+        crate::store::ToolSourceKind::Workflow => ToolRouteKind::Workflow,
+        crate::store::ToolSourceKind::Http
+        | crate::store::ToolSourceKind::Openapi
+        | crate::store::ToolSourceKind::Content => ToolRouteKind::TenantLocal,
+    }
+}
+
 fn tool_is_enabled(
     profile: &crate::store::Profile,
     source_id: &str,
     original_tool_name: &str,
 ) -> bool {
-    // No allowlist configured => allow all tools.
-    if profile.enabled_tools.is_empty() {
-        return true;
-    }
-
-    profile.enabled_tools.iter().any(|entry| {
+    let matches = |entry: &String| {
         let Some((src, name)) = entry.split_once(':') else {
             return false;
         };
         src == source_id && name == original_tool_name
-    })
+    };
+
+    // No allowlist configured => allow all tools.
+    let profile_allows =
+        profile.enabled_tools.is_empty() || profile.enabled_tools.iter().any(matches);
+    // A per-key allowlist can only narrow the profile's allowlist, never widen it.
+    let api_key_allows = profile.api_key_tool_allowlist.is_empty()
+        || profile.api_key_tool_allowlist.iter().any(matches);
+    profile_allows && api_key_allows
 }
 
 async fn publish_contract_event(state: &McpState, change: Option<ContractChange>) {
@@ -12,8 +12,12 @@ use rmcp::{
     transport::streamable_http_client::StreamableHttpPostResponse,
 };
 use std::borrow::Cow;
+use std::sync::Arc;
 use std::time::Instant;
 use tokio_util::sync::CancellationToken;
+use unrelated_http_tools::context::{CallContext, ProgressUpdate, ResourceReader};
+use unrelated_http_tools::response_shaping::CompiledResponsePipeline;
+use unrelated_http_tools::streaming::{EventDecoder, StreamFormat};
 use uuid::Uuid;
 
 pub(super) async fn route_and_proxy_tools_call(
@@ -39,6 +43,12 @@ pub(super) async fn route_and_proxy_tools_call(
         hop,
     };
     let (tool_name, req_id, args_value) = tools_call_extract_or_reject(ctx, message).await?;
+    let progress_token = match &*message {
+        ClientJsonRpcMessage::Request(JsonRpcRequest { request, .. }) => {
+            request.get_meta().get_progress_token()
+        }
+        _ => None,
+    };
     let (mut surface, built_now) = tools_call_get_surface_or_reject(&tool_name, ctx).await?;
     tools_call_refresh_surface_on_miss_or_reject(&tool_name, ctx, &mut surface, built_now).await?;
     let route = tools_call_resolve_route_or_reject(&tool_name, &req_id, ctx, &surface).await?;
@@ -50,6 +60,24 @@ pub(super) async fn route_and_proxy_tools_call(
     let timeout_secs = tool_call_timeout_secs_for(profile, &tool_ref);
     let timeout = std::time::Duration::from_secs(timeout_secs);
 
+    if route.kind == ToolRouteKind::Workflow {
+        return tools_call_workflow(
+            ctx,
+            &surface,
+            ToolsCallLocalInputs {
+                tool_ref: &tool_ref,
+                tool_name: &tool_name,
+                req_id: &req_id,
+                route: &route,
+                args: &args,
+                timeout,
+                timeout_secs,
+                progress_token: progress_token.clone(),
+            },
+        )
+        .await;
+    }
+
     if let Some(resp) = tools_call_try_local_or_reject(
         ctx,
         ToolsCallLocalInputs {
@@ -60,6 +88,7 @@ pub(super) async fn route_and_proxy_tools_call(
             args: &args,
             timeout,
             timeout_secs,
+            progress_token,
         },
     )
     .await?
@@ -110,6 +139,80 @@ pub(super) async fn route_and_proxy_tools_call(
     result
 }
 
+/// Handle the tool search meta-tools (`mcp.toolSearch`).
+///
+/// `search_tools` / `describe_tool` are answered from the session's tools surface. `call_tool` is
+/// unwrapped in place into a regular `tools/call` of the target tool and `Ok(None)` is returned,
+/// so the caller continues with the normal limits, routing, validation and audit pipeline.
+pub(super) async fn handle_tool_search_meta_call(
+    state: &McpState,
+    profile_id: &str,
+    profile: &crate::store::Profile,
+    payload: &TokenPayloadV1,
+    token: &str,
+    message: &mut ClientJsonRpcMessage,
+    hop: u32,
+) -> Result<Option<Response>, Response> {
+    use crate::tool_search::MetaCall;
+
+    let settings = &profile.mcp.tool_search;
+    let Some((tool_name, req_id, args_value)) = super::extract_call_tool(message) else {
+        return Ok(None);
+    };
+    let call = match crate::tool_search::parse_meta_call(&tool_name, &args_value, settings) {
+        Ok(Some(c)) => c,
+        Ok(None) => return Ok(None),
+        Err(msg) => {
+            return Err(super::jsonrpc_error_response(
+                req_id,
+                ErrorCode::INVALID_PARAMS,
+                format!("{tool_name}: {msg}"),
+            ));
+        }
+    };
+
+    let output = match call {
+        MetaCall::Call { name, arguments } => {
+            if let Some(call) = super::as_call_tool_mut(message) {
+                call.name = Cow::Owned(name);
+                call.arguments = Some(arguments);
+            }
+            return Ok(None);
+        }
+        MetaCall::Search { query, limit } => {
+            let (surface, _) = get_or_build_tools_surface_for_call(
+                state, profile_id, profile, payload, token, hop,
+            )
+            .await?;
+            let hits = crate::tool_search::search(&surface.tools, &query, limit);
+            serde_json::json!({ "tools": hits })
+        }
+        MetaCall::Describe { name } => {
+            let (surface, _) = get_or_build_tools_surface_for_call(
+                state, profile_id, profile, payload, token, hop,
+            )
+            .await?;
+            let Some(tool) = surface.tools.iter().find(|t| t.name == name) else {
+                return Err(super::jsonrpc_error_response(
+                    req_id,
+                    ErrorCode::INVALID_PARAMS,
+                    format!("unknown tool: {name}"),
+                ));
+            };
+            crate::tool_search::describe(tool)
+        }
+    };
+
+    let msg = rmcp::model::ServerJsonRpcMessage::Response(rmcp::model::JsonRpcResponse {
+        jsonrpc: JsonRpcVersion2_0,
+        id: req_id,
+        result: rmcp::model::ServerResult::CallToolResult(rmcp::model::CallToolResult::structured(
+            output,
+        )),
+    });
+    Ok(Some(super::sse_single_message(&msg)))
+}
+
 #[derive(Clone, Copy)]
 struct ToolsCallCtx<'a> {
     audit_ctx: ToolsCallAuditCtx<'a>,
@@ -320,58 +423,436 @@ struct ToolsCallLocalInputs<'a> {
     args: &'a serde_json::Map<String, serde_json::Value>,
     timeout: std::time::Duration,
     timeout_secs: u64,
+    /// Set when the client asked for `notifications/progress`.
+    progress_token: Option<rmcp::model::ProgressToken>,
 }
 
 async fn tools_call_try_local_or_reject(
     ctx: ToolsCallCtx<'_>,
     input: ToolsCallLocalInputs<'_>,
 ) -> Result<Option<Response>, Response> {
-    match execute_local_tool_call(
-        ctx.audit_ctx.state,
-        ctx.audit_ctx.profile,
-        input.route,
-        input.args,
-        input.req_id.clone(),
-        input.timeout,
-        input.timeout_secs,
+    if !matches!(
+        input.route.kind,
+        ToolRouteKind::SharedLocal | ToolRouteKind::TenantLocal
+    ) {
+        return Ok(None);
+    }
+    let call = LocalToolCall {
+        state: ctx.audit_ctx.state.clone(),
+        tenant_id: ctx.audit_ctx.profile.tenant_id.clone(),
+        route: input.route.clone(),
+        args: serde_json::Value::Object(input.args.clone()),
+        timeout: input.timeout,
+        timeout_secs: input.timeout_secs,
+    };
+    if let Some(progress_token) = input.progress_token.clone() {
+        return Ok(Some(stream_local_tool_call(
+            ctx,
+            &input,
+            call,
+            progress_token,
+        )));
+    }
+
+    let call_ctx =
+        CallContext::with_timeout(input.timeout).with_resource_reader(resource_reader(ctx));
+    let result = execute_local_tool_call(&call, &call_ctx).await;
+    let ok = result.is_ok();
+    record_local_tool_call_audit(ctx.audit_ctx, &input, ctx.started.elapsed(), ok).await;
+    let resp = super::sse_single_message(&local_tool_call_message(input.req_id.clone(), result));
+    if ok { Ok(Some(resp)) } else { Err(resp) }
+}
+
+/// Resolve URI-only resource arguments (e.g. binary request bodies) through this profile's
+/// `resources/read` routing, so local tools see the same resources the client does.
+fn resource_reader(ctx: ToolsCallCtx<'_>) -> ResourceReader {
+    let state = ctx.audit_ctx.state.clone();
+    let profile = ctx.audit_ctx.profile.clone();
+    let payload = ctx.audit_ctx.payload.clone();
+    let profile_id = ctx.audit_ctx.profile_id.to_string();
+    let token = ctx.token.to_string();
+    let hop = ctx.hop;
+    Arc::new(move |uri: String| {
+        let state = state.clone();
+        let profile = profile.clone();
+        let payload = payload.clone();
+        let profile_id = profile_id.clone();
+        let token = token.clone();
+        Box::pin(async move {
+            let mut message = ClientJsonRpcMessage::Request(JsonRpcRequest {
+                jsonrpc: JsonRpcVersion2_0,
+                id: RequestId::String(format!("resource-{}", Uuid::new_v4()).into()),
+                request: rmcp::model::ClientRequest::ReadResourceRequest(
+                    rmcp::model::ReadResourceRequest::new(rmcp::model::ReadResourceRequestParams {
+                        meta: None,
+                        uri,
+                    }),
+                ),
+            });
+            let resp = match super::route_and_proxy_resource_read(
+                &state,
+                &profile_id,
+                &profile,
+                &payload,
+                token,
+                &mut message,
+                hop,
+            )
+            .await
+            {
+                Ok(r) | Err(r) => r,
+            };
+            let result = read_tools_call_result(resp).await?;
+            result
+                .get("contents")
+                .and_then(|c| c.get(0))
+                .cloned()
+                .ok_or_else(|| "resources/read returned no contents".to_string())
+        }) as std::pin::Pin<Box<dyn std::future::Future<Output = _> + Send>>
+    })
+}
+
+/// Run a local tool call in the background and stream its progress notifications, followed by
+/// the result, as SSE. The call is audited once it finishes.
+fn stream_local_tool_call(
+    ctx: ToolsCallCtx<'_>,
+    input: &ToolsCallLocalInputs<'_>,
+    call: LocalToolCall,
+    progress_token: rmcp::model::ProgressToken,
+) -> Response {
+    let (tx, rx) = futures::channel::mpsc::unbounded::<rmcp::model::ServerJsonRpcMessage>();
+    let progress_tx = tx.clone();
+    let sink = Arc::new(move |update: ProgressUpdate| {
+        let notification =
+            rmcp::model::ProgressNotification::new(rmcp::model::ProgressNotificationParam {
+                progress_token: progress_token.clone(),
+                progress: update.progress,
+                total: update.total,
+                message: update.message,
+            });
+        let _ = progress_tx.unbounded_send(rmcp::model::ServerJsonRpcMessage::Notification(
+            rmcp::model::JsonRpcNotification {
+                jsonrpc: JsonRpcVersion2_0,
+                notification: rmcp::model::ServerNotification::ProgressNotification(notification),
+            },
+        ));
+    });
+
+    let call_ctx = CallContext::with_timeout(call.timeout)
+        .with_progress(sink)
+        .with_resource_reader(resource_reader(ctx));
+    let profile = ctx.audit_ctx.profile.clone();
+    let payload = ctx.audit_ctx.payload.clone();
+    let profile_id = ctx.audit_ctx.profile_id.to_string();
+    let tool_ref = input.tool_ref.to_string();
+    let tool_name = input.tool_name.to_string();
+    let req_id = input.req_id.clone();
+    let started = *ctx.started;
+    // Cancelled on shutdown, or when the client disconnects (the SSE body owns the drop guard).
+    let cancel = call.state.shutdown.child_token();
+    let disconnect_guard = cancel.clone().drop_guard();
+    tokio::spawn(async move {
+        let result = tokio::select! {
+            result = execute_local_tool_call(&call, &call_ctx) => Some(result),
+            () = cancel.cancelled() => None,
+        };
+        let (ok, error) = match result {
+            Some(result) => {
+                let ok = result.is_ok();
+                let _ = tx.unbounded_send(local_tool_call_message(req_id, result));
+                let error = (!ok)
+                    .then(|| AuditError::new("local_tool_call_failed", "local tool call failed"));
+                (ok, error)
+            }
+            None => (
+                false,
+                Some(AuditError::new(
+                    "local_tool_call_cancelled",
+                    "local tool call cancelled (client disconnected or shutdown)",
+                )),
+            ),
+        };
+        // Dropping the last sender ends the SSE stream.
+        drop(tx);
+        drop(call_ctx);
+
+        record_tools_call_audit(
+            ToolsCallAuditCtx {
+                state: &call.state,
+                profile: &profile,
+                payload: &payload,
+                profile_id: &profile_id,
+            },
+            ToolsCallAuditEvent {
+                tool_ref: Some(&tool_ref),
+                tool_name_at_time: Some(&tool_name),
+                ok,
+                elapsed: started.elapsed(),
+                error,
+                meta: serde_json::json!({}),
+            },
+        )
+        .await;
+    });
+
+    let events = rx.map(move |msg| {
+        let _guard = &disconnect_guard;
+        let data = serde_json::to_string(&msg).expect("valid json");
+        Ok::<_, std::convert::Infallible>(axum::response::sse::Event::default().data(data))
+    });
+    let mut resp = axum::response::Sse::new(events).into_response();
+    resp.headers_mut().insert(
+        axum::http::header::CONTENT_TYPE,
+        axum::http::HeaderValue::from_static(
+            rmcp::transport::common::http_header::EVENT_STREAM_MIME_TYPE,
+        ),
+    );
+    resp
+}
+
+async fn record_local_tool_call_audit(
+    audit_ctx: ToolsCallAuditCtx<'_>,
+    input: &ToolsCallLocalInputs<'_>,
+    elapsed: std::time::Duration,
+    ok: bool,
+) {
+    record_tools_call_audit(
+        audit_ctx,
+        ToolsCallAuditEvent {
+            tool_ref: Some(input.tool_ref),
+            tool_name_at_time: Some(input.tool_name),
+            ok,
+            elapsed,
+            error: (!ok)
+                .then(|| AuditError::new("local_tool_call_failed", "local tool call failed")),
+            meta: serde_json::json!({}),
+        },
     )
-    .await
+    .await;
+}
+
+/// Upper bound on the bytes read for a single workflow step response (JSON bodies are buffered).
+const MAX_WORKFLOW_STEP_RESPONSE_BYTES: usize = 16 * 1024 * 1024;
+
+async fn tools_call_workflow(
+    ctx: ToolsCallCtx<'_>,
+    surface: &CachedToolsSurface,
+    input: ToolsCallLocalInputs<'_>,
+) -> Result<Response, Response> {
+    let result = execute_workflow_tool_call(ctx, surface, &input).await;
+    record_tools_call_audit(
+        ctx.audit_ctx,
+        ToolsCallAuditEvent {
+            tool_ref: Some(input.tool_ref),
+            tool_name_at_time: Some(input.tool_name),
+            ok: result.is_ok(),
+            elapsed: ctx.started.elapsed(),
+            error: if result.is_ok() {
+                None
+            } else {
+                Some(AuditError::new(
+                    "workflow_tool_call_failed",
+                    "workflow tool call failed",
+                ))
+            },
+            meta: serde_json::json!({ "workflow": true }),
+        },
+    )
+    .await;
+    result
+}
+
+async fn execute_workflow_tool_call(
+    ctx: ToolsCallCtx<'_>,
+    surface: &CachedToolsSurface,
+    input: &ToolsCallLocalInputs<'_>,
+) -> Result<Response, Response> {
+    let state = ctx.audit_ctx.state;
+    let profile = ctx.audit_ctx.profile;
+    let fail = |msg: String| {
+        super::jsonrpc_error_response(input.req_id.clone(), ErrorCode::INTERNAL_ERROR, msg)
+    };
+
+    let source = match state
+        .tenant_catalog
+        .workflow_source(
+            state.store.as_ref(),
+            &profile.tenant_id,
+            &input.route.source_id,
+        )
+        .await
     {
-        Ok(Some(resp)) => {
-            record_tools_call_audit(
-                ctx.audit_ctx,
-                ToolsCallAuditEvent {
-                    tool_ref: Some(input.tool_ref),
-                    tool_name_at_time: Some(input.tool_name),
-                    ok: true,
-                    elapsed: ctx.started.elapsed(),
-                    error: None,
-                    meta: serde_json::json!({}),
-                },
-            )
-            .await;
-            Ok(Some(resp))
+        Ok(Some(s)) => s,
+        Ok(None) => return Err(fail("workflow source not available".to_string())),
+        Err(e) => return Err(fail(e.to_string())),
+    };
+    let Some(tool) = source.tool(&input.route.original_name) else {
+        return Err(fail(format!(
+            "unknown workflow tool: {}",
+            input.route.original_name
+        )));
+    };
+
+    let caller = WorkflowStepCaller { ctx, surface };
+    let fut = crate::workflow::run(tool, serde_json::Value::Object(input.args.clone()), &caller);
+    let output = match tokio::time::timeout(input.timeout, fut).await {
+        Ok(Ok(v)) => v,
+        Ok(Err(e)) => return Err(fail(format!("workflow failed: {e}"))),
+        Err(_) => {
+            return Err(fail(format!(
+                "tool call timed out after {}s",
+                input.timeout_secs
+            )));
         }
-        Ok(None) => Ok(None),
-        Err(resp) => {
-            record_tools_call_audit(
-                ctx.audit_ctx,
-                ToolsCallAuditEvent {
-                    tool_ref: Some(input.tool_ref),
-                    tool_name_at_time: Some(input.tool_name),
-                    ok: false,
-                    elapsed: ctx.started.elapsed(),
-                    error: Some(AuditError::new(
-                        "local_tool_call_failed",
-                        "local tool call failed",
-                    )),
-                    meta: serde_json::json!({}),
-                },
-            )
-            .await;
-            Err(resp)
+    };
+
+    let result = if output.is_object() {
+        rmcp::model::CallToolResult::structured(output)
+    } else {
+        rmcp::model::CallToolResult::success(vec![rmcp::model::Content::text(output.to_string())])
+    };
+    let msg = rmcp::model::ServerJsonRpcMessage::Response(rmcp::model::JsonRpcResponse {
+        jsonrpc: JsonRpcVersion2_0,
+        id: input.req_id.clone(),
+        result: rmcp::model::ServerResult::CallToolResult(result),
+    });
+    Ok(super::sse_single_message(&msg))
+}
+
+/// Runs workflow steps as regular `tools/call`s on the same session, so every step goes through
+/// routing, allowlisting, argument validation, timeouts, retries and audit.
+struct WorkflowStepCaller<'a> {
+    ctx: ToolsCallCtx<'a>,
+    surface: &'a CachedToolsSurface,
+}
+
+impl WorkflowStepCaller<'_> {
+    /// Map a stable tool ref to the name exposed on this profile's surface.
+    fn exposed_name(&self, tool_ref: &str) -> Option<(String, &ToolRoute)> {
+        self.surface.tools.iter().find_map(|t| {
+            let route = self.surface.routes.get(t.name.as_ref())?;
+            (stable_tool_ref(&route.source_id, &route.original_name) == tool_ref)
+                .then(|| (t.name.to_string(), route))
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl crate::workflow::StepCaller for WorkflowStepCaller<'_> {
+    async fn call_step(
+        &self,
+        tool_ref: &str,
+        args: serde_json::Map<String, serde_json::Value>,
+    ) -> Result<serde_json::Value, String> {
+        let Some((name, route)) = self.exposed_name(tool_ref) else {
+            return Err(format!(
+                "tool '{tool_ref}' is not available on this profile"
+            ));
+        };
+        if route.kind == ToolRouteKind::Workflow {
+            return Err("workflow steps cannot call workflow tools".to_string());
+        }
+
+        let mut message = ClientJsonRpcMessage::Request(JsonRpcRequest {
+            jsonrpc: JsonRpcVersion2_0,
+            id: RequestId::String(format!("workflow-{}", Uuid::new_v4()).into()),
+            request: rmcp::model::ClientRequest::CallToolRequest(
+                rmcp::model::CallToolRequest::new(rmcp::model::CallToolRequestParams {
+                    name: Cow::Owned(name),
+                    arguments: Some(args),
+                    meta: None,
+                    task: None,
+                }),
+            ),
+        });
+        let audit = self.ctx.audit_ctx;
+        let resp = match Box::pin(route_and_proxy_tools_call(
+            audit.state,
+            audit.profile_id,
+            audit.profile,
+            audit.payload,
+            self.ctx.token.to_string(),
+            &mut message,
+            self.ctx.hop,
+        ))
+        .await
+        {
+            Ok(r) | Err(r) => r,
+        };
+        let result = read_tools_call_result(resp).await?;
+        crate::workflow::step_output_from_call_result(&result)
+    }
+}
+
+/// Read a routed `tools/call` or `resources/read` response (JSON or SSE) and return the JSON-RPC
+/// `result`.
+pub(super) async fn read_tools_call_result(resp: Response) -> Result<serde_json::Value, String> {
+    let status = resp.status();
+    let is_sse = resp
+        .headers()
+        .get(axum::http::header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|v| {
+            v.starts_with(rmcp::transport::common::http_header::EVENT_STREAM_MIME_TYPE)
+        });
+
+    let mut body = resp.into_body().into_data_stream();
+    if is_sse {
+        // Decode events as chunks arrive; only the event in progress is buffered.
+        let mut decoder = EventDecoder::new(StreamFormat::Sse);
+        let mut read = 0usize;
+        while let Some(chunk) = body.next().await {
+            let chunk = chunk.map_err(|e| format!("read tool response: {e}"))?;
+            read += chunk.len();
+            if read > MAX_WORKFLOW_STEP_RESPONSE_BYTES {
+                return Err("tool response too large".to_string());
+            }
+            if let Some(v) = decoder
+                .push(&chunk)
+                .iter()
+                .find_map(|e| jsonrpc_response(e))
+            {
+                return jsonrpc_result(v);
+            }
+        }
+        return match decoder.finish().iter().find_map(|e| jsonrpc_response(e)) {
+            Some(v) => jsonrpc_result(v),
+            None => Err(format!("no tool result (HTTP {status})")),
+        };
+    }
+
+    let mut buf: Vec<u8> = Vec::new();
+    while let Some(chunk) = body.next().await {
+        let chunk = chunk.map_err(|e| format!("read tool response: {e}"))?;
+        buf.extend_from_slice(&chunk);
+        if buf.len() > MAX_WORKFLOW_STEP_RESPONSE_BYTES {
+            return Err("tool response too large".to_string());
         }
     }
+    let text = String::from_utf8_lossy(&buf);
+    match serde_json::from_str::<serde_json::Value>(&text).ok() {
+        Some(v) => jsonrpc_result(v),
+        None if status.is_success() => Err(format!("no tool result (HTTP {status})")),
+        None => Err(format!("HTTP {status}: {}", text.trim())),
+    }
+}
+
+/// Parse an SSE event's data as a JSON-RPC response (notifications and requests give `None`).
+fn jsonrpc_response(data: &str) -> Option<serde_json::Value> {
+    let v: serde_json::Value = serde_json::from_str(data).ok()?;
+    (v.get("id").is_some() && (v.get("result").is_some() || v.get("error").is_some())).then_some(v)
+}
+
+fn jsonrpc_result(v: serde_json::Value) -> Result<serde_json::Value, String> {
+    if let Some(err) = v.get("error") {
+        return Err(err
+            .get("message")
+            .and_then(serde_json::Value::as_str)
+            .map_or_else(|| err.to_string(), str::to_string));
+    }
+    v.get("result")
+        .cloned()
+        .ok_or_else(|| "missing tool result".to_string())
 }
 
 #[derive(Clone, Copy)]
@@ -487,80 +968,64 @@ fn build_transformed_call_args(
     args
 }
 
-async fn execute_local_tool_call(
-    state: &McpState,
-    profile: &crate::store::Profile,
-    route: &ToolRoute,
-    args: &serde_json::Map<String, serde_json::Value>,
-    req_id: RequestId,
+/// A `tools/call` of a gateway-native tool source (shared or tenant-owned).
+struct LocalToolCall {
+    state: McpState,
+    tenant_id: String,
+    route: ToolRoute,
+    args: serde_json::Value,
     timeout: std::time::Duration,
     timeout_secs: u64,
-) -> Result<Option<Response>, Response> {
-    if route.kind == ToolRouteKind::SharedLocal {
-        let fut = state.catalog.call_tool(
-            &route.source_id,
-            &route.original_name,
-            serde_json::Value::Object(args.clone()),
-        );
-        let result = match tokio::time::timeout(timeout, fut).await {
-            Ok(Ok(r)) => r,
-            Ok(Err(e)) => {
-                return Err(super::jsonrpc_error_response(
-                    req_id,
-                    ErrorCode::INTERNAL_ERROR,
-                    e.to_string(),
-                ));
-            }
-            Err(_) => {
-                return Err(super::jsonrpc_error_response(
-                    req_id,
-                    ErrorCode::INTERNAL_ERROR,
-                    format!("tool call timed out after {timeout_secs}s"),
-                ));
-            }
-        };
-        let msg = rmcp::model::ServerJsonRpcMessage::Response(rmcp::model::JsonRpcResponse {
-            jsonrpc: JsonRpcVersion2_0,
-            id: req_id,
-            result: rmcp::model::ServerResult::CallToolResult(result),
-        });
-        return Ok(Some(super::sse_single_message(&msg)));
-    }
+}
 
-    if route.kind == ToolRouteKind::TenantLocal {
+async fn execute_local_tool_call(
+    call: &LocalToolCall,
+    ctx: &CallContext,
+) -> Result<rmcp::model::CallToolResult, String> {
+    let state = &call.state;
+    let route = &call.route;
+    let result = if route.kind == ToolRouteKind::TenantLocal {
         let fut = Box::pin(state.tenant_catalog.call_tool(
             state.store.as_ref(),
-            &profile.tenant_id,
+            &call.tenant_id,
             &route.source_id,
             &route.original_name,
-            serde_json::Value::Object(args.clone()),
+            call.args.clone(),
+            ctx,
         ));
-        let result = match tokio::time::timeout(timeout, fut).await {
-            Ok(Ok(r)) => r,
-            Ok(Err(e)) => {
-                return Err(super::jsonrpc_error_response(
-                    req_id,
-                    ErrorCode::INTERNAL_ERROR,
-                    e.to_string(),
-                ));
-            }
-            Err(_) => {
-                return Err(super::jsonrpc_error_response(
-                    req_id,
-                    ErrorCode::INTERNAL_ERROR,
-                    format!("tool call timed out after {timeout_secs}s"),
-                ));
-            }
-        };
-        let msg = rmcp::model::ServerJsonRpcMessage::Response(rmcp::model::JsonRpcResponse {
+        tokio::time::timeout(call.timeout, fut).await
+    } else {
+        let fut = state.catalog.call_tool(
+            &route.source_id,
+            &route.original_name,
+            call.args.clone(),
+            ctx,
+        );
+        tokio::time::timeout(call.timeout, fut).await
+    };
+    match result {
+        Ok(Ok(r)) => Ok(r),
+        Ok(Err(e)) => Err(e.to_string()),
+        Err(_) => Err(format!("tool call timed out after {}s", call.timeout_secs)),
+    }
+}
+
+fn local_tool_call_message(
+    req_id: RequestId,
+    result: Result<rmcp::model::CallToolResult, String>,
+) -> rmcp::model::ServerJsonRpcMessage {
+    match result {
+        Ok(result) => rmcp::model::ServerJsonRpcMessage::Response(rmcp::model::JsonRpcResponse {
             jsonrpc: JsonRpcVersion2_0,
             id: req_id,
             result: rmcp::model::ServerResult::CallToolResult(result),
-        });
-        return Ok(Some(super::sse_single_message(&msg)));
+        }),
+        Err(message) => rmcp::model::ServerJsonRpcMessage::Error(rmcp::model::JsonRpcError {
+            jsonrpc: JsonRpcVersion2_0,
+            id: req_id,
+            error: rmcp::model::ErrorData::new(ErrorCode::INTERNAL_ERROR, message, None),
+        }),
     }
-
-    Ok(None)
 }
 
 fn inject_timeout_budget_meta(msg: &mut ClientJsonRpcMessage, remaining: std::time::Duration) {
@@ -682,6 +1147,7 @@ fn sse_from_upstream_stream_with_timeout_and_limits<S>(
     stream: S,
     timeout: std::time::Duration,
     limit_ctx: ToolCallSseLimitCtx,
+    shaping: Option<Arc<CompiledResponsePipeline>>,
 ) -> Response
 where
     S: Stream<Item = Result<sse_stream::Sse, sse_stream::Error>> + Send + 'static,
@@ -692,6 +1158,7 @@ where
         .take_until(limit_ctx.stop.clone().cancelled_owned())
         .then(move |evt| {
             let limit_ctx = limit_ctx.clone();
+            let shaping = shaping.clone();
             async move {
                 match evt {
                     Ok(sse) => {
@@ -706,7 +1173,10 @@ where
                         if let Some(id) = sse.id {
                             ev = ev.id(id);
                         }
-                        if let Some(data) = sse.data {
+                        if let Some(mut data) = sse.data {
+                            if let Some(pipeline) = shaping.as_deref() {
+                                data = shape_sse_tools_call_data(pipeline, data);
+                            }
                             ev = ev.data(data);
                         }
                         Some(Ok::<_, std::convert::Infallible>(ev))
@@ -882,6 +1352,12 @@ async fn proxy_upstream_tool_call_with_retry(
     let endpoint_url = super::upstream::apply_query_auth(&endpoint.url, endpoint.auth.as_ref());
     let headers = super::upstream::build_upstream_headers(endpoint.auth.as_ref(), call.hop + 1);
 
+    // Resolve shaping before calling upstream so invalid config fails closed without side effects.
+    let shaping = response_pipeline_for(&call.state.response_pipelines, call.profile, &tool_ref)
+        .map_err(|e| {
+            super::jsonrpc_error_response(call.req_id.clone(), ErrorCode::INTERNAL_ERROR, e)
+        })?;
+
     let deadline = std::time::Instant::now() + call.timeout;
     let resp = post_upstream_with_retry(
         &call,
@@ -896,7 +1372,14 @@ async fn proxy_upstream_tool_call_with_retry(
 
     match resp {
         StreamableHttpPostResponse::Accepted => Ok(StatusCode::ACCEPTED.into_response()),
-        StreamableHttpPostResponse::Json(msg, ..) => Ok(Json(msg).into_response()),
+        StreamableHttpPostResponse::Json(msg, ..) => match shaping {
+            Some(pipeline) => {
+                let mut v = serde_json::to_value(&msg).unwrap_or_default();
+                shape_jsonrpc_tools_call_response(&pipeline, &mut v);
+                Ok(Json(v).into_response())
+            }
+            None => Ok(Json(msg).into_response()),
+        },
         StreamableHttpPostResponse::Sse(stream, ..) => {
             let remaining = deadline.saturating_duration_since(std::time::Instant::now());
             if remaining.is_zero() {
@@ -914,12 +1397,99 @@ async fn proxy_upstream_tool_call_with_retry(
                 stop: CancellationToken::new(),
             };
             Ok(sse_from_upstream_stream_with_timeout_and_limits(
-                stream, remaining, limit_ctx,
+                stream, remaining, limit_ctx, shaping,
             ))
         }
     }
 }
 
+/// Effective response shaping for upstream MCP results of `tool_ref`: the profile's
+/// `mcp.responseTransforms` plus the tool policy's chain. `None` when there is nothing to apply.
+///
+/// Errors are surfaced to callers (fail closed): config is validated on write, so this is only
+/// reachable for legacy/invalid stored config.
+pub(super) fn response_pipeline_for(
+    pipelines: &crate::response_pipeline_cache::ResponsePipelineCache,
+    profile: &crate::store::Profile,
+    tool_ref: &str,
+) -> Result<Option<Arc<CompiledResponsePipeline>>, String> {
+    let chain = profile
+        .tool_policies
+        .iter()
+        .find(|p| p.tool == tool_ref)
+        .and_then(|p| p.response_transforms.as_ref());
+    pipelines
+        .get_or_compile(&profile.mcp.response_transforms, chain)
+        .map_err(|e| format!("invalid response transforms for tool '{tool_ref}': {e}"))
+}
+
+/// Shape a `CallToolResult`: `structuredContent` and JSON (object/array) text content items.
+/// Error results are only redacted: they don't match the output schema the other transforms
+/// shape for, but must not leak redacted keys either.
+fn shape_tools_call_result(pipeline: &CompiledResponsePipeline, result: &mut serde_json::Value) {
+    let is_error = result.get("isError").and_then(serde_json::Value::as_bool) == Some(true);
+    let apply = |v: &mut serde_json::Value| {
+        if is_error {
+            pipeline.redact_value(v);
+        } else {
+            pipeline.apply_to_value(v);
+        }
+    };
+    if let Some(sc) = result.get_mut("structuredContent") {
+        apply(sc);
+    }
+    let Some(content) = result
+        .get_mut("content")
+        .and_then(serde_json::Value::as_array_mut)
+    else {
+        return;
+    };
+    for item in content {
+        if item.get("type").and_then(serde_json::Value::as_str) != Some("text") {
+            continue;
+        }
+        let Some(serde_json::Value::String(text)) = item.get_mut("text") else {
+            continue;
+        };
+        let Ok(mut v) = serde_json::from_str::<serde_json::Value>(text) else {
+            continue;
+        };
+        if !(v.is_object() || v.is_array()) {
+            continue;
+        }
+        apply(&mut v);
+        if let Ok(shaped) = serde_json::to_string(&v) {
+            *text = shaped;
+        }
+    }
+}
+
+/// Shape a JSON-RPC message if it is a response carrying a `result`.
+fn shape_jsonrpc_tools_call_response(
+    pipeline: &CompiledResponsePipeline,
+    msg: &mut serde_json::Value,
+) -> bool {
+    if msg.get("id").is_none() {
+        return false;
+    }
+    let Some(result) = msg.get_mut("result") else {
+        return false;
+    };
+    shape_tools_call_result(pipeline, result);
+    true
+}
+
+/// Shape the `tools/call` response carried by an SSE event (other events pass through).
+fn shape_sse_tools_call_data(pipeline: &CompiledResponsePipeline, data: String) -> String {
+    let Ok(mut v) = serde_json::from_str::<serde_json::Value>(&data) else {
+        return data;
+    };
+    if !shape_jsonrpc_tools_call_response(pipeline, &mut v) {
+        return data;
+    }
+    serde_json::to_string(&v).unwrap_or(data)
+}
+
 fn stable_tool_ref(source_id: &str, original_tool_name: &str) -> String {
     format!("{source_id}:{original_tool_name}")
 }
@@ -1031,6 +1601,13 @@ pub(super) fn validate_tool_arguments(
     args: &serde_json::Value,
 ) -> Result<(), (String, serde_json::Value)> {
     let schema = serde_json::Value::Object((*tool.input_schema).clone());
+    // Fail closed: a schema that does not compile must not skip argument validation.
+    let compiled = jsonschema::validator_for(&schema).map_err(|e| {
+        (
+            format!("Invalid tool schema: {e}"),
+            serde_json::json!({ "type": "invalid-tool-schema", "message": e.to_string() }),
+        )
+    })?;
     let props = schema
         .get("properties")
         .and_then(|v| v.as_object())
@@ -1075,22 +1652,20 @@ pub(super) fn validate_tool_arguments(
     }
 
     // JSON Schema validation (types/constraints).
-    if let Ok(compiled) = jsonschema::validator_for(&schema) {
-        for e in compiled.iter_errors(args) {
-            // Filter out "required" errors; we already report them with a nicer shape.
-            if matches!(
-                e.kind(),
-                jsonschema::error::ValidationErrorKind::Required { .. }
-            ) {
-                continue;
-            }
-            let instance_path = e.instance_path().to_string();
-            violations.push(serde_json::json!({
-                "type": "constraint-violation",
-                "message": e.to_string(),
-                "instancePath": instance_path,
-            }));
+    for e in compiled.iter_errors(args) {
+        // Filter out "required" errors; we already report them with a nicer shape.
+        if matches!(
+            e.kind(),
+            jsonschema::error::ValidationErrorKind::Required { .. }
+        ) {
+            continue;
         }
+        let instance_path = e.instance_path().to_string();
+        violations.push(serde_json::json!({
+            "type": "constraint-violation",
+            "message": e.to_string(),
+            "instancePath": instance_path,
+        }));
     }
 
     if violations.is_empty() {
@@ -1140,3 +1715,130 @@ fn find_similar_strings(unknown: &str, known: &[&str]) -> Vec<String> {
     candidates.sort_by(|a, b| b.0.partial_cmp(&a.0).unwrap_or(std::cmp::Ordering::Equal));
     candidates.into_iter().map(|(_, s)| s).collect()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn profile_with_shaping(
+        mcp: serde_json::Value,
+        policies: serde_json::Value,
+    ) -> crate::store::Profile {
+        crate::store::Profile {
+            id: "p1".to_string(),
+            tenant_id: "t1".to_string(),
+            allow_partial_upstreams: false,
+            source_ids: vec!["u1".to_string()],
+            transforms: unrelated_tool_transforms::TransformPipeline::default(),
+            enabled_tools: vec![],
+            api_key_tool_allowlist: vec![],
+            data_plane_auth_mode: crate::store::DataPlaneAuthMode::Disabled,
+            accept_x_api_key: false,
+            rate_limit_enabled: false,
+            rate_limit_tool_calls_per_minute: None,
+            quota_enabled: false,
+            quota_tool_calls: None,
+            tool_call_timeout_secs: None,
+            tool_policies: serde_json::from_value(policies).expect("tool policies"),
+            mcp: serde_json::from_value(mcp).expect("mcp settings"),
+        }
+    }
+
+    #[test]
+    fn response_shaping_applies_profile_and_tool_chains_to_results() {
+        let profile = profile_with_shaping(
+            json!({ "responseTransforms": [{ "type": "dropNulls" }] }),
+            json!([{
+                "tool": "u1:search",
+                "responseTransforms": {
+                    "mode": "append",
+                    "pipeline": [{ "type": "pickPointers", "pointers": ["/items", "/total"] }]
+                }
+            }]),
+        );
+        let pipelines = crate::response_pipeline_cache::ResponsePipelineCache::new();
+        assert!(
+            response_pipeline_for(&pipelines, &profile, "u1:other")
+                .expect("valid")
+                .is_some()
+        );
+        let pipeline = response_pipeline_for(&pipelines, &profile, "u1:search")
+            .expect("valid")
+            .expect("pipeline");
+
+        let data = json!({
+            "jsonrpc": "2.0",
+            "id": 7,
+            "result": {
+                "content": [
+                    { "type": "text", "text": "{\"items\":[1],\"debug\":\"x\"}" },
+                    { "type": "text", "text": "plain text" }
+                ],
+                "structuredContent": { "items": [1], "total": null, "debug": "x" }
+            }
+        })
+        .to_string();
+        let shaped: serde_json::Value =
+            serde_json::from_str(&shape_sse_tools_call_data(&pipeline, data)).expect("json");
+        assert_eq!(
+            shaped["result"],
+            json!({
+                "content": [
+                    { "type": "text", "text": "{\"items\":[1]}" },
+                    { "type": "text", "text": "plain text" }
+                ],
+                "structuredContent": { "items": [1] }
+            })
+        );
+
+        // Notifications pass through unchanged; error results are not shaped.
+        let note = json!({ "jsonrpc": "2.0", "method": "notifications/progress", "params": {} })
+            .to_string();
+        assert_eq!(shape_sse_tools_call_data(&pipeline, note.clone()), note);
+        let mut err =
+            json!({ "id": 1, "result": { "isError": true, "structuredContent": { "a": null } } });
+        shape_jsonrpc_tools_call_response(&pipeline, &mut err);
+        assert_eq!(err["result"]["structuredContent"], json!({ "a": null }));
+
+        // ...but they are still redacted.
+        let redacting = profile_with_shaping(
+            json!({ "responseTransforms": [
+                { "type": "dropNulls" },
+                { "type": "redactKeys", "keys": ["token"] }
+            ] }),
+            json!([]),
+        );
+        let pipeline = response_pipeline_for(&pipelines, &redacting, "u1:search")
+            .expect("valid")
+            .expect("pipeline");
+        let mut err = json!({ "id": 2, "result": {
+            "isError": true,
+            "content": [{ "type": "text", "text": "{\"token\":\"s3cret\",\"detail\":null}" }],
+            "structuredContent": { "token": "s3cret", "detail": null }
+        } });
+        shape_jsonrpc_tools_call_response(&pipeline, &mut err);
+        assert_eq!(
+            err["result"]["structuredContent"],
+            json!({ "token": "***REDACTED***", "detail": null })
+        );
+        assert_eq!(
+            err["result"]["content"][0]["text"],
+            json!("{\"detail\":null,\"token\":\"***REDACTED***\"}")
+        );
+
+        let unshaped = profile_with_shaping(json!({}), json!([]));
+        assert!(
+            response_pipeline_for(&pipelines, &unshaped, "u1:search")
+                .expect("valid")
+                .is_none()
+        );
+
+        // Invalid stored config fails closed instead of silently skipping shaping.
+        let invalid = profile_with_shaping(
+            json!({ "responseTransforms": [{ "type": "pickPointers", "pointers": ["items"] }] }),
+            json!([]),
+        );
+        assert!(response_pipeline_for(&pipelines, &invalid, "u1:search").is_err());
+    }
+}
@@ -90,7 +90,11 @@ pub(super) fn build_upstream_headers(
         return headers;
     };
     match auth {
-        AuthConfig::None | AuthConfig::Query { .. } => {}
+        // Request signing is rejected for upstream endpoints (admin API).
+        AuthConfig::None
+        | AuthConfig::Query { .. }
+        | AuthConfig::Sigv4 { .. }
+        | AuthConfig::Hmac { .. } => {}
         AuthConfig::Bearer { token } => {
             if let Ok(v) = HeaderValue::from_str(&format!("Bearer {token}")) {
                 headers.insert(AUTHORIZATION, v);
@@ -308,16 +312,47 @@ pub(super) async fn resolve_endpoint(
     profile_id: &str,
     binding: &UpstreamSessionBinding,
 ) -> Result<Option<crate::endpoint_cache::UpstreamEndpoint>, Response> {
-    if let Some(ep) = state
+    let ep = match state
         .endpoint_cache
         .get(&binding.upstream, &binding.endpoint)
     {
-        return Ok(Some(ep));
+        Some(ep) => Some(ep),
+        None => {
+            let _ = resolve_endpoint_url(state, profile_id, binding).await?;
+            state
+                .endpoint_cache
+                .get(&binding.upstream, &binding.endpoint)
+        }
+    };
+    let (Some(mut ep), Some(oauth)) = (ep.clone(), binding.oauth.as_ref()) else {
+        return Ok(ep);
+    };
+
+    // Linked upstream accounts: the caller's (refreshed) token replaces the endpoint's auth.
+    match state
+        .upstream_oauth
+        .access_token(
+            state.store.as_ref(),
+            &oauth.tenant_id,
+            &binding.upstream,
+            &oauth.principal,
+        )
+        .await
+    {
+        Ok(Some(token)) => {
+            ep.auth = Some(AuthConfig::Bearer { token });
+            Ok(Some(ep))
+        }
+        Ok(None) => Err((
+            StatusCode::UNAUTHORIZED,
+            "Unauthorized: upstream account link required; re-link and re-initialize",
+        )
+            .into_response()),
+        Err(e) => {
+            tracing::warn!(upstream_id = %binding.upstream, error = %e, "upstream OAuth token unavailable");
+            Err((StatusCode::BAD_GATEWAY, "upstream OAuth token unavailable").into_response())
+        }
     }
-    let _ = resolve_endpoint_url(state, profile_id, binding).await?;
-    Ok(state
-        .endpoint_cache
-        .get(&binding.upstream, &binding.endpoint))
 }
 
 #[derive(Clone, Copy)]
@@ -23,6 +23,7 @@ pub struct OidcValidator {
 struct Inner {
     issuer: String,
     audiences: Vec<String>,
+    allowed_algs: Vec<Algorithm>,
     jwks_uri: String,
     leeway_secs: u64,
     refresh_after: Duration,
@@ -45,6 +46,46 @@ pub struct OidcConfig {
     pub jwks_uri: String,
     pub leeway_secs: u64,
     pub jwks_refresh_secs: u64,
+    /// Accepted JWS algorithms (asymmetric only; see [`parse_alg`]).
+    pub allowed_algs: Vec<Algorithm>,
+}
+
+/// Default accepted algorithm when none is configured.
+pub const DEFAULT_ALG: Algorithm = Algorithm::RS256;
+
+/// Parse a JWS algorithm name accepted for OIDC tokens.
+///
+/// Only asymmetric algorithms backed by JWKS keys are supported (RSA, RSA-PSS, ECDSA P-256/P-384);
+/// symmetric `HS*` algorithms are rejected.
+#[must_use]
+pub fn parse_alg(name: &str) -> Option<Algorithm> {
+    match name.trim() {
+        "RS256" => Some(Algorithm::RS256),
+        "RS384" => Some(Algorithm::RS384),
+        "RS512" => Some(Algorithm::RS512),
+        "PS256" => Some(Algorithm::PS256),
+        "PS384" => Some(Algorithm::PS384),
+        "PS512" => Some(Algorithm::PS512),
+        "ES256" => Some(Algorithm::ES256),
+        "ES384" => Some(Algorithm::ES384),
+        _ => None,
+    }
+}
+
+/// Read the `iss` claim of a JWT **without** verifying it.
+///
+/// Only used to route a token to the validator configured for its issuer; the chosen validator
+/// then verifies the signature and the issuer.
+#[must_use]
+pub fn unverified_issuer(jwt: &str) -> Option<String> {
+    let mut parts = jwt.split('.');
+    let payload_b64 = parts.nth(1)?;
+    let payload = URL_SAFE_NO_PAD.decode(payload_b64).ok()?;
+    let claims: serde_json::Value = serde_json::from_slice(&payload).ok()?;
+    claims
+        .get("iss")
+        .and_then(serde_json::Value::as_str)
+        .map(str::to_string)
 }
 
 impl OidcValidator {
@@ -60,6 +101,7 @@ impl OidcValidator {
     /// - `UNRELATED_GATEWAY_OIDC_JWKS_URI` (overrides discovery)
     /// - `UNRELATED_GATEWAY_OIDC_LEEWAY_SECS` (default: 60)
     /// - `UNRELATED_GATEWAY_OIDC_JWKS_REFRESH_SECS` (default: 600)
+    /// - `UNRELATED_GATEWAY_OIDC_ALLOWED_ALGS` (comma-separated, default: `RS256`)
     pub async fn from_env(http: reqwest::Client) -> anyhow::Result<Option<Self>> {
         let issuer = std::env::var("UNRELATED_GATEWAY_OIDC_ISSUER")
             .ok()
@@ -89,6 +131,22 @@ impl OidcValidator {
             .and_then(|s| s.parse::<u64>().ok())
             .unwrap_or(600);
 
+        let allowed_algs = match std::env::var("UNRELATED_GATEWAY_OIDC_ALLOWED_ALGS") {
+            Ok(raw) if !raw.trim().is_empty() => raw
+                .split(',')
+                .map(str::trim)
+                .filter(|s| !s.is_empty())
+                .map(|s| {
+                    parse_alg(s).ok_or_else(|| {
+                        anyhow::anyhow!(
+                            "UNRELATED_GATEWAY_OIDC_ALLOWED_ALGS: unsupported alg '{s}'"
+                        )
+                    })
+                })
+                .collect::<anyhow::Result<Vec<_>>>()?,
+            _ => vec![DEFAULT_ALG],
+        };
+
         let jwks_uri_override = std::env::var("UNRELATED_GATEWAY_OIDC_JWKS_URI")
             .ok()
             .map(|s| s.trim().to_string())
@@ -116,6 +174,7 @@ impl OidcValidator {
                 jwks_uri,
                 leeway_secs,
                 jwks_refresh_secs,
+                allowed_algs,
             },
         )))
     }
@@ -126,6 +185,7 @@ impl OidcValidator {
             inner: Arc::new(Inner {
                 issuer: cfg.issuer,
                 audiences: cfg.audiences,
+                allowed_algs: cfg.allowed_algs,
                 jwks_uri: cfg.jwks_uri,
                 leeway_secs: cfg.leeway_secs,
                 refresh_after: Duration::from_secs(cfg.jwks_refresh_secs.max(5)),
@@ -144,6 +204,16 @@ impl OidcValidator {
     ///
     /// Mode A: must be validated on every data-plane request.
     pub async fn validate(&self, jwt: &str) -> anyhow::Result<serde_json::Value> {
+        self.validate_for_resource(jwt, None).await
+    }
+
+    /// Like [`Self::validate`], additionally accepting `resource` (the profile URL, RFC 8707)
+    /// as an audience.
+    pub async fn validate_for_resource(
+        &self,
+        jwt: &str,
+        resource: Option<&str>,
+    ) -> anyhow::Result<serde_json::Value> {
         let header = jsonwebtoken::decode_header(jwt).context("decode jwt header")?;
         // `crit` indicates critical JOSE extensions that must be understood by the verifier.
         // `jsonwebtoken::Header` doesn't expose `crit`, so we decode the raw JOSE header.
@@ -154,13 +224,13 @@ impl OidcValidator {
             .kid
             .as_deref()
             .ok_or_else(|| anyhow::anyhow!("missing jwt kid"))?;
-        if header.alg != Algorithm::RS256 {
-            anyhow::bail!("unsupported jwt alg (expected RS256)");
+        if !self.inner.allowed_algs.contains(&header.alg) {
+            anyhow::bail!("unsupported jwt alg ({:?} is not allowed)", header.alg);
         }
 
         // Fast path: if we have the key, try decode without refreshing.
         if let Some(key) = self.get_key_if_present(kid).await
-            && let Ok(claims) = self.decode_with_key(jwt, &key)
+            && let Ok(claims) = self.decode_with_key(jwt, &key, header.alg, resource)
         {
             return Ok(claims);
         }
@@ -173,19 +243,26 @@ impl OidcValidator {
             .await
             .ok_or_else(|| anyhow::anyhow!("unknown jwt kid"))?;
 
-        self.decode_with_key(jwt, &key)
+        self.decode_with_key(jwt, &key, header.alg, resource)
     }
 
-    fn decode_with_key(&self, jwt: &str, key: &DecodingKey) -> anyhow::Result<serde_json::Value> {
-        let mut validation = Validation::new(Algorithm::RS256);
+    fn decode_with_key(
+        &self,
+        jwt: &str,
+        key: &DecodingKey,
+        alg: Algorithm,
+        resource: Option<&str>,
+    ) -> anyhow::Result<serde_json::Value> {
+        let mut validation = Validation::new(alg);
         validation.leeway = self.inner.leeway_secs;
         validation.validate_exp = true;
         validation.validate_nbf = true;
 
         // `jsonwebtoken` expects issuer/audience as string sets; the helpers take `&[&str]`.
         validation.set_issuer(&[self.inner.issuer.as_str()]);
-        if !self.inner.audiences.is_empty() {
-            let aud: Vec<&str> = self.inner.audiences.iter().map(String::as_str).collect();
+        let mut aud: Vec<&str> = self.inner.audiences.iter().map(String::as_str).collect();
+        aud.extend(resource);
+        if !aud.is_empty() {
             validation.set_audience(&aud);
         }
 
@@ -245,7 +322,10 @@ struct OidcDiscovery {
     jwks_uri: String,
 }
 
-async fn discover_jwks_uri(http: &reqwest::Client, issuer: &str) -> anyhow::Result<String> {
+pub(crate) async fn discover_jwks_uri(
+    http: &reqwest::Client,
+    issuer: &str,
+) -> anyhow::Result<String> {
     let issuer = issuer.trim_end_matches('/');
     let url = format!("{issuer}/.well-known/openid-configuration");
     let resp = http
@@ -287,6 +367,11 @@ struct Jwk {
     n: Option<String>,
     #[serde(default)]
     e: Option<String>,
+    // EC public key params (base64url-encoded).
+    #[serde(default)]
+    x: Option<String>,
+    #[serde(default)]
+    y: Option<String>,
 }
 
 async fn fetch_jwks(
@@ -306,25 +391,35 @@ async fn fetch_jwks(
 
     let mut out: HashMap<String, DecodingKey> = HashMap::new();
     for k in jwks.keys {
-        if k.kty != "RSA" {
-            continue;
-        }
         if let Some(use_) = &k.use_
             && use_ != "sig"
         {
             continue;
         }
         let Some(kid) = k.kid else { continue };
-        let Some(n) = k.n else { continue };
-        let Some(e) = k.e else { continue };
 
-        // `jsonwebtoken` expects the JWK base64url-encoded components.
-        let key = DecodingKey::from_rsa_components(&n, &e).context("build rsa decoding key")?;
+        // `jsonwebtoken` expects the JWK base64url-encoded components. The key family must still
+        // match the token's `alg`; a mismatch fails signature verification.
+        let key = match k.kty.as_str() {
+            "RSA" => {
+                let (Some(n), Some(e)) = (k.n, k.e) else {
+                    continue;
+                };
+                DecodingKey::from_rsa_components(&n, &e).context("build rsa decoding key")?
+            }
+            "EC" => {
+                let (Some(x), Some(y)) = (k.x, k.y) else {
+                    continue;
+                };
+                DecodingKey::from_ec_components(&x, &y).context("build ec decoding key")?
+            }
+            _ => continue,
+        };
         out.insert(kid, key);
     }
 
     if out.is_empty() {
-        anyhow::bail!("jwks contains no usable RSA keys");
+        anyhow::bail!("jwks contains no usable RSA/EC keys");
     }
 
     Ok((out, cache_ttl))
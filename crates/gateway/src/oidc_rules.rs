@@ -0,0 +1,407 @@
+use crate::store::{OidcClaimCondition, OidcClaimOp, OidcClaimRule};
+use parking_lot::RwLock;
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// Upper bound on conditions per rule (keeps per-request evaluation cheap and rules reviewable).
+pub const MAX_CONDITIONS_PER_RULE: usize = 16;
+
+/// Validate rule conditions as configured by admins.
+pub fn validate_conditions(conditions: &[OidcClaimCondition]) -> Result<(), String> {
+    if conditions.is_empty() {
+        // A rule without conditions would grant every token from the issuer; require an explicit
+        // condition instead (e.g. `iss`/`aud` equality) so the intent is visible.
+        return Err("rule must have at least one condition".to_string());
+    }
+    if conditions.len() > MAX_CONDITIONS_PER_RULE {
+        return Err(format!(
+            "too many conditions (max {MAX_CONDITIONS_PER_RULE})"
+        ));
+    }
+    for c in conditions {
+        let claim = c.claim.trim();
+        if claim.is_empty() {
+            return Err("condition claim must be non-empty".to_string());
+        }
+        if claim.starts_with('/') && claim.len() == 1 {
+            return Err("condition claim pointer must reference a claim".to_string());
+        }
+        if c.value.is_empty() {
+            return Err(format!(
+                "condition value for claim '{claim}' must be non-empty"
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Look up a claim by name, or by JSON pointer when the name starts with `/`
+/// (e.g. `/realm_access/roles` for nested Keycloak roles).
+fn lookup<'a>(claims: &'a Value, claim: &str) -> Option<&'a Value> {
+    let claim = claim.trim();
+    if claim.starts_with('/') {
+        claims.pointer(claim)
+    } else {
+        claims.get(claim)
+    }
+}
+
+/// Top-level claim name a condition refers to (`email` and `/email` both give `email`); `None` for
+/// nested pointers.
+fn top_level_claim(claim: &str) -> Option<String> {
+    let claim = claim.trim();
+    match claim.strip_prefix('/') {
+        Some(token) if !token.contains('/') => Some(token.replace("~1", "/").replace("~0", "~")),
+        Some(_) => None,
+        None => Some(claim.to_string()),
+    }
+}
+
+fn scalar_str(v: &Value) -> Option<String> {
+    match v {
+        Value::String(s) => Some(s.clone()),
+        Value::Bool(b) => Some(b.to_string()),
+        Value::Number(n) => Some(n.to_string()),
+        _ => None,
+    }
+}
+
+fn ends_with_ignore_ascii_case(s: &str, suffix: &str) -> bool {
+    s.len() >= suffix.len()
+        && s.is_char_boundary(s.len() - suffix.len())
+        && s[s.len() - suffix.len()..].eq_ignore_ascii_case(suffix)
+}
+
+/// Evaluate a single condition against token claims.
+///
+/// - `equals`: scalar claim equals `value` (numbers/booleans compare by their JSON text).
+/// - `contains`: array claim has a string element equal to `value`; a string claim is treated as a
+///   space-delimited list (like `scope`).
+/// - `endsWith`: string claim (or any string element of an array claim) ends with `value`,
+///   ignoring ASCII case. When matching `email` (by name or as `/email`), only tokens with
+///   `email_verified: true` match (a missing claim counts as unverified).
+pub fn condition_matches(condition: &OidcClaimCondition, claims: &Value) -> bool {
+    let Some(v) = lookup(claims, &condition.claim) else {
+        return false;
+    };
+    let value = condition.value.as_str();
+    match condition.op {
+        OidcClaimOp::Equals => scalar_str(v).is_some_and(|s| s == value),
+        OidcClaimOp::Contains => match v {
+            Value::Array(items) => items.iter().any(|i| i.as_str() == Some(value)),
+            Value::String(s) => s.split_whitespace().any(|t| t == value),
+            _ => false,
+        },
+        OidcClaimOp::EndsWith => {
+            if top_level_claim(&condition.claim).as_deref() == Some("email")
+                && claims.get("email_verified").and_then(Value::as_bool) != Some(true)
+            {
+                return false;
+            }
+            match v {
+                Value::String(s) => ends_with_ignore_ascii_case(s, value),
+                Value::Array(items) => items
+                    .iter()
+                    .filter_map(Value::as_str)
+                    .any(|s| ends_with_ignore_ascii_case(s, value)),
+                _ => false,
+            }
+        }
+    }
+}
+
+/// Returns true when all of the rule's conditions match (rules without conditions never match).
+pub fn rule_matches(rule: &OidcClaimRule, claims: &Value) -> bool {
+    !rule.conditions.is_empty() && rule.conditions.iter().all(|c| condition_matches(c, claims))
+}
+
+/// Returns true when `rule` grants access to `profile_id` (tenant-wide rules grant every profile).
+pub fn rule_applies_to_profile(rule: &OidcClaimRule, profile_id: &str) -> bool {
+    rule.profile_id.as_deref().is_none_or(|p| p == profile_id)
+}
+
+/// Find the first enabled rule that grants `profile_id` to a token with `claims`.
+pub fn find_granting_rule<'a>(
+    rules: &'a [OidcClaimRule],
+    profile_id: &str,
+    claims: &Value,
+) -> Option<&'a OidcClaimRule> {
+    rules
+        .iter()
+        .find(|r| r.enabled && rule_applies_to_profile(r, profile_id) && rule_matches(r, claims))
+}
+
+#[derive(Debug, Clone)]
+struct CachedRules {
+    expires_at: Instant,
+    rules: Arc<Vec<OidcClaimRule>>,
+}
+
+/// Enabled claim rules per `(tenant_id, issuer)`, so data-plane requests do not query the store
+/// every time. Rule writes invalidate the tenant via `pg_invalidation` (`oidc_claim_rules`); the
+/// TTL bounds a missed notification.
+#[derive(Clone)]
+pub struct OidcClaimRulesCache {
+    ttl: Duration,
+    inner: Arc<RwLock<HashMap<(String, String), CachedRules>>>,
+}
+
+impl OidcClaimRulesCache {
+    #[must_use]
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            inner: Arc::new(RwLock::new(HashMap::new())),
+        }
+    }
+
+    #[must_use]
+    pub fn get(&self, tenant_id: &str, issuer: &str) -> Option<Arc<Vec<OidcClaimRule>>> {
+        let key = (tenant_id.to_string(), issuer.to_string());
+        let mut map = self.inner.write();
+        let entry = map.get(&key)?;
+        if entry.expires_at <= Instant::now() {
+            map.remove(&key);
+            return None;
+        }
+        Some(entry.rules.clone())
+    }
+
+    pub fn put(&self, tenant_id: &str, issuer: &str, rules: Arc<Vec<OidcClaimRule>>) {
+        let expires_at = Instant::now() + self.ttl;
+        self.inner.write().insert(
+            (tenant_id.to_string(), issuer.to_string()),
+            CachedRules { expires_at, rules },
+        );
+    }
+
+    /// Best-effort cache invalidation for HA deployments.
+    pub fn invalidate_tenant(&self, tenant_id: &str) {
+        self.inner.write().retain(|(t, _), _| t != tenant_id);
+    }
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConditionEvaluation {
+    pub claim: String,
+    pub op: OidcClaimOp,
+    pub value: String,
+    pub matched: bool,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RuleEvaluation {
+    pub id: String,
+    pub profile_id: Option<String>,
+    pub enabled: bool,
+    /// All conditions matched (regardless of `enabled`/profile scope).
+    pub matched: bool,
+    /// The rule is enabled, matched, and applies to the requested profile (if any).
+    pub grants: bool,
+    pub conditions: Vec<ConditionEvaluation>,
+}
+
+/// Explain how each rule evaluates against `claims` (admin dry-run).
+///
+/// When `profile_id` is `None`, any enabled matching rule counts as granting.
+pub fn explain(
+    rules: &[OidcClaimRule],
+    profile_id: Option<&str>,
+    claims: &Value,
+) -> Vec<RuleEvaluation> {
+    rules
+        .iter()
+        .map(|r| {
+            let conditions: Vec<ConditionEvaluation> = r
+                .conditions
+                .iter()
+                .map(|c| ConditionEvaluation {
+                    claim: c.claim.clone(),
+                    op: c.op,
+                    value: c.value.clone(),
+                    matched: condition_matches(c, claims),
+                })
+                .collect();
+            let matched = !conditions.is_empty() && conditions.iter().all(|c| c.matched);
+            let applies = profile_id.is_none_or(|p| rule_applies_to_profile(r, p));
+            RuleEvaluation {
+                id: r.id.clone(),
+                profile_id: r.profile_id.clone(),
+                enabled: r.enabled,
+                matched,
+                grants: r.enabled && matched && applies,
+                conditions,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn cond(claim: &str, op: OidcClaimOp, value: &str) -> OidcClaimCondition {
+        OidcClaimCondition {
+            claim: claim.to_string(),
+            op,
+            value: value.to_string(),
+        }
+    }
+
+    fn rule(
+        id: &str,
+        profile_id: Option<&str>,
+        conditions: Vec<OidcClaimCondition>,
+    ) -> OidcClaimRule {
+        OidcClaimRule {
+            id: id.to_string(),
+            issuer: "https://issuer.example".to_string(),
+            profile_id: profile_id.map(ToString::to_string),
+            enabled: true,
+            description: None,
+            conditions,
+        }
+    }
+
+    #[test]
+    fn conditions_match_groups_email_and_custom_claims() {
+        let claims = json!({
+            "sub": "u1",
+            "groups": ["ml-platform", "eng"],
+            "email": "Alice@Corp.Example",
+            "email_verified": true,
+            "department": "research",
+            "level": 3,
+            "scope": "openid mcp:use",
+            "realm_access": {"roles": ["gateway-user"]},
+        });
+
+        assert!(condition_matches(
+            &cond("groups", OidcClaimOp::Contains, "ml-platform"),
+            &claims
+        ));
+        assert!(!condition_matches(
+            &cond("groups", OidcClaimOp::Contains, "ml"),
+            &claims
+        ));
+        assert!(condition_matches(
+            &cond("email", OidcClaimOp::EndsWith, "@corp.example"),
+            &claims
+        ));
+        assert!(!condition_matches(
+            &cond("email", OidcClaimOp::EndsWith, "@other.example"),
+            &claims
+        ));
+        assert!(condition_matches(
+            &cond("department", OidcClaimOp::Equals, "research"),
+            &claims
+        ));
+        assert!(condition_matches(
+            &cond("level", OidcClaimOp::Equals, "3"),
+            &claims
+        ));
+        assert!(condition_matches(
+            &cond("scope", OidcClaimOp::Contains, "mcp:use"),
+            &claims
+        ));
+        assert!(condition_matches(
+            &cond("/realm_access/roles", OidcClaimOp::Contains, "gateway-user"),
+            &claims
+        ));
+        assert!(!condition_matches(
+            &cond("missing", OidcClaimOp::Equals, "x"),
+            &claims
+        ));
+    }
+
+    #[test]
+    fn unverified_email_never_matches_suffix() {
+        let rule = cond("email", OidcClaimOp::EndsWith, "@corp.example");
+        let claims = json!({"email": "mallory@corp.example", "email_verified": false});
+        assert!(!condition_matches(&rule, &claims));
+
+        let claims = json!({"email": "mallory@corp.example"});
+        assert!(
+            !condition_matches(&rule, &claims),
+            "a missing email_verified claim counts as unverified"
+        );
+
+        let claims = json!({"email": "mallory@corp.example", "email_verified": "true"});
+        assert!(!condition_matches(&rule, &claims));
+
+        let pointer = cond("/email", OidcClaimOp::EndsWith, "@corp.example");
+        let claims = json!({"email": "mallory@corp.example", "email_verified": false});
+        assert!(!condition_matches(&pointer, &claims));
+        let claims = json!({"email": "alice@corp.example", "email_verified": true});
+        assert!(condition_matches(&pointer, &claims));
+    }
+
+    #[test]
+    fn rules_cache_is_keyed_by_tenant_and_issuer_and_invalidated_per_tenant() {
+        let cache = OidcClaimRulesCache::new(Duration::from_secs(60));
+        let rules = Arc::new(vec![rule(
+            "r1",
+            None,
+            vec![cond("groups", OidcClaimOp::Contains, "eng")],
+        )]);
+        cache.put("t1", "https://issuer.example", rules.clone());
+        cache.put("t2", "https://issuer.example", rules);
+        assert!(cache.get("t1", "https://issuer.example").is_some());
+        assert!(cache.get("t1", "https://other.example").is_none());
+
+        cache.invalidate_tenant("t1");
+        assert!(cache.get("t1", "https://issuer.example").is_none());
+        assert!(cache.get("t2", "https://issuer.example").is_some());
+
+        let expired = OidcClaimRulesCache::new(Duration::ZERO);
+        expired.put("t1", "https://issuer.example", Arc::new(Vec::new()));
+        assert!(expired.get("t1", "https://issuer.example").is_none());
+    }
+
+    #[test]
+    fn granting_rule_respects_profile_scope_and_enabled() {
+        let claims = json!({"groups": ["ml-platform"]});
+        let mut disabled = rule(
+            "disabled",
+            None,
+            vec![cond("groups", OidcClaimOp::Contains, "ml-platform")],
+        );
+        disabled.enabled = false;
+        let rules = vec![
+            disabled,
+            rule(
+                "p1-only",
+                Some("p1"),
+                vec![cond("groups", OidcClaimOp::Contains, "ml-platform")],
+            ),
+            rule("no-conditions", None, vec![]),
+        ];
+
+        assert_eq!(
+            find_granting_rule(&rules, "p1", &claims).map(|r| r.id.as_str()),
+            Some("p1-only")
+        );
+        assert!(find_granting_rule(&rules, "p2", &claims).is_none());
+
+        let explained = explain(&rules, Some("p2"), &claims);
+        assert!(explained[0].matched && !explained[0].grants);
+        assert!(explained[1].matched && !explained[1].grants);
+        assert!(!explained[2].matched);
+    }
+
+    #[test]
+    fn validate_rejects_empty_and_oversized_rules() {
+        assert!(validate_conditions(&[]).is_err());
+        assert!(validate_conditions(&[cond(" ", OidcClaimOp::Equals, "x")]).is_err());
+        assert!(validate_conditions(&[cond("groups", OidcClaimOp::Contains, "")]).is_err());
+        let many = vec![cond("groups", OidcClaimOp::Contains, "a"); MAX_CONDITIONS_PER_RULE + 1];
+        assert!(validate_conditions(&many).is_err());
+        assert!(validate_conditions(&[cond("groups", OidcClaimOp::Contains, "a")]).is_ok());
+    }
+}
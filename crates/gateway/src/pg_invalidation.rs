@@ -1,12 +1,18 @@
 use crate::audit::AuditSink;
 use crate::endpoint_cache::UpstreamEndpointCache;
+use crate::ip_allowlist::TenantAllowlistCache;
+use crate::oidc_rules::OidcClaimRulesCache;
+use crate::session_registry::SessionRegistry;
 use crate::tenant_catalog::TenantCatalog;
+use crate::tenant_oidc::TenantOidcValidators;
+use crate::tenant_token::TenantTokenRevocations;
 use crate::tools_cache::ToolSurfaceCache;
 use anyhow::Context as _;
 use serde::{Deserialize, Serialize};
 use sqlx::PgPool;
 use sqlx::postgres::PgListener;
 use std::sync::Arc;
+use std::time::Duration;
 use tokio_util::sync::CancellationToken;
 
 const INVALIDATION_CHANNEL: &str = "unrelated_gateway_invalidation_v1";
@@ -31,6 +37,23 @@ pub enum InvalidationEvent {
     Upstream {
         upstream_id: String,
     },
+    TenantTokenRevoked {
+        tenant_id: String,
+        token_id: String,
+    },
+    McpSessionTerminated {
+        session_id: String,
+    },
+    OidcClaimRules {
+        tenant_id: String,
+    },
+    TenantIpAllowlist {
+        tenant_id: String,
+    },
+    TenantOidcIssuer {
+        tenant_id: String,
+        issuer: String,
+    },
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -51,6 +74,23 @@ enum LocalInvalidationAction {
     Upstream {
         upstream_id: String,
     },
+    TenantTokenRevoked {
+        tenant_id: String,
+        token_id: String,
+    },
+    McpSessionTerminated {
+        session_id: String,
+    },
+    OidcClaimRules {
+        tenant_id: String,
+    },
+    TenantIpAllowlist {
+        tenant_id: String,
+    },
+    TenantOidcIssuer {
+        tenant_id: String,
+        issuer: String,
+    },
 }
 
 fn local_action_for_event(event: &InvalidationEvent) -> LocalInvalidationAction {
@@ -76,6 +116,34 @@ fn local_action_for_event(event: &InvalidationEvent) -> LocalInvalidationAction
         InvalidationEvent::Upstream { upstream_id } => LocalInvalidationAction::Upstream {
             upstream_id: upstream_id.clone(),
         },
+        InvalidationEvent::TenantTokenRevoked {
+            tenant_id,
+            token_id,
+        } => LocalInvalidationAction::TenantTokenRevoked {
+            tenant_id: tenant_id.clone(),
+            token_id: token_id.clone(),
+        },
+        InvalidationEvent::McpSessionTerminated { session_id } => {
+            LocalInvalidationAction::McpSessionTerminated {
+                session_id: session_id.clone(),
+            }
+        }
+        InvalidationEvent::OidcClaimRules { tenant_id } => {
+            LocalInvalidationAction::OidcClaimRules {
+                tenant_id: tenant_id.clone(),
+            }
+        }
+        InvalidationEvent::TenantIpAllowlist { tenant_id } => {
+            LocalInvalidationAction::TenantIpAllowlist {
+                tenant_id: tenant_id.clone(),
+            }
+        }
+        InvalidationEvent::TenantOidcIssuer { tenant_id, issuer } => {
+            LocalInvalidationAction::TenantOidcIssuer {
+                tenant_id: tenant_id.clone(),
+                issuer: issuer.clone(),
+            }
+        }
     }
 }
 
@@ -85,7 +153,12 @@ pub struct InvalidationDispatcher {
     tenant_catalog: Arc<TenantCatalog>,
     tools_cache: Arc<ToolSurfaceCache>,
     endpoint_cache: Arc<UpstreamEndpointCache>,
+    oidc_rules_cache: Arc<OidcClaimRulesCache>,
+    tenant_allowlist_cache: Arc<TenantAllowlistCache>,
+    tenant_oidc: Option<Arc<TenantOidcValidators>>,
     audit: Arc<dyn AuditSink>,
+    tenant_token_revocations: Arc<TenantTokenRevocations>,
+    sessions: Arc<SessionRegistry>,
 }
 
 impl InvalidationDispatcher {
@@ -95,16 +168,44 @@ impl InvalidationDispatcher {
         tools_cache: Arc<ToolSurfaceCache>,
         endpoint_cache: Arc<UpstreamEndpointCache>,
         audit: Arc<dyn AuditSink>,
+        tenant_token_revocations: Arc<TenantTokenRevocations>,
+        sessions: Arc<SessionRegistry>,
     ) -> Self {
         Self {
             pool,
             tenant_catalog,
             tools_cache,
             endpoint_cache,
+            oidc_rules_cache: Arc::new(OidcClaimRulesCache::new(Duration::ZERO)),
+            tenant_allowlist_cache: Arc::new(TenantAllowlistCache::new(Duration::ZERO)),
+            tenant_oidc: None,
             audit,
+            tenant_token_revocations,
+            sessions,
         }
     }
 
+    /// Invalidate `cache` on OIDC claim rule writes.
+    #[must_use]
+    pub fn with_oidc_rules_cache(mut self, cache: Arc<OidcClaimRulesCache>) -> Self {
+        self.oidc_rules_cache = cache;
+        self
+    }
+
+    /// Invalidate `cache` on tenant IP allowlist writes.
+    #[must_use]
+    pub fn with_tenant_allowlist_cache(mut self, cache: Arc<TenantAllowlistCache>) -> Self {
+        self.tenant_allowlist_cache = cache;
+        self
+    }
+
+    /// Drop cached tenant OIDC issuer settings and validators on issuer writes.
+    #[must_use]
+    pub fn with_tenant_oidc(mut self, validators: Arc<TenantOidcValidators>) -> Self {
+        self.tenant_oidc = Some(validators);
+        self
+    }
+
     pub fn pool(&self) -> Option<PgPool> {
         self.pool.clone()
     }
@@ -130,6 +231,27 @@ impl InvalidationDispatcher {
             LocalInvalidationAction::Upstream { upstream_id } => {
                 self.endpoint_cache.invalidate_upstream(&upstream_id);
             }
+            LocalInvalidationAction::TenantTokenRevoked {
+                tenant_id,
+                token_id,
+            } => {
+                self.tenant_token_revocations
+                    .mark_revoked(&tenant_id, &token_id);
+            }
+            LocalInvalidationAction::McpSessionTerminated { session_id } => {
+                self.sessions.mark_terminated(&session_id);
+            }
+            LocalInvalidationAction::OidcClaimRules { tenant_id } => {
+                self.oidc_rules_cache.invalidate_tenant(&tenant_id);
+            }
+            LocalInvalidationAction::TenantIpAllowlist { tenant_id } => {
+                self.tenant_allowlist_cache.invalidate_tenant(&tenant_id);
+            }
+            LocalInvalidationAction::TenantOidcIssuer { tenant_id, issuer } => {
+                if let Some(validators) = &self.tenant_oidc {
+                    validators.forget(&tenant_id, &issuer);
+                }
+            }
         }
     }
 
@@ -289,6 +411,55 @@ mod tests {
                 upstream_id: "u1".to_string()
             }
         );
+
+        assert_eq!(
+            local_action_for_event(&InvalidationEvent::TenantTokenRevoked {
+                tenant_id: "t4".to_string(),
+                token_id: "tok1".to_string(),
+            }),
+            LocalInvalidationAction::TenantTokenRevoked {
+                tenant_id: "t4".to_string(),
+                token_id: "tok1".to_string()
+            }
+        );
+
+        assert_eq!(
+            local_action_for_event(&InvalidationEvent::McpSessionTerminated {
+                session_id: "s1".to_string(),
+            }),
+            LocalInvalidationAction::McpSessionTerminated {
+                session_id: "s1".to_string()
+            }
+        );
+
+        assert_eq!(
+            local_action_for_event(&InvalidationEvent::OidcClaimRules {
+                tenant_id: "t5".to_string(),
+            }),
+            LocalInvalidationAction::OidcClaimRules {
+                tenant_id: "t5".to_string()
+            }
+        );
+
+        assert_eq!(
+            local_action_for_event(&InvalidationEvent::TenantIpAllowlist {
+                tenant_id: "t6".to_string(),
+            }),
+            LocalInvalidationAction::TenantIpAllowlist {
+                tenant_id: "t6".to_string()
+            }
+        );
+
+        assert_eq!(
+            local_action_for_event(&InvalidationEvent::TenantOidcIssuer {
+                tenant_id: "t7".to_string(),
+                issuer: "https://issuer.example".to_string(),
+            }),
+            LocalInvalidationAction::TenantOidcIssuer {
+                tenant_id: "t7".to_string(),
+                issuer: "https://issuer.example".to_string(),
+            }
+        );
     }
 
     #[test]
@@ -297,6 +468,13 @@ mod tests {
         let tools_cache = Arc::new(ToolSurfaceCache::new(Duration::from_secs(60)));
         let endpoint_cache = Arc::new(UpstreamEndpointCache::new(Duration::from_secs(60)));
         let audit = Arc::new(TestAuditSink::default());
+        let revocations = Arc::new(TenantTokenRevocations::new(Duration::from_secs(60)));
+        let oidc_rules_cache = Arc::new(OidcClaimRulesCache::new(Duration::from_secs(60)));
+        oidc_rules_cache.put("tenant-1", "https://issuer.example", Arc::new(Vec::new()));
+        let tenant_allowlist_cache = Arc::new(TenantAllowlistCache::new(Duration::from_secs(60)));
+        tenant_allowlist_cache.put("tenant-1", Arc::new(Vec::new()));
+        let tenant_oidc = Arc::new(TenantOidcValidators::new(reqwest::Client::new()));
+        tenant_oidc.put_issuer("tenant-1", "https://issuer.example", None);
 
         let dispatcher = InvalidationDispatcher::new(
             None,
@@ -304,7 +482,12 @@ mod tests {
             tools_cache.clone(),
             endpoint_cache.clone(),
             audit.clone(),
-        );
+            revocations.clone(),
+            Arc::new(SessionRegistry::in_memory()),
+        )
+        .with_oidc_rules_cache(oidc_rules_cache.clone())
+        .with_tenant_allowlist_cache(tenant_allowlist_cache.clone())
+        .with_tenant_oidc(tenant_oidc.clone());
 
         let tool = Tool::new("echo", "echo", Arc::new(JsonObject::new()));
         let cached_surface = CachedToolsSurface {
@@ -340,6 +523,10 @@ mod tests {
         dispatcher.apply_local(&InvalidationEvent::TenantAuditSettings {
             tenant_id: "tenant-1".to_string(),
         });
+        dispatcher.apply_local(&InvalidationEvent::TenantTokenRevoked {
+            tenant_id: "tenant-1".to_string(),
+            token_id: "token-1".to_string(),
+        });
 
         assert!(tools_cache.get("session-1", "fingerprint-1").is_none());
         assert!(endpoint_cache.get("upstream-1", "primary").is_none());
@@ -347,5 +534,31 @@ mod tests {
             audit.invalidated_tenants.lock().as_slice(),
             &["tenant-1".to_string()]
         );
+        assert_eq!(revocations.get("tenant-1", "token-1"), Some(true));
+        assert_eq!(revocations.get("tenant-2", "token-1"), None);
+
+        dispatcher.apply_local(&InvalidationEvent::OidcClaimRules {
+            tenant_id: "tenant-1".to_string(),
+        });
+        assert!(
+            oidc_rules_cache
+                .get("tenant-1", "https://issuer.example")
+                .is_none()
+        );
+
+        dispatcher.apply_local(&InvalidationEvent::TenantIpAllowlist {
+            tenant_id: "tenant-1".to_string(),
+        });
+        assert!(tenant_allowlist_cache.get("tenant-1").is_none());
+
+        dispatcher.apply_local(&InvalidationEvent::TenantOidcIssuer {
+            tenant_id: "tenant-1".to_string(),
+            issuer: "https://issuer.example".to_string(),
+        });
+        assert!(
+            tenant_oidc
+                .cached_issuer("tenant-1", "https://issuer.example")
+                .is_none()
+        );
     }
 }
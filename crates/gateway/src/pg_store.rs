@@ -1,10 +1,12 @@
 use crate::pg_invalidation;
 use crate::store::{
     AdminProfile, AdminStore, AdminTenant, AdminUpstream, AdminUpstreamEndpoint, ApiKeyAuth,
-    ApiKeyMetadata, AuditEventFilter, AuditEventRow, AuditStatsFilter, DataPlaneAuthMode,
-    OidcPrincipalBinding, Profile, Store, TenantAuditSettings, TenantSecretMetadata,
+    ApiKeyMetadata, ApiKeyRotation, AuditEventFilter, AuditEventRow, AuditStatsFilter,
+    DataPlaneAuthMode, OidcClaimRule, OidcPrincipalBinding, Profile, PutApiKeyInput,
+    RotateApiKeyInput, Store, TenantAuditSettings, TenantOidcIssuer, TenantSecretMetadata,
     TenantToolSource, ToolCallLimitRejection, ToolCallStatsByApiKey, ToolCallStatsByTool,
-    ToolSourceKind, ToolSourceSpec, Upstream, UpstreamEndpoint,
+    ToolSourceKind, ToolSourceSpec, Upstream, UpstreamEndpoint, UpstreamOAuthClient,
+    UpstreamOAuthLink, UpstreamOAuthLinkState, UpstreamOAuthToken,
 };
 use crate::tool_policy::ToolPolicy;
 use async_trait::async_trait;
@@ -19,6 +21,33 @@ use uuid::Uuid;
 
 use sha2::Digest as _;
 
+const SECRETS_ALGO: &str = "xchacha20poly1305";
+
+/// AAD name for a principal's upstream OAuth tokens.
+fn upstream_oauth_token_aad(upstream_id: &str, principal: &str) -> String {
+    format!("upstream-oauth-token:{upstream_id}:{principal}")
+}
+
+/// AAD name for a pending link attempt's PKCE verifier.
+fn upstream_oauth_link_aad(state_hash: &str) -> String {
+    format!("upstream-oauth-link:{state_hash}")
+}
+
+const UPSTREAM_OAUTH_CLIENT_COLUMNS: &str = "upstream_id, client_id, client_secret_ref, scopes, \
+     authorization_endpoint, token_endpoint, enabled";
+
+fn upstream_oauth_client_from_row(row: &PgRow) -> anyhow::Result<UpstreamOAuthClient> {
+    Ok(UpstreamOAuthClient {
+        upstream_id: row.try_get("upstream_id")?,
+        client_id: row.try_get("client_id")?,
+        client_secret_ref: row.try_get("client_secret_ref")?,
+        scopes: row.try_get("scopes")?,
+        authorization_endpoint: row.try_get("authorization_endpoint")?,
+        token_endpoint: row.try_get("token_endpoint")?,
+        enabled: row.try_get("enabled")?,
+    })
+}
+
 fn decode_json_opt<T: serde::de::DeserializeOwned>(
     v: Option<Value>,
 ) -> Result<Option<T>, sqlx::Error> {
@@ -145,6 +174,64 @@ impl PostgresStore {
         }
     }
 
+    /// Encrypt a value with the tenant secrets keyring: `(kid, nonce, ciphertext)`.
+    fn seal(
+        &self,
+        tenant_id: &str,
+        aad_name: &str,
+        plaintext: &str,
+    ) -> anyhow::Result<(String, [u8; 24], Vec<u8>)> {
+        let mut nonce = [0u8; 24];
+        OsRng
+            .try_fill_bytes(&mut nonce)
+            .map_err(|e| anyhow::anyhow!("generate secret nonce: {e:?}"))?;
+        let ciphertext = self
+            .secrets_cipher
+            .encrypt(tenant_id, aad_name, plaintext, nonce)?;
+        Ok((
+            self.secrets_cipher.active_kid().to_string(),
+            nonce,
+            ciphertext,
+        ))
+    }
+
+    fn open_sealed(&self, tenant_id: &str, aad_name: &str, row: &PgRow) -> anyhow::Result<String> {
+        let algo: String = row.try_get("algo")?;
+        if algo != SECRETS_ALGO {
+            anyhow::bail!("unsupported secret encryption algo '{algo}'");
+        }
+        let kid: String = row.try_get("kid")?;
+        let nonce: Vec<u8> = row.try_get("nonce")?;
+        let ciphertext: Vec<u8> = row.try_get("ciphertext")?;
+        self.secrets_cipher
+            .decrypt(tenant_id, aad_name, Some(&kid), &nonce, &ciphertext)
+    }
+
+    fn upstream_oauth_link_state_from_row(
+        &self,
+        state_hash: &str,
+        row: Option<PgRow>,
+    ) -> anyhow::Result<Option<UpstreamOAuthLinkState>> {
+        let Some(row) = row else {
+            return Ok(None);
+        };
+        if !row.try_get::<bool, _>("live")? {
+            return Ok(None);
+        }
+        let tenant_id: String = row.try_get("tenant_id")?;
+        let code_verifier =
+            self.open_sealed(&tenant_id, &upstream_oauth_link_aad(state_hash), &row)?;
+        Ok(Some(UpstreamOAuthLinkState {
+            state_hash: state_hash.to_string(),
+            tenant_id,
+            upstream_id: row.try_get("upstream_id")?,
+            principal: row.try_get("principal")?,
+            code_verifier,
+            redirect_uri: row.try_get("redirect_uri")?,
+            expires_at_unix: row.try_get("expires_at_unix")?,
+        }))
+    }
+
     pub(crate) fn set_invalidation_publisher(&self, publisher: InvalidationPublisher) {
         *self.invalidation_publisher.write() = Some(publisher);
     }
@@ -744,6 +831,7 @@ impl Store for PostgresStore {
             source_ids: all_ids,
             transforms: core.transforms,
             enabled_tools: core.enabled_tools,
+            api_key_tool_allowlist: Vec::new(),
             data_plane_auth_mode: core.data_plane_auth_mode,
             accept_x_api_key: core.auth.accept_x_api_key,
             rate_limit_enabled: core.limits.rate_limit_enabled,
@@ -838,6 +926,14 @@ where tenant_id = $1
                 ToolSourceKind::Openapi,
                 ToolSourceSpec::Openapi(serde_json::from_value(spec)?),
             ),
+            "workflow" => (
+                ToolSourceKind::Workflow,
+                ToolSourceSpec::Workflow(serde_json::from_value(spec)?),
+            ),
+            "content" => (
+                ToolSourceKind::Content,
+                ToolSourceSpec::Content(serde_json::from_value(spec)?),
+            ),
             other => {
                 return Err(anyhow::anyhow!(
                     "unknown tool source kind '{other}' for tenant '{tenant_id}' source '{id}'"
@@ -962,6 +1058,25 @@ where id = $1
         Ok(Some(serde_json::from_value(v)?))
     }
 
+    async fn get_tenant_allowed_cidrs(&self, tenant_id: &str) -> anyhow::Result<Vec<String>> {
+        let row = sqlx::query(
+            r"
+select allowed_cidrs
+from tenants
+where id = $1
+  and enabled = true
+",
+        )
+        .bind(tenant_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        match row {
+            Some(row) => Ok(row.try_get("allowed_cidrs")?),
+            None => Ok(Vec::new()),
+        }
+    }
+
     async fn authenticate_api_key(
         &self,
         tenant_id: &str,
@@ -974,11 +1089,12 @@ where id = $1
 
         let row = sqlx::query(
             r"
-select id, tenant_id
+select id, tenant_id, tool_allowlist, allowed_cidrs
 from api_keys
 where tenant_id = $1
   and secret_hash = $2
   and revoked_at is null
+  and (expires_at is null or expires_at > now())
   and (profile_id is null or profile_id = $3)
 ",
         )
@@ -994,9 +1110,13 @@ where tenant_id = $1
 
         let id: Uuid = row.try_get("id")?;
         let tenant_id: String = row.try_get("tenant_id")?;
+        let tool_allowlist: Vec<String> = row.try_get("tool_allowlist")?;
+        let allowed_cidrs: Vec<String> = row.try_get("allowed_cidrs")?;
         Ok(Some(ApiKeyAuth {
             api_key_id: id.to_string(),
             tenant_id,
+            tool_allowlist,
+            allowed_cidrs,
         }))
     }
 
@@ -1010,6 +1130,7 @@ from api_keys
 where tenant_id = $1
   and id = $2
   and revoked_at is null
+  and (expires_at is null or expires_at > now())
 ",
         )
         .bind(tenant_id)
@@ -1134,6 +1255,241 @@ limit 1
 
         Ok(exists)
     }
+
+    async fn list_enabled_oidc_claim_rules(
+        &self,
+        tenant_id: &str,
+        issuer: &str,
+    ) -> anyhow::Result<Vec<OidcClaimRule>> {
+        let rules = list_oidc_claim_rules_query(&self.pool, tenant_id, issuer).await?;
+        Ok(rules.into_iter().filter(|r| r.enabled).collect())
+    }
+
+    async fn get_tenant_oidc_issuer(
+        &self,
+        tenant_id: &str,
+        issuer: &str,
+    ) -> anyhow::Result<Option<TenantOidcIssuer>> {
+        let row = sqlx::query(&format!(
+            r"
+select {TENANT_OIDC_ISSUER_COLUMNS}
+from tenant_oidc_issuers
+where tenant_id = $1
+  and issuer = $2
+"
+        ))
+        .bind(tenant_id)
+        .bind(issuer)
+        .fetch_optional(&self.pool)
+        .await?;
+        row.as_ref().map(tenant_oidc_issuer_from_row).transpose()
+    }
+
+    async fn list_enabled_tenant_oidc_issuers(
+        &self,
+        tenant_id: &str,
+    ) -> anyhow::Result<Vec<TenantOidcIssuer>> {
+        let rows = sqlx::query(&format!(
+            r"
+select {TENANT_OIDC_ISSUER_COLUMNS}
+from tenant_oidc_issuers
+where tenant_id = $1
+  and enabled
+order by issuer asc
+"
+        ))
+        .bind(tenant_id)
+        .fetch_all(&self.pool)
+        .await?;
+        rows.iter().map(tenant_oidc_issuer_from_row).collect()
+    }
+
+    async fn get_upstream_oauth_client(
+        &self,
+        tenant_id: &str,
+        upstream_id: &str,
+    ) -> anyhow::Result<Option<UpstreamOAuthClient>> {
+        let row = sqlx::query(&format!(
+            r"
+select {UPSTREAM_OAUTH_CLIENT_COLUMNS}
+from upstream_oauth_clients
+where tenant_id = $1
+  and upstream_id = $2
+"
+        ))
+        .bind(tenant_id)
+        .bind(upstream_id)
+        .fetch_optional(&self.pool)
+        .await?;
+        row.as_ref().map(upstream_oauth_client_from_row).transpose()
+    }
+
+    async fn get_upstream_oauth_token(
+        &self,
+        tenant_id: &str,
+        upstream_id: &str,
+        principal: &str,
+    ) -> anyhow::Result<Option<UpstreamOAuthToken>> {
+        let row = sqlx::query(
+            r"
+select kid, nonce, ciphertext, algo
+from upstream_oauth_tokens
+where tenant_id = $1
+  and upstream_id = $2
+  and principal = $3
+",
+        )
+        .bind(tenant_id)
+        .bind(upstream_id)
+        .bind(principal)
+        .fetch_optional(&self.pool)
+        .await?;
+        let Some(row) = row else {
+            return Ok(None);
+        };
+        let plaintext = self.open_sealed(
+            tenant_id,
+            &upstream_oauth_token_aad(upstream_id, principal),
+            &row,
+        )?;
+        Ok(Some(serde_json::from_str(&plaintext)?))
+    }
+
+    async fn put_upstream_oauth_token(
+        &self,
+        tenant_id: &str,
+        upstream_id: &str,
+        principal: &str,
+        token: &UpstreamOAuthToken,
+    ) -> anyhow::Result<()> {
+        let plaintext = serde_json::to_string(token)?;
+        let (kid, nonce, ciphertext) = self.seal(
+            tenant_id,
+            &upstream_oauth_token_aad(upstream_id, principal),
+            &plaintext,
+        )?;
+        sqlx::query(
+            r"
+insert into upstream_oauth_tokens (
+  tenant_id, upstream_id, principal, kid, nonce, ciphertext, algo, expires_at, has_refresh_token
+)
+values ($1, $2, $3, $4, $5, $6, $7, to_timestamp($8), $9)
+on conflict (tenant_id, upstream_id, principal)
+do update
+set kid = excluded.kid,
+    nonce = excluded.nonce,
+    ciphertext = excluded.ciphertext,
+    algo = excluded.algo,
+    expires_at = excluded.expires_at,
+    has_refresh_token = excluded.has_refresh_token,
+    updated_at = now()
+",
+        )
+        .bind(tenant_id)
+        .bind(upstream_id)
+        .bind(principal)
+        .bind(kid)
+        .bind(nonce.as_slice())
+        .bind(ciphertext)
+        .bind(SECRETS_ALGO)
+        .bind(token.expires_at_unix)
+        .bind(token.refresh_token.is_some())
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    async fn put_upstream_oauth_link_state(
+        &self,
+        link: &UpstreamOAuthLinkState,
+    ) -> anyhow::Result<()> {
+        // Opportunistic cleanup keeps the table small without a background job.
+        sqlx::query("delete from upstream_oauth_link_states where expires_at < now()")
+            .execute(&self.pool)
+            .await?;
+
+        let (kid, nonce, ciphertext) = self.seal(
+            &link.tenant_id,
+            &upstream_oauth_link_aad(&link.state_hash),
+            &link.code_verifier,
+        )?;
+        sqlx::query(
+            r"
+insert into upstream_oauth_link_states (
+  state_hash, tenant_id, upstream_id, principal, kid, nonce, ciphertext, algo, redirect_uri,
+  expires_at
+)
+values ($1, $2, $3, $4, $5, $6, $7, $8, $9, to_timestamp($10))
+",
+        )
+        .bind(&link.state_hash)
+        .bind(&link.tenant_id)
+        .bind(&link.upstream_id)
+        .bind(&link.principal)
+        .bind(kid)
+        .bind(nonce.as_slice())
+        .bind(ciphertext)
+        .bind(SECRETS_ALGO)
+        .bind(&link.redirect_uri)
+        .bind(link.expires_at_unix)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    async fn take_upstream_oauth_link_state(
+        &self,
+        state_hash: &str,
+    ) -> anyhow::Result<Option<UpstreamOAuthLinkState>> {
+        let row = sqlx::query(
+            r"
+delete from upstream_oauth_link_states
+where state_hash = $1
+returning
+  tenant_id,
+  upstream_id,
+  principal,
+  kid,
+  nonce,
+  ciphertext,
+  algo,
+  redirect_uri,
+  extract(epoch from expires_at)::bigint as expires_at_unix,
+  expires_at > now() as live
+",
+        )
+        .bind(state_hash)
+        .fetch_optional(&self.pool)
+        .await?;
+        self.upstream_oauth_link_state_from_row(state_hash, row)
+    }
+
+    async fn get_upstream_oauth_link_state(
+        &self,
+        state_hash: &str,
+    ) -> anyhow::Result<Option<UpstreamOAuthLinkState>> {
+        let row = sqlx::query(
+            r"
+select
+  tenant_id,
+  upstream_id,
+  principal,
+  kid,
+  nonce,
+  ciphertext,
+  algo,
+  redirect_uri,
+  extract(epoch from expires_at)::bigint as expires_at_unix,
+  expires_at > now() as live
+from upstream_oauth_link_states
+where state_hash = $1
+",
+        )
+        .bind(state_hash)
+        .fetch_optional(&self.pool)
+        .await?;
+        self.upstream_oauth_link_state_from_row(state_hash, row)
+    }
 }
 
 #[async_trait]
@@ -1646,6 +2002,14 @@ order by created_at asc, id asc
                     ToolSourceKind::Openapi,
                     ToolSourceSpec::Openapi(serde_json::from_value(spec)?),
                 ),
+                "workflow" => (
+                    ToolSourceKind::Workflow,
+                    ToolSourceSpec::Workflow(serde_json::from_value(spec)?),
+                ),
+                "content" => (
+                    ToolSourceKind::Content,
+                    ToolSourceSpec::Content(serde_json::from_value(spec)?),
+                ),
                 other => {
                     return Err(anyhow::anyhow!(
                         "unknown tool source kind '{other}' for tenant '{tenant_id}' source '{id}'"
@@ -1683,6 +2047,8 @@ order by created_at asc, id asc
         let kind = match kind {
             ToolSourceKind::Http => "http",
             ToolSourceKind::Openapi => "openapi",
+            ToolSourceKind::Workflow => "workflow",
+            ToolSourceKind::Content => "content",
         };
 
         sqlx::query(
@@ -1870,67 +2236,25 @@ where tenant_id = $1
     }
 
     async fn list_api_keys(&self, tenant_id: &str) -> anyhow::Result<Vec<ApiKeyMetadata>> {
-        let rows = sqlx::query(
+        let rows = sqlx::query(&format!(
             r"
-select
-  id,
-  name,
-  prefix,
-  profile_id,
-  extract(epoch from created_at)::bigint as created_at_unix,
-  extract(epoch from last_used_at)::bigint as last_used_at_unix,
-  extract(epoch from revoked_at)::bigint as revoked_at_unix,
-  total_tool_calls_attempted,
-  total_requests_attempted
+select {API_KEY_METADATA_COLUMNS}
 from api_keys
 where tenant_id = $1
 order by created_at asc, id asc
-",
-        )
+"
+        ))
         .bind(tenant_id)
         .fetch_all(&self.pool)
         .await?;
 
-        let mut out = Vec::with_capacity(rows.len());
-        for row in rows {
-            let id: Uuid = row.try_get("id")?;
-            let name: String = row.try_get("name")?;
-            let prefix: String = row.try_get("prefix")?;
-            let profile_id: Option<Uuid> = row.try_get("profile_id")?;
-            let created_at_unix: i64 = row.try_get("created_at_unix")?;
-            let last_used_at_unix: Option<i64> = row.try_get("last_used_at_unix")?;
-            let revoked_at_unix: Option<i64> = row.try_get("revoked_at_unix")?;
-            let total_tool_calls_attempted: i64 = row.try_get("total_tool_calls_attempted")?;
-            let total_requests_attempted: i64 = row.try_get("total_requests_attempted")?;
-
-            out.push(ApiKeyMetadata {
-                id: id.to_string(),
-                name,
-                prefix,
-                profile_id: profile_id.map(|u| u.to_string()),
-                revoked_at_unix,
-                last_used_at_unix,
-                total_tool_calls_attempted,
-                total_requests_attempted,
-                created_at_unix,
-            });
-        }
-
-        Ok(out)
+        rows.iter().map(api_key_metadata_from_row).collect()
     }
 
-    async fn put_api_key(
-        &self,
-        tenant_id: &str,
-        api_key_id: &str,
-        profile_id: Option<&str>,
-        name: &str,
-        prefix: &str,
-        secret_hash: &str,
-    ) -> anyhow::Result<()> {
-        let api_key_id = Uuid::parse_str(api_key_id)
+    async fn put_api_key(&self, input: PutApiKeyInput<'_>) -> anyhow::Result<()> {
+        let api_key_id = Uuid::parse_str(input.api_key_id)
             .map_err(|_| anyhow::anyhow!("invalid api key id (expected UUID)"))?;
-        let profile_id = match profile_id {
+        let profile_id = match input.profile_id {
             Some(pid) => Some(
                 Uuid::parse_str(pid)
                     .map_err(|_| anyhow::anyhow!("invalid profile id (expected UUID)"))?,
@@ -1940,21 +2264,103 @@ order by created_at asc, id asc
 
         sqlx::query(
             r"
-insert into api_keys (id, tenant_id, profile_id, name, prefix, secret_hash)
-values ($1, $2, $3, $4, $5, $6)
+insert into api_keys (
+  id, tenant_id, profile_id, name, prefix, secret_hash, expires_at, tool_allowlist, allowed_cidrs
+)
+values ($1, $2, $3, $4, $5, $6, to_timestamp($7), $8, $9)
 ",
         )
         .bind(api_key_id)
-        .bind(tenant_id)
+        .bind(input.tenant_id)
         .bind(profile_id)
-        .bind(name)
-        .bind(prefix)
-        .bind(secret_hash)
+        .bind(input.name)
+        .bind(input.prefix)
+        .bind(input.secret_hash)
+        .bind(input.expires_at_unix)
+        .bind(input.tool_allowlist)
+        .bind(input.allowed_cidrs)
         .execute(&self.pool)
         .await?;
         Ok(())
     }
 
+    async fn rotate_api_key(
+        &self,
+        input: RotateApiKeyInput<'_>,
+    ) -> anyhow::Result<Option<ApiKeyRotation>> {
+        let api_key_id = Uuid::parse_str(input.api_key_id)
+            .map_err(|_| anyhow::anyhow!("invalid api key id (expected UUID)"))?;
+        let successor_id = Uuid::parse_str(input.successor_id)
+            .map_err(|_| anyhow::anyhow!("invalid api key id (expected UUID)"))?;
+
+        let mut tx: Transaction<'_, Postgres> = self.pool.begin().await?;
+
+        // Cap the old key's expiry at `now + grace` (never extend an earlier expiry).
+        let row = sqlx::query(
+            r"
+update api_keys
+set expires_at = least(
+      coalesce(expires_at, 'infinity'::timestamptz),
+      now() + $3 * interval '1 second'
+    ),
+    updated_at = now()
+where tenant_id = $1
+  and id = $2
+  and revoked_at is null
+  and (expires_at is null or expires_at > now())
+returning
+  profile_id,
+  name,
+  tool_allowlist,
+  allowed_cidrs,
+  extract(epoch from expires_at)::bigint as expires_at_unix
+",
+        )
+        .bind(input.tenant_id)
+        .bind(api_key_id)
+        .bind(input.grace_period_secs)
+        .fetch_optional(&mut *tx)
+        .await?;
+        let Some(row) = row else {
+            return Ok(None);
+        };
+        let profile_id: Option<Uuid> = row.try_get("profile_id")?;
+        let name: String = row.try_get("name")?;
+        let tool_allowlist: Vec<String> = row.try_get("tool_allowlist")?;
+        let allowed_cidrs: Vec<String> = row.try_get("allowed_cidrs")?;
+        let previous_expires_at_unix: i64 = row.try_get("expires_at_unix")?;
+
+        let row = sqlx::query(&format!(
+            r"
+insert into api_keys (
+  id, tenant_id, profile_id, name, prefix, secret_hash, expires_at, tool_allowlist, allowed_cidrs,
+  rotated_from_id
+)
+values ($1, $2, $3, $4, $5, $6, to_timestamp($7), $8, $9, $10)
+returning {API_KEY_METADATA_COLUMNS}
+"
+        ))
+        .bind(successor_id)
+        .bind(input.tenant_id)
+        .bind(profile_id)
+        .bind(&name)
+        .bind(input.successor_prefix)
+        .bind(input.successor_secret_hash)
+        .bind(input.successor_expires_at_unix)
+        .bind(&tool_allowlist)
+        .bind(&allowed_cidrs)
+        .bind(api_key_id)
+        .fetch_one(&mut *tx)
+        .await?;
+        let successor = api_key_metadata_from_row(&row)?;
+
+        tx.commit().await?;
+        Ok(Some(ApiKeyRotation {
+            successor,
+            previous_expires_at_unix,
+        }))
+    }
+
     async fn revoke_api_key(&self, tenant_id: &str, api_key_id: &str) -> anyhow::Result<bool> {
         let api_key_id = Uuid::parse_str(api_key_id)
             .map_err(|_| anyhow::anyhow!("invalid api key id (expected UUID)"))?;
@@ -1975,32 +2381,76 @@ where tenant_id = $1
         Ok(res.rows_affected() > 0)
     }
 
-    async fn list_oidc_principals(
-        &self,
-        tenant_id: &str,
-        issuer: &str,
-    ) -> anyhow::Result<Vec<OidcPrincipalBinding>> {
-        let rows = sqlx::query(
+    async fn revoke_tenant_token(&self, tenant_id: &str, token_id: &str) -> anyhow::Result<bool> {
+        let res = sqlx::query(
             r"
-select
-  subject,
-  profile_id,
-  enabled
-from oidc_principals
-where tenant_id = $1
-  and issuer = $2
-order by subject asc, profile_id asc nulls first
+insert into tenant_token_revocations (tenant_id, token_id)
+values ($1, $2)
+on conflict (tenant_id, token_id) do nothing
 ",
         )
         .bind(tenant_id)
-        .bind(issuer)
-        .fetch_all(&self.pool)
+        .bind(token_id)
+        .execute(&self.pool)
         .await?;
 
-        let mut out: Vec<OidcPrincipalBinding> = Vec::with_capacity(rows.len());
-        for r in rows {
-            let subject: String = r.try_get("subject")?;
-            let profile_id: Option<Uuid> = r.try_get("profile_id")?;
+        if res.rows_affected() > 0 {
+            let events = vec![pg_invalidation::InvalidationEvent::TenantTokenRevoked {
+                tenant_id: tenant_id.to_string(),
+                token_id: token_id.to_string(),
+            }];
+            self.emit_invalidation_events_best_effort(events);
+        }
+        Ok(res.rows_affected() > 0)
+    }
+
+    async fn is_tenant_token_revoked(
+        &self,
+        tenant_id: &str,
+        token_id: &str,
+    ) -> anyhow::Result<bool> {
+        let exists = sqlx::query(
+            r"
+select 1
+from tenant_token_revocations
+where tenant_id = $1
+  and token_id = $2
+",
+        )
+        .bind(tenant_id)
+        .bind(token_id)
+        .fetch_optional(&self.pool)
+        .await?
+        .is_some();
+        Ok(exists)
+    }
+
+    async fn list_oidc_principals(
+        &self,
+        tenant_id: &str,
+        issuer: &str,
+    ) -> anyhow::Result<Vec<OidcPrincipalBinding>> {
+        let rows = sqlx::query(
+            r"
+select
+  subject,
+  profile_id,
+  enabled
+from oidc_principals
+where tenant_id = $1
+  and issuer = $2
+order by subject asc, profile_id asc nulls first
+",
+        )
+        .bind(tenant_id)
+        .bind(issuer)
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut out: Vec<OidcPrincipalBinding> = Vec::with_capacity(rows.len());
+        for r in rows {
+            let subject: String = r.try_get("subject")?;
+            let profile_id: Option<Uuid> = r.try_get("profile_id")?;
             let enabled: bool = r.try_get("enabled")?;
             out.push(OidcPrincipalBinding {
                 issuer: issuer.to_string(),
@@ -2134,6 +2584,321 @@ where tenant_id = $1
         Ok(res.rows_affected())
     }
 
+    async fn list_tenant_oidc_issuers(
+        &self,
+        tenant_id: &str,
+    ) -> anyhow::Result<Vec<TenantOidcIssuer>> {
+        let rows = sqlx::query(&format!(
+            r"
+select {TENANT_OIDC_ISSUER_COLUMNS}
+from tenant_oidc_issuers
+where tenant_id = $1
+order by issuer asc
+"
+        ))
+        .bind(tenant_id)
+        .fetch_all(&self.pool)
+        .await?;
+        rows.iter().map(tenant_oidc_issuer_from_row).collect()
+    }
+
+    async fn put_tenant_oidc_issuer(
+        &self,
+        tenant_id: &str,
+        issuer: &TenantOidcIssuer,
+    ) -> anyhow::Result<()> {
+        sqlx::query(
+            r"
+insert into tenant_oidc_issuers (
+  tenant_id,
+  issuer,
+  audiences,
+  jwks_uri,
+  allowed_algs,
+  enabled
+)
+values ($1, $2, $3, $4, $5, $6)
+on conflict (tenant_id, issuer)
+do update
+set audiences = excluded.audiences,
+    jwks_uri = excluded.jwks_uri,
+    allowed_algs = excluded.allowed_algs,
+    enabled = excluded.enabled,
+    updated_at = now()
+",
+        )
+        .bind(tenant_id)
+        .bind(&issuer.issuer)
+        .bind(&issuer.audiences)
+        .bind(issuer.jwks_uri.as_deref())
+        .bind(&issuer.allowed_algs)
+        .bind(issuer.enabled)
+        .execute(&self.pool)
+        .await?;
+
+        self.emit_invalidation_events_best_effort(vec![
+            pg_invalidation::InvalidationEvent::TenantOidcIssuer {
+                tenant_id: tenant_id.to_string(),
+                issuer: issuer.issuer.clone(),
+            },
+        ]);
+        Ok(())
+    }
+
+    async fn delete_tenant_oidc_issuer(
+        &self,
+        tenant_id: &str,
+        issuer: &str,
+    ) -> anyhow::Result<bool> {
+        let res = sqlx::query(
+            r"
+delete from tenant_oidc_issuers
+where tenant_id = $1
+  and issuer = $2
+",
+        )
+        .bind(tenant_id)
+        .bind(issuer)
+        .execute(&self.pool)
+        .await?;
+
+        if res.rows_affected() > 0 {
+            self.emit_invalidation_events_best_effort(vec![
+                pg_invalidation::InvalidationEvent::TenantOidcIssuer {
+                    tenant_id: tenant_id.to_string(),
+                    issuer: issuer.to_string(),
+                },
+            ]);
+        }
+        Ok(res.rows_affected() > 0)
+    }
+
+    async fn list_upstream_oauth_clients(
+        &self,
+        tenant_id: &str,
+    ) -> anyhow::Result<Vec<UpstreamOAuthClient>> {
+        let rows = sqlx::query(&format!(
+            r"
+select {UPSTREAM_OAUTH_CLIENT_COLUMNS}
+from upstream_oauth_clients
+where tenant_id = $1
+order by upstream_id asc
+"
+        ))
+        .bind(tenant_id)
+        .fetch_all(&self.pool)
+        .await?;
+        rows.iter().map(upstream_oauth_client_from_row).collect()
+    }
+
+    async fn put_upstream_oauth_client(
+        &self,
+        tenant_id: &str,
+        client: &UpstreamOAuthClient,
+    ) -> anyhow::Result<()> {
+        sqlx::query(
+            r"
+insert into upstream_oauth_clients (
+  tenant_id,
+  upstream_id,
+  client_id,
+  client_secret_ref,
+  scopes,
+  authorization_endpoint,
+  token_endpoint,
+  enabled
+)
+values ($1, $2, $3, $4, $5, $6, $7, $8)
+on conflict (tenant_id, upstream_id)
+do update
+set client_id = excluded.client_id,
+    client_secret_ref = excluded.client_secret_ref,
+    scopes = excluded.scopes,
+    authorization_endpoint = excluded.authorization_endpoint,
+    token_endpoint = excluded.token_endpoint,
+    enabled = excluded.enabled,
+    updated_at = now()
+",
+        )
+        .bind(tenant_id)
+        .bind(&client.upstream_id)
+        .bind(&client.client_id)
+        .bind(client.client_secret_ref.as_deref())
+        .bind(&client.scopes)
+        .bind(client.authorization_endpoint.as_deref())
+        .bind(client.token_endpoint.as_deref())
+        .bind(client.enabled)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    async fn delete_upstream_oauth_client(
+        &self,
+        tenant_id: &str,
+        upstream_id: &str,
+    ) -> anyhow::Result<bool> {
+        let res = sqlx::query(
+            r"
+delete from upstream_oauth_clients
+where tenant_id = $1
+  and upstream_id = $2
+",
+        )
+        .bind(tenant_id)
+        .bind(upstream_id)
+        .execute(&self.pool)
+        .await?;
+        Ok(res.rows_affected() > 0)
+    }
+
+    async fn list_upstream_oauth_links(
+        &self,
+        tenant_id: &str,
+        upstream_id: &str,
+    ) -> anyhow::Result<Vec<UpstreamOAuthLink>> {
+        let rows = sqlx::query(
+            r"
+select
+  principal,
+  extract(epoch from expires_at)::bigint as expires_at_unix,
+  has_refresh_token,
+  extract(epoch from updated_at)::bigint as updated_at_unix
+from upstream_oauth_tokens
+where tenant_id = $1
+  and upstream_id = $2
+order by principal asc
+",
+        )
+        .bind(tenant_id)
+        .bind(upstream_id)
+        .fetch_all(&self.pool)
+        .await?;
+        rows.iter()
+            .map(|r| {
+                Ok(UpstreamOAuthLink {
+                    principal: r.try_get("principal")?,
+                    expires_at_unix: r.try_get("expires_at_unix")?,
+                    has_refresh_token: r.try_get("has_refresh_token")?,
+                    updated_at_unix: r.try_get("updated_at_unix")?,
+                })
+            })
+            .collect()
+    }
+
+    async fn delete_upstream_oauth_link(
+        &self,
+        tenant_id: &str,
+        upstream_id: &str,
+        principal: &str,
+    ) -> anyhow::Result<bool> {
+        let res = sqlx::query(
+            r"
+delete from upstream_oauth_tokens
+where tenant_id = $1
+  and upstream_id = $2
+  and principal = $3
+",
+        )
+        .bind(tenant_id)
+        .bind(upstream_id)
+        .bind(principal)
+        .execute(&self.pool)
+        .await?;
+        Ok(res.rows_affected() > 0)
+    }
+
+    async fn list_oidc_claim_rules(
+        &self,
+        tenant_id: &str,
+        issuer: &str,
+    ) -> anyhow::Result<Vec<OidcClaimRule>> {
+        list_oidc_claim_rules_query(&self.pool, tenant_id, issuer).await
+    }
+
+    async fn put_oidc_claim_rule(
+        &self,
+        tenant_id: &str,
+        rule: &OidcClaimRule,
+    ) -> anyhow::Result<()> {
+        let profile_id = match rule.profile_id.as_deref() {
+            Some(pid) => Some(
+                Uuid::parse_str(pid)
+                    .map_err(|_| anyhow::anyhow!("invalid profile id (expected UUID)"))?,
+            ),
+            None => None,
+        };
+        let conditions = serde_json::to_value(&rule.conditions)?;
+
+        sqlx::query(
+            r"
+insert into oidc_claim_rules (
+  tenant_id,
+  issuer,
+  id,
+  profile_id,
+  description,
+  conditions,
+  enabled
+)
+values ($1, $2, $3, $4, $5, $6, $7)
+on conflict (tenant_id, issuer, id)
+do update
+set profile_id = excluded.profile_id,
+    description = excluded.description,
+    conditions = excluded.conditions,
+    enabled = excluded.enabled,
+    updated_at = now()
+",
+        )
+        .bind(tenant_id)
+        .bind(&rule.issuer)
+        .bind(&rule.id)
+        .bind(profile_id)
+        .bind(rule.description.as_deref())
+        .bind(conditions)
+        .bind(rule.enabled)
+        .execute(&self.pool)
+        .await?;
+
+        self.emit_invalidation_events_best_effort(vec![
+            pg_invalidation::InvalidationEvent::OidcClaimRules {
+                tenant_id: tenant_id.to_string(),
+            },
+        ]);
+        Ok(())
+    }
+
+    async fn delete_oidc_claim_rule(
+        &self,
+        tenant_id: &str,
+        issuer: &str,
+        rule_id: &str,
+    ) -> anyhow::Result<bool> {
+        let res = sqlx::query(
+            r"
+delete from oidc_claim_rules
+where tenant_id = $1
+  and issuer = $2
+  and id = $3
+",
+        )
+        .bind(tenant_id)
+        .bind(issuer)
+        .bind(rule_id)
+        .execute(&self.pool)
+        .await?;
+
+        if res.rows_affected() > 0 {
+            self.emit_invalidation_events_best_effort(vec![
+                pg_invalidation::InvalidationEvent::OidcClaimRules {
+                    tenant_id: tenant_id.to_string(),
+                },
+            ]);
+        }
+        Ok(res.rows_affected() > 0)
+    }
+
     async fn get_tenant_transport_limits(
         &self,
         tenant_id: &str,
@@ -2180,6 +2945,54 @@ where id = $1
         Ok(())
     }
 
+    async fn get_tenant_ip_allowlist(
+        &self,
+        tenant_id: &str,
+    ) -> anyhow::Result<Option<Vec<String>>> {
+        let row = sqlx::query(
+            r"
+select allowed_cidrs
+from tenants
+where id = $1
+",
+        )
+        .bind(tenant_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        row.map(|row| row.try_get("allowed_cidrs"))
+            .transpose()
+            .map_err(Into::into)
+    }
+
+    async fn put_tenant_ip_allowlist(
+        &self,
+        tenant_id: &str,
+        allowed_cidrs: &[String],
+    ) -> anyhow::Result<()> {
+        let res = sqlx::query(
+            r"
+update tenants
+set allowed_cidrs = $2
+where id = $1
+",
+        )
+        .bind(tenant_id)
+        .bind(allowed_cidrs)
+        .execute(&self.pool)
+        .await?;
+
+        if res.rows_affected() == 0 {
+            anyhow::bail!("tenant not found");
+        }
+        self.emit_invalidation_events_best_effort(vec![
+            pg_invalidation::InvalidationEvent::TenantIpAllowlist {
+                tenant_id: tenant_id.to_string(),
+            },
+        ]);
+        Ok(())
+    }
+
     async fn get_tenant_audit_settings(
         &self,
         tenant_id: &str,
@@ -2570,6 +3383,102 @@ const fn data_plane_auth_mode_to_db(mode: DataPlaneAuthMode) -> &'static str {
     }
 }
 
+const API_KEY_METADATA_COLUMNS: &str = r"
+  id,
+  name,
+  prefix,
+  profile_id,
+  extract(epoch from created_at)::bigint as created_at_unix,
+  extract(epoch from last_used_at)::bigint as last_used_at_unix,
+  extract(epoch from revoked_at)::bigint as revoked_at_unix,
+  extract(epoch from expires_at)::bigint as expires_at_unix,
+  total_tool_calls_attempted,
+  total_requests_attempted,
+  tool_allowlist,
+  allowed_cidrs,
+  rotated_from_id";
+
+fn api_key_metadata_from_row(row: &PgRow) -> anyhow::Result<ApiKeyMetadata> {
+    let id: Uuid = row.try_get("id")?;
+    let profile_id: Option<Uuid> = row.try_get("profile_id")?;
+    let rotated_from_id: Option<Uuid> = row.try_get("rotated_from_id")?;
+    Ok(ApiKeyMetadata {
+        id: id.to_string(),
+        name: row.try_get("name")?,
+        prefix: row.try_get("prefix")?,
+        profile_id: profile_id.map(|u| u.to_string()),
+        revoked_at_unix: row.try_get("revoked_at_unix")?,
+        last_used_at_unix: row.try_get("last_used_at_unix")?,
+        total_tool_calls_attempted: row.try_get("total_tool_calls_attempted")?,
+        total_requests_attempted: row.try_get("total_requests_attempted")?,
+        created_at_unix: row.try_get("created_at_unix")?,
+        expires_at_unix: row.try_get("expires_at_unix")?,
+        tool_allowlist: row.try_get("tool_allowlist")?,
+        rotated_from_id: rotated_from_id.map(|u| u.to_string()),
+        allowed_cidrs: row.try_get("allowed_cidrs")?,
+    })
+}
+
 fn hash_api_key_secret(secret: &str) -> String {
     hex::encode(sha2::Sha256::digest(secret.as_bytes()))
 }
+
+const TENANT_OIDC_ISSUER_COLUMNS: &str = r"
+  issuer,
+  audiences,
+  jwks_uri,
+  allowed_algs,
+  enabled";
+
+fn tenant_oidc_issuer_from_row(row: &PgRow) -> anyhow::Result<TenantOidcIssuer> {
+    Ok(TenantOidcIssuer {
+        issuer: row.try_get("issuer")?,
+        audiences: row.try_get("audiences")?,
+        jwks_uri: row.try_get("jwks_uri")?,
+        allowed_algs: row.try_get("allowed_algs")?,
+        enabled: row.try_get("enabled")?,
+    })
+}
+
+async fn list_oidc_claim_rules_query(
+    pool: &PgPool,
+    tenant_id: &str,
+    issuer: &str,
+) -> anyhow::Result<Vec<OidcClaimRule>> {
+    let rows = sqlx::query(
+        r"
+select
+  id,
+  profile_id,
+  description,
+  conditions,
+  enabled
+from oidc_claim_rules
+where tenant_id = $1
+  and issuer = $2
+order by id asc
+",
+    )
+    .bind(tenant_id)
+    .bind(issuer)
+    .fetch_all(pool)
+    .await?;
+
+    let mut out: Vec<OidcClaimRule> = Vec::with_capacity(rows.len());
+    for r in rows {
+        let id: String = r.try_get("id")?;
+        let profile_id: Option<Uuid> = r.try_get("profile_id")?;
+        let conditions: Value = r.try_get("conditions")?;
+        out.push(OidcClaimRule {
+            conditions: serde_json::from_value(conditions).map_err(|e| {
+                anyhow::anyhow!("decode conditions for oidc claim rule '{id}': {e}")
+            })?,
+            id,
+            issuer: issuer.to_string(),
+            profile_id: profile_id.map(|u| u.to_string()),
+            enabled: r.try_get("enabled")?,
+            description: r.try_get("description")?,
+        });
+    }
+    Ok(out)
+}
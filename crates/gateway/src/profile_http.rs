@@ -1,7 +1,8 @@
-use crate::store::DataPlaneAuthMode;
+use crate::store::{DataPlaneAuthMode, McpProfileSettings};
 use crate::timeouts::tool_call_timeout_max_secs;
 use crate::tool_policy::ToolPolicy;
 use serde::{Deserialize, Serialize};
+use unrelated_tool_transforms::TransformPipeline;
 
 #[derive(Debug, Deserialize)]
 #[serde(untagged)]
@@ -105,6 +106,10 @@ pub(crate) fn validate_tool_timeout_and_policies(
                 return Err(format!("toolPolicies[].timeoutSecs must be <= {max}"));
             }
         }
+        if let Some(chain) = p.response_transforms.as_ref() {
+            unrelated_http_tools::response_shaping::compile_pipeline(&[], Some(chain))
+                .map_err(|e| format!("toolPolicies[].responseTransforms: {e}"))?;
+        }
         if let Some(r) = p.retry.as_ref() {
             if r.maximum_attempts == 0 {
                 return Err("toolPolicies[].retry.maximumAttempts must be >= 1".to_string());
@@ -155,3 +160,39 @@ pub(crate) fn validate_tool_allowlist(tools: &[String]) -> Result<(), String> {
     }
     Ok(())
 }
+
+/// Validate a profile transform pipeline, including that `pattern` constraints compile (an
+/// uncompilable exposed schema would otherwise skip argument validation entirely).
+pub(crate) fn validate_transforms(transforms: &TransformPipeline) -> Result<(), String> {
+    transforms
+        .validate()
+        .map_err(|e| format!("transforms: {e}"))?;
+    for (tool, o) in &transforms.tool_overrides {
+        for (param, p) in &o.params {
+            if let Some(pattern) = p.pattern.as_deref()
+                && jsonschema::validator_for(&serde_json::json!({ "pattern": pattern })).is_err()
+            {
+                return Err(format!(
+                    "transforms: toolOverrides.{tool}.params.{param}.pattern is not a valid regex"
+                ));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Validate the MCP settings that are compiled at request time (`mcp.toolSearch`,
+/// `mcp.responseTransforms`).
+pub(crate) fn validate_mcp_settings(mcp: &McpProfileSettings) -> Result<(), String> {
+    crate::tool_search::validate_settings(&mcp.tool_search)?;
+    validate_response_transforms(mcp)
+}
+
+/// Validate the profile-level response shaping pipeline (`mcp.responseTransforms`).
+pub(crate) fn validate_response_transforms(mcp: &McpProfileSettings) -> Result<(), String> {
+    unrelated_http_tools::response_shaping::compile_pipeline_from_transforms(
+        &mcp.response_transforms,
+    )
+    .map(|_| ())
+    .map_err(|e| format!("mcp.responseTransforms: {e}"))
+}
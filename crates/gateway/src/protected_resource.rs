@@ -0,0 +1,201 @@
+//! OAuth 2.0 Protected Resource Metadata (RFC 9728) for profile MCP endpoints.
+//!
+//! Each JWT-protected profile is its own protected resource (`<base>/<profile_id>/mcp`). MCP
+//! clients discover the authorization server(s) from the metadata document, or from the
+//! `resource_metadata` parameter of the `WWW-Authenticate` challenge on a 401.
+
+use axum::http::{HeaderMap, HeaderValue, uri::Authority};
+use serde::Serialize;
+
+/// Externally visible base URL of the data plane (e.g. `https://mcp.example.com`).
+pub const PUBLIC_BASE_URL_ENV: &str = "UNRELATED_GATEWAY_PUBLIC_BASE_URL";
+
+/// Well-known prefix; the resource path is appended (RFC 9728 §3.1).
+pub const WELL_KNOWN_PREFIX: &str = "/.well-known/oauth-protected-resource";
+
+/// Load and normalize `UNRELATED_GATEWAY_PUBLIC_BASE_URL` (unset/empty => `None`).
+pub fn public_base_url_from_env() -> anyhow::Result<Option<String>> {
+    let Ok(raw) = std::env::var(PUBLIC_BASE_URL_ENV) else {
+        return Ok(None);
+    };
+    let raw = raw.trim();
+    if raw.is_empty() {
+        return Ok(None);
+    }
+    normalize_base_url(raw)
+        .map(Some)
+        .map_err(|e| anyhow::anyhow!("{PUBLIC_BASE_URL_ENV}: {e}"))
+}
+
+fn normalize_base_url(raw: &str) -> Result<String, String> {
+    let url = reqwest::Url::parse(raw).map_err(|e| format!("invalid URL: {e}"))?;
+    if !matches!(url.scheme(), "https" | "http") {
+        return Err("must use http or https".to_string());
+    }
+    if url.query().is_some() || url.fragment().is_some() {
+        return Err("must not contain a query or fragment".to_string());
+    }
+    Ok(url.as_str().trim_end_matches('/').to_string())
+}
+
+/// Base URL for links in metadata and challenges.
+///
+/// Prefers the configured public base URL. Otherwise falls back to the request's `Host` header
+/// (scheme from `X-Forwarded-Proto`, default `http`), which is good enough for discovery but is
+/// never used for audience binding.
+#[must_use]
+pub fn request_base_url(configured: Option<&str>, headers: &HeaderMap) -> Option<String> {
+    if let Some(base) = configured {
+        return Some(base.to_string());
+    }
+    let host = headers
+        .get(axum::http::header::HOST)
+        .and_then(|h| h.to_str().ok())?;
+    // Reject anything that isn't a bare authority so the value is safe to quote in headers.
+    let host = host.parse::<Authority>().ok()?;
+    if host.as_str().contains('@') {
+        return None;
+    }
+    let scheme = match headers
+        .get("x-forwarded-proto")
+        .and_then(|h| h.to_str().ok())
+        .map(str::trim)
+    {
+        Some("https") => "https",
+        _ => "http",
+    };
+    Some(format!("{scheme}://{host}"))
+}
+
+/// The protected resource identifier for a profile (also the audience it binds tokens to).
+#[must_use]
+pub fn resource_url(base: &str, profile_id: &str) -> String {
+    format!("{base}/{profile_id}/mcp")
+}
+
+/// Where the profile's metadata document is served.
+#[must_use]
+pub fn metadata_url(base: &str, profile_id: &str) -> String {
+    format!("{base}{WELL_KNOWN_PREFIX}/{profile_id}/mcp")
+}
+
+/// `WWW-Authenticate` value for a 401 on a JWT-protected profile (RFC 6750 §3, RFC 9728 §5.1).
+///
+/// `error` is omitted when the request carried no token, as RFC 6750 recommends.
+#[must_use]
+pub fn bearer_challenge(metadata_url: &str, error: Option<&str>) -> Option<HeaderValue> {
+    let mut v = format!("Bearer resource_metadata=\"{metadata_url}\"");
+    if let Some(error) = error {
+        v.push_str(&format!(", error=\"{error}\""));
+    }
+    HeaderValue::from_str(&v).ok()
+}
+
+/// True when `aud` names a profile resource on this gateway other than `resource`.
+///
+/// Tokens minted for one profile (RFC 8707 resource indicator) must not be replayed against
+/// another profile, even when they also carry a generally accepted audience.
+#[must_use]
+pub fn audience_names_other_resource(
+    claims: &serde_json::Value,
+    base: &str,
+    resource: &str,
+) -> bool {
+    let prefix = format!("{base}/");
+    let names_other = |a: &str| a.starts_with(&prefix) && a != resource;
+    match claims.get("aud") {
+        Some(serde_json::Value::String(a)) => names_other(a),
+        Some(serde_json::Value::Array(items)) => items
+            .iter()
+            .filter_map(serde_json::Value::as_str)
+            .any(names_other),
+        _ => false,
+    }
+}
+
+/// RFC 9728 metadata document (field names are snake_case per the spec).
+#[derive(Debug, Serialize)]
+pub struct ProtectedResourceMetadata {
+    pub resource: String,
+    pub authorization_servers: Vec<String>,
+    pub bearer_methods_supported: Vec<&'static str>,
+}
+
+impl ProtectedResourceMetadata {
+    #[must_use]
+    pub fn new(resource: String, authorization_servers: Vec<String>) -> Self {
+        Self {
+            resource,
+            authorization_servers,
+            bearer_methods_supported: vec!["header"],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn base_url_prefers_config_and_sanitizes_host_fallback() {
+        let mut headers = HeaderMap::new();
+        headers.insert("host", HeaderValue::from_static("gw.example:8080"));
+        assert_eq!(
+            request_base_url(Some("https://mcp.example.com"), &headers).as_deref(),
+            Some("https://mcp.example.com")
+        );
+        assert_eq!(
+            request_base_url(None, &headers).as_deref(),
+            Some("http://gw.example:8080")
+        );
+        headers.insert("x-forwarded-proto", HeaderValue::from_static("https"));
+        assert_eq!(
+            request_base_url(None, &headers).as_deref(),
+            Some("https://gw.example:8080")
+        );
+        headers.insert("host", HeaderValue::from_static("evil\"example"));
+        assert!(request_base_url(None, &headers).is_none());
+
+        assert_eq!(
+            normalize_base_url("https://mcp.example.com/gw/").as_deref(),
+            Ok("https://mcp.example.com/gw")
+        );
+        assert!(normalize_base_url("ftp://mcp.example.com").is_err());
+    }
+
+    #[test]
+    fn challenge_and_urls_follow_rfc9728_layout() {
+        let base = "https://mcp.example.com";
+        assert_eq!(
+            metadata_url(base, "p1"),
+            "https://mcp.example.com/.well-known/oauth-protected-resource/p1/mcp"
+        );
+        let h = bearer_challenge(&metadata_url(base, "p1"), Some("invalid_token")).unwrap();
+        assert_eq!(
+            h.to_str().unwrap(),
+            "Bearer resource_metadata=\"https://mcp.example.com/.well-known/oauth-protected-resource/p1/mcp\", error=\"invalid_token\""
+        );
+    }
+
+    #[test]
+    fn audience_for_another_profile_is_detected() {
+        let base = "https://mcp.example.com";
+        let p1 = resource_url(base, "p1");
+        assert!(!audience_names_other_resource(
+            &json!({"aud": p1}),
+            base,
+            &p1
+        ));
+        assert!(!audience_names_other_resource(
+            &json!({"aud": "api://gateway"}),
+            base,
+            &p1
+        ));
+        assert!(audience_names_other_resource(
+            &json!({"aud": ["api://gateway", resource_url(base, "p2")]}),
+            base,
+            &p1
+        ));
+    }
+}
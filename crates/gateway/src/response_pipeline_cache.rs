@@ -0,0 +1,86 @@
+use parking_lot::RwLock;
+use std::collections::HashMap;
+use std::sync::Arc;
+use unrelated_http_tools::config::{ResponseTransform, ResponseTransformChainConfig};
+use unrelated_http_tools::response_shaping::{CompiledResponsePipeline, compile_pipeline};
+
+/// Upper bound on distinct cached pipelines; the cache is cleared when it is reached.
+const MAX_ENTRIES: usize = 1024;
+
+/// Compiled response shaping pipelines, keyed by their effective configuration.
+///
+/// Keys are derived from the config contents, so profile/policy updates simply miss the cache and
+/// no explicit invalidation is needed.
+#[derive(Clone, Default)]
+pub struct ResponsePipelineCache {
+    inner: Arc<RwLock<HashMap<String, Arc<CompiledResponsePipeline>>>>,
+}
+
+impl ResponsePipelineCache {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Compile (or reuse) the pipeline for `base` plus an optional tool chain.
+    ///
+    /// Returns `Ok(None)` when there is nothing to apply.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the effective transform configuration is invalid.
+    pub fn get_or_compile(
+        &self,
+        base: &[ResponseTransform],
+        chain: Option<&ResponseTransformChainConfig>,
+    ) -> Result<Option<Arc<CompiledResponsePipeline>>, String> {
+        if base.is_empty() && chain.is_none() {
+            return Ok(None);
+        }
+        let key = serde_json::to_string(&(base, chain)).map_err(|e| e.to_string())?;
+        if let Some(p) = self.inner.read().get(&key) {
+            return Ok(Some(p.clone()).filter(|p| !p.is_empty()));
+        }
+        let compiled = compile_pipeline(base, chain)?;
+        let mut map = self.inner.write();
+        if map.len() >= MAX_ENTRIES {
+            map.clear();
+        }
+        map.insert(key, compiled.clone());
+        Ok(Some(compiled).filter(|p| !p.is_empty()))
+    }
+
+    #[cfg(test)]
+    pub(crate) fn len(&self) -> usize {
+        self.inner.read().len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reuses_compiled_pipelines_and_rejects_invalid_config() {
+        let cache = ResponsePipelineCache::new();
+        assert!(cache.get_or_compile(&[], None).expect("ok").is_none());
+
+        let base = vec![ResponseTransform::DropNulls];
+        let a = cache
+            .get_or_compile(&base, None)
+            .expect("ok")
+            .expect("pipeline");
+        let b = cache
+            .get_or_compile(&base, None)
+            .expect("ok")
+            .expect("pipeline");
+        assert!(Arc::ptr_eq(&a, &b));
+        assert_eq!(cache.len(), 1);
+
+        let invalid = vec![ResponseTransform::PickPointers {
+            pointers: vec!["not-a-pointer".to_string()],
+        }];
+        assert!(cache.get_or_compile(&invalid, None).is_err());
+        assert_eq!(cache.len(), 1);
+    }
+}
@@ -0,0 +1,752 @@
+//! Live MCP session registry (list / terminate).
+//!
+//! Session tokens are stateless, so the registry is a side table keyed by the token's `sid`:
+//! initialize registers the session, in-session requests mark it as seen, and terminating it
+//! makes every replica reject the token (404, re-initialize required) and close its SSE streams.
+//! Mode 3 keeps the table in Postgres (`mcp_sessions`); Mode 1 keeps it in memory.
+
+use async_trait::async_trait;
+use parking_lot::{Mutex, RwLock};
+use serde::Serialize;
+use sqlx::{PgPool, Row as _};
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use tokio_util::sync::CancellationToken;
+
+use crate::session_token::UpstreamSessionBinding;
+
+const SESSION_ID_BYTES: usize = 16;
+
+/// Upper bound on sessions returned by a list call.
+pub const LIST_LIMIT: usize = 1000;
+
+/// How long a "still live" answer is trusted before re-checking the store. Terminations on other
+/// replicas arrive via `pg_invalidation`; this bounds the window if a notification is missed.
+const LIVE_CACHE_TTL: Duration = Duration::from_secs(30);
+
+/// How long this replica remembers a termination locally; afterwards the store answers (rows are
+/// kept until the session token expires).
+const TERMINATED_CACHE_TTL: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// Minimum interval between `lastSeenAt` writes for a session (per replica).
+const TOUCH_INTERVAL: Duration = Duration::from_secs(60);
+
+const CLEANUP_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Upper bound on sessions held by the in-memory store (oldest sessions are evicted first).
+const MEMORY_MAX_SESSIONS: usize = 100_000;
+
+#[must_use]
+pub fn now_unix_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+pub fn new_session_id() -> anyhow::Result<String> {
+    use rand_core::TryRngCore as _;
+    let mut bytes = [0u8; SESSION_ID_BYTES];
+    rand_core::OsRng
+        .try_fill_bytes(&mut bytes)
+        .map_err(|e| anyhow::anyhow!("OS RNG failure: {e}"))?;
+    Ok(hex::encode(bytes))
+}
+
+#[derive(Debug, Clone)]
+pub struct SessionRecord {
+    pub session_id: String,
+    pub tenant_id: String,
+    pub profile_id: String,
+    /// `apiKey:<id>` or `oidc:<issuer>|<subject>` (see `upstream_oauth::principal_key`).
+    pub principal: Option<String>,
+    pub bindings: Vec<UpstreamSessionBinding>,
+    pub created_at_unix: u64,
+    pub last_seen_at_unix: u64,
+    pub expires_at_unix: u64,
+    pub open_streams: u64,
+    pub terminated: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionUpstream {
+    pub upstream_id: String,
+    pub endpoint_id: String,
+}
+
+/// API view of a session (upstream session ids are not exposed).
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionInfo {
+    pub session_id: String,
+    pub tenant_id: String,
+    pub profile_id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub principal: Option<String>,
+    pub created_at_unix: u64,
+    pub last_seen_at_unix: u64,
+    pub expires_at_unix: u64,
+    pub upstreams: Vec<SessionUpstream>,
+    pub open_streams: u64,
+}
+
+impl From<&SessionRecord> for SessionInfo {
+    fn from(r: &SessionRecord) -> Self {
+        Self {
+            session_id: r.session_id.clone(),
+            tenant_id: r.tenant_id.clone(),
+            profile_id: r.profile_id.clone(),
+            principal: r.principal.clone(),
+            created_at_unix: r.created_at_unix,
+            last_seen_at_unix: r.last_seen_at_unix,
+            expires_at_unix: r.expires_at_unix,
+            upstreams: r
+                .bindings
+                .iter()
+                .map(|b| SessionUpstream {
+                    upstream_id: b.upstream.clone(),
+                    endpoint_id: b.endpoint.clone(),
+                })
+                .collect(),
+            open_streams: r.open_streams,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct SessionFilter {
+    pub tenant_id: Option<String>,
+    pub profile_id: Option<String>,
+}
+
+impl SessionFilter {
+    fn matches(&self, r: &SessionRecord) -> bool {
+        self.tenant_id.as_deref().is_none_or(|t| t == r.tenant_id)
+            && self.profile_id.as_deref().is_none_or(|p| p == r.profile_id)
+    }
+}
+
+#[async_trait]
+pub trait SessionStore: Send + Sync {
+    async fn insert(&self, record: &SessionRecord) -> anyhow::Result<()>;
+
+    async fn touch(&self, session_id: &str, now_unix: u64) -> anyhow::Result<()>;
+
+    async fn adjust_open_streams(&self, session_id: &str, delta: i64) -> anyhow::Result<()>;
+
+    /// Live (unexpired, not terminated) session.
+    async fn get(&self, session_id: &str, now_unix: u64) -> anyhow::Result<Option<SessionRecord>>;
+
+    /// Live sessions, most recently seen first, at most [`LIST_LIMIT`].
+    async fn list(
+        &self,
+        filter: &SessionFilter,
+        now_unix: u64,
+    ) -> anyhow::Result<Vec<SessionRecord>>;
+
+    /// Mark a session terminated. Returns the record if it was live.
+    async fn terminate(
+        &self,
+        session_id: &str,
+        now_unix: u64,
+    ) -> anyhow::Result<Option<SessionRecord>>;
+
+    async fn is_terminated(&self, session_id: &str) -> anyhow::Result<bool>;
+
+    /// Drop expired sessions; returns how many were removed.
+    async fn cleanup_expired(&self, now_unix: u64) -> anyhow::Result<u64>;
+}
+
+#[derive(Debug, Clone, Copy)]
+struct LiveEntry {
+    terminated: bool,
+    checked_at: Instant,
+}
+
+/// Front end over a [`SessionStore`] with local caches and this replica's open SSE streams.
+pub struct SessionRegistry {
+    store: Arc<dyn SessionStore>,
+    live: RwLock<HashMap<String, LiveEntry>>,
+    touched: Mutex<HashMap<String, Instant>>,
+    streams: Mutex<HashMap<String, Vec<(u64, CancellationToken)>>>,
+    next_stream_id: AtomicU64,
+}
+
+impl SessionRegistry {
+    #[must_use]
+    pub fn new(store: Arc<dyn SessionStore>) -> Self {
+        Self {
+            store,
+            live: RwLock::new(HashMap::new()),
+            touched: Mutex::new(HashMap::new()),
+            streams: Mutex::new(HashMap::new()),
+            next_stream_id: AtomicU64::new(1),
+        }
+    }
+
+    #[must_use]
+    pub fn in_memory() -> Self {
+        Self::new(Arc::new(MemorySessionStore::default()))
+    }
+
+    /// Register a new session (best-effort: a registry outage must not block initialize).
+    pub async fn register(&self, record: &SessionRecord) {
+        if let Err(e) = self.store.insert(record).await {
+            tracing::warn!(error = %e, profile_id = %record.profile_id, "failed to register MCP session");
+        }
+    }
+
+    /// Whether a session may still be used; also records activity.
+    ///
+    /// Unknown sessions (e.g. registered before a Mode 1 restart) are treated as live; store
+    /// errors fail open as well, since the token itself is still authenticated.
+    pub async fn check_live(&self, session_id: &str) -> bool {
+        let now = Instant::now();
+        let cached = self.live.read().get(session_id).copied();
+        let terminated = match cached {
+            Some(e) if e.terminated => return false,
+            Some(e) if now.duration_since(e.checked_at) < LIVE_CACHE_TTL => false,
+            _ => match self.store.is_terminated(session_id).await {
+                Ok(terminated) => {
+                    self.live.write().insert(
+                        session_id.to_string(),
+                        LiveEntry {
+                            terminated,
+                            checked_at: now,
+                        },
+                    );
+                    terminated
+                }
+                Err(e) => {
+                    tracing::warn!(error = %e, "session registry lookup failed");
+                    false
+                }
+            },
+        };
+        if terminated {
+            return false;
+        }
+
+        let due = {
+            let mut touched = self.touched.lock();
+            match touched.get(session_id) {
+                Some(at) if now.duration_since(*at) < TOUCH_INTERVAL => false,
+                _ => {
+                    touched.insert(session_id.to_string(), now);
+                    true
+                }
+            }
+        };
+        if due && let Err(e) = self.store.touch(session_id, now_unix_secs()).await {
+            tracing::warn!(error = %e, "failed to record MCP session activity");
+        }
+        true
+    }
+
+    /// Track an SSE stream for a session. The stream should end when the returned guard's token
+    /// is cancelled; dropping the guard unregisters it.
+    pub async fn open_stream(self: &Arc<Self>, session_id: &str) -> StreamGuard {
+        let id = self.next_stream_id.fetch_add(1, Ordering::Relaxed);
+        let token = CancellationToken::new();
+        self.streams
+            .lock()
+            .entry(session_id.to_string())
+            .or_default()
+            .push((id, token.clone()));
+        if let Err(e) = self.store.adjust_open_streams(session_id, 1).await {
+            tracing::warn!(error = %e, "failed to record MCP session stream");
+        }
+        StreamGuard {
+            registry: self.clone(),
+            session_id: session_id.to_string(),
+            id,
+            token,
+        }
+    }
+
+    fn close_stream(&self, session_id: &str, id: u64) {
+        let mut streams = self.streams.lock();
+        if let Some(list) = streams.get_mut(session_id) {
+            list.retain(|(sid, _)| *sid != id);
+            if list.is_empty() {
+                streams.remove(session_id);
+            }
+        }
+    }
+
+    pub async fn get(&self, session_id: &str) -> anyhow::Result<Option<SessionRecord>> {
+        self.store.get(session_id, now_unix_secs()).await
+    }
+
+    pub async fn list(&self, filter: &SessionFilter) -> anyhow::Result<Vec<SessionRecord>> {
+        self.store.list(filter, now_unix_secs()).await
+    }
+
+    /// Terminate a session: persist the termination and close this replica's streams.
+    ///
+    /// Returns the record if the session was live (upstream teardown is up to the caller).
+    pub async fn terminate(&self, session_id: &str) -> anyhow::Result<Option<SessionRecord>> {
+        let record = self.store.terminate(session_id, now_unix_secs()).await?;
+        self.mark_terminated(session_id);
+        Ok(record)
+    }
+
+    /// Apply a termination made elsewhere (another replica, via `pg_invalidation`).
+    pub fn mark_terminated(&self, session_id: &str) {
+        self.live.write().insert(
+            session_id.to_string(),
+            LiveEntry {
+                terminated: true,
+                checked_at: Instant::now(),
+            },
+        );
+        self.touched.lock().remove(session_id);
+        if let Some(streams) = self.streams.lock().remove(session_id) {
+            for (_, token) in streams {
+                token.cancel();
+            }
+        }
+    }
+
+    async fn cleanup(&self) -> anyhow::Result<u64> {
+        let now = Instant::now();
+        self.live.write().retain(|_, e| {
+            let age = now.duration_since(e.checked_at);
+            age < LIVE_CACHE_TTL || (e.terminated && age < TERMINATED_CACHE_TTL)
+        });
+        self.touched
+            .lock()
+            .retain(|_, at| now.duration_since(*at) < TOUCH_INTERVAL);
+        self.store.cleanup_expired(now_unix_secs()).await
+    }
+}
+
+/// An open SSE stream of a tracked session.
+pub struct StreamGuard {
+    registry: Arc<SessionRegistry>,
+    session_id: String,
+    id: u64,
+    token: CancellationToken,
+}
+
+impl StreamGuard {
+    /// Cancelled when the session is terminated.
+    #[must_use]
+    pub fn token(&self) -> CancellationToken {
+        self.token.clone()
+    }
+}
+
+impl Drop for StreamGuard {
+    fn drop(&mut self) {
+        self.registry.close_stream(&self.session_id, self.id);
+        let Ok(rt) = tokio::runtime::Handle::try_current() else {
+            return;
+        };
+        let registry = self.registry.clone();
+        let session_id = std::mem::take(&mut self.session_id);
+        rt.spawn(async move {
+            if let Err(e) = registry.store.adjust_open_streams(&session_id, -1).await {
+                tracing::warn!(error = %e, "failed to record MCP session stream close");
+            }
+        });
+    }
+}
+
+pub fn spawn_cleanup_task(registry: Arc<SessionRegistry>, shutdown: CancellationToken) {
+    tokio::spawn(async move {
+        let mut tick = tokio::time::interval(CLEANUP_INTERVAL);
+        tick.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+        loop {
+            tokio::select! {
+                () = shutdown.cancelled() => break,
+                _ = tick.tick() => {
+                    if let Err(e) = registry.cleanup().await {
+                        tracing::warn!(error = %e, "session registry cleanup tick failed");
+                    }
+                }
+            }
+        }
+    });
+}
+
+/// In-memory store (Mode 1).
+#[derive(Default)]
+pub struct MemorySessionStore {
+    sessions: Mutex<HashMap<String, SessionRecord>>,
+}
+
+#[async_trait]
+impl SessionStore for MemorySessionStore {
+    async fn insert(&self, record: &SessionRecord) -> anyhow::Result<()> {
+        let mut sessions = self.sessions.lock();
+        if sessions.len() >= MEMORY_MAX_SESSIONS
+            && let Some(oldest) = sessions
+                .values()
+                .min_by_key(|r| r.last_seen_at_unix)
+                .map(|r| r.session_id.clone())
+        {
+            sessions.remove(&oldest);
+        }
+        sessions.insert(record.session_id.clone(), record.clone());
+        Ok(())
+    }
+
+    async fn touch(&self, session_id: &str, now_unix: u64) -> anyhow::Result<()> {
+        if let Some(r) = self.sessions.lock().get_mut(session_id) {
+            r.last_seen_at_unix = now_unix;
+        }
+        Ok(())
+    }
+
+    async fn adjust_open_streams(&self, session_id: &str, delta: i64) -> anyhow::Result<()> {
+        if let Some(r) = self.sessions.lock().get_mut(session_id) {
+            r.open_streams = r.open_streams.saturating_add_signed(delta);
+        }
+        Ok(())
+    }
+
+    async fn get(&self, session_id: &str, now_unix: u64) -> anyhow::Result<Option<SessionRecord>> {
+        Ok(self
+            .sessions
+            .lock()
+            .get(session_id)
+            .filter(|r| !r.terminated && r.expires_at_unix > now_unix)
+            .cloned())
+    }
+
+    async fn list(
+        &self,
+        filter: &SessionFilter,
+        now_unix: u64,
+    ) -> anyhow::Result<Vec<SessionRecord>> {
+        let mut out: Vec<SessionRecord> = self
+            .sessions
+            .lock()
+            .values()
+            .filter(|r| !r.terminated && r.expires_at_unix > now_unix && filter.matches(r))
+            .cloned()
+            .collect();
+        out.sort_by(|a, b| {
+            b.last_seen_at_unix
+                .cmp(&a.last_seen_at_unix)
+                .then_with(|| a.session_id.cmp(&b.session_id))
+        });
+        out.truncate(LIST_LIMIT);
+        Ok(out)
+    }
+
+    async fn terminate(
+        &self,
+        session_id: &str,
+        now_unix: u64,
+    ) -> anyhow::Result<Option<SessionRecord>> {
+        let mut sessions = self.sessions.lock();
+        let Some(r) = sessions.get_mut(session_id) else {
+            return Ok(None);
+        };
+        if r.terminated || r.expires_at_unix <= now_unix {
+            return Ok(None);
+        }
+        r.terminated = true;
+        Ok(Some(r.clone()))
+    }
+
+    async fn is_terminated(&self, session_id: &str) -> anyhow::Result<bool> {
+        Ok(self
+            .sessions
+            .lock()
+            .get(session_id)
+            .is_some_and(|r| r.terminated))
+    }
+
+    async fn cleanup_expired(&self, now_unix: u64) -> anyhow::Result<u64> {
+        let mut sessions = self.sessions.lock();
+        let before = sessions.len();
+        sessions.retain(|_, r| r.expires_at_unix > now_unix);
+        Ok((before - sessions.len()) as u64)
+    }
+}
+
+/// Postgres-backed store (Mode 3 / HA).
+pub struct PgSessionStore {
+    pool: PgPool,
+}
+
+impl PgSessionStore {
+    #[must_use]
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+const SESSION_COLUMNS: &str = r"
+  session_id,
+  tenant_id,
+  profile_id,
+  principal,
+  bindings,
+  open_streams,
+  terminated_at is not null as terminated,
+  extract(epoch from created_at)::bigint as created_at_unix,
+  extract(epoch from last_seen_at)::bigint as last_seen_at_unix,
+  extract(epoch from expires_at)::bigint as expires_at_unix
+";
+
+fn session_from_row(row: &sqlx::postgres::PgRow) -> anyhow::Result<SessionRecord> {
+    let bindings: serde_json::Value = row.try_get("bindings")?;
+    let unix = |col: &str| -> anyhow::Result<u64> {
+        Ok(u64::try_from(row.try_get::<i64, _>(col)?).unwrap_or_default())
+    };
+    Ok(SessionRecord {
+        session_id: row.try_get("session_id")?,
+        tenant_id: row.try_get("tenant_id")?,
+        profile_id: row.try_get("profile_id")?,
+        principal: row.try_get("principal")?,
+        bindings: serde_json::from_value(bindings)?,
+        created_at_unix: unix("created_at_unix")?,
+        last_seen_at_unix: unix("last_seen_at_unix")?,
+        expires_at_unix: unix("expires_at_unix")?,
+        open_streams: u64::try_from(row.try_get::<i32, _>("open_streams")?).unwrap_or_default(),
+        terminated: row.try_get("terminated")?,
+    })
+}
+
+fn unix_to_i64(v: u64) -> i64 {
+    i64::try_from(v).unwrap_or(i64::MAX)
+}
+
+#[async_trait]
+impl SessionStore for PgSessionStore {
+    async fn insert(&self, record: &SessionRecord) -> anyhow::Result<()> {
+        sqlx::query(
+            r"
+insert into mcp_sessions (
+  session_id, tenant_id, profile_id, principal, bindings, created_at, last_seen_at, expires_at
+)
+values ($1, $2, $3, $4, $5, to_timestamp($6), to_timestamp($6), to_timestamp($7))
+on conflict (session_id) do nothing
+",
+        )
+        .bind(&record.session_id)
+        .bind(&record.tenant_id)
+        .bind(&record.profile_id)
+        .bind(record.principal.as_deref())
+        .bind(serde_json::to_value(&record.bindings)?)
+        .bind(unix_to_i64(record.created_at_unix))
+        .bind(unix_to_i64(record.expires_at_unix))
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    async fn touch(&self, session_id: &str, now_unix: u64) -> anyhow::Result<()> {
+        sqlx::query(
+            "update mcp_sessions set last_seen_at = to_timestamp($2) where session_id = $1",
+        )
+        .bind(session_id)
+        .bind(unix_to_i64(now_unix))
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    async fn adjust_open_streams(&self, session_id: &str, delta: i64) -> anyhow::Result<()> {
+        sqlx::query(
+            r"
+update mcp_sessions
+set open_streams = greatest(open_streams + $2, 0)
+where session_id = $1
+",
+        )
+        .bind(session_id)
+        .bind(i32::try_from(delta)?)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    async fn get(&self, session_id: &str, now_unix: u64) -> anyhow::Result<Option<SessionRecord>> {
+        let row = sqlx::query(&format!(
+            r"
+select {SESSION_COLUMNS}
+from mcp_sessions
+where session_id = $1
+  and terminated_at is null
+  and expires_at > to_timestamp($2)
+"
+        ))
+        .bind(session_id)
+        .bind(unix_to_i64(now_unix))
+        .fetch_optional(&self.pool)
+        .await?;
+        row.as_ref().map(session_from_row).transpose()
+    }
+
+    async fn list(
+        &self,
+        filter: &SessionFilter,
+        now_unix: u64,
+    ) -> anyhow::Result<Vec<SessionRecord>> {
+        let rows = sqlx::query(&format!(
+            r"
+select {SESSION_COLUMNS}
+from mcp_sessions
+where terminated_at is null
+  and expires_at > to_timestamp($1)
+  and ($2::text is null or tenant_id = $2)
+  and ($3::text is null or profile_id = $3)
+order by last_seen_at desc, session_id asc
+limit $4
+"
+        ))
+        .bind(unix_to_i64(now_unix))
+        .bind(filter.tenant_id.as_deref())
+        .bind(filter.profile_id.as_deref())
+        .bind(i64::try_from(LIST_LIMIT)?)
+        .fetch_all(&self.pool)
+        .await?;
+        rows.iter().map(session_from_row).collect()
+    }
+
+    async fn terminate(
+        &self,
+        session_id: &str,
+        now_unix: u64,
+    ) -> anyhow::Result<Option<SessionRecord>> {
+        let row = sqlx::query(&format!(
+            r"
+update mcp_sessions
+set terminated_at = to_timestamp($2)
+where session_id = $1
+  and terminated_at is null
+  and expires_at > to_timestamp($2)
+returning {SESSION_COLUMNS}
+"
+        ))
+        .bind(session_id)
+        .bind(unix_to_i64(now_unix))
+        .fetch_optional(&self.pool)
+        .await?;
+        row.as_ref().map(session_from_row).transpose()
+    }
+
+    async fn is_terminated(&self, session_id: &str) -> anyhow::Result<bool> {
+        let terminated: Option<bool> = sqlx::query_scalar(
+            "select terminated_at is not null from mcp_sessions where session_id = $1",
+        )
+        .bind(session_id)
+        .fetch_optional(&self.pool)
+        .await?;
+        Ok(terminated.unwrap_or(false))
+    }
+
+    async fn cleanup_expired(&self, now_unix: u64) -> anyhow::Result<u64> {
+        let res = sqlx::query("delete from mcp_sessions where expires_at <= to_timestamp($1)")
+            .bind(unix_to_i64(now_unix))
+            .execute(&self.pool)
+            .await?;
+        Ok(res.rows_affected())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(id: &str, tenant: &str, profile: &str, expires_at_unix: u64) -> SessionRecord {
+        SessionRecord {
+            session_id: id.to_string(),
+            tenant_id: tenant.to_string(),
+            profile_id: profile.to_string(),
+            principal: Some("apiKey:k1".to_string()),
+            bindings: vec![UpstreamSessionBinding {
+                upstream: "u1".to_string(),
+                endpoint: "e1".to_string(),
+                session: "upstream-session".to_string(),
+                oauth: None,
+            }],
+            created_at_unix: 1,
+            last_seen_at_unix: 1,
+            expires_at_unix,
+            open_streams: 0,
+            terminated: false,
+        }
+    }
+
+    #[tokio::test]
+    async fn memory_store_lists_filters_and_terminates() -> anyhow::Result<()> {
+        let store = MemorySessionStore::default();
+        store.insert(&record("s1", "t1", "p1", 100)).await?;
+        store.insert(&record("s2", "t1", "p2", 100)).await?;
+        store.insert(&record("s3", "t2", "p3", 100)).await?;
+        store.insert(&record("old", "t1", "p1", 10)).await?;
+        store.touch("s2", 50).await?;
+
+        let t1 = SessionFilter {
+            tenant_id: Some("t1".to_string()),
+            profile_id: None,
+        };
+        let ids: Vec<String> = store
+            .list(&t1, 20)
+            .await?
+            .into_iter()
+            .map(|r| r.session_id)
+            .collect();
+        assert_eq!(ids, vec!["s2".to_string(), "s1".to_string()]);
+
+        assert!(store.terminate("s1", 20).await?.is_some());
+        assert!(
+            store.terminate("s1", 20).await?.is_none(),
+            "already terminated"
+        );
+        assert!(store.terminate("old", 20).await?.is_none(), "expired");
+        assert!(store.is_terminated("s1").await?);
+        assert!(store.get("s1", 20).await?.is_none());
+        assert_eq!(store.list(&t1, 20).await?.len(), 1);
+
+        assert_eq!(store.cleanup_expired(20).await?, 1);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn registry_terminate_closes_streams_and_rejects_session() {
+        let registry = Arc::new(SessionRegistry::in_memory());
+        registry
+            .register(&record("s1", "t1", "p1", now_unix_secs() + 60))
+            .await;
+        assert!(registry.check_live("s1").await);
+        assert!(registry.check_live("unknown").await);
+
+        let guard = registry.open_stream("s1").await;
+        let token = guard.token();
+        assert_eq!(
+            registry
+                .get("s1")
+                .await
+                .expect("get")
+                .map(|r| r.open_streams),
+            Some(1)
+        );
+
+        let rec = registry.terminate("s1").await.expect("terminate");
+        assert!(rec.is_some());
+        assert!(token.is_cancelled());
+        assert!(!registry.check_live("s1").await);
+        assert!(registry.get("s1").await.expect("get").is_none());
+    }
+
+    #[test]
+    fn session_info_hides_upstream_session_ids() {
+        let info = SessionInfo::from(&record("s1", "t1", "p1", 100));
+        let json = serde_json::to_value(&info).expect("serialize");
+        assert_eq!(
+            json.get("upstreams"),
+            Some(&serde_json::json!([{"upstreamId": "u1", "endpointId": "e1"}]))
+        );
+        assert!(!json.to_string().contains("upstream-session"));
+    }
+}
@@ -72,6 +72,12 @@ impl SessionSigner {
         Ok(Self { keys, ttl })
     }
 
+    /// Lifetime of newly minted tokens.
+    #[must_use]
+    pub fn ttl(&self) -> Duration {
+        self.ttl
+    }
+
     /// Mint a new session token.
     ///
     /// This updates `iat`/`exp` based on `ttl`.
@@ -223,6 +229,10 @@ pub struct TokenPayloadV1 {
     /// malicious downstream clients).
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub proxy_key: Option<String>,
+    /// Session id in the live session registry (list/terminate). Absent on legacy tokens, which
+    /// are not tracked.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sid: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -230,6 +240,12 @@ pub struct TokenPayloadV1 {
 pub struct TokenAuthV1 {
     pub tenant_id: String,
     pub api_key_id: String,
+    /// The API key's tool allowlist at initialize time (immutable for the key's lifetime).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tools: Vec<String>,
+    /// The API key's client IP allowlist at initialize time (immutable for the key's lifetime).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub allowed_cidrs: Vec<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -245,6 +261,16 @@ pub struct UpstreamSessionBinding {
     pub upstream: String,
     pub endpoint: String,
     pub session: String,
+    /// Set when requests to this upstream carry the caller's linked OAuth token.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub oauth: Option<UpstreamOAuthBinding>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpstreamOAuthBinding {
+    pub tenant_id: String,
+    pub principal: String,
 }
 
 struct KeyEntry {
@@ -317,6 +343,25 @@ mod tests {
     use super::*;
     use hmac::Mac as _;
 
+    #[test]
+    fn token_auth_tools_are_optional_and_roundtrip() {
+        let legacy: TokenAuthV1 =
+            serde_json::from_str(r#"{"tenantId":"t1","apiKeyId":"k1"}"#).expect("legacy auth");
+        assert!(legacy.tools.is_empty());
+        assert_eq!(
+            serde_json::to_value(&legacy).expect("serialize"),
+            serde_json::json!({"tenantId": "t1", "apiKeyId": "k1"})
+        );
+
+        let narrowed = TokenAuthV1 {
+            tools: vec!["s1:t1".to_string()],
+            ..legacy
+        };
+        let json = serde_json::to_string(&narrowed).expect("serialize");
+        let decoded: TokenAuthV1 = serde_json::from_str(&json).expect("deserialize");
+        assert_eq!(decoded.tools, vec!["s1:t1".to_string()]);
+    }
+
     #[test]
     fn token_roundtrip() {
         let signer =
@@ -327,15 +372,19 @@ mod tests {
                 upstream: "u1".to_string(),
                 endpoint: "e1".to_string(),
                 session: "s1".to_string(),
+                oauth: None,
             }],
             auth: Some(TokenAuthV1 {
                 tenant_id: "t1".to_string(),
                 api_key_id: "k1".to_string(),
+                tools: vec![],
+                allowed_cidrs: vec![],
             }),
             oidc: None,
             iat: None,
             exp: None,
             proxy_key: None,
+            sid: None,
         };
 
         let token = signer.sign(payload).expect("token");
@@ -375,6 +424,7 @@ mod tests {
             iat: None,
             exp: None,
             proxy_key: None,
+            sid: None,
         };
         let payload_b64 = base64::engine::general_purpose::URL_SAFE_NO_PAD
             .encode(serde_json::to_vec(&payload).unwrap());
@@ -410,12 +460,14 @@ mod tests {
                 upstream: "u1".to_string(),
                 endpoint: "e1".to_string(),
                 session: "s1".to_string(),
+                oauth: None,
             }],
             auth: None,
             oidc: None,
             iat: None,
             exp: None,
             proxy_key: None,
+            sid: None,
         };
         // Create a legacy token and then tamper it.
         let legacy = {
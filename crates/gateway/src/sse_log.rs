@@ -0,0 +1,520 @@
+//! Per-session SSE event log for `Last-Event-ID` resumption (`mcp.sseReplay`).
+//!
+//! When enabled for a profile, every event on the merged SSE stream (upstream and
+//! Gateway-originated) is appended to a bounded log and sent with a gateway event id
+//! (`gw.<seq>`). On reconnect the Gateway replays logged events after that id, then resumes each
+//! upstream from its last logged event id and contract notifications from the last logged
+//! contract event. Mode 3 stores the log in Postgres so any replica can resume a session; Mode 1
+//! keeps it in memory.
+
+use async_trait::async_trait;
+use parking_lot::Mutex;
+use sha2::Digest as _;
+use sqlx::{PgPool, Row as _};
+use std::collections::{HashMap, VecDeque};
+use std::sync::Arc;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use tokio_util::sync::CancellationToken;
+
+use crate::store::McpSseReplaySettings;
+
+/// Prefix of gateway-assigned SSE event ids.
+pub const EVENT_ID_PREFIX: &str = "gw.";
+
+pub const MAX_EVENTS_LIMIT: u32 = 10_000;
+pub const MAX_RETENTION_SECS: u64 = 24 * 60 * 60;
+
+/// Upper bound on sessions held by the in-memory log (oldest sessions are evicted first).
+const MEMORY_MAX_SESSIONS: usize = 10_000;
+
+/// How often expired events are purged.
+const CLEANUP_INTERVAL: Duration = Duration::from_secs(60);
+
+/// In Postgres, trim a session back to `maxEvents` on roughly one append in this many (loads
+/// always return at most `maxEvents`, so trimming only bounds storage).
+const PG_TRIM_EVERY: i64 = 16;
+
+pub fn validate_replay_settings(s: &McpSseReplaySettings) -> Result<(), String> {
+    if s.max_events == 0 || s.max_events > MAX_EVENTS_LIMIT {
+        return Err(format!(
+            "mcp.sseReplay.maxEvents must be between 1 and {MAX_EVENTS_LIMIT}"
+        ));
+    }
+    if s.retention_secs == 0 || s.retention_secs > MAX_RETENTION_SECS {
+        return Err(format!(
+            "mcp.sseReplay.retentionSecs must be between 1 and {MAX_RETENTION_SECS}"
+        ));
+    }
+    Ok(())
+}
+
+#[must_use]
+pub fn format_event_id(seq: i64) -> String {
+    format!("{EVENT_ID_PREFIX}{seq}")
+}
+
+#[must_use]
+pub fn parse_event_id(id: &str) -> Option<i64> {
+    id.strip_prefix(EVENT_ID_PREFIX)?
+        .parse::<i64>()
+        .ok()
+        .filter(|n| *n >= 0)
+}
+
+/// Where a logged event came from (used to resume the source after a replay).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EventOrigin {
+    Upstream {
+        upstream_id: String,
+        /// The upstream's own (un-namespaced) event id, if it sent one.
+        event_id: Option<String>,
+    },
+    Contract {
+        event_id: u64,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LoggedEvent {
+    pub seq: i64,
+    pub origin: EventOrigin,
+    pub data: String,
+}
+
+#[async_trait]
+pub trait SseEventLog: Send + Sync {
+    /// Append an event and return its per-session sequence number (monotonic, > 0).
+    async fn append(
+        &self,
+        session_key: &str,
+        origin: &EventOrigin,
+        data: &str,
+        settings: &McpSseReplaySettings,
+    ) -> anyhow::Result<i64>;
+
+    /// Retained (unexpired) events for a session, oldest first, at most `maxEvents`.
+    async fn load(
+        &self,
+        session_key: &str,
+        settings: &McpSseReplaySettings,
+    ) -> anyhow::Result<Vec<LoggedEvent>>;
+
+    /// Drop expired events; returns how many were removed.
+    async fn cleanup_expired(&self) -> anyhow::Result<u64>;
+}
+
+/// Stable log key for a session (the session token itself is never stored).
+#[must_use]
+pub fn session_key(session_token: &str) -> String {
+    hex::encode(sha2::Sha256::digest(session_token.as_bytes()))
+}
+
+fn clamp(settings: &McpSseReplaySettings) -> (usize, u64) {
+    (
+        settings.max_events.clamp(1, MAX_EVENTS_LIMIT) as usize,
+        settings.retention_secs.clamp(1, MAX_RETENTION_SECS),
+    )
+}
+
+/// What a reconnect with `Last-Event-ID: gw.<after>` should do.
+#[derive(Debug, Default)]
+pub struct ResumePlan {
+    /// Logged events the client has not seen.
+    pub replay: Vec<LoggedEvent>,
+    /// Last logged upstream event id, per upstream.
+    pub upstream_cursors: HashMap<String, String>,
+    /// Last logged contract event id.
+    pub contract_after: Option<u64>,
+}
+
+#[must_use]
+pub fn plan_resume(events: Vec<LoggedEvent>, after: i64) -> ResumePlan {
+    let mut plan = ResumePlan::default();
+    for evt in events {
+        match &evt.origin {
+            EventOrigin::Upstream {
+                upstream_id,
+                event_id: Some(id),
+            } => {
+                plan.upstream_cursors
+                    .insert(upstream_id.clone(), id.clone());
+            }
+            EventOrigin::Upstream { event_id: None, .. } => {}
+            EventOrigin::Contract { event_id } => {
+                plan.contract_after =
+                    Some(plan.contract_after.map_or(*event_id, |c| c.max(*event_id)));
+            }
+        }
+        if evt.seq > after {
+            plan.replay.push(evt);
+        }
+    }
+    plan
+}
+
+/// A session's view of the log, as used by one SSE stream.
+pub struct SessionEventLog {
+    log: Arc<dyn SseEventLog>,
+    session_key: String,
+    settings: McpSseReplaySettings,
+}
+
+impl SessionEventLog {
+    #[must_use]
+    pub fn new(
+        log: Arc<dyn SseEventLog>,
+        session_token: &str,
+        settings: McpSseReplaySettings,
+    ) -> Self {
+        Self {
+            log,
+            session_key: session_key(session_token),
+            settings,
+        }
+    }
+
+    /// Log an event and return its gateway event id (`None` if logging failed; the caller then
+    /// keeps the event's original id).
+    pub async fn record(&self, origin: EventOrigin, data: &str) -> Option<String> {
+        match self
+            .log
+            .append(&self.session_key, &origin, data, &self.settings)
+            .await
+        {
+            Ok(seq) => Some(format_event_id(seq)),
+            Err(e) => {
+                tracing::warn!(error = %e, "failed to log SSE event for replay");
+                None
+            }
+        }
+    }
+
+    pub async fn resume(&self, after: i64) -> anyhow::Result<ResumePlan> {
+        let events = self.log.load(&self.session_key, &self.settings).await?;
+        Ok(plan_resume(events, after))
+    }
+}
+
+struct MemorySession {
+    next_seq: i64,
+    last_append: Instant,
+    events: VecDeque<(Instant, LoggedEvent)>,
+}
+
+/// In-memory log (Mode 1, or a single replica).
+#[derive(Default)]
+pub struct MemorySseEventLog {
+    sessions: Mutex<HashMap<String, MemorySession>>,
+}
+
+impl MemorySseEventLog {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl SseEventLog for MemorySseEventLog {
+    async fn append(
+        &self,
+        session_key: &str,
+        origin: &EventOrigin,
+        data: &str,
+        settings: &McpSseReplaySettings,
+    ) -> anyhow::Result<i64> {
+        let (max_events, retention_secs) = clamp(settings);
+        let now = Instant::now();
+        let mut sessions = self.sessions.lock();
+        if !sessions.contains_key(session_key)
+            && sessions.len() >= MEMORY_MAX_SESSIONS
+            && let Some(oldest) = sessions
+                .iter()
+                .min_by_key(|(_, s)| s.last_append)
+                .map(|(k, _)| k.clone())
+        {
+            sessions.remove(&oldest);
+        }
+        let session = sessions
+            .entry(session_key.to_string())
+            .or_insert_with(|| MemorySession {
+                next_seq: 1,
+                last_append: now,
+                events: VecDeque::new(),
+            });
+        let seq = session.next_seq;
+        session.next_seq += 1;
+        session.last_append = now;
+        session.events.push_back((
+            now + Duration::from_secs(retention_secs),
+            LoggedEvent {
+                seq,
+                origin: origin.clone(),
+                data: data.to_string(),
+            },
+        ));
+        while session.events.len() > max_events {
+            session.events.pop_front();
+        }
+        Ok(seq)
+    }
+
+    async fn load(
+        &self,
+        session_key: &str,
+        settings: &McpSseReplaySettings,
+    ) -> anyhow::Result<Vec<LoggedEvent>> {
+        let (max_events, _) = clamp(settings);
+        let now = Instant::now();
+        let sessions = self.sessions.lock();
+        let Some(session) = sessions.get(session_key) else {
+            return Ok(Vec::new());
+        };
+        let live: Vec<LoggedEvent> = session
+            .events
+            .iter()
+            .filter(|(exp, _)| *exp > now)
+            .map(|(_, e)| e.clone())
+            .collect();
+        let skip = live.len().saturating_sub(max_events);
+        Ok(live.into_iter().skip(skip).collect())
+    }
+
+    async fn cleanup_expired(&self) -> anyhow::Result<u64> {
+        let now = Instant::now();
+        let mut removed = 0u64;
+        let mut sessions = self.sessions.lock();
+        sessions.retain(|_, s| {
+            let before = s.events.len();
+            s.events.retain(|(exp, _)| *exp > now);
+            removed += (before - s.events.len()) as u64;
+            !s.events.is_empty()
+        });
+        Ok(removed)
+    }
+}
+
+/// Postgres-backed log (Mode 3 / HA).
+pub struct PgSseEventLog {
+    pool: PgPool,
+}
+
+impl PgSseEventLog {
+    #[must_use]
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl SseEventLog for PgSseEventLog {
+    async fn append(
+        &self,
+        session_key: &str,
+        origin: &EventOrigin,
+        data: &str,
+        settings: &McpSseReplaySettings,
+    ) -> anyhow::Result<i64> {
+        let (max_events, retention_secs) = clamp(settings);
+        let (upstream_id, upstream_event_id, contract_event_id) = match origin {
+            EventOrigin::Upstream {
+                upstream_id,
+                event_id,
+            } => (Some(upstream_id.as_str()), event_id.as_deref(), None),
+            EventOrigin::Contract { event_id } => (None, None, Some(i64::try_from(*event_id)?)),
+        };
+        let expires_at_unix = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs()
+            .saturating_add(retention_secs);
+        let id: i64 = sqlx::query_scalar(
+            r"
+insert into sse_event_log (
+  session_key, upstream_id, upstream_event_id, contract_event_id, data, expires_at
+)
+values ($1, $2, $3, $4, $5, to_timestamp($6))
+returning id
+",
+        )
+        .bind(session_key)
+        .bind(upstream_id)
+        .bind(upstream_event_id)
+        .bind(contract_event_id)
+        .bind(data)
+        .bind(i64::try_from(expires_at_unix).unwrap_or(i64::MAX))
+        .fetch_one(&self.pool)
+        .await?;
+
+        if id % PG_TRIM_EVERY == 0 {
+            sqlx::query(
+                r"
+delete from sse_event_log
+where session_key = $1
+  and id < (
+    select id
+    from sse_event_log
+    where session_key = $1
+    order by id desc
+    offset $2
+    limit 1
+  )
+",
+            )
+            .bind(session_key)
+            .bind(i64::try_from(max_events.saturating_sub(1)).unwrap_or(i64::MAX))
+            .execute(&self.pool)
+            .await?;
+        }
+        Ok(id)
+    }
+
+    async fn load(
+        &self,
+        session_key: &str,
+        settings: &McpSseReplaySettings,
+    ) -> anyhow::Result<Vec<LoggedEvent>> {
+        let (max_events, _) = clamp(settings);
+        let rows = sqlx::query(
+            r"
+select id, upstream_id, upstream_event_id, contract_event_id, data
+from (
+  select id, upstream_id, upstream_event_id, contract_event_id, data
+  from sse_event_log
+  where session_key = $1
+    and expires_at > now()
+  order by id desc
+  limit $2
+) t
+order by id asc
+",
+        )
+        .bind(session_key)
+        .bind(i64::try_from(max_events).unwrap_or(i64::MAX))
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut out = Vec::with_capacity(rows.len());
+        for row in rows {
+            let upstream_id: Option<String> = row.try_get("upstream_id")?;
+            let contract_event_id: Option<i64> = row.try_get("contract_event_id")?;
+            let origin = match (upstream_id, contract_event_id) {
+                (Some(upstream_id), _) => EventOrigin::Upstream {
+                    upstream_id,
+                    event_id: row.try_get("upstream_event_id")?,
+                },
+                (None, Some(id)) => EventOrigin::Contract {
+                    event_id: u64::try_from(id)?,
+                },
+                (None, None) => continue,
+            };
+            out.push(LoggedEvent {
+                seq: row.try_get("id")?,
+                origin,
+                data: row.try_get("data")?,
+            });
+        }
+        Ok(out)
+    }
+
+    async fn cleanup_expired(&self) -> anyhow::Result<u64> {
+        let res = sqlx::query("delete from sse_event_log where expires_at < now()")
+            .execute(&self.pool)
+            .await?;
+        Ok(res.rows_affected())
+    }
+}
+
+pub fn spawn_cleanup_task(log: Arc<dyn SseEventLog>, shutdown: CancellationToken) {
+    tokio::spawn(async move {
+        let mut tick = tokio::time::interval(CLEANUP_INTERVAL);
+        tick.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+        loop {
+            tokio::select! {
+                () = shutdown.cancelled() => break,
+                _ = tick.tick() => {
+                    if let Err(e) = log.cleanup_expired().await {
+                        tracing::warn!(error = %e, "sse event log cleanup tick failed");
+                    }
+                }
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn settings(max_events: u32) -> McpSseReplaySettings {
+        McpSseReplaySettings {
+            enabled: true,
+            max_events,
+            retention_secs: 60,
+        }
+    }
+
+    fn upstream(id: &str, event_id: Option<&str>) -> EventOrigin {
+        EventOrigin::Upstream {
+            upstream_id: id.to_string(),
+            event_id: event_id.map(ToString::to_string),
+        }
+    }
+
+    #[test]
+    fn event_ids_round_trip() {
+        assert_eq!(format_event_id(42), "gw.42");
+        assert_eq!(parse_event_id("gw.42"), Some(42));
+        assert_eq!(parse_event_id("gw.-1"), None);
+        assert_eq!(parse_event_id("u1/gw.42"), None);
+        assert_eq!(parse_event_id("42"), None);
+    }
+
+    #[tokio::test]
+    async fn memory_log_is_bounded_and_replays_after_cursor() -> anyhow::Result<()> {
+        let log = MemorySseEventLog::new();
+        let s = settings(3);
+        for i in 1..=5 {
+            let seq = log
+                .append(
+                    "sess",
+                    &upstream("u1", Some(&format!("e{i}"))),
+                    &format!("d{i}"),
+                    &s,
+                )
+                .await?;
+            assert_eq!(seq, i);
+        }
+        log.append("sess", &EventOrigin::Contract { event_id: 9 }, "c", &s)
+            .await?;
+
+        let events = log.load("sess", &s).await?;
+        assert_eq!(
+            events.iter().map(|e| e.seq).collect::<Vec<_>>(),
+            vec![4, 5, 6]
+        );
+        assert!(log.load("other", &s).await?.is_empty());
+
+        let plan = plan_resume(events, 4);
+        assert_eq!(
+            plan.replay.iter().map(|e| e.seq).collect::<Vec<_>>(),
+            vec![5, 6]
+        );
+        assert_eq!(
+            plan.upstream_cursors.get("u1").map(String::as_str),
+            Some("e5")
+        );
+        assert_eq!(plan.contract_after, Some(9));
+        Ok(())
+    }
+
+    #[test]
+    fn validate_rejects_out_of_range_settings() {
+        assert!(validate_replay_settings(&McpSseReplaySettings::default()).is_ok());
+        assert!(validate_replay_settings(&settings(0)).is_err());
+        assert!(validate_replay_settings(&settings(MAX_EVENTS_LIMIT + 1)).is_err());
+        let mut s = settings(10);
+        s.retention_secs = MAX_RETENTION_SECS + 1;
+        assert!(validate_replay_settings(&s).is_err());
+    }
+}
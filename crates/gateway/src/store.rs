@@ -27,6 +27,74 @@ pub struct McpProfileSettings {
     /// Security policy for upstream interactions and proxying.
     #[serde(default)]
     pub security: McpSecuritySettings,
+    /// Per-session SSE event log for `Last-Event-ID` resumption.
+    #[serde(default)]
+    pub sse_replay: McpSseReplaySettings,
+    /// Expose search/describe/call meta-tools instead of the full tool list.
+    #[serde(default)]
+    pub tool_search: McpToolSearchSettings,
+    /// Response shaping applied to proxied upstream MCP `tools/call` results (base pipeline;
+    /// per-tool chains live in `toolPolicies[].responseTransforms`).
+    #[serde(default)]
+    pub response_transforms: Vec<unrelated_http_tools::config::ResponseTransform>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct McpToolSearchSettings {
+    /// If true, `tools/list` returns only the `search_tools` / `describe_tool` / `call_tool`
+    /// meta-tools; the aggregated tools stay callable through `call_tool`.
+    #[serde(default)]
+    pub enabled: bool,
+    /// Upper bound on (and default for) the number of `search_tools` results.
+    #[serde(default = "default_tool_search_max_results")]
+    pub max_results: u32,
+}
+
+fn default_tool_search_max_results() -> u32 {
+    10
+}
+
+impl Default for McpToolSearchSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            max_results: default_tool_search_max_results(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct McpSseReplaySettings {
+    /// If true, every event on the merged SSE stream is logged and gets a gateway event id
+    /// (`gw.<seq>`); reconnects with that id replay missed events (on any replica in Mode 3).
+    #[serde(default)]
+    pub enabled: bool,
+    /// Events retained per session (oldest are dropped first).
+    #[serde(default = "default_sse_replay_max_events")]
+    pub max_events: u32,
+    /// How long logged events are retained.
+    #[serde(default = "default_sse_replay_retention_secs")]
+    pub retention_secs: u64,
+}
+
+fn default_sse_replay_max_events() -> u32 {
+    256
+}
+
+fn default_sse_replay_retention_secs() -> u64 {
+    300
+}
+
+impl Default for McpSseReplaySettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            max_events: default_sse_replay_max_events(),
+            retention_secs: default_sse_replay_retention_secs(),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -52,6 +120,10 @@ pub struct McpSecuritySettings {
     /// here.
     #[serde(default)]
     pub transport_limits: TransportLimitsSettings,
+
+    /// Optional client IP allowlist (CIDRs or bare IPs). Empty => no allowlist configured.
+    #[serde(default)]
+    pub allowed_cidrs: Vec<String>,
 }
 
 impl Default for McpSecuritySettings {
@@ -61,6 +133,7 @@ impl Default for McpSecuritySettings {
             upstream_default: UpstreamSecurityPolicy::default(),
             upstream_overrides: std::collections::HashMap::new(),
             transport_limits: TransportLimitsSettings::default(),
+            allowed_cidrs: Vec::new(),
         }
     }
 }
@@ -545,6 +618,11 @@ pub struct Profile {
     ///
     /// Semantics: empty list => no allowlist configured (allow all tools).
     pub enabled_tools: Vec<String>,
+    /// Per-session narrowing from the authenticating API key's tool allowlist.
+    ///
+    /// Never persisted: populated from the session token after data-plane auth. Empty => no
+    /// narrowing.
+    pub api_key_tool_allowlist: Vec<String>,
     /// Per-profile data-plane auth policy (Mode 3; optional in Mode 1).
     pub data_plane_auth_mode: DataPlaneAuthMode,
     /// If enabled, accept `x-api-key: <secret>` as an alias for `Authorization: Bearer <secret>`.
@@ -631,12 +709,16 @@ pub struct AdminProfile {
 pub enum ToolSourceKind {
     Http,
     Openapi,
+    Workflow,
+    Content,
 }
 
 #[derive(Debug, Clone)]
 pub enum ToolSourceSpec {
-    Http(HttpServerConfig),
-    Openapi(ApiServerConfig),
+    Http(Box<HttpServerConfig>),
+    Openapi(Box<ApiServerConfig>),
+    Workflow(crate::workflow::WorkflowSourceConfig),
+    Content(crate::content_source::ContentSourceConfig),
 }
 
 #[derive(Debug, Clone)]
@@ -661,6 +743,109 @@ pub struct OidcPrincipalBinding {
     pub enabled: bool,
 }
 
+/// Tenant-configured OIDC issuer (Mode 3). Tokens whose `iss` matches are validated against this
+/// issuer's JWKS instead of the Gateway-wide issuer.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TenantOidcIssuer {
+    pub issuer: String,
+    /// Accepted `aud` values (at least one).
+    pub audiences: Vec<String>,
+    /// Optional JWKS URI override; otherwise discovered via `/.well-known/openid-configuration`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub jwks_uri: Option<String>,
+    /// Accepted JWS algorithms (e.g. `RS256`, `ES256`).
+    pub allowed_algs: Vec<String>,
+    pub enabled: bool,
+}
+
+/// Tenant-configured OAuth client the Gateway uses to obtain per-principal tokens for an upstream
+/// (authorization code flow with PKCE).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpstreamOAuthClient {
+    /// Upstream id as referenced by profiles (tenant-owned upstreams use their internal id).
+    pub upstream_id: String,
+    pub client_id: String,
+    /// Name of the tenant secret holding the client secret (`None` => public client).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub client_secret_ref: Option<String>,
+    #[serde(default)]
+    pub scopes: Vec<String>,
+    /// Optional override; otherwise discovered from the upstream's authorization server metadata.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub authorization_endpoint: Option<String>,
+    /// Optional override; otherwise discovered from the upstream's authorization server metadata.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub token_endpoint: Option<String>,
+    pub enabled: bool,
+}
+
+/// Upstream OAuth tokens for one principal (stored encrypted).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpstreamOAuthToken {
+    pub access_token: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub refresh_token: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires_at_unix: Option<i64>,
+}
+
+/// A linked principal (token metadata only; never exposes the tokens).
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpstreamOAuthLink {
+    pub principal: String,
+    pub expires_at_unix: Option<i64>,
+    pub has_refresh_token: bool,
+    pub updated_at_unix: i64,
+}
+
+/// Pending account-link attempt, keyed by the hash of the OAuth `state` parameter.
+#[derive(Debug, Clone)]
+pub struct UpstreamOAuthLinkState {
+    pub state_hash: String,
+    pub tenant_id: String,
+    pub upstream_id: String,
+    pub principal: String,
+    /// PKCE verifier (stored encrypted).
+    pub code_verifier: String,
+    pub redirect_uri: String,
+    pub expires_at_unix: i64,
+}
+
+/// Claims-based OIDC authorization rule: when all conditions match a validated token's claims,
+/// the principal is granted the tenant (`profile_id` NULL) or a single profile.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OidcClaimRule {
+    pub id: String,
+    pub issuer: String,
+    pub profile_id: Option<String>,
+    pub enabled: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    pub conditions: Vec<OidcClaimCondition>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OidcClaimCondition {
+    /// Claim name (`groups`) or JSON pointer into the claims (`/realm_access/roles`).
+    pub claim: String,
+    pub op: OidcClaimOp,
+    pub value: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum OidcClaimOp {
+    Equals,
+    Contains,
+    EndsWith,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ApiKeyMetadata {
@@ -673,12 +858,61 @@ pub struct ApiKeyMetadata {
     pub total_tool_calls_attempted: i64,
     pub total_requests_attempted: i64,
     pub created_at_unix: i64,
+    /// Optional hard expiry. Expired keys no longer authenticate.
+    pub expires_at_unix: Option<i64>,
+    /// Optional per-key tool allowlist (`source:tool`) that narrows the profile's `enabled_tools`.
+    ///
+    /// Semantics: empty list => no narrowing.
+    pub tool_allowlist: Vec<String>,
+    /// Set when this key was issued by rotating another key.
+    pub rotated_from_id: Option<String>,
+    /// Optional client IP allowlist (CIDRs or bare IPs). Empty => no allowlist configured.
+    pub allowed_cidrs: Vec<String>,
 }
 
 #[derive(Debug, Clone)]
 pub struct ApiKeyAuth {
     pub api_key_id: String,
     pub tenant_id: String,
+    /// Per-key tool allowlist (empty => no narrowing).
+    pub tool_allowlist: Vec<String>,
+    /// Per-key client IP allowlist (empty => no allowlist configured).
+    pub allowed_cidrs: Vec<String>,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct PutApiKeyInput<'a> {
+    pub tenant_id: &'a str,
+    pub api_key_id: &'a str,
+    pub profile_id: Option<&'a str>,
+    pub name: &'a str,
+    pub prefix: &'a str,
+    pub secret_hash: &'a str,
+    pub expires_at_unix: Option<i64>,
+    pub tool_allowlist: &'a [String],
+    pub allowed_cidrs: &'a [String],
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct RotateApiKeyInput<'a> {
+    pub tenant_id: &'a str,
+    /// Key being rotated. Must be active (not revoked, not expired).
+    pub api_key_id: &'a str,
+    pub successor_id: &'a str,
+    pub successor_prefix: &'a str,
+    pub successor_secret_hash: &'a str,
+    /// Optional expiry for the successor key.
+    pub successor_expires_at_unix: Option<i64>,
+    /// How long the old key stays valid after rotation (never extends an existing expiry).
+    pub grace_period_secs: i64,
+}
+
+#[derive(Debug, Clone)]
+pub struct ApiKeyRotation {
+    /// Metadata of the newly issued key (inherits profile, name, tool and IP allowlists).
+    pub successor: ApiKeyMetadata,
+    /// Effective expiry of the rotated key.
+    pub previous_expires_at_unix: i64,
 }
 
 #[derive(Debug, Clone)]
@@ -795,6 +1029,9 @@ pub trait Store: Send + Sync {
         tenant_id: &str,
     ) -> anyhow::Result<Option<TransportLimitsSettings>>;
 
+    /// Tenant-level client IP allowlist (Mode 3). Empty => no allowlist configured.
+    async fn get_tenant_allowed_cidrs(&self, tenant_id: &str) -> anyhow::Result<Vec<String>>;
+
     /// Validate a data-plane API key secret for a tenant/profile context.
     ///
     /// IMPORTANT: the caller's secret MUST NOT be forwarded to any upstream.
@@ -842,6 +1079,70 @@ pub trait Store: Send + Sync {
         issuer: &str,
         subject: &str,
     ) -> anyhow::Result<bool>;
+
+    /// Enabled claims-based OIDC rules for a tenant and issuer (JWT mode).
+    ///
+    /// Consulted when no explicit principal binding matches; evaluation happens in
+    /// `crate::oidc_rules`.
+    async fn list_enabled_oidc_claim_rules(
+        &self,
+        tenant_id: &str,
+        issuer: &str,
+    ) -> anyhow::Result<Vec<OidcClaimRule>>;
+
+    /// Tenant-configured OIDC issuer matching a token's `iss` (JWT mode).
+    async fn get_tenant_oidc_issuer(
+        &self,
+        tenant_id: &str,
+        issuer: &str,
+    ) -> anyhow::Result<Option<TenantOidcIssuer>>;
+
+    /// Enabled tenant-configured OIDC issuers (advertised in protected-resource metadata).
+    async fn list_enabled_tenant_oidc_issuers(
+        &self,
+        tenant_id: &str,
+    ) -> anyhow::Result<Vec<TenantOidcIssuer>>;
+
+    /// Tenant OAuth client for an upstream (per-principal upstream authorization).
+    async fn get_upstream_oauth_client(
+        &self,
+        tenant_id: &str,
+        upstream_id: &str,
+    ) -> anyhow::Result<Option<UpstreamOAuthClient>>;
+
+    /// Decrypted upstream OAuth tokens for a principal.
+    async fn get_upstream_oauth_token(
+        &self,
+        tenant_id: &str,
+        upstream_id: &str,
+        principal: &str,
+    ) -> anyhow::Result<Option<UpstreamOAuthToken>>;
+
+    /// Store (encrypt) upstream OAuth tokens for a principal (after linking or refresh).
+    async fn put_upstream_oauth_token(
+        &self,
+        tenant_id: &str,
+        upstream_id: &str,
+        principal: &str,
+        token: &UpstreamOAuthToken,
+    ) -> anyhow::Result<()>;
+
+    async fn put_upstream_oauth_link_state(
+        &self,
+        link: &UpstreamOAuthLinkState,
+    ) -> anyhow::Result<()>;
+
+    /// Look up a pending link attempt without consuming it (expired attempts are never returned).
+    async fn get_upstream_oauth_link_state(
+        &self,
+        state_hash: &str,
+    ) -> anyhow::Result<Option<UpstreamOAuthLinkState>>;
+
+    /// Consume a pending link attempt (single use; expired attempts are never returned).
+    async fn take_upstream_oauth_link_state(
+        &self,
+        state_hash: &str,
+    ) -> anyhow::Result<Option<UpstreamOAuthLinkState>>;
 }
 
 #[async_trait]
@@ -890,16 +1191,24 @@ pub trait AdminStore: Send + Sync {
 
     // Mode 3: tenant API keys for data-plane auth.
     async fn list_api_keys(&self, tenant_id: &str) -> anyhow::Result<Vec<ApiKeyMetadata>>;
-    async fn put_api_key(
+    async fn put_api_key(&self, input: PutApiKeyInput<'_>) -> anyhow::Result<()>;
+    async fn revoke_api_key(&self, tenant_id: &str, api_key_id: &str) -> anyhow::Result<bool>;
+    /// Issue a successor for an active key and cap the old key's expiry at `now + grace`.
+    ///
+    /// Returns `None` if the key does not exist or is no longer active.
+    async fn rotate_api_key(
+        &self,
+        input: RotateApiKeyInput<'_>,
+    ) -> anyhow::Result<Option<ApiKeyRotation>>;
+
+    // Tenant token revocation list (control plane).
+    /// Revoke a tenant token by id. Returns `false` if it was already revoked.
+    async fn revoke_tenant_token(&self, tenant_id: &str, token_id: &str) -> anyhow::Result<bool>;
+    async fn is_tenant_token_revoked(
         &self,
         tenant_id: &str,
-        api_key_id: &str,
-        profile_id: Option<&str>,
-        name: &str,
-        prefix: &str,
-        secret_hash: &str,
-    ) -> anyhow::Result<()>;
-    async fn revoke_api_key(&self, tenant_id: &str, api_key_id: &str) -> anyhow::Result<bool>;
+        token_id: &str,
+    ) -> anyhow::Result<bool>;
 
     // OIDC principal bindings (issuer + subject) -> tenant/profile scope.
     async fn list_oidc_principals(
@@ -925,6 +1234,76 @@ pub trait AdminStore: Send + Sync {
         profile_id: Option<&str>,
     ) -> anyhow::Result<u64>;
 
+    // Tenant-configured OIDC issuers.
+    async fn list_tenant_oidc_issuers(
+        &self,
+        tenant_id: &str,
+    ) -> anyhow::Result<Vec<TenantOidcIssuer>>;
+
+    async fn put_tenant_oidc_issuer(
+        &self,
+        tenant_id: &str,
+        issuer: &TenantOidcIssuer,
+    ) -> anyhow::Result<()>;
+
+    async fn delete_tenant_oidc_issuer(
+        &self,
+        tenant_id: &str,
+        issuer: &str,
+    ) -> anyhow::Result<bool>;
+
+    // Upstream OAuth clients + linked principals (Gateway as OAuth client to upstreams).
+    async fn list_upstream_oauth_clients(
+        &self,
+        tenant_id: &str,
+    ) -> anyhow::Result<Vec<UpstreamOAuthClient>>;
+
+    async fn put_upstream_oauth_client(
+        &self,
+        tenant_id: &str,
+        client: &UpstreamOAuthClient,
+    ) -> anyhow::Result<()>;
+
+    /// Deletes the client and all linked tokens.
+    async fn delete_upstream_oauth_client(
+        &self,
+        tenant_id: &str,
+        upstream_id: &str,
+    ) -> anyhow::Result<bool>;
+
+    async fn list_upstream_oauth_links(
+        &self,
+        tenant_id: &str,
+        upstream_id: &str,
+    ) -> anyhow::Result<Vec<UpstreamOAuthLink>>;
+
+    async fn delete_upstream_oauth_link(
+        &self,
+        tenant_id: &str,
+        upstream_id: &str,
+        principal: &str,
+    ) -> anyhow::Result<bool>;
+
+    // Claims-based OIDC authorization rules -> tenant/profile scope.
+    async fn list_oidc_claim_rules(
+        &self,
+        tenant_id: &str,
+        issuer: &str,
+    ) -> anyhow::Result<Vec<OidcClaimRule>>;
+
+    async fn put_oidc_claim_rule(
+        &self,
+        tenant_id: &str,
+        rule: &OidcClaimRule,
+    ) -> anyhow::Result<()>;
+
+    async fn delete_oidc_claim_rule(
+        &self,
+        tenant_id: &str,
+        issuer: &str,
+        rule_id: &str,
+    ) -> anyhow::Result<bool>;
+
     // ---------------------------------------------------------------------
     // Audit settings + audit event querying (Mode 3 only)
     // ---------------------------------------------------------------------
@@ -940,6 +1319,15 @@ pub trait AdminStore: Send + Sync {
         limits: &TransportLimitsSettings,
     ) -> anyhow::Result<()>;
 
+    /// Returns `None` if the tenant does not exist.
+    async fn get_tenant_ip_allowlist(&self, tenant_id: &str)
+    -> anyhow::Result<Option<Vec<String>>>;
+    async fn put_tenant_ip_allowlist(
+        &self,
+        tenant_id: &str,
+        allowed_cidrs: &[String],
+    ) -> anyhow::Result<()>;
+
     async fn get_tenant_audit_settings(
         &self,
         tenant_id: &str,
@@ -1042,6 +1430,7 @@ impl ConfigStore {
             source_ids: cfg.upstreams.clone(),
             transforms: cfg.transforms.clone(),
             enabled_tools: cfg.tools.clone().unwrap_or_default(),
+            api_key_tool_allowlist: Vec::new(),
             // Mode 1 defaults: data plane is unauthenticated unless configured otherwise.
             data_plane_auth_mode: DataPlaneAuthMode::Disabled,
             accept_x_api_key: false,
@@ -1138,6 +1527,10 @@ impl Store for ConfigStore {
         Ok(None)
     }
 
+    async fn get_tenant_allowed_cidrs(&self, _tenant_id: &str) -> anyhow::Result<Vec<String>> {
+        Ok(Vec::new())
+    }
+
     async fn authenticate_api_key(
         &self,
         tenant_id: &str,
@@ -1166,6 +1559,8 @@ impl Store for ConfigStore {
         Ok(Some(ApiKeyAuth {
             api_key_id,
             tenant_id: tenant_id.to_string(),
+            tool_allowlist: Vec::new(),
+            allowed_cidrs: Vec::new(),
         }))
     }
 
@@ -1205,4 +1600,75 @@ impl Store for ConfigStore {
     ) -> anyhow::Result<bool> {
         Ok(false)
     }
+
+    async fn list_enabled_oidc_claim_rules(
+        &self,
+        _tenant_id: &str,
+        _issuer: &str,
+    ) -> anyhow::Result<Vec<OidcClaimRule>> {
+        Ok(Vec::new())
+    }
+
+    async fn get_tenant_oidc_issuer(
+        &self,
+        _tenant_id: &str,
+        _issuer: &str,
+    ) -> anyhow::Result<Option<TenantOidcIssuer>> {
+        Ok(None)
+    }
+
+    async fn list_enabled_tenant_oidc_issuers(
+        &self,
+        _tenant_id: &str,
+    ) -> anyhow::Result<Vec<TenantOidcIssuer>> {
+        Ok(Vec::new())
+    }
+
+    async fn get_upstream_oauth_client(
+        &self,
+        _tenant_id: &str,
+        _upstream_id: &str,
+    ) -> anyhow::Result<Option<UpstreamOAuthClient>> {
+        Ok(None)
+    }
+
+    async fn get_upstream_oauth_token(
+        &self,
+        _tenant_id: &str,
+        _upstream_id: &str,
+        _principal: &str,
+    ) -> anyhow::Result<Option<UpstreamOAuthToken>> {
+        Ok(None)
+    }
+
+    async fn put_upstream_oauth_token(
+        &self,
+        _tenant_id: &str,
+        _upstream_id: &str,
+        _principal: &str,
+        _token: &UpstreamOAuthToken,
+    ) -> anyhow::Result<()> {
+        anyhow::bail!("upstream OAuth is not supported in config mode")
+    }
+
+    async fn put_upstream_oauth_link_state(
+        &self,
+        _link: &UpstreamOAuthLinkState,
+    ) -> anyhow::Result<()> {
+        anyhow::bail!("upstream OAuth is not supported in config mode")
+    }
+
+    async fn get_upstream_oauth_link_state(
+        &self,
+        _state_hash: &str,
+    ) -> anyhow::Result<Option<UpstreamOAuthLinkState>> {
+        Ok(None)
+    }
+
+    async fn take_upstream_oauth_link_state(
+        &self,
+        _state_hash: &str,
+    ) -> anyhow::Result<Option<UpstreamOAuthLinkState>> {
+        Ok(None)
+    }
 }
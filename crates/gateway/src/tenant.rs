@@ -1,16 +1,20 @@
 use crate::audit::{AuditActor, AuditError, HttpAuditEvent};
 use crate::profile_http::{
     DataPlaneAuthSettings, DataPlaneLimitsSettings, NullableString, NullableU64,
-    default_data_plane_auth_mode, resolve_nullable_u64, validate_tool_allowlist,
-    validate_tool_timeout_and_policies,
+    default_data_plane_auth_mode, resolve_nullable_u64, validate_response_transforms,
+    validate_tool_allowlist, validate_tool_timeout_and_policies, validate_transforms,
 };
 use crate::serde_helpers::default_true;
 use crate::store::{
     AdminProfile, AdminStore, AdminUpstream, ApiKeyMetadata, DataPlaneAuthMode, McpProfileSettings,
-    PutProfileDataPlaneAuth, PutProfileFlags, PutProfileInput, PutProfileLimits,
-    TenantSecretMetadata, ToolSourceKind, TransportLimitsSettings, UpstreamEndpoint,
+    PutApiKeyInput, PutProfileDataPlaneAuth, PutProfileFlags, PutProfileInput, PutProfileLimits,
+    RotateApiKeyInput, TenantOidcIssuer, TenantSecretMetadata, ToolSourceKind,
+    TransportLimitsSettings, UpstreamEndpoint, UpstreamOAuthClient, UpstreamOAuthLink,
+};
+use crate::tenant_token::{
+    TenantRole, TenantScope, TenantSigner, TenantTokenPayloadV1, TenantTokenRevocations,
+    resolve_issue_scopes,
 };
-use crate::tenant_token::TenantSigner;
 use crate::tool_policy::ToolPolicy;
 use axum::extract::Path;
 use axum::http::{HeaderMap, StatusCode};
@@ -27,18 +31,26 @@ use std::time::{Instant, SystemTime, UNIX_EPOCH};
 use unrelated_http_tools::config::AuthConfig;
 use unrelated_http_tools::config::HttpServerConfig;
 use unrelated_openapi_tools::config::{
-    ApiServerConfig, AutoDiscoverConfig, HashPolicy, OpenApiOverridesConfig,
+    ApiServerConfig, AutoDiscoverConfig, HashPolicy, OpenApiOverridesConfig, SecurityConfig,
 };
 use unrelated_openapi_tools::runtime::OpenApiToolSource;
 use unrelated_tool_transforms::TransformPipeline;
 use uuid::{Uuid, Version};
 
+/// Active API keys expiring within this window are reported in `expiryWarnings`.
+const API_KEY_EXPIRY_WARNING_WINDOW_SECS: i64 = 7 * 24 * 60 * 60;
+/// Default grace period for the old key when rotating.
+const DEFAULT_API_KEY_ROTATION_GRACE_SECS: u64 = 24 * 60 * 60;
+const MAX_API_KEY_ROTATION_GRACE_SECS: u64 = 30 * 24 * 60 * 60;
+
 const OIDC_NOT_CONFIGURED_MSG: &str = "JWT/OIDC is unavailable because OIDC is not configured on the Gateway (missing UNRELATED_GATEWAY_OIDC_ISSUER). Configure OIDC or choose a different mode.";
 
 #[derive(Clone)]
 pub struct TenantState {
     pub store: Option<Arc<dyn AdminStore>>,
     pub signer: TenantSigner,
+    /// Local view of the tenant token revocation list (kept coherent via `pg_invalidation`).
+    pub revocations: Arc<TenantTokenRevocations>,
     pub shared_source_ids: Arc<std::collections::HashSet<String>>,
     /// Shared MCP data-plane state (used for profile surface probing).
     pub mcp_state: Arc<crate::mcp::McpState>,
@@ -57,6 +69,18 @@ pub fn router(state: Arc<TenantState>) -> Router {
             "/tenant/v1/upstreams/{upstream_id}/surface",
             get(get_upstream_surface),
         )
+        .route(
+            "/tenant/v1/upstreams/{upstream_id}/oauth",
+            get(get_upstream_oauth)
+                .put(put_upstream_oauth)
+                .delete(delete_upstream_oauth),
+        )
+        .route(
+            "/tenant/v1/upstreams/{upstream_id}/oauth/links",
+            get(list_upstream_oauth_links)
+                .post(start_upstream_oauth_link)
+                .delete(delete_upstream_oauth_link),
+        )
         .route(
             "/tenant/v1/profiles",
             get(list_profiles).post(create_profile),
@@ -99,6 +123,12 @@ pub fn router(state: Arc<TenantState>) -> Router {
             get(list_api_keys).post(create_api_key),
         )
         .route("/tenant/v1/api-keys/{api_key_id}", delete(revoke_api_key))
+        .route(
+            "/tenant/v1/api-keys/{api_key_id}/rotate",
+            axum::routing::post(rotate_api_key),
+        )
+        .route("/tenant/v1/tokens", axum::routing::post(issue_scoped_token))
+        .route("/tenant/v1/tokens/{token_id}", delete(revoke_tenant_token))
         .route(
             "/tenant/v1/audit/settings",
             get(get_audit_settings).put(put_audit_settings),
@@ -107,6 +137,21 @@ pub fn router(state: Arc<TenantState>) -> Router {
             "/tenant/v1/transport/limits",
             get(get_transport_limits).put(put_transport_limits),
         )
+        .route(
+            "/tenant/v1/network/allowlist",
+            get(get_ip_allowlist).put(put_ip_allowlist),
+        )
+        .route(
+            "/tenant/v1/oidc/issuers",
+            get(list_oidc_issuers)
+                .put(put_oidc_issuer)
+                .delete(delete_oidc_issuer),
+        )
+        .route("/tenant/v1/sessions", get(list_sessions))
+        .route(
+            "/tenant/v1/sessions/{session_id}",
+            get(get_session).delete(terminate_session),
+        )
         .route("/tenant/v1/audit/events", get(list_audit_events))
         .route(
             "/tenant/v1/audit/analytics/tool-calls/by-tool",
@@ -135,6 +180,8 @@ struct OpenApiInspectRequest {
 #[serde(rename_all = "camelCase")]
 struct OpenApiInspectResponse {
     title: Option<String>,
+    /// Detected spec version (`2.0`, `3.0` or `3.1`).
+    spec_version: Option<&'static str>,
     inferred_base_url: String,
     suggested_id: String,
     tools: Vec<Tool>,
@@ -209,6 +256,24 @@ async fn validate_no_self_upstream_loop(
     Ok(())
 }
 
+/// Resolve an upstream id as seen by a tenant: prefer tenant-owned, else global.
+async fn resolve_visible_upstream_id(
+    store: &dyn AdminStore,
+    tenant_id: &str,
+    upstream_id: &str,
+) -> Result<String, Response> {
+    let internal_id = tenant_upstream_internal_id(tenant_id, upstream_id);
+    match store.get_upstream(&internal_id).await {
+        Ok(Some(_)) => Ok(internal_id),
+        Ok(None) => match store.get_upstream(upstream_id).await {
+            Ok(Some(_)) => Ok(upstream_id.to_string()),
+            Ok(None) => Err((StatusCode::NOT_FOUND, "upstream not found").into_response()),
+            Err(e) => Err((StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response()),
+        },
+        Err(e) => Err((StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response()),
+    }
+}
+
 async fn resolve_upstream_ids_for_tenant(
     store: &dyn AdminStore,
     tenant_id: &str,
@@ -253,17 +318,66 @@ async fn resolve_upstreams_for_create_profile(
     Ok(resolved)
 }
 
-fn authn(headers: &HeaderMap, signer: &TenantSigner) -> Result<String, impl IntoResponse> {
+/// Authenticate a tenant token and require `scope` for the route.
+async fn authn(
+    headers: &HeaderMap,
+    state: &TenantState,
+    scope: TenantScope,
+) -> Result<String, Response> {
+    let payload = verify_tenant_token(headers, state).await?;
+    if !payload.allows(scope) {
+        return Err((
+            StatusCode::FORBIDDEN,
+            format!("tenant token is missing scope '{}'", scope.as_str()),
+        )
+            .into_response());
+    }
+    Ok(payload.tenant_id)
+}
+
+async fn verify_tenant_token(
+    headers: &HeaderMap,
+    state: &TenantState,
+) -> Result<TenantTokenPayloadV1, Response> {
     let Some(authz) = headers.get("Authorization").and_then(|h| h.to_str().ok()) else {
-        return Err((StatusCode::UNAUTHORIZED, "missing Authorization header"));
+        return Err((StatusCode::UNAUTHORIZED, "missing Authorization header").into_response());
     };
     let Some(token) = authz.strip_prefix("Bearer ").map(str::trim) else {
-        return Err((StatusCode::UNAUTHORIZED, "invalid Authorization header"));
+        return Err((StatusCode::UNAUTHORIZED, "invalid Authorization header").into_response());
     };
-    let payload = signer
+    let payload = state
+        .signer
         .verify(token)
-        .map_err(|_| (StatusCode::UNAUTHORIZED, "invalid tenant token"))?;
-    Ok(payload.tenant_id)
+        .map_err(|_| (StatusCode::UNAUTHORIZED, "invalid tenant token").into_response())?;
+
+    if let Some(token_id) = payload.token_id.as_deref()
+        && is_tenant_token_revoked(state, &payload, token_id).await?
+    {
+        return Err((StatusCode::UNAUTHORIZED, "tenant token revoked").into_response());
+    }
+    Ok(payload)
+}
+
+async fn is_tenant_token_revoked(
+    state: &TenantState,
+    payload: &TenantTokenPayloadV1,
+    token_id: &str,
+) -> Result<bool, Response> {
+    let tenant_id = payload.tenant_id.as_str();
+    if let Some(revoked) = state.revocations.get(tenant_id, token_id) {
+        return Ok(revoked);
+    }
+    let Some(store) = &state.store else {
+        return Ok(false);
+    };
+    let revoked = store
+        .is_tenant_token_revoked(tenant_id, token_id)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response())?;
+    state
+        .revocations
+        .put(tenant_id, token_id, revoked, Some(payload.exp_unix_secs));
+    Ok(revoked)
 }
 
 fn upstream_to_response(tenant_id: &str, u: AdminUpstream) -> Option<UpstreamResponse> {
@@ -311,7 +425,7 @@ async fn list_upstreams(
     axum::Extension(state): axum::Extension<Arc<TenantState>>,
     headers: HeaderMap,
 ) -> impl IntoResponse {
-    let tenant_id = match authn(&headers, &state.signer) {
+    let tenant_id = match authn(&headers, &state, TenantScope::UpstreamsRead).await {
         Ok(t) => t,
         Err(resp) => return resp.into_response(),
     };
@@ -342,7 +456,7 @@ async fn get_upstream(
     headers: HeaderMap,
     Path(upstream_id): Path<String>,
 ) -> impl IntoResponse {
-    let tenant_id = match authn(&headers, &state.signer) {
+    let tenant_id = match authn(&headers, &state, TenantScope::UpstreamsRead).await {
         Ok(t) => t,
         Err(resp) => return resp.into_response(),
     };
@@ -377,7 +491,7 @@ async fn put_upstream(
     Path(upstream_id): Path<String>,
     Json(req): Json<PutUpstreamRequest>,
 ) -> impl IntoResponse {
-    let tenant_id = match authn(&headers, &state.signer) {
+    let tenant_id = match authn(&headers, &state, TenantScope::UpstreamsWrite).await {
         Ok(t) => t,
         Err(resp) => return resp.into_response(),
     };
@@ -411,6 +525,19 @@ async fn put_upstream(
     // Outbound safety (SSRF hardening): validate upstream endpoints before storing them.
     let safety = crate::outbound_safety::gateway_outbound_http_safety();
     for ep in &endpoints {
+        if matches!(
+            ep.auth,
+            Some(AuthConfig::Sigv4 { .. } | AuthConfig::Hmac { .. })
+        ) {
+            return (
+                StatusCode::BAD_REQUEST,
+                format!(
+                    "upstream endpoint '{}': request signing auth (sigv4/hmac) is only supported for HTTP and OpenAPI tool sources",
+                    ep.id
+                ),
+            )
+                .into_response();
+        }
         // Upstream endpoint scheme policy: prefer HTTPS by default (dev override supported).
         if let Err(e) = crate::outbound_safety::check_upstream_https_policy(&ep.url) {
             return (
@@ -449,7 +576,7 @@ async fn delete_upstream(
     headers: HeaderMap,
     Path(upstream_id): Path<String>,
 ) -> impl IntoResponse {
-    let tenant_id = match authn(&headers, &state.signer) {
+    let tenant_id = match authn(&headers, &state, TenantScope::UpstreamsWrite).await {
         Ok(t) => t,
         Err(resp) => return resp.into_response(),
     };
@@ -679,7 +806,7 @@ async fn list_profiles(
     axum::Extension(state): axum::Extension<Arc<TenantState>>,
     headers: HeaderMap,
 ) -> impl IntoResponse {
-    let tenant_id = match authn(&headers, &state.signer) {
+    let tenant_id = match authn(&headers, &state, TenantScope::ProfilesRead).await {
         Ok(t) => t,
         Err(resp) => return resp.into_response(),
     };
@@ -705,7 +832,7 @@ async fn get_profile(
     headers: HeaderMap,
     Path(profile_id): Path<String>,
 ) -> impl IntoResponse {
-    let tenant_id = match authn(&headers, &state.signer) {
+    let tenant_id = match authn(&headers, &state, TenantScope::ProfilesRead).await {
         Ok(t) => t,
         Err(resp) => return resp.into_response(),
     };
@@ -753,7 +880,7 @@ async fn create_profile(
     headers: HeaderMap,
     Json(req): Json<CreateProfileRequest>,
 ) -> impl IntoResponse {
-    let tenant_id = match authn(&headers, &state.signer) {
+    let tenant_id = match authn(&headers, &state, TenantScope::ProfilesWrite).await {
         Ok(t) => t,
         Err(resp) => return resp.into_response(),
     };
@@ -768,7 +895,17 @@ async fn create_profile(
     }
 
     let profile_id = Uuid::new_v4().to_string();
-    let validated = match validate_create_profile_settings(state.as_ref(), &req) {
+    let oidc_configured = match crate::tenant_oidc::oidc_configured_for_tenant(
+        state.mcp_state.oidc.is_some(),
+        store.as_ref(),
+        &tenant_id,
+    )
+    .await
+    {
+        Ok(v) => v,
+        Err(e) => return (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+    };
+    let validated = match validate_create_profile_settings(&req, oidc_configured) {
         Ok(v) => v,
         Err(resp) => return *resp,
     };
@@ -841,8 +978,8 @@ struct CreateProfileValidatedSettings {
 }
 
 fn validate_create_profile_settings(
-    state: &TenantState,
     req: &CreateProfileRequest,
+    oidc_configured: bool,
 ) -> Result<CreateProfileValidatedSettings, Box<Response>> {
     if req.name.trim().is_empty() {
         return Err(Box::new(
@@ -858,8 +995,7 @@ fn validate_create_profile_settings(
             mode: default_data_plane_auth_mode(),
             accept_x_api_key: false,
         });
-    if data_plane_auth.mode == DataPlaneAuthMode::JwtEveryRequest && state.mcp_state.oidc.is_none()
-    {
+    if data_plane_auth.mode == DataPlaneAuthMode::JwtEveryRequest && !oidc_configured {
         return Err(Box::new(
             (StatusCode::BAD_REQUEST, OIDC_NOT_CONFIGURED_MSG).into_response(),
         ));
@@ -887,11 +1023,32 @@ fn validate_create_profile_settings(
     if let Err(msg) = validate_tool_allowlist(&enabled_tools) {
         return Err(Box::new((StatusCode::BAD_REQUEST, msg).into_response()));
     }
+    if let Err(msg) = validate_transforms(&req.transforms) {
+        return Err(Box::new((StatusCode::BAD_REQUEST, msg).into_response()));
+    }
     if let Err(msg) =
         crate::transport_limits::validate_transport_limits_settings(&mcp.security.transport_limits)
     {
         return Err(Box::new((StatusCode::BAD_REQUEST, msg).into_response()));
     }
+    if let Err(msg) = crate::ip_allowlist::validate_allowlist(&mcp.security.allowed_cidrs) {
+        return Err(Box::new(
+            (
+                StatusCode::BAD_REQUEST,
+                format!("mcp.security.allowedCidrs: {msg}"),
+            )
+                .into_response(),
+        ));
+    }
+    if let Err(msg) = crate::sse_log::validate_replay_settings(&mcp.sse_replay) {
+        return Err(Box::new((StatusCode::BAD_REQUEST, msg).into_response()));
+    }
+    if let Err(msg) = crate::tool_search::validate_settings(&mcp.tool_search) {
+        return Err(Box::new((StatusCode::BAD_REQUEST, msg).into_response()));
+    }
+    if let Err(msg) = validate_response_transforms(&mcp) {
+        return Err(Box::new((StatusCode::BAD_REQUEST, msg).into_response()));
+    }
 
     Ok(CreateProfileValidatedSettings {
         enabled_tools,
@@ -909,7 +1066,7 @@ async fn put_profile(
     Path(profile_id): Path<String>,
     Json(req): Json<PutProfileRequest>,
 ) -> impl IntoResponse {
-    let tenant_id = match authn(&headers, &state.signer) {
+    let tenant_id = match authn(&headers, &state, TenantScope::ProfilesWrite).await {
         Ok(t) => t,
         Err(resp) => return resp.into_response(),
     };
@@ -1054,8 +1211,25 @@ async fn tenant_put_profile_inner_impl(
     )
     .await?;
     let enabled_tools = req.tools.unwrap_or_default();
+    let oidc_configured = crate::tenant_oidc::oidc_configured_for_tenant(
+        state.mcp_state.oidc.is_some(),
+        store,
+        tenant_id,
+    )
+    .await
+    .map_err(|e| {
+        Box::new(TenantPutProfileOutcome::fail(
+            profile_id.clone(),
+            enabled_for_meta,
+            Some(profile_uuid),
+            StatusCode::INTERNAL_SERVER_ERROR,
+            e.to_string(),
+            AuditError::new("internal_error", e.to_string()),
+            Some(name.clone()),
+        ))
+    })?;
     let data_plane_auth = tenant_put_profile_resolve_auth(
-        state,
+        oidc_configured,
         &profile_id,
         enabled_for_meta,
         profile_uuid,
@@ -1081,13 +1255,14 @@ async fn tenant_put_profile_inner_impl(
         tool_call_timeout_secs,
         &tool_policies,
     )?;
-    tenant_put_profile_validate_transport_limits(
+    tenant_put_profile_validate_transforms(
         &profile_id,
         enabled_for_meta,
         profile_uuid,
         &name,
-        &mcp,
+        &req.transforms,
     )?;
+    tenant_put_profile_validate_security(&profile_id, enabled_for_meta, profile_uuid, &name, &mcp)?;
 
     tenant_put_profile_store_put(
         store,
@@ -1248,7 +1423,7 @@ async fn tenant_put_profile_resolve_upstreams(
 }
 
 fn tenant_put_profile_resolve_auth(
-    state: &TenantState,
+    oidc_configured: bool,
     profile_id: &str,
     enabled_for_meta: bool,
     profile_uuid: Uuid,
@@ -1260,7 +1435,7 @@ fn tenant_put_profile_resolve_auth(
         mode: existing.data_plane_auth_mode,
         accept_x_api_key: existing.accept_x_api_key,
     });
-    if auth.mode == DataPlaneAuthMode::JwtEveryRequest && state.mcp_state.oidc.is_none() {
+    if auth.mode == DataPlaneAuthMode::JwtEveryRequest && !oidc_configured {
         return Err(Box::new(TenantPutProfileOutcome::fail(
             profile_id.to_string(),
             enabled_for_meta,
@@ -1351,7 +1526,28 @@ fn tenant_put_profile_validate_tools(
     Ok(())
 }
 
-fn tenant_put_profile_validate_transport_limits(
+fn tenant_put_profile_validate_transforms(
+    profile_id: &str,
+    enabled_for_meta: bool,
+    profile_uuid: Uuid,
+    name_for_meta: &str,
+    transforms: &TransformPipeline,
+) -> TenantPutProfileStep<()> {
+    if let Err(msg) = validate_transforms(transforms) {
+        return Err(Box::new(TenantPutProfileOutcome::fail(
+            profile_id.to_string(),
+            enabled_for_meta,
+            Some(profile_uuid),
+            StatusCode::BAD_REQUEST,
+            msg.clone(),
+            AuditError::new("bad_request", msg),
+            Some(name_for_meta.to_string()),
+        )));
+    }
+    Ok(())
+}
+
+fn tenant_put_profile_validate_security(
     profile_id: &str,
     enabled_for_meta: bool,
     profile_uuid: Uuid,
@@ -1371,6 +1567,51 @@ fn tenant_put_profile_validate_transport_limits(
             Some(name_for_meta.to_string()),
         )));
     }
+    if let Err(msg) = crate::ip_allowlist::validate_allowlist(&mcp.security.allowed_cidrs) {
+        let msg = format!("mcp.security.allowedCidrs: {msg}");
+        return Err(Box::new(TenantPutProfileOutcome::fail(
+            profile_id.to_string(),
+            enabled_for_meta,
+            Some(profile_uuid),
+            StatusCode::BAD_REQUEST,
+            msg.clone(),
+            AuditError::new("bad_request", msg),
+            Some(name_for_meta.to_string()),
+        )));
+    }
+    if let Err(msg) = crate::sse_log::validate_replay_settings(&mcp.sse_replay) {
+        return Err(Box::new(TenantPutProfileOutcome::fail(
+            profile_id.to_string(),
+            enabled_for_meta,
+            Some(profile_uuid),
+            StatusCode::BAD_REQUEST,
+            msg.clone(),
+            AuditError::new("bad_request", msg),
+            Some(name_for_meta.to_string()),
+        )));
+    }
+    if let Err(msg) = crate::tool_search::validate_settings(&mcp.tool_search) {
+        return Err(Box::new(TenantPutProfileOutcome::fail(
+            profile_id.to_string(),
+            enabled_for_meta,
+            Some(profile_uuid),
+            StatusCode::BAD_REQUEST,
+            msg.clone(),
+            AuditError::new("bad_request", msg),
+            Some(name_for_meta.to_string()),
+        )));
+    }
+    if let Err(msg) = validate_response_transforms(mcp) {
+        return Err(Box::new(TenantPutProfileOutcome::fail(
+            profile_id.to_string(),
+            enabled_for_meta,
+            Some(profile_uuid),
+            StatusCode::BAD_REQUEST,
+            msg.clone(),
+            AuditError::new("bad_request", msg),
+            Some(name_for_meta.to_string()),
+        )));
+    }
     Ok(())
 }
 
@@ -1463,7 +1704,7 @@ async fn delete_profile(
     headers: HeaderMap,
     Path(profile_id): Path<String>,
 ) -> impl IntoResponse {
-    let tenant_id = match authn(&headers, &state.signer) {
+    let tenant_id = match authn(&headers, &state, TenantScope::ProfilesWrite).await {
         Ok(t) => t,
         Err(resp) => return resp.into_response(),
     };
@@ -1605,7 +1846,7 @@ async fn get_upstream_surface(
     headers: HeaderMap,
     Path(upstream_id): Path<String>,
 ) -> impl IntoResponse {
-    let tenant_id = match authn(&headers, &state.signer) {
+    let tenant_id = match authn(&headers, &state, TenantScope::UpstreamsRead).await {
         Ok(t) => t,
         Err(resp) => return resp.into_response(),
     };
@@ -1620,17 +1861,11 @@ async fn get_upstream_surface(
         Err(e) => return (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
     }
 
-    // Resolve upstream id: prefer tenant-owned, else global.
-    let internal_id = tenant_upstream_internal_id(&tenant_id, &upstream_id);
-    let resolved = match admin_store.get_upstream(&internal_id).await {
-        Ok(Some(_)) => internal_id,
-        Ok(None) => match admin_store.get_upstream(&upstream_id).await {
-            Ok(Some(_)) => upstream_id.clone(),
-            Ok(None) => return (StatusCode::NOT_FOUND, "upstream not found").into_response(),
-            Err(e) => return (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
-        },
-        Err(e) => return (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
-    };
+    let resolved =
+        match resolve_visible_upstream_id(admin_store.as_ref(), &tenant_id, &upstream_id).await {
+            Ok(id) => id,
+            Err(resp) => return resp,
+        };
 
     let profile = crate::store::Profile {
         id: format!("probe-upstream:{upstream_id}"),
@@ -1639,6 +1874,7 @@ async fn get_upstream_surface(
         source_ids: vec![resolved],
         transforms: TransformPipeline::default(),
         enabled_tools: vec![],
+        api_key_tool_allowlist: Vec::new(),
         data_plane_auth_mode: DataPlaneAuthMode::Disabled,
         accept_x_api_key: false,
         rate_limit_enabled: false,
@@ -1677,7 +1913,7 @@ async fn get_profile_surface(
     headers: HeaderMap,
     Path(profile_id): Path<String>,
 ) -> impl IntoResponse {
-    let tenant_id = match authn(&headers, &state.signer) {
+    let tenant_id = match authn(&headers, &state, TenantScope::ProfilesRead).await {
         Ok(t) => t,
         Err(resp) => return resp.into_response(),
     };
@@ -1720,6 +1956,7 @@ async fn get_profile_surface(
         source_ids,
         transforms: admin_profile.transforms,
         enabled_tools: admin_profile.enabled_tools,
+        api_key_tool_allowlist: Vec::new(),
         data_plane_auth_mode: admin_profile.data_plane_auth_mode,
         accept_x_api_key: admin_profile.accept_x_api_key,
         rate_limit_enabled: admin_profile.rate_limit_enabled,
@@ -1773,13 +2010,25 @@ enum PutToolSourceBody {
         #[serde(default = "default_true")]
         enabled: bool,
         #[serde(flatten)]
-        config: HttpServerConfig,
+        config: Box<HttpServerConfig>,
     },
     Openapi {
         #[serde(default = "default_true")]
         enabled: bool,
         #[serde(flatten)]
-        config: ApiServerConfig,
+        config: Box<ApiServerConfig>,
+    },
+    Workflow {
+        #[serde(default = "default_true")]
+        enabled: bool,
+        #[serde(flatten)]
+        config: crate::workflow::WorkflowSourceConfig,
+    },
+    Content {
+        #[serde(default = "default_true")]
+        enabled: bool,
+        #[serde(flatten)]
+        config: crate::content_source::ContentSourceConfig,
     },
 }
 
@@ -1926,6 +2175,8 @@ fn tool_source_kind_str(k: ToolSourceKind) -> &'static str {
     match k {
         ToolSourceKind::Http => "http",
         ToolSourceKind::Openapi => "openapi",
+        ToolSourceKind::Workflow => "workflow",
+        ToolSourceKind::Content => "content",
     }
 }
 
@@ -1979,10 +2230,12 @@ fn openapi_default_config(spec_url: &str) -> ApiServerConfig {
         spec_hash_policy: HashPolicy::Warn,
         base_url: None,
         auth: None,
+        security: SecurityConfig::default(),
         auto_discover: AutoDiscoverConfig::Enabled(true),
         endpoints: std::collections::HashMap::new(),
         defaults: unrelated_http_tools::config::EndpointDefaults::default(),
         response_transforms: vec![],
+        xml: Default::default(),
         response_overrides: vec![],
         overrides: OpenApiOverridesConfig::default(),
     }
@@ -1993,7 +2246,7 @@ async fn openapi_inspect(
     headers: HeaderMap,
     Json(req): Json<OpenApiInspectRequest>,
 ) -> impl IntoResponse {
-    let tenant_id = match authn(&headers, &state.signer) {
+    let tenant_id = match authn(&headers, &state, TenantScope::ToolSourcesWrite).await {
         Ok(t) => t,
         Err(resp) => return resp.into_response(),
     };
@@ -2040,6 +2293,7 @@ async fn openapi_inspect(
             let suggested_id = suggest_source_id_from_title(title.as_deref(), spec_url);
             Json(OpenApiInspectResponse {
                 title,
+                spec_version: src.spec_version().map(|v| v.as_str()),
                 inferred_base_url: base_url,
                 suggested_id,
                 tools,
@@ -2059,7 +2313,7 @@ async fn validate_source_id(
     headers: HeaderMap,
     Json(req): Json<ValidateSourceIdRequest>,
 ) -> impl IntoResponse {
-    let tenant_id = match authn(&headers, &state.signer) {
+    let tenant_id = match authn(&headers, &state, TenantScope::ToolSourcesRead).await {
         Ok(t) => t,
         Err(resp) => return resp.into_response(),
     };
@@ -2121,7 +2375,7 @@ async fn list_tool_sources(
     axum::Extension(state): axum::Extension<Arc<TenantState>>,
     headers: HeaderMap,
 ) -> impl IntoResponse {
-    let tenant_id = match authn(&headers, &state.signer) {
+    let tenant_id = match authn(&headers, &state, TenantScope::ToolSourcesRead).await {
         Ok(t) => t,
         Err(resp) => return resp.into_response(),
     };
@@ -2150,7 +2404,7 @@ async fn get_tool_source(
     headers: HeaderMap,
     Path(source_id): Path<String>,
 ) -> impl IntoResponse {
-    let tenant_id = match authn(&headers, &state.signer) {
+    let tenant_id = match authn(&headers, &state, TenantScope::ToolSourcesRead).await {
         Ok(t) => t,
         Err(resp) => return resp.into_response(),
     };
@@ -2163,6 +2417,8 @@ async fn get_tool_source(
             let spec = match &s.spec {
                 crate::store::ToolSourceSpec::Http(cfg) => serde_json::to_value(cfg),
                 crate::store::ToolSourceSpec::Openapi(cfg) => serde_json::to_value(cfg),
+                crate::store::ToolSourceSpec::Workflow(cfg) => serde_json::to_value(cfg),
+                crate::store::ToolSourceSpec::Content(cfg) => serde_json::to_value(cfg),
             };
             let spec = match spec {
                 Ok(v) => v,
@@ -2189,7 +2445,7 @@ async fn get_tool_source_tools(
     headers: HeaderMap,
     Path(source_id): Path<String>,
 ) -> impl IntoResponse {
-    let tenant_id = match authn(&headers, &state.signer) {
+    let tenant_id = match authn(&headers, &state, TenantScope::ToolSourcesRead).await {
         Ok(t) => t,
         Err(resp) => return resp.into_response(),
     };
@@ -2222,7 +2478,7 @@ async fn put_tool_source(
     Path(source_id): Path<String>,
     Json(body): Json<PutToolSourceBody>,
 ) -> impl IntoResponse {
-    let tenant_id = match authn(&headers, &state.signer) {
+    let tenant_id = match authn(&headers, &state, TenantScope::ToolSourcesWrite).await {
         Ok(t) => t,
         Err(resp) => return resp.into_response(),
     };
@@ -2367,6 +2623,25 @@ async fn tenant_put_tool_source_inner(
         return outcome;
     }
 
+    if let PutToolSourceBody::Workflow { config, .. } = &body
+        && let Err(e) = config.validate()
+    {
+        return TenantPutToolSourceOutcome::fail(
+            StatusCode::BAD_REQUEST,
+            format!("invalid workflow: {e}"),
+            AuditError::new("bad_request", "invalid workflow"),
+        );
+    }
+    if let PutToolSourceBody::Content { config, .. } = &body
+        && let Err(e) = config.validate()
+    {
+        return TenantPutToolSourceOutcome::fail(
+            StatusCode::BAD_REQUEST,
+            format!("invalid content source: {e}"),
+            AuditError::new("bad_request", "invalid content source"),
+        );
+    }
+
     let (enabled, kind, spec_res) = match body {
         PutToolSourceBody::Http { enabled, config } => {
             (enabled, ToolSourceKind::Http, serde_json::to_value(&config))
@@ -2376,6 +2651,16 @@ async fn tenant_put_tool_source_inner(
             ToolSourceKind::Openapi,
             serde_json::to_value(&config),
         ),
+        PutToolSourceBody::Workflow { enabled, config } => (
+            enabled,
+            ToolSourceKind::Workflow,
+            serde_json::to_value(&config),
+        ),
+        PutToolSourceBody::Content { enabled, config } => (
+            enabled,
+            ToolSourceKind::Content,
+            serde_json::to_value(&config),
+        ),
     };
     let kind_for_meta = Some(format!("{kind:?}"));
     let enabled_for_meta = Some(enabled);
@@ -2417,7 +2702,7 @@ async fn delete_tool_source(
     headers: HeaderMap,
     Path(source_id): Path<String>,
 ) -> impl IntoResponse {
-    let tenant_id = match authn(&headers, &state.signer) {
+    let tenant_id = match authn(&headers, &state, TenantScope::ToolSourcesWrite).await {
         Ok(t) => t,
         Err(resp) => return resp.into_response(),
     };
@@ -2477,7 +2762,7 @@ async fn list_secrets(
     axum::Extension(state): axum::Extension<Arc<TenantState>>,
     headers: HeaderMap,
 ) -> impl IntoResponse {
-    let tenant_id = match authn(&headers, &state.signer) {
+    let tenant_id = match authn(&headers, &state, TenantScope::SecretsRead).await {
         Ok(t) => t,
         Err(resp) => return resp.into_response(),
     };
@@ -2496,7 +2781,7 @@ async fn put_secret(
     headers: HeaderMap,
     Json(req): Json<PutSecretRequest>,
 ) -> impl IntoResponse {
-    let tenant_id = match authn(&headers, &state.signer) {
+    let tenant_id = match authn(&headers, &state, TenantScope::SecretsWrite).await {
         Ok(t) => t,
         Err(resp) => return resp.into_response(),
     };
@@ -2601,7 +2886,7 @@ async fn delete_secret(
     headers: HeaderMap,
     Path(name): Path<String>,
 ) -> impl IntoResponse {
-    let tenant_id = match authn(&headers, &state.signer) {
+    let tenant_id = match authn(&headers, &state, TenantScope::SecretsWrite).await {
         Ok(t) => t,
         Err(resp) => return resp.into_response(),
     };
@@ -2661,6 +2946,36 @@ async fn delete_secret(
 #[serde(rename_all = "camelCase")]
 struct ApiKeysResponse {
     api_keys: Vec<ApiKeyMetadata>,
+    /// Active keys that expire within `API_KEY_EXPIRY_WARNING_WINDOW_SECS`.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    expiry_warnings: Vec<ApiKeyExpiryWarning>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ApiKeyExpiryWarning {
+    api_key_id: String,
+    name: String,
+    expires_at_unix: i64,
+    expires_in_secs: i64,
+}
+
+fn api_key_expiry_warnings(keys: &[ApiKeyMetadata], now_unix: i64) -> Vec<ApiKeyExpiryWarning> {
+    keys.iter()
+        .filter(|k| k.revoked_at_unix.is_none())
+        .filter_map(|k| {
+            let expires_at_unix = k.expires_at_unix?;
+            let expires_in_secs = expires_at_unix - now_unix;
+            (expires_in_secs > 0 && expires_in_secs <= API_KEY_EXPIRY_WARNING_WINDOW_SECS).then(
+                || ApiKeyExpiryWarning {
+                    api_key_id: k.id.clone(),
+                    name: k.name.clone(),
+                    expires_at_unix,
+                    expires_in_secs,
+                },
+            )
+        })
+        .collect()
 }
 
 #[derive(Debug, Deserialize)]
@@ -2672,6 +2987,18 @@ struct CreateApiKeyRequest {
     /// If set, key is scoped to the specific profile. If omitted, key is tenant-wide.
     #[serde(default)]
     profile_id: Option<String>,
+    /// Optional hard expiry (unix seconds). Must be in the future.
+    #[serde(default)]
+    expires_at_unix: Option<i64>,
+    /// Optional per-key tool allowlist (`<source_id>:<original_tool_name>`).
+    ///
+    /// Narrows the profile's enabled tools; for profile-scoped keys it must be a subset of the
+    /// profile allowlist (when one is configured).
+    #[serde(default)]
+    tools: Vec<String>,
+    /// Optional client IP allowlist (CIDRs or bare IPs).
+    #[serde(default)]
+    allowed_cidrs: Vec<String>,
 }
 
 #[derive(Debug, Serialize)]
@@ -2683,13 +3010,50 @@ struct CreateApiKeyResponse {
     secret: String,
     prefix: String,
     profile_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    expires_at_unix: Option<i64>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    tools: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    allowed_cidrs: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RotateApiKeyRequest {
+    /// How long the old key stays valid (default: 24h, max: 30 days). `0` expires it immediately.
+    #[serde(default)]
+    grace_period_secs: Option<u64>,
+    /// Optional hard expiry for the successor key (unix seconds). Must be in the future.
+    #[serde(default)]
+    expires_at_unix: Option<i64>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct RotateApiKeyResponse {
+    ok: bool,
+    id: String,
+    /// Returned only once. We do NOT store or return it again.
+    secret: String,
+    prefix: String,
+    profile_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    expires_at_unix: Option<i64>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    tools: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    allowed_cidrs: Vec<String>,
+    rotated_from_id: String,
+    /// When the rotated key stops authenticating.
+    previous_expires_at_unix: i64,
 }
 
 async fn list_api_keys(
     axum::Extension(state): axum::Extension<Arc<TenantState>>,
     headers: HeaderMap,
 ) -> impl IntoResponse {
-    let tenant_id = match authn(&headers, &state.signer) {
+    let tenant_id = match authn(&headers, &state, TenantScope::ApiKeysRead).await {
         Ok(t) => t,
         Err(resp) => return resp.into_response(),
     };
@@ -2697,8 +3061,19 @@ async fn list_api_keys(
         return (StatusCode::SERVICE_UNAVAILABLE, "Tenant store unavailable").into_response();
     };
 
+    let now_unix = match now_unix_secs() {
+        Ok(v) => i64::try_from(v).unwrap_or(i64::MAX),
+        Err(e) => return (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+    };
     match store.list_api_keys(&tenant_id).await {
-        Ok(api_keys) => Json(ApiKeysResponse { api_keys }).into_response(),
+        Ok(api_keys) => {
+            let expiry_warnings = api_key_expiry_warnings(&api_keys, now_unix);
+            Json(ApiKeysResponse {
+                api_keys,
+                expiry_warnings,
+            })
+            .into_response()
+        }
         Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
     }
 }
@@ -2708,7 +3083,7 @@ async fn create_api_key(
     headers: HeaderMap,
     Json(req): Json<CreateApiKeyRequest>,
 ) -> impl IntoResponse {
-    let tenant_id = match authn(&headers, &state.signer) {
+    let tenant_id = match authn(&headers, &state, TenantScope::ApiKeysWrite).await {
         Ok(t) => t,
         Err(resp) => return resp.into_response(),
     };
@@ -2738,6 +3113,8 @@ async fn create_api_key(
                 "name": outcome.name_for_meta,
                 "prefix": outcome.prefix_for_meta,
                 "profile_id": outcome.profile_id_for_meta,
+                "expires_at_unix": outcome.expires_at_unix_for_meta,
+                "tools": outcome.tools_for_meta,
             }),
             error: outcome.error,
         }))
@@ -2756,6 +3133,8 @@ struct TenantCreateApiKeyOutcome {
     name_for_meta: Option<String>,
     prefix_for_meta: Option<String>,
     profile_id_for_meta: Option<String>,
+    expires_at_unix_for_meta: Option<i64>,
+    tools_for_meta: Vec<String>,
 }
 
 impl TenantCreateApiKeyOutcome {
@@ -2777,10 +3156,38 @@ impl TenantCreateApiKeyOutcome {
             name_for_meta: None,
             prefix_for_meta: None,
             profile_id_for_meta,
+            expires_at_unix_for_meta: None,
+            tools_for_meta: Vec::new(),
         }
     }
 }
 
+/// Validate an optional API key expiry (must be in the future).
+fn validate_api_key_expiry(expires_at_unix: Option<i64>) -> Result<(), String> {
+    let Some(expires_at_unix) = expires_at_unix else {
+        return Ok(());
+    };
+    let now = now_unix_secs().map_err(|e| e.to_string())?;
+    if expires_at_unix <= i64::try_from(now).unwrap_or(i64::MAX) {
+        return Err("expiresAtUnix must be in the future".to_string());
+    }
+    Ok(())
+}
+
+/// Validate a per-key tool allowlist against the key's profile (if any).
+fn validate_api_key_tools(tools: &[String], profile: Option<&AdminProfile>) -> Result<(), String> {
+    validate_tool_allowlist(tools)?;
+    if let Some(profile) = profile
+        && !profile.enabled_tools.is_empty()
+        && let Some(t) = tools.iter().find(|t| !profile.enabled_tools.contains(t))
+    {
+        return Err(format!(
+            "tools entry '{t}' is not enabled on profile (per-key tools can only narrow the profile allowlist)"
+        ));
+    }
+    Ok(())
+}
+
 async fn tenant_create_api_key_inner(
     store: &dyn crate::store::AdminStore,
     tenant_id: &str,
@@ -2803,11 +3210,43 @@ async fn tenant_create_api_key_inner(
         );
     }
 
-    if let Some(profile_id) = req.profile_id.as_deref()
-        && let Err(outcome) =
-            tenant_validate_profile_for_api_key(store, tenant_id, profile_id, profile_uuid).await
+    let tools: Vec<String> = req.tools.iter().map(|t| t.trim().to_string()).collect();
+    let allowed_cidrs: Vec<String> = req
+        .allowed_cidrs
+        .iter()
+        .map(|c| c.trim().to_string())
+        .collect();
+    if let Err(msg) = validate_api_key_expiry(req.expires_at_unix)
+        .and_then(|()| crate::ip_allowlist::validate_allowlist(&allowed_cidrs))
     {
-        return outcome;
+        return TenantCreateApiKeyOutcome::fail(
+            StatusCode::BAD_REQUEST,
+            msg.clone(),
+            AuditError::new("bad_request", msg),
+            profile_id_for_meta,
+            profile_uuid,
+        );
+    }
+
+    let profile = match req.profile_id.as_deref() {
+        Some(profile_id) => {
+            match tenant_validate_profile_for_api_key(store, tenant_id, profile_id, profile_uuid)
+                .await
+            {
+                Ok(p) => Some(p),
+                Err(outcome) => return outcome,
+            }
+        }
+        None => None,
+    };
+    if let Err(msg) = validate_api_key_tools(&tools, profile.as_ref()) {
+        return TenantCreateApiKeyOutcome::fail(
+            StatusCode::BAD_REQUEST,
+            msg.clone(),
+            AuditError::new("bad_request", msg),
+            profile_id_for_meta,
+            profile_uuid,
+        );
     }
 
     let api_key_id = Uuid::new_v4().to_string();
@@ -2817,14 +3256,17 @@ async fn tenant_create_api_key_inner(
     let secret_hash = hex::encode(sha2::Sha256::digest(secret.as_bytes()));
 
     if let Err(e) = store
-        .put_api_key(
+        .put_api_key(PutApiKeyInput {
             tenant_id,
-            &api_key_id,
-            req.profile_id.as_deref(),
-            &name,
-            &prefix,
-            &secret_hash,
-        )
+            api_key_id: &api_key_id,
+            profile_id: req.profile_id.as_deref(),
+            name: &name,
+            prefix: &prefix,
+            secret_hash: &secret_hash,
+            expires_at_unix: req.expires_at_unix,
+            tool_allowlist: &tools,
+            allowed_cidrs: &allowed_cidrs,
+        })
         .await
     {
         let msg = e.to_string();
@@ -2838,6 +3280,8 @@ async fn tenant_create_api_key_inner(
             name_for_meta: Some(name),
             prefix_for_meta: Some(prefix),
             profile_id_for_meta,
+            expires_at_unix_for_meta: req.expires_at_unix,
+            tools_for_meta: tools,
         };
     }
 
@@ -2848,6 +3292,9 @@ async fn tenant_create_api_key_inner(
             secret,
             prefix: prefix.clone(),
             profile_id: req.profile_id,
+            expires_at_unix: req.expires_at_unix,
+            tools: tools.clone(),
+            allowed_cidrs,
         })
         .into_response(),
         status: StatusCode::OK,
@@ -2858,6 +3305,8 @@ async fn tenant_create_api_key_inner(
         name_for_meta: Some(name),
         prefix_for_meta: Some(prefix),
         profile_id_for_meta,
+        expires_at_unix_for_meta: req.expires_at_unix,
+        tools_for_meta: tools,
     }
 }
 
@@ -2866,7 +3315,7 @@ async fn tenant_validate_profile_for_api_key(
     tenant_id: &str,
     profile_id: &str,
     profile_uuid: Option<Uuid>,
-) -> Result<(), TenantCreateApiKeyOutcome> {
+) -> Result<AdminProfile, TenantCreateApiKeyOutcome> {
     // UUIDv4 only, otherwise 404 (avoid enumeration patterns).
     if Uuid::parse_str(profile_id)
         .ok()
@@ -2883,7 +3332,7 @@ async fn tenant_validate_profile_for_api_key(
     }
 
     match store.get_profile(profile_id).await {
-        Ok(Some(p)) if p.tenant_id == tenant_id && p.enabled => Ok(()),
+        Ok(Some(p)) if p.tenant_id == tenant_id && p.enabled => Ok(p),
         Ok(_) => Err(TenantCreateApiKeyOutcome::fail(
             StatusCode::NOT_FOUND,
             "profile not found",
@@ -2909,7 +3358,7 @@ async fn revoke_api_key(
     headers: HeaderMap,
     Path(api_key_id): Path<String>,
 ) -> impl IntoResponse {
-    let tenant_id = match authn(&headers, &state.signer) {
+    let tenant_id = match authn(&headers, &state, TenantScope::ApiKeysWrite).await {
         Ok(t) => t,
         Err(resp) => return resp.into_response(),
     };
@@ -2969,30 +3418,171 @@ async fn revoke_api_key(
     resp
 }
 
-fn validate_audit_default_level(level: &str) -> Result<(), &'static str> {
-    match level {
-        "off" | "summary" | "metadata" | "payload" => Ok(()),
-        _ => Err("invalid defaultLevel (allowed: off|summary|metadata|payload)"),
-    }
-}
-
-async fn get_audit_settings(
+async fn rotate_api_key(
     axum::Extension(state): axum::Extension<Arc<TenantState>>,
     headers: HeaderMap,
+    Path(api_key_id): Path<String>,
+    Json(req): Json<RotateApiKeyRequest>,
 ) -> impl IntoResponse {
-    let tenant_id = match authn(&headers, &state.signer) {
+    let tenant_id = match authn(&headers, &state, TenantScope::ApiKeysWrite).await {
         Ok(t) => t,
         Err(resp) => return resp.into_response(),
     };
     let Some(store) = &state.store else {
         return (StatusCode::SERVICE_UNAVAILABLE, "Tenant store unavailable").into_response();
     };
+    let started = Instant::now();
 
-    // Ensure tenant exists + enabled.
-    match store.get_tenant(&tenant_id).await {
-        Ok(Some(t)) if t.enabled => {}
-        Ok(_) => return (StatusCode::UNAUTHORIZED, "invalid tenant").into_response(),
-        Err(e) => return (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+    let api_key_uuid = Uuid::parse_str(&api_key_id).ok();
+    let (status, error, successor_id, resp) =
+        tenant_rotate_api_key_inner(store.as_ref(), &tenant_id, &api_key_id, &req).await;
+
+    state
+        .audit
+        .record(crate::audit::http_event(HttpAuditEvent {
+            tenant_id,
+            actor: AuditActor {
+                api_key_id: api_key_uuid,
+                ..AuditActor::default()
+            },
+            action: "tenant.api_key_rotate",
+            http_method: "POST",
+            http_route: "/tenant/v1/api-keys/{api_key_id}/rotate",
+            status_code: i32::from(status.as_u16()),
+            ok: status.is_success(),
+            elapsed: started.elapsed(),
+            meta: serde_json::json!({
+                "api_key_id": api_key_id,
+                "successor_id": successor_id,
+                "grace_period_secs": req.grace_period_secs,
+                "expires_at_unix": req.expires_at_unix,
+            }),
+            error,
+        }))
+        .await;
+
+    resp
+}
+
+async fn tenant_rotate_api_key_inner(
+    store: &dyn crate::store::AdminStore,
+    tenant_id: &str,
+    api_key_id: &str,
+    req: &RotateApiKeyRequest,
+) -> (StatusCode, Option<AuditError>, Option<String>, Response) {
+    fn fail(
+        status: StatusCode,
+        kind: &'static str,
+        msg: String,
+    ) -> (StatusCode, Option<AuditError>, Option<String>, Response) {
+        (
+            status,
+            Some(AuditError::new(kind, msg.clone())),
+            None,
+            (status, msg).into_response(),
+        )
+    }
+
+    if Uuid::parse_str(api_key_id).is_err() {
+        return fail(
+            StatusCode::NOT_FOUND,
+            "not_found",
+            "api key not found".to_string(),
+        );
+    }
+    let grace_period_secs = req
+        .grace_period_secs
+        .unwrap_or(DEFAULT_API_KEY_ROTATION_GRACE_SECS);
+    if grace_period_secs > MAX_API_KEY_ROTATION_GRACE_SECS {
+        return fail(
+            StatusCode::BAD_REQUEST,
+            "bad_request",
+            format!("gracePeriodSecs must be <= {MAX_API_KEY_ROTATION_GRACE_SECS}"),
+        );
+    }
+    if let Err(msg) = validate_api_key_expiry(req.expires_at_unix) {
+        return fail(StatusCode::BAD_REQUEST, "bad_request", msg);
+    }
+
+    let successor_id = Uuid::new_v4().to_string();
+    let secret = generate_api_key_secret();
+    let prefix = api_key_prefix(&secret);
+    let secret_hash = hex::encode(sha2::Sha256::digest(secret.as_bytes()));
+
+    let rotation = match store
+        .rotate_api_key(RotateApiKeyInput {
+            tenant_id,
+            api_key_id,
+            successor_id: &successor_id,
+            successor_prefix: &prefix,
+            successor_secret_hash: &secret_hash,
+            successor_expires_at_unix: req.expires_at_unix,
+            grace_period_secs: i64::try_from(grace_period_secs).unwrap_or(i64::MAX),
+        })
+        .await
+    {
+        Ok(Some(r)) => r,
+        Ok(None) => {
+            return fail(
+                StatusCode::NOT_FOUND,
+                "not_found",
+                "api key not found (or no longer active)".to_string(),
+            );
+        }
+        Err(e) => {
+            return fail(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "internal_error",
+                e.to_string(),
+            );
+        }
+    };
+
+    let successor = rotation.successor;
+    (
+        StatusCode::OK,
+        None,
+        Some(successor.id.clone()),
+        Json(RotateApiKeyResponse {
+            ok: true,
+            id: successor.id,
+            secret,
+            prefix: successor.prefix,
+            profile_id: successor.profile_id,
+            expires_at_unix: successor.expires_at_unix,
+            tools: successor.tool_allowlist,
+            allowed_cidrs: successor.allowed_cidrs,
+            rotated_from_id: api_key_id.to_string(),
+            previous_expires_at_unix: rotation.previous_expires_at_unix,
+        })
+        .into_response(),
+    )
+}
+
+fn validate_audit_default_level(level: &str) -> Result<(), &'static str> {
+    match level {
+        "off" | "summary" | "metadata" | "payload" => Ok(()),
+        _ => Err("invalid defaultLevel (allowed: off|summary|metadata|payload)"),
+    }
+}
+
+async fn get_audit_settings(
+    axum::Extension(state): axum::Extension<Arc<TenantState>>,
+    headers: HeaderMap,
+) -> impl IntoResponse {
+    let tenant_id = match authn(&headers, &state, TenantScope::AuditRead).await {
+        Ok(t) => t,
+        Err(resp) => return resp.into_response(),
+    };
+    let Some(store) = &state.store else {
+        return (StatusCode::SERVICE_UNAVAILABLE, "Tenant store unavailable").into_response();
+    };
+
+    // Ensure tenant exists + enabled.
+    match store.get_tenant(&tenant_id).await {
+        Ok(Some(t)) if t.enabled => {}
+        Ok(_) => return (StatusCode::UNAUTHORIZED, "invalid tenant").into_response(),
+        Err(e) => return (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
     }
 
     match store.get_tenant_audit_settings(&tenant_id).await {
@@ -3012,7 +3602,7 @@ async fn put_audit_settings(
     headers: HeaderMap,
     Json(req): Json<PutTenantAuditSettingsRequest>,
 ) -> impl IntoResponse {
-    let tenant_id = match authn(&headers, &state.signer) {
+    let tenant_id = match authn(&headers, &state, TenantScope::AuditWrite).await {
         Ok(t) => t,
         Err(resp) => return resp.into_response(),
     };
@@ -3057,63 +3647,1077 @@ async fn put_audit_settings(
     }
 }
 
-async fn get_transport_limits(
+async fn get_transport_limits(
+    axum::Extension(state): axum::Extension<Arc<TenantState>>,
+    headers: HeaderMap,
+) -> impl IntoResponse {
+    let tenant_id = match authn(&headers, &state, TenantScope::TransportRead).await {
+        Ok(t) => t,
+        Err(resp) => return resp.into_response(),
+    };
+    let Some(store) = &state.store else {
+        return (StatusCode::SERVICE_UNAVAILABLE, "Tenant store unavailable").into_response();
+    };
+
+    // Ensure tenant exists + enabled.
+    match store.get_tenant(&tenant_id).await {
+        Ok(Some(t)) if t.enabled => {}
+        Ok(_) => return (StatusCode::UNAUTHORIZED, "invalid tenant").into_response(),
+        Err(e) => return (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+    }
+
+    match store.get_tenant_transport_limits(&tenant_id).await {
+        Ok(Some(limits)) => Json(limits).into_response(),
+        Ok(None) => (StatusCode::NOT_FOUND, "tenant not found").into_response(),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+    }
+}
+
+async fn put_transport_limits(
+    axum::Extension(state): axum::Extension<Arc<TenantState>>,
+    headers: HeaderMap,
+    Json(req): Json<TransportLimitsSettings>,
+) -> impl IntoResponse {
+    let tenant_id = match authn(&headers, &state, TenantScope::TransportWrite).await {
+        Ok(t) => t,
+        Err(resp) => return resp.into_response(),
+    };
+    let Some(store) = &state.store else {
+        return (StatusCode::SERVICE_UNAVAILABLE, "Tenant store unavailable").into_response();
+    };
+
+    // Ensure tenant exists + enabled.
+    match store.get_tenant(&tenant_id).await {
+        Ok(Some(t)) if t.enabled => {}
+        Ok(_) => return (StatusCode::UNAUTHORIZED, "invalid tenant").into_response(),
+        Err(e) => return (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+    }
+
+    if let Err(msg) = crate::transport_limits::validate_transport_limits_settings(&req) {
+        return (StatusCode::BAD_REQUEST, msg).into_response();
+    }
+
+    match store.put_tenant_transport_limits(&tenant_id, &req).await {
+        Ok(()) => Json(OkResponse { ok: true }).into_response(),
+        Err(e) => {
+            let msg = e.to_string();
+            (StatusCode::INTERNAL_SERVER_ERROR, msg).into_response()
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SessionsQuery {
+    profile_id: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SessionsResponse {
+    sessions: Vec<crate::session_registry::SessionInfo>,
+}
+
+/// Live session owned by the tenant (`None` for unknown sessions and other tenants' sessions).
+async fn tenant_session(
+    state: &TenantState,
+    tenant_id: &str,
+    session_id: &str,
+) -> anyhow::Result<Option<crate::session_registry::SessionRecord>> {
+    Ok(state
+        .mcp_state
+        .sessions
+        .get(session_id)
+        .await?
+        .filter(|r| r.tenant_id == tenant_id))
+}
+
+async fn list_sessions(
+    axum::Extension(state): axum::Extension<Arc<TenantState>>,
+    headers: HeaderMap,
+    axum::extract::Query(q): axum::extract::Query<SessionsQuery>,
+) -> impl IntoResponse {
+    let tenant_id = match authn(&headers, &state, TenantScope::SessionsRead).await {
+        Ok(t) => t,
+        Err(resp) => return resp.into_response(),
+    };
+    let filter = crate::session_registry::SessionFilter {
+        tenant_id: Some(tenant_id),
+        profile_id: q.profile_id,
+    };
+    match state.mcp_state.sessions.list(&filter).await {
+        Ok(sessions) => Json(SessionsResponse {
+            sessions: sessions.iter().map(Into::into).collect(),
+        })
+        .into_response(),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+    }
+}
+
+async fn get_session(
+    axum::Extension(state): axum::Extension<Arc<TenantState>>,
+    headers: HeaderMap,
+    Path(session_id): Path<String>,
+) -> impl IntoResponse {
+    let tenant_id = match authn(&headers, &state, TenantScope::SessionsRead).await {
+        Ok(t) => t,
+        Err(resp) => return resp.into_response(),
+    };
+    match tenant_session(&state, &tenant_id, &session_id).await {
+        Ok(Some(r)) => Json(crate::session_registry::SessionInfo::from(&r)).into_response(),
+        Ok(None) => (StatusCode::NOT_FOUND, "session not found").into_response(),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+    }
+}
+
+async fn terminate_session(
+    axum::Extension(state): axum::Extension<Arc<TenantState>>,
+    headers: HeaderMap,
+    Path(session_id): Path<String>,
+) -> impl IntoResponse {
+    let tenant_id = match authn(&headers, &state, TenantScope::SessionsWrite).await {
+        Ok(t) => t,
+        Err(resp) => return resp.into_response(),
+    };
+    let started = Instant::now();
+
+    let (status, error, resp, profile_id) =
+        match tenant_terminate_session_inner(&state, &tenant_id, &session_id).await {
+            Ok(record) => (
+                StatusCode::OK,
+                None,
+                Json(OkResponse { ok: true }).into_response(),
+                Some(record.profile_id),
+            ),
+            Err((status, msg)) => {
+                let kind = if status == StatusCode::NOT_FOUND {
+                    "not_found"
+                } else {
+                    "internal_error"
+                };
+                (
+                    status,
+                    Some(AuditError::new(kind, msg.clone())),
+                    (status, msg).into_response(),
+                    None,
+                )
+            }
+        };
+
+    state
+        .audit
+        .record(crate::audit::http_event(HttpAuditEvent {
+            tenant_id,
+            actor: AuditActor::default(),
+            action: "tenant.session_terminate",
+            http_method: "DELETE",
+            http_route: "/tenant/v1/sessions/{session_id}",
+            status_code: i32::from(status.as_u16()),
+            ok: status.is_success(),
+            elapsed: started.elapsed(),
+            meta: serde_json::json!({
+                "session_id": session_id,
+                "profile_id": profile_id,
+            }),
+            error,
+        }))
+        .await;
+
+    resp
+}
+
+async fn tenant_terminate_session_inner(
+    state: &TenantState,
+    tenant_id: &str,
+    session_id: &str,
+) -> Result<crate::session_registry::SessionRecord, (StatusCode, String)> {
+    let internal = |e: anyhow::Error| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string());
+    let not_found = || (StatusCode::NOT_FOUND, "session not found".to_string());
+
+    if tenant_session(state, tenant_id, session_id)
+        .await
+        .map_err(internal)?
+        .is_none()
+    {
+        return Err(not_found());
+    }
+    let record = crate::mcp::terminate_session(&state.mcp_state, session_id)
+        .await
+        .map_err(internal)?
+        .ok_or_else(not_found)?;
+    state
+        .invalidation
+        .publish_best_effort(
+            &crate::pg_invalidation::InvalidationEvent::McpSessionTerminated {
+                session_id: session_id.to_string(),
+            },
+        )
+        .await;
+    Ok(record)
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct IpAllowlistSettings {
+    /// Client IP allowlist (CIDRs or bare IPs). Empty => no allowlist configured.
+    #[serde(default)]
+    allowed_cidrs: Vec<String>,
+}
+
+async fn get_ip_allowlist(
+    axum::Extension(state): axum::Extension<Arc<TenantState>>,
+    headers: HeaderMap,
+) -> impl IntoResponse {
+    let tenant_id = match authn(&headers, &state, TenantScope::NetworkRead).await {
+        Ok(t) => t,
+        Err(resp) => return resp.into_response(),
+    };
+    let Some(store) = &state.store else {
+        return (StatusCode::SERVICE_UNAVAILABLE, "Tenant store unavailable").into_response();
+    };
+
+    match store.get_tenant_ip_allowlist(&tenant_id).await {
+        Ok(Some(allowed_cidrs)) => Json(IpAllowlistSettings { allowed_cidrs }).into_response(),
+        Ok(None) => (StatusCode::NOT_FOUND, "tenant not found").into_response(),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+    }
+}
+
+async fn put_ip_allowlist(
+    axum::Extension(state): axum::Extension<Arc<TenantState>>,
+    headers: HeaderMap,
+    Json(req): Json<IpAllowlistSettings>,
+) -> impl IntoResponse {
+    let tenant_id = match authn(&headers, &state, TenantScope::NetworkWrite).await {
+        Ok(t) => t,
+        Err(resp) => return resp.into_response(),
+    };
+    let Some(store) = &state.store else {
+        return (StatusCode::SERVICE_UNAVAILABLE, "Tenant store unavailable").into_response();
+    };
+    let started = Instant::now();
+
+    let allowed_cidrs: Vec<String> = req
+        .allowed_cidrs
+        .iter()
+        .map(|c| c.trim().to_string())
+        .collect();
+    let (status, error, resp) = match crate::ip_allowlist::validate_allowlist(&allowed_cidrs) {
+        Err(msg) => (
+            StatusCode::BAD_REQUEST,
+            Some(AuditError::new("bad_request", msg.clone())),
+            (StatusCode::BAD_REQUEST, msg).into_response(),
+        ),
+        Ok(()) => match store
+            .put_tenant_ip_allowlist(&tenant_id, &allowed_cidrs)
+            .await
+        {
+            Ok(()) => {
+                // Keep the data-plane allowlist cache coherent on this node.
+                state.invalidation.apply_local(
+                    &crate::pg_invalidation::InvalidationEvent::TenantIpAllowlist {
+                        tenant_id: tenant_id.clone(),
+                    },
+                );
+                (
+                    StatusCode::OK,
+                    None,
+                    Json(OkResponse { ok: true }).into_response(),
+                )
+            }
+            Err(e) => {
+                let msg = e.to_string();
+                (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    Some(AuditError::new("internal_error", msg.clone())),
+                    (StatusCode::INTERNAL_SERVER_ERROR, msg).into_response(),
+                )
+            }
+        },
+    };
+
+    state
+        .audit
+        .record(crate::audit::http_event(HttpAuditEvent {
+            tenant_id,
+            actor: AuditActor::default(),
+            action: "tenant.ip_allowlist_put",
+            http_method: "PUT",
+            http_route: "/tenant/v1/network/allowlist",
+            status_code: i32::from(status.as_u16()),
+            ok: status.is_success(),
+            elapsed: started.elapsed(),
+            meta: serde_json::json!({
+                "allowed_cidrs": allowed_cidrs,
+            }),
+            error,
+        }))
+        .await;
+
+    resp
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct OidcIssuersResponse {
+    issuers: Vec<TenantOidcIssuer>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PutOidcIssuerRequest {
+    issuer: String,
+    audiences: Vec<String>,
+    #[serde(default)]
+    jwks_uri: Option<String>,
+    /// Defaults to `["RS256"]`.
+    #[serde(default)]
+    allowed_algs: Option<Vec<String>>,
+    #[serde(default = "default_true")]
+    enabled: bool,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct OidcIssuerQuery {
+    issuer: String,
+}
+
+async fn list_oidc_issuers(
+    axum::Extension(state): axum::Extension<Arc<TenantState>>,
+    headers: HeaderMap,
+) -> impl IntoResponse {
+    let tenant_id = match authn(&headers, &state, TenantScope::OidcRead).await {
+        Ok(t) => t,
+        Err(resp) => return resp.into_response(),
+    };
+    let Some(store) = &state.store else {
+        return (StatusCode::SERVICE_UNAVAILABLE, "Tenant store unavailable").into_response();
+    };
+
+    match store.list_tenant_oidc_issuers(&tenant_id).await {
+        Ok(issuers) => Json(OidcIssuersResponse { issuers }).into_response(),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+    }
+}
+
+async fn put_oidc_issuer(
+    axum::Extension(state): axum::Extension<Arc<TenantState>>,
+    headers: HeaderMap,
+    Json(req): Json<PutOidcIssuerRequest>,
+) -> impl IntoResponse {
+    let tenant_id = match authn(&headers, &state, TenantScope::OidcWrite).await {
+        Ok(t) => t,
+        Err(resp) => return resp.into_response(),
+    };
+    let Some(store) = &state.store else {
+        return (StatusCode::SERVICE_UNAVAILABLE, "Tenant store unavailable").into_response();
+    };
+    let started = Instant::now();
+
+    let issuer = TenantOidcIssuer {
+        issuer: req.issuer.trim().to_string(),
+        audiences: req.audiences.iter().map(|a| a.trim().to_string()).collect(),
+        jwks_uri: req
+            .jwks_uri
+            .map(|u| u.trim().to_string())
+            .filter(|u| !u.is_empty()),
+        allowed_algs: req
+            .allowed_algs
+            .unwrap_or_else(|| vec![crate::tenant_oidc::DEFAULT_ALLOWED_ALG.to_string()]),
+        enabled: req.enabled,
+    };
+    let (status, error, resp) =
+        match tenant_put_oidc_issuer_inner(store.as_ref(), &tenant_id, &issuer).await {
+            Ok(()) => {
+                state
+                    .mcp_state
+                    .tenant_oidc
+                    .forget(&tenant_id, &issuer.issuer);
+                (
+                    StatusCode::OK,
+                    None,
+                    Json(OkResponse { ok: true }).into_response(),
+                )
+            }
+            Err((status, msg)) => {
+                let kind = if status == StatusCode::INTERNAL_SERVER_ERROR {
+                    "internal_error"
+                } else {
+                    "bad_request"
+                };
+                (
+                    status,
+                    Some(AuditError::new(kind, msg.clone())),
+                    (status, msg).into_response(),
+                )
+            }
+        };
+
+    state
+        .audit
+        .record(crate::audit::http_event(HttpAuditEvent {
+            tenant_id,
+            actor: AuditActor::default(),
+            action: "tenant.oidc_issuer_put",
+            http_method: "PUT",
+            http_route: "/tenant/v1/oidc/issuers",
+            status_code: i32::from(status.as_u16()),
+            ok: status.is_success(),
+            elapsed: started.elapsed(),
+            meta: serde_json::json!({
+                "issuer": issuer.issuer,
+                "audiences": issuer.audiences,
+                "jwks_uri": issuer.jwks_uri,
+                "allowed_algs": issuer.allowed_algs,
+                "enabled": issuer.enabled,
+            }),
+            error,
+        }))
+        .await;
+
+    resp
+}
+
+async fn tenant_put_oidc_issuer_inner(
+    store: &dyn AdminStore,
+    tenant_id: &str,
+    issuer: &TenantOidcIssuer,
+) -> Result<(), (StatusCode, String)> {
+    crate::tenant_oidc::validate_issuer(issuer).map_err(|m| (StatusCode::BAD_REQUEST, m))?;
+
+    // Tenant-provided URLs are fetched by the Gateway: apply the outbound SSRF policy up front.
+    let safety = crate::outbound_safety::gateway_outbound_http_safety();
+    for url in std::iter::once(issuer.issuer.as_str()).chain(issuer.jwks_uri.as_deref()) {
+        crate::outbound_safety::check_url_allowed(&safety, url)
+            .await
+            .map_err(|e| (StatusCode::BAD_REQUEST, format!("url not allowed: {e}")))?;
+    }
+
+    let existing = store
+        .list_tenant_oidc_issuers(tenant_id)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    if existing.len() >= crate::tenant_oidc::MAX_ISSUERS_PER_TENANT
+        && !existing.iter().any(|e| e.issuer == issuer.issuer)
+    {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            format!(
+                "too many OIDC issuers (max {})",
+                crate::tenant_oidc::MAX_ISSUERS_PER_TENANT
+            ),
+        ));
+    }
+
+    store
+        .put_tenant_oidc_issuer(tenant_id, issuer)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))
+}
+
+async fn delete_oidc_issuer(
+    axum::Extension(state): axum::Extension<Arc<TenantState>>,
+    headers: HeaderMap,
+    axum::extract::Query(q): axum::extract::Query<OidcIssuerQuery>,
+) -> impl IntoResponse {
+    let tenant_id = match authn(&headers, &state, TenantScope::OidcWrite).await {
+        Ok(t) => t,
+        Err(resp) => return resp.into_response(),
+    };
+    let Some(store) = &state.store else {
+        return (StatusCode::SERVICE_UNAVAILABLE, "Tenant store unavailable").into_response();
+    };
+    let started = Instant::now();
+
+    let issuer = q.issuer.trim().to_string();
+    let (status, error, resp) = match store.delete_tenant_oidc_issuer(&tenant_id, &issuer).await {
+        Ok(true) => {
+            state.mcp_state.tenant_oidc.forget(&tenant_id, &issuer);
+            (
+                StatusCode::OK,
+                None,
+                Json(OkResponse { ok: true }).into_response(),
+            )
+        }
+        Ok(false) => (
+            StatusCode::NOT_FOUND,
+            Some(AuditError::new("not_found", "oidc issuer not found")),
+            (StatusCode::NOT_FOUND, "oidc issuer not found").into_response(),
+        ),
+        Err(e) => {
+            let msg = e.to_string();
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Some(AuditError::new("internal_error", msg.clone())),
+                (StatusCode::INTERNAL_SERVER_ERROR, msg).into_response(),
+            )
+        }
+    };
+
+    state
+        .audit
+        .record(crate::audit::http_event(HttpAuditEvent {
+            tenant_id,
+            actor: AuditActor::default(),
+            action: "tenant.oidc_issuer_delete",
+            http_method: "DELETE",
+            http_route: "/tenant/v1/oidc/issuers",
+            status_code: i32::from(status.as_u16()),
+            ok: status.is_success(),
+            elapsed: started.elapsed(),
+            meta: serde_json::json!({ "issuer": issuer }),
+            error,
+        }))
+        .await;
+
+    resp
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct UpstreamOAuthResponse {
+    client: UpstreamOAuthClient,
+    /// Where the upstream's authorization server must redirect (register this with the client).
+    redirect_uri: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PutUpstreamOAuthRequest {
+    client_id: String,
+    #[serde(default)]
+    client_secret_ref: Option<String>,
+    #[serde(default)]
+    scopes: Vec<String>,
+    #[serde(default)]
+    authorization_endpoint: Option<String>,
+    #[serde(default)]
+    token_endpoint: Option<String>,
+    #[serde(default = "default_true")]
+    enabled: bool,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct UpstreamOAuthLinksResponse {
+    links: Vec<UpstreamOAuthLink>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct StartUpstreamOAuthLinkRequest {
+    #[serde(default)]
+    api_key_id: Option<String>,
+    #[serde(default)]
+    oidc_issuer: Option<String>,
+    #[serde(default)]
+    oidc_subject: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct StartUpstreamOAuthLinkResponse {
+    principal: String,
+    /// Link URL the user opens (the Gateway's start endpoint, which redirects onwards).
+    authorization_url: String,
+    expires_at_unix: i64,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct UpstreamOAuthLinkQuery {
+    principal: String,
+}
+
+/// Authenticate, check the tenant, and resolve the upstream for the upstream OAuth endpoints.
+async fn upstream_oauth_context(
+    state: &TenantState,
+    headers: &HeaderMap,
+    scope: TenantScope,
+    upstream_id: &str,
+) -> Result<(Arc<dyn AdminStore>, String, String), Response> {
+    let tenant_id = authn(headers, state, scope).await?;
+    let Some(store) = &state.store else {
+        return Err((StatusCode::SERVICE_UNAVAILABLE, "Tenant store unavailable").into_response());
+    };
+    match store.get_tenant(&tenant_id).await {
+        Ok(Some(t)) if t.enabled => {}
+        Ok(_) => return Err((StatusCode::UNAUTHORIZED, "invalid tenant").into_response()),
+        Err(e) => {
+            return Err((StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response());
+        }
+    }
+    let resolved = resolve_visible_upstream_id(store.as_ref(), &tenant_id, upstream_id).await?;
+    Ok((store.clone(), tenant_id, resolved))
+}
+
+fn upstream_oauth_redirect_uri(state: &TenantState) -> Option<String> {
+    state
+        .mcp_state
+        .public_base_url
+        .as_deref()
+        .map(|base| format!("{base}{}", crate::upstream_oauth::CALLBACK_PATH))
+}
+
+async fn get_upstream_oauth(
+    axum::Extension(state): axum::Extension<Arc<TenantState>>,
+    headers: HeaderMap,
+    Path(upstream_id): Path<String>,
+) -> impl IntoResponse {
+    let (store, tenant_id, resolved) =
+        match upstream_oauth_context(&state, &headers, TenantScope::UpstreamsRead, &upstream_id)
+            .await
+        {
+            Ok(c) => c,
+            Err(resp) => return resp,
+        };
+    match store.list_upstream_oauth_clients(&tenant_id).await {
+        Ok(clients) => match clients.into_iter().find(|c| c.upstream_id == resolved) {
+            Some(mut client) => {
+                client.upstream_id = upstream_id;
+                Json(UpstreamOAuthResponse {
+                    client,
+                    redirect_uri: upstream_oauth_redirect_uri(&state),
+                })
+                .into_response()
+            }
+            None => (
+                StatusCode::NOT_FOUND,
+                "upstream OAuth client not configured",
+            )
+                .into_response(),
+        },
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+    }
+}
+
+async fn put_upstream_oauth(
+    axum::Extension(state): axum::Extension<Arc<TenantState>>,
+    headers: HeaderMap,
+    Path(upstream_id): Path<String>,
+    Json(req): Json<PutUpstreamOAuthRequest>,
+) -> impl IntoResponse {
+    let (store, tenant_id, resolved) =
+        match upstream_oauth_context(&state, &headers, TenantScope::UpstreamsWrite, &upstream_id)
+            .await
+        {
+            Ok(c) => c,
+            Err(resp) => return resp,
+        };
+    let started = Instant::now();
+
+    let trimmed = |v: Option<String>| v.map(|s| s.trim().to_string()).filter(|s| !s.is_empty());
+    let client = UpstreamOAuthClient {
+        upstream_id: resolved,
+        client_id: req.client_id.trim().to_string(),
+        client_secret_ref: trimmed(req.client_secret_ref),
+        scopes: req
+            .scopes
+            .iter()
+            .map(|s| s.trim().to_string())
+            .filter(|s| !s.is_empty())
+            .collect(),
+        authorization_endpoint: trimmed(req.authorization_endpoint),
+        token_endpoint: trimmed(req.token_endpoint),
+        enabled: req.enabled,
+    };
+    let (status, error, resp) =
+        match tenant_put_upstream_oauth_inner(store.as_ref(), &tenant_id, &client).await {
+            Ok(()) => (
+                StatusCode::OK,
+                None,
+                Json(OkResponse { ok: true }).into_response(),
+            ),
+            Err((status, msg)) => {
+                let kind = if status == StatusCode::INTERNAL_SERVER_ERROR {
+                    "internal_error"
+                } else {
+                    "bad_request"
+                };
+                (
+                    status,
+                    Some(AuditError::new(kind, msg.clone())),
+                    (status, msg).into_response(),
+                )
+            }
+        };
+
+    state
+        .audit
+        .record(crate::audit::http_event(HttpAuditEvent {
+            tenant_id,
+            actor: AuditActor::default(),
+            action: "tenant.upstream_oauth_put",
+            http_method: "PUT",
+            http_route: "/tenant/v1/upstreams/{upstream_id}/oauth",
+            status_code: i32::from(status.as_u16()),
+            ok: status.is_success(),
+            elapsed: started.elapsed(),
+            meta: serde_json::json!({
+                "upstream_id": upstream_id,
+                "client_id": client.client_id,
+                "client_secret_ref": client.client_secret_ref,
+                "scopes": client.scopes,
+                "enabled": client.enabled,
+            }),
+            error,
+        }))
+        .await;
+
+    resp
+}
+
+async fn tenant_put_upstream_oauth_inner(
+    store: &dyn AdminStore,
+    tenant_id: &str,
+    client: &UpstreamOAuthClient,
+) -> Result<(), (StatusCode, String)> {
+    if client.client_id.is_empty() {
+        return Err((StatusCode::BAD_REQUEST, "clientId is required".to_string()));
+    }
+    if let Some(secret_ref) = client.client_secret_ref.as_deref() {
+        let secrets = store
+            .list_secrets(tenant_id)
+            .await
+            .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+        if !secrets.iter().any(|s| s.name == secret_ref) {
+            return Err((
+                StatusCode::BAD_REQUEST,
+                format!("clientSecretRef '{secret_ref}' is not a tenant secret"),
+            ));
+        }
+    }
+
+    // Endpoints are fetched by the Gateway: same HTTPS + SSRF policy as upstream endpoints.
+    let safety = crate::outbound_safety::gateway_outbound_http_safety();
+    for (field, url) in [
+        (
+            "authorizationEndpoint",
+            client.authorization_endpoint.as_deref(),
+        ),
+        ("tokenEndpoint", client.token_endpoint.as_deref()),
+    ] {
+        let Some(url) = url else { continue };
+        crate::outbound_safety::check_upstream_https_policy(url)
+            .map_err(|e| (StatusCode::BAD_REQUEST, format!("{field}: {e}")))?;
+        crate::outbound_safety::check_url_allowed(&safety, url)
+            .await
+            .map_err(|e| (StatusCode::BAD_REQUEST, format!("{field} not allowed: {e}")))?;
+    }
+
+    store
+        .put_upstream_oauth_client(tenant_id, client)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))
+}
+
+async fn delete_upstream_oauth(
+    axum::Extension(state): axum::Extension<Arc<TenantState>>,
+    headers: HeaderMap,
+    Path(upstream_id): Path<String>,
+) -> impl IntoResponse {
+    let (store, tenant_id, resolved) =
+        match upstream_oauth_context(&state, &headers, TenantScope::UpstreamsWrite, &upstream_id)
+            .await
+        {
+            Ok(c) => c,
+            Err(resp) => return resp,
+        };
+    let started = Instant::now();
+
+    let (status, error, resp) = match store
+        .delete_upstream_oauth_client(&tenant_id, &resolved)
+        .await
+    {
+        Ok(true) => (
+            StatusCode::OK,
+            None,
+            Json(OkResponse { ok: true }).into_response(),
+        ),
+        Ok(false) => (
+            StatusCode::NOT_FOUND,
+            Some(AuditError::new(
+                "not_found",
+                "upstream OAuth client not configured",
+            )),
+            (
+                StatusCode::NOT_FOUND,
+                "upstream OAuth client not configured",
+            )
+                .into_response(),
+        ),
+        Err(e) => {
+            let msg = e.to_string();
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Some(AuditError::new("internal_error", msg.clone())),
+                (StatusCode::INTERNAL_SERVER_ERROR, msg).into_response(),
+            )
+        }
+    };
+
+    state
+        .audit
+        .record(crate::audit::http_event(HttpAuditEvent {
+            tenant_id,
+            actor: AuditActor::default(),
+            action: "tenant.upstream_oauth_delete",
+            http_method: "DELETE",
+            http_route: "/tenant/v1/upstreams/{upstream_id}/oauth",
+            status_code: i32::from(status.as_u16()),
+            ok: status.is_success(),
+            elapsed: started.elapsed(),
+            meta: serde_json::json!({ "upstream_id": upstream_id }),
+            error,
+        }))
+        .await;
+
+    resp
+}
+
+async fn list_upstream_oauth_links(
     axum::Extension(state): axum::Extension<Arc<TenantState>>,
     headers: HeaderMap,
+    Path(upstream_id): Path<String>,
 ) -> impl IntoResponse {
-    let tenant_id = match authn(&headers, &state.signer) {
-        Ok(t) => t,
-        Err(resp) => return resp.into_response(),
-    };
-    let Some(store) = &state.store else {
-        return (StatusCode::SERVICE_UNAVAILABLE, "Tenant store unavailable").into_response();
-    };
-
-    // Ensure tenant exists + enabled.
-    match store.get_tenant(&tenant_id).await {
-        Ok(Some(t)) if t.enabled => {}
-        Ok(_) => return (StatusCode::UNAUTHORIZED, "invalid tenant").into_response(),
-        Err(e) => return (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
-    }
-
-    match store.get_tenant_transport_limits(&tenant_id).await {
-        Ok(Some(limits)) => Json(limits).into_response(),
-        Ok(None) => (StatusCode::NOT_FOUND, "tenant not found").into_response(),
+    let (store, tenant_id, resolved) =
+        match upstream_oauth_context(&state, &headers, TenantScope::UpstreamsRead, &upstream_id)
+            .await
+        {
+            Ok(c) => c,
+            Err(resp) => return resp,
+        };
+    match store.list_upstream_oauth_links(&tenant_id, &resolved).await {
+        Ok(links) => Json(UpstreamOAuthLinksResponse { links }).into_response(),
         Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
     }
 }
 
-async fn put_transport_limits(
+async fn start_upstream_oauth_link(
     axum::Extension(state): axum::Extension<Arc<TenantState>>,
     headers: HeaderMap,
-    Json(req): Json<TransportLimitsSettings>,
+    Path(upstream_id): Path<String>,
+    Json(req): Json<StartUpstreamOAuthLinkRequest>,
 ) -> impl IntoResponse {
-    let tenant_id = match authn(&headers, &state.signer) {
-        Ok(t) => t,
-        Err(resp) => return resp.into_response(),
+    let (store, tenant_id, resolved) =
+        match upstream_oauth_context(&state, &headers, TenantScope::UpstreamsWrite, &upstream_id)
+            .await
+        {
+            Ok(c) => c,
+            Err(resp) => return resp,
+        };
+    let started = Instant::now();
+
+    let (status, error, resp, principal) = match tenant_start_upstream_oauth_link_inner(
+        &state,
+        store.as_ref(),
+        &tenant_id,
+        &resolved,
+        req,
+    )
+    .await
+    {
+        Ok(out) => {
+            let principal = out.principal.clone();
+            (
+                StatusCode::OK,
+                None,
+                Json(out).into_response(),
+                Some(principal),
+            )
+        }
+        Err((status, msg)) => {
+            let kind = if status == StatusCode::INTERNAL_SERVER_ERROR {
+                "internal_error"
+            } else if status == StatusCode::BAD_GATEWAY {
+                "upstream_error"
+            } else {
+                "bad_request"
+            };
+            (
+                status,
+                Some(AuditError::new(kind, msg.clone())),
+                (status, msg).into_response(),
+                None,
+            )
+        }
     };
-    let Some(store) = &state.store else {
-        return (StatusCode::SERVICE_UNAVAILABLE, "Tenant store unavailable").into_response();
+
+    state
+        .audit
+        .record(crate::audit::http_event(HttpAuditEvent {
+            tenant_id,
+            actor: AuditActor::default(),
+            action: "tenant.upstream_oauth_link_start",
+            http_method: "POST",
+            http_route: "/tenant/v1/upstreams/{upstream_id}/oauth/links",
+            status_code: i32::from(status.as_u16()),
+            ok: status.is_success(),
+            elapsed: started.elapsed(),
+            meta: serde_json::json!({
+                "upstream_id": upstream_id,
+                "principal": principal,
+            }),
+            error,
+        }))
+        .await;
+
+    resp
+}
+
+async fn tenant_start_upstream_oauth_link_inner(
+    state: &TenantState,
+    store: &dyn AdminStore,
+    tenant_id: &str,
+    upstream_id: &str,
+    req: StartUpstreamOAuthLinkRequest,
+) -> Result<StartUpstreamOAuthLinkResponse, (StatusCode, String)> {
+    let Some(redirect_uri) = upstream_oauth_redirect_uri(state) else {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            format!(
+                "account linking requires {} to be set on the Gateway",
+                crate::protected_resource::PUBLIC_BASE_URL_ENV
+            ),
+        ));
     };
 
-    // Ensure tenant exists + enabled.
-    match store.get_tenant(&tenant_id).await {
-        Ok(Some(t)) if t.enabled => {}
-        Ok(_) => return (StatusCode::UNAUTHORIZED, "invalid tenant").into_response(),
-        Err(e) => return (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
-    }
+    let principal = match (req.api_key_id, req.oidc_issuer, req.oidc_subject) {
+        (Some(api_key_id), None, None) => {
+            let keys = store
+                .list_api_keys(tenant_id)
+                .await
+                .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+            if !keys
+                .iter()
+                .any(|k| k.id == api_key_id && k.revoked_at_unix.is_none())
+            {
+                return Err((StatusCode::BAD_REQUEST, "api key not found".to_string()));
+            }
+            crate::upstream_oauth::api_key_principal(&api_key_id)
+        }
+        (None, Some(issuer), Some(subject)) if !issuer.is_empty() && !subject.is_empty() => {
+            crate::upstream_oauth::oidc_principal(&issuer, &subject)
+        }
+        _ => {
+            return Err((
+                StatusCode::BAD_REQUEST,
+                "provide either apiKeyId or oidcIssuer + oidcSubject".to_string(),
+            ));
+        }
+    };
 
-    if let Err(msg) = crate::transport_limits::validate_transport_limits_settings(&req) {
-        return (StatusCode::BAD_REQUEST, msg).into_response();
-    }
+    let data_store = state.mcp_state.store.as_ref();
+    let client = data_store
+        .get_upstream_oauth_client(tenant_id, upstream_id)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+        .filter(|c| c.enabled)
+        .ok_or_else(|| {
+            (
+                StatusCode::BAD_REQUEST,
+                "upstream OAuth client is not configured or disabled".to_string(),
+            )
+        })?;
+    let resource = data_store
+        .get_upstream(upstream_id)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+        .as_ref()
+        .and_then(crate::upstream_oauth::upstream_resource)
+        .ok_or_else(|| {
+            (
+                StatusCode::BAD_REQUEST,
+                "upstream has no endpoints".to_string(),
+            )
+        })?;
 
-    match store.put_tenant_transport_limits(&tenant_id, &req).await {
-        Ok(()) => Json(OkResponse { ok: true }).into_response(),
+    let (authorization_url, expires_at_unix) = state
+        .mcp_state
+        .upstream_oauth
+        .begin_link(
+            data_store,
+            tenant_id,
+            &client,
+            &resource,
+            &principal,
+            &redirect_uri,
+        )
+        .await
+        .map_err(|e| (StatusCode::BAD_GATEWAY, e.to_string()))?;
+    Ok(StartUpstreamOAuthLinkResponse {
+        principal,
+        authorization_url,
+        expires_at_unix,
+    })
+}
+
+async fn delete_upstream_oauth_link(
+    axum::Extension(state): axum::Extension<Arc<TenantState>>,
+    headers: HeaderMap,
+    Path(upstream_id): Path<String>,
+    axum::extract::Query(q): axum::extract::Query<UpstreamOAuthLinkQuery>,
+) -> impl IntoResponse {
+    let (store, tenant_id, resolved) =
+        match upstream_oauth_context(&state, &headers, TenantScope::UpstreamsWrite, &upstream_id)
+            .await
+        {
+            Ok(c) => c,
+            Err(resp) => return resp,
+        };
+    let started = Instant::now();
+
+    let (status, error, resp) = match store
+        .delete_upstream_oauth_link(&tenant_id, &resolved, &q.principal)
+        .await
+    {
+        Ok(true) => {
+            state
+                .mcp_state
+                .upstream_oauth
+                .forget(&tenant_id, &resolved, &q.principal);
+            (
+                StatusCode::OK,
+                None,
+                Json(OkResponse { ok: true }).into_response(),
+            )
+        }
+        Ok(false) => (
+            StatusCode::NOT_FOUND,
+            Some(AuditError::new("not_found", "link not found")),
+            (StatusCode::NOT_FOUND, "link not found").into_response(),
+        ),
         Err(e) => {
             let msg = e.to_string();
-            (StatusCode::INTERNAL_SERVER_ERROR, msg).into_response()
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Some(AuditError::new("internal_error", msg.clone())),
+                (StatusCode::INTERNAL_SERVER_ERROR, msg).into_response(),
+            )
         }
-    }
+    };
+
+    state
+        .audit
+        .record(crate::audit::http_event(HttpAuditEvent {
+            tenant_id,
+            actor: AuditActor::default(),
+            action: "tenant.upstream_oauth_unlink",
+            http_method: "DELETE",
+            http_route: "/tenant/v1/upstreams/{upstream_id}/oauth/links",
+            status_code: i32::from(status.as_u16()),
+            ok: status.is_success(),
+            elapsed: started.elapsed(),
+            meta: serde_json::json!({
+                "upstream_id": upstream_id,
+                "principal": q.principal,
+            }),
+            error,
+        }))
+        .await;
+
+    resp
 }
 
 async fn list_audit_events(
@@ -3121,7 +4725,7 @@ async fn list_audit_events(
     headers: HeaderMap,
     axum::extract::Query(q): axum::extract::Query<AuditEventsQuery>,
 ) -> impl IntoResponse {
-    let tenant_id = match authn(&headers, &state.signer) {
+    let tenant_id = match authn(&headers, &state, TenantScope::AuditRead).await {
         Ok(t) => t,
         Err(resp) => return resp.into_response(),
     };
@@ -3159,7 +4763,7 @@ async fn tool_call_stats_by_tool(
     headers: HeaderMap,
     axum::extract::Query(q): axum::extract::Query<AuditStatsQuery>,
 ) -> impl IntoResponse {
-    let tenant_id = match authn(&headers, &state.signer) {
+    let tenant_id = match authn(&headers, &state, TenantScope::AuditRead).await {
         Ok(t) => t,
         Err(resp) => return resp.into_response(),
     };
@@ -3193,7 +4797,7 @@ async fn tool_call_stats_by_api_key(
     headers: HeaderMap,
     axum::extract::Query(q): axum::extract::Query<AuditStatsQuery>,
 ) -> impl IntoResponse {
-    let tenant_id = match authn(&headers, &state.signer) {
+    let tenant_id = match authn(&headers, &state, TenantScope::AuditRead).await {
         Ok(t) => t,
         Err(resp) => return resp.into_response(),
     };
@@ -3227,7 +4831,7 @@ async fn get_profile_audit_settings(
     headers: HeaderMap,
     Path(profile_id): Path<String>,
 ) -> impl IntoResponse {
-    let tenant_id = match authn(&headers, &state.signer) {
+    let tenant_id = match authn(&headers, &state, TenantScope::AuditRead).await {
         Ok(t) => t,
         Err(resp) => return resp.into_response(),
     };
@@ -3264,7 +4868,7 @@ async fn put_profile_audit_settings(
     Path(profile_id): Path<String>,
     Json(req): Json<PutProfileAuditSettingsRequest>,
 ) -> impl IntoResponse {
-    let tenant_id = match authn(&headers, &state.signer) {
+    let tenant_id = match authn(&headers, &state, TenantScope::AuditWrite).await {
         Ok(t) => t,
         Err(resp) => return resp.into_response(),
     };
@@ -3340,6 +4944,208 @@ async fn put_profile_audit_settings(
     resp
 }
 
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct IssueScopedTokenRequest {
+    /// TTL in seconds. Defaults to (and is capped by) the remaining lifetime of the caller's token.
+    #[serde(default)]
+    ttl_seconds: Option<u64>,
+    #[serde(default)]
+    role: Option<TenantRole>,
+    #[serde(default)]
+    scopes: Option<Vec<TenantScope>>,
+}
+
+/// Issue a (down-)scoped tenant token for the caller's tenant.
+///
+/// The new token can never grant more than the caller's token: requested scopes must be a subset
+/// of the caller's scopes, and expiry is capped at the caller's expiry.
+async fn issue_scoped_token(
+    axum::Extension(state): axum::Extension<Arc<TenantState>>,
+    headers: HeaderMap,
+    Json(req): Json<IssueScopedTokenRequest>,
+) -> impl IntoResponse {
+    let caller = match verify_tenant_token(&headers, &state).await {
+        Ok(p) => p,
+        Err(resp) => return resp,
+    };
+    if !caller.allows(TenantScope::TokensWrite) {
+        return (
+            StatusCode::FORBIDDEN,
+            format!(
+                "tenant token is missing scope '{}'",
+                TenantScope::TokensWrite.as_str()
+            ),
+        )
+            .into_response();
+    }
+    let started = Instant::now();
+
+    let now = match now_unix_secs() {
+        Ok(n) => n,
+        Err(e) => return (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+    };
+    let ttl = req
+        .ttl_seconds
+        .unwrap_or_else(|| caller.exp_unix_secs.saturating_sub(now));
+    let exp = now
+        .saturating_add(ttl)
+        .max(now + 1)
+        .min(caller.exp_unix_secs);
+
+    let outcome = tenant_issue_scoped_token_inner(&state.signer, &caller, req, exp);
+    let (status, error, resp, token_id, scopes) = match outcome {
+        Ok(issued) => (
+            StatusCode::OK,
+            None,
+            Json(&issued).into_response(),
+            issued.token_id.clone(),
+            issued.scopes.clone(),
+        ),
+        Err((status, error)) => (
+            status,
+            Some(error.clone()),
+            (status, error.message).into_response(),
+            None,
+            None,
+        ),
+    };
+
+    state
+        .audit
+        .record(crate::audit::http_event(HttpAuditEvent {
+            tenant_id: caller.tenant_id.clone(),
+            actor: AuditActor::default(),
+            action: "tenant.token_issue",
+            http_method: "POST",
+            http_route: "/tenant/v1/tokens",
+            status_code: i32::from(status.as_u16()),
+            ok: status.is_success(),
+            elapsed: started.elapsed(),
+            meta: serde_json::json!({
+                "token_id": token_id,
+                "issuer_token_id": caller.token_id,
+                "scopes": scopes,
+                "exp_unix_secs": exp,
+            }),
+            error,
+        }))
+        .await;
+
+    resp
+}
+
+fn tenant_issue_scoped_token_inner(
+    signer: &TenantSigner,
+    caller: &TenantTokenPayloadV1,
+    req: IssueScopedTokenRequest,
+    exp_unix_secs: u64,
+) -> Result<IssueTenantTokenResponse, (StatusCode, AuditError)> {
+    let scopes = resolve_issue_scopes(req.role, req.scopes).map_err(|e| {
+        (
+            StatusCode::BAD_REQUEST,
+            AuditError::new("bad_request", e.to_string()),
+        )
+    })?;
+
+    let exceeds_caller = match (&caller.scopes, &scopes) {
+        (None, _) => false,
+        (Some(_), None) => true,
+        (Some(have), Some(want)) => want.iter().any(|s| !have.contains(s)),
+    };
+    if exceeds_caller {
+        return Err((
+            StatusCode::FORBIDDEN,
+            AuditError::new("forbidden", "requested scopes exceed the caller's scopes"),
+        ));
+    }
+
+    let payload = TenantTokenPayloadV1 {
+        tenant_id: caller.tenant_id.clone(),
+        exp_unix_secs,
+        token_id: Some(Uuid::new_v4().to_string()),
+        scopes,
+    };
+    let token = signer.sign_v1(&payload).map_err(|e| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            AuditError::new("internal_error", e.to_string()),
+        )
+    })?;
+    Ok(IssueTenantTokenResponse {
+        ok: true,
+        tenant_id: payload.tenant_id,
+        token,
+        exp_unix_secs,
+        token_id: payload.token_id,
+        scopes: payload.scopes,
+    })
+}
+
+async fn revoke_tenant_token(
+    axum::Extension(state): axum::Extension<Arc<TenantState>>,
+    headers: HeaderMap,
+    Path(token_id): Path<String>,
+) -> impl IntoResponse {
+    let tenant_id = match authn(&headers, &state, TenantScope::TokensWrite).await {
+        Ok(t) => t,
+        Err(resp) => return resp.into_response(),
+    };
+    let Some(store) = &state.store else {
+        return (StatusCode::SERVICE_UNAVAILABLE, "Tenant store unavailable").into_response();
+    };
+    let started = Instant::now();
+
+    let (status, error, resp) = if token_id.trim().is_empty() {
+        (
+            StatusCode::BAD_REQUEST,
+            Some(AuditError::new("bad_request", "token id is required")),
+            (StatusCode::BAD_REQUEST, "token id is required").into_response(),
+        )
+    } else {
+        match store.revoke_tenant_token(&tenant_id, &token_id).await {
+            // Revoking an already revoked token is a no-op (idempotent).
+            Ok(_) => {
+                // Apply locally right away; other replicas follow via LISTEN/NOTIFY.
+                state.revocations.mark_revoked(&tenant_id, &token_id);
+                (
+                    StatusCode::OK,
+                    None,
+                    Json(OkResponse { ok: true }).into_response(),
+                )
+            }
+            Err(e) => {
+                let msg = e.to_string();
+                (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    Some(AuditError::new("internal_error", msg.clone())),
+                    (StatusCode::INTERNAL_SERVER_ERROR, msg).into_response(),
+                )
+            }
+        }
+    };
+
+    state
+        .audit
+        .record(crate::audit::http_event(HttpAuditEvent {
+            tenant_id,
+            actor: AuditActor::default(),
+            action: "tenant.token_revoke",
+            http_method: "DELETE",
+            http_route: "/tenant/v1/tokens/{token_id}",
+            status_code: i32::from(status.as_u16()),
+            ok: status.is_success(),
+            elapsed: started.elapsed(),
+            meta: serde_json::json!({
+                "token_id": token_id,
+            }),
+            error,
+        }))
+        .await;
+
+    resp
+}
+
 fn generate_api_key_secret() -> String {
     // 32 bytes of randomness using UUIDv4 (backed by `getrandom`).
     let mut bytes = Vec::with_capacity(32);
@@ -3361,6 +5167,12 @@ pub struct IssueTenantTokenRequest {
     /// TTL in seconds. Defaults to 365 days.
     #[serde(default)]
     pub ttl_seconds: Option<u64>,
+    /// Predefined scope set (mutually exclusive with `scopes`). Omit both for full access.
+    #[serde(default)]
+    pub role: Option<TenantRole>,
+    /// Explicit scope list, e.g. `["profiles:read", "audit:read"]`.
+    #[serde(default)]
+    pub scopes: Option<Vec<TenantScope>>,
 }
 
 #[derive(Debug, Serialize)]
@@ -3370,6 +5182,12 @@ pub struct IssueTenantTokenResponse {
     pub tenant_id: String,
     pub token: String,
     pub exp_unix_secs: u64,
+    /// Token id used for revocation.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub token_id: Option<String>,
+    /// Granted scopes (`None` = full access).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub scopes: Option<Vec<TenantScope>>,
 }
 
 pub fn now_unix_secs() -> anyhow::Result<u64> {
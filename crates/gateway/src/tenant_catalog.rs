@@ -1,4 +1,6 @@
+use crate::content_source::ContentSourceConfig;
 use crate::store::{Store, ToolSourceKind, ToolSourceSpec};
+use crate::workflow::WorkflowSourceConfig;
 use anyhow::Context as _;
 use parking_lot::RwLock;
 use rmcp::model::{CallToolResult, Tool};
@@ -8,8 +10,10 @@ use std::collections::HashMap;
 use std::sync::Arc;
 use std::time::Duration;
 use unrelated_http_tools::config::AuthConfig;
+use unrelated_http_tools::context::CallContext;
 use unrelated_http_tools::runtime::HttpToolSource;
 use unrelated_http_tools::safety::OutboundHttpSafety;
+use unrelated_openapi_tools::config::SecurityCredential;
 use unrelated_openapi_tools::runtime::OpenApiToolSource;
 
 #[derive(Clone)]
@@ -20,6 +24,8 @@ pub struct TenantCatalog {
 struct TenantCatalogInner {
     cache: RwLock<HashMap<(String, String), CachedSource>>,
     safety: OutboundHttpSafety,
+    /// Client for `content` source URL resources (redirects disabled; safety checked per URL).
+    content_http: reqwest::Client,
     default_timeout: Duration,
     startup_timeout: Duration,
     openapi_probe_enabled: bool,
@@ -36,6 +42,14 @@ enum CachedSource {
         spec_hash: String,
         source: Box<OpenApiToolSource>,
     },
+    Workflow {
+        spec_hash: String,
+        source: Arc<WorkflowSourceConfig>,
+    },
+    Content {
+        spec_hash: String,
+        source: Arc<ContentSourceConfig>,
+    },
 }
 
 impl TenantCatalog {
@@ -50,6 +64,11 @@ impl TenantCatalog {
             inner: Arc::new(TenantCatalogInner {
                 cache: RwLock::new(HashMap::new()),
                 safety,
+                content_http: reqwest::Client::builder()
+                    .redirect(reqwest::redirect::Policy::none())
+                    .timeout(Duration::from_secs(30))
+                    .build()
+                    .unwrap_or_default(),
                 default_timeout: Duration::from_secs(30),
                 startup_timeout: Duration::from_secs(30),
                 openapi_probe_enabled: true,
@@ -82,6 +101,20 @@ impl TenantCatalog {
         tenant_id: &str,
         source_id: &str,
     ) -> anyhow::Result<Option<Vec<Tool>>> {
+        Ok(
+            Box::pin(self.list_source_tools(store, tenant_id, source_id))
+                .await?
+                .map(|(_, tools)| tools),
+        )
+    }
+
+    /// List tools for a tenant-owned local source, along with the source kind.
+    pub async fn list_source_tools(
+        &self,
+        store: &dyn Store,
+        tenant_id: &str,
+        source_id: &str,
+    ) -> anyhow::Result<Option<(ToolSourceKind, Vec<Tool>)>> {
         let Some(source) = Box::pin(self.ensure_source(store, tenant_id, source_id))
             .await
             .with_context(|| format!("ensure tenant source '{source_id}'"))?
@@ -90,11 +123,33 @@ impl TenantCatalog {
         };
 
         Ok(Some(match source {
-            CachedSource::Http { source, .. } => source.list_tools(),
-            CachedSource::Openapi { source, .. } => source.list_tools(),
+            CachedSource::Http { source, .. } => (ToolSourceKind::Http, source.list_tools()),
+            CachedSource::Openapi { source, .. } => (ToolSourceKind::Openapi, source.list_tools()),
+            CachedSource::Workflow { source, .. } => {
+                (ToolSourceKind::Workflow, source.list_tools())
+            }
+            CachedSource::Content { .. } => (ToolSourceKind::Content, Vec::new()),
         }))
     }
 
+    /// Load the workflow definitions for a tenant-owned `workflow` source.
+    ///
+    /// Returns `None` if the source does not exist, is disabled, or is not a workflow source.
+    pub async fn workflow_source(
+        &self,
+        store: &dyn Store,
+        tenant_id: &str,
+        source_id: &str,
+    ) -> anyhow::Result<Option<Arc<WorkflowSourceConfig>>> {
+        let source = Box::pin(self.ensure_source(store, tenant_id, source_id))
+            .await
+            .with_context(|| format!("ensure tenant source '{source_id}'"))?;
+        Ok(match source {
+            Some(CachedSource::Workflow { source, .. }) => Some(source),
+            _ => None,
+        })
+    }
+
     /// Execute a tool call for a tenant-owned local source.
     pub async fn call_tool(
         &self,
@@ -103,6 +158,7 @@ impl TenantCatalog {
         source_id: &str,
         tool_name: &str,
         arguments: Value,
+        ctx: &CallContext,
     ) -> anyhow::Result<CallToolResult> {
         let Some(source) = Box::pin(self.ensure_source(store, tenant_id, source_id))
             .await
@@ -113,16 +169,57 @@ impl TenantCatalog {
 
         match source {
             CachedSource::Http { source, .. } => Ok(source
-                .call_tool(tool_name, arguments)
+                .call_tool_with_context(tool_name, arguments, ctx)
                 .await
                 .map_err(|e| anyhow::anyhow!(e.to_string()))?),
             CachedSource::Openapi { source, .. } => Ok(source
-                .call_tool(tool_name, arguments)
+                .call_tool_with_context(tool_name, arguments, ctx)
                 .await
                 .map_err(|e| anyhow::anyhow!(e.to_string()))?),
+            CachedSource::Workflow { .. } => {
+                anyhow::bail!("workflow source '{source_id}' is executed by the gateway")
+            }
+            CachedSource::Content { .. } => {
+                anyhow::bail!("content source '{source_id}' has no tools")
+            }
         }
     }
 
+    /// Load the prompts/resources of a tenant-owned `content` source.
+    ///
+    /// Returns `None` if the source does not exist, is disabled, or is not a content source.
+    pub async fn content_source(
+        &self,
+        store: &dyn Store,
+        tenant_id: &str,
+        source_id: &str,
+    ) -> anyhow::Result<Option<Arc<ContentSourceConfig>>> {
+        let source = Box::pin(self.ensure_source(store, tenant_id, source_id))
+            .await
+            .with_context(|| format!("ensure tenant source '{source_id}'"))?;
+        Ok(match source {
+            Some(CachedSource::Content { source, .. }) => Some(source),
+            _ => None,
+        })
+    }
+
+    /// Read a resource from a tenant's `content` source (URL resources use the catalog's outbound
+    /// policy, file resources the tenant's content directory).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the resource is unknown or cannot be loaded.
+    pub async fn read_content_resource(
+        &self,
+        tenant_id: &str,
+        source: &ContentSourceConfig,
+        uri: &str,
+    ) -> Result<rmcp::model::ReadResourceResult, String> {
+        source
+            .read_resource(tenant_id, uri, &self.inner.content_http, &self.inner.safety)
+            .await
+    }
+
     async fn ensure_source(
         &self,
         store: &dyn Store,
@@ -152,7 +249,7 @@ impl TenantCatalog {
 
                 let built = HttpToolSource::new_with_safety(
                     source_id.to_string(),
-                    cfg,
+                    *cfg,
                     self.inner.default_timeout,
                     self.inner.safety.clone(),
                 )
@@ -167,6 +264,9 @@ impl TenantCatalog {
             }
             (ToolSourceKind::Openapi, ToolSourceSpec::Openapi(mut cfg)) => {
                 resolve_auth_secrets(store, tenant_id, cfg.auth.as_mut()).await?;
+                for credential in cfg.security.credentials.values_mut() {
+                    resolve_security_credential_secrets(store, tenant_id, credential).await?;
+                }
                 let spec_hash = hash_json(&cfg)?;
 
                 // Fast path: if cached and hash matches, reuse (avoid rebuilding).
@@ -178,7 +278,7 @@ impl TenantCatalog {
 
                 let built = OpenApiToolSource::build_with_safety(
                     source_id.to_string(),
-                    cfg,
+                    *cfg,
                     self.inner.default_timeout,
                     self.inner.startup_timeout,
                     self.inner.openapi_probe_enabled,
@@ -195,6 +295,42 @@ impl TenantCatalog {
                 self.inner.cache.write().insert(key, stored.clone());
                 Ok(Some(stored))
             }
+            (ToolSourceKind::Workflow, ToolSourceSpec::Workflow(cfg)) => {
+                let spec_hash = hash_json(&cfg)?;
+
+                if let Some(existing) = self.inner.cache.read().get(&key).cloned()
+                    && matches!(&existing, CachedSource::Workflow { spec_hash: h, .. } if h == &spec_hash)
+                {
+                    return Ok(Some(existing));
+                }
+
+                cfg.validate()
+                    .map_err(|e| anyhow::anyhow!("invalid workflow source '{source_id}': {e}"))?;
+                let stored = CachedSource::Workflow {
+                    spec_hash,
+                    source: Arc::new(cfg),
+                };
+                self.inner.cache.write().insert(key, stored.clone());
+                Ok(Some(stored))
+            }
+            (ToolSourceKind::Content, ToolSourceSpec::Content(cfg)) => {
+                let spec_hash = hash_json(&cfg)?;
+
+                if let Some(existing) = self.inner.cache.read().get(&key).cloned()
+                    && matches!(&existing, CachedSource::Content { spec_hash: h, .. } if h == &spec_hash)
+                {
+                    return Ok(Some(existing));
+                }
+
+                cfg.validate()
+                    .map_err(|e| anyhow::anyhow!("invalid content source '{source_id}': {e}"))?;
+                let stored = CachedSource::Content {
+                    spec_hash,
+                    source: Arc::new(cfg),
+                };
+                self.inner.cache.write().insert(key, stored.clone());
+                Ok(Some(stored))
+            }
             (kind, _) => {
                 anyhow::bail!("tool source kind/spec mismatch for '{source_id}': {kind:?}")
             }
@@ -236,6 +372,37 @@ async fn resolve_auth_secrets(
             resolve_secret_ref(store, tenant_id, value).await
         }
         AuthConfig::Basic { password, .. } => resolve_secret_ref(store, tenant_id, password).await,
+        AuthConfig::Sigv4 {
+            access_key_id,
+            secret_access_key,
+            session_token,
+            ..
+        } => {
+            resolve_secret_ref(store, tenant_id, access_key_id).await?;
+            resolve_secret_ref(store, tenant_id, secret_access_key).await?;
+            if let Some(token) = session_token {
+                resolve_secret_ref(store, tenant_id, token).await?;
+            }
+            Ok(())
+        }
+        AuthConfig::Hmac { secret, .. } => resolve_secret_ref(store, tenant_id, secret).await,
+    }
+}
+
+async fn resolve_security_credential_secrets(
+    store: &dyn Store,
+    tenant_id: &str,
+    credential: &mut SecurityCredential,
+) -> anyhow::Result<()> {
+    match credential {
+        SecurityCredential::ApiKey { value } => resolve_secret_ref(store, tenant_id, value).await,
+        SecurityCredential::Bearer { token } => resolve_secret_ref(store, tenant_id, token).await,
+        SecurityCredential::Basic { password, .. } => {
+            resolve_secret_ref(store, tenant_id, password).await
+        }
+        SecurityCredential::Oauth2ClientCredentials { client_secret, .. } => {
+            resolve_secret_ref(store, tenant_id, client_secret).await
+        }
     }
 }
 
@@ -333,6 +500,9 @@ mod tests {
         ) -> anyhow::Result<Option<crate::store::TransportLimitsSettings>> {
             Ok(None)
         }
+        async fn get_tenant_allowed_cidrs(&self, _tenant_id: &str) -> anyhow::Result<Vec<String>> {
+            Ok(Vec::new())
+        }
 
         async fn authenticate_api_key(
             &self,
@@ -383,6 +553,78 @@ mod tests {
         ) -> anyhow::Result<bool> {
             Ok(false)
         }
+
+        async fn list_enabled_oidc_claim_rules(
+            &self,
+            _tenant_id: &str,
+            _issuer: &str,
+        ) -> anyhow::Result<Vec<crate::store::OidcClaimRule>> {
+            Ok(Vec::new())
+        }
+
+        async fn get_tenant_oidc_issuer(
+            &self,
+            _tenant_id: &str,
+            _issuer: &str,
+        ) -> anyhow::Result<Option<crate::store::TenantOidcIssuer>> {
+            Ok(None)
+        }
+
+        async fn list_enabled_tenant_oidc_issuers(
+            &self,
+            _tenant_id: &str,
+        ) -> anyhow::Result<Vec<crate::store::TenantOidcIssuer>> {
+            Ok(Vec::new())
+        }
+
+        async fn get_upstream_oauth_client(
+            &self,
+            _tenant_id: &str,
+            _upstream_id: &str,
+        ) -> anyhow::Result<Option<crate::store::UpstreamOAuthClient>> {
+            Ok(None)
+        }
+
+        async fn get_upstream_oauth_token(
+            &self,
+            _tenant_id: &str,
+            _upstream_id: &str,
+            _principal: &str,
+        ) -> anyhow::Result<Option<crate::store::UpstreamOAuthToken>> {
+            Ok(None)
+        }
+
+        async fn put_upstream_oauth_token(
+            &self,
+            _tenant_id: &str,
+            _upstream_id: &str,
+            _principal: &str,
+            _token: &crate::store::UpstreamOAuthToken,
+        ) -> anyhow::Result<()> {
+            Ok(())
+        }
+
+        async fn put_upstream_oauth_link_state(
+            &self,
+            _link: &crate::store::UpstreamOAuthLinkState,
+        ) -> anyhow::Result<()> {
+            Ok(())
+        }
+
+        async fn get_upstream_oauth_link_state(
+            &self,
+
+            _state_hash: &str,
+        ) -> anyhow::Result<Option<crate::store::UpstreamOAuthLinkState>> {
+            Ok(None)
+        }
+
+        async fn take_upstream_oauth_link_state(
+            &self,
+            _state_hash: &str,
+        ) -> anyhow::Result<Option<crate::store::UpstreamOAuthLinkState>> {
+            Ok(None)
+        }
     }
 
     #[test]
@@ -399,6 +641,30 @@ mod tests {
         assert_eq!(parse_secret_ref("}"), None);
     }
 
+    #[tokio::test]
+    async fn resolves_secret_refs_in_openapi_security_credentials() -> anyhow::Result<()> {
+        let store = FakeStore::default();
+        let mut credential = SecurityCredential::Oauth2ClientCredentials {
+            client_id: "client".to_string(),
+            client_secret: "${secret:oauth_secret}".to_string(),
+            scopes: vec![],
+            token_url: None,
+        };
+
+        let err = resolve_security_credential_secrets(&store, "t1", &mut credential)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("missing secret 'oauth_secret'"));
+
+        store.put_secret("oauth_secret", "s3cr3t");
+        resolve_security_credential_secrets(&store, "t1", &mut credential).await?;
+        let SecurityCredential::Oauth2ClientCredentials { client_secret, .. } = credential else {
+            panic!("expected oauth2 credential");
+        };
+        assert_eq!(client_secret, "s3cr3t");
+        Ok(())
+    }
+
     #[tokio::test]
     async fn tenant_http_source_missing_secret_causes_list_tools_error_then_succeeds()
     -> anyhow::Result<()> {
@@ -412,6 +678,7 @@ mod tests {
             }),
             defaults: http_tools::EndpointDefaults::default(),
             response_transforms: vec![],
+            xml: Default::default(),
             tools: std::collections::HashMap::from([(
                 "ping".to_string(),
                 http_tools::HttpToolConfig {
@@ -419,6 +686,11 @@ mod tests {
                     path: "/ping".to_string(),
                     description: None,
                     params: std::collections::HashMap::new(),
+                    body_encoding: Default::default(),
+                    body_template: None,
+                    pagination: None,
+                    async_operation: None,
+                    retry: None,
                     response: http_tools::HttpResponseConfig::default(),
                 },
             )]),
@@ -428,7 +700,7 @@ mod tests {
             id: "s1".to_string(),
             kind: crate::store::ToolSourceKind::Http,
             enabled: true,
-            spec: crate::store::ToolSourceSpec::Http(cfg.clone()),
+            spec: crate::store::ToolSourceSpec::Http(Box::new(cfg.clone())),
         });
 
         let err = catalog.list_tools(&store, "t1", "s1").await.unwrap_err();
@@ -469,11 +741,12 @@ mod tests {
             id: "s1".to_string(),
             kind: crate::store::ToolSourceKind::Http,
             enabled: true,
-            spec: crate::store::ToolSourceSpec::Http(http_tools::HttpServerConfig {
+            spec: crate::store::ToolSourceSpec::Http(Box::new(http_tools::HttpServerConfig {
                 base_url: "https://example.com".to_string(),
                 auth: None,
                 defaults: http_tools::EndpointDefaults::default(),
                 response_transforms: vec![],
+                xml: Default::default(),
                 tools: std::collections::HashMap::from([
                     (
                         "tool_a".to_string(),
@@ -482,6 +755,11 @@ mod tests {
                             path: "/a".to_string(),
                             description: None,
                             params: std::collections::HashMap::new(),
+                            body_encoding: Default::default(),
+                            body_template: None,
+                            pagination: None,
+                            async_operation: None,
+                            retry: None,
                             response: http_tools::HttpResponseConfig::default(),
                         },
                     ),
@@ -492,11 +770,16 @@ mod tests {
                             path: "/b".to_string(),
                             description: None,
                             params: std::collections::HashMap::new(),
+                            body_encoding: Default::default(),
+                            body_template: None,
+                            pagination: None,
+                            async_operation: None,
+                            retry: None,
                             response: http_tools::HttpResponseConfig::default(),
                         },
                     ),
                 ]),
-            }),
+            })),
         });
 
         let tools = catalog
@@ -512,11 +795,12 @@ mod tests {
             id: "s1".to_string(),
             kind: crate::store::ToolSourceKind::Http,
             enabled: true,
-            spec: crate::store::ToolSourceSpec::Http(http_tools::HttpServerConfig {
+            spec: crate::store::ToolSourceSpec::Http(Box::new(http_tools::HttpServerConfig {
                 base_url: "https://example.com".to_string(),
                 auth: None,
                 defaults: http_tools::EndpointDefaults::default(),
                 response_transforms: vec![],
+                xml: Default::default(),
                 tools: std::collections::HashMap::from([(
                     "tool_a".to_string(),
                     http_tools::HttpToolConfig {
@@ -524,10 +808,15 @@ mod tests {
                         path: "/a".to_string(),
                         description: None,
                         params: std::collections::HashMap::new(),
+                        body_encoding: Default::default(),
+                        body_template: None,
+                        pagination: None,
+                        async_operation: None,
+                        retry: None,
                         response: http_tools::HttpResponseConfig::default(),
                     },
                 )]),
-            }),
+            })),
         });
 
         let tools = catalog
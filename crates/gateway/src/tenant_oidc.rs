@@ -0,0 +1,323 @@
+use crate::oidc::{OidcConfig, OidcValidator, parse_alg};
+use crate::store::{AdminStore, TenantOidcIssuer};
+use parking_lot::RwLock;
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+/// Default `allowedAlgs` for new issuers.
+pub const DEFAULT_ALLOWED_ALG: &str = "RS256";
+
+/// Upper bound on issuers per tenant.
+pub const MAX_ISSUERS_PER_TENANT: usize = 10;
+/// Upper bound on audiences per issuer.
+pub const MAX_AUDIENCES_PER_ISSUER: usize = 16;
+
+/// Dev override: allow `http://` tenant issuer / JWKS URLs (local IdPs only).
+pub const ALLOW_HTTP_ENV: &str = "UNRELATED_GATEWAY_TENANT_OIDC_ALLOW_HTTP";
+
+/// Leeway/refresh for tenant issuers mirror the Gateway-wide defaults.
+const TENANT_ISSUER_LEEWAY_SECS: u64 = 60;
+const TENANT_ISSUER_JWKS_REFRESH_SECS: u64 = 600;
+
+/// How long issuer settings are cached; writes invalidate them via `pg_invalidation`
+/// (`tenant_oidc_issuer`), so this only bounds a missed notification.
+const ISSUER_SETTINGS_TTL: Duration = Duration::from_secs(30);
+
+fn check_https_url(field: &str, raw: &str) -> Result<(), String> {
+    let url = reqwest::Url::parse(raw).map_err(|e| format!("invalid {field}: {e}"))?;
+    if url.query().is_some() || url.fragment().is_some() {
+        return Err(format!("{field} must not contain a query or fragment"));
+    }
+    match url.scheme() {
+        "https" => Ok(()),
+        "http" if unrelated_env::flag(ALLOW_HTTP_ENV) => Ok(()),
+        _ => Err(format!(
+            "{field} must use https (dev override: {ALLOW_HTTP_ENV}=1)"
+        )),
+    }
+}
+
+/// Validate tenant-provided issuer settings (syntax only; outbound safety is checked separately).
+pub fn validate_issuer(cfg: &TenantOidcIssuer) -> Result<(), String> {
+    if cfg.issuer.trim() != cfg.issuer || cfg.issuer.is_empty() {
+        return Err("issuer must be a non-empty URL without surrounding whitespace".to_string());
+    }
+    check_https_url("issuer", &cfg.issuer)?;
+
+    // Without an audience check, any token the IdP mints for *other* applications would be
+    // accepted. Shared IdPs (Entra, Auth0, Okta) make that a real risk, so require one.
+    if cfg.audiences.is_empty() {
+        return Err("at least one audience is required".to_string());
+    }
+    if cfg.audiences.len() > MAX_AUDIENCES_PER_ISSUER {
+        return Err(format!(
+            "too many audiences (max {MAX_AUDIENCES_PER_ISSUER})"
+        ));
+    }
+    if cfg.audiences.iter().any(|a| a.trim().is_empty()) {
+        return Err("audiences must be non-empty".to_string());
+    }
+
+    if let Some(jwks_uri) = cfg.jwks_uri.as_deref() {
+        check_https_url("jwksUri", jwks_uri)?;
+    }
+
+    if cfg.allowed_algs.is_empty() {
+        return Err("at least one allowed alg is required".to_string());
+    }
+    for alg in &cfg.allowed_algs {
+        if parse_alg(alg).is_none() {
+            return Err(format!(
+                "unsupported alg '{alg}' (allowed: RS256|RS384|RS512|PS256|PS384|PS512|ES256|ES384)"
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// JWT data-plane auth needs either the Gateway-wide issuer or an enabled tenant issuer.
+pub async fn oidc_configured_for_tenant(
+    global_configured: bool,
+    store: &dyn AdminStore,
+    tenant_id: &str,
+) -> anyhow::Result<bool> {
+    if global_configured {
+        return Ok(true);
+    }
+    Ok(store
+        .list_tenant_oidc_issuers(tenant_id)
+        .await?
+        .iter()
+        .any(|i| i.enabled))
+}
+
+/// Per-tenant OIDC validators and issuer settings, keyed by `(tenant_id, issuer)`.
+///
+/// Each validator owns its JWKS cache. Issuer settings (including "not configured") are cached
+/// for [`ISSUER_SETTINGS_TTL`]; caching settings that no longer match a validator (deleted,
+/// disabled or edited issuer) drops that validator.
+pub struct TenantOidcValidators {
+    http: reqwest::Client,
+    validators: RwLock<HashMap<(String, String), (TenantOidcIssuer, OidcValidator)>>,
+    issuers: RwLock<HashMap<(String, String), CachedIssuer>>,
+}
+
+/// Issuer settings as last loaded from the store (`None`: not configured).
+#[derive(Debug, Clone)]
+struct CachedIssuer {
+    expires_at: Instant,
+    cfg: Option<TenantOidcIssuer>,
+}
+
+impl TenantOidcValidators {
+    #[must_use]
+    pub fn new(http: reqwest::Client) -> Self {
+        Self {
+            http,
+            validators: RwLock::new(HashMap::new()),
+            issuers: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// Cached settings for a tenant issuer: `Some(None)` when the issuer is known to be absent,
+    /// `None` on a cache miss.
+    #[must_use]
+    pub fn cached_issuer(&self, tenant_id: &str, issuer: &str) -> Option<Option<TenantOidcIssuer>> {
+        let key = (tenant_id.to_string(), issuer.to_string());
+        let mut map = self.issuers.write();
+        let entry = map.get(&key)?;
+        if entry.expires_at <= Instant::now() {
+            map.remove(&key);
+            return None;
+        }
+        Some(entry.cfg.clone())
+    }
+
+    /// Cache settings loaded from the store, dropping a validator built from other settings.
+    pub fn put_issuer(&self, tenant_id: &str, issuer: &str, cfg: Option<TenantOidcIssuer>) {
+        let key = (tenant_id.to_string(), issuer.to_string());
+        {
+            let mut validators = self.validators.write();
+            let stale = validators
+                .get(&key)
+                .is_some_and(|(cached, _)| cfg.as_ref().is_none_or(|c| !c.enabled || c != cached));
+            if stale {
+                validators.remove(&key);
+            }
+        }
+        let expires_at = Instant::now() + ISSUER_SETTINGS_TTL;
+        self.issuers
+            .write()
+            .insert(key, CachedIssuer { expires_at, cfg });
+    }
+
+    /// Return the validator for a tenant issuer, performing discovery on first use.
+    pub async fn validator(
+        &self,
+        tenant_id: &str,
+        cfg: &TenantOidcIssuer,
+    ) -> anyhow::Result<OidcValidator> {
+        let key = (tenant_id.to_string(), cfg.issuer.clone());
+        if let Some((cached_cfg, v)) = self.validators.read().get(&key)
+            && cached_cfg == cfg
+        {
+            return Ok(v.clone());
+        }
+
+        let safety = crate::outbound_safety::gateway_outbound_http_safety();
+        let jwks_uri = match cfg.jwks_uri.as_deref() {
+            Some(uri) => uri.to_string(),
+            None => {
+                let discovery = format!(
+                    "{}/.well-known/openid-configuration",
+                    cfg.issuer.trim_end_matches('/')
+                );
+                crate::outbound_safety::check_url_allowed(&safety, &discovery)
+                    .await
+                    .map_err(|e| anyhow::anyhow!("oidc discovery url not allowed: {e}"))?;
+                crate::oidc::discover_jwks_uri(&self.http, &cfg.issuer).await?
+            }
+        };
+        crate::outbound_safety::check_url_allowed(&safety, &jwks_uri)
+            .await
+            .map_err(|e| anyhow::anyhow!("jwks uri not allowed: {e}"))?;
+
+        let validator = OidcValidator::new(
+            self.http.clone(),
+            OidcConfig {
+                issuer: cfg.issuer.clone(),
+                audiences: cfg.audiences.clone(),
+                jwks_uri,
+                leeway_secs: TENANT_ISSUER_LEEWAY_SECS,
+                jwks_refresh_secs: TENANT_ISSUER_JWKS_REFRESH_SECS,
+                allowed_algs: cfg
+                    .allowed_algs
+                    .iter()
+                    .filter_map(|a| parse_alg(a))
+                    .collect(),
+            },
+        );
+        self.validators
+            .write()
+            .insert(key, (cfg.clone(), validator.clone()));
+        Ok(validator)
+    }
+
+    /// Drop the cached settings and validator of an issuer (after it was changed or deleted).
+    pub fn forget(&self, tenant_id: &str, issuer: &str) {
+        let key = (tenant_id.to_string(), issuer.to_string());
+        self.issuers.write().remove(&key);
+        self.validators.write().remove(&key);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn issuer() -> TenantOidcIssuer {
+        TenantOidcIssuer {
+            issuer: "https://login.example.com/tenant-a/v2.0".to_string(),
+            audiences: vec!["api://gateway".to_string()],
+            jwks_uri: None,
+            allowed_algs: vec!["RS256".to_string()],
+            enabled: true,
+        }
+    }
+
+    #[test]
+    fn validate_accepts_https_issuer_with_audience() {
+        assert!(validate_issuer(&issuer()).is_ok());
+
+        let mut es = issuer();
+        es.allowed_algs = vec!["ES256".to_string(), "PS256".to_string()];
+        es.jwks_uri = Some("https://login.example.com/keys".to_string());
+        assert!(validate_issuer(&es).is_ok());
+    }
+
+    #[test]
+    fn validate_rejects_insecure_or_incomplete_settings() {
+        let mut c = issuer();
+        c.issuer = "http://login.example.com".to_string();
+        assert!(validate_issuer(&c).is_err());
+
+        let mut c = issuer();
+        c.issuer = "https://login.example.com?x=1".to_string();
+        assert!(validate_issuer(&c).is_err());
+
+        let mut c = issuer();
+        c.audiences.clear();
+        assert!(validate_issuer(&c).is_err());
+
+        let mut c = issuer();
+        c.allowed_algs = vec!["HS256".to_string()];
+        assert!(validate_issuer(&c).is_err());
+
+        let mut c = issuer();
+        c.jwks_uri = Some("ftp://login.example.com/keys".to_string());
+        assert!(validate_issuer(&c).is_err());
+    }
+
+    #[test]
+    fn changed_or_removed_issuer_settings_drop_the_validator() {
+        let validators = TenantOidcValidators::new(reqwest::Client::new());
+        let cfg = issuer();
+        let key = ("t1".to_string(), cfg.issuer.clone());
+        let build = || {
+            let validator = OidcValidator::new(
+                reqwest::Client::new(),
+                OidcConfig {
+                    issuer: cfg.issuer.clone(),
+                    audiences: cfg.audiences.clone(),
+                    jwks_uri: "https://login.example.com/keys".to_string(),
+                    leeway_secs: 0,
+                    jwks_refresh_secs: 0,
+                    allowed_algs: Vec::new(),
+                },
+            );
+            validators
+                .validators
+                .write()
+                .insert(key.clone(), (cfg.clone(), validator));
+        };
+        assert!(validators.cached_issuer("t1", &cfg.issuer).is_none());
+
+        validators.put_issuer("t1", &cfg.issuer, Some(cfg.clone()));
+        build();
+        assert_eq!(
+            validators.cached_issuer("t1", &cfg.issuer),
+            Some(Some(cfg.clone()))
+        );
+
+        // Same settings keep the validator.
+        validators.put_issuer("t1", &cfg.issuer, Some(cfg.clone()));
+        assert!(validators.validators.read().contains_key(&key));
+
+        let mut edited = cfg.clone();
+        edited.audiences = vec!["api://other".to_string()];
+        validators.put_issuer("t1", &cfg.issuer, Some(edited));
+        assert!(!validators.validators.read().contains_key(&key));
+
+        build();
+        validators.put_issuer("t1", &cfg.issuer, None);
+        assert!(!validators.validators.read().contains_key(&key));
+        assert_eq!(validators.cached_issuer("t1", &cfg.issuer), Some(None));
+
+        build();
+        validators.forget("t1", &cfg.issuer);
+        assert!(validators.cached_issuer("t1", &cfg.issuer).is_none());
+        assert!(!validators.validators.read().contains_key(&key));
+    }
+
+    #[test]
+    fn unverified_issuer_reads_iss_claim() {
+        use base64::{Engine as _, engine::general_purpose::URL_SAFE_NO_PAD};
+        let payload = URL_SAFE_NO_PAD.encode(br#"{"iss":"https://idp.example","sub":"u1"}"#);
+        let jwt = format!("eyJhbGciOiJSUzI1NiJ9.{payload}.sig");
+        assert_eq!(
+            crate::oidc::unverified_issuer(&jwt).as_deref(),
+            Some("https://idp.example")
+        );
+        assert!(crate::oidc::unverified_issuer("not-a-jwt").is_none());
+    }
+}
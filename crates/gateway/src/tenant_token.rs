@@ -1,8 +1,11 @@
 use base64::{Engine as _, engine::general_purpose::URL_SAFE_NO_PAD};
 use hmac::{Hmac, Mac as _};
+use parking_lot::RwLock;
 use serde::{Deserialize, Serialize};
 use sha2::Sha256;
-use std::time::{SystemTime, UNIX_EPOCH};
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 
 type HmacSha256 = Hmac<Sha256>;
 
@@ -82,6 +85,266 @@ impl TenantSigner {
 pub struct TenantTokenPayloadV1 {
     pub tenant_id: String,
     pub exp_unix_secs: u64,
+    /// Token id used for revocation. Legacy tokens (issued before scoped tokens) have no id and
+    /// cannot be revoked before they expire.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub token_id: Option<String>,
+    /// Granted scopes. `None` means full tenant access (legacy tokens + `admin` role).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scopes: Option<Vec<TenantScope>>,
+}
+
+impl TenantTokenPayloadV1 {
+    #[must_use]
+    pub fn allows(&self, scope: TenantScope) -> bool {
+        self.scopes.as_ref().is_none_or(|s| s.contains(&scope))
+    }
+}
+
+/// Per-route permission carried by a tenant token (`<resource>:<read|write>`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum TenantScope {
+    #[serde(rename = "upstreams:read")]
+    UpstreamsRead,
+    #[serde(rename = "upstreams:write")]
+    UpstreamsWrite,
+    #[serde(rename = "profiles:read")]
+    ProfilesRead,
+    #[serde(rename = "profiles:write")]
+    ProfilesWrite,
+    #[serde(rename = "tool-sources:read")]
+    ToolSourcesRead,
+    #[serde(rename = "tool-sources:write")]
+    ToolSourcesWrite,
+    #[serde(rename = "secrets:read")]
+    SecretsRead,
+    #[serde(rename = "secrets:write")]
+    SecretsWrite,
+    #[serde(rename = "api-keys:read")]
+    ApiKeysRead,
+    #[serde(rename = "api-keys:write")]
+    ApiKeysWrite,
+    #[serde(rename = "audit:read")]
+    AuditRead,
+    #[serde(rename = "audit:write")]
+    AuditWrite,
+    #[serde(rename = "transport:read")]
+    TransportRead,
+    #[serde(rename = "transport:write")]
+    TransportWrite,
+    #[serde(rename = "network:read")]
+    NetworkRead,
+    #[serde(rename = "network:write")]
+    NetworkWrite,
+    #[serde(rename = "oidc:read")]
+    OidcRead,
+    #[serde(rename = "oidc:write")]
+    OidcWrite,
+    #[serde(rename = "sessions:read")]
+    SessionsRead,
+    #[serde(rename = "sessions:write")]
+    SessionsWrite,
+    #[serde(rename = "tokens:write")]
+    TokensWrite,
+}
+
+impl TenantScope {
+    pub const ALL: [Self; 21] = [
+        Self::UpstreamsRead,
+        Self::UpstreamsWrite,
+        Self::ProfilesRead,
+        Self::ProfilesWrite,
+        Self::ToolSourcesRead,
+        Self::ToolSourcesWrite,
+        Self::SecretsRead,
+        Self::SecretsWrite,
+        Self::ApiKeysRead,
+        Self::ApiKeysWrite,
+        Self::AuditRead,
+        Self::AuditWrite,
+        Self::TransportRead,
+        Self::TransportWrite,
+        Self::NetworkRead,
+        Self::NetworkWrite,
+        Self::OidcRead,
+        Self::OidcWrite,
+        Self::SessionsRead,
+        Self::SessionsWrite,
+        Self::TokensWrite,
+    ];
+
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::UpstreamsRead => "upstreams:read",
+            Self::UpstreamsWrite => "upstreams:write",
+            Self::ProfilesRead => "profiles:read",
+            Self::ProfilesWrite => "profiles:write",
+            Self::ToolSourcesRead => "tool-sources:read",
+            Self::ToolSourcesWrite => "tool-sources:write",
+            Self::SecretsRead => "secrets:read",
+            Self::SecretsWrite => "secrets:write",
+            Self::ApiKeysRead => "api-keys:read",
+            Self::ApiKeysWrite => "api-keys:write",
+            Self::AuditRead => "audit:read",
+            Self::AuditWrite => "audit:write",
+            Self::TransportRead => "transport:read",
+            Self::TransportWrite => "transport:write",
+            Self::NetworkRead => "network:read",
+            Self::NetworkWrite => "network:write",
+            Self::OidcRead => "oidc:read",
+            Self::OidcWrite => "oidc:write",
+            Self::SessionsRead => "sessions:read",
+            Self::SessionsWrite => "sessions:write",
+            Self::TokensWrite => "tokens:write",
+        }
+    }
+
+    #[must_use]
+    pub fn is_read(self) -> bool {
+        self.as_str().ends_with(":read")
+    }
+}
+
+/// Predefined scope sets for issuing tenant tokens.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum TenantRole {
+    /// Full tenant access (equivalent to a legacy, unscoped token).
+    Admin,
+    /// Read-only access to every tenant resource (dashboards).
+    Viewer,
+}
+
+impl TenantRole {
+    /// Scope set for the role. `None` means unrestricted.
+    #[must_use]
+    pub fn scopes(self) -> Option<Vec<TenantScope>> {
+        match self {
+            Self::Admin => None,
+            Self::Viewer => Some(
+                TenantScope::ALL
+                    .into_iter()
+                    .filter(|s| s.is_read())
+                    .collect(),
+            ),
+        }
+    }
+}
+
+/// Resolve the scopes to embed in a newly issued token from an optional role and/or explicit
+/// scope list. Explicit scopes win over the role; neither means full access.
+pub fn resolve_issue_scopes(
+    role: Option<TenantRole>,
+    scopes: Option<Vec<TenantScope>>,
+) -> anyhow::Result<Option<Vec<TenantScope>>> {
+    match (role, scopes) {
+        (Some(_), Some(_)) => Err(anyhow::anyhow!("role and scopes are mutually exclusive")),
+        (Some(role), None) => Ok(role.scopes()),
+        (None, Some(mut scopes)) => {
+            if scopes.is_empty() {
+                return Err(anyhow::anyhow!("scopes must be non-empty"));
+            }
+            scopes.sort_by_key(|s| s.as_str());
+            scopes.dedup();
+            Ok(Some(scopes))
+        }
+        (None, None) => Ok(None),
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct RevocationEntry {
+    revoked: bool,
+    expires_at: Instant,
+}
+
+#[derive(Debug)]
+struct RevocationCacheState {
+    /// Keyed by `(tenant_id, token_id)`: token ids are only unique within a tenant.
+    entries: HashMap<(String, String), RevocationEntry>,
+    next_prune: Instant,
+}
+
+/// Local cache of tenant token revocation state.
+///
+/// Revocations are persisted in Postgres; other replicas learn about them via
+/// `pg_invalidation` (`tenant_token_revoked`). Entries expire after a short TTL (a missed
+/// notification is bounded, and the store stays authoritative), but never outlive the token's
+/// `exp`: a revoked entry whose token expiry is known is kept until then. Expired entries are pruned
+/// so the cache does not grow without bound.
+#[derive(Clone)]
+pub struct TenantTokenRevocations {
+    ttl: Duration,
+    inner: Arc<RwLock<RevocationCacheState>>,
+}
+
+impl TenantTokenRevocations {
+    #[must_use]
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            inner: Arc::new(RwLock::new(RevocationCacheState {
+                entries: HashMap::new(),
+                next_prune: Instant::now() + ttl,
+            })),
+        }
+    }
+
+    /// Cached revocation state for a tenant's token id (`None` on miss).
+    #[must_use]
+    pub fn get(&self, tenant_id: &str, token_id: &str) -> Option<bool> {
+        let key = (tenant_id.to_string(), token_id.to_string());
+        let mut state = self.inner.write();
+        let entry = *state.entries.get(&key)?;
+        if entry.expires_at <= Instant::now() {
+            state.entries.remove(&key);
+            return None;
+        }
+        Some(entry.revoked)
+    }
+
+    /// Cache a store lookup. `exp_unix_secs` (the token's expiry) bounds the entry; revoked entries
+    /// are kept until then.
+    pub fn put(&self, tenant_id: &str, token_id: &str, revoked: bool, exp_unix_secs: Option<u64>) {
+        let now = Instant::now();
+        let until_exp = exp_unix_secs.map(|exp| {
+            let now_unix = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs();
+            now + Duration::from_secs(exp.saturating_sub(now_unix))
+        });
+        let expires_at = match (revoked, until_exp) {
+            (true, Some(exp)) => exp,
+            (false, Some(exp)) => exp.min(now + self.ttl),
+            (_, None) => now + self.ttl,
+        };
+
+        let mut state = self.inner.write();
+        if state.next_prune <= now {
+            state.entries.retain(|_, e| e.expires_at > now);
+            state.next_prune = now + self.ttl;
+        }
+        state.entries.insert(
+            (tenant_id.to_string(), token_id.to_string()),
+            RevocationEntry {
+                revoked,
+                expires_at,
+            },
+        );
+    }
+
+    /// Record a revocation (local or notified); the token's expiry is unknown here, so the entry
+    /// lasts one TTL and later lookups go to the store.
+    pub fn mark_revoked(&self, tenant_id: &str, token_id: &str) {
+        self.put(tenant_id, token_id, true, None);
+    }
+
+    #[cfg(test)]
+    fn len(&self) -> usize {
+        self.inner.read().entries.len()
+    }
 }
 
 #[cfg(test)]
@@ -95,6 +358,8 @@ mod tests {
         let payload = TenantTokenPayloadV1 {
             tenant_id: "t1".to_string(),
             exp_unix_secs: 200,
+            token_id: None,
+            scopes: None,
         };
         let token = signer.sign_v1(&payload).expect("sign");
 
@@ -104,4 +369,124 @@ mod tests {
         let err = signer.verify_at(&token, 200).unwrap_err();
         assert!(err.to_string().contains("expired"));
     }
+
+    #[test]
+    fn tenant_token_scopes_roundtrip_and_enforce() {
+        let signer = TenantSigner::new(b"secret".to_vec());
+
+        let payload = TenantTokenPayloadV1 {
+            tenant_id: "t1".to_string(),
+            exp_unix_secs: 200,
+            token_id: Some("tok-1".to_string()),
+            scopes: Some(vec![TenantScope::ProfilesRead, TenantScope::AuditRead]),
+        };
+        let token = signer.sign_v1(&payload).expect("sign");
+        let decoded = signer.verify_at(&token, 100).expect("verify");
+
+        assert_eq!(decoded.token_id.as_deref(), Some("tok-1"));
+        assert!(decoded.allows(TenantScope::ProfilesRead));
+        assert!(decoded.allows(TenantScope::AuditRead));
+        assert!(!decoded.allows(TenantScope::ProfilesWrite));
+        assert!(!decoded.allows(TenantScope::SecretsWrite));
+
+        let json = serde_json::to_value(&payload).expect("serialize");
+        assert_eq!(
+            json.get("scopes"),
+            Some(&serde_json::json!(["profiles:read", "audit:read"]))
+        );
+    }
+
+    #[test]
+    fn legacy_tenant_token_payload_has_full_access() {
+        let payload: TenantTokenPayloadV1 =
+            serde_json::from_value(serde_json::json!({"tenantId": "t1", "expUnixSecs": 10}))
+                .expect("deserialize");
+        assert!(payload.token_id.is_none());
+        for scope in TenantScope::ALL {
+            assert!(payload.allows(scope), "{}", scope.as_str());
+        }
+    }
+
+    #[test]
+    fn resolve_issue_scopes_expands_roles_and_rejects_conflicts() {
+        assert!(resolve_issue_scopes(None, None).expect("none").is_none());
+        assert!(
+            resolve_issue_scopes(Some(TenantRole::Admin), None)
+                .expect("admin")
+                .is_none()
+        );
+
+        let viewer = resolve_issue_scopes(Some(TenantRole::Viewer), None)
+            .expect("viewer")
+            .expect("scoped");
+        assert!(viewer.contains(&TenantScope::SecretsRead));
+        assert!(viewer.iter().all(|s| s.is_read()));
+
+        let explicit = resolve_issue_scopes(
+            None,
+            Some(vec![TenantScope::SecretsWrite, TenantScope::SecretsWrite]),
+        )
+        .expect("explicit")
+        .expect("scoped");
+        assert_eq!(explicit, vec![TenantScope::SecretsWrite]);
+
+        assert!(resolve_issue_scopes(None, Some(vec![])).is_err());
+        assert!(
+            resolve_issue_scopes(Some(TenantRole::Viewer), Some(vec![TenantScope::AuditRead]))
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn revocation_cache_keeps_revoked_until_token_expiry_and_expires_negative_entries() {
+        let cache = TenantTokenRevocations::new(Duration::from_secs(0));
+        assert_eq!(cache.get("t1", "a"), None);
+
+        cache.put("t1", "a", false, None);
+        assert_eq!(
+            cache.get("t1", "a"),
+            None,
+            "negative entries expire after ttl"
+        );
+
+        let exp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs()
+            + 3600;
+        cache.put("t1", "b", true, Some(exp));
+        assert_eq!(cache.get("t1", "b"), Some(true));
+
+        cache.put("t1", "c", true, Some(1));
+        assert_eq!(
+            cache.get("t1", "c"),
+            None,
+            "entries never outlive the token"
+        );
+
+        cache.mark_revoked("t1", "d");
+        assert_eq!(
+            cache.get("t1", "d"),
+            None,
+            "notified revocations last one ttl"
+        );
+    }
+
+    #[test]
+    fn revocation_cache_is_scoped_per_tenant_and_pruned() {
+        let cache = TenantTokenRevocations::new(Duration::from_secs(60));
+        cache.mark_revoked("t1", "tok");
+        assert_eq!(cache.get("t1", "tok"), Some(true));
+        assert_eq!(
+            cache.get("t2", "tok"),
+            None,
+            "other tenants' tokens are unaffected"
+        );
+
+        let cache = TenantTokenRevocations::new(Duration::from_secs(0));
+        for i in 0..10 {
+            cache.mark_revoked("t1", &format!("tok-{i}"));
+        }
+        assert_eq!(cache.len(), 1, "expired entries are pruned on insert");
+    }
 }
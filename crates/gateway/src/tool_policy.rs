@@ -1,4 +1,5 @@
 use serde::{Deserialize, Serialize};
+use unrelated_http_tools::config::ResponseTransformChainConfig;
 
 /// Per-tool retry policy (Temporal-style fields).
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -36,4 +37,9 @@ pub struct ToolPolicy {
     /// Optional per-tool retry policy (Gateway-only).
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub retry: Option<RetryPolicy>,
+    /// Optional response shaping chain for upstream MCP results of this tool (Gateway-only).
+    ///
+    /// Applied on top of the profile's `mcp.responseTransforms` (`mode: append|replace`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub response_transforms: Option<ResponseTransformChainConfig>,
 }
@@ -0,0 +1,446 @@
+//! Tool search meta-surface (`mcp.toolSearch`).
+//!
+//! Profiles that aggregate many sources can expose hundreds of tools. With tool search enabled,
+//! `tools/list` returns three meta-tools instead:
+//! - `search_tools(query, limit?)`: BM25-ranked matches over tool names, descriptions and
+//!   parameter names/descriptions,
+//! - `describe_tool(name)`: the full tool definition (including `inputSchema`),
+//! - `call_tool(name, arguments)`: dispatch to a tool through the normal `tools/call` pipeline.
+//!
+//! The index is built from the session's `CachedToolsSurface`, so search results reflect exactly
+//! the tools (and exposed names) the profile would otherwise list.
+
+use crate::store::McpToolSearchSettings;
+use rmcp::model::{JsonObject, Tool};
+use serde::Serialize;
+use serde_json::{Map, Value, json};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+pub const SEARCH_TOOLS: &str = "search_tools";
+pub const DESCRIBE_TOOL: &str = "describe_tool";
+pub const CALL_TOOL: &str = "call_tool";
+
+const MAX_RESULTS_LIMIT: u32 = 50;
+const MAX_QUERY_BYTES: usize = 1024;
+
+// BM25 parameters (standard defaults).
+const K1: f64 = 1.2;
+const B: f64 = 0.75;
+// Field weights: a term in the tool name counts more than one in a description.
+const NAME_WEIGHT: f64 = 3.0;
+const DESCRIPTION_WEIGHT: f64 = 1.0;
+const PARAM_WEIGHT: f64 = 1.0;
+// Bound schema traversal for parameter text.
+const MAX_SCHEMA_DEPTH: usize = 4;
+
+/// Validate `mcp.toolSearch` settings.
+///
+/// # Errors
+///
+/// Returns a human-readable message if a field is out of range.
+pub fn validate_settings(s: &McpToolSearchSettings) -> Result<(), String> {
+    if s.max_results == 0 || s.max_results > MAX_RESULTS_LIMIT {
+        return Err(format!(
+            "mcp.toolSearch.maxResults must be between 1 and {MAX_RESULTS_LIMIT}"
+        ));
+    }
+    Ok(())
+}
+
+/// The meta-tools advertised by `tools/list` in tool search mode.
+#[must_use]
+pub fn meta_tools(settings: &McpToolSearchSettings) -> Vec<Tool> {
+    let schema = |v: Value| Arc::new(v.as_object().cloned().unwrap_or_else(JsonObject::new));
+    vec![
+        Tool::new(
+            SEARCH_TOOLS,
+            "Search the available tools by keyword. Returns the best matching tool names with \
+             short descriptions; use describe_tool for a tool's parameters and call_tool to \
+             invoke it.",
+            schema(json!({
+                "type": "object",
+                "properties": {
+                    "query": {
+                        "type": "string",
+                        "description": "Keywords describing the task, e.g. \"create invoice\"."
+                    },
+                    "limit": {
+                        "type": "integer",
+                        "minimum": 1,
+                        "maximum": settings.max_results,
+                        "description": "Maximum number of results."
+                    }
+                },
+                "required": ["query"],
+                "additionalProperties": false
+            })),
+        ),
+        Tool::new(
+            DESCRIBE_TOOL,
+            "Return the full definition of a tool, including its input schema.",
+            schema(json!({
+                "type": "object",
+                "properties": {
+                    "name": { "type": "string", "description": "Tool name from search_tools." }
+                },
+                "required": ["name"],
+                "additionalProperties": false
+            })),
+        ),
+        Tool::new(
+            CALL_TOOL,
+            "Call a tool by name with arguments matching its input schema.",
+            schema(json!({
+                "type": "object",
+                "properties": {
+                    "name": { "type": "string", "description": "Tool name from search_tools." },
+                    "arguments": {
+                        "type": "object",
+                        "description": "Tool arguments (see describe_tool)."
+                    }
+                },
+                "required": ["name"],
+                "additionalProperties": false
+            })),
+        ),
+    ]
+}
+
+/// A parsed meta-tool invocation.
+#[derive(Debug, Clone, PartialEq)]
+pub enum MetaCall {
+    Search {
+        query: String,
+        limit: usize,
+    },
+    Describe {
+        name: String,
+    },
+    Call {
+        name: String,
+        arguments: Map<String, Value>,
+    },
+}
+
+/// Parse a `tools/call` of a meta-tool. Returns `Ok(None)` if `tool_name` is not a meta-tool.
+///
+/// # Errors
+///
+/// Returns a human-readable message if the arguments are invalid.
+pub fn parse_meta_call(
+    tool_name: &str,
+    args: &Value,
+    settings: &McpToolSearchSettings,
+) -> Result<Option<MetaCall>, String> {
+    let empty = Map::new();
+    let args = match args {
+        Value::Object(m) => m,
+        Value::Null => &empty,
+        _ => return Err("arguments must be an object".to_string()),
+    };
+    let name_arg = || -> Result<String, String> {
+        match args.get("name") {
+            Some(Value::String(s)) if !s.is_empty() => Ok(s.clone()),
+            _ => Err("'name' must be a non-empty string".to_string()),
+        }
+    };
+
+    let call = match tool_name {
+        SEARCH_TOOLS => {
+            let query = match args.get("query") {
+                Some(Value::String(s)) if !s.trim().is_empty() => s.clone(),
+                _ => return Err("'query' must be a non-empty string".to_string()),
+            };
+            if query.len() > MAX_QUERY_BYTES {
+                return Err(format!("'query' exceeds {MAX_QUERY_BYTES} bytes"));
+            }
+            let max = settings.max_results;
+            let limit = match args.get("limit") {
+                None | Some(Value::Null) => max,
+                Some(v) => v
+                    .as_u64()
+                    .and_then(|n| u32::try_from(n).ok())
+                    .filter(|n| (1..=max).contains(n))
+                    .ok_or_else(|| format!("'limit' must be an integer between 1 and {max}"))?,
+            };
+            MetaCall::Search {
+                query,
+                limit: limit as usize,
+            }
+        }
+        DESCRIBE_TOOL => MetaCall::Describe { name: name_arg()? },
+        CALL_TOOL => {
+            let arguments = match args.get("arguments") {
+                None | Some(Value::Null) => Map::new(),
+                Some(Value::Object(m)) => m.clone(),
+                Some(_) => return Err("'arguments' must be an object".to_string()),
+            };
+            MetaCall::Call {
+                name: name_arg()?,
+                arguments,
+            }
+        }
+        _ => return Ok(None),
+    };
+    Ok(Some(call))
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SearchHit {
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    pub score: f64,
+}
+
+/// Rank `tools` against `query` (BM25 over name, description and parameter text).
+///
+/// Only tools matching at least one query term are returned, best first.
+#[must_use]
+pub fn search(tools: &[Tool], query: &str, limit: usize) -> Vec<SearchHit> {
+    let mut terms = tokenize(query);
+    terms.sort_unstable();
+    terms.dedup();
+    if terms.is_empty() || tools.is_empty() {
+        return Vec::new();
+    }
+
+    let docs: Vec<Doc> = tools.iter().map(Doc::from_tool).collect();
+    #[allow(clippy::cast_precision_loss)]
+    let n = docs.len() as f64;
+    let avg_len = docs.iter().map(|d| d.len).sum::<f64>() / n;
+
+    let idf: Vec<f64> = terms
+        .iter()
+        .map(|t| {
+            #[allow(clippy::cast_precision_loss)]
+            let df = docs.iter().filter(|d| d.tf.contains_key(t)).count() as f64;
+            ((n - df + 0.5) / (df + 0.5) + 1.0).ln()
+        })
+        .collect();
+
+    let mut hits: Vec<SearchHit> = tools
+        .iter()
+        .zip(&docs)
+        .filter_map(|(tool, doc)| {
+            let score: f64 = terms
+                .iter()
+                .zip(&idf)
+                .filter_map(|(t, idf)| {
+                    let tf = *doc.tf.get(t)?;
+                    let norm = K1 * (1.0 - B + B * doc.len / avg_len.max(1.0));
+                    Some(idf * tf * (K1 + 1.0) / (tf + norm))
+                })
+                .sum();
+            (score > 0.0).then(|| SearchHit {
+                name: tool.name.to_string(),
+                description: tool.description.as_deref().map(str::to_string),
+                score: (score * 1000.0).round() / 1000.0,
+            })
+        })
+        .collect();
+
+    hits.sort_by(|a, b| {
+        b.score
+            .total_cmp(&a.score)
+            .then_with(|| a.name.cmp(&b.name))
+    });
+    hits.truncate(limit);
+    hits
+}
+
+/// Weighted term frequencies for one tool.
+struct Doc {
+    tf: HashMap<String, f64>,
+    len: f64,
+}
+
+impl Doc {
+    fn from_tool(tool: &Tool) -> Self {
+        let mut doc = Self {
+            tf: HashMap::new(),
+            len: 0.0,
+        };
+        doc.add(&tool.name, NAME_WEIGHT);
+        if let Some(d) = tool.description.as_deref() {
+            doc.add(d, DESCRIPTION_WEIGHT);
+        }
+        let mut params = String::new();
+        collect_param_text(&tool.input_schema, 0, &mut params);
+        doc.add(&params, PARAM_WEIGHT);
+        doc
+    }
+
+    fn add(&mut self, text: &str, weight: f64) {
+        for t in tokenize(text) {
+            *self.tf.entry(t).or_default() += weight;
+            self.len += weight;
+        }
+    }
+}
+
+fn collect_param_text(schema: &JsonObject, depth: usize, out: &mut String) {
+    if depth > MAX_SCHEMA_DEPTH {
+        return;
+    }
+    let Some(Value::Object(props)) = schema.get("properties") else {
+        return;
+    };
+    for (name, prop) in props {
+        out.push(' ');
+        out.push_str(name);
+        let Value::Object(prop) = prop else {
+            continue;
+        };
+        if let Some(Value::String(d)) = prop.get("description") {
+            out.push(' ');
+            out.push_str(d);
+        }
+        collect_param_text(prop, depth + 1, out);
+        if let Some(Value::Object(items)) = prop.get("items") {
+            collect_param_text(items, depth + 1, out);
+        }
+    }
+}
+
+/// Lowercased terms; splits on non-alphanumerics and camelCase boundaries and folds a trailing
+/// plural `s` so `listUsers` matches "list user".
+fn tokenize(text: &str) -> Vec<String> {
+    let mut out = Vec::new();
+    let mut cur = String::new();
+    let mut prev_lower = false;
+    let mut flush = |cur: &mut String| {
+        if cur.len() > 1 || cur.chars().any(|c| c.is_ascii_digit()) {
+            let mut t = std::mem::take(cur);
+            if t.len() > 3 && t.ends_with('s') && !t.ends_with("ss") {
+                t.pop();
+            }
+            out.push(t);
+        }
+        cur.clear();
+    };
+    for c in text.chars() {
+        if !c.is_alphanumeric() {
+            flush(&mut cur);
+            prev_lower = false;
+            continue;
+        }
+        if c.is_uppercase() && prev_lower {
+            flush(&mut cur);
+        }
+        prev_lower = c.is_lowercase() || c.is_ascii_digit();
+        cur.extend(c.to_lowercase());
+    }
+    flush(&mut cur);
+    out
+}
+
+/// `describe_tool` output: the tool definition as advertised to clients.
+#[must_use]
+pub fn describe(tool: &Tool) -> Value {
+    serde_json::to_value(tool).unwrap_or(Value::Null)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tool(name: &str, description: &str, schema: Value) -> Tool {
+        Tool::new(
+            name.to_string(),
+            description.to_string(),
+            Arc::new(schema.as_object().cloned().unwrap_or_default()),
+        )
+    }
+
+    fn surface() -> Vec<Tool> {
+        vec![
+            tool(
+                "crm:listCustomers",
+                "List customers in the CRM",
+                json!({ "type": "object", "properties": { "page": { "type": "integer" } } }),
+            ),
+            tool(
+                "billing:create_invoice",
+                "Create a draft invoice",
+                json!({ "type": "object", "properties": {
+                    "customer_id": { "type": "string", "description": "Customer to bill" },
+                    "lines": { "type": "array", "items": { "type": "object", "properties": {
+                        "sku": { "type": "string", "description": "Product SKU" }
+                    } } }
+                } }),
+            ),
+            tool("desk:get_ticket", "Fetch a support ticket", json!({})),
+        ]
+    }
+
+    #[test]
+    fn search_ranks_name_matches_first_and_uses_parameter_text() {
+        let tools = surface();
+
+        let hits = search(&tools, "invoice", 10);
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].name, "billing:create_invoice");
+
+        // "customer" appears in a name (camelCase, plural) and in a parameter description.
+        let hits = search(&tools, "customers", 10);
+        let names: Vec<&str> = hits.iter().map(|h| h.name.as_str()).collect();
+        assert_eq!(names, vec!["crm:listCustomers", "billing:create_invoice"]);
+
+        // Nested array item properties are indexed.
+        let hits = search(&tools, "sku", 10);
+        assert_eq!(hits[0].name, "billing:create_invoice");
+
+        assert!(search(&tools, "weather", 10).is_empty());
+        assert_eq!(search(&tools, "customer ticket", 1).len(), 1);
+    }
+
+    #[test]
+    fn parse_meta_call_validates_arguments() {
+        let settings = McpToolSearchSettings {
+            enabled: true,
+            max_results: 5,
+        };
+        assert_eq!(
+            parse_meta_call(SEARCH_TOOLS, &json!({ "query": "x" }), &settings),
+            Ok(Some(MetaCall::Search {
+                query: "x".to_string(),
+                limit: 5
+            }))
+        );
+        assert!(
+            parse_meta_call(
+                SEARCH_TOOLS,
+                &json!({ "query": "x", "limit": 6 }),
+                &settings
+            )
+            .is_err()
+        );
+        assert!(parse_meta_call(SEARCH_TOOLS, &json!({ "query": " " }), &settings).is_err());
+        assert!(parse_meta_call(DESCRIBE_TOOL, &json!({}), &settings).is_err());
+        assert_eq!(
+            parse_meta_call(
+                CALL_TOOL,
+                &json!({ "name": "a", "arguments": { "k": 1 } }),
+                &settings
+            ),
+            Ok(Some(MetaCall::Call {
+                name: "a".to_string(),
+                arguments: json!({ "k": 1 }).as_object().cloned().unwrap()
+            }))
+        );
+        assert!(
+            parse_meta_call(
+                CALL_TOOL,
+                &json!({ "name": "a", "arguments": [] }),
+                &settings
+            )
+            .is_err()
+        );
+        assert_eq!(
+            parse_meta_call("crm:listCustomers", &json!({}), &settings),
+            Ok(None)
+        );
+    }
+}
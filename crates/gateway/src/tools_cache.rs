@@ -12,6 +12,8 @@ pub enum ToolRouteKind {
     Upstream,
     SharedLocal,
     TenantLocal,
+    /// Tenant-owned `workflow` source; executed by the gateway as a sequence of `tools/call`s.
+    Workflow,
 }
 
 #[derive(Debug, Clone)]
@@ -114,6 +116,7 @@ pub fn profile_fingerprint(profile: &Profile) -> String {
         "allowPartialUpstreams": profile.allow_partial_upstreams,
         "sourceIds": profile.source_ids,
         "enabledTools": profile.enabled_tools,
+        "apiKeyToolAllowlist": profile.api_key_tool_allowlist,
         "transforms": profile.transforms,
     });
     let s = serde_json::to_string(&v).expect("profile fingerprint json serializes");
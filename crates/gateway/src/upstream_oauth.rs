@@ -0,0 +1,1290 @@
+//! The Gateway as OAuth client to upstream MCP servers (Mode 3).
+//!
+//! Tenants register an OAuth client per upstream. Each data-plane principal (API key or OIDC
+//! subject) links their own upstream account once via the authorization code flow with PKCE; the
+//! Gateway then attaches that principal's access token to upstream requests and refreshes it
+//! transparently. Tokens and PKCE verifiers are encrypted at rest with the tenant secrets keyring.
+
+use crate::mcp::McpState;
+use crate::session_token::{TokenAuthV1, TokenOidcV1};
+use crate::store::{Store, UpstreamOAuthClient, UpstreamOAuthLinkState, UpstreamOAuthToken};
+use axum::extract::{Query, State};
+use axum::http::{HeaderMap, StatusCode};
+use axum::response::{IntoResponse as _, Response};
+use base64::Engine as _;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use parking_lot::{Mutex, RwLock};
+use rand_core::{OsRng, TryRngCore as _};
+use serde::Deserialize;
+use sha2::{Digest as _, Sha256};
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+/// Data-plane path of the OAuth redirect endpoint (registered as the client's redirect URI).
+pub const CALLBACK_PATH: &str = "/oauth/upstream/callback";
+
+/// Data-plane path that starts a link attempt in the user's browser (the link URL handed out).
+pub const START_PATH: &str = "/oauth/upstream/start";
+
+/// Cookie binding a link attempt to the browser that started it (value: the state hash).
+const LINK_COOKIE: &str = "unrelated_upstream_oauth_link";
+
+/// How long a link attempt (authorization URL) stays valid.
+pub const LINK_TTL_SECS: i64 = 600;
+
+/// Refresh access tokens this long before they expire.
+const REFRESH_SKEW_SECS: i64 = 60;
+
+/// How long decrypted tokens are reused before re-reading the store (bounds staleness after an
+/// unlink or a refresh on another replica).
+const TOKEN_CACHE_TTL: Duration = Duration::from_secs(30);
+
+const DISCOVERY_CACHE_TTL: Duration = Duration::from_secs(600);
+const OAUTH_HTTP_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Principal key for an API key.
+#[must_use]
+pub fn api_key_principal(api_key_id: &str) -> String {
+    format!("apiKey:{api_key_id}")
+}
+
+/// Principal key for an OIDC subject.
+#[must_use]
+pub fn oidc_principal(issuer: &str, subject: &str) -> String {
+    format!("oidc:{issuer}|{subject}")
+}
+
+/// Principal key for the caller of a data-plane session (`None` for unauthenticated profiles).
+#[must_use]
+pub fn principal_key(auth: Option<&TokenAuthV1>, oidc: Option<&TokenOidcV1>) -> Option<String> {
+    if let Some(oidc) = oidc {
+        return Some(oidc_principal(&oidc.issuer, &oidc.subject));
+    }
+    auth.map(|a| api_key_principal(&a.api_key_id))
+}
+
+fn random_b64url(len: usize) -> anyhow::Result<String> {
+    let mut bytes = vec![0u8; len];
+    OsRng
+        .try_fill_bytes(&mut bytes)
+        .map_err(|e| anyhow::anyhow!("OS RNG failure: {e}"))?;
+    Ok(URL_SAFE_NO_PAD.encode(bytes))
+}
+
+/// PKCE `S256` challenge for a verifier (RFC 7636 §4.2).
+#[must_use]
+pub fn pkce_challenge(verifier: &str) -> String {
+    URL_SAFE_NO_PAD.encode(Sha256::digest(verifier.as_bytes()))
+}
+
+/// Storage key for an OAuth `state` value (the raw value is never persisted).
+#[must_use]
+pub fn state_hash(state: &str) -> String {
+    hex::encode(Sha256::digest(state.as_bytes()))
+}
+
+fn now_unix() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| i64::try_from(d.as_secs()).unwrap_or(i64::MAX))
+}
+
+/// The MCP endpoint URL an upstream's tokens are minted for (RFC 8707 resource indicator).
+///
+/// Endpoints of one upstream are replicas of the same server, so the first one identifies it.
+#[must_use]
+pub fn upstream_resource(upstream: &crate::store::Upstream) -> Option<String> {
+    upstream.endpoints.first().map(|e| e.url.clone())
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OAuthEndpoints {
+    pub authorization_endpoint: String,
+    pub token_endpoint: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct ProtectedResourceDocument {
+    #[serde(default)]
+    authorization_servers: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct AuthorizationServerDocument {
+    authorization_endpoint: Option<String>,
+    token_endpoint: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TokenResponse {
+    access_token: String,
+    #[serde(default)]
+    token_type: Option<String>,
+    #[serde(default)]
+    refresh_token: Option<String>,
+    #[serde(default)]
+    expires_in: Option<i64>,
+}
+
+/// Well-known URL with the path of `url` appended (RFC 8414 §3.1 / RFC 9728 §3.1 layout).
+fn well_known_url(url: &reqwest::Url, suffix: &str, with_path: bool) -> String {
+    let origin = url.origin().ascii_serialization();
+    let path = url.path().trim_end_matches('/');
+    if with_path && !path.is_empty() {
+        format!("{origin}/.well-known/{suffix}{path}")
+    } else {
+        format!("{origin}/.well-known/{suffix}")
+    }
+}
+
+fn check_endpoint_policy(field: &str, url: &str) -> anyhow::Result<()> {
+    crate::outbound_safety::check_upstream_https_policy(url)
+        .map_err(|e| anyhow::anyhow!("{field}: {e}"))
+}
+
+/// Build the authorization request URL for the code flow with PKCE.
+pub fn authorization_url(
+    endpoints: &OAuthEndpoints,
+    client: &UpstreamOAuthClient,
+    redirect_uri: &str,
+    state: &str,
+    code_challenge: &str,
+    resource: &str,
+) -> anyhow::Result<String> {
+    let mut url = reqwest::Url::parse(&endpoints.authorization_endpoint)
+        .map_err(|e| anyhow::anyhow!("invalid authorization endpoint: {e}"))?;
+    {
+        let mut q = url.query_pairs_mut();
+        q.append_pair("response_type", "code")
+            .append_pair("client_id", &client.client_id)
+            .append_pair("redirect_uri", redirect_uri)
+            .append_pair("state", state)
+            .append_pair("code_challenge", code_challenge)
+            .append_pair("code_challenge_method", "S256")
+            .append_pair("resource", resource);
+        if !client.scopes.is_empty() {
+            q.append_pair("scope", &client.scopes.join(" "));
+        }
+    }
+    Ok(url.into())
+}
+
+/// Link URL for a pending attempt: [`START_PATH`] next to the callback `redirect_uri`.
+fn link_start_url(redirect_uri: &str, state: &str) -> anyhow::Result<String> {
+    let base = redirect_uri
+        .strip_suffix(CALLBACK_PATH)
+        .ok_or_else(|| anyhow::anyhow!("redirect URI must end with {CALLBACK_PATH}"))?;
+    let mut url = reqwest::Url::parse(&format!("{base}{START_PATH}"))
+        .map_err(|e| anyhow::anyhow!("invalid redirect URI: {e}"))?;
+    url.query_pairs_mut().append_pair("state", state);
+    Ok(url.into())
+}
+
+/// `Set-Cookie` value binding a link attempt to the current browser (`None` clears it).
+fn link_cookie(state_hash: Option<&str>, secure: bool) -> String {
+    let (value, max_age) = state_hash.map_or(("", 0), |h| (h, LINK_TTL_SECS));
+    let secure = if secure { "; Secure" } else { "" };
+    format!(
+        "{LINK_COOKIE}={value}; Path=/oauth/upstream; Max-Age={max_age}; HttpOnly; SameSite=Lax{secure}"
+    )
+}
+
+/// Whether the request carries the cookie set by [`start`] for this attempt.
+fn has_link_cookie(headers: &HeaderMap, state_hash: &str) -> bool {
+    headers
+        .get_all(axum::http::header::COOKIE)
+        .iter()
+        .filter_map(|v| v.to_str().ok())
+        .flat_map(|v| v.split(';'))
+        .filter_map(|c| c.trim().split_once('='))
+        .any(|(name, value)| name == LINK_COOKIE && value == state_hash)
+}
+
+/// `(tenant_id, upstream_id, principal)`.
+type PrincipalKey = (String, String, String);
+
+type RefreshLocks = Mutex<HashMap<PrincipalKey, Arc<tokio::sync::Mutex<()>>>>;
+
+/// A principal's refresh lock; the map entry is removed with its last holder (also when the
+/// request is cancelled mid-refresh), so the map only holds in-flight refreshes.
+struct RefreshLock<'a> {
+    locks: &'a RefreshLocks,
+    key: &'a PrincipalKey,
+    lock: Arc<tokio::sync::Mutex<()>>,
+}
+
+impl<'a> RefreshLock<'a> {
+    fn acquire(locks: &'a RefreshLocks, key: &'a PrincipalKey) -> Self {
+        let lock = locks.lock().entry(key.clone()).or_default().clone();
+        Self { locks, key, lock }
+    }
+}
+
+impl Drop for RefreshLock<'_> {
+    fn drop(&mut self) {
+        let mut locks = self.locks.lock();
+        // One reference is the map's, one is ours; any other belongs to a waiting request.
+        if Arc::strong_count(&self.lock) <= 2
+            && locks
+                .get(self.key)
+                .is_some_and(|l| Arc::ptr_eq(l, &self.lock))
+        {
+            locks.remove(self.key);
+        }
+    }
+}
+
+/// OAuth client runtime: discovery, code exchange, and token refresh for upstream MCP servers.
+pub struct UpstreamOAuthManager {
+    http: reqwest::Client,
+    tokens: RwLock<HashMap<PrincipalKey, (Instant, UpstreamOAuthToken)>>,
+    refresh_locks: RefreshLocks,
+    discovery: RwLock<HashMap<String, (Instant, OAuthEndpoints)>>,
+}
+
+impl UpstreamOAuthManager {
+    #[must_use]
+    pub fn new(http: reqwest::Client) -> Self {
+        Self {
+            http,
+            tokens: RwLock::new(HashMap::new()),
+            refresh_locks: Mutex::new(HashMap::new()),
+            discovery: RwLock::new(HashMap::new()),
+        }
+    }
+
+    async fn fetch_json<T: serde::de::DeserializeOwned>(&self, url: &str) -> anyhow::Result<T> {
+        let safety = crate::outbound_safety::gateway_outbound_http_safety();
+        crate::outbound_safety::check_url_allowed(&safety, url)
+            .await
+            .map_err(|e| anyhow::anyhow!("url not allowed: {e}"))?;
+        let resp = self
+            .http
+            .get(url)
+            .timeout(OAUTH_HTTP_TIMEOUT)
+            .header(reqwest::header::ACCEPT, "application/json")
+            .send()
+            .await?;
+        if !resp.status().is_success() {
+            anyhow::bail!("GET {url} returned {}", resp.status());
+        }
+        Ok(resp.json().await?)
+    }
+
+    async fn fetch_first<T: serde::de::DeserializeOwned>(
+        &self,
+        candidates: &[String],
+    ) -> anyhow::Result<T> {
+        let mut last_err = anyhow::anyhow!("no metadata candidates");
+        for url in candidates {
+            match self.fetch_json(url).await {
+                Ok(v) => return Ok(v),
+                Err(e) => last_err = e,
+            }
+        }
+        Err(last_err)
+    }
+
+    /// Discover the authorization server for an upstream MCP endpoint.
+    ///
+    /// Follows the MCP authorization spec: protected resource metadata (RFC 9728) names the
+    /// authorization server, whose metadata (RFC 8414, or OpenID discovery) lists the endpoints.
+    /// Servers without resource metadata are assumed to be their own authorization server.
+    async fn discover(&self, resource: &str) -> anyhow::Result<OAuthEndpoints> {
+        if let Some((at, e)) = self.discovery.read().get(resource)
+            && at.elapsed() < DISCOVERY_CACHE_TTL
+        {
+            return Ok(e.clone());
+        }
+
+        let resource_url = reqwest::Url::parse(resource)
+            .map_err(|e| anyhow::anyhow!("invalid upstream URL: {e}"))?;
+        let prm_candidates = vec![
+            well_known_url(&resource_url, "oauth-protected-resource", true),
+            well_known_url(&resource_url, "oauth-protected-resource", false),
+        ];
+        let issuer = match self
+            .fetch_first::<ProtectedResourceDocument>(&prm_candidates)
+            .await
+        {
+            Ok(doc) => doc.authorization_servers.into_iter().next(),
+            Err(e) => {
+                tracing::debug!(error = %e, "upstream has no protected resource metadata");
+                None
+            }
+        }
+        .unwrap_or_else(|| resource_url.origin().ascii_serialization());
+
+        let issuer_url = reqwest::Url::parse(&issuer)
+            .map_err(|e| anyhow::anyhow!("invalid authorization server '{issuer}': {e}"))?;
+        let mut as_candidates = vec![
+            well_known_url(&issuer_url, "oauth-authorization-server", true),
+            well_known_url(&issuer_url, "openid-configuration", true),
+            format!(
+                "{}/.well-known/openid-configuration",
+                issuer.trim_end_matches('/')
+            ),
+        ];
+        as_candidates.dedup();
+        let doc = self
+            .fetch_first::<AuthorizationServerDocument>(&as_candidates)
+            .await
+            .map_err(|e| anyhow::anyhow!("authorization server metadata discovery failed: {e}"))?;
+        let endpoints = OAuthEndpoints {
+            authorization_endpoint: doc.authorization_endpoint.ok_or_else(|| {
+                anyhow::anyhow!("authorization server metadata lacks authorization_endpoint")
+            })?,
+            token_endpoint: doc.token_endpoint.ok_or_else(|| {
+                anyhow::anyhow!("authorization server metadata lacks token_endpoint")
+            })?,
+        };
+        self.discovery
+            .write()
+            .insert(resource.to_string(), (Instant::now(), endpoints.clone()));
+        Ok(endpoints)
+    }
+
+    /// Effective endpoints: configured overrides win, the rest is discovered.
+    pub async fn endpoints(
+        &self,
+        client: &UpstreamOAuthClient,
+        resource: &str,
+    ) -> anyhow::Result<OAuthEndpoints> {
+        let endpoints = match (
+            client.authorization_endpoint.as_deref(),
+            client.token_endpoint.as_deref(),
+        ) {
+            (Some(a), Some(t)) => OAuthEndpoints {
+                authorization_endpoint: a.to_string(),
+                token_endpoint: t.to_string(),
+            },
+            (a, t) => {
+                let discovered = self.discover(resource).await?;
+                OAuthEndpoints {
+                    authorization_endpoint: a
+                        .map_or(discovered.authorization_endpoint, str::to_string),
+                    token_endpoint: t.map_or(discovered.token_endpoint, str::to_string),
+                }
+            }
+        };
+        check_endpoint_policy("authorization endpoint", &endpoints.authorization_endpoint)?;
+        check_endpoint_policy("token endpoint", &endpoints.token_endpoint)?;
+        Ok(endpoints)
+    }
+
+    /// Start linking `principal`: persist the PKCE verifier and return the link URL
+    /// ([`START_PATH`], which redirects the user's browser to the authorization server).
+    pub async fn begin_link(
+        &self,
+        store: &dyn Store,
+        tenant_id: &str,
+        client: &UpstreamOAuthClient,
+        resource: &str,
+        principal: &str,
+        redirect_uri: &str,
+    ) -> anyhow::Result<(String, i64)> {
+        // Resolve endpoints up front so misconfiguration fails here, not in the user's browser.
+        self.endpoints(client, resource).await?;
+        let verifier = random_b64url(32)?;
+        let state = random_b64url(32)?;
+        let expires_at_unix = now_unix() + LINK_TTL_SECS;
+        store
+            .put_upstream_oauth_link_state(&UpstreamOAuthLinkState {
+                state_hash: state_hash(&state),
+                tenant_id: tenant_id.to_string(),
+                upstream_id: client.upstream_id.clone(),
+                principal: principal.to_string(),
+                code_verifier: verifier.clone(),
+                redirect_uri: redirect_uri.to_string(),
+                expires_at_unix,
+            })
+            .await?;
+        Ok((link_start_url(redirect_uri, &state)?, expires_at_unix))
+    }
+
+    /// Authorization URL for a pending link attempt (`None` when unknown or expired).
+    pub async fn link_authorization_url(
+        &self,
+        store: &dyn Store,
+        state: &str,
+    ) -> anyhow::Result<Option<(String, UpstreamOAuthLinkState)>> {
+        let Some(link) = store
+            .get_upstream_oauth_link_state(&state_hash(state))
+            .await?
+        else {
+            return Ok(None);
+        };
+        let client = store
+            .get_upstream_oauth_client(&link.tenant_id, &link.upstream_id)
+            .await?
+            .filter(|c| c.enabled)
+            .ok_or_else(|| anyhow::anyhow!("upstream OAuth client is not configured"))?;
+        let resource = self.resource(store, &link.upstream_id).await?;
+        let endpoints = self.endpoints(&client, &resource).await?;
+        let url = authorization_url(
+            &endpoints,
+            &client,
+            &link.redirect_uri,
+            state,
+            &pkce_challenge(&link.code_verifier),
+            &resource,
+        )?;
+        Ok(Some((url, link)))
+    }
+
+    async fn token_request(
+        &self,
+        store: &dyn Store,
+        tenant_id: &str,
+        client: &UpstreamOAuthClient,
+        token_endpoint: &str,
+        mut form: Vec<(&'static str, String)>,
+    ) -> anyhow::Result<UpstreamOAuthToken> {
+        let safety = crate::outbound_safety::gateway_outbound_http_safety();
+        crate::outbound_safety::check_url_allowed(&safety, token_endpoint)
+            .await
+            .map_err(|e| anyhow::anyhow!("token endpoint not allowed: {e}"))?;
+
+        let mut req = self.http.post(token_endpoint).timeout(OAUTH_HTTP_TIMEOUT);
+        if let Some(secret_ref) = client.client_secret_ref.as_deref() {
+            let secret = store
+                .get_tenant_secret_value(tenant_id, secret_ref)
+                .await?
+                .ok_or_else(|| anyhow::anyhow!("client secret '{secret_ref}' not found"))?;
+            req = req.basic_auth(&client.client_id, Some(secret));
+        } else {
+            form.push(("client_id", client.client_id.clone()));
+        }
+        let resp = req
+            .header(reqwest::header::ACCEPT, "application/json")
+            .form(&form)
+            .send()
+            .await?;
+        let status = resp.status();
+        if !status.is_success() {
+            // Token endpoint errors are small JSON objects (RFC 6749 §5.2); never echo secrets.
+            let body: serde_json::Value = resp.json().await.unwrap_or_default();
+            let error = body
+                .get("error")
+                .and_then(serde_json::Value::as_str)
+                .unwrap_or("unknown_error");
+            anyhow::bail!("token endpoint returned {status} ({error})");
+        }
+        let t: TokenResponse = resp.json().await?;
+        if let Some(tt) = t.token_type.as_deref()
+            && !tt.eq_ignore_ascii_case("bearer")
+        {
+            anyhow::bail!("unsupported token_type '{tt}'");
+        }
+        Ok(UpstreamOAuthToken {
+            access_token: t.access_token,
+            refresh_token: t.refresh_token,
+            expires_at_unix: t.expires_in.map(|s| now_unix() + s),
+        })
+    }
+
+    /// Finish a link attempt from the redirect callback; returns the consumed link state.
+    pub async fn complete_link(
+        &self,
+        store: &dyn Store,
+        code: &str,
+        state: &str,
+    ) -> anyhow::Result<Option<UpstreamOAuthLinkState>> {
+        let Some(link) = store
+            .take_upstream_oauth_link_state(&state_hash(state))
+            .await?
+        else {
+            return Ok(None);
+        };
+        let client = store
+            .get_upstream_oauth_client(&link.tenant_id, &link.upstream_id)
+            .await?
+            .filter(|c| c.enabled)
+            .ok_or_else(|| anyhow::anyhow!("upstream OAuth client is not configured"))?;
+        let resource = self.resource(store, &link.upstream_id).await?;
+        let endpoints = self.endpoints(&client, &resource).await?;
+        let token = self
+            .token_request(
+                store,
+                &link.tenant_id,
+                &client,
+                &endpoints.token_endpoint,
+                vec![
+                    ("grant_type", "authorization_code".to_string()),
+                    ("code", code.to_string()),
+                    ("redirect_uri", link.redirect_uri.clone()),
+                    ("code_verifier", link.code_verifier.clone()),
+                    ("resource", resource),
+                ],
+            )
+            .await?;
+        store
+            .put_upstream_oauth_token(&link.tenant_id, &link.upstream_id, &link.principal, &token)
+            .await?;
+        self.forget(&link.tenant_id, &link.upstream_id, &link.principal);
+        Ok(Some(link))
+    }
+
+    async fn resource(&self, store: &dyn Store, upstream_id: &str) -> anyhow::Result<String> {
+        store
+            .get_upstream(upstream_id)
+            .await?
+            .as_ref()
+            .and_then(upstream_resource)
+            .ok_or_else(|| anyhow::anyhow!("upstream '{upstream_id}' has no endpoints"))
+    }
+
+    /// Current access token for `principal`, refreshing it when it is about to expire.
+    ///
+    /// `Ok(None)` means the principal has not linked an account (or the link can no longer be
+    /// refreshed) and must go through the link flow again.
+    pub async fn access_token(
+        &self,
+        store: &dyn Store,
+        tenant_id: &str,
+        upstream_id: &str,
+        principal: &str,
+    ) -> anyhow::Result<Option<String>> {
+        let key = (
+            tenant_id.to_string(),
+            upstream_id.to_string(),
+            principal.to_string(),
+        );
+        let fresh = |t: &UpstreamOAuthToken| {
+            t.expires_at_unix
+                .is_none_or(|exp| exp - REFRESH_SKEW_SECS > now_unix())
+        };
+        if let Some((at, t)) = self.tokens.read().get(&key)
+            && at.elapsed() < TOKEN_CACHE_TTL
+            && fresh(t)
+        {
+            return Ok(Some(t.access_token.clone()));
+        }
+
+        let Some(token) = store
+            .get_upstream_oauth_token(tenant_id, upstream_id, principal)
+            .await?
+        else {
+            self.tokens.write().remove(&key);
+            return Ok(None);
+        };
+        let token = if fresh(&token) {
+            token
+        } else {
+            // Serialize refreshes per principal: refresh tokens are often single-use.
+            let lock = RefreshLock::acquire(&self.refresh_locks, &key);
+            let _guard = lock.lock.lock().await;
+            let Some(token) = self
+                .refresh(store, tenant_id, upstream_id, principal, &fresh)
+                .await?
+            else {
+                return Ok(None);
+            };
+            token
+        };
+        let access_token = token.access_token.clone();
+        self.tokens.write().insert(key, (Instant::now(), token));
+        Ok(Some(access_token))
+    }
+
+    /// Refresh the stored token unless another request already did (caller holds the lock).
+    async fn refresh(
+        &self,
+        store: &dyn Store,
+        tenant_id: &str,
+        upstream_id: &str,
+        principal: &str,
+        fresh: &impl Fn(&UpstreamOAuthToken) -> bool,
+    ) -> anyhow::Result<Option<UpstreamOAuthToken>> {
+        let Some(current) = store
+            .get_upstream_oauth_token(tenant_id, upstream_id, principal)
+            .await?
+        else {
+            return Ok(None);
+        };
+        if fresh(&current) {
+            return Ok(Some(current));
+        }
+        let Some(refresh_token) = current.refresh_token.clone() else {
+            return Ok(None);
+        };
+        let Some(client) = store
+            .get_upstream_oauth_client(tenant_id, upstream_id)
+            .await?
+            .filter(|c| c.enabled)
+        else {
+            return Ok(None);
+        };
+        let resource = self.resource(store, upstream_id).await?;
+        let endpoints = self.endpoints(&client, &resource).await?;
+        let mut refreshed = self
+            .token_request(
+                store,
+                tenant_id,
+                &client,
+                &endpoints.token_endpoint,
+                vec![
+                    ("grant_type", "refresh_token".to_string()),
+                    ("refresh_token", refresh_token.clone()),
+                    ("resource", resource),
+                ],
+            )
+            .await?;
+        // Servers that don't rotate refresh tokens omit them from the response.
+        if refreshed.refresh_token.is_none() {
+            refreshed.refresh_token = Some(refresh_token);
+        }
+        store
+            .put_upstream_oauth_token(tenant_id, upstream_id, principal, &refreshed)
+            .await?;
+        Ok(Some(refreshed))
+    }
+
+    /// Drop cached tokens for a principal (after linking or unlinking on this replica).
+    pub fn forget(&self, tenant_id: &str, upstream_id: &str, principal: &str) {
+        self.tokens.write().remove(&(
+            tenant_id.to_string(),
+            upstream_id.to_string(),
+            principal.to_string(),
+        ));
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct StartQuery {
+    #[serde(default)]
+    state: Option<String>,
+}
+
+/// `GET /oauth/upstream/start`: the link URL. Binds the attempt to this browser with a cookie and
+/// redirects to the authorization server; the callback only completes attempts carrying it.
+pub async fn start(State(state): State<Arc<McpState>>, Query(q): Query<StartQuery>) -> Response {
+    let Some(link_state) = q.state.as_deref() else {
+        return (StatusCode::BAD_REQUEST, "missing state").into_response();
+    };
+    match state
+        .upstream_oauth
+        .link_authorization_url(state.store.as_ref(), link_state)
+        .await
+    {
+        Ok(Some((url, link))) => (
+            StatusCode::FOUND,
+            [
+                (axum::http::header::LOCATION, url),
+                (
+                    axum::http::header::SET_COOKIE,
+                    link_cookie(
+                        Some(&link.state_hash),
+                        link.redirect_uri.starts_with("https://"),
+                    ),
+                ),
+            ],
+        )
+            .into_response(),
+        Ok(None) => (StatusCode::BAD_REQUEST, "unknown or expired link attempt").into_response(),
+        Err(e) => {
+            tracing::warn!(error = %e, "upstream OAuth link start failed");
+            (
+                StatusCode::BAD_GATEWAY,
+                format!("Account linking failed: {e}"),
+            )
+                .into_response()
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CallbackQuery {
+    #[serde(default)]
+    code: Option<String>,
+    #[serde(default)]
+    state: Option<String>,
+    #[serde(default)]
+    error: Option<String>,
+}
+
+/// Drop a pending link attempt after the provider reported an error; returns whether one was
+/// consumed. Like completion, only the browser that started the attempt may cancel it, so a
+/// leaked `state` can't be used to abort someone else's link.
+async fn cancel_link(store: &dyn Store, headers: &HeaderMap, link_state: &str) -> bool {
+    let hash = state_hash(link_state);
+    if !has_link_cookie(headers, &hash) {
+        return false;
+    }
+    matches!(
+        store.take_upstream_oauth_link_state(&hash).await,
+        Ok(Some(_))
+    )
+}
+
+/// `GET /oauth/upstream/callback`: OAuth redirect endpoint for account linking.
+pub async fn callback(
+    State(state): State<Arc<McpState>>,
+    headers: HeaderMap,
+    Query(q): Query<CallbackQuery>,
+) -> Response {
+    let started = Instant::now();
+    if let Some(error) = q.error.as_deref() {
+        // Consume the pending attempt so it can't be completed later.
+        if let Some(s) = q.state.as_deref() {
+            cancel_link(state.store.as_ref(), &headers, s).await;
+        }
+        return (
+            StatusCode::BAD_REQUEST,
+            format!("Account linking was not completed: {error}"),
+        )
+            .into_response();
+    }
+    let (Some(code), Some(link_state)) = (q.code.as_deref(), q.state.as_deref()) else {
+        return (StatusCode::BAD_REQUEST, "missing code or state").into_response();
+    };
+    // Only the browser that opened the link URL may complete the attempt: a leaked
+    // authorization URL must not link someone else's upstream account to this principal.
+    if !has_link_cookie(&headers, &state_hash(link_state)) {
+        return (
+            StatusCode::BAD_REQUEST,
+            "link attempt was not started in this browser; open the link URL again",
+        )
+            .into_response();
+    }
+
+    let outcome = state
+        .upstream_oauth
+        .complete_link(state.store.as_ref(), code, link_state)
+        .await;
+    let (status, body, link) = match outcome {
+        Ok(Some(link)) => (
+            StatusCode::OK,
+            "Account linked. You can close this window.".to_string(),
+            Some(link),
+        ),
+        Ok(None) => (
+            StatusCode::BAD_REQUEST,
+            "unknown or expired link attempt".to_string(),
+            None,
+        ),
+        Err(e) => {
+            tracing::warn!(error = %e, "upstream OAuth link failed");
+            (
+                StatusCode::BAD_GATEWAY,
+                format!("Account linking failed: {e}"),
+                None,
+            )
+        }
+    };
+
+    if let Some(link) = &link {
+        state
+            .audit
+            .record(crate::audit::http_event(crate::audit::HttpAuditEvent {
+                tenant_id: link.tenant_id.clone(),
+                actor: crate::audit::AuditActor::default(),
+                action: "upstream_oauth.link_complete",
+                http_method: "GET",
+                http_route: CALLBACK_PATH,
+                status_code: i32::from(status.as_u16()),
+                ok: true,
+                elapsed: started.elapsed(),
+                meta: serde_json::json!({
+                    "upstream_id": link.upstream_id,
+                    "principal": link.principal,
+                }),
+                error: None,
+            }))
+            .await;
+    }
+
+    (
+        status,
+        [(
+            axum::http::header::SET_COOKIE,
+            link_cookie(
+                None,
+                link.as_ref()
+                    .is_some_and(|l| l.redirect_uri.starts_with("https://")),
+            ),
+        )],
+        body,
+    )
+        .into_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn client() -> UpstreamOAuthClient {
+        UpstreamOAuthClient {
+            upstream_id: "github".to_string(),
+            client_id: "gw-client".to_string(),
+            client_secret_ref: None,
+            scopes: vec!["repo".to_string(), "read:user".to_string()],
+            authorization_endpoint: None,
+            token_endpoint: None,
+            enabled: true,
+        }
+    }
+
+    #[test]
+    fn principal_key_prefers_oidc_subject() {
+        let auth = TokenAuthV1 {
+            tenant_id: "t1".to_string(),
+            api_key_id: "k1".to_string(),
+            tools: vec![],
+            allowed_cidrs: vec![],
+        };
+        let oidc = TokenOidcV1 {
+            issuer: "https://idp.example".to_string(),
+            subject: "u1".to_string(),
+        };
+        assert_eq!(
+            principal_key(Some(&auth), None).as_deref(),
+            Some("apiKey:k1")
+        );
+        assert_eq!(
+            principal_key(None, Some(&oidc)).as_deref(),
+            Some("oidc:https://idp.example|u1")
+        );
+        assert!(principal_key(None, None).is_none());
+    }
+
+    #[test]
+    fn pkce_challenge_is_unpadded_sha256() {
+        let c = pkce_challenge("verifier");
+        assert_eq!(c.len(), 43);
+        assert!(!c.contains('=') && !c.contains('+') && !c.contains('/'));
+        assert_ne!(c, pkce_challenge("verifier2"));
+        assert_eq!(state_hash("s").len(), 64);
+    }
+
+    #[test]
+    fn authorization_url_carries_pkce_resource_and_scopes() {
+        let endpoints = OAuthEndpoints {
+            authorization_endpoint: "https://auth.example/authorize?tenant=x".to_string(),
+            token_endpoint: "https://auth.example/token".to_string(),
+        };
+        let url = authorization_url(
+            &endpoints,
+            &client(),
+            "https://gw.example/oauth/upstream/callback",
+            "st",
+            "ch",
+            "https://mcp.example/mcp",
+        )
+        .unwrap();
+        let parsed = reqwest::Url::parse(&url).unwrap();
+        let q: HashMap<_, _> = parsed.query_pairs().into_owned().collect();
+        assert_eq!(q["tenant"], "x");
+        assert_eq!(q["response_type"], "code");
+        assert_eq!(q["client_id"], "gw-client");
+        assert_eq!(q["code_challenge"], "ch");
+        assert_eq!(q["code_challenge_method"], "S256");
+        assert_eq!(q["resource"], "https://mcp.example/mcp");
+        assert_eq!(q["scope"], "repo read:user");
+    }
+
+    #[test]
+    fn well_known_urls_insert_path_after_prefix() {
+        let u = reqwest::Url::parse("https://mcp.example/api/mcp/").unwrap();
+        assert_eq!(
+            well_known_url(&u, "oauth-protected-resource", true),
+            "https://mcp.example/.well-known/oauth-protected-resource/api/mcp"
+        );
+        assert_eq!(
+            well_known_url(&u, "oauth-protected-resource", false),
+            "https://mcp.example/.well-known/oauth-protected-resource"
+        );
+    }
+
+    /// In-memory store for the token/link paths (everything else is unused here).
+    #[derive(Default)]
+    struct OAuthTestStore {
+        client: Option<UpstreamOAuthClient>,
+        upstream_url: String,
+        tokens: Mutex<HashMap<String, UpstreamOAuthToken>>,
+        links: Mutex<HashMap<String, UpstreamOAuthLinkState>>,
+    }
+
+    #[async_trait::async_trait]
+    impl Store for OAuthTestStore {
+        async fn get_profile(
+            &self,
+            _profile_id: &str,
+        ) -> anyhow::Result<Option<crate::store::Profile>> {
+            Ok(None)
+        }
+        async fn get_upstream(
+            &self,
+            _upstream_id: &str,
+        ) -> anyhow::Result<Option<crate::store::Upstream>> {
+            Ok(Some(crate::store::Upstream {
+                endpoints: vec![crate::store::UpstreamEndpoint {
+                    id: "e1".to_string(),
+                    url: self.upstream_url.clone(),
+                    auth: None,
+                }],
+            }))
+        }
+        async fn get_tenant_tool_source(
+            &self,
+            _tenant_id: &str,
+            _source_id: &str,
+        ) -> anyhow::Result<Option<crate::store::TenantToolSource>> {
+            Ok(None)
+        }
+        async fn get_tenant_secret_value(
+            &self,
+            _tenant_id: &str,
+            _name: &str,
+        ) -> anyhow::Result<Option<String>> {
+            Ok(None)
+        }
+        async fn get_tenant_transport_limits(
+            &self,
+            _tenant_id: &str,
+        ) -> anyhow::Result<Option<crate::store::TransportLimitsSettings>> {
+            Ok(None)
+        }
+        async fn get_tenant_allowed_cidrs(&self, _tenant_id: &str) -> anyhow::Result<Vec<String>> {
+            Ok(Vec::new())
+        }
+        async fn authenticate_api_key(
+            &self,
+            _tenant_id: &str,
+            _profile_id: &str,
+            _secret: &str,
+        ) -> anyhow::Result<Option<crate::store::ApiKeyAuth>> {
+            Ok(None)
+        }
+        async fn is_api_key_active(
+            &self,
+            _tenant_id: &str,
+            _api_key_id: &str,
+        ) -> anyhow::Result<bool> {
+            Ok(false)
+        }
+        async fn touch_api_key(&self, _tenant_id: &str, _api_key_id: &str) -> anyhow::Result<()> {
+            Ok(())
+        }
+        async fn record_tool_call_attempt(
+            &self,
+            _tenant_id: &str,
+            _api_key_id: &str,
+        ) -> anyhow::Result<()> {
+            Ok(())
+        }
+        async fn check_and_apply_tool_call_limits(
+            &self,
+            _tenant_id: &str,
+            _profile_id: &str,
+            _api_key_id: &str,
+            _rate_limit_tool_calls_per_minute: Option<i64>,
+            _quota_tool_calls: Option<i64>,
+        ) -> anyhow::Result<Option<crate::store::ToolCallLimitRejection>> {
+            Ok(None)
+        }
+        async fn is_oidc_principal_allowed(
+            &self,
+            _tenant_id: &str,
+            _profile_id: &str,
+            _issuer: &str,
+            _subject: &str,
+        ) -> anyhow::Result<bool> {
+            Ok(false)
+        }
+        async fn list_enabled_oidc_claim_rules(
+            &self,
+            _tenant_id: &str,
+            _issuer: &str,
+        ) -> anyhow::Result<Vec<crate::store::OidcClaimRule>> {
+            Ok(Vec::new())
+        }
+        async fn get_tenant_oidc_issuer(
+            &self,
+            _tenant_id: &str,
+            _issuer: &str,
+        ) -> anyhow::Result<Option<crate::store::TenantOidcIssuer>> {
+            Ok(None)
+        }
+        async fn list_enabled_tenant_oidc_issuers(
+            &self,
+            _tenant_id: &str,
+        ) -> anyhow::Result<Vec<crate::store::TenantOidcIssuer>> {
+            Ok(Vec::new())
+        }
+        async fn get_upstream_oauth_client(
+            &self,
+            _tenant_id: &str,
+            _upstream_id: &str,
+        ) -> anyhow::Result<Option<UpstreamOAuthClient>> {
+            Ok(self.client.clone())
+        }
+        async fn get_upstream_oauth_token(
+            &self,
+            _tenant_id: &str,
+            _upstream_id: &str,
+            principal: &str,
+        ) -> anyhow::Result<Option<UpstreamOAuthToken>> {
+            Ok(self.tokens.lock().get(principal).cloned())
+        }
+        async fn put_upstream_oauth_token(
+            &self,
+            _tenant_id: &str,
+            _upstream_id: &str,
+            principal: &str,
+            token: &UpstreamOAuthToken,
+        ) -> anyhow::Result<()> {
+            self.tokens
+                .lock()
+                .insert(principal.to_string(), token.clone());
+            Ok(())
+        }
+        async fn put_upstream_oauth_link_state(
+            &self,
+            link: &UpstreamOAuthLinkState,
+        ) -> anyhow::Result<()> {
+            self.links
+                .lock()
+                .insert(link.state_hash.clone(), link.clone());
+            Ok(())
+        }
+        async fn get_upstream_oauth_link_state(
+            &self,
+            state_hash: &str,
+        ) -> anyhow::Result<Option<UpstreamOAuthLinkState>> {
+            Ok(self.links.lock().get(state_hash).cloned())
+        }
+        async fn take_upstream_oauth_link_state(
+            &self,
+            state_hash: &str,
+        ) -> anyhow::Result<Option<UpstreamOAuthLinkState>> {
+            Ok(self.links.lock().remove(state_hash))
+        }
+    }
+
+    /// Token endpoint answering both grants; counts requests per grant type.
+    async fn start_token_endpoint() -> (String, Arc<Mutex<Vec<HashMap<String, String>>>>) {
+        let requests: Arc<Mutex<Vec<HashMap<String, String>>>> = Arc::default();
+        let seen = requests.clone();
+        let app = axum::Router::new().route(
+            "/token",
+            axum::routing::post(
+                move |axum::Form(form): axum::Form<HashMap<String, String>>| {
+                    let seen = seen.clone();
+                    async move {
+                        let n = {
+                            let mut seen = seen.lock();
+                            seen.push(form);
+                            seen.len()
+                        };
+                        // Slow enough for concurrent refreshes to overlap.
+                        tokio::time::sleep(Duration::from_millis(50)).await;
+                        axum::Json(serde_json::json!({
+                            "access_token": format!("access-{n}"),
+                            "token_type": "Bearer",
+                            "expires_in": 3600
+                        }))
+                    }
+                },
+            ),
+        );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
+            .await
+            .expect("bind");
+        let base = format!("http://{}", listener.local_addr().expect("addr"));
+        tokio::spawn(async move { axum::serve(listener, app).await });
+        (base, requests)
+    }
+
+    fn test_store(base: &str) -> OAuthTestStore {
+        OAuthTestStore {
+            client: Some(UpstreamOAuthClient {
+                authorization_endpoint: Some(format!("{base}/authorize")),
+                token_endpoint: Some(format!("{base}/token")),
+                ..client()
+            }),
+            upstream_url: format!("{base}/mcp"),
+            ..OAuthTestStore::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn access_token_refreshes_once_and_drops_the_refresh_lock() {
+        let (base, requests) = start_token_endpoint().await;
+        let store = test_store(&base);
+        store.tokens.lock().insert(
+            "apiKey:k1".to_string(),
+            UpstreamOAuthToken {
+                access_token: "stale".to_string(),
+                refresh_token: Some("r1".to_string()),
+                expires_at_unix: Some(now_unix() - 10),
+            },
+        );
+        let manager = UpstreamOAuthManager::new(reqwest::Client::new());
+
+        let (a, b) = tokio::join!(
+            manager.access_token(&store, "t1", "github", "apiKey:k1"),
+            manager.access_token(&store, "t1", "github", "apiKey:k1"),
+        );
+        assert_eq!(a.expect("ok").as_deref(), Some("access-1"));
+        assert_eq!(b.expect("ok").as_deref(), Some("access-1"));
+
+        let requests = requests.lock().clone();
+        assert_eq!(requests.len(), 1, "concurrent refreshes are serialized");
+        assert_eq!(requests[0]["grant_type"], "refresh_token");
+        assert_eq!(requests[0]["refresh_token"], "r1");
+        // The refresh token is kept when the server does not rotate it.
+        let stored = store.tokens.lock()["apiKey:k1"].clone();
+        assert_eq!(stored.refresh_token.as_deref(), Some("r1"));
+        assert!(manager.refresh_locks.lock().is_empty());
+
+        // Expired without a refresh token: the principal must re-link.
+        store.tokens.lock().insert(
+            "apiKey:k2".to_string(),
+            UpstreamOAuthToken {
+                access_token: "stale".to_string(),
+                refresh_token: None,
+                expires_at_unix: Some(now_unix() - 10),
+            },
+        );
+        let token = manager
+            .access_token(&store, "t1", "github", "apiKey:k2")
+            .await
+            .expect("ok");
+        assert!(token.is_none());
+        assert!(manager.refresh_locks.lock().is_empty());
+    }
+
+    #[tokio::test]
+    async fn link_flow_binds_the_callback_to_the_starting_browser() {
+        let (base, requests) = start_token_endpoint().await;
+        let store = test_store(&base);
+        let manager = UpstreamOAuthManager::new(reqwest::Client::new());
+        let client = store.client.clone().expect("client");
+        let redirect_uri = format!("https://gw.example{CALLBACK_PATH}");
+
+        let (link_url, _) = manager
+            .begin_link(
+                &store,
+                "t1",
+                &client,
+                &format!("{base}/mcp"),
+                "apiKey:k1",
+                &redirect_uri,
+            )
+            .await
+            .expect("begin");
+        let link_url = reqwest::Url::parse(&link_url).expect("url");
+        assert_eq!(link_url.path(), START_PATH);
+        let state = link_url
+            .query_pairs()
+            .find(|(k, _)| k == "state")
+            .map(|(_, v)| v.into_owned())
+            .expect("state");
+
+        // The start endpoint resolves the authorization URL without consuming the attempt.
+        let (authorize, link) = manager
+            .link_authorization_url(&store, &state)
+            .await
+            .expect("ok")
+            .expect("pending link");
+        assert!(authorize.starts_with(&format!("{base}/authorize?")));
+        assert!(authorize.contains(&format!("state={state}")));
+        assert_eq!(link.principal, "apiKey:k1");
+
+        // Only the browser holding the cookie set by the start endpoint may complete it.
+        let cookie = link_cookie(Some(&link.state_hash), true);
+        assert!(cookie.contains("HttpOnly") && cookie.contains("; Secure"));
+        let mut headers = HeaderMap::new();
+        assert!(!has_link_cookie(&headers, &state_hash(&state)));
+        headers.insert(
+            axum::http::header::COOKIE,
+            format!("other=1; {LINK_COOKIE}={}", link.state_hash)
+                .parse()
+                .expect("header"),
+        );
+        assert!(has_link_cookie(&headers, &state_hash(&state)));
+        assert!(!has_link_cookie(&headers, &state_hash("another-attempt")));
+
+        let completed = manager
+            .complete_link(&store, "code-1", &state)
+            .await
+            .expect("ok")
+            .expect("link");
+        assert_eq!(completed.principal, "apiKey:k1");
+        let requests = requests.lock().clone();
+        assert_eq!(requests[0]["grant_type"], "authorization_code");
+        assert_eq!(requests[0]["code_verifier"], link.code_verifier);
+        assert_eq!(
+            store.tokens.lock()["apiKey:k1"].access_token,
+            "access-1".to_string()
+        );
+
+        // Attempts are single use.
+        assert!(
+            manager
+                .complete_link(&store, "code-1", &state)
+                .await
+                .expect("ok")
+                .is_none()
+        );
+        assert!(
+            manager
+                .link_authorization_url(&store, &state)
+                .await
+                .expect("ok")
+                .is_none()
+        );
+    }
+
+    #[tokio::test]
+    async fn provider_errors_only_cancel_the_attempt_from_the_starting_browser() {
+        let store = test_store("http://127.0.0.1:9");
+        let manager = UpstreamOAuthManager::new(reqwest::Client::new());
+        let client = store.client.clone().expect("client");
+        let (link_url, _) = manager
+            .begin_link(
+                &store,
+                "t1",
+                &client,
+                "http://127.0.0.1:9/mcp",
+                "apiKey:k1",
+                &format!("https://gw.example{CALLBACK_PATH}"),
+            )
+            .await
+            .expect("begin");
+        let state = reqwest::Url::parse(&link_url)
+            .expect("url")
+            .query_pairs()
+            .find(|(k, _)| k == "state")
+            .map(|(_, v)| v.into_owned())
+            .expect("state");
+
+        // `?error=...` with a leaked state but without the browser cookie leaves the attempt alone.
+        assert!(!cancel_link(&store, &HeaderMap::new(), &state).await);
+        assert!(
+            manager
+                .link_authorization_url(&store, &state)
+                .await
+                .expect("ok")
+                .is_some()
+        );
+
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            axum::http::header::COOKIE,
+            format!("{LINK_COOKIE}={}", state_hash(&state))
+                .parse()
+                .expect("header"),
+        );
+        assert!(cancel_link(&store, &headers, &state).await);
+        assert!(
+            manager
+                .link_authorization_url(&store, &state)
+                .await
+                .expect("ok")
+                .is_none()
+        );
+    }
+}
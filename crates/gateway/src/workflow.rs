@@ -0,0 +1,624 @@
+//! Gateway-local workflow tools (Mode 3 tenant tool source kind `workflow`).
+//!
+//! A workflow tool is a virtual tool defined as a small DAG of steps over tool refs that are
+//! already on the calling profile's surface. Step arguments and the tool output are JSON
+//! templates: a string `"${/pointer}"` is replaced by the value at that JSON pointer (type
+//! preserved), and `${/pointer}` embedded in a longer string is interpolated as text. Pointers are
+//! resolved against `{"input": <tool arguments>, "steps": {"<step id>": <step output>}}`.
+//!
+//! Execution lives in `mcp::tool_call`: each step is a regular `tools/call` through the profile's
+//! routing, policy, timeout and audit path.
+
+use async_trait::async_trait;
+use rmcp::model::{JsonObject, Tool};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+
+const MAX_TOOLS: usize = 64;
+const MAX_STEPS: usize = 32;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkflowSourceConfig {
+    pub tools: Vec<WorkflowToolConfig>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkflowToolConfig {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(default = "default_input_schema")]
+    pub input_schema: Value,
+    pub steps: Vec<WorkflowStep>,
+    /// Output template. Defaults to an object of every step output keyed by step id.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub output: Option<Value>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkflowStep {
+    pub id: String,
+    /// Stable tool ref (`<source_id>:<original_tool_name>`) on the calling profile.
+    pub tool: String,
+    /// Arguments template: an object, or a string that is exactly one `${/pointer}` to an object.
+    #[serde(default = "empty_object")]
+    pub args: Value,
+    /// Explicit dependencies, in addition to the steps referenced from `args`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub depends_on: Vec<String>,
+}
+
+fn default_input_schema() -> Value {
+    serde_json::json!({ "type": "object" })
+}
+
+fn empty_object() -> Value {
+    Value::Object(Map::new())
+}
+
+impl WorkflowSourceConfig {
+    /// Validate the whole source (names, step graph and templates).
+    ///
+    /// # Errors
+    ///
+    /// Returns a human-readable message describing the first problem found.
+    pub fn validate(&self) -> Result<(), String> {
+        if self.tools.is_empty() {
+            return Err("workflow source must define at least one tool".to_string());
+        }
+        if self.tools.len() > MAX_TOOLS {
+            return Err(format!(
+                "workflow source defines more than {MAX_TOOLS} tools"
+            ));
+        }
+        let mut names = HashSet::new();
+        for tool in &self.tools {
+            if tool.name.trim().is_empty() {
+                return Err("workflow tool name must not be empty".to_string());
+            }
+            if !names.insert(tool.name.as_str()) {
+                return Err(format!("duplicate workflow tool name '{}'", tool.name));
+            }
+            tool.validate()
+                .map_err(|e| format!("workflow tool '{}': {e}", tool.name))?;
+        }
+        Ok(())
+    }
+
+    #[must_use]
+    pub fn tool(&self, name: &str) -> Option<&WorkflowToolConfig> {
+        self.tools.iter().find(|t| t.name == name)
+    }
+
+    #[must_use]
+    pub fn list_tools(&self) -> Vec<Tool> {
+        self.tools
+            .iter()
+            .map(|t| {
+                let schema = t
+                    .input_schema
+                    .as_object()
+                    .cloned()
+                    .unwrap_or_else(JsonObject::new);
+                Tool::new(
+                    t.name.clone(),
+                    t.description.clone().unwrap_or_default(),
+                    Arc::new(schema),
+                )
+            })
+            .collect()
+    }
+}
+
+impl WorkflowToolConfig {
+    fn validate(&self) -> Result<(), String> {
+        if !self.input_schema.is_object() {
+            return Err("inputSchema must be a JSON object".to_string());
+        }
+        if self.steps.is_empty() {
+            return Err("at least one step is required".to_string());
+        }
+        if self.steps.len() > MAX_STEPS {
+            return Err(format!("more than {MAX_STEPS} steps"));
+        }
+
+        let mut ids = HashSet::new();
+        for step in &self.steps {
+            if !is_valid_step_id(&step.id) {
+                return Err(format!(
+                    "invalid step id '{}' (allowed: [a-zA-Z0-9_-])",
+                    step.id
+                ));
+            }
+            if !ids.insert(step.id.as_str()) {
+                return Err(format!("duplicate step id '{}'", step.id));
+            }
+            match step.tool.split_once(':') {
+                Some((src, name)) if !src.is_empty() && !name.is_empty() => {}
+                _ => {
+                    return Err(format!(
+                        "step '{}': tool must be a tool ref '<source_id>:<tool_name>'",
+                        step.id
+                    ));
+                }
+            }
+            let whole_value = step
+                .args
+                .as_str()
+                .is_some_and(|s| matches!(parse_template(s).as_deref(), Ok([Segment::Pointer(_)])));
+            if !(step.args.is_object() || whole_value) {
+                return Err(format!(
+                    "step '{}': args must be an object or a single '${{/pointer}}' template",
+                    step.id
+                ));
+            }
+        }
+
+        for step in &self.steps {
+            for dep in step_dependencies(step).map_err(|e| format!("step '{}': {e}", step.id))? {
+                if dep == step.id {
+                    return Err(format!("step '{}' depends on itself", step.id));
+                }
+                if !ids.contains(dep.as_str()) {
+                    return Err(format!(
+                        "step '{}' references unknown step '{dep}'",
+                        step.id
+                    ));
+                }
+            }
+        }
+        if let Some(output) = &self.output {
+            let mut pointers = Vec::new();
+            collect_pointers(output, &mut pointers).map_err(|e| format!("output: {e}"))?;
+            for p in pointers {
+                if let Some(dep) = pointer_step(&p).map_err(|e| format!("output: {e}"))?
+                    && !ids.contains(dep.as_str())
+                {
+                    return Err(format!("output references unknown step '{dep}'"));
+                }
+            }
+        }
+
+        plan_waves(self).map(|_| ())
+    }
+}
+
+fn is_valid_step_id(id: &str) -> bool {
+    !id.is_empty()
+        && id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+}
+
+/// Steps a step depends on: `dependsOn` plus every step referenced from its `args`.
+fn step_dependencies(step: &WorkflowStep) -> Result<Vec<String>, String> {
+    let mut pointers = Vec::new();
+    collect_pointers(&step.args, &mut pointers)?;
+    let mut out: Vec<String> = step.depends_on.clone();
+    for p in pointers {
+        if let Some(dep) = pointer_step(&p)?
+            && !out.contains(&dep)
+        {
+            out.push(dep);
+        }
+    }
+    Ok(out)
+}
+
+/// Returns the referenced step id for `/steps/<id>...` pointers, `None` for `/input...`.
+fn pointer_step(pointer: &str) -> Result<Option<String>, String> {
+    if pointer == "/input" || pointer.starts_with("/input/") {
+        return Ok(None);
+    }
+    if let Some(rest) = pointer.strip_prefix("/steps/") {
+        let id = rest.split('/').next().unwrap_or_default();
+        if !id.is_empty() {
+            return Ok(Some(id.to_string()));
+        }
+    }
+    Err(format!(
+        "pointer '{pointer}' must start with '/input' or '/steps/<id>'"
+    ))
+}
+
+/// Group steps into waves; every step only depends on steps from earlier waves.
+fn plan_waves(tool: &WorkflowToolConfig) -> Result<Vec<Vec<usize>>, String> {
+    let index: HashMap<&str, usize> = tool
+        .steps
+        .iter()
+        .enumerate()
+        .map(|(i, s)| (s.id.as_str(), i))
+        .collect();
+    let mut deps: Vec<HashSet<usize>> = Vec::with_capacity(tool.steps.len());
+    for step in &tool.steps {
+        let mut set = HashSet::new();
+        for dep in step_dependencies(step)? {
+            let Some(&i) = index.get(dep.as_str()) else {
+                return Err(format!(
+                    "step '{}' references unknown step '{dep}'",
+                    step.id
+                ));
+            };
+            set.insert(i);
+        }
+        deps.push(set);
+    }
+
+    let mut done: HashSet<usize> = HashSet::new();
+    let mut waves = Vec::new();
+    while done.len() < tool.steps.len() {
+        let wave: Vec<usize> = (0..tool.steps.len())
+            .filter(|i| !done.contains(i) && deps[*i].is_subset(&done))
+            .collect();
+        if wave.is_empty() {
+            return Err("steps contain a dependency cycle".to_string());
+        }
+        done.extend(wave.iter().copied());
+        waves.push(wave);
+    }
+    Ok(waves)
+}
+
+enum Segment<'a> {
+    Literal(&'a str),
+    Pointer(&'a str),
+}
+
+/// Split a template string into literal text and `${/pointer}` placeholders (`$${` escapes `${`).
+fn parse_template(s: &str) -> Result<Vec<Segment<'_>>, String> {
+    let mut out = Vec::new();
+    let mut rest = s;
+    while let Some(pos) = rest.find("${") {
+        if pos > 0 && rest[..pos].ends_with('$') {
+            out.push(Segment::Literal(&rest[..pos - 1]));
+            out.push(Segment::Literal("${"));
+            rest = &rest[pos + 2..];
+            continue;
+        }
+        if pos > 0 {
+            out.push(Segment::Literal(&rest[..pos]));
+        }
+        let after = &rest[pos + 2..];
+        let Some(end) = after.find('}') else {
+            return Err(format!("unterminated placeholder in '{s}'"));
+        };
+        let pointer = &after[..end];
+        if !pointer.starts_with('/') {
+            return Err(format!(
+                "placeholder '${{{pointer}}}' must be a JSON pointer"
+            ));
+        }
+        out.push(Segment::Pointer(pointer));
+        rest = &after[end + 1..];
+    }
+    if !rest.is_empty() {
+        out.push(Segment::Literal(rest));
+    }
+    Ok(out)
+}
+
+fn collect_pointers(template: &Value, out: &mut Vec<String>) -> Result<(), String> {
+    match template {
+        Value::String(s) => {
+            for seg in parse_template(s)? {
+                if let Segment::Pointer(p) = seg {
+                    pointer_step(p)?;
+                    out.push(p.to_string());
+                }
+            }
+            Ok(())
+        }
+        Value::Array(items) => items.iter().try_for_each(|v| collect_pointers(v, out)),
+        Value::Object(map) => map.values().try_for_each(|v| collect_pointers(v, out)),
+        _ => Ok(()),
+    }
+}
+
+/// Render a template against the workflow context. `None` means "missing" (object keys whose
+/// value is missing are dropped; missing array items become `null`).
+fn render(template: &Value, ctx: &Value) -> Option<Value> {
+    match template {
+        Value::String(s) => {
+            // Templates are validated on save; render unparseable strings verbatim.
+            let Ok(segments) = parse_template(s) else {
+                return Some(template.clone());
+            };
+            if let [Segment::Pointer(p)] = segments.as_slice() {
+                return ctx.pointer(p).cloned();
+            }
+            let mut text = String::new();
+            for seg in segments {
+                match seg {
+                    Segment::Literal(l) => text.push_str(l),
+                    Segment::Pointer(p) => match ctx.pointer(p) {
+                        None | Some(Value::Null) => {}
+                        Some(Value::String(v)) => text.push_str(v),
+                        Some(other) => text.push_str(&other.to_string()),
+                    },
+                }
+            }
+            Some(Value::String(text))
+        }
+        Value::Array(items) => Some(Value::Array(
+            items
+                .iter()
+                .map(|v| render(v, ctx).unwrap_or(Value::Null))
+                .collect(),
+        )),
+        Value::Object(map) => Some(Value::Object(
+            map.iter()
+                .filter_map(|(k, v)| render(v, ctx).map(|v| (k.clone(), v)))
+                .collect(),
+        )),
+        other => Some(other.clone()),
+    }
+}
+
+/// Executes a single workflow step as a `tools/call` and returns the step output.
+#[async_trait]
+pub trait StepCaller: Send + Sync {
+    async fn call_step(&self, tool_ref: &str, args: Map<String, Value>) -> Result<Value, String>;
+}
+
+/// Run a workflow tool: independent steps of a wave run concurrently, waves run in order.
+///
+/// # Errors
+///
+/// Returns a message naming the first failing step.
+pub async fn run(
+    tool: &WorkflowToolConfig,
+    input: Value,
+    caller: &dyn StepCaller,
+) -> Result<Value, String> {
+    let waves = plan_waves(tool)?;
+    let mut ctx = serde_json::json!({ "input": input, "steps": {} });
+
+    for wave in waves {
+        let mut calls = Vec::with_capacity(wave.len());
+        for i in wave {
+            let step = &tool.steps[i];
+            let args = match render(&step.args, &ctx) {
+                Some(Value::Object(m)) => m,
+                None | Some(Value::Null) => Map::new(),
+                Some(_) => {
+                    return Err(format!(
+                        "step '{}': args did not render to an object",
+                        step.id
+                    ));
+                }
+            };
+            calls.push(async move {
+                let res = caller.call_step(&step.tool, args).await;
+                (step.id.as_str(), res)
+            });
+        }
+        for (id, res) in futures::future::join_all(calls).await {
+            let output = res.map_err(|e| format!("step '{id}' failed: {e}"))?;
+            ctx["steps"][id] = output;
+        }
+    }
+
+    Ok(match &tool.output {
+        Some(template) => render(template, &ctx).unwrap_or(Value::Null),
+        None => ctx["steps"].take(),
+    })
+}
+
+/// Turn a `tools/call` result into a step output: `structuredContent` if present, otherwise the
+/// text content (parsed as JSON when possible).
+///
+/// # Errors
+///
+/// Returns the tool's error text when the result has `isError: true`.
+pub fn step_output_from_call_result(result: &Value) -> Result<Value, String> {
+    let text = result
+        .get("content")
+        .and_then(Value::as_array)
+        .map(|items| {
+            items
+                .iter()
+                .filter(|c| c.get("type").and_then(Value::as_str) == Some("text"))
+                .filter_map(|c| c.get("text").and_then(Value::as_str))
+                .collect::<Vec<_>>()
+                .join("\n")
+        })
+        .unwrap_or_default();
+
+    if result.get("isError").and_then(Value::as_bool) == Some(true) {
+        return Err(if text.is_empty() {
+            "tool returned an error".to_string()
+        } else {
+            text
+        });
+    }
+    if let Some(structured) = result.get("structuredContent") {
+        return Ok(structured.clone());
+    }
+    Ok(serde_json::from_str(&text).unwrap_or(Value::String(text)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use parking_lot::Mutex;
+    use serde_json::json;
+
+    fn workflow(v: Value) -> WorkflowSourceConfig {
+        serde_json::from_value(v).expect("workflow config")
+    }
+
+    struct RecordingCaller {
+        calls: Mutex<Vec<(String, Map<String, Value>)>>,
+    }
+
+    #[async_trait]
+    impl StepCaller for RecordingCaller {
+        async fn call_step(
+            &self,
+            tool_ref: &str,
+            args: Map<String, Value>,
+        ) -> Result<Value, String> {
+            self.calls.lock().push((tool_ref.to_string(), args.clone()));
+            match tool_ref {
+                "crm:find_customer" => Ok(json!({ "id": 42, "name": "Ada" })),
+                "desk:open_tickets" => Ok(json!([{ "id": "T-1" }])),
+                other => Err(format!("unexpected tool {other}")),
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn run_maps_step_outputs_into_later_steps_and_output() {
+        let cfg = workflow(json!({
+            "tools": [{
+                "name": "customer_overview",
+                "steps": [
+                    { "id": "customer", "tool": "crm:find_customer", "args": { "email": "${/input/email}" } },
+                    { "id": "tickets", "tool": "desk:open_tickets",
+                      "args": { "customerId": "${/steps/customer/id}", "label": "cust-${/steps/customer/id}", "missing": "${/input/nope}" } }
+                ],
+                "output": { "customer": "${/steps/customer}", "tickets": "${/steps/tickets}" }
+            }]
+        }));
+        cfg.validate().expect("valid");
+
+        let caller = RecordingCaller {
+            calls: Mutex::new(Vec::new()),
+        };
+        let out = run(
+            &cfg.tools[0],
+            json!({ "email": "ada@example.com" }),
+            &caller,
+        )
+        .await
+        .expect("run");
+
+        assert_eq!(
+            out,
+            json!({ "customer": { "id": 42, "name": "Ada" }, "tickets": [{ "id": "T-1" }] })
+        );
+        let calls = caller.calls.lock();
+        assert_eq!(calls.len(), 2);
+        assert_eq!(
+            calls[0].1,
+            json!({ "email": "ada@example.com" })
+                .as_object()
+                .unwrap()
+                .clone()
+        );
+        assert_eq!(
+            calls[1].1,
+            json!({ "customerId": 42, "label": "cust-42" })
+                .as_object()
+                .unwrap()
+                .clone()
+        );
+    }
+
+    #[test]
+    fn validate_rejects_cycles_unknown_steps_and_bad_pointers() {
+        let cycle = workflow(json!({ "tools": [{ "name": "t", "steps": [
+            { "id": "a", "tool": "s:x", "args": { "v": "${/steps/b/v}" } },
+            { "id": "b", "tool": "s:y", "dependsOn": ["a"] }
+        ]}]}));
+        assert!(cycle.validate().unwrap_err().contains("cycle"));
+
+        let unknown = workflow(json!({ "tools": [{ "name": "t", "steps": [
+            { "id": "a", "tool": "s:x", "dependsOn": ["zzz"] }
+        ]}]}));
+        assert!(unknown.validate().unwrap_err().contains("unknown step"));
+
+        let bad_pointer = workflow(json!({ "tools": [{ "name": "t", "steps": [
+            { "id": "a", "tool": "s:x", "args": { "v": "${/env/HOME}" } }
+        ]}]}));
+        assert!(
+            bad_pointer
+                .validate()
+                .unwrap_err()
+                .contains("must start with")
+        );
+
+        let bad_ref = workflow(json!({ "tools": [{ "name": "t", "steps": [
+            { "id": "a", "tool": "just_a_name" }
+        ]}]}));
+        assert!(bad_ref.validate().unwrap_err().contains("tool ref"));
+    }
+
+    #[test]
+    fn validate_accepts_object_and_whole_pointer_args_only() {
+        let args = |args: Value| {
+            workflow(json!({ "tools": [{ "name": "t", "steps": [
+                { "id": "a", "tool": "s:x", "args": args }
+            ]}]}))
+            .validate()
+        };
+        assert!(args(json!({ "v": "${/input/v}", "n": 1 })).is_ok());
+        assert!(args(json!("${/input}")).is_ok());
+        assert!(
+            workflow(
+                json!({ "tools": [{ "name": "t", "steps": [{ "id": "a", "tool": "s:x" }] }] })
+            )
+            .validate()
+            .is_ok()
+        );
+
+        for bad in [
+            json!("prefix-${/input}"),
+            json!("plain text"),
+            json!([1, 2]),
+            json!(7),
+            json!(null),
+        ] {
+            let err = args(bad.clone()).unwrap_err();
+            assert!(err.contains("args must be an object"), "{bad}: {err}");
+        }
+    }
+
+    #[test]
+    fn independent_steps_share_a_wave_and_escapes_render_literally() {
+        let cfg = workflow(json!({ "tools": [{ "name": "t", "steps": [
+            { "id": "a", "tool": "s:x" },
+            { "id": "b", "tool": "s:y" },
+            { "id": "c", "tool": "s:z", "args": { "v": "${/steps/a}", "w": "${/steps/b}" } }
+        ]}]}));
+        assert_eq!(
+            plan_waves(&cfg.tools[0]).unwrap(),
+            vec![vec![0, 1], vec![2]]
+        );
+
+        let ctx = json!({ "input": { "x": 1 } });
+        assert_eq!(
+            render(&json!("cost: $${/input/x} = ${/input/x}"), &ctx),
+            Some(json!("cost: ${/input/x} = 1"))
+        );
+    }
+
+    #[test]
+    fn step_output_prefers_structured_content_and_surfaces_errors() {
+        assert_eq!(
+            step_output_from_call_result(&json!({
+                "content": [{ "type": "text", "text": "{\"a\":1}" }],
+                "structuredContent": { "b": 2 }
+            })),
+            Ok(json!({ "b": 2 }))
+        );
+        assert_eq!(
+            step_output_from_call_result(
+                &json!({ "content": [{ "type": "text", "text": "{\"a\":1}" }] })
+            ),
+            Ok(json!({ "a": 1 }))
+        );
+        assert_eq!(
+            step_output_from_call_result(&json!({
+                "content": [{ "type": "text", "text": "boom" }],
+                "isError": true
+            })),
+            Err("boom".to_string())
+        );
+    }
+}
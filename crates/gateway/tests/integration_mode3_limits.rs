@@ -268,15 +268,28 @@ fn generate_test_keypair_and_jwks(kid: &str) -> anyhow::Result<(String, serde_js
 }
 
 fn sign_rs256_jwt(pem: &str, kid: &str, subject: &str, now: u64) -> anyhow::Result<String> {
+    sign_rs256_jwt_with_claims(pem, kid, subject, now, json!({}))
+}
+
+fn sign_rs256_jwt_with_claims(
+    pem: &str,
+    kid: &str,
+    subject: &str,
+    now: u64,
+    extra_claims: serde_json::Value,
+) -> anyhow::Result<String> {
     use jsonwebtoken::{Algorithm, EncodingKey, Header};
 
-    let claims = json!({
+    let mut claims = json!({
         "iss": TEST_OIDC_ISSUER,
         "sub": subject,
         "iat": now,
         "nbf": now.saturating_sub(1),
         "exp": now + 3600,
     });
+    if let (Some(claims), Some(extra)) = (claims.as_object_mut(), extra_claims.as_object()) {
+        claims.extend(extra.clone());
+    }
     let header = Header {
         alg: Algorithm::RS256,
         kid: Some(kid.to_string()),
@@ -476,6 +489,8 @@ fn spawn_gateway_with_oidc(
         )
         .env("UNRELATED_GATEWAY_OIDC_ISSUER", TEST_OIDC_ISSUER)
         .env("UNRELATED_GATEWAY_OIDC_JWKS_URI", jwks_uri)
+        // Tenant issuers in tests point at the plain-HTTP loopback JWKS server.
+        .env("UNRELATED_GATEWAY_TENANT_OIDC_ALLOW_HTTP", "1")
         .stdout(std::process::Stdio::piped())
         .stderr(std::process::Stdio::piped())
         .spawn()
@@ -929,6 +944,251 @@ async fn mode3_revoked_api_key_breaks_session_for_initialize_only_mode() -> anyh
     Ok(())
 }
 
+#[tokio::test]
+#[ignore = "requires Docker (testcontainers)"]
+async fn mode3_api_key_rotation_and_per_key_tool_allowlist() -> anyhow::Result<()> {
+    let pg = start_postgres().await?;
+    let upstream = start_mock_upstream().await?;
+    let gw = start_gateway_mode3(&pg.database_url).await?;
+    let client = reqwest::Client::new();
+
+    admin_create_tenant(&client, &gw.admin_base, "t1").await?;
+    admin_create_upstream(
+        &client,
+        &gw.admin_base,
+        "u1",
+        &format!("http://127.0.0.1:{}/mcp", upstream.port),
+    )
+    .await?;
+    let profile_id = admin_create_profile(
+        &client,
+        &gw.admin_base,
+        json!({
+            "tenantId": "t1",
+            "name": "p1",
+            "enabled": true,
+            "allowPartialUpstreams": true,
+            "upstreams": ["u1"],
+            "tools": []
+        }),
+    )
+    .await?;
+    let t1_token = admin_issue_tenant_token(&client, &gw.admin_base, "t1").await?;
+
+    // Key narrowed to a tool the upstream does not expose: tools/list is empty.
+    let resp = client
+        .post(format!("{}/tenant/v1/api-keys", gw.admin_base))
+        .header("Authorization", format!("Bearer {t1_token}"))
+        .json(&json!({"name": "narrow", "profileId": profile_id, "tools": ["u1:other"]}))
+        .send()
+        .await?
+        .error_for_status()?;
+    let body: serde_json::Value = resp.json().await?;
+    let narrow_secret = body["secret"].as_str().context("secret")?.to_string();
+    let narrow_id = body["id"].as_str().context("id")?.to_string();
+
+    let session_id =
+        mcp_initialize_with_api_key(&client, &gw.data_base, &profile_id, &narrow_secret).await?;
+    let list = read_first_event_stream_json_message(
+        post_mcp(
+            &client,
+            &profile_mcp_url(&gw.data_base, &profile_id),
+            Some(&session_id),
+            None,
+            json!({"jsonrpc": "2.0", "id": 1, "method": "tools/list", "params": {}}),
+        )
+        .await?,
+    )
+    .await?;
+    let tools = list["result"]["tools"].as_array().context("tools array")?;
+    anyhow::ensure!(tools.is_empty(), "expected no tools, got {tools:?}");
+
+    // Rotate with no grace period: the old key stops working, the successor inherits the allowlist.
+    let resp = client
+        .post(format!(
+            "{}/tenant/v1/api-keys/{narrow_id}/rotate",
+            gw.admin_base
+        ))
+        .header("Authorization", format!("Bearer {t1_token}"))
+        .json(&json!({"gracePeriodSecs": 0}))
+        .send()
+        .await?
+        .error_for_status()?;
+    let rotated: serde_json::Value = resp.json().await?;
+    anyhow::ensure!(rotated["rotatedFromId"] == json!(narrow_id));
+    anyhow::ensure!(rotated["tools"] == json!(["u1:other"]));
+    let successor_secret = rotated["secret"].as_str().context("secret")?.to_string();
+
+    let reinit = post_mcp_allow_error(
+        &client,
+        &profile_mcp_url(&gw.data_base, &profile_id),
+        None,
+        Some(&narrow_secret),
+        json!({
+            "jsonrpc": "2.0",
+            "id": 2,
+            "method": "initialize",
+            "params": {
+                "protocolVersion": "2024-11-05",
+                "capabilities": {},
+                "clientInfo": { "name": "mode3-rotate-test", "version": "0" }
+            }
+        }),
+    )
+    .await?;
+    anyhow::ensure!(
+        reinit.status() == reqwest::StatusCode::UNAUTHORIZED,
+        "expected 401 for rotated key, got {}",
+        reinit.status()
+    );
+    let _ = mcp_initialize_with_api_key(&client, &gw.data_base, &profile_id, &successor_secret)
+        .await
+        .context("initialize with successor key")?;
+
+    // Keys expiring soon are surfaced as warnings.
+    let expires_at_unix = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)?
+        .as_secs()
+        + 3600;
+    client
+        .post(format!("{}/tenant/v1/api-keys", gw.admin_base))
+        .header("Authorization", format!("Bearer {t1_token}"))
+        .json(&json!({"name": "short", "profileId": profile_id, "expiresAtUnix": expires_at_unix}))
+        .send()
+        .await?
+        .error_for_status()?;
+    let listed: serde_json::Value = client
+        .get(format!("{}/tenant/v1/api-keys", gw.admin_base))
+        .header("Authorization", format!("Bearer {t1_token}"))
+        .send()
+        .await?
+        .error_for_status()?
+        .json()
+        .await?;
+    let warnings = listed["expiryWarnings"]
+        .as_array()
+        .context("expiryWarnings")?;
+    anyhow::ensure!(
+        warnings.iter().any(|w| w["name"] == "short"),
+        "expected expiry warning for 'short', got {warnings:?}"
+    );
+    Ok(())
+}
+
+#[tokio::test]
+#[ignore = "requires Docker (testcontainers)"]
+async fn mode3_ip_allowlists_reject_requests_from_outside_cidrs() -> anyhow::Result<()> {
+    let pg = start_postgres().await?;
+    let upstream = start_mock_upstream().await?;
+    let gw = start_gateway_mode3(&pg.database_url).await?;
+    let client = reqwest::Client::new();
+
+    admin_create_tenant(&client, &gw.admin_base, "t1").await?;
+    admin_create_upstream(
+        &client,
+        &gw.admin_base,
+        "u1",
+        &format!("http://127.0.0.1:{}/mcp", upstream.port),
+    )
+    .await?;
+    let profile_id = admin_create_profile(
+        &client,
+        &gw.admin_base,
+        json!({
+            "tenantId": "t1",
+            "name": "p1",
+            "enabled": true,
+            "allowPartialUpstreams": true,
+            "upstreams": ["u1"],
+            "tools": []
+        }),
+    )
+    .await?;
+    let t1_token = admin_issue_tenant_token(&client, &gw.admin_base, "t1").await?;
+
+    let initialize = json!({
+        "jsonrpc": "2.0",
+        "id": 0,
+        "method": "initialize",
+        "params": {
+            "protocolVersion": "2024-11-05",
+            "capabilities": {},
+            "clientInfo": { "name": "mode3-ip-test", "version": "0" }
+        }
+    });
+
+    // Key restricted to a subnet the test client is not in (the gateway sees 127.0.0.1).
+    let body: serde_json::Value = client
+        .post(format!("{}/tenant/v1/api-keys", gw.admin_base))
+        .header("Authorization", format!("Bearer {t1_token}"))
+        .json(&json!({"name": "ci", "profileId": profile_id, "allowedCidrs": ["10.0.0.0/8"]}))
+        .send()
+        .await?
+        .error_for_status()?
+        .json()
+        .await?;
+    let ci_secret = body["secret"].as_str().context("secret")?.to_string();
+    let denied = post_mcp_allow_error(
+        &client,
+        &profile_mcp_url(&gw.data_base, &profile_id),
+        None,
+        Some(&ci_secret),
+        initialize.clone(),
+    )
+    .await?;
+    anyhow::ensure!(
+        denied.status() == reqwest::StatusCode::FORBIDDEN,
+        "expected 403 for key outside its CIDRs, got {}",
+        denied.status()
+    );
+
+    // Loopback key works.
+    let body: serde_json::Value = client
+        .post(format!("{}/tenant/v1/api-keys", gw.admin_base))
+        .header("Authorization", format!("Bearer {t1_token}"))
+        .json(&json!({"name": "local", "profileId": profile_id, "allowedCidrs": ["127.0.0.1"]}))
+        .send()
+        .await?
+        .error_for_status()?
+        .json()
+        .await?;
+    let local_secret = body["secret"].as_str().context("secret")?.to_string();
+    let session_id =
+        mcp_initialize_with_api_key(&client, &gw.data_base, &profile_id, &local_secret).await?;
+
+    // A tenant-level allowlist applies to existing sessions too.
+    client
+        .put(format!("{}/tenant/v1/network/allowlist", gw.admin_base))
+        .header("Authorization", format!("Bearer {t1_token}"))
+        .json(&json!({"allowedCidrs": ["192.0.2.0/24"]}))
+        .send()
+        .await?
+        .error_for_status()?;
+    let denied = post_mcp_allow_error(
+        &client,
+        &profile_mcp_url(&gw.data_base, &profile_id),
+        Some(&session_id),
+        None,
+        json!({"jsonrpc": "2.0", "id": 1, "method": "tools/list", "params": {}}),
+    )
+    .await?;
+    anyhow::ensure!(
+        denied.status() == reqwest::StatusCode::FORBIDDEN,
+        "expected 403 under tenant allowlist, got {}",
+        denied.status()
+    );
+
+    // Invalid CIDRs are rejected on write.
+    let bad = client
+        .put(format!("{}/tenant/v1/network/allowlist", gw.admin_base))
+        .header("Authorization", format!("Bearer {t1_token}"))
+        .json(&json!({"allowedCidrs": ["10.0.0.0/33"]}))
+        .send()
+        .await?;
+    anyhow::ensure!(bad.status() == reqwest::StatusCode::BAD_REQUEST);
+    Ok(())
+}
+
 #[tokio::test]
 #[ignore = "requires Docker (testcontainers)"]
 async fn mode3_jwt_every_request_enforces_profile_scoped_and_tenant_wide_oidc_bindings()
@@ -1020,3 +1280,317 @@ async fn mode3_jwt_every_request_enforces_profile_scoped_and_tenant_wide_oidc_bi
     anyhow::ensure!(allowed_p2.status().is_success());
     Ok(())
 }
+
+#[tokio::test]
+#[ignore = "requires Docker (testcontainers)"]
+async fn mode3_oidc_claim_rules_grant_profiles_by_token_claims() -> anyhow::Result<()> {
+    let kid = "test-kid";
+    let (pem, jwks_json) = generate_test_keypair_and_jwks(kid)?;
+    let jwks = start_jwks_server(jwks_json).await?;
+    let pg = start_postgres().await?;
+    let upstream = start_mock_upstream().await?;
+    let gw = start_gateway_mode3_with_oidc(&pg.database_url, &jwks.jwks_uri).await?;
+
+    let client = reqwest::Client::new();
+    admin_create_tenant(&client, &gw.admin_base, "t1").await?;
+    admin_create_upstream(
+        &client,
+        &gw.admin_base,
+        "u1",
+        &format!("http://127.0.0.1:{}/mcp", upstream.port),
+    )
+    .await?;
+
+    let profile_body = json!({
+        "tenantId": "t1",
+        "name": "p1",
+        "enabled": true,
+        "allowPartialUpstreams": true,
+        "upstreams": ["u1"],
+        "tools": [],
+        "dataPlaneAuth": { "mode": "jwtEveryRequest" }
+    });
+    let mut profile_body_2 = profile_body.clone();
+    profile_body_2["name"] = json!("p2");
+    let p1_id = admin_create_profile(&client, &gw.admin_base, profile_body).await?;
+    let p2_id = admin_create_profile(&client, &gw.admin_base, profile_body_2).await?;
+
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .context("time")?
+        .as_secs();
+    let member = sign_rs256_jwt_with_claims(
+        &pem,
+        kid,
+        "alice",
+        now,
+        json!({"groups": ["ml-platform"], "email": "alice@corp.example", "email_verified": true}),
+    )?;
+    let outsider = sign_rs256_jwt_with_claims(
+        &pem,
+        kid,
+        "mallory",
+        now,
+        json!({"groups": ["sales"], "email": "mallory@corp.example", "email_verified": true}),
+    )?;
+
+    let denied =
+        mcp_initialize_with_jwt_allow_error(&client, &gw.data_base, &p1_id, &member, 0).await?;
+    anyhow::ensure!(denied.status() == reqwest::StatusCode::UNAUTHORIZED);
+
+    let rule = admin_put(
+        &client,
+        &gw.admin_base,
+        "/admin/v1/tenants/t1/oidc-claim-rules/ml-platform",
+        json!({
+            "profileId": p1_id,
+            "conditions": [
+                {"claim": "groups", "op": "contains", "value": "ml-platform"},
+                {"claim": "email", "op": "endsWith", "value": "@corp.example"}
+            ]
+        }),
+    )
+    .await?;
+    anyhow::ensure!(rule["id"] == "ml-platform", "unexpected rule: {rule}");
+
+    let allowed_p1 =
+        mcp_initialize_with_jwt_allow_error(&client, &gw.data_base, &p1_id, &member, 1).await?;
+    anyhow::ensure!(allowed_p1.status().is_success());
+    let _ = read_first_event_stream_json_message(allowed_p1).await?;
+
+    let denied_p2 =
+        mcp_initialize_with_jwt_allow_error(&client, &gw.data_base, &p2_id, &member, 2).await?;
+    anyhow::ensure!(denied_p2.status() == reqwest::StatusCode::UNAUTHORIZED);
+
+    let denied_outsider =
+        mcp_initialize_with_jwt_allow_error(&client, &gw.data_base, &p1_id, &outsider, 3).await?;
+    anyhow::ensure!(denied_outsider.status() == reqwest::StatusCode::UNAUTHORIZED);
+
+    // Dry-run endpoint: validated token, per-rule breakdown.
+    let report = admin_post(
+        &client,
+        &gw.admin_base,
+        "/admin/v1/tenants/t1/oidc-claim-rules/test",
+        json!({"token": member, "profileId": p2_id}),
+    )
+    .await?;
+    anyhow::ensure!(report["subject"] == "alice", "report: {report}");
+    anyhow::ensure!(report["allowed"] == false, "report: {report}");
+    anyhow::ensure!(report["rules"][0]["matched"] == true, "report: {report}");
+    anyhow::ensure!(report["rules"][0]["grants"] == false, "report: {report}");
+
+    let report = admin_post(
+        &client,
+        &gw.admin_base,
+        "/admin/v1/tenants/t1/oidc-claim-rules/test",
+        json!({"claims": {"sub": "mallory", "groups": ["sales"]}}),
+    )
+    .await?;
+    anyhow::ensure!(report["allowed"] == false, "report: {report}");
+    anyhow::ensure!(
+        report["rules"][0]["conditions"][0]["matched"] == false,
+        "report: {report}"
+    );
+    Ok(())
+}
+
+#[tokio::test]
+#[ignore = "requires Docker (testcontainers)"]
+async fn mode3_tenant_oidc_issuer_routes_tokens_by_iss() -> anyhow::Result<()> {
+    const TENANT_ISSUER: &str = "https://idp.tenant-a.example";
+    let kid = "tenant-kid";
+    let (pem, jwks_json) = generate_test_keypair_and_jwks(kid)?;
+    let jwks = start_jwks_server(jwks_json).await?;
+    let pg = start_postgres().await?;
+    let upstream = start_mock_upstream().await?;
+    // The Gateway-wide issuer uses the same JWKS; tokens are told apart by `iss` only.
+    let gw = start_gateway_mode3_with_oidc(&pg.database_url, &jwks.jwks_uri).await?;
+
+    let client = reqwest::Client::new();
+    admin_create_tenant(&client, &gw.admin_base, "t1").await?;
+    admin_create_tenant(&client, &gw.admin_base, "t2").await?;
+    admin_create_upstream(
+        &client,
+        &gw.admin_base,
+        "u1",
+        &format!("http://127.0.0.1:{}/mcp", upstream.port),
+    )
+    .await?;
+
+    let mut profile_ids = Vec::new();
+    for tenant in ["t1", "t2"] {
+        profile_ids.push(
+            admin_create_profile(
+                &client,
+                &gw.admin_base,
+                json!({
+                    "tenantId": tenant,
+                    "name": "p",
+                    "enabled": true,
+                    "allowPartialUpstreams": true,
+                    "upstreams": ["u1"],
+                    "tools": [],
+                    "dataPlaneAuth": { "mode": "jwtEveryRequest" }
+                }),
+            )
+            .await?,
+        );
+    }
+    let (p1_id, p2_id) = (&profile_ids[0], &profile_ids[1]);
+
+    let tenant_token = admin_issue_tenant_token(&client, &gw.admin_base, "t1").await?;
+    client
+        .put(format!("{}/tenant/v1/oidc/issuers", gw.admin_base))
+        .header("Authorization", format!("Bearer {tenant_token}"))
+        .json(&json!({
+            "issuer": TENANT_ISSUER,
+            "audiences": ["api://gateway"],
+            "jwksUri": jwks.jwks_uri,
+        }))
+        .send()
+        .await
+        .context("tenant PUT /oidc/issuers")?
+        .error_for_status()
+        .context("tenant PUT /oidc/issuers status")?;
+
+    let _ = admin_put(
+        &client,
+        &gw.admin_base,
+        "/admin/v1/tenants/t1/oidc-principals",
+        json!({"subject": "alice", "issuer": TENANT_ISSUER, "enabled": true}),
+    )
+    .await?;
+    // Bindings can only reference issuers the tenant has registered.
+    let unknown = client
+        .put(format!(
+            "{}/admin/v1/tenants/t2/oidc-principals",
+            gw.admin_base
+        ))
+        .header("Authorization", format!("Bearer {ADMIN_TOKEN}"))
+        .json(&json!({"subject": "alice", "issuer": TENANT_ISSUER, "enabled": true}))
+        .send()
+        .await
+        .context("admin PUT t2 oidc-principals")?;
+    anyhow::ensure!(unknown.status() == reqwest::StatusCode::BAD_REQUEST);
+
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .context("time")?
+        .as_secs();
+    let good = sign_rs256_jwt_with_claims(
+        &pem,
+        kid,
+        "alice",
+        now,
+        json!({"iss": TENANT_ISSUER, "aud": "api://gateway"}),
+    )?;
+    let wrong_aud = sign_rs256_jwt_with_claims(
+        &pem,
+        kid,
+        "alice",
+        now,
+        json!({"iss": TENANT_ISSUER, "aud": "api://other-app"}),
+    )?;
+
+    let ok = mcp_initialize_with_jwt_allow_error(&client, &gw.data_base, p1_id, &good, 1).await?;
+    anyhow::ensure!(ok.status().is_success(), "status: {}", ok.status());
+    let _ = read_first_event_stream_json_message(ok).await?;
+
+    let denied =
+        mcp_initialize_with_jwt_allow_error(&client, &gw.data_base, p1_id, &wrong_aud, 2).await?;
+    anyhow::ensure!(denied.status() == reqwest::StatusCode::UNAUTHORIZED);
+
+    // t2 has not registered the issuer: its tokens are untrusted there.
+    let other_tenant =
+        mcp_initialize_with_jwt_allow_error(&client, &gw.data_base, p2_id, &good, 3).await?;
+    anyhow::ensure!(other_tenant.status() == reqwest::StatusCode::UNAUTHORIZED);
+    Ok(())
+}
+
+#[tokio::test]
+#[ignore = "requires Docker (testcontainers)"]
+async fn mode3_jwt_profile_advertises_protected_resource_metadata() -> anyhow::Result<()> {
+    let (_pem, jwks_json) = generate_test_keypair_and_jwks("test-kid")?;
+    let jwks = start_jwks_server(jwks_json).await?;
+    let pg = start_postgres().await?;
+    let upstream = start_mock_upstream().await?;
+    let gw = start_gateway_mode3_with_oidc(&pg.database_url, &jwks.jwks_uri).await?;
+
+    let client = reqwest::Client::new();
+    admin_create_tenant(&client, &gw.admin_base, "t1").await?;
+    admin_create_upstream(
+        &client,
+        &gw.admin_base,
+        "u1",
+        &format!("http://127.0.0.1:{}/mcp", upstream.port),
+    )
+    .await?;
+    let profile_id = admin_create_profile(
+        &client,
+        &gw.admin_base,
+        json!({
+            "tenantId": "t1",
+            "name": "p1",
+            "enabled": true,
+            "allowPartialUpstreams": true,
+            "upstreams": ["u1"],
+            "tools": [],
+            "dataPlaneAuth": { "mode": "jwtEveryRequest" }
+        }),
+    )
+    .await?;
+
+    // Without a public base URL the links are derived from the request's Host header.
+    let metadata_url = format!(
+        "{}/.well-known/oauth-protected-resource/{profile_id}/mcp",
+        gw.data_base
+    );
+
+    let resp = post_mcp_allow_error(
+        &client,
+        &profile_mcp_url(&gw.data_base, &profile_id),
+        None,
+        None,
+        json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "initialize",
+            "params": {
+                "protocolVersion": "2024-11-05",
+                "capabilities": {},
+                "clientInfo": { "name": "mode3-oidc-test", "version": "0" }
+            }
+        }),
+    )
+    .await?;
+    anyhow::ensure!(resp.status() == reqwest::StatusCode::UNAUTHORIZED);
+    let challenge = resp
+        .headers()
+        .get("www-authenticate")
+        .and_then(|v| v.to_str().ok())
+        .context("missing WWW-Authenticate")?;
+    anyhow::ensure!(
+        challenge == format!("Bearer resource_metadata=\"{metadata_url}\""),
+        "challenge: {challenge}"
+    );
+
+    let metadata: serde_json::Value = client
+        .get(&metadata_url)
+        .send()
+        .await
+        .context("GET protected resource metadata")?
+        .error_for_status()
+        .context("GET protected resource metadata status")?
+        .json()
+        .await
+        .context("protected resource metadata json")?;
+    anyhow::ensure!(
+        metadata["resource"] == profile_mcp_url(&gw.data_base, &profile_id),
+        "metadata: {metadata}"
+    );
+    anyhow::ensure!(
+        metadata["authorization_servers"] == json!([TEST_OIDC_ISSUER]),
+        "metadata: {metadata}"
+    );
+    Ok(())
+}
@@ -1065,3 +1065,135 @@ async fn tenant_profile_surface_probe_returns_tools_and_source_status() -> anyho
 
     Ok(())
 }
+
+#[tokio::test]
+#[ignore = "requires Docker (testcontainers)"]
+#[allow(clippy::too_many_lines)]
+async fn scoped_tenant_tokens_are_enforced_per_route_and_revocable() -> anyhow::Result<()> {
+    // Postgres
+    let pg = GenericImage::new("postgres", "16-alpine")
+        .with_exposed_port(5432.tcp())
+        .with_env_var("POSTGRES_PASSWORD", "postgres")
+        .with_env_var("POSTGRES_USER", "postgres")
+        .with_env_var("POSTGRES_DB", "gateway")
+        .start()
+        .await
+        .context("start postgres container")?;
+    let host = pg.get_host().await?.to_string();
+    let port = pg.get_host_port_ipv4(5432).await?;
+    let database_url =
+        format!("postgres://postgres:postgres@{host}:{port}/gateway?sslmode=disable");
+    wait_pg_ready(&database_url, Duration::from_secs(30)).await?;
+    apply_dbmate_migrations(&database_url).await?;
+
+    // Gateway (Mode 3)
+    let gw = spawn_gateway(&database_url, Some(ADMIN_TOKEN), SESSION_SECRET)?;
+    let data_base = gw.data_base.clone();
+    let admin_base = gw.admin_base.clone();
+    let _gateway_child = KillOnDrop(gw.child);
+    wait_http_ok(&format!("{data_base}/health"), Duration::from_secs(20)).await?;
+    wait_http_ok(&format!("{admin_base}/health"), Duration::from_secs(20)).await?;
+
+    let client = reqwest::Client::new();
+    let _ = admin_post(
+        &client,
+        &admin_base,
+        "/admin/v1/tenants",
+        json!({ "id": "t1", "enabled": true }),
+    )
+    .await?;
+
+    // Read-only dashboard token (viewer role).
+    let viewer = admin_post(
+        &client,
+        &admin_base,
+        "/admin/v1/tenant-tokens",
+        json!({"tenantId": "t1", "ttlSeconds": 3600, "role": "viewer"}),
+    )
+    .await?;
+    let viewer_token = viewer
+        .get("token")
+        .and_then(serde_json::Value::as_str)
+        .context("viewer token")?
+        .to_string();
+    let viewer_token_id = viewer
+        .get("tokenId")
+        .and_then(serde_json::Value::as_str)
+        .context("viewer tokenId")?
+        .to_string();
+
+    let resp = client
+        .get(format!("{admin_base}/tenant/v1/profiles"))
+        .header("Authorization", format!("Bearer {viewer_token}"))
+        .send()
+        .await
+        .context("viewer GET /profiles")?;
+    anyhow::ensure!(resp.status() == reqwest::StatusCode::OK);
+
+    let resp = client
+        .post(format!("{admin_base}/tenant/v1/secrets"))
+        .header("Authorization", format!("Bearer {viewer_token}"))
+        .json(&json!({"name": "s1", "value": "v1"}))
+        .send()
+        .await
+        .context("viewer POST /secrets")?;
+    anyhow::ensure!(resp.status() == reqwest::StatusCode::FORBIDDEN);
+
+    // A full-access token can mint a narrower token, but a scoped token cannot widen itself.
+    let full_token = admin_issue_tenant_token(&client, &admin_base, "t1").await?;
+    let ci = client
+        .post(format!("{admin_base}/tenant/v1/tokens"))
+        .header("Authorization", format!("Bearer {full_token}"))
+        .json(&json!({"scopes": ["secrets:write", "tokens:write"]}))
+        .send()
+        .await
+        .context("tenant POST /tokens")?
+        .error_for_status()
+        .context("tenant POST /tokens status")?
+        .json::<serde_json::Value>()
+        .await?;
+    let ci_token = ci
+        .get("token")
+        .and_then(serde_json::Value::as_str)
+        .context("ci token")?
+        .to_string();
+
+    let resp = client
+        .post(format!("{admin_base}/tenant/v1/secrets"))
+        .header("Authorization", format!("Bearer {ci_token}"))
+        .json(&json!({"name": "s1", "value": "v1"}))
+        .send()
+        .await
+        .context("ci POST /secrets")?;
+    anyhow::ensure!(resp.status().is_success());
+
+    let resp = client
+        .post(format!("{admin_base}/tenant/v1/tokens"))
+        .header("Authorization", format!("Bearer {ci_token}"))
+        .json(&json!({"scopes": ["profiles:write"]}))
+        .send()
+        .await
+        .context("ci POST /tokens (widen)")?;
+    anyhow::ensure!(resp.status() == reqwest::StatusCode::FORBIDDEN);
+
+    // Revocation takes effect immediately.
+    let resp = client
+        .delete(format!(
+            "{admin_base}/admin/v1/tenants/t1/tenant-tokens/{viewer_token_id}"
+        ))
+        .header("Authorization", format!("Bearer {ADMIN_TOKEN}"))
+        .send()
+        .await
+        .context("admin revoke tenant token")?;
+    anyhow::ensure!(resp.status().is_success());
+
+    let resp = client
+        .get(format!("{admin_base}/tenant/v1/profiles"))
+        .header("Authorization", format!("Bearer {viewer_token}"))
+        .send()
+        .await
+        .context("revoked viewer GET /profiles")?;
+    anyhow::ensure!(resp.status() == reqwest::StatusCode::UNAUTHORIZED);
+
+    Ok(())
+}
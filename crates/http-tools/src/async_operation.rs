@@ -0,0 +1,335 @@
+//! Long-running operations: `202 Accepted` followed by status polling.
+//!
+//! When a tool with `asyncOperation` gets a `202 Accepted`, the runtime finds the status URL in
+//! the response ([`locate_url`]) and polls it with `GET`. A [`Poller`] spaces the polls
+//! (exponential backoff capped at `maxPollIntervalMs`), stops waiting at `maxWaitMs` or shortly
+//! before the caller's deadline, classifies every status response ([`OperationState`]) and turns
+//! it into a progress update. Once the operation succeeded, the result is the final status
+//! response, or the document at `resultUrl`.
+
+use crate::config::{AsyncOperationConfig, UrlLocation};
+use crate::context::{CallContext, ProgressUpdate};
+use reqwest::header::HeaderMap;
+use serde_json::Value;
+use std::time::{Duration, Instant};
+
+/// Polling stops this long before the caller's deadline, so the call can still report why it
+/// stopped instead of being cut off by the caller's timeout. Short budgets keep
+/// `1 / DEADLINE_MARGIN_DIVISOR` of the budget instead.
+const DEADLINE_MARGIN: Duration = Duration::from_millis(500);
+const DEADLINE_MARGIN_DIVISOR: u32 = 4;
+
+/// State of an operation after a status response.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OperationState {
+    /// Still running; carries the state at `statePointer`, if any.
+    Running(Option<String>),
+    Succeeded,
+    /// Failed with this state.
+    Failed(String),
+}
+
+/// Check an async operation config.
+///
+/// # Errors
+///
+/// Returns a message for an invalid JSON pointer, a `statePointer` without `successStates`, or
+/// invalid polling intervals.
+pub fn validate(config: &AsyncOperationConfig) -> Result<(), String> {
+    let check_pointer = |field: &str, pointer: &str| {
+        if pointer.starts_with('/') {
+            Ok(())
+        } else {
+            Err(format!(
+                "asyncOperation {field} '{pointer}' must be a JSON pointer (e.g. '/status')"
+            ))
+        }
+    };
+
+    for (field, location) in [
+        ("statusUrl", Some(&config.status_url)),
+        ("resultUrl", config.result_url.as_ref()),
+    ] {
+        if let Some(UrlLocation::Pointer(pointer)) = location {
+            check_pointer(field, pointer)?;
+        }
+    }
+    if let Some(pointer) = &config.state_pointer {
+        check_pointer("statePointer", pointer)?;
+        if config.success_states.is_empty() {
+            return Err("asyncOperation statePointer requires successStates".to_string());
+        }
+    } else if !config.success_states.is_empty() || !config.failure_states.is_empty() {
+        return Err("asyncOperation successStates/failureStates require statePointer".to_string());
+    }
+    if let Some(pointer) = &config.progress_pointer {
+        check_pointer("progressPointer", pointer)?;
+    }
+    if config.poll_interval_ms == 0 || config.max_poll_interval_ms < config.poll_interval_ms {
+        return Err(
+            "asyncOperation pollIntervalMs must be at least 1 and at most maxPollIntervalMs"
+                .to_string(),
+        );
+    }
+    if !config.backoff_coefficient.is_finite() || config.backoff_coefficient < 1.0 {
+        return Err("asyncOperation backoffCoefficient must be at least 1".to_string());
+    }
+    Ok(())
+}
+
+/// The URL at `location` in a response (header value or string at a JSON pointer).
+#[must_use]
+pub fn locate_url(location: &UrlLocation, headers: &HeaderMap, body: &Value) -> Option<String> {
+    let url = match location {
+        UrlLocation::Header(name) => headers.get(name.as_str())?.to_str().ok()?,
+        UrlLocation::Pointer(pointer) => body.pointer(pointer)?.as_str()?,
+    };
+    let url = url.trim();
+    (!url.is_empty()).then(|| url.to_string())
+}
+
+/// Polling state of one operation.
+#[derive(Debug)]
+pub struct Poller<'a> {
+    config: &'a AsyncOperationConfig,
+    started: Instant,
+    deadline: Instant,
+    delay: Duration,
+    polls: u32,
+    progress: f64,
+}
+
+impl<'a> Poller<'a> {
+    #[must_use]
+    pub fn new(config: &'a AsyncOperationConfig, ctx: &CallContext) -> Self {
+        let started = Instant::now();
+        let max_wait = started + Duration::from_millis(config.max_wait_ms);
+        let deadline = ctx.deadline().map_or(max_wait, |d| {
+            let budget = d.saturating_duration_since(started);
+            max_wait.min(d - DEADLINE_MARGIN.min(budget / DEADLINE_MARGIN_DIVISOR))
+        });
+        Self {
+            config,
+            started,
+            deadline,
+            delay: Duration::from_millis(config.poll_interval_ms),
+            polls: 0,
+            progress: 0.0,
+        }
+    }
+
+    /// Delay before the next poll, or `None` when the poll would start after the deadline.
+    pub fn next_delay(&mut self) -> Option<Duration> {
+        let delay = self.delay;
+        if Instant::now() + delay >= self.deadline {
+            return None;
+        }
+        let max = Duration::from_millis(self.config.max_poll_interval_ms);
+        self.delay = delay.mul_f64(self.config.backoff_coefficient).min(max);
+        Some(delay)
+    }
+
+    /// Time left for a poll request.
+    #[must_use]
+    pub fn remaining(&self) -> Duration {
+        self.deadline.saturating_duration_since(Instant::now())
+    }
+
+    /// Time since the operation was accepted.
+    #[must_use]
+    pub fn elapsed(&self) -> Duration {
+        self.started.elapsed()
+    }
+
+    /// Classify a (successful) status response.
+    #[must_use]
+    pub fn state(&self, status: u16, body: &Value) -> OperationState {
+        let Some(pointer) = &self.config.state_pointer else {
+            return if status == 202 {
+                OperationState::Running(None)
+            } else {
+                OperationState::Succeeded
+            };
+        };
+        let state = match body.pointer(pointer) {
+            Some(Value::String(s)) => s.clone(),
+            Some(v @ (Value::Number(_) | Value::Bool(_))) => v.to_string(),
+            _ => return OperationState::Running(None),
+        };
+        if self.config.failure_states.contains(&state) {
+            OperationState::Failed(state)
+        } else if self.config.success_states.contains(&state) {
+            OperationState::Succeeded
+        } else {
+            OperationState::Running(Some(state))
+        }
+    }
+
+    /// Progress update for a status response of a running operation; `None` when the progress did
+    /// not increase (MCP requires every notification to advance it).
+    pub fn progress(&mut self, state: Option<&str>, body: &Value) -> Option<ProgressUpdate> {
+        self.polls += 1;
+        let reported = self
+            .config
+            .progress_pointer
+            .as_deref()
+            .and_then(|p| body.pointer(p))
+            .and_then(Value::as_f64);
+        let progress = match (&self.config.progress_pointer, reported) {
+            (Some(_), Some(value)) => value,
+            (Some(_), None) => self.progress,
+            (None, _) => f64::from(self.polls),
+        };
+        if progress <= self.progress {
+            return None;
+        }
+        self.progress = progress;
+        let message = match state {
+            Some(state) => format!("operation {state}"),
+            None => "operation running".to_string(),
+        };
+        Some(ProgressUpdate {
+            progress: self.progress,
+            total: self
+                .config
+                .progress_pointer
+                .as_ref()
+                .and(self.config.progress_total),
+            message: Some(message),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn config(v: Value) -> AsyncOperationConfig {
+        serde_json::from_value(v).expect("async operation config")
+    }
+
+    #[test]
+    fn locates_urls_in_headers_and_bodies() {
+        let mut headers = HeaderMap::new();
+        headers.insert("location", "/jobs/1".parse().unwrap());
+        let body = json!({ "links": { "status": "https://api.example.com/jobs/1" } });
+
+        assert_eq!(
+            locate_url(&UrlLocation::default(), &headers, &body).as_deref(),
+            Some("/jobs/1")
+        );
+        assert_eq!(
+            locate_url(
+                &UrlLocation::Pointer("/links/status".to_string()),
+                &headers,
+                &body
+            )
+            .as_deref(),
+            Some("https://api.example.com/jobs/1")
+        );
+        assert_eq!(
+            locate_url(
+                &UrlLocation::Pointer("/missing".to_string()),
+                &headers,
+                &body
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn classifies_status_responses() {
+        let by_status = config(json!({}));
+        let poller = Poller::new(&by_status, &CallContext::default());
+        assert_eq!(poller.state(202, &json!({})), OperationState::Running(None));
+        assert_eq!(poller.state(200, &json!({})), OperationState::Succeeded);
+
+        let by_state = config(json!({
+            "statePointer": "/status",
+            "successStates": ["done"],
+            "failureStates": ["failed", "cancelled"]
+        }));
+        let poller = Poller::new(&by_state, &CallContext::default());
+        assert_eq!(
+            poller.state(200, &json!({ "status": "queued" })),
+            OperationState::Running(Some("queued".to_string()))
+        );
+        assert_eq!(
+            poller.state(200, &json!({ "status": "done" })),
+            OperationState::Succeeded
+        );
+        assert_eq!(
+            poller.state(200, &json!({ "status": "cancelled" })),
+            OperationState::Failed("cancelled".to_string())
+        );
+    }
+
+    #[test]
+    fn backs_off_and_stops_before_the_deadline() {
+        let cfg = config(json!({
+            "pollIntervalMs": 100,
+            "backoffCoefficient": 2.0,
+            "maxPollIntervalMs": 300
+        }));
+        let mut poller = Poller::new(&cfg, &CallContext::default());
+        let delays: Vec<_> = (0..4).map(|_| poller.next_delay().unwrap()).collect();
+        assert_eq!(
+            delays,
+            [100, 200, 300, 300].map(Duration::from_millis).to_vec()
+        );
+
+        // Short budgets keep a proportional margin instead of leaving no time to poll.
+        let ctx = CallContext::with_timeout(Duration::from_millis(400));
+        let mut poller = Poller::new(&cfg, &ctx);
+        assert_eq!(poller.next_delay(), Some(Duration::from_millis(100)));
+        assert_eq!(poller.next_delay(), Some(Duration::from_millis(200)));
+        assert_eq!(poller.next_delay(), None);
+
+        let ctx = CallContext::with_timeout(Duration::from_secs(10));
+        let poller = Poller::new(&cfg, &ctx);
+        assert!(poller.remaining() <= Duration::from_millis(9_500));
+        assert!(poller.remaining() > Duration::from_millis(9_000));
+    }
+
+    #[test]
+    fn reports_monotonic_progress() {
+        let cfg = config(json!({ "progressPointer": "/percent", "progressTotal": 100 }));
+        let mut poller = Poller::new(&cfg, &CallContext::default());
+        let update = poller
+            .progress(None, &json!({ "percent": 40 }))
+            .expect("update");
+        assert_eq!(update.progress, 40.0);
+        assert_eq!(update.total, Some(100.0));
+        // Repeated, lower or missing values are not reported again.
+        assert_eq!(poller.progress(None, &json!({ "percent": 40 })), None);
+        assert_eq!(poller.progress(None, &json!({ "percent": 10 })), None);
+        assert_eq!(poller.progress(None, &json!({})), None);
+        let update = poller
+            .progress(None, &json!({ "percent": 55 }))
+            .expect("update");
+        assert_eq!(update.progress, 55.0);
+
+        let cfg = config(json!({}));
+        let mut poller = Poller::new(&cfg, &CallContext::default());
+        poller.progress(Some("queued"), &json!({}));
+        let update = poller
+            .progress(Some("running"), &json!({}))
+            .expect("update");
+        assert_eq!(update.progress, 2.0);
+        assert_eq!(update.message.as_deref(), Some("operation running"));
+    }
+
+    #[test]
+    fn validates_states_and_intervals() {
+        assert!(validate(&config(json!({}))).is_ok());
+        let err = validate(&config(json!({ "statePointer": "/status" }))).unwrap_err();
+        assert!(err.contains("successStates"), "{err}");
+        let err = validate(&config(json!({ "successStates": ["done"] }))).unwrap_err();
+        assert!(err.contains("statePointer"), "{err}");
+        let err = validate(&config(json!({ "statusUrl": { "pointer": "status" } }))).unwrap_err();
+        assert!(err.contains("statusUrl"), "{err}");
+        let err = validate(&config(json!({ "backoffCoefficient": 0.5 }))).unwrap_err();
+        assert!(err.contains("backoffCoefficient"), "{err}");
+    }
+}
@@ -0,0 +1,773 @@
+//! Request body encoding for HTTP tool calls.
+//!
+//! Body arguments arrive either as named fields (`in: body` params / flattened object properties)
+//! or as a single `body` payload. [`encode_body`] turns them into request bytes for a
+//! [`BodyEncoding`]:
+//! - `json`: the payload, or the fields as one object
+//! - `form`: fields as `application/x-www-form-urlencoded` (arrays repeat the key, objects are
+//!   sent as JSON text)
+//! - `multipart`: one `multipart/form-data` part per field (arrays become repeated parts); binary
+//!   fields become file parts, objects become `application/json` parts
+//! - `text`: the payload as text (non-string values as JSON text)
+//! - `binary`: the payload as raw bytes
+//! - `xml`: the payload, or the fields as one object, serialized as XML (see [`crate::xml`])
+//!
+//! Binary values ([`decode_binary_value`]) are accepted as a base64 string, a base64 `data:` URI,
+//! or MCP resource contents (`{ "uri", "mimeType", "blob" | "text" }`, optionally wrapped as an
+//! embedded resource `{ "type": "resource", "resource": {...} }`). A resource given only by its
+//! `uri` is read through the host first ([`inline_resource_refs`]); hosts without a resource reader
+//! reject it.
+
+use crate::config::BodyEncoding;
+use crate::context::CallContext;
+use crate::xml::{XmlBody, is_xml_media_type, json_to_xml, render_template};
+use base64::Engine as _;
+use serde_json::{Value, json};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+const FORM_CONTENT_TYPE: &str = "application/x-www-form-urlencoded";
+const OCTET_STREAM: &str = "application/octet-stream";
+
+/// An encoded request body.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EncodedBody {
+    pub content_type: String,
+    pub bytes: Vec<u8>,
+}
+
+/// Decoded binary tool argument.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BinaryValue {
+    pub bytes: Vec<u8>,
+    pub mime_type: Option<String>,
+    pub file_name: Option<String>,
+}
+
+impl BodyEncoding {
+    /// Encoding for an `OpenAPI` request body media type, if supported.
+    #[must_use]
+    pub fn for_media_type(media_type: &str) -> Option<Self> {
+        let essence = media_type
+            .split(';')
+            .next()
+            .unwrap_or_default()
+            .trim()
+            .to_ascii_lowercase();
+        let (kind, subtype) = essence.split_once('/')?;
+        Some(match (kind, subtype) {
+            ("application", "json") => Self::Json,
+            (_, sub) if sub.ends_with("+json") => Self::Json,
+            ("application", "x-www-form-urlencoded") => Self::Form,
+            ("multipart", "form-data") => Self::Multipart,
+            _ if is_xml_media_type(&essence) => Self::Xml,
+            ("text", _) => Self::Text,
+            ("application", "octet-stream" | "pdf" | "zip" | "gzip")
+            | ("image" | "audio" | "video", _)
+            | ("*", "*") => Self::Binary,
+            _ => return None,
+        })
+    }
+
+    /// Whether body params of this encoding are named fields (vs. a single `body` payload).
+    #[must_use]
+    pub fn has_fields(self) -> bool {
+        matches!(self, Self::Json | Self::Form | Self::Multipart | Self::Xml)
+    }
+}
+
+/// Whether a JSON Schema describes raw binary content (`format: binary`, or a
+/// `contentMediaType` without `contentEncoding`).
+#[must_use]
+pub fn is_binary_schema(schema: &Value) -> bool {
+    schema.get("format").and_then(Value::as_str) == Some("binary")
+        || (schema.get("contentMediaType").is_some() && schema.get("contentEncoding").is_none())
+}
+
+/// Tool argument schema for a binary value, keeping the original `description`.
+#[must_use]
+pub fn binary_value_schema(schema: &Value) -> Value {
+    let mut description = schema
+        .get("description")
+        .and_then(Value::as_str)
+        .map(|d| format!("{d} "))
+        .unwrap_or_default();
+    description.push_str(
+        "Binary content: a base64 string, a base64 data: URI, or MCP resource contents \
+         ({uri, mimeType, blob|text}).",
+    );
+    json!({
+        "description": description,
+        "anyOf": [
+            { "type": "string" },
+            {
+                "type": "object",
+                "properties": {
+                    "type": { "const": "resource" },
+                    "resource": { "type": "object" },
+                    "uri": { "type": "string" },
+                    "mimeType": { "type": "string" },
+                    "blob": { "type": "string" },
+                    "text": { "type": "string" }
+                }
+            }
+        ]
+    })
+}
+
+/// Decode a binary tool argument.
+///
+/// # Errors
+///
+/// Returns a message if the value is not valid base64, a base64 `data:` URI, or resource contents
+/// with inline `blob`/`text`.
+pub fn decode_binary_value(value: &Value) -> Result<BinaryValue, String> {
+    match value {
+        Value::String(s) => {
+            if let Some(rest) = s.strip_prefix("data:") {
+                let (meta, data) = rest
+                    .split_once(',')
+                    .ok_or_else(|| "invalid data: URI (missing ',')".to_string())?;
+                let Some(mime) = meta.strip_suffix(";base64") else {
+                    return Err("only base64 data: URIs are supported".to_string());
+                };
+                return Ok(BinaryValue {
+                    bytes: decode_base64(data)?,
+                    mime_type: (!mime.is_empty())
+                        .then(|| parse_mime_type(mime))
+                        .transpose()?,
+                    file_name: None,
+                });
+            }
+            Ok(BinaryValue {
+                bytes: decode_base64(s)?,
+                mime_type: None,
+                file_name: None,
+            })
+        }
+        Value::Object(map) => {
+            if map.get("type").and_then(Value::as_str) == Some("resource")
+                && let Some(resource) = map.get("resource")
+            {
+                return decode_binary_value(resource);
+            }
+            let uri = map.get("uri").and_then(Value::as_str).ok_or_else(|| {
+                "binary object values must be MCP resource contents with a 'uri'".to_string()
+            })?;
+            let bytes = if let Some(blob) = map.get("blob").and_then(Value::as_str) {
+                decode_base64(blob)?
+            } else if let Some(text) = map.get("text").and_then(Value::as_str) {
+                text.as_bytes().to_vec()
+            } else {
+                return Err(format!(
+                    "resource '{uri}' has no inline contents; pass the 'blob' or 'text' returned by resources/read"
+                ));
+            };
+            let file_name = uri
+                .rsplit('/')
+                .next()
+                .filter(|name| !name.is_empty() && !name.contains(':'))
+                .map(str::to_string);
+            Ok(BinaryValue {
+                bytes,
+                mime_type: map
+                    .get("mimeType")
+                    .and_then(Value::as_str)
+                    .map(parse_mime_type)
+                    .transpose()?,
+                file_name,
+            })
+        }
+        other => Err(format!(
+            "expected base64 string or resource contents for binary value, got {}",
+            json_type_name(other)
+        )),
+    }
+}
+
+/// Read the resources that binary arguments reference by URI only (`{ "uri" }`, bare or embedded)
+/// through the host ([`CallContext::read_resource`]), so [`encode_body`] gets their contents
+/// inline. An explicit `mimeType` on the argument wins over the one read.
+///
+/// # Errors
+///
+/// Returns a message if a resource cannot be read.
+pub async fn inline_resource_refs(
+    encoding: BodyEncoding,
+    payload: &mut Option<Value>,
+    fields: &mut HashMap<String, Value>,
+    binary_fields: &HashSet<String>,
+    ctx: &CallContext,
+) -> Result<(), String> {
+    match encoding {
+        BodyEncoding::Binary => {
+            if let Some(payload) = payload {
+                inline_resource_ref(payload, ctx).await?;
+            }
+        }
+        BodyEncoding::Multipart => {
+            let payload_fields = payload
+                .as_mut()
+                .and_then(Value::as_object_mut)
+                .into_iter()
+                .flat_map(|m| m.iter_mut());
+            for (name, value) in fields.iter_mut().chain(payload_fields) {
+                if !binary_fields.contains(name) {
+                    continue;
+                }
+                if let Value::Array(items) = value {
+                    for item in items {
+                        inline_resource_ref(item, ctx)
+                            .await
+                            .map_err(|e| format!("field '{name}': {e}"))?;
+                    }
+                } else {
+                    inline_resource_ref(value, ctx)
+                        .await
+                        .map_err(|e| format!("field '{name}': {e}"))?;
+                }
+            }
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+async fn inline_resource_ref(value: &mut Value, ctx: &CallContext) -> Result<(), String> {
+    let Value::Object(map) = value else {
+        return Ok(());
+    };
+    let target = if map.get("type").and_then(Value::as_str) == Some("resource") {
+        match map.get_mut("resource") {
+            Some(Value::Object(resource)) => resource,
+            _ => return Ok(()),
+        }
+    } else {
+        map
+    };
+    if target.contains_key("blob") || target.contains_key("text") {
+        return Ok(());
+    }
+    let Some(uri) = target
+        .get("uri")
+        .and_then(Value::as_str)
+        .map(str::to_string)
+    else {
+        return Ok(());
+    };
+    let Value::Object(contents) = ctx.read_resource(&uri).await? else {
+        return Err(format!("resource '{uri}' did not return resource contents"));
+    };
+    for key in ["blob", "text", "mimeType"] {
+        if let Some(v) = contents.get(key) {
+            target.entry(key).or_insert_with(|| v.clone());
+        }
+    }
+    if !target.contains_key("blob") && !target.contains_key("text") {
+        return Err(format!("resource '{uri}' has no 'blob' or 'text' contents"));
+    }
+    Ok(())
+}
+
+/// Validate a client-supplied media type before it is written into request or part headers.
+fn parse_mime_type(raw: &str) -> Result<String, String> {
+    raw.parse::<mime::Mime>()
+        .map(|m| m.to_string())
+        .map_err(|_| format!("invalid mimeType {raw:?}"))
+}
+
+/// Encode body arguments.
+///
+/// `content_type` is the declared media type (e.g. from the `OpenAPI` spec); when absent, the
+/// encoding's default is used. `binary_fields` names the fields sent as file parts (`multipart`);
+/// `xml` describes `xml` bodies (defaults apply when absent).
+///
+/// Returns `None` when there are no body arguments.
+///
+/// # Errors
+///
+/// Returns a message if the arguments do not fit the encoding (e.g. named fields for `text`) or a
+/// binary value cannot be decoded.
+pub fn encode_body(
+    encoding: BodyEncoding,
+    content_type: Option<&str>,
+    payload: Option<&Value>,
+    fields: &HashMap<String, Value>,
+    binary_fields: &HashSet<String>,
+    xml: Option<&XmlBody>,
+) -> Result<Option<EncodedBody>, String> {
+    if payload.is_none() && fields.is_empty() {
+        return Ok(None);
+    }
+
+    let body = match encoding {
+        BodyEncoding::Json => {
+            let bytes = match payload {
+                Some(payload) => serde_json::to_vec(payload),
+                None => serde_json::to_vec(fields),
+            }
+            .map_err(|e| e.to_string())?;
+            let content_type = content_type
+                .filter(|ct| BodyEncoding::for_media_type(ct) == Some(BodyEncoding::Json))
+                .unwrap_or("application/json");
+            EncodedBody {
+                content_type: content_type.to_string(),
+                bytes,
+            }
+        }
+        BodyEncoding::Form => {
+            let fields = field_entries(payload, fields)?;
+            let mut form = url::form_urlencoded::Serializer::new(String::new());
+            for (name, value) in fields {
+                for item in repeated(value) {
+                    if !item.is_null() {
+                        form.append_pair(name, &field_text(item));
+                    }
+                }
+            }
+            EncodedBody {
+                content_type: FORM_CONTENT_TYPE.to_string(),
+                bytes: form.finish().into_bytes(),
+            }
+        }
+        BodyEncoding::Multipart => {
+            encode_multipart(&field_entries(payload, fields)?, binary_fields)?
+        }
+        BodyEncoding::Text => {
+            let payload = single_payload(encoding, payload, fields)?;
+            let text = match payload {
+                Value::String(s) => s.clone(),
+                other => other.to_string(),
+            };
+            EncodedBody {
+                content_type: content_type
+                    .unwrap_or("text/plain; charset=utf-8")
+                    .to_string(),
+                bytes: text.into_bytes(),
+            }
+        }
+        BodyEncoding::Binary => {
+            let value = decode_binary_value(single_payload(encoding, payload, fields)?)?;
+            let content_type = content_type
+                .filter(|ct| !ct.contains('*'))
+                .map(str::to_string)
+                .or(value.mime_type)
+                .unwrap_or_else(|| OCTET_STREAM.to_string());
+            EncodedBody {
+                content_type,
+                bytes: value.bytes,
+            }
+        }
+        BodyEncoding::Xml => {
+            let default = XmlBody::default();
+            let xml = xml.unwrap_or(&default);
+            let text = if let Some(template) = &xml.template {
+                let mut args = fields.clone();
+                if let Some(payload) = payload {
+                    args.insert("body".to_string(), payload.clone());
+                }
+                render_template(template, &args)?
+            } else {
+                match payload {
+                    Some(payload) => json_to_xml(payload, xml)?,
+                    None => json_to_xml(
+                        &serde_json::to_value(fields).map_err(|e| e.to_string())?,
+                        xml,
+                    )?,
+                }
+            };
+            EncodedBody {
+                content_type: content_type
+                    .filter(|ct| is_xml_media_type(ct))
+                    .unwrap_or("application/xml")
+                    .to_string(),
+                bytes: text.into_bytes(),
+            }
+        }
+    };
+    Ok(Some(body))
+}
+
+/// Named fields in a stable (sorted) order; an object payload contributes its properties.
+fn field_entries<'a>(
+    payload: Option<&'a Value>,
+    fields: &'a HashMap<String, Value>,
+) -> Result<BTreeMap<&'a str, &'a Value>, String> {
+    let mut out: BTreeMap<&str, &Value> = fields.iter().map(|(k, v)| (k.as_str(), v)).collect();
+    match payload {
+        None => {}
+        Some(Value::Object(map)) => out.extend(map.iter().map(|(k, v)| (k.as_str(), v))),
+        Some(other) => {
+            return Err(format!(
+                "form and multipart bodies need an object, got {}",
+                json_type_name(other)
+            ));
+        }
+    }
+    Ok(out)
+}
+
+fn single_payload<'a>(
+    encoding: BodyEncoding,
+    payload: Option<&'a Value>,
+    fields: &HashMap<String, Value>,
+) -> Result<&'a Value, String> {
+    match payload {
+        Some(payload) if fields.is_empty() => Ok(payload),
+        _ => Err(format!(
+            "{} bodies take a single 'body' argument",
+            match encoding {
+                BodyEncoding::Text => "text",
+                _ => "binary",
+            }
+        )),
+    }
+}
+
+/// Array items are sent as repeated fields/parts.
+fn repeated(value: &Value) -> Vec<&Value> {
+    match value {
+        Value::Array(items) => items.iter().collect(),
+        other => vec![other],
+    }
+}
+
+fn field_text(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+fn encode_multipart(
+    fields: &BTreeMap<&str, &Value>,
+    binary_fields: &HashSet<String>,
+) -> Result<EncodedBody, String> {
+    struct Part {
+        headers: String,
+        bytes: Vec<u8>,
+    }
+
+    let mut parts: Vec<Part> = Vec::new();
+    for (name, value) in fields {
+        let binary = binary_fields.contains(*name);
+        for item in repeated(value) {
+            if item.is_null() {
+                continue;
+            }
+            let mut headers = format!(
+                "Content-Disposition: form-data; name=\"{}\"",
+                escape_quoted(name)
+            );
+            let bytes = if binary {
+                let value =
+                    decode_binary_value(item).map_err(|e| format!("field '{name}': {e}"))?;
+                let file_name = value.file_name.as_deref().unwrap_or(name);
+                headers.push_str(&format!("; filename=\"{}\"", escape_quoted(file_name)));
+                headers.push_str(&format!(
+                    "\r\nContent-Type: {}",
+                    value.mime_type.as_deref().unwrap_or(OCTET_STREAM)
+                ));
+                value.bytes
+            } else if item.is_object() || item.is_array() {
+                headers.push_str("\r\nContent-Type: application/json");
+                item.to_string().into_bytes()
+            } else {
+                field_text(item).into_bytes()
+            };
+            parts.push(Part { headers, bytes });
+        }
+    }
+
+    let boundary = loop {
+        let candidate = next_boundary();
+        let needle = candidate.as_bytes();
+        if !parts
+            .iter()
+            .any(|p| p.bytes.windows(needle.len()).any(|w| w == needle))
+        {
+            break candidate;
+        }
+    };
+
+    let mut bytes = Vec::new();
+    for part in parts {
+        bytes.extend_from_slice(format!("--{boundary}\r\n{}\r\n\r\n", part.headers).as_bytes());
+        bytes.extend_from_slice(&part.bytes);
+        bytes.extend_from_slice(b"\r\n");
+    }
+    bytes.extend_from_slice(format!("--{boundary}--\r\n").as_bytes());
+    Ok(EncodedBody {
+        content_type: format!("multipart/form-data; boundary={boundary}"),
+        bytes,
+    })
+}
+
+fn next_boundary() -> String {
+    static COUNTER: AtomicU64 = AtomicU64::new(0);
+    let nanos = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |d| d.as_nanos());
+    let n = COUNTER.fetch_add(1, Ordering::Relaxed);
+    format!("unrelated-boundary-{nanos:x}-{n:x}")
+}
+
+/// Escape a `Content-Disposition` quoted-string value (as browsers do for form data).
+fn escape_quoted(s: &str) -> String {
+    s.replace('"', "%22")
+        .replace('\r', "%0D")
+        .replace('\n', "%0A")
+}
+
+fn decode_base64(s: &str) -> Result<Vec<u8>, String> {
+    let compact: String = s.chars().filter(|c| !c.is_ascii_whitespace()).collect();
+    base64::engine::general_purpose::STANDARD
+        .decode(&compact)
+        .or_else(|_| base64::engine::general_purpose::URL_SAFE.decode(&compact))
+        .or_else(|_| base64::engine::general_purpose::STANDARD_NO_PAD.decode(&compact))
+        .or_else(|_| base64::engine::general_purpose::URL_SAFE_NO_PAD.decode(&compact))
+        .map_err(|e| format!("invalid base64: {e}"))
+}
+
+fn json_type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fields(v: Value) -> HashMap<String, Value> {
+        serde_json::from_value(v).unwrap()
+    }
+
+    #[test]
+    fn maps_media_types_to_encodings() {
+        for (media_type, expected) in [
+            ("application/json", Some(BodyEncoding::Json)),
+            ("application/merge-patch+json", Some(BodyEncoding::Json)),
+            (
+                "application/x-www-form-urlencoded",
+                Some(BodyEncoding::Form),
+            ),
+            ("multipart/form-data", Some(BodyEncoding::Multipart)),
+            ("text/plain; charset=utf-8", Some(BodyEncoding::Text)),
+            ("application/octet-stream", Some(BodyEncoding::Binary)),
+            ("image/png", Some(BodyEncoding::Binary)),
+            ("application/xml", Some(BodyEncoding::Xml)),
+            ("text/xml", Some(BodyEncoding::Xml)),
+            ("application/vnd.custom", None),
+        ] {
+            assert_eq!(
+                BodyEncoding::for_media_type(media_type),
+                expected,
+                "{media_type}"
+            );
+        }
+    }
+
+    #[test]
+    fn encodes_form_fields_in_stable_order() {
+        let body = encode_body(
+            BodyEncoding::Form,
+            None,
+            None,
+            &fields(json!({ "b": [1, 2], "a": "x y", "c": { "k": true }, "d": null })),
+            &HashSet::new(),
+            None,
+        )
+        .unwrap()
+        .unwrap();
+        assert_eq!(body.content_type, FORM_CONTENT_TYPE);
+        assert_eq!(
+            String::from_utf8(body.bytes).unwrap(),
+            "a=x+y&b=1&b=2&c=%7B%22k%22%3Atrue%7D"
+        );
+    }
+
+    #[test]
+    fn encodes_multipart_with_file_parts() {
+        let body = encode_body(
+            BodyEncoding::Multipart,
+            Some("multipart/form-data"),
+            None,
+            &fields(json!({
+                "file": { "uri": "file:///tmp/pet.png", "mimeType": "image/png", "blob": "iVBORw==" },
+                "meta": { "tag": "x" },
+                "name": "rex",
+            })),
+            &HashSet::from(["file".to_string()]),
+            None,
+        )
+        .unwrap()
+        .unwrap();
+        let boundary = body
+            .content_type
+            .strip_prefix("multipart/form-data; boundary=")
+            .unwrap()
+            .to_string();
+        let mut expected = format!(
+            "--{boundary}\r\nContent-Disposition: form-data; name=\"file\"; filename=\"pet.png\"\r\nContent-Type: image/png\r\n\r\n"
+        )
+        .into_bytes();
+        expected.extend_from_slice(&[0x89, b'P', b'N', b'G']);
+        expected.extend_from_slice(
+            format!(
+                "\r\n--{boundary}\r\nContent-Disposition: form-data; name=\"meta\"\r\nContent-Type: application/json\r\n\r\n{{\"tag\":\"x\"}}\r\n\
+                 --{boundary}\r\nContent-Disposition: form-data; name=\"name\"\r\n\r\nrex\r\n--{boundary}--\r\n"
+            )
+            .as_bytes(),
+        );
+        assert_eq!(body.bytes, expected);
+    }
+
+    #[test]
+    fn rejects_mime_types_that_would_inject_part_headers() {
+        let err = encode_body(
+            BodyEncoding::Multipart,
+            Some("multipart/form-data"),
+            None,
+            &fields(json!({
+                "file": {
+                    "uri": "file:///tmp/a.txt",
+                    "mimeType": "text/plain\r\n\r\n--boundary\r\nContent-Disposition: form-data; name=\"admin\"",
+                    "text": "hi"
+                },
+            })),
+            &HashSet::from(["file".to_string()]),
+            None,
+        )
+        .unwrap_err();
+        assert!(err.starts_with("field 'file': invalid mimeType"), "{err}");
+
+        let err =
+            decode_binary_value(&json!("data:text/plain\r\nX-Evil: 1;base64,aGk=")).unwrap_err();
+        assert!(err.starts_with("invalid mimeType"), "{err}");
+    }
+
+    #[test]
+    fn encodes_text_and_binary_payloads() {
+        let none = HashMap::new();
+        let text = encode_body(
+            BodyEncoding::Text,
+            None,
+            Some(&json!("hello")),
+            &none,
+            &HashSet::new(),
+            None,
+        )
+        .unwrap()
+        .unwrap();
+        assert_eq!(text.content_type, "text/plain; charset=utf-8");
+        assert_eq!(text.bytes, b"hello");
+
+        let binary = encode_body(
+            BodyEncoding::Binary,
+            Some("*/*"),
+            Some(&json!("data:application/pdf;base64,JVBERg==")),
+            &none,
+            &HashSet::new(),
+            None,
+        )
+        .unwrap()
+        .unwrap();
+        assert_eq!(binary.content_type, "application/pdf");
+        assert_eq!(binary.bytes, b"%PDF");
+
+        let err = encode_body(
+            BodyEncoding::Text,
+            None,
+            None,
+            &fields(json!({ "a": 1 })),
+            &HashSet::new(),
+            None,
+        )
+        .unwrap_err();
+        assert_eq!(err, "text bodies take a single 'body' argument");
+    }
+
+    #[test]
+    fn decodes_embedded_resources_and_rejects_uri_only_values() {
+        let value = decode_binary_value(&json!({
+            "type": "resource",
+            "resource": { "uri": "mem://notes/a.txt", "text": "hi" }
+        }))
+        .unwrap();
+        assert_eq!(value.bytes, b"hi");
+        assert_eq!(value.file_name.as_deref(), Some("a.txt"));
+
+        let err = decode_binary_value(&json!({ "uri": "mem://x" })).unwrap_err();
+        assert!(err.contains("no inline contents"), "{err}");
+    }
+
+    #[tokio::test]
+    async fn reads_uri_only_resources_through_the_host() {
+        let reader: crate::context::ResourceReader = std::sync::Arc::new(|uri: String| {
+            Box::pin(async move {
+                match uri.as_str() {
+                    "mem://pets/rex.png" => {
+                        Ok(json!({ "uri": uri, "mimeType": "image/png", "blob": "iVBORw==" }))
+                    }
+                    _ => Err("not found".to_string()),
+                }
+            })
+        });
+        let ctx = CallContext::default().with_resource_reader(reader);
+        let binary = HashSet::from(["file".to_string()]);
+
+        let mut payload = Some(json!({
+            "file": [
+                { "uri": "mem://pets/rex.png" },
+                { "type": "resource", "resource": { "uri": "mem://pets/rex.png", "mimeType": "image/x-png" } }
+            ]
+        }));
+        let mut fields = HashMap::new();
+        inline_resource_refs(
+            BodyEncoding::Multipart,
+            &mut payload,
+            &mut fields,
+            &binary,
+            &ctx,
+        )
+        .await
+        .unwrap();
+        let files = &payload.as_ref().unwrap()["file"];
+        let first = decode_binary_value(&files[0]).unwrap();
+        assert_eq!(first.bytes, [0x89, b'P', b'N', b'G']);
+        assert_eq!(first.mime_type.as_deref(), Some("image/png"));
+        assert_eq!(first.file_name.as_deref(), Some("rex.png"));
+        let second = decode_binary_value(&files[1]).unwrap();
+        assert_eq!(second.mime_type.as_deref(), Some("image/x-png"));
+
+        let mut payload = Some(json!({ "uri": "mem://missing" }));
+        let err = inline_resource_refs(
+            BodyEncoding::Binary,
+            &mut payload,
+            &mut fields,
+            &HashSet::new(),
+            &ctx,
+        )
+        .await
+        .unwrap_err();
+        assert!(err.contains("not found"), "{err}");
+
+        let mut payload = Some(json!({ "uri": "mem://pets/rex.png" }));
+        let err = inline_resource_refs(
+            BodyEncoding::Binary,
+            &mut payload,
+            &mut fields,
+            &HashSet::new(),
+            &CallContext::default(),
+        )
+        .await
+        .unwrap_err();
+        assert!(err.contains("no inline contents"), "{err}");
+    }
+}
@@ -15,6 +15,55 @@ pub enum AuthConfig {
     Basic { username: String, password: String },
     /// Query parameter authentication.
     Query { name: String, value: String },
+    /// AWS Signature Version 4 (see [`crate::signing`]).
+    #[serde(rename_all = "camelCase")]
+    Sigv4 {
+        access_key_id: String,
+        secret_access_key: String,
+        /// Session token of temporary credentials (sent as `X-Amz-Security-Token`).
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        session_token: Option<String>,
+        region: String,
+        service: String,
+    },
+    /// HMAC-SHA256 signature over method, path, timestamp and body digest (see
+    /// [`crate::signing`]).
+    #[serde(rename_all = "camelCase")]
+    Hmac {
+        secret: String,
+        /// Sent in `keyIdHeader` so the server can pick the secret.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        key_id: Option<String>,
+        #[serde(default = "default_signature_header")]
+        signature_header: String,
+        #[serde(default = "default_timestamp_header")]
+        timestamp_header: String,
+        #[serde(default = "default_key_id_header")]
+        key_id_header: String,
+        #[serde(default)]
+        encoding: SignatureEncoding,
+    },
+}
+
+fn default_signature_header() -> String {
+    "X-Signature".to_string()
+}
+
+fn default_timestamp_header() -> String {
+    "X-Timestamp".to_string()
+}
+
+fn default_key_id_header() -> String {
+    "X-Key-Id".to_string()
+}
+
+/// Encoding of an HMAC signature.
+#[derive(Debug, Clone, Copy, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum SignatureEncoding {
+    #[default]
+    Hex,
+    Base64,
 }
 
 /// Default settings for endpoints/tools.
@@ -35,6 +84,81 @@ pub struct EndpointDefaults {
     /// Additional headers applied to every request.
     #[serde(default)]
     pub headers: HashMap<String, String>,
+
+    /// Retry transient failures of every request (tools may override with `retry`).
+    #[serde(default)]
+    pub retry: Option<RetryConfig>,
+}
+
+/// Retries of transient failures (see [`crate::retry`]).
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct RetryConfig {
+    /// Maximum number of attempts, including the first one (default: 3).
+    #[serde(default = "default_retry_max_attempts")]
+    pub max_attempts: u32,
+    /// Delay before the first retry in milliseconds (default: 200).
+    #[serde(default = "default_retry_initial_interval_ms")]
+    pub initial_interval_ms: u64,
+    /// Delay multiplier applied after every retry (default: 2).
+    #[serde(default = "default_retry_backoff")]
+    pub backoff_coefficient: f64,
+    /// Maximum delay between attempts in milliseconds (default: 5000).
+    #[serde(default = "default_retry_max_interval_ms")]
+    pub max_interval_ms: u64,
+    /// Response statuses that are retried (default: 408, 429, 502, 503, 504).
+    #[serde(default = "default_retry_statuses")]
+    pub statuses: Vec<u16>,
+    /// Transport errors that are retried (default: connect, timeout).
+    #[serde(default = "default_retry_transport_errors")]
+    pub transport_errors: Vec<TransportErrorKind>,
+    /// Also retry non-idempotent methods (`POST`, `PATCH`).
+    #[serde(default)]
+    pub retry_non_idempotent: bool,
+}
+
+fn default_retry_max_attempts() -> u32 {
+    3
+}
+
+fn default_retry_initial_interval_ms() -> u64 {
+    200
+}
+
+fn default_retry_backoff() -> f64 {
+    2.0
+}
+
+fn default_retry_max_interval_ms() -> u64 {
+    5000
+}
+
+fn default_retry_statuses() -> Vec<u16> {
+    vec![408, 429, 502, 503, 504]
+}
+
+fn default_retry_transport_errors() -> Vec<TransportErrorKind> {
+    vec![TransportErrorKind::Connect, TransportErrorKind::Timeout]
+}
+
+/// Retryable transport failure.
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum TransportErrorKind {
+    /// The connection could not be established.
+    Connect,
+    /// The attempt timed out.
+    Timeout,
+}
+
+impl TransportErrorKind {
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Connect => "connect",
+            Self::Timeout => "timeout",
+        }
+    }
 }
 
 /// Array serialization style (query parameters).
@@ -68,6 +192,9 @@ pub struct HttpServerConfig {
     /// - These transforms apply to the HTTP response body (what becomes `structured_content.body`).
     #[serde(default)]
     pub response_transforms: Vec<ResponseTransform>,
+    /// XML ↔ JSON conventions for `xml` bodies and XML responses.
+    #[serde(default)]
+    pub xml: XmlConfig,
     #[serde(default)]
     pub tools: HashMap<String, HttpToolConfig>,
 }
@@ -82,10 +209,200 @@ pub struct HttpToolConfig {
     pub description: Option<String>,
     #[serde(default)]
     pub params: HashMap<String, HttpParamConfig>,
+    /// How `in: body` params are encoded into the request body.
+    #[serde(default)]
+    pub body_encoding: BodyEncoding,
+    /// XML body template for `bodyEncoding: xml`; `{{name}}` placeholders are replaced by the
+    /// (escaped) values of body params.
+    #[serde(default)]
+    pub body_template: Option<String>,
+    /// Follow paginated responses and concatenate their items.
+    #[serde(default)]
+    pub pagination: Option<PaginationConfig>,
+    /// Poll `202 Accepted` responses until the operation finishes.
+    #[serde(default)]
+    pub async_operation: Option<AsyncOperationConfig>,
+    /// Retry transient failures (replaces `defaults.retry`).
+    #[serde(default)]
+    pub retry: Option<RetryConfig>,
     #[serde(default)]
     pub response: HttpResponseConfig,
 }
 
+/// Request body encoding (see [`crate::body`]).
+#[derive(Debug, Clone, Copy, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum BodyEncoding {
+    /// `application/json`: body params as one JSON object (or the `body` param as-is).
+    #[default]
+    Json,
+    /// `application/x-www-form-urlencoded`.
+    Form,
+    /// `multipart/form-data`: one part per body param; binary params become file parts.
+    Multipart,
+    /// `text/plain`: the `body` param as text.
+    Text,
+    /// `application/octet-stream`: the `body` param as binary content.
+    Binary,
+    /// `application/xml`: body params serialized as XML (see [`crate::xml`]).
+    Xml,
+}
+
+/// XML ↔ JSON conventions (see [`crate::xml`]).
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase", default)]
+pub struct XmlConfig {
+    /// Prefix of JSON keys holding XML attributes (default `@`).
+    pub attribute_prefix: String,
+    /// JSON key holding an element's text when it also has attributes or child elements
+    /// (default `#text`).
+    pub text_key: String,
+}
+
+impl Default for XmlConfig {
+    fn default() -> Self {
+        Self {
+            attribute_prefix: "@".to_string(),
+            text_key: "#text".to_string(),
+        }
+    }
+}
+
+/// Automatic pagination (see [`crate::pagination`]).
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct PaginationConfig {
+    /// How the next page is requested.
+    #[serde(flatten)]
+    pub strategy: PaginationStrategy,
+    /// JSON pointer to the item array of a page (default: the page is the array).
+    #[serde(default)]
+    pub items_pointer: String,
+    /// Maximum number of pages fetched per call (default: 10).
+    #[serde(default = "default_max_pages")]
+    pub max_pages: usize,
+    /// Maximum number of items returned per call (default: 1000).
+    #[serde(default = "default_max_items")]
+    pub max_items: usize,
+}
+
+fn default_max_pages() -> usize {
+    10
+}
+
+fn default_max_items() -> usize {
+    1000
+}
+
+/// How the next page is requested. `param` names a tool param; it is set on the arguments of the
+/// next request (so the param's location applies).
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(
+    tag = "type",
+    rename_all = "camelCase",
+    rename_all_fields = "camelCase"
+)]
+pub enum PaginationStrategy {
+    /// Follow the `Link: <url>; rel="next"` response header.
+    LinkHeader,
+    /// Send the cursor found at `cursorPointer` in the page as `param`; stops when it is absent.
+    Cursor {
+        cursor_pointer: String,
+        param: String,
+    },
+    /// Advance `param` by the number of items received; stops on an empty page or a page shorter
+    /// than the `limitParam` argument.
+    Offset {
+        param: String,
+        #[serde(default)]
+        limit_param: Option<String>,
+    },
+    /// Increment `param` (starting at `start`, default 1); stops on an empty page.
+    Page {
+        param: String,
+        #[serde(default = "default_first_page")]
+        start: u64,
+    },
+}
+
+fn default_first_page() -> u64 {
+    1
+}
+
+/// Long-running operations answered with `202 Accepted` (see [`crate::async_operation`]).
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct AsyncOperationConfig {
+    /// Where the status URL is found in the `202` response (default: the `Location` header).
+    #[serde(default)]
+    pub status_url: UrlLocation,
+    /// JSON pointer to the operation state in status responses. Without it, a status response
+    /// other than `202` ends the operation.
+    #[serde(default)]
+    pub state_pointer: Option<String>,
+    /// States (at `statePointer`) of a successfully finished operation.
+    #[serde(default)]
+    pub success_states: Vec<String>,
+    /// States (at `statePointer`) of a failed operation.
+    #[serde(default)]
+    pub failure_states: Vec<String>,
+    /// Where the result URL is found in the final status response; without it, the final status
+    /// response is the result.
+    #[serde(default)]
+    pub result_url: Option<UrlLocation>,
+    /// JSON pointer to a numeric progress value in status responses.
+    #[serde(default)]
+    pub progress_pointer: Option<String>,
+    /// Total of `progressPointer` values (e.g. `100` for percentages).
+    #[serde(default)]
+    pub progress_total: Option<f64>,
+    /// Delay before the first poll in milliseconds (default: 1000).
+    #[serde(default = "default_poll_interval_ms")]
+    pub poll_interval_ms: u64,
+    /// Delay multiplier applied after every poll (default: 1.5).
+    #[serde(default = "default_poll_backoff")]
+    pub backoff_coefficient: f64,
+    /// Maximum delay between polls in milliseconds (default: 10000).
+    #[serde(default = "default_max_poll_interval_ms")]
+    pub max_poll_interval_ms: u64,
+    /// Maximum time to wait for the operation in milliseconds (default: 300000); the caller's
+    /// timeout budget may end the wait earlier.
+    #[serde(default = "default_max_wait_ms")]
+    pub max_wait_ms: u64,
+}
+
+fn default_poll_interval_ms() -> u64 {
+    1000
+}
+
+fn default_poll_backoff() -> f64 {
+    1.5
+}
+
+fn default_max_poll_interval_ms() -> u64 {
+    10_000
+}
+
+fn default_max_wait_ms() -> u64 {
+    300_000
+}
+
+/// Where a URL is found in a response.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum UrlLocation {
+    /// A response header (e.g. `Location`).
+    Header(String),
+    /// A JSON pointer into the response body.
+    Pointer(String),
+}
+
+impl Default for UrlLocation {
+    fn default() -> Self {
+        Self::Header("Location".to_string())
+    }
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct HttpResponseConfig {
@@ -109,6 +426,34 @@ pub struct HttpResponseConfig {
     /// - `mode: append` can be used to apply the server pipeline first, then the tool pipeline
     #[serde(default)]
     pub transforms: Option<ResponseTransformChainConfig>,
+    /// How streaming responses (SSE / NDJSON) are combined into the result.
+    #[serde(default)]
+    pub stream: Option<StreamConfig>,
+}
+
+/// Streaming responses (see [`crate::streaming`]).
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct StreamConfig {
+    /// How events are combined (default: `array`).
+    #[serde(default)]
+    pub aggregate: StreamAggregation,
+    /// JSON pointer selecting the part of each (JSON) event that is kept.
+    #[serde(default)]
+    pub pointer: Option<String>,
+}
+
+/// How the events of a streaming response become the result.
+#[derive(Debug, Clone, Copy, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum StreamAggregation {
+    /// The last event.
+    Last,
+    /// The events concatenated as text.
+    Concat,
+    /// An array of all events.
+    #[default]
+    Array,
 }
 
 // ============================================================================
@@ -0,0 +1,179 @@
+//! Per-call context for tool calls: the caller's deadline, progress reporting, resource reads and
+//! the number of HTTP attempts made.
+//!
+//! Hosts (adapter, gateway) build a [`CallContext`] from the MCP request: the deadline from the
+//! tool call timeout budget, a [`ProgressSink`] when the client sent a `progressToken`, and a
+//! [`ResourceReader`] when binary arguments may reference MCP resources by URI. The runtimes use
+//! it for work that spans several HTTP requests (e.g. polling long-running operations).
+
+use serde_json::Value;
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU32, AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+
+/// One progress update, forwarded as MCP `notifications/progress` by the host.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProgressUpdate {
+    /// Increases with every update.
+    pub progress: f64,
+    pub total: Option<f64>,
+    pub message: Option<String>,
+}
+
+/// Receives progress updates of one call.
+pub type ProgressSink = Arc<dyn Fn(ProgressUpdate) + Send + Sync>;
+
+/// Reads an MCP resource by URI (`resources/read` on the caller's session) and returns its
+/// contents (`{ "uri", "mimeType", "blob" | "text" }`).
+pub type ResourceReader = Arc<
+    dyn Fn(String) -> Pin<Box<dyn Future<Output = Result<Value, String>> + Send>> + Send + Sync,
+>;
+
+/// Deadline, progress reporting and attempt count of one tool call.
+#[derive(Clone, Default)]
+pub struct CallContext {
+    deadline: Option<Instant>,
+    progress: Option<ProgressSink>,
+    resources: Option<ResourceReader>,
+    /// Highest progress value reported so far (`f64` bits), shared by every page of the call.
+    last_progress: Arc<AtomicU64>,
+    attempts: Arc<AtomicU32>,
+}
+
+impl CallContext {
+    /// A context whose deadline is `timeout` from now.
+    #[must_use]
+    pub fn with_timeout(timeout: Duration) -> Self {
+        Self {
+            deadline: Instant::now().checked_add(timeout),
+            ..Self::default()
+        }
+    }
+
+    /// Report progress to `sink`.
+    #[must_use]
+    pub fn with_progress(mut self, sink: ProgressSink) -> Self {
+        self.progress = Some(sink);
+        self
+    }
+
+    /// Resolve resource URIs in binary arguments with `reader`.
+    #[must_use]
+    pub fn with_resource_reader(mut self, reader: ResourceReader) -> Self {
+        self.resources = Some(reader);
+        self
+    }
+
+    /// Read a resource through the host.
+    ///
+    /// # Errors
+    ///
+    /// Returns a message when the host cannot read resources or the read fails.
+    pub async fn read_resource(&self, uri: &str) -> Result<Value, String> {
+        let Some(reader) = &self.resources else {
+            return Err(format!(
+                "resource '{uri}' has no inline contents; pass the 'blob' or 'text' returned by resources/read"
+            ));
+        };
+        reader(uri.to_string())
+            .await
+            .map_err(|e| format!("read resource '{uri}': {e}"))
+    }
+
+    #[must_use]
+    pub fn deadline(&self) -> Option<Instant> {
+        self.deadline
+    }
+
+    /// Time left until the deadline (zero once it has passed).
+    #[must_use]
+    pub fn remaining(&self) -> Option<Duration> {
+        self.deadline
+            .map(|d| d.saturating_duration_since(Instant::now()))
+    }
+
+    /// Whether progress updates are consumed.
+    #[must_use]
+    pub fn reports_progress(&self) -> bool {
+        self.progress.is_some()
+    }
+
+    pub fn report(&self, update: ProgressUpdate) {
+        if let Some(sink) = &self.progress {
+            let _ = self
+                .last_progress
+                .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |last| {
+                    (update.progress > f64::from_bits(last)).then_some(update.progress.to_bits())
+                });
+            sink(update);
+        }
+    }
+
+    /// Report one step past the highest progress reported so far, so counters kept across
+    /// pages, polls and streamed events of one call keep increasing.
+    pub fn report_step(&self, message: Option<String>) {
+        let Some(sink) = &self.progress else {
+            return;
+        };
+        let previous = self
+            .last_progress
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |last| {
+                Some((f64::from_bits(last).floor() + 1.0).to_bits())
+            })
+            .unwrap_or_default();
+        sink(ProgressUpdate {
+            progress: f64::from_bits(previous).floor() + 1.0,
+            total: None,
+            message,
+        });
+    }
+
+    /// Count one outbound HTTP attempt (see [`crate::retry`]).
+    pub fn record_attempt(&self) {
+        self.attempts.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// HTTP attempts made so far, retries included.
+    #[must_use]
+    pub fn attempts(&self) -> u32 {
+        self.attempts.load(Ordering::Relaxed)
+    }
+}
+
+impl fmt::Debug for CallContext {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CallContext")
+            .field("deadline", &self.deadline)
+            .field("progress", &self.progress.is_some())
+            .field("resources", &self.resources.is_some())
+            .field("attempts", &self.attempts())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn progress_steps_continue_across_reports() {
+        let updates = Arc::new(parking_lot::Mutex::new(Vec::<f64>::new()));
+        let sink = updates.clone();
+        let ctx =
+            CallContext::default().with_progress(Arc::new(move |u| sink.lock().push(u.progress)));
+
+        ctx.report_step(None);
+        ctx.report_step(None);
+        // A clone (e.g. the next page) shares the counter.
+        ctx.clone().report(ProgressUpdate {
+            progress: 40.0,
+            total: Some(100.0),
+            message: None,
+        });
+        ctx.report_step(None);
+        assert_eq!(*updates.lock(), [1.0, 2.0, 40.0, 41.0]);
+    }
+}
@@ -6,8 +6,16 @@
 //!
 //! It intentionally contains **no** tenant storage logic and **no** gateway-specific policy.
 
+pub mod async_operation;
+pub mod body;
 pub mod config;
+pub mod context;
+pub mod pagination;
 pub mod response_shaping;
+pub mod retry;
 pub mod runtime;
 pub mod safety;
 pub mod semantics;
+pub mod signing;
+pub mod streaming;
+pub mod xml;
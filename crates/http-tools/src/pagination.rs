@@ -0,0 +1,426 @@
+//! Automatic pagination for HTTP tool calls.
+//!
+//! A [`Paginator`] drives one tool call: the runtime sends the first request with the call
+//! arguments, feeds every successful page to [`Paginator::add_page`], and follows the returned
+//! [`NextPage`] (updated [`Paginator::arguments`] or a `Link` URL) until it is
+//! [`NextPage::Done`]. The item arrays of all pages (at `itemsPointer`) are concatenated into the
+//! first page, which becomes the response body before response shaping.
+//!
+//! Calls stop at `maxPages` / `maxItems`; [`PaginationSummary`] reports how much was fetched and
+//! whether the upstream had more.
+
+use crate::config::{PaginationConfig, PaginationStrategy};
+use serde::Serialize;
+use serde_json::{Value, json};
+
+/// What to request after a page.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NextPage {
+    /// Repeat the request with [`Paginator::arguments`].
+    Arguments,
+    /// Request this URL (from a `Link` header; may be relative to the current page URL).
+    Url(String),
+    /// Stop.
+    Done,
+}
+
+/// Why a call stopped while the upstream still had pages.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum TruncationReason {
+    MaxPages,
+    MaxItems,
+}
+
+/// Pages and items fetched by a paginated call.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PaginationSummary {
+    pub pages: usize,
+    pub items: usize,
+    /// Set when more items were available upstream.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub truncated: Option<TruncationReason>,
+}
+
+impl PaginationSummary {
+    /// Note for the tool result when the call was truncated.
+    #[must_use]
+    pub fn notice(&self) -> Option<String> {
+        let reason = match self.truncated? {
+            TruncationReason::MaxPages => "maxPages",
+            TruncationReason::MaxItems => "maxItems",
+        };
+        Some(format!(
+            "Results truncated: returned {} items from {} pages ({reason} reached); more are available upstream.",
+            self.items, self.pages
+        ))
+    }
+}
+
+/// JSON Schema of the `pagination` member of structured results.
+#[must_use]
+pub fn summary_schema() -> Value {
+    json!({
+        "type": "object",
+        "required": ["pages", "items"],
+        "properties": {
+            "pages": { "type": "integer" },
+            "items": { "type": "integer" },
+            "truncated": { "type": "string", "enum": ["maxPages", "maxItems"] }
+        }
+    })
+}
+
+/// Check a pagination config against the tool's params (`has_param` tests a tool argument name).
+///
+/// # Errors
+///
+/// Returns a message for an undeclared `param`/`limitParam`, an invalid JSON pointer, or a zero
+/// limit.
+pub fn validate(config: &PaginationConfig, has_param: impl Fn(&str) -> bool) -> Result<(), String> {
+    let check_param = |param: &str| {
+        if has_param(param) {
+            Ok(())
+        } else {
+            Err(format!(
+                "pagination param '{param}' is not a param of the tool"
+            ))
+        }
+    };
+    let check_pointer = |field: &str, pointer: &str| {
+        if pointer.is_empty() || pointer.starts_with('/') {
+            Ok(())
+        } else {
+            Err(format!(
+                "pagination {field} '{pointer}' must be a JSON pointer (e.g. '/data')"
+            ))
+        }
+    };
+
+    match &config.strategy {
+        PaginationStrategy::LinkHeader => {}
+        PaginationStrategy::Cursor {
+            cursor_pointer,
+            param,
+        } => {
+            check_pointer("cursorPointer", cursor_pointer)?;
+            check_param(param)?;
+        }
+        PaginationStrategy::Offset { param, limit_param } => {
+            check_param(param)?;
+            if let Some(limit_param) = limit_param {
+                check_param(limit_param)?;
+            }
+        }
+        PaginationStrategy::Page { param, .. } => check_param(param)?,
+    }
+    check_pointer("itemsPointer", &config.items_pointer)?;
+    if config.max_pages == 0 || config.max_items == 0 {
+        return Err("pagination maxPages and maxItems must be at least 1".to_string());
+    }
+    Ok(())
+}
+
+/// Pagination state of one tool call.
+#[derive(Debug)]
+pub struct Paginator<'a> {
+    config: &'a PaginationConfig,
+    arguments: Value,
+    first: Option<Value>,
+    items: Vec<Value>,
+    pages: usize,
+    truncated: Option<TruncationReason>,
+}
+
+impl<'a> Paginator<'a> {
+    #[must_use]
+    pub fn new(config: &'a PaginationConfig, arguments: &Value) -> Self {
+        Self {
+            config,
+            arguments: if arguments.is_object() {
+                arguments.clone()
+            } else {
+                json!({})
+            },
+            first: None,
+            items: Vec::new(),
+            pages: 0,
+            truncated: None,
+        }
+    }
+
+    /// Tool arguments for the next request.
+    #[must_use]
+    pub fn arguments(&self) -> &Value {
+        &self.arguments
+    }
+
+    /// Record a successful page. `link` is the page's `Link` header (all values, comma-joined).
+    ///
+    /// # Errors
+    ///
+    /// Returns a message if the page has a non-array value at `itemsPointer`.
+    pub fn add_page(&mut self, mut page: Value, link: Option<&str>) -> Result<NextPage, String> {
+        self.pages += 1;
+
+        // Read the cursor before the items are moved out of the page.
+        let cursor = match &self.config.strategy {
+            PaginationStrategy::Cursor { cursor_pointer, .. } => page
+                .pointer(cursor_pointer)
+                .filter(|c| !matches!(c, Value::Null) && c.as_str() != Some(""))
+                .cloned(),
+            _ => None,
+        };
+
+        let items = take_items(&mut page, &self.config.items_pointer)?;
+        let received = items.len();
+        let room = self.config.max_items.saturating_sub(self.items.len());
+        let cut = received > room;
+        self.items.extend(items.into_iter().take(room));
+        if self.first.is_none() {
+            self.first = Some(page);
+        }
+
+        let next = match &self.config.strategy {
+            PaginationStrategy::LinkHeader => link.and_then(next_link).map(NextPage::Url),
+            PaginationStrategy::Cursor { param, .. } => cursor.map(|c| self.set(param, c)),
+            PaginationStrategy::Offset { param, limit_param } => {
+                let limit = limit_param
+                    .as_deref()
+                    .and_then(|p| as_u64(self.arguments.get(p)));
+                let received = received as u64;
+                if received == 0 || limit.is_some_and(|l| received < l) {
+                    None
+                } else {
+                    let offset = as_u64(self.arguments.get(param)).unwrap_or(0) + received;
+                    Some(self.set(param, json!(offset)))
+                }
+            }
+            PaginationStrategy::Page { param, start } => {
+                if received == 0 {
+                    None
+                } else {
+                    let page = as_u64(self.arguments.get(param)).unwrap_or(*start) + 1;
+                    Some(self.set(param, json!(page)))
+                }
+            }
+        };
+
+        let Some(next) = next else {
+            return Ok(NextPage::Done);
+        };
+        if cut || self.items.len() >= self.config.max_items {
+            self.truncated = Some(TruncationReason::MaxItems);
+            return Ok(NextPage::Done);
+        }
+        if self.pages >= self.config.max_pages {
+            self.truncated = Some(TruncationReason::MaxPages);
+            return Ok(NextPage::Done);
+        }
+        Ok(next)
+    }
+
+    /// The first page with the items of all pages, and the summary.
+    #[must_use]
+    pub fn finish(self) -> (Value, PaginationSummary) {
+        let summary = PaginationSummary {
+            pages: self.pages,
+            items: self.items.len(),
+            truncated: self.truncated,
+        };
+        let items = Value::Array(self.items);
+        let mut body = self.first.unwrap_or(Value::Null);
+        if self.config.items_pointer.is_empty() {
+            body = items;
+        } else if let Some(slot) = body.pointer_mut(&self.config.items_pointer) {
+            *slot = items;
+        }
+        (body, summary)
+    }
+
+    fn set(&mut self, param: &str, value: Value) -> NextPage {
+        self.arguments[param] = value;
+        NextPage::Arguments
+    }
+}
+
+/// Move the item array out of a page; a missing or `null` array counts as empty.
+fn take_items(page: &mut Value, pointer: &str) -> Result<Vec<Value>, String> {
+    let slot = if pointer.is_empty() {
+        Some(page)
+    } else {
+        page.pointer_mut(pointer)
+    };
+    match slot {
+        None | Some(Value::Null) => Ok(Vec::new()),
+        Some(Value::Array(items)) => Ok(std::mem::take(items)),
+        Some(_) => Err(format!(
+            "pagination: value at itemsPointer '{pointer}' is not an array"
+        )),
+    }
+}
+
+fn as_u64(value: Option<&Value>) -> Option<u64> {
+    match value? {
+        Value::Number(n) => n.as_u64(),
+        Value::String(s) => s.trim().parse().ok(),
+        _ => None,
+    }
+}
+
+/// Target of the `rel="next"` link in a `Link` header value.
+#[must_use]
+pub fn next_link(header: &str) -> Option<String> {
+    let mut rest = header;
+    while let Some(start) = rest.find('<') {
+        let end = start + rest[start..].find('>')?;
+        let target = &rest[start + 1..end];
+        let params_end = rest[end..].find('<').map_or(rest.len(), |i| end + i);
+        let is_next = rest[end + 1..params_end].split(';').any(|param| {
+            param.split_once('=').is_some_and(|(key, value)| {
+                key.trim().eq_ignore_ascii_case("rel")
+                    && value
+                        .trim()
+                        .trim_end_matches(',')
+                        .trim_matches('"')
+                        .split_whitespace()
+                        .any(|rel| rel.eq_ignore_ascii_case("next"))
+            })
+        });
+        if is_next {
+            return Some(target.to_string());
+        }
+        rest = &rest[params_end..];
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(v: Value) -> PaginationConfig {
+        serde_json::from_value(v).expect("pagination config")
+    }
+
+    #[test]
+    fn parses_next_link() {
+        assert_eq!(
+            next_link(r#"<https://api.example.com/items?page=1>; rel="prev", <https://api.example.com/items?page=3>; rel="next""#)
+                .as_deref(),
+            Some("https://api.example.com/items?page=3")
+        );
+        assert_eq!(
+            next_link("</items?cursor=a,b>; rel=\"last next\"").as_deref(),
+            Some("/items?cursor=a,b")
+        );
+        assert_eq!(next_link(r#"<https://x/1>; rel="prev""#), None);
+    }
+
+    #[test]
+    fn follows_cursor_and_concatenates_items() {
+        let config = config(json!({
+            "type": "cursor",
+            "cursorPointer": "/meta/next",
+            "param": "cursor",
+            "itemsPointer": "/data"
+        }));
+        let mut pager = Paginator::new(&config, &json!({ "q": "x" }));
+
+        let next = pager
+            .add_page(json!({ "data": [1, 2], "meta": { "next": "c2" } }), None)
+            .unwrap();
+        assert_eq!(next, NextPage::Arguments);
+        assert_eq!(pager.arguments(), &json!({ "q": "x", "cursor": "c2" }));
+
+        let next = pager
+            .add_page(json!({ "data": [3], "meta": { "next": null } }), None)
+            .unwrap();
+        assert_eq!(next, NextPage::Done);
+
+        let (body, summary) = pager.finish();
+        assert_eq!(body, json!({ "data": [1, 2, 3], "meta": { "next": "c2" } }));
+        assert_eq!(
+            summary,
+            PaginationSummary {
+                pages: 2,
+                items: 3,
+                truncated: None
+            }
+        );
+        assert_eq!(summary.notice(), None);
+    }
+
+    #[test]
+    fn advances_offset_until_a_short_page() {
+        let config = config(json!({ "type": "offset", "param": "offset", "limitParam": "limit" }));
+        let mut pager = Paginator::new(&config, &json!({ "limit": 2 }));
+
+        assert_eq!(
+            pager.add_page(json!(["a", "b"]), None).unwrap(),
+            NextPage::Arguments
+        );
+        assert_eq!(pager.arguments()["offset"], json!(2));
+        assert_eq!(pager.add_page(json!(["c"]), None).unwrap(), NextPage::Done);
+        assert_eq!(pager.finish().0, json!(["a", "b", "c"]));
+    }
+
+    #[test]
+    fn stops_at_max_pages_and_max_items() {
+        let pages = config(json!({ "type": "page", "param": "page", "maxPages": 2 }));
+        let mut pager = Paginator::new(&pages, &json!({}));
+        assert_eq!(
+            pager.add_page(json!([1]), None).unwrap(),
+            NextPage::Arguments
+        );
+        assert_eq!(pager.arguments()["page"], json!(2));
+        assert_eq!(pager.add_page(json!([2]), None).unwrap(), NextPage::Done);
+        let (_, summary) = pager.finish();
+        assert_eq!(summary.truncated, Some(TruncationReason::MaxPages));
+        assert!(summary.notice().unwrap().contains("maxPages"));
+
+        let links = config(json!({ "type": "linkHeader", "maxItems": 3 }));
+        let mut pager = Paginator::new(&links, &json!({}));
+        assert_eq!(
+            pager
+                .add_page(json!([1, 2]), Some("</p2>; rel=next"))
+                .unwrap(),
+            NextPage::Url("/p2".to_string())
+        );
+        assert_eq!(
+            pager
+                .add_page(json!([3, 4]), Some("</p3>; rel=next"))
+                .unwrap(),
+            NextPage::Done
+        );
+        let (body, summary) = pager.finish();
+        assert_eq!(body, json!([1, 2, 3]));
+        assert_eq!(summary.truncated, Some(TruncationReason::MaxItems));
+    }
+
+    #[test]
+    fn validates_params_and_pointers() {
+        let has = |p: &str| p == "cursor";
+        let ok = config(json!({ "type": "cursor", "cursorPointer": "/next", "param": "cursor" }));
+        assert!(validate(&ok, has).is_ok());
+
+        let err = validate(&config(json!({ "type": "page", "param": "p" })), has).unwrap_err();
+        assert!(err.contains("'p'"), "{err}");
+
+        let err = validate(
+            &config(json!({ "type": "cursor", "cursorPointer": "next", "param": "cursor" })),
+            has,
+        )
+        .unwrap_err();
+        assert!(err.contains("cursorPointer"), "{err}");
+    }
+
+    #[test]
+    fn rejects_non_array_items() {
+        let config = config(json!({ "type": "page", "param": "page", "itemsPointer": "/data" }));
+        let mut pager = Paginator::new(&config, &json!({}));
+        let err = pager.add_page(json!({ "data": {} }), None).unwrap_err();
+        assert!(err.contains("not an array"), "{err}");
+    }
+}
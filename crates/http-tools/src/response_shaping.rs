@@ -147,6 +147,16 @@ impl CompiledResponsePipeline {
         }
     }
 
+    /// Apply only the `redactKeys` steps (in-place), e.g. to error payloads that are otherwise
+    /// passed through unshaped.
+    pub fn redact_value(&self, v: &mut Value) {
+        for step in &self.steps {
+            if let CompiledTransform::RedactKeys { keys, replacement } = step {
+                redact_keys_in_place(v, keys, replacement);
+            }
+        }
+    }
+
     /// Apply schema transformations for the pipeline (best-effort).
     ///
     /// Returns a list of warnings (empty if all rewrites were applied cleanly).
@@ -0,0 +1,251 @@
+//! Retries of transient failures (`retry` / `defaults.retry`).
+//!
+//! [`send`] executes a request and retries it when the response status or transport error is
+//! configured as retryable. Only idempotent methods are retried unless `retryNonIdempotent` is set,
+//! and requests whose body cannot be replayed (streams) are sent once. Delays grow exponentially
+//! with jitter; a `Retry-After` header raises the delay (a `Retry-After` above `maxIntervalMs` ends
+//! the retries). No retry starts when it could not finish before the caller's deadline, and every
+//! attempt's timeout is capped by the time left.
+
+use crate::config::{RetryConfig, TransportErrorKind};
+use crate::context::CallContext;
+use reqwest::{Client, Method, RequestBuilder, Response, StatusCode};
+use rmcp::model::{CallToolResult, Meta};
+use std::time::{Duration, SystemTime};
+
+/// Check a retry config.
+///
+/// # Errors
+///
+/// Returns a message for `maxAttempts: 0`, an invalid backoff or a status outside `100..=599`.
+pub fn validate(config: &RetryConfig) -> Result<(), String> {
+    if config.max_attempts == 0 {
+        return Err("retry maxAttempts must be at least 1".to_string());
+    }
+    if !config.backoff_coefficient.is_finite() || config.backoff_coefficient < 1.0 {
+        return Err("retry backoffCoefficient must be at least 1".to_string());
+    }
+    if config.max_interval_ms < config.initial_interval_ms {
+        return Err("retry maxIntervalMs must be at least initialIntervalMs".to_string());
+    }
+    if let Some(status) = config.statuses.iter().find(|s| !(100..=599).contains(*s)) {
+        return Err(format!("retry status {status} is not an HTTP status code"));
+    }
+    Ok(())
+}
+
+/// Whether requests with `method` may be repeated without changing the outcome.
+#[must_use]
+pub fn is_idempotent(method: &Method) -> bool {
+    matches!(
+        *method,
+        Method::GET | Method::HEAD | Method::OPTIONS | Method::TRACE | Method::PUT | Method::DELETE
+    )
+}
+
+/// Backoff before retry number `retry` (1 for the first retry). `jitter` in `[0, 1)` removes up to
+/// half of the delay.
+#[must_use]
+pub fn backoff_delay(config: &RetryConfig, retry: u32, jitter: f64) -> Duration {
+    let exp = i32::try_from(retry.saturating_sub(1).min(30)).unwrap_or(30);
+    let max = Duration::from_millis(config.max_interval_ms);
+    let factor = config.backoff_coefficient.powi(exp).min(1e9);
+    // Products too large for a `Duration` (or not finite) are capped at `maxIntervalMs`.
+    let delay = Duration::try_from_secs_f64(
+        Duration::from_millis(config.initial_interval_ms).as_secs_f64() * factor,
+    )
+    .map_or(max, |d| d.min(max));
+    delay.mul_f64(1.0 - jitter.clamp(0.0, 1.0) / 2.0)
+}
+
+/// Parse a `Retry-After` value (delay seconds or an HTTP date).
+#[must_use]
+pub fn parse_retry_after(value: &str, now: SystemTime) -> Option<Duration> {
+    let value = value.trim();
+    if let Ok(secs) = value.parse::<u64>() {
+        return Some(Duration::from_secs(secs));
+    }
+    let at = httpdate::parse_http_date(value).ok()?;
+    Some(at.duration_since(now).unwrap_or(Duration::ZERO))
+}
+
+/// Why an attempt may be retried.
+enum Retryable {
+    Status(StatusCode, Option<Duration>),
+    Transport(&'static str),
+}
+
+fn retryable(config: &RetryConfig, result: &reqwest::Result<Response>) -> Option<Retryable> {
+    match result {
+        Ok(response) => {
+            let status = response.status();
+            config.statuses.contains(&status.as_u16()).then(|| {
+                let retry_after = response
+                    .headers()
+                    .get(reqwest::header::RETRY_AFTER)
+                    .and_then(|v| v.to_str().ok())
+                    .and_then(|v| parse_retry_after(v, SystemTime::now()));
+                Retryable::Status(status, retry_after)
+            })
+        }
+        Err(e) => {
+            let kind = if e.is_connect() {
+                TransportErrorKind::Connect
+            } else if e.is_timeout() {
+                TransportErrorKind::Timeout
+            } else {
+                return None;
+            };
+            config
+                .transport_errors
+                .contains(&kind)
+                .then_some(Retryable::Transport(kind.as_str()))
+        }
+    }
+}
+
+/// Send a request, retrying per `config` within `ctx`'s deadline. Every attempt is recorded on
+/// `ctx`.
+///
+/// # Errors
+///
+/// Returns the transport error of the last attempt.
+pub async fn send(
+    client: &Client,
+    request: RequestBuilder,
+    config: Option<&RetryConfig>,
+    ctx: &CallContext,
+) -> reqwest::Result<Response> {
+    let mut current = request.build()?;
+    let base_timeout = current.timeout().copied();
+    let config = config.filter(|c| {
+        c.max_attempts > 1 && (c.retry_non_idempotent || is_idempotent(current.method()))
+    });
+
+    let mut attempt = 1;
+    loop {
+        if let Some(remaining) = ctx.remaining() {
+            *current.timeout_mut() = Some(base_timeout.map_or(remaining, |t| t.min(remaining)));
+        }
+        // Keep a copy for the next attempt; streaming bodies cannot be replayed.
+        let next = config
+            .filter(|c| attempt < c.max_attempts)
+            .and_then(|_| current.try_clone());
+        ctx.record_attempt();
+        let result = client.execute(current).await;
+
+        let (Some(config), Some(next)) = (config, next) else {
+            return result;
+        };
+        let Some(reason) = retryable(config, &result) else {
+            return result;
+        };
+        let mut delay = backoff_delay(config, attempt, fastrand::f64());
+        let reason = match reason {
+            Retryable::Status(status, retry_after) => {
+                if let Some(retry_after) = retry_after {
+                    if retry_after > Duration::from_millis(config.max_interval_ms) {
+                        return result;
+                    }
+                    delay = delay.max(retry_after);
+                }
+                status.to_string()
+            }
+            Retryable::Transport(kind) => format!("{kind} error"),
+        };
+        if ctx.remaining().is_some_and(|r| r <= delay) {
+            return result;
+        }
+        tracing::debug!(
+            url = %next.url(),
+            attempt,
+            reason = %reason,
+            delay_ms = delay.as_millis(),
+            "retrying HTTP request"
+        );
+        drop(result);
+        tokio::time::sleep(delay).await;
+        attempt += 1;
+        current = next;
+    }
+}
+
+/// Report the HTTP attempts of a call in the result's `_meta` (`unrelated.attempts`).
+pub fn record_attempts(result: &mut CallToolResult, attempts: u32) {
+    result.meta.get_or_insert_with(Meta::new).insert(
+        "unrelated".to_string(),
+        serde_json::json!({ "attempts": attempts }),
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn config(v: serde_json::Value) -> RetryConfig {
+        serde_json::from_value(v).expect("retry config")
+    }
+
+    #[test]
+    fn backs_off_exponentially_with_jitter() {
+        let cfg = config(json!({
+            "initialIntervalMs": 100,
+            "backoffCoefficient": 2.0,
+            "maxIntervalMs": 350
+        }));
+        let delays: Vec<_> = (1..=4).map(|r| backoff_delay(&cfg, r, 0.0)).collect();
+        assert_eq!(
+            delays,
+            [100, 200, 350, 350].map(Duration::from_millis).to_vec()
+        );
+        assert_eq!(backoff_delay(&cfg, 2, 0.5), Duration::from_millis(150));
+
+        // Would overflow a `Duration` before the cap applies.
+        let huge = config(json!({
+            "initialIntervalMs": u64::MAX,
+            "backoffCoefficient": 1000.0,
+            "maxIntervalMs": 86_400_000
+        }));
+        assert_eq!(backoff_delay(&huge, 30, 0.0), Duration::from_secs(86_400));
+        let nan = RetryConfig {
+            backoff_coefficient: f64::NAN,
+            ..cfg
+        };
+        assert_eq!(backoff_delay(&nan, 3, 0.0), Duration::from_millis(350));
+    }
+
+    #[test]
+    fn parses_retry_after_seconds_and_dates() {
+        let now = httpdate::parse_http_date("Sun, 06 Nov 1994 08:49:37 GMT").unwrap();
+        assert_eq!(parse_retry_after(" 3 ", now), Some(Duration::from_secs(3)));
+        assert_eq!(
+            parse_retry_after("Sun, 06 Nov 1994 08:50:07 GMT", now),
+            Some(Duration::from_secs(30))
+        );
+        assert_eq!(
+            parse_retry_after("Sun, 06 Nov 1994 08:00:00 GMT", now),
+            Some(Duration::ZERO)
+        );
+        assert_eq!(parse_retry_after("soon", now), None);
+    }
+
+    #[test]
+    fn retries_idempotent_methods_only() {
+        assert!(is_idempotent(&Method::GET));
+        assert!(is_idempotent(&Method::PUT));
+        assert!(!is_idempotent(&Method::POST));
+        assert!(!is_idempotent(&Method::PATCH));
+    }
+
+    #[test]
+    fn validates_attempts_backoff_and_statuses() {
+        assert!(validate(&config(json!({}))).is_ok());
+        let err = validate(&config(json!({ "maxAttempts": 0 }))).unwrap_err();
+        assert!(err.contains("maxAttempts"), "{err}");
+        let err = validate(&config(json!({ "backoffCoefficient": 0.5 }))).unwrap_err();
+        assert!(err.contains("backoffCoefficient"), "{err}");
+        let err = validate(&config(json!({ "statuses": [429, 1000] }))).unwrap_err();
+        assert!(err.contains("1000"), "{err}");
+    }
+}
@@ -4,15 +4,23 @@
 //! - the Adapter (standalone mode)
 //! - the Gateway (gateway-native tool sources)
 
+use crate::async_operation::{OperationState, Poller, locate_url};
+use crate::body::{binary_value_schema, encode_body, inline_resource_refs, is_binary_schema};
 use crate::config::{
-    AuthConfig, HttpParamLocation, HttpResponseMode, HttpServerConfig, QueryStyleConfig,
+    AsyncOperationConfig, AuthConfig, BodyEncoding, HttpParamLocation, HttpResponseMode,
+    HttpServerConfig, PaginationConfig, QueryStyleConfig, RetryConfig, StreamConfig, XmlConfig,
 };
+use crate::context::CallContext;
+use crate::pagination::{NextPage, PaginationSummary, Paginator};
 use crate::response_shaping::CompiledResponsePipeline;
 use crate::safety::{OutboundHttpSafety, RedirectPolicy, sanitize_reqwest_error};
+use crate::streaming::{StreamError, read_stream, stream_format};
+use crate::xml::{XmlBody, is_xml_media_type, template_placeholders, xml_to_json};
 use base64::Engine as _;
 use mime::Mime;
 use openapiv3::QueryStyle;
-use reqwest::{Client, Method};
+use reqwest::header::HeaderMap;
+use reqwest::{Client, Method, StatusCode};
 use rmcp::model::{CallToolResult, Content, JsonObject, Tool};
 use serde_json::{Value, json};
 use std::collections::{HashMap, HashSet};
@@ -51,6 +59,15 @@ struct GeneratedTool {
     path: String,
     parameters: Vec<ToolParameter>,
     input_schema: Value,
+    body_encoding: BodyEncoding,
+    /// Serialization of `xml` bodies (set for `bodyEncoding: xml`).
+    xml_body: Option<Arc<XmlBody>>,
+    pagination: Option<Arc<PaginationConfig>>,
+    async_operation: Option<Arc<AsyncOperationConfig>>,
+    /// The tool's `retry`, or the source's `defaults.retry`.
+    retry: Option<Arc<RetryConfig>>,
+    /// Aggregation of streaming responses (`response.stream`).
+    stream: Option<Arc<StreamConfig>>,
     response_mode: HttpResponseMode,
     output_schema: Option<Arc<JsonObject>>,
     response_pipeline: Arc<CompiledResponsePipeline>,
@@ -65,6 +82,8 @@ struct ToolParameter {
     default: Option<Value>,
     schema: Value,
     query: Option<QuerySerialization>,
+    /// Body param carrying binary content (see [`crate::body::decode_binary_value`]).
+    binary: bool,
 }
 
 #[derive(Debug, Clone)]
@@ -88,6 +107,7 @@ struct RequestParts {
     headers: Vec<(String, String)>,
     body_fields: HashMap<String, Value>,
     body_payload: Option<Value>,
+    binary_fields: HashSet<String>,
 }
 
 #[derive(Clone)]
@@ -149,6 +169,11 @@ impl HttpToolSource {
         })?;
 
         let name = name.into();
+        if let Some(auth) = &config.auth {
+            crate::signing::validate(auth).map_err(|e| {
+                HttpToolsError::Config(format!("Invalid auth for HTTP tool source '{name}': {e}"))
+            })?;
+        }
         let tools = generate_tools(&name, &config)?;
 
         let client = match safety.redirects {
@@ -203,6 +228,22 @@ impl HttpToolSource {
     /// - required parameters are missing
     /// - the HTTP request fails (transport or non-2xx response)
     pub async fn call_tool(&self, tool_name: &str, arguments: Value) -> Result<CallToolResult> {
+        self.call_tool_with_context(tool_name, arguments, &CallContext::default())
+            .await
+    }
+
+    /// Execute a tool call within the caller's deadline, reporting progress to `ctx`.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`Self::call_tool`]; also when a long-running operation fails or does not finish
+    /// in time.
+    pub async fn call_tool_with_context(
+        &self,
+        tool_name: &str,
+        arguments: Value,
+        ctx: &CallContext,
+    ) -> Result<CallToolResult> {
         let tool = self
             .inner
             .tools
@@ -210,51 +251,107 @@ impl HttpToolSource {
             .find(|t| t.name == tool_name || t.original_name == tool_name)
             .ok_or_else(|| HttpToolsError::Runtime(format!("Tool not found: {tool_name}")))?;
 
-        let resp = execute_request(&self.inner, tool, &arguments).await?;
-        match resp {
+        let resp = execute_request(&self.inner, tool, &arguments, ctx).await?;
+        let mut result = match resp {
             ToolResponse::Image { bytes, mime_type } => {
                 let b64 = base64::engine::general_purpose::STANDARD.encode(bytes);
                 // Response shaping doesn't apply to binary.
-                Ok(CallToolResult {
+                CallToolResult {
                     content: vec![Content::image(b64, mime_type)],
                     structured_content: None,
                     is_error: Some(false),
                     meta: None,
-                })
-            }
-            ToolResponse::Value(mut body) => {
-                tool.response_pipeline.apply_to_value(&mut body);
-
-                // Emit `structured_content` only when the tool advertises an output schema.
-                if tool.output_schema.is_some() {
-                    let structured = json!({ "body": body });
-                    // Return both `structured_content` and `Content::text(...)` for interoperability:
-                    // some MCP clients only render `content` and ignore `structured_content`.
-                    let text = serde_json::to_string(&structured)
-                        .unwrap_or_else(|_| structured.to_string());
-
-                    Ok(CallToolResult {
-                        content: vec![Content::text(text)],
-                        structured_content: Some(structured),
-                        is_error: Some(false),
-                        meta: None,
-                    })
-                } else {
-                    let text = if let Some(s) = body.as_str() {
-                        s.to_string()
-                    } else {
-                        serde_json::to_string(&body).unwrap_or_else(|_| body.to_string())
-                    };
-                    Ok(CallToolResult::success(vec![Content::text(text)]))
                 }
             }
+            ToolResponse::Value(body) => value_result(tool, body, None),
+            ToolResponse::Paged { body, summary } => value_result(tool, body, Some(summary)),
+        };
+        if tool.retry.is_some() {
+            crate::retry::record_attempts(&mut result, ctx.attempts());
         }
+        Ok(result)
     }
 }
 
+/// Shape a response body into a tool result; paginated calls also report their summary.
+fn value_result(
+    tool: &GeneratedTool,
+    mut body: Value,
+    pagination: Option<PaginationSummary>,
+) -> CallToolResult {
+    tool.response_pipeline.apply_to_value(&mut body);
+    let notice = pagination.as_ref().and_then(PaginationSummary::notice);
+
+    // Emit `structured_content` only when the tool advertises an output schema.
+    let mut result = if tool.output_schema.is_some() {
+        let mut structured = json!({ "body": body });
+        if let Some(summary) = &pagination {
+            structured["pagination"] = json!(summary);
+        }
+        // Return both `structured_content` and `Content::text(...)` for interoperability:
+        // some MCP clients only render `content` and ignore `structured_content`.
+        let text = serde_json::to_string(&structured).unwrap_or_else(|_| structured.to_string());
+
+        CallToolResult {
+            content: vec![Content::text(text)],
+            structured_content: Some(structured),
+            is_error: Some(false),
+            meta: None,
+        }
+    } else {
+        let text = if let Some(s) = body.as_str() {
+            s.to_string()
+        } else {
+            serde_json::to_string(&body).unwrap_or_else(|_| body.to_string())
+        };
+        CallToolResult::success(vec![Content::text(text)])
+    };
+    if let Some(notice) = notice {
+        result.content.push(Content::text(notice));
+    }
+    result
+}
+
 enum ToolResponse {
     Value(Value),
-    Image { bytes: Vec<u8>, mime_type: String },
+    Image {
+        bytes: Vec<u8>,
+        mime_type: String,
+    },
+    /// Items of all pages of a paginated call.
+    Paged {
+        body: Value,
+        summary: PaginationSummary,
+    },
+}
+
+/// One successful upstream response with its request URL, status and headers.
+struct Page {
+    response: ToolResponse,
+    url: Url,
+    status: StatusCode,
+    headers: HeaderMap,
+}
+
+impl Page {
+    /// The `Link` header(s), comma-joined.
+    fn link(&self) -> Option<String> {
+        let link = self
+            .headers
+            .get_all(reqwest::header::LINK)
+            .iter()
+            .filter_map(|v| v.to_str().ok())
+            .collect::<Vec<_>>()
+            .join(", ");
+        (!link.is_empty()).then_some(link)
+    }
+
+    fn body(&self) -> &Value {
+        match &self.response {
+            ToolResponse::Value(body) => body,
+            _ => &Value::Null,
+        }
+    }
 }
 
 fn generate_tools(source_name: &str, config: &HttpServerConfig) -> Result<Vec<GeneratedTool>> {
@@ -288,9 +385,48 @@ fn generate_tools(source_name: &str, config: &HttpServerConfig) -> Result<Vec<Ge
             tool_name,
             &tool_cfg.response,
             &response_pipeline,
+            tool_cfg.pagination.is_some(),
         )?;
 
         let parameters = collect_tool_parameters(source_name, tool_name, tool_cfg)?;
+        let xml_body = build_xml_body(source_name, tool_name, tool_cfg, &parameters, &config.xml)?;
+        if let Some(pagination) = &tool_cfg.pagination {
+            crate::pagination::validate(pagination, |name| {
+                parameters.iter().any(|p| p.tool_name == name)
+            })
+            .map_err(|e| {
+                HttpToolsError::Config(format!(
+                    "Invalid pagination for tool '{tool_name}' in HTTP tool source '{source_name}': {e}"
+                ))
+            })?;
+        }
+        if let Some(operation) = &tool_cfg.async_operation {
+            let invalid = |e: String| {
+                HttpToolsError::Config(format!(
+                    "Invalid asyncOperation for tool '{tool_name}' in HTTP tool source '{source_name}': {e}"
+                ))
+            };
+            if tool_cfg.pagination.is_some() {
+                return Err(invalid("cannot be combined with pagination".to_string()));
+            }
+            crate::async_operation::validate(operation).map_err(invalid)?;
+        }
+        let retry = tool_cfg.retry.as_ref().or(config.defaults.retry.as_ref());
+        if let Some(retry) = retry {
+            crate::retry::validate(retry).map_err(|e| {
+                HttpToolsError::Config(format!(
+                    "Invalid retry for tool '{tool_name}' in HTTP tool source '{source_name}': {e}"
+                ))
+            })?;
+        }
+
+        if let Some(stream) = &tool_cfg.response.stream {
+            crate::streaming::validate(stream).map_err(|e| {
+                HttpToolsError::Config(format!(
+                    "Invalid response.stream for tool '{tool_name}' in HTTP tool source '{source_name}': {e}"
+                ))
+            })?;
+        }
 
         let input_schema = build_input_schema(&parameters);
 
@@ -302,6 +438,12 @@ fn generate_tools(source_name: &str, config: &HttpServerConfig) -> Result<Vec<Ge
             path,
             parameters,
             input_schema,
+            body_encoding: tool_cfg.body_encoding,
+            xml_body,
+            pagination: tool_cfg.pagination.clone().map(Arc::new),
+            async_operation: tool_cfg.async_operation.clone().map(Arc::new),
+            retry: retry.cloned().map(Arc::new),
+            stream: tool_cfg.response.stream.clone().map(Arc::new),
             response_mode,
             output_schema,
             response_pipeline,
@@ -311,6 +453,66 @@ fn generate_tools(source_name: &str, config: &HttpServerConfig) -> Result<Vec<Ge
     Ok(out)
 }
 
+/// Build the `xml` body serialization: the `body` param's schema, or an object of all body params
+/// under a root element named after the tool.
+fn build_xml_body(
+    source_name: &str,
+    tool_name: &str,
+    tool_cfg: &crate::config::HttpToolConfig,
+    parameters: &[ToolParameter],
+    conventions: &XmlConfig,
+) -> Result<Option<Arc<XmlBody>>> {
+    if tool_cfg.body_encoding != BodyEncoding::Xml {
+        if tool_cfg.body_template.is_some() {
+            return Err(HttpToolsError::Config(format!(
+                "bodyTemplate in tool '{tool_name}' (source '{source_name}') requires bodyEncoding: xml"
+            )));
+        }
+        return Ok(None);
+    }
+
+    let body_params: Vec<&ToolParameter> = parameters
+        .iter()
+        .filter(|p| matches!(p.location, HttpParamLocation::Body))
+        .collect();
+
+    if let Some(template) = &tool_cfg.body_template {
+        let placeholders = template_placeholders(template).map_err(|e| {
+            HttpToolsError::Config(format!(
+                "Invalid bodyTemplate in tool '{tool_name}' (source '{source_name}'): {e}"
+            ))
+        })?;
+        if let Some(unknown) = placeholders
+            .iter()
+            .find(|name| !body_params.iter().any(|p| p.http_name == **name))
+        {
+            return Err(HttpToolsError::Config(format!(
+                "bodyTemplate in tool '{tool_name}' (source '{source_name}') references unknown body param '{unknown}'"
+            )));
+        }
+    }
+
+    let schema = if let Some(payload) = body_params
+        .iter()
+        .find(|p| p.tool_name == "body" && p.http_name == "body")
+    {
+        payload.schema.clone()
+    } else {
+        let properties: serde_json::Map<String, Value> = body_params
+            .iter()
+            .map(|p| (p.http_name.clone(), p.schema.clone()))
+            .collect();
+        json!({ "type": "object", "properties": properties })
+    };
+
+    Ok(Some(Arc::new(XmlBody {
+        root: tool_name.to_string(),
+        schema,
+        template: tool_cfg.body_template.clone(),
+        conventions: conventions.clone(),
+    })))
+}
+
 fn parse_http_method(source_name: &str, tool_name: &str, method: &str) -> Result<Method> {
     let method_str = method.trim();
     method_str.to_uppercase().parse().map_err(|_| {
@@ -325,6 +527,7 @@ fn build_wrapped_output_schema(
     tool_name: &str,
     response_cfg: &crate::config::HttpResponseConfig,
     response_pipeline: &CompiledResponsePipeline,
+    paginated: bool,
 ) -> Result<Option<Arc<JsonObject>>> {
     let Some(body_schema) = response_cfg.output_schema.as_ref() else {
         return Ok(None);
@@ -346,13 +549,16 @@ fn build_wrapped_output_schema(
         );
     }
 
-    let wrapped = json!({
+    let mut wrapped = json!({
         "type": "object",
         "required": ["body"],
         "properties": {
             "body": body_schema
         }
     });
+    if paginated {
+        wrapped["properties"]["pagination"] = crate::pagination::summary_schema();
+    }
     let schema_obj = wrapped.as_object().cloned().unwrap_or_else(JsonObject::new);
     Ok(Some(Arc::new(schema_obj)))
 }
@@ -380,6 +586,13 @@ fn collect_tool_parameters(
             .schema
             .clone()
             .unwrap_or_else(|| json!({"type": "string"}));
+        let binary = matches!(p.location, HttpParamLocation::Body)
+            && (is_binary_schema(&schema) || tool_cfg.body_encoding == BodyEncoding::Binary);
+        let schema = if binary {
+            binary_value_schema(&schema)
+        } else {
+            schema
+        };
 
         let query = if matches!(p.location, HttpParamLocation::Query) {
             let style = p.style.map_or(QueryStyle::Form, map_query_style);
@@ -402,6 +615,7 @@ fn collect_tool_parameters(
             default: p.default.clone(),
             schema,
             query,
+            binary,
         });
     }
 
@@ -412,53 +626,283 @@ async fn execute_request(
     inner: &HttpToolSourceInner,
     tool: &GeneratedTool,
     arguments: &Value,
+    ctx: &CallContext,
+) -> Result<ToolResponse> {
+    if let Some(operation) = tool.async_operation.as_deref() {
+        let page = send_request(inner, tool, arguments, None, ctx).await?;
+        if page.status != StatusCode::ACCEPTED {
+            return Ok(page.response);
+        }
+        return await_operation(inner, tool, operation, page, ctx).await;
+    }
+    let Some(pagination) = tool.pagination.as_deref() else {
+        return Ok(send_request(inner, tool, arguments, None, ctx)
+            .await?
+            .response);
+    };
+
+    let mut pager = Paginator::new(pagination, arguments);
+    let mut next_url = None;
+    let mut first = true;
+    loop {
+        let page = send_request(inner, tool, pager.arguments(), next_url.take(), ctx).await?;
+        let link = page.link();
+        let body = match page.response {
+            ToolResponse::Value(body) if !body.is_string() => body,
+            // Non-JSON first pages (text, images) are returned as-is.
+            response if first => return Ok(response),
+            _ => {
+                return Err(HttpToolsError::Runtime(
+                    "pagination: a follow-up page is not a JSON document".to_string(),
+                ));
+            }
+        };
+        first = false;
+        match pager
+            .add_page(body, link.as_deref())
+            .map_err(HttpToolsError::Runtime)?
+        {
+            NextPage::Done => break,
+            NextPage::Arguments => {}
+            NextPage::Url(link) => next_url = Some(resolve_next_link(inner, &page.url, &link)?),
+        }
+    }
+
+    let (body, summary) = pager.finish();
+    Ok(ToolResponse::Paged { body, summary })
+}
+
+/// Poll an accepted long-running operation until it finishes (see [`crate::async_operation`]).
+async fn await_operation(
+    inner: &HttpToolSourceInner,
+    tool: &GeneratedTool,
+    operation: &AsyncOperationConfig,
+    accepted: Page,
+    ctx: &CallContext,
 ) -> Result<ToolResponse> {
-    let base_url = &inner.config.base_url;
+    let location = locate_url(&operation.status_url, &accepted.headers, accepted.body())
+        .ok_or_else(|| {
+            HttpToolsError::Runtime(
+                "asyncOperation: the 202 response has no status URL".to_string(),
+            )
+        })?;
+    let status_url = resolve_operation_url(inner, &accepted.url, &location)?;
+
+    let mut poller = Poller::new(operation, ctx);
+    let mut last_state = None;
+    loop {
+        let Some(delay) = poller.next_delay() else {
+            let state = last_state.map_or(String::new(), |s| format!(", last state '{s}'"));
+            return Err(HttpToolsError::Runtime(format!(
+                "asyncOperation: operation still running after {}s{state}; status URL: {status_url}",
+                poller.elapsed().as_secs()
+            )));
+        };
+        tokio::time::sleep(delay).await;
+
+        let page = fetch_url(inner, tool, status_url.clone(), poller.remaining(), ctx).await?;
+        match poller.state(page.status.as_u16(), page.body()) {
+            OperationState::Running(state) => {
+                if let Some(update) = poller.progress(state.as_deref(), page.body()) {
+                    ctx.report(update);
+                }
+                last_state = state;
+            }
+            OperationState::Failed(state) => {
+                return Err(HttpToolsError::Http(format!(
+                    "asyncOperation: operation failed with state '{state}': {}",
+                    page.body()
+                )));
+            }
+            OperationState::Succeeded => {
+                let Some(result_url) = &operation.result_url else {
+                    return Ok(page.response);
+                };
+                let location =
+                    locate_url(result_url, &page.headers, page.body()).ok_or_else(|| {
+                        HttpToolsError::Runtime(
+                            "asyncOperation: the final status response has no result URL"
+                                .to_string(),
+                        )
+                    })?;
+                let url = resolve_operation_url(inner, &page.url, &location)?;
+                return Ok(fetch_url(inner, tool, url, poller.remaining(), ctx)
+                    .await?
+                    .response);
+            }
+        }
+    }
+}
+
+/// Resolve a status/result URL of an async operation; it must stay on the source's origin.
+fn resolve_operation_url(
+    inner: &HttpToolSourceInner,
+    current: &Url,
+    location: &str,
+) -> Result<Url> {
+    let url = current.join(location).map_err(|e| {
+        HttpToolsError::Runtime(format!("asyncOperation: invalid URL '{location}': {e}"))
+    })?;
+    let base = Url::parse(&inner.config.base_url)
+        .map_err(|e| HttpToolsError::Runtime(format!("Invalid URL: {e}")))?;
+    if url.origin() != base.origin() {
+        return Err(HttpToolsError::Runtime(
+            "asyncOperation: URL points outside the source's baseUrl origin".to_string(),
+        ));
+    }
+    Ok(with_query_auth(inner, url))
+}
+
+/// Resolve a `Link: rel="next"` target; it must stay on the source's origin.
+fn resolve_next_link(inner: &HttpToolSourceInner, current: &Url, link: &str) -> Result<Url> {
+    let url = current
+        .join(link)
+        .map_err(|e| HttpToolsError::Runtime(format!("pagination: invalid next link: {e}")))?;
+    let base = Url::parse(&inner.config.base_url)
+        .map_err(|e| HttpToolsError::Runtime(format!("Invalid URL: {e}")))?;
+    if url.origin() != base.origin() {
+        return Err(HttpToolsError::Runtime(
+            "pagination: next link points outside the source's baseUrl origin".to_string(),
+        ));
+    }
+    Ok(with_query_auth(inner, url))
+}
+
+/// Add query auth to a URL taken from a response, unless the URL already carries it.
+fn with_query_auth(inner: &HttpToolSourceInner, mut url: Url) -> Url {
+    if let Some(AuthConfig::Query { name, value }) = inner.config.auth.as_ref()
+        && !url.query_pairs().any(|(k, _)| k == name.as_str())
+    {
+        url.query_pairs_mut().append_pair(name, value);
+    }
+    url
+}
+
+/// Send one request; `url` replaces the URL built from the arguments (pagination links).
+async fn send_request(
+    inner: &HttpToolSourceInner,
+    tool: &GeneratedTool,
+    arguments: &Value,
+    url: Option<Url>,
+    ctx: &CallContext,
+) -> Result<Page> {
     let mut parts = build_request_parts(tool, arguments)?;
-    apply_query_auth(inner.config.auth.as_ref(), &mut parts.query_params);
-    let url = build_url(base_url, &parts.path, &parts.query_params)?;
+    inline_resource_refs(
+        tool.body_encoding,
+        &mut parts.body_payload,
+        &mut parts.body_fields,
+        &parts.binary_fields,
+        ctx,
+    )
+    .await
+    .map_err(|e| HttpToolsError::Runtime(format!("Invalid request body: {e}")))?;
+    let url = match url {
+        Some(url) => url,
+        None => {
+            apply_query_auth(inner.config.auth.as_ref(), &mut parts.query_params);
+            build_url(&inner.config.base_url, &parts.path, &parts.query_params)?
+        }
+    };
 
     // Outbound safety checks (SSRF + allowlists).
     inner.safety.check_url(&url).await?;
 
-    let mut request = inner.client.request(tool.method.clone(), url);
+    let mut request = inner.client.request(tool.method.clone(), url.clone());
     request = apply_auth(inner.config.auth.as_ref(), request);
-    request = apply_headers(&inner.config, request, parts.headers);
-    request = apply_body(request, parts.body_payload.as_ref(), &parts.body_fields);
+    request = apply_headers(&inner.config, request, std::mem::take(&mut parts.headers));
+    request = apply_body(request, tool, &parts)?;
     request = apply_timeout(inner, request);
+    request = sign_request(inner, request)?;
+
+    let response = crate::retry::send(&inner.client, request, tool.retry.as_deref(), ctx).await?;
+    read_page(inner, tool, url, response, ctx).await
+}
 
-    let response = request.send().await?;
+/// `GET` a URL taken from a response (async operation status/result), within `remaining`.
+async fn fetch_url(
+    inner: &HttpToolSourceInner,
+    tool: &GeneratedTool,
+    url: Url,
+    remaining: Duration,
+    ctx: &CallContext,
+) -> Result<Page> {
+    inner.safety.check_url(&url).await?;
+
+    let mut request = inner.client.get(url.clone());
+    request = apply_auth(inner.config.auth.as_ref(), request);
+    request = apply_headers(&inner.config, request, Vec::new());
+    let timeout = request_timeout(inner).map_or(remaining, |t| t.min(remaining));
+    request = request.timeout(timeout);
+    request = sign_request(inner, request)?;
+
+    let response = crate::retry::send(&inner.client, request, tool.retry.as_deref(), ctx).await?;
+    read_page(inner, tool, url, response, ctx).await
+}
+
+/// Read a response; non-2xx responses become errors. Streaming responses are read event by event
+/// when the tool configures `response.stream`.
+async fn read_page(
+    inner: &HttpToolSourceInner,
+    tool: &GeneratedTool,
+    url: Url,
+    response: reqwest::Response,
+    ctx: &CallContext,
+) -> Result<Page> {
     let status = response.status();
-    let content_type = response
-        .headers()
+    let headers = response.headers().clone();
+    let content_type = headers
         .get(reqwest::header::CONTENT_TYPE)
         .and_then(|v| v.to_str().ok())
         .map(std::string::ToString::to_string);
+    if status.is_success()
+        && let Some(config) = tool.stream.as_deref()
+        && let Some(format) = stream_format(content_type.as_deref())
+    {
+        let body = read_stream(
+            response,
+            format,
+            config,
+            inner.safety.max_response_bytes,
+            ctx,
+        )
+        .await
+        .map_err(|e| match e {
+            StreamError::Transport(e) => HttpToolsError::from(e),
+            StreamError::TooLarge(msg) => HttpToolsError::Http(msg),
+        })?;
+        return Ok(Page {
+            response: ToolResponse::Value(body),
+            url,
+            status,
+            headers,
+        });
+    }
     let bytes = read_response_body_limited_bytes(response, inner.safety.max_response_bytes).await?;
 
     if status.is_success() {
-        if is_image_content_type(content_type.as_deref()) {
+        let response = if is_image_content_type(content_type.as_deref()) {
             let mime_type = content_type.unwrap_or_else(|| "image/*".to_string());
-            return Ok(ToolResponse::Image { bytes, mime_type });
-        }
-
-        let body = bytes_to_text_or_base64_json(&bytes, content_type.as_deref());
-        match tool.response_mode {
-            HttpResponseMode::Text => Ok(ToolResponse::Value(body)),
-            HttpResponseMode::Json => {
-                let v = match body {
-                    Value::String(s) => serde_json::from_str(&s).unwrap_or_else(|_| json!(s)),
-                    other => other,
-                };
-                Ok(ToolResponse::Value(v))
+            ToolResponse::Image { bytes, mime_type }
+        } else {
+            let body = bytes_to_text_or_base64_json(&bytes, content_type.as_deref());
+            match tool.response_mode {
+                HttpResponseMode::Text => ToolResponse::Value(body),
+                HttpResponseMode::Json => ToolResponse::Value(parse_body_value(
+                    body,
+                    content_type.as_deref(),
+                    &inner.config.xml,
+                )),
             }
-        }
+        };
+        Ok(Page {
+            response,
+            url,
+            status,
+            headers,
+        })
     } else {
         let body = bytes_to_text_or_base64_json(&bytes, content_type.as_deref());
-        let error_body: Value = match body {
-            Value::String(s) => serde_json::from_str(&s).unwrap_or_else(|_| json!(s)),
-            other => other,
-        };
+        let error_body = parse_body_value(body, content_type.as_deref(), &inner.config.xml);
         let status_code = status.as_u16();
         let reason = status.canonical_reason().unwrap_or("Unknown");
         Err(HttpToolsError::Http(format!(
@@ -507,6 +951,17 @@ fn is_image_content_type(content_type: Option<&str>) -> bool {
     m.type_() == mime::IMAGE
 }
 
+/// Parse a text body as XML (XML content types) or JSON; unparseable text stays a string.
+fn parse_body_value(body: Value, content_type: Option<&str>, xml: &XmlConfig) -> Value {
+    let Value::String(text) = body else {
+        return body;
+    };
+    if content_type.is_some_and(is_xml_media_type) {
+        return xml_to_json(&text, xml).unwrap_or(Value::String(text));
+    }
+    serde_json::from_str(&text).unwrap_or(Value::String(text))
+}
+
 fn bytes_to_text_or_base64_json(bytes: &[u8], content_type: Option<&str>) -> Value {
     if let Ok(s) = std::str::from_utf8(bytes) {
         Value::String(s.to_string())
@@ -530,6 +985,7 @@ fn build_request_parts(tool: &GeneratedTool, arguments: &Value) -> Result<Reques
     let mut headers: Vec<(String, String)> = Vec::new();
     let mut body_fields: HashMap<String, Value> = HashMap::new();
     let mut body_payload: Option<Value> = None;
+    let mut binary_fields: HashSet<String> = HashSet::new();
 
     for param in &tool.parameters {
         let value = arguments
@@ -571,6 +1027,9 @@ fn build_request_parts(tool: &GeneratedTool, arguments: &Value) -> Result<Reques
                     if param.tool_name == "body" && param.http_name == "body" {
                         body_payload = Some(val);
                     } else {
+                        if param.binary {
+                            binary_fields.insert(param.http_name.clone());
+                        }
                         body_fields.insert(param.http_name.clone(), val);
                     }
                 }
@@ -584,6 +1043,7 @@ fn build_request_parts(tool: &GeneratedTool, arguments: &Value) -> Result<Reques
         headers,
         body_fields,
         body_payload,
+        binary_fields,
     })
 }
 
@@ -633,28 +1093,40 @@ fn apply_headers(
 }
 
 fn apply_body(
-    mut request: reqwest::RequestBuilder,
-    body_payload: Option<&Value>,
-    body_fields: &HashMap<String, Value>,
-) -> reqwest::RequestBuilder {
-    if let Some(payload) = body_payload {
-        request = request.json(payload);
-    } else if !body_fields.is_empty() {
-        request = request.json(body_fields);
+    request: reqwest::RequestBuilder,
+    tool: &GeneratedTool,
+    parts: &RequestParts,
+) -> Result<reqwest::RequestBuilder> {
+    let body = encode_body(
+        tool.body_encoding,
+        None,
+        parts.body_payload.as_ref(),
+        &parts.body_fields,
+        &parts.binary_fields,
+        tool.xml_body.as_deref(),
+    )
+    .map_err(|e| HttpToolsError::Runtime(format!("Invalid request body: {e}")))?;
+    Ok(match body {
+        Some(body) => request
+            .header(reqwest::header::CONTENT_TYPE, body.content_type)
+            .body(body.bytes),
+        None => request,
+    })
+}
+
+fn request_timeout(inner: &HttpToolSourceInner) -> Option<Duration> {
+    match inner.config.defaults.timeout {
+        Some(0) => None,
+        Some(secs) => Some(Duration::from_secs(secs)),
+        None => Some(inner.default_timeout),
     }
-    request
 }
 
 fn apply_timeout(
     inner: &HttpToolSourceInner,
     mut request: reqwest::RequestBuilder,
 ) -> reqwest::RequestBuilder {
-    let effective_timeout = match inner.config.defaults.timeout {
-        Some(0) => None,
-        Some(secs) => Some(Duration::from_secs(secs)),
-        None => Some(inner.default_timeout),
-    };
-    if let Some(t) = effective_timeout {
+    if let Some(t) = request_timeout(inner) {
         request = request.timeout(t);
     }
     request
@@ -670,10 +1142,26 @@ fn apply_auth(
         Some(AuthConfig::Basic { username, password }) => {
             request.basic_auth(username, Some(password))
         }
-        Some(AuthConfig::Query { .. } | AuthConfig::None) | None => request,
+        // Signatures are added once the request is built (`sign_request`).
+        Some(
+            AuthConfig::Query { .. }
+            | AuthConfig::None
+            | AuthConfig::Sigv4 { .. }
+            | AuthConfig::Hmac { .. },
+        )
+        | None => request,
     }
 }
 
+/// Sign the built request (`sigv4` / `hmac` auth).
+fn sign_request(
+    inner: &HttpToolSourceInner,
+    request: reqwest::RequestBuilder,
+) -> Result<reqwest::RequestBuilder> {
+    crate::signing::sign_request(&inner.client, request, inner.config.auth.as_ref())
+        .map_err(|e| HttpToolsError::Runtime(format!("Failed to sign request: {e}")))
+}
+
 fn map_query_style(s: QueryStyleConfig) -> QueryStyle {
     match s {
         QueryStyleConfig::Form => QueryStyle::Form,
@@ -975,10 +1463,16 @@ mod tests {
                 path: "/users/{id}".to_string(),
                 description: None,
                 params,
+                body_encoding: Default::default(),
+                body_template: None,
+                pagination: None,
+                async_operation: None,
+                retry: None,
                 response: HttpResponseConfig {
                     mode: HttpResponseMode::Json,
                     output_schema: None,
                     transforms: None,
+                    stream: None,
                 },
             },
         );
@@ -988,6 +1482,7 @@ mod tests {
             auth: None,
             defaults: EndpointDefaults::default(),
             response_transforms: Vec::new(),
+            xml: Default::default(),
             tools,
         };
 
@@ -1112,10 +1607,16 @@ mod tests {
                 path: "/users/{id}".to_string(),
                 description: None,
                 params,
+                body_encoding: Default::default(),
+                body_template: None,
+                pagination: None,
+                async_operation: None,
+                retry: None,
                 response: HttpResponseConfig {
                     mode: HttpResponseMode::Json,
                     output_schema: None,
                     transforms: None,
+                    stream: None,
                 },
             },
         );
@@ -1128,6 +1629,7 @@ mod tests {
             }),
             defaults,
             response_transforms: Vec::new(),
+            xml: Default::default(),
             tools,
         };
 
@@ -1252,10 +1754,16 @@ mod tests {
                 path: "/users/{id}".to_string(),
                 description: None,
                 params,
+                body_encoding: Default::default(),
+                body_template: None,
+                pagination: None,
+                async_operation: None,
+                retry: None,
                 response: HttpResponseConfig {
                     mode: HttpResponseMode::Json,
                     output_schema: Some(json!({"type": "object"})),
                     transforms: None,
+                    stream: None,
                 },
             },
         );
@@ -1265,6 +1773,7 @@ mod tests {
             auth: None,
             defaults: EndpointDefaults::default(),
             response_transforms: Vec::new(),
+            xml: Default::default(),
             tools,
         };
 
@@ -1339,10 +1848,16 @@ mod tests {
                 path: "/img".to_string(),
                 description: None,
                 params: HashMap::new(),
+                body_encoding: Default::default(),
+                body_template: None,
+                pagination: None,
+                async_operation: None,
+                retry: None,
                 response: HttpResponseConfig {
                     mode: HttpResponseMode::Text,
                     output_schema: None,
                     transforms: None,
+                    stream: None,
                 },
             },
         )]);
@@ -1352,6 +1867,7 @@ mod tests {
             auth: None,
             defaults: EndpointDefaults::default(),
             response_transforms: Vec::new(),
+            xml: Default::default(),
             tools,
         };
 
@@ -1391,4 +1907,482 @@ mod tests {
             .expect("server task join")
             .expect("server result");
     }
+
+    #[tokio::test]
+    async fn call_tool_sends_xml_bodies_and_converts_xml_responses() {
+        async fn echo_xml(body: Bytes) -> impl axum::response::IntoResponse {
+            (
+                [("content-type", "application/xml")],
+                format!(
+                    "<echo><sent>{}</sent></echo>",
+                    String::from_utf8_lossy(&body)
+                )
+                .replace("<?xml version=\"1.0\" encoding=\"UTF-8\"?>", ""),
+            )
+        }
+
+        let app = Router::new().route("/{*path}", any(echo_xml));
+        let listener = TcpListener::bind("127.0.0.1:0").await.expect("bind");
+        let base_url = format!("http://{}", listener.local_addr().expect("local_addr"));
+        let server_handle = tokio::spawn(async move { axum::serve(listener, app).await });
+
+        let cfg: HttpServerConfig = serde_json::from_value(json!({
+            "baseUrl": base_url,
+            "xml": { "attributePrefix": "_" },
+            "tools": {
+                "templated": {
+                    "method": "POST",
+                    "path": "/orders",
+                    "bodyEncoding": "xml",
+                    "bodyTemplate": "<order qty=\"{{qty}}\"><sku>{{sku}}</sku></order>",
+                    "params": {
+                        "sku": { "in": "body" },
+                        "qty": { "in": "body", "schema": { "type": "integer" } }
+                    }
+                },
+                "hinted": {
+                    "method": "POST",
+                    "path": "/orders",
+                    "bodyEncoding": "xml",
+                    "params": {
+                        "sku": { "in": "body", "schema": { "type": "string", "xml": { "attribute": true } } },
+                        "note": { "in": "body" }
+                    }
+                }
+            }
+        }))
+        .expect("config");
+        let source = HttpToolSource::new("test", cfg, Duration::from_secs(30)).expect("valid");
+
+        let body = |result: rmcp::model::CallToolResult| {
+            serde_json::from_str::<Value>(&result.content[0].as_text().expect("text").text)
+                .expect("json")
+        };
+
+        let result = source
+            .call_tool("templated", json!({ "sku": "a<b", "qty": 2 }))
+            .await
+            .expect("call_tool");
+        assert_eq!(
+            body(result),
+            json!({ "echo": { "sent": { "order": { "_qty": "2", "sku": "a<b" } } } })
+        );
+
+        let result = source
+            .call_tool("hinted", json!({ "sku": "x1", "note": "fast" }))
+            .await
+            .expect("call_tool");
+        assert_eq!(
+            body(result),
+            json!({ "echo": { "sent": { "hinted": { "_sku": "x1", "note": "fast" } } } })
+        );
+
+        let err = HttpToolSource::new(
+            "test",
+            serde_json::from_value(json!({
+                "baseUrl": "http://127.0.0.1:1",
+                "tools": {
+                    "bad": {
+                        "method": "POST",
+                        "path": "/",
+                        "bodyEncoding": "xml",
+                        "bodyTemplate": "<a>{{missing}}</a>"
+                    }
+                }
+            }))
+            .expect("config"),
+            Duration::from_secs(30),
+        )
+        .err()
+        .expect("unknown placeholder is rejected");
+        assert!(
+            err.to_string().contains("unknown body param 'missing'"),
+            "{err}"
+        );
+
+        server_handle.abort();
+    }
+
+    #[tokio::test]
+    async fn call_tool_follows_link_header_and_cursor_pages() {
+        async fn items(uri: Uri) -> impl axum::response::IntoResponse {
+            let mut pairs: Vec<&str> = uri.query().unwrap_or_default().split('&').collect();
+            pairs.sort_unstable();
+            let query = pairs.join("&");
+            let page = |data: Value, next: Option<&str>| {
+                let mut headers = HeaderMap::new();
+                if let Some(next) = next {
+                    headers.insert(
+                        "link",
+                        format!(r#"<{next}>; rel="next""#).parse().expect("header"),
+                    );
+                }
+                (headers, axum::Json(data))
+            };
+            match query.as_str() {
+                // Link-header pages: the links omit `key`, query auth re-adds it.
+                "key=k" => page(json!([1, 2]), Some("/items?p=2")),
+                "key=k&p=2" => page(json!([3]), Some("/items?p=3")),
+                "key=k&p=3" => page(json!([4]), None),
+                // Cursor pages.
+                "key=k&mode=cursor" => page(json!({ "data": [1], "next": "b" }), None),
+                "cursor=b&key=k&mode=cursor" => page(json!({ "data": [2], "next": null }), None),
+                other => page(json!({ "unexpected": other }), None),
+            }
+        }
+
+        let app = Router::new().route("/items", any(items));
+        let listener = TcpListener::bind("127.0.0.1:0").await.expect("bind");
+        let base_url = format!("http://{}", listener.local_addr().expect("local_addr"));
+        let server_handle = tokio::spawn(async move { axum::serve(listener, app).await });
+
+        let cfg: HttpServerConfig = serde_json::from_value(json!({
+            "baseUrl": base_url,
+            "auth": { "type": "query", "name": "key", "value": "k" },
+            "tools": {
+                "linked": {
+                    "method": "GET",
+                    "path": "/items",
+                    "pagination": { "type": "linkHeader", "maxPages": 2 },
+                    "response": { "outputSchema": { "type": "array" } }
+                },
+                "cursored": {
+                    "method": "GET",
+                    "path": "/items",
+                    "params": {
+                        "mode": { "in": "query" },
+                        "cursor": { "in": "query" }
+                    },
+                    "pagination": {
+                        "type": "cursor",
+                        "cursorPointer": "/next",
+                        "param": "cursor",
+                        "itemsPointer": "/data"
+                    }
+                }
+            }
+        }))
+        .expect("config");
+        let source = HttpToolSource::new("test", cfg, Duration::from_secs(30)).expect("valid");
+
+        let result = source
+            .call_tool("linked", json!({}))
+            .await
+            .expect("call_tool");
+        assert_eq!(
+            result.structured_content,
+            Some(json!({
+                "body": [1, 2, 3],
+                "pagination": { "pages": 2, "items": 3, "truncated": "maxPages" }
+            }))
+        );
+        assert!(
+            result.content[1]
+                .as_text()
+                .expect("notice")
+                .text
+                .contains("truncated")
+        );
+
+        let result = source
+            .call_tool("cursored", json!({ "mode": "cursor" }))
+            .await
+            .expect("call_tool");
+        assert_eq!(result.content.len(), 1);
+        let text = &result.content[0].as_text().expect("text").text;
+        assert_eq!(
+            serde_json::from_str::<Value>(text).expect("json"),
+            json!({ "data": [1, 2], "next": "b" })
+        );
+
+        let err = HttpToolSource::new(
+            "test",
+            serde_json::from_value(json!({
+                "baseUrl": "http://127.0.0.1:1",
+                "tools": {
+                    "bad": {
+                        "method": "GET",
+                        "path": "/",
+                        "pagination": { "type": "page", "param": "page" }
+                    }
+                }
+            }))
+            .expect("config"),
+            Duration::from_secs(30),
+        )
+        .err()
+        .expect("undeclared pagination param is rejected");
+        assert!(err.to_string().contains("'page'"), "{err}");
+
+        server_handle.abort();
+    }
+
+    #[tokio::test]
+    async fn call_tool_polls_accepted_operations_and_reports_progress() {
+        use crate::context::{CallContext, ProgressUpdate};
+        use axum::extract::State;
+        use std::sync::Arc;
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        type Polls = Arc<AtomicUsize>;
+
+        async fn jobs(State(polls): State<Polls>, uri: Uri) -> axum::response::Response {
+            use axum::response::IntoResponse as _;
+            match uri.path() {
+                "/jobs" => (
+                    axum::http::StatusCode::ACCEPTED,
+                    [("location", "/jobs/1")],
+                    axum::Json(json!({ "id": 1 })),
+                )
+                    .into_response(),
+                "/jobs/1" => {
+                    let body = match polls.fetch_add(1, Ordering::SeqCst) {
+                        0 => json!({ "status": "running", "percent": 50 }),
+                        _ => json!({ "status": "done", "result": "/jobs/1/result" }),
+                    };
+                    axum::Json(body).into_response()
+                }
+                "/jobs/1/result" => axum::Json(json!({ "answer": 42 })).into_response(),
+                "/slow" => (
+                    axum::http::StatusCode::ACCEPTED,
+                    [("location", "/slow/1")],
+                    axum::Json(json!({ "id": 2 })),
+                )
+                    .into_response(),
+                _ => axum::Json(json!({ "status": "running" })).into_response(),
+            }
+        }
+
+        let polls: Polls = Arc::default();
+        let app = Router::new()
+            .route("/{*path}", any(jobs))
+            .with_state(polls.clone());
+        let listener = TcpListener::bind("127.0.0.1:0").await.expect("bind");
+        let base_url = format!("http://{}", listener.local_addr().expect("local_addr"));
+        let server_handle = tokio::spawn(async move { axum::serve(listener, app).await });
+
+        let cfg: HttpServerConfig = serde_json::from_value(json!({
+            "baseUrl": base_url,
+            "tools": {
+                "runJob": {
+                    "method": "POST",
+                    "path": "/jobs",
+                    "asyncOperation": {
+                        "statePointer": "/status",
+                        "successStates": ["done"],
+                        "failureStates": ["failed"],
+                        "resultUrl": { "pointer": "/result" },
+                        "progressPointer": "/percent",
+                        "progressTotal": 100,
+                        "pollIntervalMs": 10
+                    }
+                },
+                "slowJob": {
+                    "method": "POST",
+                    "path": "/slow",
+                    "asyncOperation": {
+                        "statePointer": "/status",
+                        "successStates": ["done"],
+                        "pollIntervalMs": 10
+                    }
+                },
+                "stuckJob": {
+                    "method": "POST",
+                    "path": "/stuck",
+                    "asyncOperation": { "statusUrl": { "pointer": "/status" }, "pollIntervalMs": 10 }
+                }
+            }
+        }))
+        .expect("config");
+        let source = HttpToolSource::new("test", cfg, Duration::from_secs(30)).expect("valid");
+
+        let updates = Arc::new(parking_lot::Mutex::new(Vec::<ProgressUpdate>::new()));
+        let sink = updates.clone();
+        let ctx = CallContext::with_timeout(Duration::from_secs(10))
+            .with_progress(Arc::new(move |u| sink.lock().push(u)));
+        let result = source
+            .call_tool_with_context("runJob", json!({}), &ctx)
+            .await
+            .expect("call_tool");
+        let text = &result.content[0].as_text().expect("text").text;
+        assert_eq!(
+            serde_json::from_str::<Value>(text).expect("json"),
+            json!({ "answer": 42 })
+        );
+        assert_eq!(polls.load(Ordering::SeqCst), 2);
+        assert_eq!(
+            updates.lock().as_slice(),
+            [ProgressUpdate {
+                progress: 50.0,
+                total: Some(100.0),
+                message: Some("operation running".to_string()),
+            }]
+        );
+
+        // `/stuck` answers 200 (not 202), so its response is returned as-is.
+        let result = source
+            .call_tool("stuckJob", json!({}))
+            .await
+            .expect("call_tool");
+        assert!(
+            result.content[0]
+                .as_text()
+                .expect("text")
+                .text
+                .contains("running")
+        );
+
+        let err = source
+            .call_tool_with_context(
+                "slowJob",
+                json!({}),
+                &CallContext::with_timeout(Duration::from_millis(200)),
+            )
+            .await
+            .expect_err("deadline ends the wait");
+        assert!(err.to_string().contains("still running"), "{err}");
+
+        server_handle.abort();
+    }
+
+    #[tokio::test]
+    async fn call_tool_retries_transient_failures_of_idempotent_requests() {
+        use axum::extract::State;
+        use std::sync::Arc;
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        type Hits = Arc<AtomicUsize>;
+
+        async fn flaky(State(hits): State<Hits>) -> axum::response::Response {
+            use axum::response::IntoResponse as _;
+            if hits.fetch_add(1, Ordering::SeqCst) % 2 == 0 {
+                (
+                    axum::http::StatusCode::SERVICE_UNAVAILABLE,
+                    [("retry-after", "0")],
+                    "busy",
+                )
+                    .into_response()
+            } else {
+                axum::Json(json!({ "ok": true })).into_response()
+            }
+        }
+
+        let hits: Hits = Arc::default();
+        let app = Router::new()
+            .route("/{*path}", any(flaky))
+            .with_state(hits.clone());
+        let listener = TcpListener::bind("127.0.0.1:0").await.expect("bind");
+        let base_url = format!("http://{}", listener.local_addr().expect("local_addr"));
+        let server_handle = tokio::spawn(async move { axum::serve(listener, app).await });
+
+        let cfg: HttpServerConfig = serde_json::from_value(json!({
+            "baseUrl": base_url,
+            "defaults": { "retry": { "initialIntervalMs": 10 } },
+            "tools": {
+                "getThing": { "method": "GET", "path": "/thing" },
+                "createThing": { "method": "POST", "path": "/thing" }
+            }
+        }))
+        .expect("config");
+        let source = HttpToolSource::new("test", cfg, Duration::from_secs(30)).expect("valid");
+
+        let result = source
+            .call_tool("getThing", json!({}))
+            .await
+            .expect("retried");
+        assert_eq!(hits.load(Ordering::SeqCst), 2);
+        assert_eq!(
+            result.meta.expect("meta").get("unrelated"),
+            Some(&json!({ "attempts": 2 }))
+        );
+
+        // POST is not idempotent: one attempt only.
+        let err = source
+            .call_tool("createThing", json!({}))
+            .await
+            .expect_err("not retried");
+        assert!(err.to_string().contains("503"), "{err}");
+        assert_eq!(hits.load(Ordering::SeqCst), 3);
+
+        server_handle.abort();
+    }
+
+    #[tokio::test]
+    async fn call_tool_reads_streaming_responses_event_by_event() {
+        use crate::context::{CallContext, ProgressUpdate};
+        use std::sync::Arc;
+
+        async fn stream(uri: Uri) -> axum::response::Response {
+            use axum::response::IntoResponse as _;
+            match uri.path() {
+                "/chat" => (
+                    [("content-type", "text/event-stream")],
+                    "data: {\"delta\":\"Hel\"}\n\ndata: {\"delta\":\"lo\"}\n\ndata: [DONE]\n\n",
+                )
+                    .into_response(),
+                _ => (
+                    [("content-type", "application/x-ndjson")],
+                    "{\"line\":1}\n{\"line\":2}\n",
+                )
+                    .into_response(),
+            }
+        }
+
+        let app = Router::new().route("/{*path}", any(stream));
+        let listener = TcpListener::bind("127.0.0.1:0").await.expect("bind");
+        let base_url = format!("http://{}", listener.local_addr().expect("local_addr"));
+        let server_handle = tokio::spawn(async move { axum::serve(listener, app).await });
+
+        let cfg: HttpServerConfig = serde_json::from_value(json!({
+            "baseUrl": base_url,
+            "tools": {
+                "chat": {
+                    "method": "POST",
+                    "path": "/chat",
+                    "response": { "stream": { "aggregate": "concat", "pointer": "/delta" } }
+                },
+                "tail": { "method": "GET", "path": "/tail", "response": { "stream": {} } },
+                "raw": { "method": "GET", "path": "/tail", "response": { "mode": "text" } }
+            }
+        }))
+        .expect("config");
+        let source = HttpToolSource::new("test", cfg, Duration::from_secs(30)).expect("valid");
+
+        let updates = Arc::new(parking_lot::Mutex::new(Vec::<ProgressUpdate>::new()));
+        let sink = updates.clone();
+        let ctx = CallContext::default().with_progress(Arc::new(move |u| sink.lock().push(u)));
+        let result = source
+            .call_tool_with_context("chat", json!({}), &ctx)
+            .await
+            .expect("call_tool");
+        assert_eq!(result.content[0].as_text().expect("text").text, "Hello");
+        let messages: Vec<_> = updates
+            .lock()
+            .iter()
+            .map(|u| (u.progress, u.message.clone().unwrap_or_default()))
+            .collect();
+        assert_eq!(
+            messages,
+            [(1.0, "Hel".to_string()), (2.0, "lo".to_string())]
+        );
+
+        let result = source
+            .call_tool("tail", json!({}))
+            .await
+            .expect("call_tool");
+        let text = &result.content[0].as_text().expect("text").text;
+        assert_eq!(
+            serde_json::from_str::<Value>(text).expect("json"),
+            json!([{ "line": 1 }, { "line": 2 }])
+        );
+
+        // Without `response.stream` the body is buffered and returned as-is.
+        let result = source.call_tool("raw", json!({})).await.expect("call_tool");
+        assert_eq!(
+            result.content[0].as_text().expect("text").text,
+            "{\"line\":1}\n{\"line\":2}\n"
+        );
+
+        server_handle.abort();
+    }
 }
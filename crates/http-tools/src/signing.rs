@@ -0,0 +1,488 @@
+//! Request signing auth (`auth.type: sigv4` / `hmac`).
+//!
+//! Signatures cover the request as it is sent, so the runtimes call [`sign_request`] once the
+//! request is fully built (URL, headers and body). Retries resend the signed request unchanged.
+//!
+//! - `sigv4`: AWS Signature Version 4 (`Authorization: AWS4-HMAC-SHA256 ...`). The signed headers
+//!   are `host`, `content-type` and every `x-amz-*` header. Streamed bodies (multipart) are signed
+//!   as `UNSIGNED-PAYLOAD`.
+//! - `hmac`: hex/base64 HMAC-SHA256 of `METHOD\npath?query\ntimestamp\nhex(sha256(body))` in
+//!   `signatureHeader`, with the Unix timestamp (seconds) in `timestampHeader`.
+
+use crate::config::{AuthConfig, SignatureEncoding};
+use base64::Engine as _;
+use hmac::{Hmac, Mac};
+use percent_encoding::{AsciiSet, NON_ALPHANUMERIC, percent_decode_str, utf8_percent_encode};
+use reqwest::header::{CONTENT_TYPE, HOST, HeaderName, HeaderValue};
+use reqwest::{Client, Request, RequestBuilder};
+use sha2::{Digest, Sha256};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// Characters SigV4 leaves unencoded (RFC 3986 unreserved).
+const SIGV4_ENCODE_SET: &AsciiSet = &NON_ALPHANUMERIC
+    .remove(b'-')
+    .remove(b'_')
+    .remove(b'.')
+    .remove(b'~');
+
+const UNSIGNED_PAYLOAD: &str = "UNSIGNED-PAYLOAD";
+
+/// Check a signing auth config (other auth types are accepted as-is).
+///
+/// # Errors
+///
+/// Returns a message for empty credentials or scope, or an invalid header name.
+pub fn validate(auth: &AuthConfig) -> Result<(), String> {
+    match auth {
+        AuthConfig::Sigv4 {
+            access_key_id,
+            secret_access_key,
+            region,
+            service,
+            ..
+        } => {
+            for (field, value) in [
+                ("accessKeyId", access_key_id),
+                ("secretAccessKey", secret_access_key),
+                ("region", region),
+                ("service", service),
+            ] {
+                if value.trim().is_empty() {
+                    return Err(format!("sigv4 {field} must not be empty"));
+                }
+            }
+            Ok(())
+        }
+        AuthConfig::Hmac {
+            secret,
+            signature_header,
+            timestamp_header,
+            key_id_header,
+            ..
+        } => {
+            if secret.is_empty() {
+                return Err("hmac secret must not be empty".to_string());
+            }
+            for header in [signature_header, timestamp_header, key_id_header] {
+                HeaderName::from_bytes(header.as_bytes())
+                    .map_err(|_| format!("hmac header '{header}' is not a valid header name"))?;
+            }
+            Ok(())
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Sign a fully built request when `auth` is a signing auth type; other requests are returned
+/// unchanged.
+///
+/// # Errors
+///
+/// Returns a message when the request cannot be built or signed.
+pub fn sign_request(
+    client: &Client,
+    request: RequestBuilder,
+    auth: Option<&AuthConfig>,
+) -> Result<RequestBuilder, String> {
+    let Some(auth @ (AuthConfig::Sigv4 { .. } | AuthConfig::Hmac { .. })) = auth else {
+        return Ok(request);
+    };
+    let mut request = request.build().map_err(|e| e.without_url().to_string())?;
+    sign(&mut request, auth, SystemTime::now())?;
+    Ok(RequestBuilder::from_parts(client.clone(), request))
+}
+
+/// Add the signature headers of `auth` to `request`, as of `now`.
+///
+/// # Errors
+///
+/// Returns a message for header values that cannot be signed or sent, and for `hmac` requests
+/// with a streamed body.
+pub fn sign(request: &mut Request, auth: &AuthConfig, now: SystemTime) -> Result<(), String> {
+    match auth {
+        AuthConfig::Sigv4 {
+            access_key_id,
+            secret_access_key,
+            session_token,
+            region,
+            service,
+        } => {
+            let amz_date = chrono::DateTime::<chrono::Utc>::from(now)
+                .format("%Y%m%dT%H%M%SZ")
+                .to_string();
+            insert_header(request, "x-amz-date", &amz_date)?;
+            if let Some(token) = session_token {
+                insert_header(request, "x-amz-security-token", token)?;
+            }
+            let payload_hash = match body_bytes(request) {
+                Some(body) => hex::encode(Sha256::digest(body)),
+                None => UNSIGNED_PAYLOAD.to_string(),
+            };
+            if service == "s3" {
+                insert_header(request, "x-amz-content-sha256", &payload_hash)?;
+            }
+
+            let (canonical_headers, signed_headers) = sigv4_canonical_headers(request)?;
+            let canonical_request = format!(
+                "{}\n{}\n{}\n{canonical_headers}\n{signed_headers}\n{payload_hash}",
+                request.method(),
+                sigv4_canonical_uri(request.url().path(), service == "s3"),
+                sigv4_canonical_query(request.url().query().unwrap_or_default()),
+            );
+            let date = &amz_date[..8];
+            let scope = format!("{date}/{region}/{service}/aws4_request");
+            let string_to_sign = format!(
+                "AWS4-HMAC-SHA256\n{amz_date}\n{scope}\n{}",
+                hex::encode(Sha256::digest(canonical_request.as_bytes()))
+            );
+            let key = [date, region, service, "aws4_request"].iter().fold(
+                format!("AWS4{secret_access_key}").into_bytes(),
+                |key, part| hmac_sha256(&key, part.as_bytes()),
+            );
+            let signature = hex::encode(hmac_sha256(&key, string_to_sign.as_bytes()));
+            insert_header(
+                request,
+                "authorization",
+                &format!(
+                    "AWS4-HMAC-SHA256 Credential={access_key_id}/{scope}, \
+                     SignedHeaders={signed_headers}, Signature={signature}"
+                ),
+            )
+        }
+        AuthConfig::Hmac {
+            secret,
+            key_id,
+            signature_header,
+            timestamp_header,
+            key_id_header,
+            encoding,
+        } => {
+            let body = body_bytes(request).ok_or_else(|| {
+                "hmac signing needs a buffered request body (multipart bodies cannot be signed)"
+                    .to_string()
+            })?;
+            let body_hash = hex::encode(Sha256::digest(body));
+            let timestamp = now
+                .duration_since(UNIX_EPOCH)
+                .map_err(|_| "system clock is before 1970".to_string())?
+                .as_secs()
+                .to_string();
+            let url = request.url();
+            let target = match url.query() {
+                Some(query) => format!("{}?{query}", url.path()),
+                None => url.path().to_string(),
+            };
+            let string_to_sign =
+                format!("{}\n{target}\n{timestamp}\n{body_hash}", request.method());
+            let mac = hmac_sha256(secret.as_bytes(), string_to_sign.as_bytes());
+            let signature = match encoding {
+                SignatureEncoding::Hex => hex::encode(mac),
+                SignatureEncoding::Base64 => base64::engine::general_purpose::STANDARD.encode(mac),
+            };
+            insert_header(request, timestamp_header, &timestamp)?;
+            insert_header(request, signature_header, &signature)?;
+            if let Some(key_id) = key_id {
+                insert_header(request, key_id_header, key_id)?;
+            }
+            Ok(())
+        }
+        _ => Ok(()),
+    }
+}
+
+/// The request body; an empty slice without a body, `None` for streamed bodies.
+fn body_bytes(request: &Request) -> Option<&[u8]> {
+    match request.body() {
+        Some(body) => body.as_bytes(),
+        None => Some(&[]),
+    }
+}
+
+fn hmac_sha256(key: &[u8], data: &[u8]) -> Vec<u8> {
+    let mut mac = HmacSha256::new_from_slice(key).expect("HMAC accepts keys of any length");
+    mac.update(data);
+    mac.finalize().into_bytes().to_vec()
+}
+
+fn insert_header(request: &mut Request, name: &str, value: &str) -> Result<(), String> {
+    let name = HeaderName::from_bytes(name.as_bytes())
+        .map_err(|_| format!("invalid signature header name '{name}'"))?;
+    let value = HeaderValue::from_str(value)
+        .map_err(|_| format!("invalid value for signature header '{name}'"))?;
+    request.headers_mut().insert(name, value);
+    Ok(())
+}
+
+/// Path segments URI-encoded per SigV4: S3 encodes the decoded path once, other services encode
+/// the (already encoded) request path again.
+fn sigv4_canonical_uri(path: &str, s3: bool) -> String {
+    let path = if path.is_empty() { "/" } else { path };
+    path.split('/')
+        .map(|segment| {
+            if s3 {
+                let decoded = percent_decode_str(segment).decode_utf8_lossy();
+                utf8_percent_encode(&decoded, SIGV4_ENCODE_SET).to_string()
+            } else {
+                utf8_percent_encode(segment, SIGV4_ENCODE_SET).to_string()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// Query parameters URI-encoded and sorted by name, then value.
+fn sigv4_canonical_query(query: &str) -> String {
+    let encode = |s: &str| {
+        let decoded = percent_decode_str(s).decode_utf8_lossy();
+        utf8_percent_encode(&decoded, SIGV4_ENCODE_SET).to_string()
+    };
+    let mut pairs: Vec<(String, String)> = query
+        .split('&')
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| {
+            let (name, value) = pair.split_once('=').unwrap_or((pair, ""));
+            (encode(name), encode(value))
+        })
+        .collect();
+    pairs.sort();
+    pairs
+        .iter()
+        .map(|(name, value)| format!("{name}={value}"))
+        .collect::<Vec<_>>()
+        .join("&")
+}
+
+/// Canonical header block (one `name:value\n` per header) and the signed header list.
+fn sigv4_canonical_headers(request: &Request) -> Result<(String, String), String> {
+    let url = request.url();
+    let host = match request.headers().get(HOST) {
+        Some(host) => host
+            .to_str()
+            .map_err(|_| "host header is not ASCII".to_string())?
+            .to_string(),
+        None => {
+            let host = url.host_str().unwrap_or_default();
+            match url.port() {
+                Some(port) => format!("{host}:{port}"),
+                None => host.to_string(),
+            }
+        }
+    };
+    let mut headers = vec![("host".to_string(), host)];
+    for name in request.headers().keys() {
+        let name = name.as_str();
+        if name == CONTENT_TYPE.as_str() || name.starts_with("x-amz-") {
+            let values = request
+                .headers()
+                .get_all(name)
+                .iter()
+                .map(|v| {
+                    v.to_str()
+                        .map(|v| v.split_whitespace().collect::<Vec<_>>().join(" "))
+                        .map_err(|_| format!("header '{name}' is not ASCII"))
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            headers.push((name.to_string(), values.join(",")));
+        }
+    }
+    headers.sort();
+    let canonical = headers
+        .iter()
+        .map(|(name, value)| format!("{name}:{value}\n"))
+        .collect();
+    let signed = headers
+        .iter()
+        .map(|(name, _)| name.as_str())
+        .collect::<Vec<_>>()
+        .join(";");
+    Ok((canonical, signed))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use std::time::Duration;
+
+    fn auth(v: serde_json::Value) -> AuthConfig {
+        serde_json::from_value(v).expect("auth config")
+    }
+
+    fn at(unix_secs: u64) -> SystemTime {
+        UNIX_EPOCH + Duration::from_secs(unix_secs)
+    }
+
+    fn header<'a>(request: &'a Request, name: &str) -> &'a str {
+        request
+            .headers()
+            .get(name)
+            .and_then(|v| v.to_str().ok())
+            .unwrap_or_default()
+    }
+
+    fn aws_test_suite_auth() -> AuthConfig {
+        auth(json!({
+            "type": "sigv4",
+            "accessKeyId": "AKIDEXAMPLE",
+            "secretAccessKey": "wJalrXUtnFEMI/K7MDENG+bPxRfiCYEXAMPLEKEY",
+            "region": "us-east-1",
+            "service": "service"
+        }))
+    }
+
+    // 2015-08-30T12:36:00Z, the date of the AWS SigV4 test suite.
+    const AWS_TEST_SUITE_TIME: u64 = 1_440_938_160;
+
+    #[test]
+    fn signs_aws_test_suite_requests() {
+        let client = Client::new();
+        let cases = [
+            (
+                "get-vanilla",
+                client.get("https://example.amazonaws.com/"),
+                "host;x-amz-date",
+                "5fa00fa31553b73ebf1942676e86291e8372ff2a2260956d9b8aae1d763fbf31",
+            ),
+            (
+                "get-vanilla-query-order-key-case",
+                client.get("https://example.amazonaws.com/?Param2=value2&Param1=value1"),
+                "host;x-amz-date",
+                "b97d918cfa904a5beff61c982a1b6f458b799221646efd99d3219ec94cdf2500",
+            ),
+            (
+                "post-x-www-form-urlencoded",
+                client
+                    .post("https://example.amazonaws.com/")
+                    .header("content-type", "application/x-www-form-urlencoded")
+                    .body("Param1=value1"),
+                "content-type;host;x-amz-date",
+                "ff11897932ad3f4e8b18135d722051e5ac45fc38421b1da7b9d196a0fe09473a",
+            ),
+        ];
+        for (name, request, signed_headers, signature) in cases {
+            let mut request = request.build().expect("request");
+            sign(
+                &mut request,
+                &aws_test_suite_auth(),
+                at(AWS_TEST_SUITE_TIME),
+            )
+            .expect("sign");
+            assert_eq!(header(&request, "x-amz-date"), "20150830T123600Z", "{name}");
+            assert_eq!(
+                header(&request, "authorization"),
+                format!(
+                    "AWS4-HMAC-SHA256 Credential=AKIDEXAMPLE/20150830/us-east-1/service/aws4_request, \
+                     SignedHeaders={signed_headers}, Signature={signature}"
+                ),
+                "{name}"
+            );
+        }
+    }
+
+    #[test]
+    fn signs_session_tokens_and_s3_payload_hashes() {
+        let auth = auth(json!({
+            "type": "sigv4",
+            "accessKeyId": "AKIDEXAMPLE",
+            "secretAccessKey": "wJalrXUtnFEMI/K7MDENG+bPxRfiCYEXAMPLEKEY",
+            "sessionToken": "token",
+            "region": "us-east-1",
+            "service": "s3"
+        }));
+        let mut request = Client::new()
+            .get("https://bucket.s3.amazonaws.com/my%20key")
+            .build()
+            .expect("request");
+        sign(&mut request, &auth, at(AWS_TEST_SUITE_TIME)).expect("sign");
+        assert_eq!(header(&request, "x-amz-security-token"), "token");
+        assert_eq!(
+            header(&request, "x-amz-content-sha256"),
+            "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+        );
+        assert!(
+            header(&request, "authorization").contains(
+                "SignedHeaders=host;x-amz-content-sha256;x-amz-date;x-amz-security-token,"
+            ),
+            "{}",
+            header(&request, "authorization")
+        );
+    }
+
+    #[test]
+    fn builds_canonical_uris_and_queries() {
+        assert_eq!(sigv4_canonical_uri("", false), "/");
+        assert_eq!(
+            sigv4_canonical_uri("/a%20b/c", false),
+            "/a%2520b/c",
+            "non-S3 paths are encoded twice"
+        );
+        assert_eq!(sigv4_canonical_uri("/a%20b/c", true), "/a%20b/c");
+        assert_eq!(
+            sigv4_canonical_query("b=2&a=x%2Fy&a=1&flag"),
+            "a=1&a=x%2Fy&b=2&flag="
+        );
+    }
+
+    #[test]
+    fn signs_hmac_requests() {
+        let hex_auth = auth(json!({
+            "type": "hmac",
+            "secret": "s3cr3t",
+            "keyId": "client-1"
+        }));
+        let mut request = Client::new()
+            .post("https://api.example.com/v1/orders?dryRun=true")
+            .body("{\"sku\":\"A1\"}")
+            .build()
+            .expect("request");
+        sign(&mut request, &hex_auth, at(1_700_000_000)).expect("sign");
+        assert_eq!(header(&request, "x-timestamp"), "1700000000");
+        assert_eq!(header(&request, "x-key-id"), "client-1");
+        assert_eq!(
+            header(&request, "x-signature"),
+            "55f87bdb154136f0966b49db75abd8b1896d042c4085deec16dc5788158932f5"
+        );
+
+        let base64_auth = auth(json!({
+            "type": "hmac",
+            "secret": "s3cr3t",
+            "signatureHeader": "X-Api-Signature",
+            "timestampHeader": "X-Api-Timestamp",
+            "encoding": "base64"
+        }));
+        let mut request = Client::new()
+            .get("https://api.example.com/v1/orders")
+            .build()
+            .expect("request");
+        sign(&mut request, &base64_auth, at(1_700_000_000)).expect("sign");
+        assert_eq!(header(&request, "x-api-timestamp"), "1700000000");
+        assert_eq!(
+            header(&request, "x-api-signature"),
+            "C17uSB/UZgksynAT2lEiSFEhYxv4CJHD4PN1c0Ul/2c="
+        );
+        assert!(request.headers().get("x-key-id").is_none());
+    }
+
+    #[test]
+    fn validates_signing_configs() {
+        assert!(validate(&aws_test_suite_auth()).is_ok());
+        let err = validate(&auth(json!({
+            "type": "sigv4",
+            "accessKeyId": "AKID",
+            "secretAccessKey": "secret",
+            "region": "",
+            "service": "execute-api"
+        })))
+        .unwrap_err();
+        assert!(err.contains("region"), "{err}");
+        let err = validate(&auth(json!({
+            "type": "hmac",
+            "secret": "s",
+            "signatureHeader": "bad header"
+        })))
+        .unwrap_err();
+        assert!(err.contains("bad header"), "{err}");
+    }
+}
@@ -0,0 +1,369 @@
+//! Streaming responses: `text/event-stream` (SSE) and newline-delimited JSON.
+//!
+//! Instead of buffering the whole body, [`read_stream`] decodes events as chunks arrive
+//! ([`EventDecoder`]), reports each event as progress when the caller asked for it, and combines
+//! the events into one result ([`Aggregator`], `response.stream.aggregate`). The size limit
+//! (`max_response_bytes`) applies to the aggregated result and to a single pending event, so a long
+//! stream aggregated with `last` does not hit it.
+
+use crate::config::{StreamAggregation, StreamConfig};
+use crate::context::CallContext;
+use serde_json::Value;
+
+/// Progress messages carry at most this many characters of an event.
+const MAX_PROGRESS_MESSAGE_CHARS: usize = 1000;
+
+/// A streaming response format.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StreamFormat {
+    /// `text/event-stream`: the `data:` lines of each event.
+    Sse,
+    /// `application/x-ndjson`, `application/jsonl`, ...: one event per non-empty line.
+    Ndjson,
+}
+
+/// The streaming format of a content type, if any.
+#[must_use]
+pub fn stream_format(content_type: Option<&str>) -> Option<StreamFormat> {
+    let essence = content_type?.split(';').next()?.trim().to_ascii_lowercase();
+    match essence.as_str() {
+        "text/event-stream" => Some(StreamFormat::Sse),
+        "application/x-ndjson"
+        | "application/ndjson"
+        | "application/jsonl"
+        | "application/x-jsonlines"
+        | "application/json-seq" => Some(StreamFormat::Ndjson),
+        _ => None,
+    }
+}
+
+/// Check a stream config.
+///
+/// # Errors
+///
+/// Returns a message for a `pointer` that is not a JSON pointer.
+pub fn validate(config: &StreamConfig) -> Result<(), String> {
+    match &config.pointer {
+        Some(pointer) if !pointer.starts_with('/') => Err(format!(
+            "stream pointer '{pointer}' must be a JSON pointer (e.g. '/delta')"
+        )),
+        _ => Ok(()),
+    }
+}
+
+/// Splits a byte stream into events.
+#[derive(Debug)]
+pub struct EventDecoder {
+    format: StreamFormat,
+    /// Bytes of the current, incomplete line.
+    line: Vec<u8>,
+    /// `data:` lines of the current SSE event.
+    data: Vec<String>,
+    pending_bytes: usize,
+}
+
+impl EventDecoder {
+    #[must_use]
+    pub fn new(format: StreamFormat) -> Self {
+        Self {
+            format,
+            line: Vec::new(),
+            data: Vec::new(),
+            pending_bytes: 0,
+        }
+    }
+
+    /// Feed a chunk; returns the events it completed.
+    pub fn push(&mut self, chunk: &[u8]) -> Vec<String> {
+        let mut events = Vec::new();
+        for &b in chunk {
+            if b == b'\n' {
+                let line = std::mem::take(&mut self.line);
+                self.line_done(&line, &mut events);
+            } else {
+                self.line.push(b);
+            }
+        }
+        self.pending_bytes = self.line.len() + self.data.iter().map(String::len).sum::<usize>();
+        events
+    }
+
+    /// Bytes buffered for the incomplete event.
+    #[must_use]
+    pub fn pending_bytes(&self) -> usize {
+        self.pending_bytes
+    }
+
+    /// End of stream: returns the last event when it was not terminated.
+    pub fn finish(mut self) -> Vec<String> {
+        let mut events = Vec::new();
+        let line = std::mem::take(&mut self.line);
+        if !line.is_empty() {
+            self.line_done(&line, &mut events);
+        }
+        if self.format == StreamFormat::Sse {
+            self.line_done(b"", &mut events);
+        }
+        events
+    }
+
+    fn line_done(&mut self, line: &[u8], events: &mut Vec<String>) {
+        let line = String::from_utf8_lossy(line);
+        let line = line.strip_suffix('\r').unwrap_or(&line);
+        match self.format {
+            StreamFormat::Ndjson => {
+                // `application/json-seq` prefixes records with RS (0x1E).
+                let line = line.trim_start_matches('\u{1e}').trim();
+                if !line.is_empty() {
+                    events.push(line.to_string());
+                }
+            }
+            StreamFormat::Sse => {
+                if line.is_empty() {
+                    if !self.data.is_empty() {
+                        let data = std::mem::take(&mut self.data).join("\n");
+                        // OpenAI-style end marker.
+                        if data != "[DONE]" {
+                            events.push(data);
+                        }
+                    }
+                } else if let Some(data) = line.strip_prefix("data:") {
+                    self.data
+                        .push(data.strip_prefix(' ').unwrap_or(data).to_string());
+                }
+                // Comments, `event:`, `id:` and `retry:` lines carry no data.
+            }
+        }
+    }
+}
+
+/// Combines decoded events into the result.
+#[derive(Debug)]
+pub struct Aggregator<'a> {
+    config: &'a StreamConfig,
+    max_bytes: Option<usize>,
+    events: Vec<Value>,
+    text: String,
+    last: Option<Value>,
+    size: usize,
+    count: usize,
+}
+
+impl<'a> Aggregator<'a> {
+    #[must_use]
+    pub fn new(config: &'a StreamConfig, max_bytes: Option<usize>) -> Self {
+        Self {
+            config,
+            max_bytes,
+            events: Vec::new(),
+            text: String::new(),
+            last: None,
+            size: 0,
+            count: 0,
+        }
+    }
+
+    /// Add an event (parsed as JSON when possible); returns the value that was kept, or `None`
+    /// when the event has nothing at `pointer`.
+    ///
+    /// # Errors
+    ///
+    /// Returns a message when the aggregated result exceeds the size limit.
+    pub fn add(&mut self, event: &str) -> Result<Option<Value>, String> {
+        let mut value = serde_json::from_str(event).unwrap_or_else(|_| Value::String(event.into()));
+        if let Some(pointer) = &self.config.pointer {
+            match value.pointer(pointer) {
+                Some(v) if !v.is_null() => value = v.clone(),
+                _ => return Ok(None),
+            }
+        }
+        self.count += 1;
+        let size = match &value {
+            Value::String(s) => s.len(),
+            other => other.to_string().len(),
+        };
+        match self.config.aggregate {
+            StreamAggregation::Last => {
+                self.size = size;
+                self.last = Some(value.clone());
+            }
+            StreamAggregation::Concat => {
+                self.size += size;
+                match &value {
+                    Value::String(s) => self.text.push_str(s),
+                    other => self.text.push_str(&other.to_string()),
+                }
+            }
+            StreamAggregation::Array => {
+                self.size += size + 1;
+                self.events.push(value.clone());
+            }
+        }
+        if let Some(max) = self.max_bytes
+            && self.size > max
+        {
+            return Err(format!("Response too large: exceeded {max} bytes"));
+        }
+        Ok(Some(value))
+    }
+
+    /// Number of events kept.
+    #[must_use]
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    #[must_use]
+    pub fn finish(self) -> Value {
+        match self.config.aggregate {
+            StreamAggregation::Last => self.last.unwrap_or(Value::Null),
+            StreamAggregation::Concat => Value::String(self.text),
+            StreamAggregation::Array => Value::Array(self.events),
+        }
+    }
+}
+
+/// Reading a streaming response failed.
+#[derive(Debug)]
+pub enum StreamError {
+    Transport(reqwest::Error),
+    /// The size limit was exceeded.
+    TooLarge(String),
+}
+
+/// Read a streaming response incrementally, reporting every event to `ctx`.
+///
+/// # Errors
+///
+/// Returns a transport error, or [`StreamError::TooLarge`] when the aggregated result or a single
+/// event exceeds `max_bytes`.
+pub async fn read_stream(
+    mut response: reqwest::Response,
+    format: StreamFormat,
+    config: &StreamConfig,
+    max_bytes: Option<usize>,
+    ctx: &CallContext,
+) -> Result<Value, StreamError> {
+    let mut decoder = EventDecoder::new(format);
+    let mut aggregator = Aggregator::new(config, max_bytes);
+    while let Some(chunk) = response.chunk().await.map_err(StreamError::Transport)? {
+        for event in decoder.push(&chunk) {
+            add_event(&mut aggregator, &event, ctx)?;
+        }
+        if let Some(max) = max_bytes
+            && decoder.pending_bytes() > max
+        {
+            return Err(StreamError::TooLarge(format!(
+                "Response too large: an event exceeded {max} bytes"
+            )));
+        }
+    }
+    for event in decoder.finish() {
+        add_event(&mut aggregator, &event, ctx)?;
+    }
+    Ok(aggregator.finish())
+}
+
+fn add_event(
+    aggregator: &mut Aggregator<'_>,
+    event: &str,
+    ctx: &CallContext,
+) -> Result<(), StreamError> {
+    let Some(value) = aggregator.add(event).map_err(StreamError::TooLarge)? else {
+        return Ok(());
+    };
+    if ctx.reports_progress() {
+        let mut message = match value {
+            Value::String(s) => s,
+            other => other.to_string(),
+        };
+        if let Some((idx, _)) = message.char_indices().nth(MAX_PROGRESS_MESSAGE_CHARS) {
+            message.truncate(idx);
+            message.push('…');
+        }
+        ctx.report_step(Some(message));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn decode(format: StreamFormat, chunks: &[&str]) -> Vec<String> {
+        let mut decoder = EventDecoder::new(format);
+        let mut events: Vec<String> = chunks
+            .iter()
+            .flat_map(|c| decoder.push(c.as_bytes()))
+            .collect();
+        events.extend(decoder.finish());
+        events
+    }
+
+    #[test]
+    fn detects_streaming_content_types() {
+        assert_eq!(
+            stream_format(Some("text/event-stream; charset=utf-8")),
+            Some(StreamFormat::Sse)
+        );
+        assert_eq!(
+            stream_format(Some("application/x-ndjson")),
+            Some(StreamFormat::Ndjson)
+        );
+        assert_eq!(stream_format(Some("application/json")), None);
+        assert_eq!(stream_format(None), None);
+    }
+
+    #[test]
+    fn decodes_sse_events_across_chunks() {
+        let events = decode(
+            StreamFormat::Sse,
+            &[
+                ": keep-alive\n\nevent: delta\nda",
+                "ta: {\"a\":1}\r\n\r\ndata: line 1\ndata: line 2\n\n",
+                "data: [DONE]\n\ndata: tail",
+            ],
+        );
+        assert_eq!(events, ["{\"a\":1}", "line 1\nline 2", "tail"]);
+    }
+
+    #[test]
+    fn decodes_ndjson_lines() {
+        let events = decode(
+            StreamFormat::Ndjson,
+            &["{\"n\":1}\n\n{\"n\"", ":2}\n{\"n\":3}"],
+        );
+        assert_eq!(events, ["{\"n\":1}", "{\"n\":2}", "{\"n\":3}"]);
+    }
+
+    #[test]
+    fn aggregates_events() {
+        let stream = |v: Value| -> StreamConfig { serde_json::from_value(v).expect("config") };
+
+        let array = stream(json!({}));
+        let mut agg = Aggregator::new(&array, None);
+        agg.add("{\"n\":1}").unwrap();
+        agg.add("text").unwrap();
+        assert_eq!(agg.finish(), json!([{ "n": 1 }, "text"]));
+
+        let concat = stream(json!({ "aggregate": "concat", "pointer": "/delta" }));
+        let mut agg = Aggregator::new(&concat, None);
+        agg.add("{\"delta\":\"Hel\"}").unwrap();
+        agg.add("{\"delta\":\"lo\"}").unwrap();
+        agg.add("{\"other\":1}").unwrap();
+        assert_eq!(agg.finish(), json!("Hello"));
+
+        let last = stream(json!({ "aggregate": "last" }));
+        let mut agg = Aggregator::new(&last, Some(10));
+        for n in 0..100 {
+            agg.add(&n.to_string()).unwrap();
+        }
+        assert_eq!(agg.finish(), json!(99));
+
+        let mut agg = Aggregator::new(&array, Some(10));
+        agg.add("\"12345\"").unwrap();
+        let err = agg.add("\"67890\"").unwrap_err();
+        assert!(err.contains("too large"), "{err}");
+    }
+}
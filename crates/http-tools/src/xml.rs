@@ -0,0 +1,841 @@
+//! XML request and response bodies.
+//!
+//! XML responses ([`xml_to_json`]) are converted with a fixed convention so response shaping and
+//! `outputSchema` can address them:
+//! - the document becomes `{ "<root>": <element> }`
+//! - attributes become `"<attributePrefix><name>"` string members (namespace declarations are
+//!   dropped, names are local names)
+//! - child elements become members; repeated names become arrays
+//! - an element with only text becomes its (trimmed) text; otherwise the text is kept under
+//!   `textKey`
+//!
+//! [`xml_to_json_with_schema`] instead reads the root element into the shape of a JSON Schema
+//! carrying `OpenAPI` `xml` hints (`name`, `attribute`, `wrapped`) and coerces text to the declared
+//! scalar types; parts the schema does not describe fall back to the convention.
+//!
+//! Request bodies ([`json_to_xml`]) use the same hints in reverse (plus `namespace`/`prefix`);
+//! members the schema does not describe follow the convention (attribute prefix, text key).
+//! Elements are written in JSON key order (sorted), so [`render_template`] is the way to send
+//! documents whose element order matters.
+
+use crate::config::XmlConfig;
+use quick_xml::Reader;
+use quick_xml::escape::{escape, resolve_predefined_entity};
+use quick_xml::events::{BytesStart, Event};
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+
+/// Root element name used when neither the schema nor the caller names one.
+pub const DEFAULT_ROOT: &str = "root";
+
+const XML_DECLARATION: &str = r#"<?xml version="1.0" encoding="UTF-8"?>"#;
+
+/// Maximum element nesting accepted when parsing (conversion recurses per level).
+const MAX_DEPTH: usize = 128;
+
+static EMPTY_SCHEMA: Value = Value::Null;
+
+/// How an `xml` request body is produced.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct XmlBody {
+    /// Root element name when the schema has no `xml.name` (empty uses [`DEFAULT_ROOT`]).
+    pub root: String,
+    /// JSON Schema of the body value, with `OpenAPI` `xml` hints.
+    pub schema: Value,
+    /// Template rendered instead of serializing the body value.
+    pub template: Option<String>,
+    pub conventions: XmlConfig,
+}
+
+/// Whether a media type is XML (`application/xml`, `text/xml` or `*+xml`).
+#[must_use]
+pub fn is_xml_media_type(media_type: &str) -> bool {
+    let essence = media_type
+        .split(';')
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_ascii_lowercase();
+    matches!(essence.as_str(), "application/xml" | "text/xml") || essence.ends_with("+xml")
+}
+
+/// Convert an XML document to JSON using the attribute/text conventions of `config`.
+///
+/// # Errors
+///
+/// Returns a message if the document is not well-formed XML or is nested too deeply.
+pub fn xml_to_json(text: &str, config: &XmlConfig) -> Result<Value, String> {
+    let root = parse(text)?;
+    let mut out = Map::new();
+    out.insert(root.name.clone(), convention_value(&root, config));
+    Ok(Value::Object(out))
+}
+
+/// Convert an XML document to the JSON value described by `schema` (the root element's schema).
+///
+/// # Errors
+///
+/// Returns a message if the document is not well-formed XML or is nested too deeply.
+pub fn xml_to_json_with_schema(
+    text: &str,
+    schema: &Value,
+    config: &XmlConfig,
+) -> Result<Value, String> {
+    let root = parse(text)?;
+    Ok(schema_value(&root, schema, config))
+}
+
+/// Serialize a JSON body value to an XML document.
+///
+/// # Errors
+///
+/// Returns a message if a name is not a valid XML name or a structured value is used where only
+/// text fits (attributes, text key).
+pub fn json_to_xml(value: &Value, body: &XmlBody) -> Result<String, String> {
+    let root = Hints::of(&body.schema)
+        .name
+        .or_else(|| Some(body.root.as_str()).filter(|r| !r.is_empty()))
+        .unwrap_or(DEFAULT_ROOT);
+    let mut out = String::from(XML_DECLARATION);
+    write_element(&mut out, root, value, &body.schema, &body.conventions)?;
+    Ok(out)
+}
+
+/// Render an XML body template: `{{name}}` is replaced by the escaped text of argument `name`
+/// (empty when absent or null).
+///
+/// # Errors
+///
+/// Returns a message for an unterminated placeholder or a non-scalar argument.
+pub fn render_template(template: &str, args: &HashMap<String, Value>) -> Result<String, String> {
+    let mut out = String::with_capacity(template.len());
+    for segment in template_segments(template)? {
+        match segment {
+            Segment::Literal(text) => out.push_str(text),
+            Segment::Placeholder(name) => {
+                if let Some(value) = args.get(name) {
+                    let text = scalar_text(value)
+                        .map_err(|e| format!("template placeholder '{name}': {e}"))?;
+                    out.push_str(&escape(text.as_str()));
+                }
+            }
+        }
+    }
+    Ok(out)
+}
+
+/// Argument names referenced by a template.
+///
+/// # Errors
+///
+/// Returns a message for an unterminated placeholder.
+pub fn template_placeholders(template: &str) -> Result<Vec<&str>, String> {
+    Ok(template_segments(template)?
+        .into_iter()
+        .filter_map(|s| match s {
+            Segment::Placeholder(name) => Some(name),
+            Segment::Literal(_) => None,
+        })
+        .collect())
+}
+
+enum Segment<'a> {
+    Literal(&'a str),
+    Placeholder(&'a str),
+}
+
+fn template_segments(template: &str) -> Result<Vec<Segment<'_>>, String> {
+    let mut segments = Vec::new();
+    let mut rest = template;
+    while let Some(start) = rest.find("{{") {
+        segments.push(Segment::Literal(&rest[..start]));
+        let after = &rest[start + 2..];
+        let end = after
+            .find("}}")
+            .ok_or_else(|| "unterminated '{{' in XML body template".to_string())?;
+        segments.push(Segment::Placeholder(after[..end].trim()));
+        rest = &after[end + 2..];
+    }
+    segments.push(Segment::Literal(rest));
+    Ok(segments)
+}
+
+// ============================================================================
+// Parsing
+// ============================================================================
+
+#[derive(Debug, Default)]
+struct Element {
+    name: String,
+    attributes: Vec<(String, String)>,
+    children: Vec<Element>,
+    text: String,
+}
+
+impl Element {
+    fn attribute(&self, name: &str) -> Option<&str> {
+        self.attributes
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, v)| v.as_str())
+    }
+
+    fn child(&self, name: &str) -> Option<&Element> {
+        self.children.iter().find(|c| c.name == name)
+    }
+}
+
+fn parse(text: &str) -> Result<Element, String> {
+    let mut reader = Reader::from_str(text);
+    let mut stack: Vec<Element> = Vec::new();
+    let mut root: Option<Element> = None;
+
+    loop {
+        let event = reader
+            .read_event()
+            .map_err(|e| format!("invalid XML at byte {}: {e}", reader.error_position()))?;
+        if matches!(event, Event::Start(_) | Event::Empty(_)) && stack.len() >= MAX_DEPTH {
+            return Err(format!(
+                "invalid XML: elements nested deeper than {MAX_DEPTH} levels"
+            ));
+        }
+        match event {
+            Event::Start(start) => stack.push(start_element(&start)?),
+            Event::Empty(start) => close_element(start_element(&start)?, &mut stack, &mut root)?,
+            Event::End(_) => {
+                let element = stack
+                    .pop()
+                    .ok_or_else(|| "invalid XML: unexpected end tag".to_string())?;
+                close_element(element, &mut stack, &mut root)?;
+            }
+            Event::Text(t) => {
+                if let Some(current) = stack.last_mut() {
+                    current
+                        .text
+                        .push_str(&t.decode().map_err(|e| e.to_string())?);
+                }
+            }
+            Event::CData(c) => {
+                if let Some(current) = stack.last_mut() {
+                    current
+                        .text
+                        .push_str(&c.decode().map_err(|e| e.to_string())?);
+                }
+            }
+            Event::GeneralRef(r) => {
+                if let Some(current) = stack.last_mut() {
+                    if let Some(ch) = r.resolve_char_ref().map_err(|e| e.to_string())? {
+                        current.text.push(ch);
+                    } else {
+                        let name = r.decode().map_err(|e| e.to_string())?;
+                        let resolved = resolve_predefined_entity(&name)
+                            .ok_or_else(|| format!("invalid XML: unknown entity '&{name};'"))?;
+                        current.text.push_str(resolved);
+                    }
+                }
+            }
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+
+    if !stack.is_empty() {
+        return Err("invalid XML: unclosed element".to_string());
+    }
+    root.ok_or_else(|| "invalid XML: no root element".to_string())
+}
+
+fn start_element(start: &BytesStart<'_>) -> Result<Element, String> {
+    let mut element = Element {
+        name: String::from_utf8_lossy(start.local_name().as_ref()).into_owned(),
+        ..Element::default()
+    };
+    for attr in start.attributes() {
+        let attr = attr.map_err(|e| format!("invalid XML attribute: {e}"))?;
+        if attr.key.as_namespace_binding().is_some() {
+            continue;
+        }
+        let value = attr
+            .unescape_value()
+            .map_err(|e| format!("invalid XML attribute: {e}"))?;
+        element.attributes.push((
+            String::from_utf8_lossy(attr.key.local_name().as_ref()).into_owned(),
+            value.into_owned(),
+        ));
+    }
+    Ok(element)
+}
+
+fn close_element(
+    mut element: Element,
+    stack: &mut [Element],
+    root: &mut Option<Element>,
+) -> Result<(), String> {
+    element.text = element.text.trim().to_string();
+    if let Some(parent) = stack.last_mut() {
+        parent.children.push(element);
+    } else if root.is_none() {
+        *root = Some(element);
+    } else {
+        return Err("invalid XML: multiple root elements".to_string());
+    }
+    Ok(())
+}
+
+// ============================================================================
+// XML → JSON
+// ============================================================================
+
+fn convention_value(element: &Element, config: &XmlConfig) -> Value {
+    if element.attributes.is_empty() && element.children.is_empty() {
+        return Value::String(element.text.clone());
+    }
+
+    let mut out = Map::new();
+    for (name, value) in &element.attributes {
+        out.insert(
+            format!("{}{name}", config.attribute_prefix),
+            Value::String(value.clone()),
+        );
+    }
+    for child in &element.children {
+        let value = convention_value(child, config);
+        match out.get_mut(&child.name) {
+            // Convention values are never arrays, so an array here holds repeated elements.
+            Some(Value::Array(items)) => items.push(value),
+            Some(existing) => {
+                let first = existing.take();
+                *existing = Value::Array(vec![first, value]);
+            }
+            None => {
+                out.insert(child.name.clone(), value);
+            }
+        }
+    }
+    if !element.text.is_empty() {
+        out.insert(config.text_key.clone(), Value::String(element.text.clone()));
+    }
+    Value::Object(out)
+}
+
+fn schema_value(element: &Element, schema: &Value, config: &XmlConfig) -> Value {
+    match Shape::of(schema) {
+        Shape::Object => {
+            let properties = object_properties(schema);
+            if properties.is_empty() {
+                return convention_value(element, config);
+            }
+            let mut out = Map::new();
+            for (name, prop) in properties {
+                let hints = Hints::of(prop);
+                let xml_name = hints.name.unwrap_or(name);
+                if hints.attribute {
+                    if let Some(value) = element.attribute(xml_name) {
+                        out.insert(name.clone(), scalar_value(value, prop));
+                    }
+                } else if Shape::of(prop) == Shape::Array {
+                    let items = items_schema(prop);
+                    let item_name = Hints::of(items).name;
+                    let values: Vec<Value> = if hints.wrapped {
+                        element
+                            .child(xml_name)
+                            .into_iter()
+                            .flat_map(|wrapper| &wrapper.children)
+                            .filter(|c| item_name.is_none_or(|n| c.name == n))
+                            .map(|c| schema_value(c, items, config))
+                            .collect()
+                    } else {
+                        let item_name = item_name.unwrap_or(name);
+                        element
+                            .children
+                            .iter()
+                            .filter(|c| c.name == item_name)
+                            .map(|c| schema_value(c, items, config))
+                            .collect()
+                    };
+                    if !values.is_empty() {
+                        out.insert(name.clone(), Value::Array(values));
+                    }
+                } else if let Some(child) = element.child(xml_name) {
+                    out.insert(name.clone(), schema_value(child, prop, config));
+                }
+            }
+            Value::Object(out)
+        }
+        Shape::Array => {
+            let items = items_schema(schema);
+            let item_name = Hints::of(items).name;
+            Value::Array(
+                element
+                    .children
+                    .iter()
+                    .filter(|c| item_name.is_none_or(|n| c.name == n))
+                    .map(|c| schema_value(c, items, config))
+                    .collect(),
+            )
+        }
+        Shape::Scalar => scalar_value(&element.text, schema),
+        Shape::Unknown => convention_value(element, config),
+    }
+}
+
+/// Coerce element/attribute text to the first declared scalar type it parses as.
+fn scalar_value(text: &str, schema: &Value) -> Value {
+    let trimmed = text.trim();
+    for ty in schema_types(schema) {
+        let parsed = match ty {
+            "integer" => trimmed
+                .parse::<i64>()
+                .ok()
+                .map(Value::from)
+                .or_else(|| trimmed.parse::<u64>().ok().map(Value::from)),
+            "number" => trimmed
+                .parse::<f64>()
+                .ok()
+                .and_then(serde_json::Number::from_f64)
+                .map(Value::Number),
+            "boolean" => match trimmed {
+                "true" | "1" => Some(Value::Bool(true)),
+                "false" | "0" => Some(Value::Bool(false)),
+                _ => None,
+            },
+            "null" if trimmed.is_empty() => Some(Value::Null),
+            _ => None,
+        };
+        if let Some(value) = parsed {
+            return value;
+        }
+    }
+    Value::String(text.to_string())
+}
+
+// ============================================================================
+// JSON → XML
+// ============================================================================
+
+fn write_element(
+    out: &mut String,
+    name: &str,
+    value: &Value,
+    schema: &Value,
+    config: &XmlConfig,
+) -> Result<(), String> {
+    let tag = open_tag(out, name, &Hints::of(schema))?;
+    match value {
+        Value::Object(map) => {
+            let properties = object_properties(schema);
+            let mut text: Option<&Value> = None;
+            let mut children: Vec<(&str, &Value, &Value)> = Vec::new();
+            for (key, value) in map {
+                if value.is_null() {
+                    continue;
+                }
+                let prop = properties.iter().find(|(n, _)| *n == key).map(|(_, s)| *s);
+                match prop {
+                    Some(prop) if Hints::of(prop).attribute => {
+                        let hints = Hints::of(prop);
+                        let attr_name = qualified(hints.name.unwrap_or(key), hints.prefix);
+                        write_attribute(out, &attr_name, value)?;
+                    }
+                    Some(prop) => children.push((key, value, prop)),
+                    None => {
+                        if let Some(attr_name) = key
+                            .strip_prefix(config.attribute_prefix.as_str())
+                            .filter(|_| !config.attribute_prefix.is_empty())
+                        {
+                            write_attribute(out, attr_name, value)?;
+                        } else if *key == config.text_key {
+                            text = Some(value);
+                        } else {
+                            children.push((key, value, &EMPTY_SCHEMA));
+                        }
+                    }
+                }
+            }
+            if children.is_empty() && text.is_none() {
+                out.push_str("/>");
+                return Ok(());
+            }
+            out.push('>');
+            if let Some(text) = text {
+                out.push_str(&escape(scalar_text(text)?.as_str()));
+            }
+            for (key, value, prop) in children {
+                write_member(out, key, value, prop, config)?;
+            }
+        }
+        Value::Array(items) => {
+            let item_schema = items_schema(schema);
+            let item_name = Hints::of(item_schema).name.unwrap_or("item");
+            out.push('>');
+            for item in items.iter().filter(|i| !i.is_null()) {
+                write_element(out, item_name, item, item_schema, config)?;
+            }
+        }
+        scalar => {
+            let text = scalar_text(scalar)?;
+            if text.is_empty() {
+                out.push_str("/>");
+                return Ok(());
+            }
+            out.push('>');
+            out.push_str(&escape(text.as_str()));
+        }
+    }
+    out.push_str("</");
+    out.push_str(&tag);
+    out.push('>');
+    Ok(())
+}
+
+/// Write an object member: arrays become repeated (optionally wrapped) elements.
+fn write_member(
+    out: &mut String,
+    key: &str,
+    value: &Value,
+    schema: &Value,
+    config: &XmlConfig,
+) -> Result<(), String> {
+    let hints = Hints::of(schema);
+    let Value::Array(items) = value else {
+        return write_element(out, hints.name.unwrap_or(key), value, schema, config);
+    };
+
+    let item_schema = items_schema(schema);
+    let item_name = Hints::of(item_schema).name.unwrap_or(key);
+    let wrapper = if hints.wrapped {
+        let tag = open_tag(out, hints.name.unwrap_or(key), &hints)?;
+        out.push('>');
+        Some(tag)
+    } else {
+        None
+    };
+    for item in items.iter().filter(|i| !i.is_null()) {
+        write_element(out, item_name, item, item_schema, config)?;
+    }
+    if let Some(tag) = wrapper {
+        out.push_str("</");
+        out.push_str(&tag);
+        out.push('>');
+    }
+    Ok(())
+}
+
+/// Write `<name` plus namespace declarations; returns the qualified tag name.
+fn open_tag(out: &mut String, name: &str, hints: &Hints<'_>) -> Result<String, String> {
+    let tag = qualified(name, hints.prefix);
+    check_name(&tag)?;
+    out.push('<');
+    out.push_str(&tag);
+    if let Some(namespace) = hints.namespace {
+        let attr = hints
+            .prefix
+            .map_or_else(|| "xmlns".to_string(), |p| format!("xmlns:{p}"));
+        write_attribute(out, &attr, &Value::String(namespace.to_string()))?;
+    }
+    Ok(tag)
+}
+
+fn write_attribute(out: &mut String, name: &str, value: &Value) -> Result<(), String> {
+    check_name(name)?;
+    let text = scalar_text(value).map_err(|e| format!("attribute '{name}': {e}"))?;
+    out.push(' ');
+    out.push_str(name);
+    out.push_str("=\"");
+    out.push_str(&escape(text.as_str()));
+    out.push('"');
+    Ok(())
+}
+
+fn qualified(name: &str, prefix: Option<&str>) -> String {
+    match prefix {
+        Some(prefix) if !prefix.is_empty() => format!("{prefix}:{name}"),
+        _ => name.to_string(),
+    }
+}
+
+fn check_name(name: &str) -> Result<(), String> {
+    let mut chars = name.chars();
+    let valid = chars.next().is_some_and(|c| c.is_alphabetic() || c == '_')
+        && chars.all(|c| c.is_alphanumeric() || matches!(c, '_' | '-' | '.' | ':'));
+    if valid {
+        Ok(())
+    } else {
+        Err(format!("'{name}' is not a valid XML name"))
+    }
+}
+
+fn scalar_text(value: &Value) -> Result<String, String> {
+    match value {
+        Value::Null => Ok(String::new()),
+        Value::String(s) => Ok(s.clone()),
+        Value::Number(n) => Ok(n.to_string()),
+        Value::Bool(b) => Ok(b.to_string()),
+        Value::Array(_) | Value::Object(_) => {
+            Err("structured values cannot be written as XML text".to_string())
+        }
+    }
+}
+
+// ============================================================================
+// Schema helpers
+// ============================================================================
+
+/// `OpenAPI` `xml` object of a schema.
+#[derive(Debug, Default)]
+struct Hints<'a> {
+    name: Option<&'a str>,
+    namespace: Option<&'a str>,
+    prefix: Option<&'a str>,
+    attribute: bool,
+    wrapped: bool,
+}
+
+impl<'a> Hints<'a> {
+    fn of(schema: &'a Value) -> Self {
+        let Some(xml) = schema.get("xml") else {
+            return Self::default();
+        };
+        let text = |key: &str| xml.get(key).and_then(Value::as_str);
+        let flag = |key: &str| xml.get(key).and_then(Value::as_bool) == Some(true);
+        Self {
+            name: text("name"),
+            namespace: text("namespace"),
+            prefix: text("prefix"),
+            attribute: flag("attribute"),
+            wrapped: flag("wrapped"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Shape {
+    Object,
+    Array,
+    Scalar,
+    Unknown,
+}
+
+impl Shape {
+    fn of(schema: &Value) -> Self {
+        let types = schema_types(schema);
+        if types.contains(&"object") || !object_properties(schema).is_empty() {
+            Self::Object
+        } else if types.contains(&"array") || schema.get("items").is_some() {
+            Self::Array
+        } else if types
+            .iter()
+            .any(|t| matches!(*t, "string" | "integer" | "number" | "boolean"))
+        {
+            Self::Scalar
+        } else {
+            Self::Unknown
+        }
+    }
+}
+
+fn schema_types(schema: &Value) -> Vec<&str> {
+    match schema.get("type") {
+        Some(Value::String(t)) => vec![t.as_str()],
+        Some(Value::Array(types)) => types.iter().filter_map(Value::as_str).collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// `properties` of a schema, including those of its `allOf` branches.
+fn object_properties(schema: &Value) -> Vec<(&String, &Value)> {
+    let mut out: Vec<(&String, &Value)> = schema
+        .get("properties")
+        .and_then(Value::as_object)
+        .into_iter()
+        .flatten()
+        .collect();
+    for branch in schema
+        .get("allOf")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+    {
+        for (name, prop) in object_properties(branch) {
+            if !out.iter().any(|(n, _)| *n == name) {
+                out.push((name, prop));
+            }
+        }
+    }
+    out
+}
+
+fn items_schema(schema: &Value) -> &Value {
+    schema.get("items").unwrap_or(&EMPTY_SCHEMA)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    const PET_SCHEMA: &str = r#"{
+        "type": "object",
+        "xml": { "name": "Pet" },
+        "properties": {
+            "id": { "type": "integer", "xml": { "attribute": true } },
+            "name": { "type": "string" },
+            "vaccinated": { "type": "boolean" },
+            "tags": {
+                "type": "array",
+                "xml": { "wrapped": true },
+                "items": { "type": "string", "xml": { "name": "tag" } }
+            },
+            "photoUrls": {
+                "type": "array",
+                "items": { "type": "string", "xml": { "name": "photoUrl" } }
+            }
+        }
+    }"#;
+
+    fn pet_schema() -> Value {
+        serde_json::from_str(PET_SCHEMA).unwrap()
+    }
+
+    #[test]
+    fn converts_xml_with_attribute_and_text_conventions() {
+        let xml = r#"<?xml version="1.0"?>
+            <ns:order xmlns:ns="urn:orders" id="7">
+              <item sku="a">Apple &amp; pear</item>
+              <item sku="b"/>
+              <note><![CDATA[<fragile>]]></note>
+              <total>12.5</total>
+            </ns:order>"#;
+
+        assert_eq!(
+            xml_to_json(xml, &XmlConfig::default()).unwrap(),
+            json!({
+                "order": {
+                    "@id": "7",
+                    "item": [
+                        { "@sku": "a", "#text": "Apple & pear" },
+                        { "@sku": "b" }
+                    ],
+                    "note": "<fragile>",
+                    "total": "12.5"
+                }
+            })
+        );
+
+        let config = XmlConfig {
+            attribute_prefix: "_".to_string(),
+            text_key: "value".to_string(),
+        };
+        assert_eq!(
+            xml_to_json(r#"<a b="1">x</a>"#, &config).unwrap(),
+            json!({ "a": { "_b": "1", "value": "x" } })
+        );
+    }
+
+    #[test]
+    fn converts_xml_into_schema_shape() {
+        let xml = r#"<Pet id="42">
+              <name>Rex</name>
+              <vaccinated>true</vaccinated>
+              <tags><tag>good</tag><tag>dog</tag></tags>
+              <photoUrl>a.png</photoUrl>
+              <photoUrl>b.png</photoUrl>
+              <extra>ignored</extra>
+            </Pet>"#;
+
+        assert_eq!(
+            xml_to_json_with_schema(xml, &pet_schema(), &XmlConfig::default()).unwrap(),
+            json!({
+                "id": 42,
+                "name": "Rex",
+                "vaccinated": true,
+                "tags": ["good", "dog"],
+                "photoUrls": ["a.png", "b.png"]
+            })
+        );
+    }
+
+    #[test]
+    fn serializes_json_with_xml_hints_and_conventions() {
+        let body = XmlBody {
+            root: "ignored".to_string(),
+            schema: pet_schema(),
+            ..XmlBody::default()
+        };
+        let value = json!({
+            "id": 42,
+            "name": "R&D <dog>",
+            "tags": ["good"],
+            "photoUrls": ["a.png", "b.png"],
+            "owner": { "@ref": "u1", "#text": "Ann" },
+            "notes": null
+        });
+        assert_eq!(
+            json_to_xml(&value, &body).unwrap(),
+            format!(
+                "{XML_DECLARATION}<Pet id=\"42\"><name>R&amp;D &lt;dog&gt;</name>\
+                 <owner ref=\"u1\">Ann</owner><photoUrl>a.png</photoUrl><photoUrl>b.png</photoUrl>\
+                 <tags><tag>good</tag></tags></Pet>"
+            )
+        );
+
+        // Round-trips through the schema-guided reader.
+        let xml = json_to_xml(&value, &body).unwrap();
+        let back = xml_to_json_with_schema(&xml, &pet_schema(), &XmlConfig::default()).unwrap();
+        assert_eq!(back["photoUrls"], json!(["a.png", "b.png"]));
+        assert_eq!(back["id"], json!(42));
+
+        let namespaced = XmlBody {
+            root: "order".to_string(),
+            schema: json!({ "xml": { "namespace": "urn:o", "prefix": "o" } }),
+            ..XmlBody::default()
+        };
+        assert_eq!(
+            json_to_xml(&json!({ "qty": 2 }), &namespaced).unwrap(),
+            format!("{XML_DECLARATION}<o:order xmlns:o=\"urn:o\"><qty>2</qty></o:order>")
+        );
+
+        let err = json_to_xml(&json!({ "bad name": 1 }), &XmlBody::default()).unwrap_err();
+        assert_eq!(err, "'bad name' is not a valid XML name");
+    }
+
+    #[test]
+    fn renders_templates_with_escaped_arguments() {
+        let args: HashMap<String, Value> =
+            serde_json::from_value(json!({ "name": "a<b", "qty": 3 })).unwrap();
+        assert_eq!(
+            render_template(
+                "<order><n>{{ name }}</n><q>{{qty}}</q><x>{{missing}}</x></order>",
+                &args
+            )
+            .unwrap(),
+            "<order><n>a&lt;b</n><q>3</q><x></x></order>"
+        );
+        assert_eq!(
+            template_placeholders("{{a}} and {{ b }}").unwrap(),
+            vec!["a", "b"]
+        );
+        assert!(render_template("<a>{{name</a>", &args).is_err());
+    }
+
+    #[test]
+    fn rejects_malformed_xml() {
+        let config = XmlConfig::default();
+        assert!(xml_to_json("<a><b></a>", &config).is_err());
+        assert!(xml_to_json("<a/><b/>", &config).is_err());
+        assert!(xml_to_json("not xml", &config).is_err());
+    }
+
+    #[test]
+    fn rejects_deeply_nested_xml() {
+        let config = XmlConfig::default();
+        let depth = 100_000;
+        let doc = format!("{}{}", "<a>".repeat(depth), "</a>".repeat(depth));
+        let err = xml_to_json(&doc, &config).unwrap_err();
+        assert!(err.contains("nested deeper than"), "{err}");
+
+        let ok = format!("{}{}", "<a>".repeat(MAX_DEPTH), "</a>".repeat(MAX_DEPTH));
+        assert!(xml_to_json(&ok, &config).is_ok());
+        assert!(xml_to_json_with_schema(&ok, &json!({}), &config).is_ok());
+    }
+}
@@ -1,7 +1,8 @@
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use unrelated_http_tools::config::{
-    AuthConfig, EndpointDefaults, HttpToolConfig, ResponseTransform, ResponseTransformChainConfig,
+    AsyncOperationConfig, AuthConfig, EndpointDefaults, HttpToolConfig, PaginationConfig,
+    ResponseTransform, ResponseTransformChainConfig, XmlConfig,
 };
 
 /// Configuration for an OpenAPI-based tool source.
@@ -27,6 +28,10 @@ pub struct ApiServerConfig {
     #[serde(default)]
     pub auth: Option<AuthConfig>,
 
+    /// Credentials for the spec's `securitySchemes`, applied per operation.
+    #[serde(default)]
+    pub security: SecurityConfig,
+
     /// Auto-discovery configuration.
     #[serde(default)]
     pub auto_discover: AutoDiscoverConfig,
@@ -48,6 +53,10 @@ pub struct ApiServerConfig {
     #[serde(default)]
     pub response_overrides: Vec<ResponseOverrideConfig>,
 
+    /// XML ↔ JSON conventions for XML content the spec's schemas do not describe.
+    #[serde(default)]
+    pub xml: XmlConfig,
+
     /// Optional `OpenAPI` tool overrides (manual HTTP tool DSL).
     #[serde(default)]
     pub overrides: OpenApiOverridesConfig,
@@ -66,6 +75,60 @@ pub enum HashPolicy {
     Ignore,
 }
 
+/// Per-operation authentication derived from `components.securitySchemes`.
+///
+/// Each operation's `security` requirements (or the spec-level default) are matched against
+/// `credentials` by scheme name. Unlike `auth`, which is sent with every request, a credential is
+/// only sent to operations that require its scheme.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SecurityConfig {
+    /// Credentials keyed by security scheme name.
+    #[serde(default)]
+    pub credentials: HashMap<String, SecurityCredential>,
+
+    /// What to do with operations whose requirements cannot be satisfied.
+    #[serde(default)]
+    pub unsatisfied: UnsatisfiedSecurityPolicy,
+}
+
+/// A credential for one security scheme.
+///
+/// String values support `${VAR}` expansion (adapter) and `${secret:name}` references (gateway).
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum SecurityCredential {
+    /// Value for an `apiKey` scheme (sent in the scheme's header, query param, or cookie).
+    ApiKey { value: String },
+    /// Token for an `http` bearer scheme (also accepted for `oauth2`/`openIdConnect` schemes).
+    Bearer { token: String },
+    /// Username/password for an `http` basic scheme.
+    Basic { username: String, password: String },
+    /// Client credentials for an `oauth2` scheme with a `clientCredentials` flow.
+    #[serde(rename_all = "camelCase")]
+    Oauth2ClientCredentials {
+        client_id: String,
+        client_secret: String,
+        /// Scopes to request (defaults to the scopes listed by the operation's requirement).
+        #[serde(default)]
+        scopes: Vec<String>,
+        /// Override the flow's `tokenUrl`.
+        #[serde(default)]
+        token_url: Option<String>,
+    },
+}
+
+/// Policy for operations whose security requirements are not satisfied by configured credentials.
+#[derive(Debug, Clone, Copy, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum UnsatisfiedSecurityPolicy {
+    /// Expose the tool anyway and log a warning.
+    #[default]
+    Warn,
+    /// Do not expose the tool.
+    Skip,
+}
+
 /// Auto-discovery configuration.
 #[derive(Debug, Clone, Deserialize, Serialize)]
 #[serde(untagged)]
@@ -172,6 +235,14 @@ pub struct OpenApiOverrideToolConfig {
     /// Optional tool description override.
     #[serde(default)]
     pub description: Option<String>,
+
+    /// Follow paginated responses (takes precedence over `request.pagination`).
+    #[serde(default)]
+    pub pagination: Option<PaginationConfig>,
+
+    /// Poll `202 Accepted` responses (takes precedence over `request.asyncOperation`).
+    #[serde(default)]
+    pub async_operation: Option<AsyncOperationConfig>,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize, Default)]
@@ -10,3 +10,6 @@ pub mod config;
 pub mod error;
 pub mod resolver;
 pub mod runtime;
+pub mod schema;
+pub mod security;
+pub mod spec;
@@ -95,13 +95,20 @@ impl<'a> OpenApiResolver<'a> {
     pub fn new(root_doc: DocId, spec: &OpenAPI, client: &'a Client) -> Result<Self> {
         let root_value =
             serde_json::to_value(spec).map_err(|e| OpenApiToolsError::OpenApi(e.to_string()))?;
+        Ok(Self::with_document(root_doc, root_value, client))
+    }
+
+    /// Create a new resolver whose root document is the given raw JSON value (e.g. a normalized
+    /// spec from [`crate::spec::load`]).
+    #[must_use]
+    pub fn with_document(root_doc: DocId, document: Value, client: &'a Client) -> Self {
         let mut docs = HashMap::new();
-        docs.insert(root_doc.clone(), Arc::new(root_value));
-        Ok(Self {
+        docs.insert(root_doc.clone(), Arc::new(document));
+        Self {
             root_doc,
             client,
             docs: RwLock::new(docs),
-        })
+        }
     }
 
     #[must_use]
@@ -179,6 +186,25 @@ impl<'a> OpenApiResolver<'a> {
         self.resolve_reference_or(current_doc, item).await
     }
 
+    /// Resolve a raw `$ref` to the referenced JSON value (without deserializing it).
+    ///
+    /// Returns the document containing the value (nested refs resolve relative to it) and a
+    /// canonical key identifying the target (for cycle detection by callers).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the reference cannot be resolved, or if the referenced document
+    /// cannot be loaded/parsed.
+    pub async fn resolve_ref(
+        &self,
+        current_doc: &DocId,
+        reference: &str,
+    ) -> Result<(DocId, Value, String)> {
+        let key = Self::canonical_ref_key(current_doc, reference)?;
+        let (doc, value) = self.resolve_ref_value(current_doc, reference).await?;
+        Ok((doc, value, key))
+    }
+
     async fn resolve_reference_or<T>(
         &self,
         current_doc: &DocId,
@@ -3,9 +3,14 @@
 //! This module implements an `OpenAPI` → MCP tool source by converting `OpenAPI` operations into
 //! MCP tools and executing outbound HTTP requests for `tools/call`.
 
-use crate::config::{ApiServerConfig, HashPolicy, OpenApiOverrideToolConfig, ParamConfig};
+use crate::config::{
+    ApiServerConfig, HashPolicy, OpenApiOverrideToolConfig, ParamConfig, UnsatisfiedSecurityPolicy,
+};
 use crate::error::{OpenApiToolsError, Result};
 use crate::resolver::{DocId, OpenApiResolver};
+use crate::schema::{SchemaConverter, SchemaDirection};
+use crate::security::{self, AppliedCredential, ClientCredentialsGrant, SecurityResolution};
+use crate::spec::{self, LoadedSpec, SpecVersion};
 use base64::Engine as _;
 use mime::Mime;
 use openapiv3::{
@@ -14,21 +19,33 @@ use openapiv3::{
 };
 use parking_lot::RwLock;
 use regex::Regex;
+use reqwest::header::HeaderMap;
 use reqwest::{Client, Method};
 use rmcp::model::{CallToolResult, Content, JsonObject, Tool};
 use serde_json::{Value, json};
 use sha2::{Digest, Sha256};
 use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
+use unrelated_http_tools::async_operation::{OperationState, Poller, locate_url};
+use unrelated_http_tools::body::{
+    binary_value_schema, encode_body, inline_resource_refs, is_binary_schema,
+};
 use unrelated_http_tools::config::{
-    ArrayStyle, AuthConfig, HttpParamLocation, HttpResponseMode, HttpToolConfig, QueryStyleConfig,
-    ResponseTransform, ResponseTransformChainConfig,
+    ArrayStyle, AsyncOperationConfig, AuthConfig, BodyEncoding, HttpParamLocation,
+    HttpResponseMode, HttpToolConfig, PaginationConfig, QueryStyleConfig, ResponseTransform,
+    ResponseTransformChainConfig, RetryConfig, StreamConfig, XmlConfig,
 };
+use unrelated_http_tools::context::CallContext;
+use unrelated_http_tools::pagination::{NextPage, PaginationSummary, Paginator};
 use unrelated_http_tools::response_shaping::{
     CompiledResponsePipeline, apply_chain, compile_pipeline_from_transforms,
 };
 use unrelated_http_tools::safety::{OutboundHttpSafety, RedirectPolicy, sanitize_reqwest_error};
+use unrelated_http_tools::streaming::{StreamError, read_stream, stream_format};
+use unrelated_http_tools::xml::{
+    XmlBody, is_xml_media_type, template_placeholders, xml_to_json, xml_to_json_with_schema,
+};
 use url::Url;
 
 /// `OpenAPI` tool source that exposes HTTP API endpoints as MCP tools.
@@ -38,8 +55,10 @@ pub struct OpenApiToolSource {
     name: String,
     /// Configuration
     config: ApiServerConfig,
-    /// Parsed `OpenAPI` spec
+    /// Parsed `OpenAPI` spec (normalized to the 3.0 model)
     spec: Arc<RwLock<Option<OpenAPI>>>,
+    /// Version detected when loading the spec
+    spec_version: Arc<RwLock<Option<SpecVersion>>>,
     /// Generated tools
     tools: Arc<RwLock<Vec<GeneratedTool>>>,
     /// HTTP client
@@ -56,8 +75,23 @@ pub struct OpenApiToolSource {
     probe_timeout: Duration,
     /// Outbound HTTP safety policy (SSRF protections, limits, redirect policy).
     safety: OutboundHttpSafety,
+    /// Cached `OAuth2` client credentials tokens
+    oauth_tokens: Arc<RwLock<HashMap<ClientCredentialsGrant, CachedToken>>>,
+}
+
+/// An `OAuth2` access token and when it stops being reused.
+#[derive(Debug, Clone)]
+struct CachedToken {
+    access_token: String,
+    refresh_at: Instant,
 }
 
+/// Tokens are refreshed this long before the server-reported expiry.
+const TOKEN_EXPIRY_MARGIN: Duration = Duration::from_secs(30);
+
+/// Maximum number of unsatisfied operations listed in the discovery warning.
+const MAX_LISTED_UNSATISFIED: usize = 10;
+
 /// A tool generated from an `OpenAPI` operation.
 #[derive(Debug, Clone)]
 struct GeneratedTool {
@@ -77,12 +111,30 @@ struct GeneratedTool {
     parameters: Vec<ToolParameter>,
     /// Input schema for MCP
     input_schema: Value,
+    /// Request body encoding
+    body_encoding: BodyEncoding,
+    /// Request body media type from the spec (`None` uses the encoding's default)
+    body_content_type: Option<String>,
+    /// Serialization of XML request bodies
+    xml_body: Option<Arc<XmlBody>>,
+    /// Response body schema with `xml` hints, for reading XML responses
+    xml_response_schema: Option<Arc<Value>>,
     /// Response mode (json/text) for this tool
     response_mode: HttpResponseMode,
     /// Optional output schema for MCP `Tool.output_schema` (must be a JSON Schema object).
     output_schema: Option<Arc<JsonObject>>,
     /// Compiled response shaping pipeline (applied to the response body value).
     response_pipeline: Arc<CompiledResponsePipeline>,
+    /// Pagination following (manual overrides only)
+    pagination: Option<Arc<PaginationConfig>>,
+    /// Polling of `202 Accepted` responses (manual overrides only)
+    async_operation: Option<Arc<AsyncOperationConfig>>,
+    /// Retry policy replacing `defaults.retry` (manual overrides only)
+    retry: Option<Arc<RetryConfig>>,
+    /// Aggregation of streaming responses (manual overrides only; default: array)
+    stream: Option<Arc<StreamConfig>>,
+    /// Credentials required by the operation's security requirements
+    security: Vec<AppliedCredential>,
 }
 
 #[derive(Debug, Clone)]
@@ -147,6 +199,24 @@ struct ToolParameter {
     schema: Value,
     /// Query serialization settings (style/explode), for query parameters only
     query: Option<QuerySerialization>,
+    /// Body parameter carrying binary content (multipart file part or raw body)
+    binary: bool,
+}
+
+/// Request body media type selected for an operation.
+#[derive(Debug, Clone)]
+struct BodyMedia {
+    encoding: BodyEncoding,
+    content_type: String,
+    /// Set for XML bodies.
+    xml: Option<XmlBody>,
+}
+
+/// Response body schema derived from the spec.
+struct DerivedBodySchema {
+    schema: Value,
+    /// The schema with `xml` hints, when the response is XML.
+    xml_schema: Option<Value>,
 }
 
 #[derive(Debug, Clone)]
@@ -170,11 +240,49 @@ struct RequestParts {
     headers: Vec<(String, String)>,
     body_fields: HashMap<String, Value>,
     body_payload: Option<Value>,
+    binary_fields: HashSet<String>,
 }
 
 enum ToolResponse {
     Value(Value),
-    Image { bytes: Vec<u8>, mime_type: String },
+    Image {
+        bytes: Vec<u8>,
+        mime_type: String,
+    },
+    /// Items of all pages of a paginated call.
+    Paged {
+        body: Value,
+        summary: PaginationSummary,
+    },
+}
+
+/// One successful upstream response with its request URL, status and headers.
+struct Page {
+    response: ToolResponse,
+    url: Url,
+    status: reqwest::StatusCode,
+    headers: HeaderMap,
+}
+
+impl Page {
+    /// The `Link` header(s), comma-joined.
+    fn link(&self) -> Option<String> {
+        let link = self
+            .headers
+            .get_all(reqwest::header::LINK)
+            .iter()
+            .filter_map(|v| v.to_str().ok())
+            .collect::<Vec<_>>()
+            .join(", ");
+        (!link.is_empty()).then_some(link)
+    }
+
+    fn body(&self) -> &Value {
+        match &self.response {
+            ToolResponse::Value(body) => body,
+            _ => &Value::Null,
+        }
+    }
 }
 
 /// Parameter location.
@@ -234,6 +342,7 @@ impl OpenApiToolSource {
             name,
             config,
             spec: Arc::new(RwLock::new(None)),
+            spec_version: Arc::new(RwLock::new(None)),
             tools: Arc::new(RwLock::new(Vec::new())),
             client,
             base_url: Arc::new(RwLock::new(None)),
@@ -242,6 +351,7 @@ impl OpenApiToolSource {
             probe_enabled,
             probe_timeout,
             safety,
+            oauth_tokens: Arc::new(RwLock::new(HashMap::new())),
         }
     }
 
@@ -329,8 +439,8 @@ impl OpenApiToolSource {
         }
     }
 
-    /// Load and parse the `OpenAPI` spec.
-    async fn load_spec(&self) -> Result<OpenAPI> {
+    /// Load and parse the spec (Swagger 2.0, `OpenAPI` 3.0 or 3.1).
+    async fn load_spec(&self) -> Result<LoadedSpec> {
         let spec_content = if self.config.spec.starts_with("http://")
             || self.config.spec.starts_with("https://")
         {
@@ -395,20 +505,36 @@ impl OpenApiToolSource {
         }
 
         // Parse spec (JSON is a valid subset of YAML, so serde_yaml alone is enough)
-        let spec: OpenAPI = serde_yaml::from_str(&spec_content).map_err(|e| {
+        let raw: serde_yaml::Value = serde_yaml::from_str(&spec_content).map_err(|e| {
             OpenApiToolsError::OpenApiSpecParse {
                 location: self.config.spec.clone(),
                 source: e,
             }
         })?;
+        let document = serde_json::to_value(raw).map_err(|e| {
+            OpenApiToolsError::OpenApi(format!(
+                "Spec '{}' cannot be represented as JSON: {e}",
+                self.config.spec
+            ))
+        })?;
 
-        Ok(spec)
+        spec::load(&self.config.spec, document)
     }
 
-    /// Discover tools from the `OpenAPI` spec.
+    /// Discover tools from an already-parsed 3.0 model (resolver root is the re-serialized model).
+    #[cfg(test)]
     async fn discover_tools(&self, spec: &OpenAPI) -> Result<Vec<GeneratedTool>> {
         let root_doc = DocId::parse(&self.config.spec)?;
         let resolver = OpenApiResolver::new(root_doc, spec, &self.client)?;
+        self.discover_tools_with(&resolver, spec).await
+    }
+
+    /// Discover tools from the `OpenAPI` model, resolving refs (and schemas) through `resolver`.
+    async fn discover_tools_with(
+        &self,
+        resolver: &OpenApiResolver<'_>,
+        spec: &OpenAPI,
+    ) -> Result<Vec<GeneratedTool>> {
         let mut tools = Vec::new();
         let mut tool_names: HashSet<String> = HashSet::new();
         let mut ops: Vec<OperationInfo> = Vec::new();
@@ -416,12 +542,23 @@ impl OpenApiToolSource {
             vec![0; self.config.response_overrides.len()];
         let mut response_overrides: HashMap<OperationKey, ResolvedResponseOverride> =
             HashMap::new();
+        let mut op_security: HashMap<OperationKey, Vec<AppliedCredential>> = HashMap::new();
+        let mut unsatisfied: Vec<String> = Vec::new();
 
         // Get explicit endpoint configs
         let explicit_endpoints = &self.config.endpoints;
 
         self.validate_response_override_configs()?;
 
+        let unknown = security::unknown_credential_names(spec, &self.config.security.credentials);
+        if !unknown.is_empty() {
+            tracing::warn!(
+                "Security credentials in '{}' do not match any securitySchemes entry: {}",
+                self.name,
+                unknown.join(", ")
+            );
+        }
+
         for (path, path_item) in &spec.paths.paths {
             let (path_doc, path_item) = match resolver
                 .resolve_path_item(resolver.root_doc(), path_item)
@@ -485,6 +622,33 @@ impl OpenApiToolSource {
                     || (self.config.auto_discover.is_enabled()
                         && self.should_auto_discover(method, path, op));
 
+                let resolution = security::resolve_operation_security(
+                    spec,
+                    op,
+                    &self.config.security.credentials,
+                );
+                let security = match resolution {
+                    SecurityResolution::Anonymous => Vec::new(),
+                    SecurityResolution::Satisfied(credentials) => credentials,
+                    // Legacy `auth` is sent with every request and assumed to cover the operation.
+                    SecurityResolution::Unsatisfied(_) if self.config.auth.is_some() => Vec::new(),
+                    SecurityResolution::Unsatisfied(reasons) => {
+                        if should_generate {
+                            unsatisfied.push(format!(
+                                "{} {} ({})",
+                                method.to_uppercase(),
+                                path,
+                                reasons.join("; ")
+                            ));
+                            if self.config.security.unsatisfied == UnsatisfiedSecurityPolicy::Skip {
+                                continue;
+                            }
+                        }
+                        Vec::new()
+                    }
+                };
+                op_security.insert(op_key, security.clone());
+
                 if !should_generate {
                     continue;
                 }
@@ -498,7 +662,13 @@ impl OpenApiToolSource {
                 };
 
                 match self
-                    .generate_tool(&resolver, input, &mut tool_names, &response_overrides)
+                    .generate_tool(
+                        resolver,
+                        input,
+                        &mut tool_names,
+                        &response_overrides,
+                        security,
+                    )
                     .await
                 {
                     Ok(tool) => tools.push(tool),
@@ -515,13 +685,43 @@ impl OpenApiToolSource {
             }
         }
 
-        self.apply_overrides(&ops, &mut tools, &response_overrides)?;
+        self.apply_overrides(&ops, &mut tools, &response_overrides, &op_security)?;
 
         self.warn_unmatched_response_overrides(&response_override_match_counts);
+        self.warn_unsatisfied_security(&unsatisfied);
 
         Ok(tools)
     }
 
+    fn warn_unsatisfied_security(&self, unsatisfied: &[String]) {
+        if unsatisfied.is_empty() {
+            return;
+        }
+        let action = match self.config.security.unsatisfied {
+            UnsatisfiedSecurityPolicy::Warn => "exposed without credentials",
+            UnsatisfiedSecurityPolicy::Skip => "skipped",
+        };
+        let mut listed = unsatisfied
+            .iter()
+            .take(MAX_LISTED_UNSATISFIED)
+            .cloned()
+            .collect::<Vec<_>>()
+            .join(", ");
+        if unsatisfied.len() > MAX_LISTED_UNSATISFIED {
+            listed.push_str(&format!(
+                ", ... and {} more",
+                unsatisfied.len() - MAX_LISTED_UNSATISFIED
+            ));
+        }
+        tracing::warn!(
+            "{} operation(s) in '{}' have unsatisfied security requirements ({}): {}",
+            unsatisfied.len(),
+            self.name,
+            action,
+            listed
+        );
+    }
+
     fn validate_response_override_configs(&self) -> Result<()> {
         for (idx, ovr) in self.config.response_overrides.iter().enumerate() {
             if ovr.matcher.operation_id.is_none()
@@ -613,6 +813,7 @@ impl OpenApiToolSource {
         ops: &[OperationInfo],
         tools: &mut Vec<GeneratedTool>,
         response_overrides: &HashMap<OperationKey, ResolvedResponseOverride>,
+        op_security: &HashMap<OperationKey, Vec<AppliedCredential>>,
     ) -> Result<()> {
         for (override_tool_name, override_cfg) in &self.config.overrides.tools {
             let Some(matched) = match_override(ops, &override_cfg.matcher, &self.name)? else {
@@ -645,14 +846,17 @@ impl OpenApiToolSource {
             let op_key = OperationKey::from_info(&matched);
             let response_override = response_overrides.get(&op_key);
 
-            let generated = manual_override_to_tool(
+            let mut generated = manual_override_to_tool(
                 &self.name,
                 override_tool_name,
                 override_cfg,
                 matched.operation_id.clone(),
                 response_override,
                 &self.config.response_transforms,
+                &self.config.xml,
             )?;
+            // The override still calls the matched operation, so it keeps its credentials.
+            generated.security = op_security.get(&op_key).cloned().unwrap_or_default();
             tools.push(generated);
         }
 
@@ -692,7 +896,7 @@ impl OpenApiToolSource {
         resolver: &OpenApiResolver<'_>,
         input: ToolGenerationInput<'_>,
         param_configs: Option<&HashMap<String, ParamConfig>>,
-    ) -> Result<Vec<ToolParameter>> {
+    ) -> Result<(Vec<ToolParameter>, Option<BodyMedia>)> {
         let current_doc = input.current_doc;
         let path_item_params = input.path_item_params;
         let operation = input.operation;
@@ -730,23 +934,46 @@ impl OpenApiToolSource {
         }
 
         // Request body parameters (flatten object properties)
+        let mut body_media = None;
         if let Some(body_ref) = &operation.request_body {
             let (body_doc, body) = resolver.resolve_request_body(current_doc, body_ref).await?;
-            if let Some(schema_ref) = body
-                .content
-                .get("application/json")
-                .and_then(|c| c.schema.as_ref())
-            {
-                let body_params = self
-                    .extract_body_params(
-                        resolver,
-                        &body_doc,
-                        &body,
-                        schema_ref,
-                        param_configs,
-                        &param_names,
-                    )
-                    .await?;
+            if let Some((mut media, schema_ref)) = select_body_media(&body) {
+                // Converted from the raw documents (refs resolved), so this works for every spec
+                // version.
+                let schema = match schema_ref {
+                    Some(schema_ref) => {
+                        SchemaConverter::new(resolver, SchemaDirection::Input)
+                            .convert_schema_ref(&body_doc, schema_ref)
+                            .await?
+                    }
+                    None if media.encoding == BodyEncoding::Text => json!({ "type": "string" }),
+                    None => json!({}),
+                };
+                if media.encoding == BodyEncoding::Xml {
+                    let xml_schema = match schema_ref {
+                        Some(schema_ref) => {
+                            SchemaConverter::new(resolver, SchemaDirection::Input)
+                                .with_xml_hints()
+                                .convert_schema_ref(&body_doc, schema_ref)
+                                .await?
+                        }
+                        None => json!({}),
+                    };
+                    media.xml = Some(XmlBody {
+                        root: xml_root_name(schema_ref),
+                        schema: xml_schema,
+                        template: None,
+                        conventions: self.config.xml.clone(),
+                    });
+                }
+                let body_params = Self::extract_body_params(
+                    &body,
+                    schema,
+                    media.encoding,
+                    param_configs,
+                    &param_names,
+                );
+                body_media = Some(media);
 
                 // Check for collisions
                 for bp in &body_params {
@@ -765,7 +992,7 @@ impl OpenApiToolSource {
             }
         }
 
-        Ok(parameters)
+        Ok((parameters, body_media))
     }
 
     /// Generate a tool from an `OpenAPI` operation.
@@ -775,6 +1002,7 @@ impl OpenApiToolSource {
         input: ToolGenerationInput<'_>,
         tool_names: &mut HashSet<String>,
         response_overrides: &HashMap<OperationKey, ResolvedResponseOverride>,
+        security: Vec<AppliedCredential>,
     ) -> Result<GeneratedTool> {
         let current_doc = input.current_doc;
         let path = input.path;
@@ -797,7 +1025,7 @@ impl OpenApiToolSource {
         let description = Self::tool_description(explicit_config, operation, method, path);
 
         let param_configs = explicit_config.map(|c| &c.params);
-        let parameters = self
+        let (parameters, body_media) = self
             .collect_tool_parameters(resolver, input, param_configs)
             .await?;
 
@@ -833,12 +1061,18 @@ impl OpenApiToolSource {
             };
 
         // Determine body schema: responseOverrides.outputSchema wins, otherwise best-effort derive from spec.
+        let derived = self
+            .derive_body_schema(resolver, current_doc, operation)
+            .await?;
+        let xml_response_schema = derived
+            .as_ref()
+            .and_then(|d| d.xml_schema.clone())
+            .map(Arc::new);
         let body_schema =
             if let Some(schema) = response_override.and_then(|o| o.output_schema.as_ref()) {
                 Some(schema.clone())
             } else {
-                self.derive_body_schema(resolver, current_doc, operation)
-                    .await?
+                derived.map(|d| d.schema)
             };
 
         let output_schema = if let Some(mut body_schema) = body_schema {
@@ -851,7 +1085,7 @@ impl OpenApiToolSource {
                     "response schema transform warning"
                 );
             }
-            Some(wrap_body_output_schema(&body_schema)?)
+            Some(wrap_body_output_schema(&body_schema, false)?)
         } else {
             None
         };
@@ -867,9 +1101,23 @@ impl OpenApiToolSource {
             path: path.to_string(),
             parameters,
             input_schema,
+            body_encoding: body_media
+                .as_ref()
+                .map_or(BodyEncoding::Json, |m| m.encoding),
+            xml_body: body_media
+                .as_ref()
+                .and_then(|m| m.xml.clone())
+                .map(Arc::new),
+            body_content_type: body_media.map(|m| m.content_type),
+            xml_response_schema,
             response_mode: HttpResponseMode::Json,
             output_schema,
             response_pipeline,
+            pagination: None,
+            async_operation: None,
+            retry: None,
+            stream: None,
+            security,
         })
     }
 
@@ -878,7 +1126,7 @@ impl OpenApiToolSource {
         resolver: &OpenApiResolver<'_>,
         current_doc: &DocId,
         operation: &Operation,
-    ) -> Result<Option<Value>> {
+    ) -> Result<Option<DerivedBodySchema>> {
         // Prefer explicit 2xx codes (200..=299), otherwise fall back to 2XX range.
         let mut explicit_2xx: Vec<(u16, &ReferenceOr<Response>)> = Vec::new();
         let mut range_2xx: Option<&ReferenceOr<Response>> = None;
@@ -903,8 +1151,8 @@ impl OpenApiToolSource {
 
         let (resp_doc, resp) = resolver.resolve_response(current_doc, resp_ref).await?;
 
-        // Select a JSON-ish media type.
-        let mt = if let Some(mt) = resp.content.get("application/json") {
+        // Select a JSON-ish media type, otherwise an XML one (converted to JSON at call time).
+        let json_mt = if let Some(mt) = resp.content.get("application/json") {
             Some(mt)
         } else {
             resp.content.iter().find_map(|(k, v)| {
@@ -912,16 +1160,30 @@ impl OpenApiToolSource {
                 (lower.contains("json") || lower.ends_with("+json")).then_some(v)
             })
         };
-        let Some(mt) = mt else {
-            return Ok(None);
+        let (mt, xml) = match json_mt {
+            Some(mt) => (mt, false),
+            None => match resp.content.iter().find(|(k, _)| is_xml_media_type(k)) {
+                Some((_, mt)) => (mt, true),
+                None => return Ok(None),
+            },
         };
 
         let Some(schema_ref) = mt.schema.as_ref() else {
             return Ok(None);
         };
 
-        let body_schema = extract_schema_ref(resolver, &resp_doc, schema_ref).await?;
-        Ok(Some(body_schema))
+        let schema = extract_schema_ref(resolver, &resp_doc, schema_ref).await?;
+        let xml_schema = if xml {
+            Some(
+                SchemaConverter::new(resolver, SchemaDirection::Output)
+                    .with_xml_hints()
+                    .convert_schema_ref(&resp_doc, schema_ref)
+                    .await?,
+            )
+        } else {
+            None
+        };
+        Ok(Some(DerivedBodySchema { schema, xml_schema }))
     }
 
     /// Extract parameter info from `OpenAPI` parameter.
@@ -1018,50 +1280,53 @@ impl OpenApiToolSource {
             default,
             schema,
             query: query_ser,
+            binary: false,
         })
     }
 
     /// Extract body parameters from request body schema.
-    async fn extract_body_params(
-        &self,
-        resolver: &OpenApiResolver<'_>,
-        current_doc: &DocId,
+    fn extract_body_params(
         body: &RequestBody,
-        schema_ref: &ReferenceOr<Schema>,
+        schema: Value,
+        encoding: BodyEncoding,
         param_configs: Option<&HashMap<String, ParamConfig>>,
         existing_names: &HashSet<String>,
-    ) -> Result<Vec<ToolParameter>> {
+    ) -> Vec<ToolParameter> {
         let mut params = Vec::new();
 
-        // Resolve schema ref (internal components/schemas supported).
-        let schema = match schema_ref {
-            ReferenceOr::Item(s) => s.clone(),
-            ReferenceOr::Reference { .. } => {
-                resolver.resolve_schema(current_doc, schema_ref).await?.1
-            }
-        };
-
         // If the requestBody itself is not required, we avoid marking any of its
         // flattened params as required (we can't express conditional requiredness
         // cleanly at the tool-arg level).
         let body_required = body.required;
 
-        // Flatten object properties. Otherwise, expose a single `body` argument.
-        if let openapiv3::SchemaKind::Type(openapiv3::Type::Object(obj)) = &schema.schema_kind {
-            for (prop_name, prop_schema) in &obj.properties {
-                let required = body_required && obj.required.contains(prop_name);
+        // Flatten object properties (`readOnly` ones were already dropped by the converter).
+        // Otherwise, expose a single `body` argument.
+        if encoding.has_fields() && is_object_schema(&schema) {
+            let required_props: Vec<&str> = schema
+                .get("required")
+                .and_then(Value::as_array)
+                .into_iter()
+                .flatten()
+                .filter_map(Value::as_str)
+                .collect();
+            for (prop_name, prop_schema) in schema
+                .get("properties")
+                .and_then(Value::as_object)
+                .into_iter()
+                .flatten()
+            {
+                let required = body_required && required_props.contains(&prop_name.as_str());
 
                 // Skip if name already exists (collision)
                 if existing_names.contains(prop_name) {
                     continue; // Will be caught by collision check in caller
                 }
 
-                let mut prop_schema_value = match prop_schema {
-                    ReferenceOr::Item(s) => schema_to_json(s),
-                    ReferenceOr::Reference { reference } => {
-                        // Keep $ref for nested schemas (still useful for clients/tools).
-                        json!({"$ref": reference})
-                    }
+                // Multipart file parts (or arrays of them) take binary values.
+                let (mut prop_schema_value, binary) = if encoding == BodyEncoding::Multipart {
+                    binary_part_schema(prop_schema)
+                } else {
+                    (prop_schema.clone(), false)
                 };
 
                 // Apply config overrides
@@ -1085,43 +1350,223 @@ impl OpenApiToolSource {
                     default,
                     schema: prop_schema_value,
                     query: None,
+                    binary,
                 });
             }
         } else {
             // Fallback: represent the full body as one tool argument named "body"
             // (unless it would collide).
             if !existing_names.contains("body") {
-                let required = body_required;
+                let binary = encoding == BodyEncoding::Binary;
+                let schema = if binary {
+                    binary_value_schema(&schema)
+                } else {
+                    schema
+                };
                 params.push(ToolParameter {
                     tool_name: "body".to_string(),
                     original_name: "body".to_string(),
                     location: ParamLocation::Body,
-                    required,
+                    required: body_required,
                     default: None,
-                    schema: schema_to_json(&schema),
+                    schema,
                     query: None,
+                    binary,
                 });
             }
         }
 
-        Ok(params)
+        params
     }
 
-    /// Execute an HTTP request for a tool call.
+    /// Execute an HTTP request for a tool call, following pages when the tool paginates and
+    /// polling accepted long-running operations.
     async fn execute_request(
         &self,
         tool: &GeneratedTool,
         arguments: &Value,
+        ctx: &CallContext,
+    ) -> Result<ToolResponse> {
+        if let Some(operation) = tool.async_operation.as_deref() {
+            let page = self.send_request(tool, arguments, None, ctx).await?;
+            if page.status != reqwest::StatusCode::ACCEPTED {
+                return Ok(page.response);
+            }
+            return self.await_operation(tool, operation, page, ctx).await;
+        }
+        let Some(pagination) = tool.pagination.as_deref() else {
+            return Ok(self
+                .send_request(tool, arguments, None, ctx)
+                .await?
+                .response);
+        };
+
+        let mut pager = Paginator::new(pagination, arguments);
+        let mut next_url = None;
+        let mut first = true;
+        loop {
+            let page = self
+                .send_request(tool, pager.arguments(), next_url.take(), ctx)
+                .await?;
+            let link = page.link();
+            let body = match page.response {
+                ToolResponse::Value(body) if !body.is_string() => body,
+                // Non-JSON first pages (text, images) are returned as-is.
+                response if first => return Ok(response),
+                _ => {
+                    return Err(OpenApiToolsError::Runtime(
+                        "pagination: a follow-up page is not a JSON document".to_string(),
+                    ));
+                }
+            };
+            first = false;
+            match pager
+                .add_page(body, link.as_deref())
+                .map_err(OpenApiToolsError::Runtime)?
+            {
+                NextPage::Done => break,
+                NextPage::Arguments => {}
+                NextPage::Url(link) => {
+                    next_url = Some(self.resolve_response_url(tool, &page.url, &link)?);
+                }
+            }
+        }
+
+        let (body, summary) = pager.finish();
+        Ok(ToolResponse::Paged { body, summary })
+    }
+
+    /// Poll an accepted long-running operation until it finishes.
+    async fn await_operation(
+        &self,
+        tool: &GeneratedTool,
+        operation: &AsyncOperationConfig,
+        accepted: Page,
+        ctx: &CallContext,
     ) -> Result<ToolResponse> {
-        let base_url = self
-            .base_url
+        let location = locate_url(&operation.status_url, &accepted.headers, accepted.body())
+            .ok_or_else(|| {
+                OpenApiToolsError::Runtime(
+                    "asyncOperation: the 202 response has no status URL".to_string(),
+                )
+            })?;
+        let status_url = self.resolve_response_url(tool, &accepted.url, &location)?;
+
+        let mut poller = Poller::new(operation, ctx);
+        let mut last_state = None;
+        loop {
+            let Some(delay) = poller.next_delay() else {
+                let state = last_state.map_or(String::new(), |s| format!(", last state '{s}'"));
+                return Err(OpenApiToolsError::Runtime(format!(
+                    "asyncOperation: operation still running after {}s{state}; status URL: {status_url}",
+                    poller.elapsed().as_secs()
+                )));
+            };
+            tokio::time::sleep(delay).await;
+
+            let page = self
+                .fetch_url(tool, status_url.clone(), poller.remaining(), ctx)
+                .await?;
+            match poller.state(page.status.as_u16(), page.body()) {
+                OperationState::Running(state) => {
+                    if let Some(update) = poller.progress(state.as_deref(), page.body()) {
+                        ctx.report(update);
+                    }
+                    last_state = state;
+                }
+                OperationState::Failed(state) => {
+                    return Err(OpenApiToolsError::Http(format!(
+                        "asyncOperation: operation failed with state '{state}': {}",
+                        page.body()
+                    )));
+                }
+                OperationState::Succeeded => {
+                    let Some(result_url) = &operation.result_url else {
+                        return Ok(page.response);
+                    };
+                    let location =
+                        locate_url(result_url, &page.headers, page.body()).ok_or_else(|| {
+                            OpenApiToolsError::Runtime(
+                                "asyncOperation: the final status response has no result URL"
+                                    .to_string(),
+                            )
+                        })?;
+                    let url = self.resolve_response_url(tool, &page.url, &location)?;
+                    return Ok(self
+                        .fetch_url(tool, url, poller.remaining(), ctx)
+                        .await?
+                        .response);
+                }
+            }
+        }
+    }
+
+    fn configured_base_url(&self) -> Result<String> {
+        self.base_url
             .read()
             .clone()
-            .ok_or_else(|| OpenApiToolsError::Runtime("Base URL not configured".to_string()))?;
+            .ok_or_else(|| OpenApiToolsError::Runtime("Base URL not configured".to_string()))
+    }
+
+    /// Resolve a URL taken from a response (pagination links, async operation status/result);
+    /// it must stay on the base URL's origin.
+    fn resolve_response_url(
+        &self,
+        tool: &GeneratedTool,
+        current: &Url,
+        location: &str,
+    ) -> Result<Url> {
+        let mut url = current.join(location).map_err(|e| {
+            OpenApiToolsError::Runtime(format!("invalid response URL '{location}': {e}"))
+        })?;
+        let base = Url::parse(&self.configured_base_url()?)
+            .map_err(|e| OpenApiToolsError::Runtime(format!("Invalid URL: {e}")))?;
+        if url.origin() != base.origin() {
+            return Err(OpenApiToolsError::Runtime(format!(
+                "response URL '{location}' points outside the base URL origin"
+            )));
+        }
+
+        // Query credentials are not echoed by every API; re-add the ones the URL lacks.
+        let mut credentials = Vec::new();
+        self.apply_query_auth(&mut credentials);
+        Self::apply_query_security(&tool.security, &mut credentials);
+        for pair in credentials {
+            if !url.query_pairs().any(|(k, _)| k == pair.key.as_str()) {
+                url.query_pairs_mut().append_pair(&pair.key, &pair.value);
+            }
+        }
+        Ok(url)
+    }
+
+    /// Send one request; `url` replaces the URL built from the arguments (pagination links).
+    async fn send_request(
+        &self,
+        tool: &GeneratedTool,
+        arguments: &Value,
+        url: Option<Url>,
+        ctx: &CallContext,
+    ) -> Result<Page> {
+        let base_url = self.configured_base_url()?;
 
         let mut parts = self.build_request_parts(tool, arguments)?;
-        self.apply_query_auth(&mut parts.query_params);
-        let url = Self::build_url(&base_url, &parts.path, &parts.query_params)?;
+        inline_resource_refs(
+            tool.body_encoding,
+            &mut parts.body_payload,
+            &mut parts.body_fields,
+            &parts.binary_fields,
+            ctx,
+        )
+        .await
+        .map_err(|e| OpenApiToolsError::Runtime(format!("Invalid request body: {e}")))?;
+        let url = match url {
+            Some(url) => url,
+            None => {
+                self.apply_query_auth(&mut parts.query_params);
+                Self::apply_query_security(&tool.security, &mut parts.query_params);
+                Self::build_url(&base_url, &parts.path, &parts.query_params)?
+            }
+        };
 
         // Outbound safety checks (SSRF + allowlists).
         self.safety
@@ -1130,54 +1575,137 @@ impl OpenApiToolSource {
             .map_err(|e| OpenApiToolsError::Http(e.to_string()))?;
 
         // Build request
-        let mut request = self.client.request(tool.method.clone(), url);
+        let mut request = self.client.request(tool.method.clone(), url.clone());
         request = self.apply_auth(request);
-        request = self.apply_headers(request, parts.headers);
-        request = Self::apply_body(request, parts.body_payload.as_ref(), &parts.body_fields);
+        request = self
+            .apply_security(request, &tool.security, &base_url)
+            .await?;
+        request = self.apply_headers(request, std::mem::take(&mut parts.headers));
+        request = Self::apply_body(request, tool, &parts)?;
         request = self.apply_timeout(request);
+        request = self.sign_request(request)?;
 
         // Execute request
-        let response = request
-            .send()
+        let response =
+            unrelated_http_tools::retry::send(&self.client, request, self.retry_policy(tool), ctx)
+                .await
+                .map_err(|e| OpenApiToolsError::Request(sanitize_reqwest_error(&e)))?;
+        self.read_page(tool, url, response, ctx).await
+    }
+
+    /// `GET` a URL taken from a response (async operation status/result), within `remaining`.
+    async fn fetch_url(
+        &self,
+        tool: &GeneratedTool,
+        url: Url,
+        remaining: Duration,
+        ctx: &CallContext,
+    ) -> Result<Page> {
+        let base_url = self.configured_base_url()?;
+        self.safety
+            .check_url(&url)
             .await
-            .map_err(|e| OpenApiToolsError::Request(sanitize_reqwest_error(&e)))?;
+            .map_err(|e| OpenApiToolsError::Http(e.to_string()))?;
+
+        let mut request = self.client.get(url.clone());
+        request = self.apply_auth(request);
+        request = self
+            .apply_security(request, &tool.security, &base_url)
+            .await?;
+        request = self.apply_headers(request, Vec::new());
+        let timeout = self
+            .request_timeout()
+            .map_or(remaining, |t| t.min(remaining));
+        request = request.timeout(timeout);
+        request = self.sign_request(request)?;
+
+        let response =
+            unrelated_http_tools::retry::send(&self.client, request, self.retry_policy(tool), ctx)
+                .await
+                .map_err(|e| OpenApiToolsError::Request(sanitize_reqwest_error(&e)))?;
+        self.read_page(tool, url, response, ctx).await
+    }
 
-        // Handle response
+    /// The tool's `retry`, or `defaults.retry`.
+    fn retry_policy<'a>(&'a self, tool: &'a GeneratedTool) -> Option<&'a RetryConfig> {
+        tool.retry
+            .as_deref()
+            .or(self.config.defaults.retry.as_ref())
+    }
+
+    /// Read a response; non-2xx responses become errors. Streaming responses are read event by
+    /// event.
+    async fn read_page(
+        &self,
+        tool: &GeneratedTool,
+        url: Url,
+        response: reqwest::Response,
+        ctx: &CallContext,
+    ) -> Result<Page> {
         let status = response.status();
-        let content_type = response
-            .headers()
+        if status == reqwest::StatusCode::UNAUTHORIZED {
+            // A cached token may have been revoked; fetch a fresh one on the next call.
+            self.forget_oauth_tokens(&tool.security);
+        }
+        let headers = response.headers().clone();
+        let content_type = headers
             .get(reqwest::header::CONTENT_TYPE)
             .and_then(|v| v.to_str().ok())
             .map(std::string::ToString::to_string);
+        if status.is_success()
+            && let Some(config) = tool.stream.as_deref()
+            && let Some(format) = stream_format(content_type.as_deref())
+        {
+            let body = read_stream(
+                response,
+                format,
+                config,
+                self.safety.max_response_bytes,
+                ctx,
+            )
+            .await
+            .map_err(|e| match e {
+                StreamError::Transport(e) => OpenApiToolsError::Request(sanitize_reqwest_error(&e)),
+                StreamError::TooLarge(msg) => OpenApiToolsError::Http(msg),
+            })?;
+            return Ok(Page {
+                response: ToolResponse::Value(body),
+                url,
+                status,
+                headers,
+            });
+        }
         let bytes =
             Self::read_response_body_limited_bytes(response, self.safety.max_response_bytes)
                 .await?;
 
         if status.is_success() {
-            if Self::is_image_content_type(content_type.as_deref()) {
+            let response = if Self::is_image_content_type(content_type.as_deref()) {
                 let mime_type = content_type.unwrap_or_else(|| "image/*".to_string());
-                return Ok(ToolResponse::Image { bytes, mime_type });
-            }
-
-            let body = Self::bytes_to_text_or_base64_json(&bytes, content_type.as_deref());
-            match tool.response_mode {
-                HttpResponseMode::Text => Ok(ToolResponse::Value(body)),
-                HttpResponseMode::Json => {
-                    // Try to parse as JSON, fall back to text
-                    let result: Value = match body {
-                        Value::String(s) => serde_json::from_str(&s).unwrap_or_else(|_| json!(s)),
-                        other => other,
-                    };
-                    Ok(ToolResponse::Value(result))
+                ToolResponse::Image { bytes, mime_type }
+            } else {
+                let body = Self::bytes_to_text_or_base64_json(&bytes, content_type.as_deref());
+                match tool.response_mode {
+                    HttpResponseMode::Text => ToolResponse::Value(body),
+                    HttpResponseMode::Json => ToolResponse::Value(Self::parse_body_value(
+                        body,
+                        content_type.as_deref(),
+                        tool.xml_response_schema.as_deref(),
+                        &self.config.xml,
+                    )),
                 }
-            }
+            };
+            Ok(Page {
+                response,
+                url,
+                status,
+                headers,
+            })
         } else {
             // Map HTTP error to MCP error
             let body = Self::bytes_to_text_or_base64_json(&bytes, content_type.as_deref());
-            let error_body: Value = match body {
-                Value::String(s) => serde_json::from_str(&s).unwrap_or_else(|_| json!(s)),
-                other => other,
-            };
+            let error_body =
+                Self::parse_body_value(body, content_type.as_deref(), None, &self.config.xml);
             let status_code = status.as_u16();
             let reason = status.canonical_reason().unwrap_or("Unknown");
             Err(OpenApiToolsError::Http(format!(
@@ -1270,6 +1798,27 @@ impl OpenApiToolSource {
         m.type_() == mime::IMAGE
     }
 
+    /// Parse a text body as XML (XML content types; shaped by `xml_schema` when known) or JSON;
+    /// unparseable text stays a string.
+    fn parse_body_value(
+        body: Value,
+        content_type: Option<&str>,
+        xml_schema: Option<&Value>,
+        conventions: &XmlConfig,
+    ) -> Value {
+        let Value::String(text) = body else {
+            return body;
+        };
+        if content_type.is_some_and(is_xml_media_type) {
+            let parsed = match xml_schema {
+                Some(schema) => xml_to_json_with_schema(&text, schema, conventions),
+                None => xml_to_json(&text, conventions),
+            };
+            return parsed.unwrap_or(Value::String(text));
+        }
+        serde_json::from_str(&text).unwrap_or(Value::String(text))
+    }
+
     fn bytes_to_text_or_base64_json(bytes: &[u8], content_type: Option<&str>) -> Value {
         if let Ok(s) = std::str::from_utf8(bytes) {
             Value::String(s.to_string())
@@ -1290,6 +1839,7 @@ impl OpenApiToolSource {
         let mut headers: Vec<(String, String)> = Vec::new();
         let mut body_fields: HashMap<String, Value> = HashMap::new();
         let mut body_payload: Option<Value> = None;
+        let mut binary_fields: HashSet<String> = HashSet::new();
 
         for param in &tool.parameters {
             // Get value from arguments or use default
@@ -1333,6 +1883,9 @@ impl OpenApiToolSource {
                         if param.original_name == "body" && param.tool_name == "body" {
                             body_payload = Some(val);
                         } else {
+                            if param.binary {
+                                binary_fields.insert(param.original_name.clone());
+                            }
                             body_fields.insert(param.original_name.clone(), val);
                         }
                     }
@@ -1350,6 +1903,7 @@ impl OpenApiToolSource {
             headers,
             body_fields,
             body_payload,
+            binary_fields,
         })
     }
 
@@ -1363,6 +1917,147 @@ impl OpenApiToolSource {
         }
     }
 
+    fn apply_query_security(credentials: &[AppliedCredential], query_params: &mut Vec<QueryPair>) {
+        for credential in credentials {
+            if let AppliedCredential::Query { name, value } = credential {
+                query_params.push(QueryPair {
+                    key: name.clone(),
+                    value: value.clone(),
+                    allow_reserved: false,
+                });
+            }
+        }
+    }
+
+    /// Apply the operation's security credentials to the HTTP request.
+    async fn apply_security(
+        &self,
+        mut request: reqwest::RequestBuilder,
+        credentials: &[AppliedCredential],
+        base_url: &str,
+    ) -> Result<reqwest::RequestBuilder> {
+        let mut cookies: Vec<String> = Vec::new();
+        for credential in credentials {
+            request = match credential {
+                AppliedCredential::Header { name, value } => request.header(name, value),
+                AppliedCredential::Cookie { name, value } => {
+                    cookies.push(format!("{name}={value}"));
+                    request
+                }
+                AppliedCredential::Bearer { token } => request.bearer_auth(token),
+                AppliedCredential::Basic { username, password } => {
+                    request.basic_auth(username, Some(password))
+                }
+                AppliedCredential::OAuth2ClientCredentials(grant) => {
+                    let token = self.oauth2_access_token(grant, base_url).await?;
+                    request.bearer_auth(token)
+                }
+                AppliedCredential::Query { .. } => request, // applied during URL building
+            };
+        }
+        if !cookies.is_empty() {
+            request = request.header(reqwest::header::COOKIE, cookies.join("; "));
+        }
+        Ok(request)
+    }
+
+    /// Obtain an access token with the client credentials grant, reusing a cached one until it
+    /// is about to expire.
+    ///
+    /// Tokens without `expires_in` are not cached.
+    async fn oauth2_access_token(
+        &self,
+        grant: &ClientCredentialsGrant,
+        base_url: &str,
+    ) -> Result<String> {
+        if let Some(cached) = self.oauth_tokens.read().get(grant)
+            && cached.refresh_at > Instant::now()
+        {
+            return Ok(cached.access_token.clone());
+        }
+
+        // Relative token URLs are resolved against the API base URL.
+        let token_url = Url::parse(base_url)
+            .and_then(|base| base.join(&grant.token_url))
+            .map_err(|e| {
+                OpenApiToolsError::Config(format!(
+                    "Invalid OAuth2 token URL '{}' in '{}': {e}",
+                    grant.token_url, self.name
+                ))
+            })?;
+        self.safety
+            .check_url(&token_url)
+            .await
+            .map_err(|e| OpenApiToolsError::Http(format!("OAuth2 token request blocked: {e}")))?;
+
+        let form = {
+            let mut form = url::form_urlencoded::Serializer::new(String::new());
+            form.append_pair("grant_type", "client_credentials");
+            if !grant.scopes.is_empty() {
+                form.append_pair("scope", &grant.scopes.join(" "));
+            }
+            form.finish()
+        };
+        let request = self
+            .client
+            .post(token_url)
+            .basic_auth(&grant.client_id, Some(&grant.client_secret))
+            .header(
+                reqwest::header::CONTENT_TYPE,
+                "application/x-www-form-urlencoded",
+            )
+            .header(reqwest::header::ACCEPT, "application/json")
+            .body(form);
+        let response = self
+            .apply_timeout(request)
+            .send()
+            .await
+            .map_err(|e| OpenApiToolsError::Request(sanitize_reqwest_error(&e)))?;
+
+        let status = response.status();
+        let bytes =
+            Self::read_response_body_limited_bytes(response, self.safety.max_response_bytes)
+                .await?;
+        if !status.is_success() {
+            return Err(OpenApiToolsError::Http(format!(
+                "OAuth2 token request to '{}' returned {}",
+                grant.token_url,
+                status.as_u16()
+            )));
+        }
+        let body: Value = serde_json::from_slice(&bytes).map_err(|e| {
+            OpenApiToolsError::Http(format!("OAuth2 token response is not JSON: {e}"))
+        })?;
+        let access_token = body
+            .get("access_token")
+            .and_then(Value::as_str)
+            .ok_or_else(|| {
+                OpenApiToolsError::Http("OAuth2 token response has no access_token".to_string())
+            })?
+            .to_string();
+
+        if let Some(expires_in) = body.get("expires_in").and_then(Value::as_u64) {
+            let lifetime = Duration::from_secs(expires_in).saturating_sub(TOKEN_EXPIRY_MARGIN);
+            self.oauth_tokens.write().insert(
+                grant.clone(),
+                CachedToken {
+                    access_token: access_token.clone(),
+                    refresh_at: Instant::now() + lifetime,
+                },
+            );
+        }
+        Ok(access_token)
+    }
+
+    fn forget_oauth_tokens(&self, credentials: &[AppliedCredential]) {
+        let mut tokens = self.oauth_tokens.write();
+        for credential in credentials {
+            if let AppliedCredential::OAuth2ClientCredentials(grant) = credential {
+                tokens.remove(grant);
+            }
+        }
+    }
+
     fn build_url(base_url: &str, path: &str, query_params: &[QueryPair]) -> Result<Url> {
         let url = format!("{}{}", base_url.trim_end_matches('/'), path);
         let mut url = Url::parse(&url)
@@ -1399,26 +2094,37 @@ impl OpenApiToolSource {
     }
 
     fn apply_body(
-        mut request: reqwest::RequestBuilder,
-        body_payload: Option<&Value>,
-        body_fields: &HashMap<String, Value>,
-    ) -> reqwest::RequestBuilder {
-        if let Some(payload) = body_payload {
-            request = request.json(payload);
-        } else if !body_fields.is_empty() {
-            request = request.json(body_fields);
-        }
-        request
+        request: reqwest::RequestBuilder,
+        tool: &GeneratedTool,
+        parts: &RequestParts,
+    ) -> Result<reqwest::RequestBuilder> {
+        let body = encode_body(
+            tool.body_encoding,
+            tool.body_content_type.as_deref(),
+            parts.body_payload.as_ref(),
+            &parts.body_fields,
+            &parts.binary_fields,
+            tool.xml_body.as_deref(),
+        )
+        .map_err(|e| OpenApiToolsError::Runtime(format!("Invalid request body: {e}")))?;
+        Ok(match body {
+            Some(body) => request
+                .header(reqwest::header::CONTENT_TYPE, body.content_type)
+                .body(body.bytes),
+            None => request,
+        })
     }
 
-    fn apply_timeout(&self, mut request: reqwest::RequestBuilder) -> reqwest::RequestBuilder {
-        let effective_timeout = match self.config.defaults.timeout {
+    fn request_timeout(&self) -> Option<Duration> {
+        match self.config.defaults.timeout {
             Some(0) => None, // explicit disable
             Some(secs) => Some(Duration::from_secs(secs)),
             None => Some(self.default_timeout),
-        };
+        }
+    }
 
-        if let Some(t) = effective_timeout {
+    fn apply_timeout(&self, mut request: reqwest::RequestBuilder) -> reqwest::RequestBuilder {
+        if let Some(t) = self.request_timeout() {
             request = request.timeout(t);
         }
 
@@ -1433,10 +2139,27 @@ impl OpenApiToolSource {
             Some(AuthConfig::Basic { username, password }) => {
                 request.basic_auth(username, Some(password))
             }
-            Some(AuthConfig::Query { .. } | AuthConfig::None) | None => request, // query auth is applied during URL building
+            // Query auth is applied during URL building, signatures once the request is built.
+            Some(
+                AuthConfig::Query { .. }
+                | AuthConfig::None
+                | AuthConfig::Sigv4 { .. }
+                | AuthConfig::Hmac { .. },
+            )
+            | None => request,
         }
     }
 
+    /// Sign the built request (`sigv4` / `hmac` auth).
+    fn sign_request(&self, request: reqwest::RequestBuilder) -> Result<reqwest::RequestBuilder> {
+        unrelated_http_tools::signing::sign_request(
+            &self.client,
+            request,
+            self.config.auth.as_ref(),
+        )
+        .map_err(|e| OpenApiToolsError::Request(format!("Failed to sign request: {e}")))
+    }
+
     fn serialize_query_param(
         &self,
         name: &str,
@@ -1726,6 +2449,7 @@ fn normalize_tool_path(path: &str) -> String {
 fn build_manual_override_parameters(
     tool_name: &str,
     params: &HashMap<String, unrelated_http_tools::config::HttpParamConfig>,
+    body_encoding: BodyEncoding,
 ) -> Result<Vec<ToolParameter>> {
     let mut parameters: Vec<ToolParameter> = Vec::new();
 
@@ -1743,6 +2467,13 @@ fn build_manual_override_parameters(
             .schema
             .clone()
             .unwrap_or_else(|| json!({"type": "string"}));
+        let binary = location == ParamLocation::Body
+            && (is_binary_schema(&schema) || body_encoding == BodyEncoding::Binary);
+        let schema = if binary {
+            binary_value_schema(&schema)
+        } else {
+            schema
+        };
 
         let query = if location == ParamLocation::Query {
             let style = p.style.map_or(QueryStyle::Form, map_query_style_config);
@@ -1765,6 +2496,7 @@ fn build_manual_override_parameters(
             default: p.default.clone(),
             schema,
             query,
+            binary,
         });
     }
 
@@ -1809,6 +2541,7 @@ fn build_manual_override_output_schema(
     response_override: Option<&ResolvedResponseOverride>,
     response_cfg: &unrelated_http_tools::config::HttpResponseConfig,
     response_pipeline: &CompiledResponsePipeline,
+    paginated: bool,
 ) -> Result<Option<Arc<JsonObject>>> {
     // Output schema precedence:
     // 1) explicit per-tool outputSchema (manual override request)
@@ -1838,7 +2571,7 @@ fn build_manual_override_output_schema(
         );
     }
 
-    Ok(Some(wrap_body_output_schema(&body_schema)?))
+    Ok(Some(wrap_body_output_schema(&body_schema, paginated)?))
 }
 
 fn manual_override_to_tool(
@@ -1848,29 +2581,82 @@ fn manual_override_to_tool(
     operation_id: Option<String>,
     response_override: Option<&ResolvedResponseOverride>,
     global_response_transforms: &[ResponseTransform],
+    xml_conventions: &XmlConfig,
 ) -> Result<GeneratedTool> {
     let HttpToolConfig {
         method,
         path,
         description,
         params,
+        body_encoding,
+        body_template,
+        pagination,
+        async_operation,
+        retry,
         response,
     } = &override_cfg.request;
 
     let method = parse_manual_override_http_method(tool_name, method)?;
     let normalized_path = normalize_tool_path(path);
-    let parameters = build_manual_override_parameters(tool_name, params)?;
-
-    let input_schema = build_input_schema(&parameters);
-
-    let final_description = override_cfg
-        .description
-        .clone()
-        .or_else(|| description.clone())
-        .or_else(|| {
-            let method_name = method.as_str();
-            Some(format!("Calls {method_name} {normalized_path}"))
-        });
+    let parameters = build_manual_override_parameters(tool_name, params, *body_encoding)?;
+    let xml_body = build_manual_override_xml_body(
+        tool_name,
+        *body_encoding,
+        body_template.as_ref(),
+        &parameters,
+        xml_conventions,
+    )?;
+    let pagination = override_cfg.pagination.as_ref().or(pagination.as_ref());
+    if let Some(pagination) = pagination {
+        unrelated_http_tools::pagination::validate(pagination, |name| {
+            parameters.iter().any(|p| p.tool_name == name)
+        })
+        .map_err(|e| {
+            OpenApiToolsError::Config(format!(
+                "Invalid pagination for OpenAPI override tool '{tool_name}' in '{backend_name}': {e}"
+            ))
+        })?;
+    }
+    let async_operation = override_cfg
+        .async_operation
+        .as_ref()
+        .or(async_operation.as_ref());
+    if let Some(operation) = async_operation {
+        let invalid = |e: String| {
+            OpenApiToolsError::Config(format!(
+                "Invalid asyncOperation for OpenAPI override tool '{tool_name}' in '{backend_name}': {e}"
+            ))
+        };
+        if pagination.is_some() {
+            return Err(invalid("cannot be combined with pagination".to_string()));
+        }
+        unrelated_http_tools::async_operation::validate(operation).map_err(invalid)?;
+    }
+    if let Some(retry) = retry {
+        unrelated_http_tools::retry::validate(retry).map_err(|e| {
+            OpenApiToolsError::Config(format!(
+                "Invalid retry for OpenAPI override tool '{tool_name}' in '{backend_name}': {e}"
+            ))
+        })?;
+    }
+    if let Some(stream) = &response.stream {
+        unrelated_http_tools::streaming::validate(stream).map_err(|e| {
+            OpenApiToolsError::Config(format!(
+                "Invalid response.stream for OpenAPI override tool '{tool_name}' in '{backend_name}': {e}"
+            ))
+        })?;
+    }
+
+    let input_schema = build_input_schema(&parameters);
+
+    let final_description = override_cfg
+        .description
+        .clone()
+        .or_else(|| description.clone())
+        .or_else(|| {
+            let method_name = method.as_str();
+            Some(format!("Calls {method_name} {normalized_path}"))
+        });
 
     let response_pipeline = compile_manual_override_response_pipeline(
         backend_name,
@@ -1886,6 +2672,7 @@ fn manual_override_to_tool(
         response_override,
         response,
         &response_pipeline,
+        pagination.is_some(),
     )?;
 
     Ok(GeneratedTool {
@@ -1897,12 +2684,81 @@ fn manual_override_to_tool(
         path: normalized_path,
         parameters,
         input_schema,
+        body_encoding: *body_encoding,
+        body_content_type: None,
+        xml_body,
+        xml_response_schema: None,
         response_mode: response.mode,
         output_schema,
         response_pipeline,
+        pagination: pagination.cloned().map(Arc::new),
+        async_operation: async_operation.cloned().map(Arc::new),
+        retry: retry.clone().map(Arc::new),
+        stream: response.stream.clone().map(Arc::new),
+        security: Vec::new(),
     })
 }
 
+/// XML body of a manual override: the `body` param's schema, or an object of all body params
+/// under a root element named after the tool.
+fn build_manual_override_xml_body(
+    tool_name: &str,
+    body_encoding: BodyEncoding,
+    body_template: Option<&String>,
+    parameters: &[ToolParameter],
+    conventions: &XmlConfig,
+) -> Result<Option<Arc<XmlBody>>> {
+    if body_encoding != BodyEncoding::Xml {
+        if body_template.is_some() {
+            return Err(OpenApiToolsError::Config(format!(
+                "bodyTemplate in override tool '{tool_name}' requires bodyEncoding: xml"
+            )));
+        }
+        return Ok(None);
+    }
+
+    let body_params: Vec<&ToolParameter> = parameters
+        .iter()
+        .filter(|p| p.location == ParamLocation::Body)
+        .collect();
+
+    if let Some(template) = body_template {
+        let placeholders = template_placeholders(template).map_err(|e| {
+            OpenApiToolsError::Config(format!(
+                "Invalid bodyTemplate in override tool '{tool_name}': {e}"
+            ))
+        })?;
+        if let Some(unknown) = placeholders
+            .iter()
+            .find(|name| !body_params.iter().any(|p| p.original_name == **name))
+        {
+            return Err(OpenApiToolsError::Config(format!(
+                "bodyTemplate in override tool '{tool_name}' references unknown body param '{unknown}'"
+            )));
+        }
+    }
+
+    let schema = if let Some(payload) = body_params
+        .iter()
+        .find(|p| p.tool_name == "body" && p.original_name == "body")
+    {
+        payload.schema.clone()
+    } else {
+        let properties: serde_json::Map<String, Value> = body_params
+            .iter()
+            .map(|p| (p.original_name.clone(), p.schema.clone()))
+            .collect();
+        json!({ "type": "object", "properties": properties })
+    };
+
+    Ok(Some(Arc::new(XmlBody {
+        root: tool_name.to_string(),
+        schema,
+        template: body_template.cloned(),
+        conventions: conventions.clone(),
+    })))
+}
+
 fn map_query_style_config(style: QueryStyleConfig) -> QueryStyle {
     match style {
         QueryStyleConfig::Form => QueryStyle::Form,
@@ -1948,6 +2804,22 @@ impl OpenApiToolSource {
     /// - required parameters are missing
     /// - the outbound HTTP request fails (transport or non-2xx response)
     pub async fn call_tool(&self, name: &str, arguments: Value) -> Result<CallToolResult> {
+        self.call_tool_with_context(name, arguments, &CallContext::default())
+            .await
+    }
+
+    /// Execute a tool call within the caller's deadline, reporting progress to `ctx`.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`Self::call_tool`]; also when a long-running operation fails or does not finish
+    /// in time.
+    pub async fn call_tool_with_context(
+        &self,
+        name: &str,
+        arguments: Value,
+        ctx: &CallContext,
+    ) -> Result<CallToolResult> {
         // Clone the tool inside the sync block to avoid holding lock across await.
         let tool = {
             let tools = self.tools.read();
@@ -1958,42 +2830,25 @@ impl OpenApiToolSource {
                 .ok_or_else(|| OpenApiToolsError::Runtime(format!("Tool not found: {name}")))?
         };
 
-        let resp = self.execute_request(&tool, &arguments).await?;
-        match resp {
+        let resp = self.execute_request(&tool, &arguments, ctx).await?;
+        let mut result = match resp {
             ToolResponse::Image { bytes, mime_type } => {
                 let b64 = base64::engine::general_purpose::STANDARD.encode(bytes);
                 // Response shaping doesn't apply to binary.
-                Ok(CallToolResult {
+                CallToolResult {
                     content: vec![Content::image(b64, mime_type)],
                     structured_content: None,
                     is_error: Some(false),
                     meta: None,
-                })
-            }
-            ToolResponse::Value(mut body) => {
-                tool.response_pipeline.apply_to_value(&mut body);
-
-                // Emit `structured_content` only when the tool advertises an output schema.
-                if tool.output_schema.is_some() {
-                    let structured = json!({ "body": body });
-                    let text = serde_json::to_string(&structured)
-                        .unwrap_or_else(|_| structured.to_string());
-                    Ok(CallToolResult {
-                        content: vec![Content::text(text)],
-                        structured_content: Some(structured),
-                        is_error: Some(false),
-                        meta: None,
-                    })
-                } else {
-                    let text = if let Some(s) = body.as_str() {
-                        s.to_string()
-                    } else {
-                        serde_json::to_string(&body).unwrap_or_else(|_| body.to_string())
-                    };
-                    Ok(CallToolResult::success(vec![Content::text(text)]))
                 }
             }
+            ToolResponse::Value(body) => value_result(&tool, body, None),
+            ToolResponse::Paged { body, summary } => value_result(&tool, body, Some(summary)),
+        };
+        if self.retry_policy(&tool).is_some() {
+            unrelated_http_tools::retry::record_attempts(&mut result, ctx.attempts());
         }
+        Ok(result)
     }
 
     fn resolve_base_url(&self, base_url: &str) -> Result<String> {
@@ -2033,9 +2888,30 @@ impl OpenApiToolSource {
     pub async fn start(&self) -> Result<()> {
         let startup_timeout = self.startup_timeout;
 
+        if let Some(auth) = &self.config.auth {
+            unrelated_http_tools::signing::validate(auth).map_err(|e| {
+                OpenApiToolsError::Config(format!(
+                    "Invalid auth for OpenAPI tool source '{}': {e}",
+                    self.name
+                ))
+            })?;
+        }
+        if let Some(retry) = &self.config.defaults.retry {
+            unrelated_http_tools::retry::validate(retry).map_err(|e| {
+                OpenApiToolsError::Config(format!(
+                    "Invalid defaults.retry for OpenAPI tool source '{}': {e}",
+                    self.name
+                ))
+            })?;
+        }
+
         let startup = async {
             // Load and parse spec.
-            let spec = self.load_spec().await?;
+            let LoadedSpec {
+                version,
+                document,
+                openapi: spec,
+            } = self.load_spec().await?;
 
             // Determine base URL.
             let base_url = self
@@ -2052,22 +2928,25 @@ impl OpenApiToolSource {
             let base_url = self.resolve_base_url(&base_url)?;
 
             // Discover tools.
-            let tools = self.discover_tools(&spec).await?;
+            let root_doc = DocId::parse(&self.config.spec)?;
+            let resolver = OpenApiResolver::with_document(root_doc, document, &self.client);
+            let tools = self.discover_tools_with(&resolver, &spec).await?;
 
-            Ok::<_, OpenApiToolsError>((spec, base_url, tools))
+            Ok::<_, OpenApiToolsError>((spec, version, base_url, tools))
         };
 
-        let (spec, base_url, tools) = match tokio::time::timeout(startup_timeout, startup).await {
-            Ok(Ok(v)) => v,
-            Ok(Err(e)) => return Err(e),
-            Err(_) => {
-                return Err(OpenApiToolsError::Startup(format!(
-                    "Startup timeout after {}s for OpenAPI tool source '{}'",
-                    startup_timeout.as_secs(),
-                    self.name
-                )));
-            }
-        };
+        let (spec, version, base_url, tools) =
+            match tokio::time::timeout(startup_timeout, startup).await {
+                Ok(Ok(v)) => v,
+                Ok(Err(e)) => return Err(e),
+                Err(_) => {
+                    return Err(OpenApiToolsError::Startup(format!(
+                        "Startup timeout after {}s for OpenAPI tool source '{}'",
+                        startup_timeout.as_secs(),
+                        self.name
+                    )));
+                }
+            };
 
         // Optional reachability probe (baseUrl only).
         self.probe_base_url(&base_url).await?;
@@ -2075,13 +2954,15 @@ impl OpenApiToolSource {
         *self.base_url.write() = Some(base_url);
 
         tracing::info!(
-            "Discovered {} tools from OpenAPI spec '{}'",
+            "Discovered {} tools from OpenAPI {} spec '{}'",
             tools.len(),
+            version.as_str(),
             self.name
         );
 
         // Store spec and tools.
         *self.spec.write() = Some(spec);
+        *self.spec_version.write() = Some(version);
         *self.tools.write() = tools;
 
         Ok(())
@@ -2102,6 +2983,14 @@ impl OpenApiToolSource {
     pub fn spec_title(&self) -> Option<String> {
         self.spec.read().as_ref().map(|s| s.info.title.clone())
     }
+
+    /// The spec version detected while loading (`2.0`, `3.0` or `3.1`).
+    ///
+    /// Returns `None` if the source has not been started yet.
+    #[must_use]
+    pub fn spec_version(&self) -> Option<SpecVersion> {
+        *self.spec_version.read()
+    }
 }
 
 // ============================================================================
@@ -2109,6 +2998,42 @@ impl OpenApiToolSource {
 // ============================================================================
 
 /// Generate a canonical tool name from method and path.
+/// Shape a response body into a tool result; paginated calls also report their summary.
+fn value_result(
+    tool: &GeneratedTool,
+    mut body: Value,
+    pagination: Option<PaginationSummary>,
+) -> CallToolResult {
+    tool.response_pipeline.apply_to_value(&mut body);
+    let notice = pagination.as_ref().and_then(PaginationSummary::notice);
+
+    // Emit `structured_content` only when the tool advertises an output schema.
+    let mut result = if tool.output_schema.is_some() {
+        let mut structured = json!({ "body": body });
+        if let Some(summary) = &pagination {
+            structured["pagination"] = json!(summary);
+        }
+        let text = serde_json::to_string(&structured).unwrap_or_else(|_| structured.to_string());
+        CallToolResult {
+            content: vec![Content::text(text)],
+            structured_content: Some(structured),
+            is_error: Some(false),
+            meta: None,
+        }
+    } else {
+        let text = if let Some(s) = body.as_str() {
+            s.to_string()
+        } else {
+            serde_json::to_string(&body).unwrap_or_else(|_| body.to_string())
+        };
+        CallToolResult::success(vec![Content::text(text)])
+    };
+    if let Some(notice) = notice {
+        result.content.push(Content::text(notice));
+    }
+    result
+}
+
 fn generate_canonical_name(method: &str, path: &str) -> String {
     let mut name = format!("{}_{}", method.to_lowercase(), path);
 
@@ -2284,13 +3209,10 @@ async fn extract_schema(
     use ParameterSchemaOrContent::{Content, Schema};
 
     match format {
-        Schema(ReferenceOr::Item(schema)) => Ok(schema_to_json(schema)),
-        Schema(schema_ref @ ReferenceOr::Reference { reference }) => {
-            // Try to inline internal schema refs for better tool schemas.
-            match resolver.resolve_schema(current_doc, schema_ref).await {
-                Ok((_doc, s)) => Ok(schema_to_json(&s)),
-                Err(_) => Ok(json!({"$ref": reference})),
-            }
+        Schema(schema_ref) => {
+            SchemaConverter::new(resolver, SchemaDirection::Input)
+                .convert_schema_ref(current_doc, schema_ref)
+                .await
         }
         Content(_) => Ok(json!({"type": "string"})), // Fallback
     }
@@ -2353,76 +3275,71 @@ async fn merge_parameters(
     Ok(merged)
 }
 
-/// Convert `OpenAPI` schema to JSON Schema value.
-fn schema_to_json(schema: &Schema) -> Value {
-    let mut result = json!({});
-
-    if let Some(desc) = &schema.schema_data.description {
-        result["description"] = json!(desc);
-    }
-
-    match &schema.schema_kind {
-        openapiv3::SchemaKind::Type(t) => match t {
-            openapiv3::Type::String(s) => {
-                result["type"] = json!("string");
-                if !s.enumeration.is_empty() {
-                    let enum_values: Vec<_> = s
-                        .enumeration
-                        .iter()
-                        .filter_map(std::clone::Clone::clone)
-                        .collect();
-                    result["enum"] = json!(enum_values);
-                }
-            }
-            openapiv3::Type::Number(_) => {
-                result["type"] = json!("number");
-            }
-            openapiv3::Type::Integer(_) => {
-                result["type"] = json!("integer");
-            }
-            openapiv3::Type::Boolean(_) => {
-                result["type"] = json!("boolean");
-            }
-            openapiv3::Type::Array(a) => {
-                result["type"] = json!("array");
-                if let Some(items) = &a.items {
-                    match items {
-                        ReferenceOr::Item(item_schema) => {
-                            result["items"] = schema_to_json(item_schema);
-                        }
-                        ReferenceOr::Reference { reference } => {
-                            result["items"] = json!({"$ref": reference});
-                        }
-                    }
-                }
-            }
-            openapiv3::Type::Object(o) => {
-                result["type"] = json!("object");
-                let mut properties = json!({});
-                for (name, prop) in &o.properties {
-                    match prop {
-                        ReferenceOr::Item(prop_schema) => {
-                            properties[name] = schema_to_json(prop_schema);
-                        }
-                        ReferenceOr::Reference { reference } => {
-                            properties[name] = json!({ "$ref": reference });
-                        }
-                    }
-                }
-                if !o.properties.is_empty() {
-                    result["properties"] = properties;
-                }
-                if !o.required.is_empty() {
-                    result["required"] = json!(o.required);
-                }
-            }
+/// Whether a converted schema is an object type (optionally nullable), i.e. flattenable.
+fn is_object_schema(schema: &Value) -> bool {
+    match schema.get("type") {
+        Some(Value::String(t)) => t == "object",
+        Some(Value::Array(types)) => {
+            types.iter().any(|t| t == "object")
+                && types.iter().all(|t| t == "object" || t == "null")
+        }
+        _ => false,
+    }
+}
+
+/// Pick the request body media type: JSON if declared, otherwise the first other supported type
+/// in spec order.
+fn select_body_media(body: &RequestBody) -> Option<(BodyMedia, Option<&ReferenceOr<Schema>>)> {
+    let supported: Vec<_> = body
+        .content
+        .iter()
+        .filter_map(|(media_type, media)| {
+            BodyEncoding::for_media_type(media_type).map(|encoding| (encoding, media_type, media))
+        })
+        .collect();
+    let (encoding, media_type, media) = supported
+        .iter()
+        .find(|(encoding, _, _)| *encoding == BodyEncoding::Json)
+        .or_else(|| supported.first())?;
+    // JSON bodies without a schema have nothing to expose.
+    if *encoding == BodyEncoding::Json && media.schema.is_none() {
+        return None;
+    }
+    Some((
+        BodyMedia {
+            encoding: *encoding,
+            content_type: (*media_type).clone(),
+            xml: None,
         },
-        _ => {
-            result["type"] = json!("object");
+        media.schema.as_ref(),
+    ))
+}
+
+/// XML root element name for a body schema: its component name when referenced (the schema's
+/// own `xml.name` still wins).
+fn xml_root_name(schema_ref: Option<&ReferenceOr<Schema>>) -> String {
+    match schema_ref {
+        Some(ReferenceOr::Reference { reference }) => {
+            reference.rsplit('/').next().unwrap_or_default().to_string()
         }
+        _ => String::new(),
     }
+}
 
-    result
+/// Schema for a multipart property, and whether it is sent as a file part (binary value or array
+/// of binary values).
+fn binary_part_schema(schema: &Value) -> (Value, bool) {
+    if is_binary_schema(schema) {
+        return (binary_value_schema(schema), true);
+    }
+    if let Some(items) = schema.get("items")
+        && is_binary_schema(items)
+    {
+        let mut schema = schema.clone();
+        schema["items"] = binary_value_schema(items);
+        return (schema, true);
+    }
+    (schema.clone(), false)
 }
 
 /// Build input schema for a tool from its parameters.
@@ -2457,7 +3374,7 @@ fn build_input_schema(parameters: &[ToolParameter]) -> Value {
     schema
 }
 
-fn wrap_body_output_schema(body_schema: &Value) -> Result<Arc<JsonObject>> {
+fn wrap_body_output_schema(body_schema: &Value, paginated: bool) -> Result<Arc<JsonObject>> {
     if !body_schema.is_object() {
         return Err(OpenApiToolsError::Config(
             "outputSchema must be a JSON object (JSON Schema)".to_string(),
@@ -2465,32 +3382,30 @@ fn wrap_body_output_schema(body_schema: &Value) -> Result<Arc<JsonObject>> {
     }
 
     // MCP requires the root output schema to be an object.
-    let wrapped = json!({
+    let mut wrapped = json!({
         "type": "object",
         "required": ["body"],
         "properties": {
             "body": body_schema.clone()
         }
     });
+    if paginated {
+        wrapped["properties"]["pagination"] = unrelated_http_tools::pagination::summary_schema();
+    }
 
     let obj = wrapped.as_object().cloned().unwrap_or_else(JsonObject::new);
     Ok(Arc::new(obj))
 }
 
+/// Extract JSON schema from a response body schema.
 async fn extract_schema_ref(
     resolver: &OpenApiResolver<'_>,
     current_doc: &DocId,
     schema_ref: &ReferenceOr<Schema>,
 ) -> Result<Value> {
-    match schema_ref {
-        ReferenceOr::Item(schema) => Ok(schema_to_json(schema)),
-        ReferenceOr::Reference { reference } => {
-            match resolver.resolve_schema(current_doc, schema_ref).await {
-                Ok((_doc, s)) => Ok(schema_to_json(&s)),
-                Err(_) => Ok(json!({"$ref": reference})),
-            }
-        }
-    }
+    SchemaConverter::new(resolver, SchemaDirection::Output)
+        .convert_schema_ref(current_doc, schema_ref)
+        .await
 }
 
 /// Convert a JSON value to a string for URL/header parameters.
@@ -2511,6 +3426,7 @@ fn value_to_string(value: &Value) -> String {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::config::SecurityConfig;
     use std::fs;
     use tempfile::tempdir;
     use unrelated_http_tools::config::EndpointDefaults;
@@ -2562,14 +3478,17 @@ mod tests {
             spec_hash_policy: HashPolicy::Ignore,
             base_url: None,
             auth: None,
+            security: SecurityConfig::default(),
             auto_discover: crate::config::AutoDiscoverConfig::Enabled(true),
             endpoints: HashMap::new(),
             defaults: EndpointDefaults {
                 timeout: None,
                 array_style: None,
                 headers: HashMap::new(),
+                retry: None,
             },
             response_transforms: Vec::new(),
+            xml: Default::default(),
             response_overrides: Vec::new(),
             overrides: crate::config::OpenApiOverridesConfig::default(),
         };
@@ -2597,14 +3516,17 @@ mod tests {
             spec_hash_policy: HashPolicy::Ignore,
             base_url: None,
             auth: None,
+            security: SecurityConfig::default(),
             auto_discover: crate::config::AutoDiscoverConfig::Enabled(true),
             endpoints: HashMap::new(),
             defaults: EndpointDefaults {
                 timeout: None,
                 array_style: None,
                 headers: HashMap::new(),
+                retry: None,
             },
             response_transforms: Vec::new(),
+            xml: Default::default(),
             response_overrides: Vec::new(),
             overrides: crate::config::OpenApiOverridesConfig::default(),
         };
@@ -2628,14 +3550,17 @@ mod tests {
             spec_hash_policy: HashPolicy::Ignore,
             base_url: Some("https://example.com".to_string()),
             auth: None,
+            security: SecurityConfig::default(),
             auto_discover: crate::config::AutoDiscoverConfig::Enabled(true),
             endpoints: HashMap::new(),
             defaults: EndpointDefaults {
                 timeout: None,
                 array_style: None,
                 headers: HashMap::new(),
+                retry: None,
             },
             response_transforms: Vec::new(),
+            xml: Default::default(),
             response_overrides: Vec::new(),
             overrides: crate::config::OpenApiOverridesConfig::default(),
         };
@@ -2812,6 +3737,65 @@ paths:
         assert!(!age.required);
     }
 
+    #[tokio::test]
+    async fn test_inlines_nested_refs_and_skips_read_only_body_params() {
+        let spec_yaml = r#"
+openapi: "3.0.0"
+info:
+  title: t
+  version: "1"
+components:
+  schemas:
+    User:
+      type: object
+      required: [id, name]
+      properties:
+        id: { type: string, readOnly: true }
+        name: { type: string }
+        address: { $ref: '#/components/schemas/Address' }
+        secret: { type: string, writeOnly: true }
+    Address:
+      type: object
+      nullable: true
+      properties:
+        city: { type: string, maxLength: 64 }
+paths:
+  /users:
+    post:
+      operationId: createUser
+      requestBody:
+        required: true
+        content:
+          application/json:
+            schema: { $ref: '#/components/schemas/User' }
+      responses:
+        "201":
+          description: created
+          content:
+            application/json:
+              schema: { $ref: '#/components/schemas/User' }
+"#;
+        let spec: OpenAPI = serde_yaml::from_str(spec_yaml).unwrap();
+        let backend = test_backend();
+
+        let tools = backend.discover_tools(&spec).await.unwrap();
+        let tool = tools.iter().find(|t| t.name == "createUser").unwrap();
+        assert_eq!(
+            tool.input_schema["properties"]["address"],
+            json!({
+                "type": ["object", "null"],
+                "properties": { "city": { "type": "string", "maxLength": 64 } }
+            })
+        );
+        assert!(tool.input_schema["properties"].get("id").is_none());
+        assert_eq!(tool.input_schema["required"], json!(["name"]));
+
+        let body = &tool.output_schema.as_ref().expect("output_schema")["properties"]["body"];
+        assert!(body["properties"].get("id").is_some());
+        assert!(body["properties"].get("secret").is_none());
+        assert_eq!(body["required"], json!(["id", "name"]));
+    }
+
     #[test]
     fn test_query_serialization_respects_explode() {
         let backend = test_backend();
@@ -2913,14 +3897,17 @@ paths:
             spec_hash_policy: HashPolicy::Ignore,
             base_url: Some("https://example.com".to_string()),
             auth: None,
+            security: SecurityConfig::default(),
             auto_discover: crate::config::AutoDiscoverConfig::Enabled(true),
             endpoints: HashMap::new(),
             defaults: EndpointDefaults {
                 timeout: None,
                 array_style: None,
                 headers: HashMap::new(),
+                retry: None,
             },
             response_transforms: Vec::new(),
+            xml: Default::default(),
             response_overrides: Vec::new(),
             overrides: crate::config::OpenApiOverridesConfig::default(),
         };
@@ -2941,6 +3928,118 @@ paths:
         assert!(schema.get("properties").and_then(|p| p.get("q")).is_some());
     }
 
+    fn file_backend(spec_path: &std::path::Path, base_url: Option<&str>) -> OpenApiToolSource {
+        let mut cfg = test_backend().config;
+        cfg.spec = spec_path.display().to_string();
+        cfg.base_url = base_url.map(str::to_string);
+        OpenApiToolSource::new(
+            "test".to_string(),
+            cfg,
+            Duration::from_secs(30),
+            Duration::from_secs(30),
+            false,
+            Duration::from_secs(0),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_loads_openapi31_and_swagger20_specs() {
+        let dir = tempdir().unwrap();
+        let v31 = dir.path().join("v31.yaml");
+        fs::write(
+            &v31,
+            r##"
+openapi: 3.1.0
+info: { title: t31, version: "1" }
+servers: [{ url: "https://api.example.com" }]
+components:
+  schemas:
+    Item:
+      type: object
+      required: [name]
+      properties:
+        id: { type: string, readOnly: true }
+        name: { type: [string, "null"], examples: [widget] }
+        kind: { const: widget }
+paths:
+  /items:
+    post:
+      operationId: createItem
+      parameters:
+        - { name: limit, in: query, schema: { type: integer, exclusiveMaximum: 100 } }
+      requestBody:
+        required: true
+        content:
+          application/json:
+            schema: { $ref: "#/components/schemas/Item" }
+      responses:
+        "200": { description: ok }
+"##,
+        )
+        .unwrap();
+        let backend = file_backend(&v31, None);
+        backend.start().await.unwrap();
+        assert_eq!(backend.spec_version(), Some(SpecVersion::OpenApi31));
+        assert_eq!(
+            backend.inferred_base_url().as_deref(),
+            Some("https://api.example.com")
+        );
+        {
+            let tools = backend.tools.read();
+            let tool = tools.iter().find(|t| t.name == "createItem").unwrap();
+            assert_eq!(
+                tool.input_schema["properties"],
+                json!({
+                    "limit": { "type": "integer", "exclusiveMaximum": 100 },
+                    "name": { "type": ["string", "null"], "examples": ["widget"] },
+                    "kind": { "const": "widget" }
+                })
+            );
+            assert_eq!(tool.input_schema["required"], json!(["name"]));
+        }
+
+        let v20 = dir.path().join("v20.json");
+        fs::write(
+            &v20,
+            r##"{
+  "swagger": "2.0",
+  "info": { "title": "t20", "version": "1" },
+  "host": "legacy.example.com",
+  "basePath": "/api",
+  "schemes": ["http"],
+  "paths": {
+    "/pets/{id}": {
+      "get": {
+        "operationId": "getPet",
+        "parameters": [{ "name": "id", "in": "path", "type": "integer", "required": true }],
+        "responses": { "200": { "description": "ok", "schema": { "$ref": "#/definitions/Pet" } } }
+      }
+    }
+  },
+  "definitions": { "Pet": { "type": "object", "properties": { "name": { "type": "string" } } } }
+}"##,
+        )
+        .unwrap();
+        let backend = file_backend(&v20, None);
+        backend.start().await.unwrap();
+        assert_eq!(backend.spec_version(), Some(SpecVersion::Swagger20));
+        assert_eq!(
+            backend.inferred_base_url().as_deref(),
+            Some("http://legacy.example.com/api")
+        );
+        let tools = backend.tools.read();
+        let tool = tools.iter().find(|t| t.name == "getPet").unwrap();
+        assert_eq!(
+            tool.input_schema["properties"]["id"]["type"],
+            json!("integer")
+        );
+        let out = tool.output_schema.as_ref().expect("output_schema");
+        assert_eq!(
+            out["properties"]["body"]["properties"]["name"],
+            json!({ "type": "string" })
+        );
+    }
+
     #[tokio::test]
     async fn test_resolves_nested_external_file_refs_for_request_body_flattening() {
         let dir = tempdir().unwrap();
@@ -3004,14 +4103,17 @@ paths:
             spec_hash_policy: HashPolicy::Ignore,
             base_url: Some("https://example.com".to_string()),
             auth: None,
+            security: SecurityConfig::default(),
             auto_discover: crate::config::AutoDiscoverConfig::Enabled(true),
             endpoints: HashMap::new(),
             defaults: EndpointDefaults {
                 timeout: None,
                 array_style: None,
                 headers: HashMap::new(),
+                retry: None,
             },
             response_transforms: Vec::new(),
+            xml: Default::default(),
             response_overrides: Vec::new(),
             overrides: crate::config::OpenApiOverridesConfig::default(),
         };
@@ -3041,4 +4143,565 @@ paths:
                 .is_some_and(|r| r.iter().any(|v| v == "name"))
         );
     }
+
+    #[allow(clippy::too_many_lines)]
+    #[tokio::test]
+    async fn test_applies_security_scheme_credentials_per_operation() {
+        use axum::Router;
+        use axum::extract::State;
+        use axum::http::{HeaderMap, Uri};
+        use axum::routing::{any, post};
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        async fn token_handler(
+            State(issued): State<Arc<AtomicUsize>>,
+            headers: HeaderMap,
+            body: String,
+        ) -> axum::Json<Value> {
+            let n = issued.fetch_add(1, Ordering::SeqCst);
+            let basic = base64::engine::general_purpose::STANDARD.encode("client:s3cr3t");
+            assert_eq!(headers["authorization"], format!("Basic {basic}").as_str());
+            assert_eq!(body, "grant_type=client_credentials&scope=pets%3Aread");
+            axum::Json(json!({ "access_token": format!("tok-{n}"), "expires_in": 3600 }))
+        }
+
+        async fn echo_handler(uri: Uri, headers: HeaderMap) -> axum::Json<Value> {
+            let header = |name: &str| headers.get(name).and_then(|v| v.to_str().ok());
+            axum::Json(json!({
+                "query": uri.query(),
+                "authorization": header("authorization"),
+                "key": header("x-api-key"),
+                "cookie": header("cookie"),
+            }))
+        }
+
+        let issued = Arc::new(AtomicUsize::new(0));
+        let app = Router::new()
+            .route("/oauth/token", post(token_handler))
+            .route("/{*path}", any(echo_handler))
+            .with_state(issued.clone());
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let server = tokio::spawn(async move { axum::serve(listener, app).await });
+
+        let dir = tempdir().unwrap();
+        let spec_path = dir.path().join("secured.yaml");
+        fs::write(
+            &spec_path,
+            r##"
+openapi: 3.0.3
+info: { title: secured, version: "1" }
+security:
+  - basic: []
+paths:
+  /public:
+    get: { operationId: publicOp, security: [], responses: { "200": { description: ok } } }
+  /keyed:
+    get:
+      operationId: keyedOp
+      security: [{ key: [], session: [], q: [] }]
+      responses: { "200": { description: ok } }
+  /oauth:
+    get:
+      operationId: oauthOp
+      security: [{ oauth: ["pets:read"] }]
+      responses: { "200": { description: ok } }
+  /basic:
+    get: { operationId: basicOp, responses: { "200": { description: ok } } }
+components:
+  securitySchemes:
+    basic: { type: http, scheme: basic }
+    key: { type: apiKey, in: header, name: X-Api-Key }
+    session: { type: apiKey, in: cookie, name: sid }
+    q: { type: apiKey, in: query, name: api_key }
+    oauth:
+      type: oauth2
+      flows:
+        clientCredentials: { tokenUrl: /oauth/token, scopes: { "pets:read": read } }
+"##,
+        )
+        .unwrap();
+
+        let mut cfg = test_backend().config;
+        cfg.spec = spec_path.display().to_string();
+        cfg.base_url = Some(base_url);
+        cfg.security = serde_json::from_value(json!({
+            "unsatisfied": "skip",
+            "credentials": {
+                "key": { "type": "apiKey", "value": "k1" },
+                "session": { "type": "apiKey", "value": "abc" },
+                "q": { "type": "apiKey", "value": "qv" },
+                "oauth": { "type": "oauth2ClientCredentials", "clientId": "client", "clientSecret": "s3cr3t" }
+            }
+        }))
+        .unwrap();
+        let backend = OpenApiToolSource::new(
+            "test".to_string(),
+            cfg,
+            Duration::from_secs(30),
+            Duration::from_secs(30),
+            false,
+            Duration::from_secs(0),
+        );
+        backend.start().await.unwrap();
+
+        let mut names: Vec<String> = backend
+            .list_tools()
+            .into_iter()
+            .map(|t| t.name.into_owned())
+            .collect();
+        names.sort();
+        assert_eq!(names, vec!["keyedOp", "oauthOp", "publicOp"]);
+
+        let call = |name: &'static str| {
+            let backend = backend.clone();
+            async move {
+                let result = backend.call_tool(name, json!({})).await.unwrap();
+                let text = result.content[0].as_text().unwrap().text.clone();
+                serde_json::from_str::<Value>(&text).unwrap()
+            }
+        };
+
+        assert_eq!(
+            call("publicOp").await,
+            json!({ "query": null, "authorization": null, "key": null, "cookie": null })
+        );
+        assert_eq!(
+            call("keyedOp").await,
+            json!({ "query": "api_key=qv", "authorization": null, "key": "k1", "cookie": "sid=abc" })
+        );
+        for _ in 0..2 {
+            assert_eq!(call("oauthOp").await["authorization"], "Bearer tok-0");
+        }
+        assert_eq!(issued.load(Ordering::SeqCst), 1, "token is cached");
+
+        server.abort();
+    }
+
+    #[allow(clippy::too_many_lines)]
+    #[tokio::test]
+    async fn test_encodes_non_json_request_bodies_from_media_types() {
+        use axum::Router;
+        use axum::body::Bytes;
+        use axum::http::HeaderMap;
+        use axum::routing::any;
+
+        async fn echo_handler(headers: HeaderMap, body: Bytes) -> axum::Json<Value> {
+            axum::Json(json!({
+                "contentType": headers.get("content-type").and_then(|v| v.to_str().ok()),
+                "body": String::from_utf8_lossy(&body),
+            }))
+        }
+
+        let app = Router::new().route("/{*path}", any(echo_handler));
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let server = tokio::spawn(async move { axum::serve(listener, app).await });
+
+        let dir = tempdir().unwrap();
+        let spec_path = dir.path().join("bodies.yaml");
+        fs::write(
+            &spec_path,
+            r##"
+openapi: 3.0.3
+info: { title: bodies, version: "1" }
+paths:
+  /form:
+    post:
+      operationId: login
+      requestBody:
+        content:
+          application/x-www-form-urlencoded:
+            schema:
+              type: object
+              properties: { user: { type: string }, scopes: { type: array, items: { type: string } } }
+      responses: { "200": { description: ok } }
+  /upload:
+    post:
+      operationId: upload
+      requestBody:
+        required: true
+        content:
+          multipart/form-data:
+            schema:
+              type: object
+              required: [file]
+              properties:
+                file: { type: string, format: binary, description: The image. }
+                caption: { type: string }
+      responses: { "200": { description: ok } }
+  /raw:
+    put:
+      operationId: putRaw
+      requestBody:
+        content:
+          application/octet-stream: {}
+      responses: { "200": { description: ok } }
+  /note:
+    put:
+      operationId: putNote
+      requestBody:
+        content:
+          text/plain:
+            schema: { type: string }
+      responses: { "200": { description: ok } }
+"##,
+        )
+        .unwrap();
+
+        let backend = file_backend(&spec_path, Some(&base_url));
+        backend.start().await.unwrap();
+
+        {
+            let tools = backend.tools.read();
+            let upload = tools.iter().find(|t| t.name == "upload").unwrap();
+            let file_schema = &upload.input_schema["properties"]["file"];
+            assert!(file_schema["anyOf"].is_array(), "{file_schema}");
+            assert!(
+                file_schema["description"]
+                    .as_str()
+                    .unwrap()
+                    .starts_with("The image. Binary content")
+            );
+            assert_eq!(upload.input_schema["required"], json!(["file"]));
+            let raw = tools.iter().find(|t| t.name == "putRaw").unwrap();
+            assert!(raw.input_schema["properties"]["body"]["anyOf"].is_array());
+        }
+
+        let call = |name: &'static str, args: Value| {
+            let backend = backend.clone();
+            async move {
+                let result = backend.call_tool(name, args).await.unwrap();
+                let text = result.content[0].as_text().unwrap().text.clone();
+                serde_json::from_str::<Value>(&text).unwrap()
+            }
+        };
+
+        assert_eq!(
+            call("login", json!({ "user": "a b", "scopes": ["r", "w"] })).await,
+            json!({
+                "contentType": "application/x-www-form-urlencoded",
+                "body": "scopes=r&scopes=w&user=a+b"
+            })
+        );
+
+        let upload = call(
+            "upload",
+            json!({
+                "file": { "uri": "file:///pets/rex.txt", "mimeType": "text/plain", "text": "woof" },
+                "caption": "Rex"
+            }),
+        )
+        .await;
+        let content_type = upload["contentType"].as_str().unwrap();
+        let boundary = content_type
+            .strip_prefix("multipart/form-data; boundary=")
+            .unwrap();
+        assert_eq!(
+            upload["body"],
+            format!(
+                "--{boundary}\r\nContent-Disposition: form-data; name=\"caption\"\r\n\r\nRex\r\n\
+                 --{boundary}\r\nContent-Disposition: form-data; name=\"file\"; filename=\"rex.txt\"\r\n\
+                 Content-Type: text/plain\r\n\r\nwoof\r\n--{boundary}--\r\n"
+            )
+        );
+
+        assert_eq!(
+            call("putRaw", json!({ "body": "aGVsbG8=" })).await,
+            json!({ "contentType": "application/octet-stream", "body": "hello" })
+        );
+        assert_eq!(
+            call("putNote", json!({ "body": "remember" })).await,
+            json!({ "contentType": "text/plain", "body": "remember" })
+        );
+
+        server.abort();
+    }
+    #[tokio::test]
+    async fn test_sends_and_reads_xml_bodies() {
+        use axum::Router;
+        use axum::body::Bytes;
+        use axum::extract::State;
+        use axum::routing::{get, post};
+
+        type Seen = Arc<parking_lot::Mutex<Vec<String>>>;
+
+        async fn echo_xml(
+            State(seen): State<Seen>,
+            body: Bytes,
+        ) -> impl axum::response::IntoResponse {
+            let text = String::from_utf8_lossy(&body).into_owned();
+            seen.lock().push(text.clone());
+            ([("content-type", "application/xml")], text)
+        }
+
+        async fn legacy() -> impl axum::response::IntoResponse {
+            (
+                [("content-type", "text/xml; charset=utf-8")],
+                r#"<status code="ok">fine<node>a</node><node>b</node></status>"#,
+            )
+        }
+
+        let seen: Seen = Arc::default();
+        let app = Router::new()
+            .route("/pets", post(echo_xml))
+            .route("/legacy", get(legacy))
+            .with_state(seen.clone());
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let server = tokio::spawn(async move { axum::serve(listener, app).await });
+
+        let dir = tempdir().unwrap();
+        let spec_path = dir.path().join("xml.yaml");
+        fs::write(
+            &spec_path,
+            r##"
+openapi: 3.0.3
+info: { title: xml, version: "1" }
+paths:
+  /pets:
+    post:
+      operationId: createPet
+      requestBody:
+        content:
+          application/xml:
+            schema: { $ref: "#/components/schemas/Pet" }
+      responses:
+        "200":
+          description: ok
+          content:
+            application/xml:
+              schema: { $ref: "#/components/schemas/Pet" }
+  /legacy:
+    get:
+      operationId: legacyStatus
+      responses: { "200": { description: ok } }
+components:
+  schemas:
+    Pet:
+      type: object
+      properties:
+        id: { type: integer, xml: { attribute: true } }
+        name: { type: string }
+        tags:
+          type: array
+          xml: { wrapped: true }
+          items: { type: string, xml: { name: tag } }
+"##,
+        )
+        .unwrap();
+
+        let backend = file_backend(&spec_path, Some(&base_url));
+        backend.start().await.unwrap();
+
+        {
+            let tools = backend.tools.read();
+            let create = tools.iter().find(|t| t.name == "createPet").unwrap();
+            // `xml` hints stay out of the advertised schemas.
+            assert_eq!(
+                create.input_schema["properties"]["tags"],
+                json!({ "type": "array", "items": { "type": "string" } })
+            );
+            let out = create.output_schema.as_ref().expect("output_schema");
+            assert_eq!(
+                out["properties"]["body"]["properties"]["id"],
+                json!({ "type": "integer" })
+            );
+        }
+
+        let result = backend
+            .call_tool(
+                "createPet",
+                json!({ "id": 7, "name": "Rex & co", "tags": ["good", "dog"] }),
+            )
+            .await
+            .unwrap();
+        assert_eq!(
+            seen.lock().as_slice(),
+            [
+                r#"<?xml version="1.0" encoding="UTF-8"?><Pet id="7"><name>Rex &amp; co</name><tags><tag>good</tag><tag>dog</tag></tags></Pet>"#
+            ]
+        );
+        assert_eq!(
+            result.structured_content,
+            Some(json!({ "body": { "id": 7, "name": "Rex & co", "tags": ["good", "dog"] } }))
+        );
+
+        let result = backend.call_tool("legacyStatus", json!({})).await.unwrap();
+        let text = result.content[0].as_text().unwrap().text.clone();
+        assert_eq!(
+            serde_json::from_str::<Value>(&text).unwrap(),
+            json!({ "status": { "@code": "ok", "#text": "fine", "node": ["a", "b"] } })
+        );
+
+        server.abort();
+    }
+
+    #[tokio::test]
+    async fn test_override_follows_offset_pages() {
+        use axum::Router;
+        use axum::extract::Query;
+        use axum::routing::get;
+
+        async fn items(Query(query): Query<HashMap<String, usize>>) -> axum::Json<Value> {
+            let offset = query.get("offset").copied().unwrap_or(0);
+            let limit = query.get("limit").copied().unwrap_or(2);
+            let all = ["a", "b", "c", "d", "e"];
+            let page = &all[offset.min(all.len())..(offset + limit).min(all.len())];
+            axum::Json(json!({ "items": page, "total": all.len() }))
+        }
+
+        let app = Router::new().route("/items", get(items));
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let server = tokio::spawn(async move { axum::serve(listener, app).await });
+
+        let dir = tempdir().unwrap();
+        let spec_path = dir.path().join("items.yaml");
+        fs::write(
+            &spec_path,
+            r#"
+openapi: 3.0.3
+info: { title: items, version: "1" }
+paths:
+  /items:
+    get:
+      operationId: listItems
+      responses: { "200": { description: ok } }
+"#,
+        )
+        .unwrap();
+
+        let mut backend = file_backend(&spec_path, Some(&base_url));
+        backend.config.overrides = serde_json::from_value(json!({
+            "tools": {
+                "listAllItems": {
+                    "match": { "operationId": "listItems" },
+                    "request": {
+                        "method": "GET",
+                        "path": "/items",
+                        "params": {
+                            "offset": { "in": "query", "schema": { "type": "integer" } },
+                            "limit": { "in": "query", "schema": { "type": "integer" } }
+                        }
+                    },
+                    "pagination": {
+                        "type": "offset",
+                        "param": "offset",
+                        "limitParam": "limit",
+                        "itemsPointer": "/items"
+                    }
+                }
+            }
+        }))
+        .unwrap();
+        backend.start().await.unwrap();
+
+        let result = backend
+            .call_tool("listAllItems", json!({ "limit": 2 }))
+            .await
+            .unwrap();
+        let text = result.content[0].as_text().unwrap().text.clone();
+        assert_eq!(
+            serde_json::from_str::<Value>(&text).unwrap(),
+            json!({ "items": ["a", "b", "c", "d", "e"], "total": 5 })
+        );
+        assert_eq!(result.content.len(), 1);
+
+        server.abort();
+    }
+
+    #[tokio::test]
+    async fn test_override_polls_accepted_operations() {
+        use axum::Router;
+        use axum::extract::State;
+        use axum::http::StatusCode;
+        use axum::routing::{get, post};
+        use std::sync::Mutex;
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use unrelated_http_tools::context::ProgressUpdate;
+
+        async fn start() -> (StatusCode, axum::Json<Value>) {
+            (
+                StatusCode::ACCEPTED,
+                axum::Json(json!({ "statusUrl": "/exports/1" })),
+            )
+        }
+        async fn status(State(polls): State<Arc<AtomicUsize>>) -> axum::Json<Value> {
+            match polls.fetch_add(1, Ordering::SeqCst) {
+                0 => axum::Json(json!({ "state": "running", "percent": 50 })),
+                _ => axum::Json(json!({ "state": "done", "result": "/exports/1/file" })),
+            }
+        }
+        async fn file() -> axum::Json<Value> {
+            axum::Json(json!({ "rows": 3 }))
+        }
+
+        let app = Router::new()
+            .route("/exports", post(start))
+            .route("/exports/1", get(status))
+            .route("/exports/1/file", get(file))
+            .with_state(Arc::new(AtomicUsize::new(0)));
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let server = tokio::spawn(async move { axum::serve(listener, app).await });
+
+        let dir = tempdir().unwrap();
+        let spec_path = dir.path().join("exports.yaml");
+        fs::write(
+            &spec_path,
+            r#"
+openapi: 3.0.3
+info: { title: exports, version: "1" }
+paths:
+  /exports:
+    post:
+      operationId: startExport
+      responses: { "202": { description: accepted } }
+"#,
+        )
+        .unwrap();
+
+        let mut backend = file_backend(&spec_path, Some(&base_url));
+        backend.config.overrides = serde_json::from_value(json!({
+            "tools": {
+                "export": {
+                    "match": { "operationId": "startExport" },
+                    "request": { "method": "POST", "path": "/exports" },
+                    "asyncOperation": {
+                        "statusUrl": { "pointer": "/statusUrl" },
+                        "statePointer": "/state",
+                        "successStates": ["done"],
+                        "failureStates": ["failed"],
+                        "resultUrl": { "pointer": "/result" },
+                        "progressPointer": "/percent",
+                        "progressTotal": 100,
+                        "pollIntervalMs": 10
+                    }
+                }
+            }
+        }))
+        .unwrap();
+        backend.start().await.unwrap();
+
+        let updates = Arc::new(Mutex::new(Vec::<ProgressUpdate>::new()));
+        let sink = updates.clone();
+        let ctx = CallContext::with_timeout(Duration::from_secs(10))
+            .with_progress(Arc::new(move |u| sink.lock().unwrap().push(u)));
+        let result = backend
+            .call_tool_with_context("export", json!({}), &ctx)
+            .await
+            .unwrap();
+        let text = result.content[0].as_text().unwrap().text.clone();
+        assert_eq!(
+            serde_json::from_str::<Value>(&text).unwrap(),
+            json!({ "rows": 3 })
+        );
+        let updates = updates.lock().unwrap();
+        assert_eq!(updates.len(), 1);
+        assert_eq!(updates[0].progress, 50.0);
+        assert_eq!(updates[0].total, Some(100.0));
+
+        server.abort();
+    }
 }
@@ -0,0 +1,593 @@
+//! `OpenAPI` Schema Object → JSON Schema conversion.
+//!
+//! Tool input/output schemas must be self-contained JSON Schema: clients and the gateway's argument
+//! validation never see the spec. The converter therefore:
+//! - dereferences `$ref`s through [`OpenApiResolver`] (relative to the document containing them)
+//!   and inlines them; a ref that is already being expanded is replaced by a shallow stub instead
+//!   of recursing again, and a schema that expands to more than [`MAX_SCHEMA_NODES`] nodes (e.g.
+//!   refs that fan out to shared refs at every level) is rejected
+//! - keeps composition (`allOf`/`oneOf`/`anyOf`/`not`) and validation keywords (`format`, bounds,
+//!   `pattern`, lengths, `additionalProperties`, `default`, ...)
+//! - maps `OpenAPI` 3.0 dialect keywords: `nullable` → `"null"` type, boolean
+//!   `exclusiveMinimum`/`exclusiveMaximum` → numeric form, `example` → `examples`
+//! - pins the discriminator property (`const`) in each `oneOf`/`anyOf` branch of a `discriminator`
+//! - drops `readOnly` properties from input schemas and `writeOnly` properties from output schemas
+//! - drops `discriminator`, `xml`, `externalDocs` and `x-*` extensions (`xml` is kept by
+//!   [`SchemaConverter::with_xml_hints`], for reading and writing XML bodies)
+
+use crate::error::{OpenApiToolsError, Result};
+use crate::resolver::{DocId, OpenApiResolver};
+use openapiv3::{ReferenceOr, Schema};
+use serde_json::{Map, Value, json};
+use std::future::Future;
+use std::pin::Pin;
+
+/// Maximum number of nested `$ref` expansions before falling back to a stub.
+const MAX_REF_DEPTH: usize = 32;
+
+/// Maximum number of schema objects one conversion may produce once refs are inlined.
+pub const MAX_SCHEMA_NODES: usize = 10_000;
+
+/// Keywords whose value is a single subschema.
+const SUBSCHEMA_KEYWORDS: &[&str] = &[
+    "items",
+    "additionalItems",
+    "additionalProperties",
+    "unevaluatedItems",
+    "unevaluatedProperties",
+    "not",
+    "contains",
+    "propertyNames",
+    "if",
+    "then",
+    "else",
+];
+
+/// Keywords whose value is an array of subschemas.
+const SUBSCHEMA_ARRAY_KEYWORDS: &[&str] = &["allOf", "anyOf", "oneOf", "prefixItems"];
+
+/// Keywords whose value is a map of subschemas (other than `properties`).
+const SUBSCHEMA_MAP_KEYWORDS: &[&str] = &["patternProperties", "dependentSchemas", "$defs"];
+
+/// `OpenAPI`-only keywords that have no JSON Schema meaning (or are translated separately).
+const DROPPED_KEYWORDS: &[&str] = &[
+    "nullable",
+    "discriminator",
+    "xml",
+    "externalDocs",
+    "example",
+    "exclusiveMinimum",
+    "exclusiveMaximum",
+];
+
+/// Which side of a tool call a schema describes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SchemaDirection {
+    /// Tool arguments (request side): `readOnly` properties are dropped.
+    Input,
+    /// Tool results (response side): `writeOnly` properties are dropped.
+    Output,
+}
+
+type ConvertFuture<'a> = Pin<Box<dyn Future<Output = Value> + Send + 'a>>;
+
+/// Converts `OpenAPI` schemas of one document set into JSON Schema.
+pub struct SchemaConverter<'r, 'c> {
+    resolver: &'r OpenApiResolver<'c>,
+    direction: SchemaDirection,
+    /// Keep `OpenAPI` `xml` objects.
+    xml_hints: bool,
+    /// Canonical keys of the refs currently being expanded (cycle detection).
+    stack: Vec<String>,
+    /// Schema objects converted so far (bounded by [`MAX_SCHEMA_NODES`]).
+    nodes: usize,
+}
+
+impl<'r, 'c> SchemaConverter<'r, 'c> {
+    #[must_use]
+    pub fn new(resolver: &'r OpenApiResolver<'c>, direction: SchemaDirection) -> Self {
+        Self {
+            resolver,
+            direction,
+            xml_hints: false,
+            stack: Vec::new(),
+            nodes: 0,
+        }
+    }
+
+    /// Keep `xml` objects (`name`, `attribute`, `wrapped`, ...) in converted schemas.
+    #[must_use]
+    pub fn with_xml_hints(mut self) -> Self {
+        self.xml_hints = true;
+        self
+    }
+
+    /// Convert a (possibly referenced) schema found in `doc`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the inlined schema exceeds [`MAX_SCHEMA_NODES`].
+    pub async fn convert_schema_ref(
+        &mut self,
+        doc: &DocId,
+        schema: &ReferenceOr<Schema>,
+    ) -> Result<Value> {
+        let raw = serde_json::to_value(schema).unwrap_or_else(|_| json!({}));
+        self.convert_checked(doc, &raw).await
+    }
+
+    /// Convert a schema found in `doc`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the inlined schema exceeds [`MAX_SCHEMA_NODES`].
+    pub async fn convert_schema(&mut self, doc: &DocId, schema: &Schema) -> Result<Value> {
+        let raw = serde_json::to_value(schema).unwrap_or_else(|_| json!({}));
+        self.convert_checked(doc, &raw).await
+    }
+
+    /// Convert a raw schema value and fail if it exceeded the node budget.
+    async fn convert_checked(&mut self, doc: &DocId, schema: &Value) -> Result<Value> {
+        let out = self.convert(doc, schema).await;
+        if self.budget_exceeded() {
+            return Err(OpenApiToolsError::OpenApi(format!(
+                "schema expands to more than {MAX_SCHEMA_NODES} nodes once $refs are inlined"
+            )));
+        }
+        Ok(out)
+    }
+
+    /// Whether a conversion ran out of its node budget (its output is then incomplete).
+    fn budget_exceeded(&self) -> bool {
+        self.nodes > MAX_SCHEMA_NODES
+    }
+
+    /// Whether a converted property schema is excluded in this direction (`readOnly` for input,
+    /// `writeOnly` for output).
+    #[must_use]
+    pub fn excludes(&self, converted: &Value) -> bool {
+        let flag = match self.direction {
+            SchemaDirection::Input => "readOnly",
+            SchemaDirection::Output => "writeOnly",
+        };
+        converted.get(flag).and_then(Value::as_bool) == Some(true)
+    }
+
+    /// Convert a raw `OpenAPI` schema value found in `doc`.
+    ///
+    /// Past [`MAX_SCHEMA_NODES`] the remaining subschemas are not expanded; the public entry
+    /// points report that as an error.
+    pub fn convert<'a>(&'a mut self, doc: &'a DocId, schema: &'a Value) -> ConvertFuture<'a> {
+        Box::pin(async move {
+            let Some(obj) = schema.as_object() else {
+                // Boolean schemas pass through.
+                return schema.clone();
+            };
+            self.nodes += 1;
+            if self.budget_exceeded() {
+                return json!({});
+            }
+            if let Some(reference) = obj.get("$ref").and_then(Value::as_str) {
+                // Siblings of `$ref` are ignored in OpenAPI 3.0.
+                return self.convert_ref(doc, reference).await;
+            }
+
+            let mut out = Map::new();
+            let mut dropped: Vec<&str> = Vec::new();
+            for (key, value) in obj {
+                let key_str = key.as_str();
+                if key_str == "properties" {
+                    let mut props = Map::new();
+                    for (name, prop) in value.as_object().into_iter().flatten() {
+                        let converted = self.convert(doc, prop).await;
+                        if self.excludes(&converted) {
+                            dropped.push(name);
+                        } else {
+                            props.insert(name.clone(), converted);
+                        }
+                    }
+                    out.insert(key.clone(), Value::Object(props));
+                } else if SUBSCHEMA_KEYWORDS.contains(&key_str) {
+                    let converted = if let Value::Array(items) = value {
+                        // Draft-4 tuple `items`.
+                        let mut list = Vec::with_capacity(items.len());
+                        for item in items {
+                            list.push(self.convert(doc, item).await);
+                        }
+                        Value::Array(list)
+                    } else {
+                        self.convert(doc, value).await
+                    };
+                    out.insert(key.clone(), converted);
+                } else if SUBSCHEMA_ARRAY_KEYWORDS.contains(&key_str) {
+                    let mut list = Vec::new();
+                    for item in value.as_array().into_iter().flatten() {
+                        list.push(self.convert(doc, item).await);
+                    }
+                    out.insert(key.clone(), Value::Array(list));
+                } else if SUBSCHEMA_MAP_KEYWORDS.contains(&key_str) {
+                    let mut map = Map::new();
+                    for (name, item) in value.as_object().into_iter().flatten() {
+                        map.insert(name.clone(), self.convert(doc, item).await);
+                    }
+                    out.insert(key.clone(), Value::Object(map));
+                } else if (!DROPPED_KEYWORDS.contains(&key_str) && !key_str.starts_with("x-"))
+                    || (self.xml_hints && key_str == "xml")
+                {
+                    out.insert(key.clone(), value.clone());
+                }
+            }
+
+            if !dropped.is_empty()
+                && let Some(required) = out.get_mut("required").and_then(Value::as_array_mut)
+            {
+                required.retain(|r| r.as_str().is_none_or(|r| !dropped.contains(&r)));
+            }
+
+            if let Some(example) = obj.get("example") {
+                out.entry("examples")
+                    .or_insert_with(|| Value::Array(vec![example.clone()]));
+            }
+
+            for (flag, bound) in [
+                ("exclusiveMinimum", "minimum"),
+                ("exclusiveMaximum", "maximum"),
+            ] {
+                match obj.get(flag) {
+                    Some(Value::Bool(true)) => {
+                        if let Some(b) = out.remove(bound) {
+                            out.insert(flag.to_string(), b);
+                        }
+                    }
+                    Some(n @ Value::Number(_)) => {
+                        out.insert(flag.to_string(), n.clone());
+                    }
+                    _ => {}
+                }
+            }
+
+            if let Some(discriminator) = obj.get("discriminator") {
+                pin_discriminator(obj, discriminator, &mut out);
+            }
+
+            if obj.get("nullable").and_then(Value::as_bool) == Some(true) {
+                return make_nullable(out);
+            }
+            Value::Object(out)
+        })
+    }
+
+    async fn convert_ref(&mut self, doc: &DocId, reference: &str) -> Value {
+        let (target_doc, target, key) = match self.resolver.resolve_ref(doc, reference).await {
+            Ok(resolved) => resolved,
+            Err(e) => {
+                tracing::warn!(
+                    reference = %reference,
+                    error = %e,
+                    "failed to resolve schema $ref; using an unconstrained schema"
+                );
+                return json!({});
+            }
+        };
+
+        if self.stack.contains(&key) || self.stack.len() >= MAX_REF_DEPTH {
+            return recursive_stub(reference, &target);
+        }
+
+        self.stack.push(key);
+        let out = self.convert(&target_doc, &target).await;
+        self.stack.pop();
+        out
+    }
+}
+
+/// Last pointer segment of a `$ref` (the component name for `#/components/schemas/<name>`).
+fn ref_name(reference: &str) -> &str {
+    reference.rsplit('/').next().unwrap_or(reference)
+}
+
+/// Shallow stand-in for a ref that is already being expanded.
+///
+/// Keeps the target's `type` (when unambiguous) and read/write annotations so the property is
+/// still described and filtered correctly, but does not constrain its contents.
+fn recursive_stub(reference: &str, target: &Value) -> Value {
+    let mut out = Map::new();
+    let nullable = target.get("nullable").and_then(Value::as_bool) == Some(true);
+    if !nullable && let Some(t) = target.get("type").filter(|t| t.is_string()) {
+        out.insert("type".to_string(), t.clone());
+    }
+    for key in ["title", "readOnly", "writeOnly"] {
+        if let Some(v) = target.get(key) {
+            out.insert(key.to_string(), v.clone());
+        }
+    }
+    out.insert(
+        "description".to_string(),
+        Value::String(format!(
+            "Recursive reference to '{}' (not expanded).",
+            ref_name(reference)
+        )),
+    );
+    Value::Object(out)
+}
+
+/// Translate `discriminator` into a `const` on the discriminator property of every referenced
+/// `oneOf`/`anyOf` branch.
+///
+/// Branch values come from `discriminator.mapping` (by ref or component name) and default to the
+/// component name. Inline branches are left unchanged.
+fn pin_discriminator(
+    raw: &Map<String, Value>,
+    discriminator: &Value,
+    out: &mut Map<String, Value>,
+) {
+    let Some(prop) = discriminator.get("propertyName").and_then(Value::as_str) else {
+        return;
+    };
+    let mapping = discriminator.get("mapping").and_then(Value::as_object);
+
+    for keyword in ["oneOf", "anyOf"] {
+        let (Some(raw_branches), Some(branches)) = (
+            raw.get(keyword).and_then(Value::as_array),
+            out.get_mut(keyword).and_then(Value::as_array_mut),
+        ) else {
+            continue;
+        };
+        for (raw_branch, branch) in raw_branches.iter().zip(branches.iter_mut()) {
+            let Some(reference) = raw_branch.get("$ref").and_then(Value::as_str) else {
+                continue;
+            };
+            let name = ref_name(reference);
+            let value = mapping
+                .and_then(|m| {
+                    m.iter().find_map(|(value, target)| {
+                        let target = target.as_str()?;
+                        (target == reference || ref_name(target) == name).then_some(value)
+                    })
+                })
+                .map_or(name, String::as_str);
+            pin_property(branch, prop, value);
+        }
+    }
+}
+
+fn pin_property(branch: &mut Value, prop: &str, value: &str) {
+    let Some(obj) = branch
+        .as_object_mut()
+        .filter(|o| o.get("properties").is_some_and(Value::is_object))
+    else {
+        let base = branch.take();
+        *branch = json!({
+            "allOf": [
+                base,
+                { "type": "object", "properties": { prop: { "const": value } }, "required": [prop] }
+            ]
+        });
+        return;
+    };
+
+    if let Some(props) = obj.get_mut("properties").and_then(Value::as_object_mut) {
+        match props.get_mut(prop).and_then(Value::as_object_mut) {
+            Some(p) => {
+                p.remove("enum");
+                p.insert("const".to_string(), Value::String(value.to_string()));
+            }
+            None => {
+                props.insert(prop.to_string(), json!({ "const": value }));
+            }
+        }
+    }
+    let required = obj
+        .entry("required".to_string())
+        .or_insert_with(|| Value::Array(Vec::new()));
+    if let Some(required) = required.as_array_mut()
+        && !required.iter().any(|r| r.as_str() == Some(prop))
+    {
+        required.push(Value::String(prop.to_string()));
+    }
+}
+
+/// Apply `nullable: true` to a converted schema.
+fn make_nullable(mut out: Map<String, Value>) -> Value {
+    if let Some(values) = out.get_mut("enum").and_then(Value::as_array_mut)
+        && !values.contains(&Value::Null)
+    {
+        values.push(Value::Null);
+    }
+    let has_enum = out.contains_key("enum");
+    match out.get_mut("type") {
+        Some(Value::String(t)) => {
+            let t = std::mem::take(t);
+            out.insert("type".to_string(), json!([t, "null"]));
+        }
+        Some(Value::Array(types)) => {
+            if !types.iter().any(|t| t.as_str() == Some("null")) {
+                types.push(json!("null"));
+            }
+        }
+        _ if has_enum => {}
+        _ => {
+            // Untyped (e.g. composition): allow null next to the schema, keeping annotations on
+            // the outer schema so they stay visible (and `readOnly`/`writeOnly` filtering works).
+            let mut wrapper = Map::new();
+            for key in [
+                "title",
+                "description",
+                "default",
+                "examples",
+                "deprecated",
+                "readOnly",
+                "writeOnly",
+            ] {
+                if let Some(v) = out.remove(key) {
+                    wrapper.insert(key.to_string(), v);
+                }
+            }
+            wrapper.insert(
+                "anyOf".to_string(),
+                json!([Value::Object(out), { "type": "null" }]),
+            );
+            return Value::Object(wrapper);
+        }
+    }
+    Value::Object(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use openapiv3::OpenAPI;
+
+    const SPEC: &str = r##"
+openapi: "3.0.3"
+info: { title: t, version: "1" }
+paths: {}
+components:
+  schemas:
+    Pet:
+      oneOf:
+        - $ref: "#/components/schemas/Cat"
+        - $ref: "#/components/schemas/Dog"
+      discriminator:
+        propertyName: kind
+        mapping:
+          kitty: "#/components/schemas/Cat"
+    Cat:
+      type: object
+      required: [kind, id, name]
+      properties:
+        kind: { type: string }
+        id: { type: string, format: uuid, readOnly: true }
+        name: { type: string, minLength: 1, pattern: "^[A-Z]", example: Tom }
+        password: { type: string, writeOnly: true }
+        age: { type: integer, minimum: 0, exclusiveMinimum: true, nullable: true }
+        owner: { $ref: "#/components/schemas/Person" }
+      additionalProperties: false
+      x-internal: true
+    Dog:
+      allOf:
+        - $ref: "#/components/schemas/Base"
+      nullable: true
+      description: A dog
+    Base:
+      type: object
+      properties:
+        tags: { type: array, items: { type: string, enum: [a, b] }, maxItems: 3 }
+    Person:
+      type: object
+      properties:
+        name: { type: string }
+        friend: { $ref: "#/components/schemas/Person" }
+"##;
+
+    async fn convert(direction: SchemaDirection) -> Value {
+        let spec: OpenAPI = serde_yaml::from_str(SPEC).expect("spec");
+        let client = reqwest::Client::new();
+        let doc = DocId::parse("/nonexistent/spec.yaml").expect("doc id");
+        let resolver = OpenApiResolver::new(doc.clone(), &spec, &client).expect("resolver");
+        let mut converter = SchemaConverter::new(&resolver, direction);
+        converter
+            .convert(&doc, &json!({ "$ref": "#/components/schemas/Pet" }))
+            .await
+    }
+
+    #[tokio::test]
+    async fn converts_composition_refs_and_openapi_keywords_for_input() {
+        let out = convert(SchemaDirection::Input).await;
+        assert!(!out.to_string().contains("$ref"), "{out}");
+        assert!(out.get("discriminator").is_none());
+
+        let cat = &out["oneOf"][0];
+        assert_eq!(cat["additionalProperties"], json!(false));
+        assert_eq!(
+            cat["properties"]["kind"],
+            json!({ "type": "string", "const": "kitty" })
+        );
+        assert_eq!(cat["required"], json!(["kind", "name"]));
+        assert!(cat["properties"].get("id").is_none());
+        assert!(cat.get("x-internal").is_none());
+        assert_eq!(cat["properties"]["password"]["writeOnly"], json!(true));
+        assert_eq!(
+            cat["properties"]["name"],
+            json!({ "type": "string", "minLength": 1, "pattern": "^[A-Z]", "examples": ["Tom"] })
+        );
+        assert_eq!(
+            cat["properties"]["age"],
+            json!({ "type": ["integer", "null"], "exclusiveMinimum": 0 })
+        );
+        let person = &cat["properties"]["owner"];
+        assert_eq!(person["properties"]["name"], json!({ "type": "string" }));
+        assert_eq!(
+            person["properties"]["friend"],
+            json!({
+                "type": "object",
+                "description": "Recursive reference to 'Person' (not expanded)."
+            })
+        );
+
+        // Untyped nullable branch: wrapped in `anyOf`, then pinned via `allOf`.
+        let dog = &out["oneOf"][1]["allOf"];
+        assert_eq!(dog[1]["properties"]["kind"]["const"], json!("Dog"));
+        assert_eq!(dog[1]["required"], json!(["kind"]));
+        assert_eq!(dog[0]["description"], json!("A dog"));
+        assert_eq!(dog[0]["anyOf"][1], json!({ "type": "null" }));
+        assert_eq!(
+            dog[0]["anyOf"][0]["allOf"][0]["properties"]["tags"],
+            json!({ "type": "array", "items": { "type": "string", "enum": ["a", "b"] }, "maxItems": 3 })
+        );
+    }
+
+    #[tokio::test]
+    async fn drops_write_only_properties_for_output() {
+        let out = convert(SchemaDirection::Output).await;
+        let cat = &out["oneOf"][0];
+        assert!(cat["properties"].get("password").is_none());
+        assert_eq!(
+            cat["properties"]["id"],
+            json!({ "type": "string", "format": "uuid", "readOnly": true })
+        );
+        assert_eq!(cat["required"], json!(["kind", "id", "name"]));
+    }
+
+    #[tokio::test]
+    async fn rejects_refs_that_fan_out_past_the_node_budget() {
+        // Every level references the next one four times: ~4^16 nodes once inlined.
+        let mut schemas = Map::new();
+        for level in 0..16 {
+            let next = json!({ "$ref": format!("#/components/schemas/L{}", level + 1) });
+            schemas.insert(
+                format!("L{level}"),
+                json!({
+                    "type": "object",
+                    "properties": { "a": next, "b": next, "c": next, "d": next },
+                }),
+            );
+        }
+        schemas.insert("L16".to_string(), json!({ "type": "string" }));
+        let spec: OpenAPI = serde_json::from_value(json!({
+            "openapi": "3.0.3",
+            "info": { "title": "t", "version": "1" },
+            "paths": {},
+            "components": { "schemas": schemas },
+        }))
+        .expect("spec");
+        let client = reqwest::Client::new();
+        let doc = DocId::parse("/nonexistent/spec.yaml").expect("doc id");
+        let resolver = OpenApiResolver::new(doc.clone(), &spec, &client).expect("resolver");
+
+        let err = SchemaConverter::new(&resolver, SchemaDirection::Input)
+            .convert_schema_ref(&doc, &ReferenceOr::ref_("#/components/schemas/L0"))
+            .await
+            .expect_err("budget exceeded");
+        assert!(err.to_string().contains("more than"), "{err}");
+
+        let small = SchemaConverter::new(&resolver, SchemaDirection::Input)
+            .convert_schema_ref(&doc, &ReferenceOr::ref_("#/components/schemas/L14"))
+            .await
+            .expect("small schema");
+        assert_eq!(
+            small["properties"]["a"]["properties"]["d"],
+            json!({ "type": "string" })
+        );
+    }
+}
@@ -0,0 +1,382 @@
+//! `OpenAPI` security requirements → configured credentials.
+//!
+//! An operation's `security` (or the spec-level default when absent) lists alternative
+//! requirement objects; any one of them authorizes the request. Each requirement names schemes
+//! from `components.securitySchemes`, and every named scheme must be satisfied. Schemes are
+//! matched to [`SecurityCredential`]s by name:
+//! - `apiKey` (header/query/cookie) ← `apiKey`
+//! - `http` `bearer` ← `bearer`; `http` `basic` ← `basic`
+//! - `oauth2` with a `clientCredentials` flow ← `oauth2ClientCredentials`; `oauth2` and
+//!   `openIdConnect` also accept a static `bearer` token
+//!
+//! The first alternative whose schemes are all satisfied wins. An empty alternative (`{}`) makes
+//! the operation callable anonymously; it is only used when no other alternative is satisfied.
+
+use crate::config::SecurityCredential;
+use openapiv3::{APIKeyLocation, OpenAPI, Operation, ReferenceOr, SecurityScheme};
+use std::collections::HashMap;
+
+const SCHEME_REF_PREFIX: &str = "#/components/securitySchemes/";
+
+/// A credential bound to the request location required by its scheme.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AppliedCredential {
+    Header {
+        name: String,
+        value: String,
+    },
+    Query {
+        name: String,
+        value: String,
+    },
+    Cookie {
+        name: String,
+        value: String,
+    },
+    Bearer {
+        token: String,
+    },
+    Basic {
+        username: String,
+        password: String,
+    },
+    /// Bearer token obtained with the `OAuth2` client credentials grant (at call time).
+    OAuth2ClientCredentials(ClientCredentialsGrant),
+}
+
+/// Parameters of an `OAuth2` client credentials token request.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ClientCredentialsGrant {
+    /// Token endpoint (may be relative to the API base URL).
+    pub token_url: String,
+    pub client_id: String,
+    pub client_secret: String,
+    pub scopes: Vec<String>,
+}
+
+/// Outcome of matching an operation's requirements against configured credentials.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SecurityResolution {
+    /// The operation declares no requirements, or allows anonymous access.
+    Anonymous,
+    /// Credentials to send with every call of the operation.
+    Satisfied(Vec<AppliedCredential>),
+    /// No alternative could be satisfied; one reason per unsatisfied scheme.
+    Unsatisfied(Vec<String>),
+}
+
+/// Match the effective security requirements of `operation` against `credentials`.
+#[must_use]
+pub fn resolve_operation_security(
+    spec: &OpenAPI,
+    operation: &Operation,
+    credentials: &HashMap<String, SecurityCredential>,
+) -> SecurityResolution {
+    let requirements = operation
+        .security
+        .as_deref()
+        .or(spec.security.as_deref())
+        .unwrap_or_default();
+    if requirements.is_empty() {
+        return SecurityResolution::Anonymous;
+    }
+
+    let mut anonymous = false;
+    let mut reasons: Vec<String> = Vec::new();
+    for requirement in requirements {
+        if requirement.is_empty() {
+            anonymous = true;
+            continue;
+        }
+        let mut applied = Vec::new();
+        let mut satisfied = true;
+        for (scheme_name, scopes) in requirement {
+            match apply_scheme(spec, scheme_name, scopes, credentials) {
+                Ok(credential) => applied.push(credential),
+                Err(reason) => {
+                    satisfied = false;
+                    if !reasons.contains(&reason) {
+                        reasons.push(reason);
+                    }
+                }
+            }
+        }
+        if satisfied {
+            return SecurityResolution::Satisfied(applied);
+        }
+    }
+
+    if anonymous {
+        SecurityResolution::Anonymous
+    } else {
+        SecurityResolution::Unsatisfied(reasons)
+    }
+}
+
+/// Names of configured credentials that do not match any declared security scheme.
+#[must_use]
+pub fn unknown_credential_names(
+    spec: &OpenAPI,
+    credentials: &HashMap<String, SecurityCredential>,
+) -> Vec<String> {
+    let mut unknown: Vec<String> = credentials
+        .keys()
+        .filter(|name| lookup_scheme(spec, name).is_none())
+        .cloned()
+        .collect();
+    unknown.sort();
+    unknown
+}
+
+/// Look up a scheme in `components.securitySchemes`, following local `$ref`s.
+fn lookup_scheme<'a>(spec: &'a OpenAPI, name: &str) -> Option<&'a SecurityScheme> {
+    let schemes = &spec.components.as_ref()?.security_schemes;
+    let mut current = schemes.get(name)?;
+    // Bounded to guard against ref cycles.
+    for _ in 0..=schemes.len() {
+        match current {
+            ReferenceOr::Item(scheme) => return Some(scheme),
+            ReferenceOr::Reference { reference } => {
+                current = schemes.get(reference.strip_prefix(SCHEME_REF_PREFIX)?)?;
+            }
+        }
+    }
+    None
+}
+
+fn apply_scheme(
+    spec: &OpenAPI,
+    scheme_name: &str,
+    scopes: &[String],
+    credentials: &HashMap<String, SecurityCredential>,
+) -> Result<AppliedCredential, String> {
+    let scheme = lookup_scheme(spec, scheme_name)
+        .ok_or_else(|| format!("scheme '{scheme_name}' is not declared"))?;
+    let credential = credentials
+        .get(scheme_name)
+        .ok_or_else(|| format!("no credential configured for scheme '{scheme_name}'"))?;
+
+    match (scheme, credential) {
+        (SecurityScheme::APIKey { location, name, .. }, SecurityCredential::ApiKey { value }) => {
+            let (name, value) = (name.clone(), value.clone());
+            Ok(match location {
+                APIKeyLocation::Header => AppliedCredential::Header { name, value },
+                APIKeyLocation::Query => AppliedCredential::Query { name, value },
+                APIKeyLocation::Cookie => AppliedCredential::Cookie { name, value },
+            })
+        }
+        (SecurityScheme::HTTP { scheme, .. }, SecurityCredential::Bearer { token })
+            if scheme.eq_ignore_ascii_case("bearer") =>
+        {
+            Ok(AppliedCredential::Bearer {
+                token: token.clone(),
+            })
+        }
+        (SecurityScheme::HTTP { scheme, .. }, SecurityCredential::Basic { username, password })
+            if scheme.eq_ignore_ascii_case("basic") =>
+        {
+            Ok(AppliedCredential::Basic {
+                username: username.clone(),
+                password: password.clone(),
+            })
+        }
+        (
+            SecurityScheme::OAuth2 { .. } | SecurityScheme::OpenIDConnect { .. },
+            SecurityCredential::Bearer { token },
+        ) => Ok(AppliedCredential::Bearer {
+            token: token.clone(),
+        }),
+        (
+            SecurityScheme::OAuth2 { flows, .. },
+            SecurityCredential::Oauth2ClientCredentials {
+                client_id,
+                client_secret,
+                scopes: configured_scopes,
+                token_url,
+            },
+        ) => {
+            let token_url = token_url
+                .clone()
+                .or_else(|| {
+                    flows
+                        .client_credentials
+                        .as_ref()
+                        .map(|f| f.token_url.clone())
+                })
+                .ok_or_else(|| {
+                    format!(
+                        "scheme '{scheme_name}' has no clientCredentials flow and no tokenUrl is configured"
+                    )
+                })?;
+            let scopes = if configured_scopes.is_empty() {
+                scopes.to_vec()
+            } else {
+                configured_scopes.clone()
+            };
+            Ok(AppliedCredential::OAuth2ClientCredentials(
+                ClientCredentialsGrant {
+                    token_url,
+                    client_id: client_id.clone(),
+                    client_secret: client_secret.clone(),
+                    scopes,
+                },
+            ))
+        }
+        (scheme, credential) => Err(format!(
+            "credential type '{}' does not fit {} scheme '{scheme_name}'",
+            credential_kind(credential),
+            scheme_kind(scheme)
+        )),
+    }
+}
+
+fn credential_kind(credential: &SecurityCredential) -> &'static str {
+    match credential {
+        SecurityCredential::ApiKey { .. } => "apiKey",
+        SecurityCredential::Bearer { .. } => "bearer",
+        SecurityCredential::Basic { .. } => "basic",
+        SecurityCredential::Oauth2ClientCredentials { .. } => "oauth2ClientCredentials",
+    }
+}
+
+fn scheme_kind(scheme: &SecurityScheme) -> String {
+    match scheme {
+        SecurityScheme::APIKey { .. } => "apiKey".to_string(),
+        SecurityScheme::HTTP { scheme, .. } => format!("http {scheme}"),
+        SecurityScheme::OAuth2 { .. } => "oauth2".to_string(),
+        SecurityScheme::OpenIDConnect { .. } => "openIdConnect".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SPEC: &str = r##"
+openapi: "3.0.3"
+info: { title: t, version: "1" }
+security:
+  - key: []
+paths:
+  /pets:
+    get:
+      operationId: listPets
+      security: [{}]
+      responses: { "200": { description: ok } }
+    post:
+      operationId: createPet
+      responses: { "200": { description: ok } }
+  /admin:
+    delete:
+      operationId: purge
+      security:
+        - basic: []
+          key: []
+        - oauth: [pets:admin]
+      responses: { "200": { description: ok } }
+components:
+  securitySchemes:
+    key: { type: apiKey, in: header, name: X-Api-Key }
+    basic: { type: http, scheme: basic }
+    alias: { $ref: "#/components/securitySchemes/key" }
+    oauth:
+      type: oauth2
+      flows:
+        clientCredentials:
+          tokenUrl: https://auth.example.com/token
+          scopes: { "pets:admin": admin }
+"##;
+
+    fn spec() -> OpenAPI {
+        serde_yaml::from_str(SPEC).expect("spec")
+    }
+
+    fn operation<'a>(spec: &'a OpenAPI, path: &str, method: &str) -> &'a Operation {
+        let ReferenceOr::Item(item) = &spec.paths.paths[path] else {
+            panic!("path ref");
+        };
+        match method {
+            "get" => item.get.as_ref(),
+            "post" => item.post.as_ref(),
+            "delete" => item.delete.as_ref(),
+            _ => None,
+        }
+        .expect("operation")
+    }
+
+    fn creds(json: serde_json::Value) -> HashMap<String, SecurityCredential> {
+        serde_json::from_value(json).expect("credentials")
+    }
+
+    #[test]
+    fn resolves_operation_and_global_requirements() {
+        let spec = spec();
+        let credentials = creds(serde_json::json!({
+            "key": { "type": "apiKey", "value": "k" }
+        }));
+
+        assert_eq!(
+            resolve_operation_security(&spec, operation(&spec, "/pets", "get"), &credentials),
+            SecurityResolution::Anonymous
+        );
+        assert_eq!(
+            resolve_operation_security(&spec, operation(&spec, "/pets", "post"), &credentials),
+            SecurityResolution::Satisfied(vec![AppliedCredential::Header {
+                name: "X-Api-Key".to_string(),
+                value: "k".to_string(),
+            }])
+        );
+        // First alternative needs `basic` too; second needs `oauth`.
+        let SecurityResolution::Unsatisfied(reasons) =
+            resolve_operation_security(&spec, operation(&spec, "/admin", "delete"), &credentials)
+        else {
+            panic!("expected unsatisfied");
+        };
+        assert_eq!(
+            reasons,
+            vec![
+                "no credential configured for scheme 'basic'".to_string(),
+                "no credential configured for scheme 'oauth'".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn uses_first_satisfied_alternative_and_requirement_scopes() {
+        let spec = spec();
+        let credentials = creds(serde_json::json!({
+            "basic": { "type": "bearer", "token": "wrong kind" },
+            "oauth": { "type": "oauth2ClientCredentials", "clientId": "id", "clientSecret": "s" }
+        }));
+
+        assert_eq!(
+            resolve_operation_security(&spec, operation(&spec, "/admin", "delete"), &credentials),
+            SecurityResolution::Satisfied(vec![AppliedCredential::OAuth2ClientCredentials(
+                ClientCredentialsGrant {
+                    token_url: "https://auth.example.com/token".to_string(),
+                    client_id: "id".to_string(),
+                    client_secret: "s".to_string(),
+                    scopes: vec!["pets:admin".to_string()],
+                }
+            )])
+        );
+    }
+
+    #[test]
+    fn follows_scheme_refs_and_reports_unknown_credentials() {
+        let spec = spec();
+        assert!(matches!(
+            lookup_scheme(&spec, "alias"),
+            Some(SecurityScheme::APIKey { .. })
+        ));
+
+        let credentials = creds(serde_json::json!({
+            "key": { "type": "apiKey", "value": "k" },
+            "typo": { "type": "bearer", "token": "t" }
+        }));
+        assert_eq!(
+            unknown_credential_names(&spec, &credentials),
+            vec!["typo".to_string()]
+        );
+    }
+}
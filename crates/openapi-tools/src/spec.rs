@@ -0,0 +1,892 @@
+//! Spec version detection and normalization.
+//!
+//! Tool generation walks the [`openapiv3`] (`OpenAPI` 3.0) model, while schemas are converted from
+//! the raw resolver documents (see [`crate::schema`]). Supported inputs:
+//! - `OpenAPI` 3.0: parsed as-is.
+//! - `OpenAPI` 3.1: ingested natively. Every inline `schema` outside `components.schemas` is moved
+//!   into a side table of the document and replaced by a `$ref` to it, so the 3.0 model only ever
+//!   sees references while JSON Schema 2020-12 keywords (`type` arrays, `const`, `examples`, ...)
+//!   pass through unchanged. `webhooks` are ignored (they describe inbound calls).
+//! - Swagger 2.0: converted to an equivalent 3.0 document (`host`/`basePath`/`schemes` → `servers`,
+//!   body/formData parameters → `requestBody` using `consumes`, response schemas → `content` using
+//!   `produces`, `collectionFormat` → `style`/`explode`, `securityDefinitions` →
+//!   `components.securitySchemes`, `definitions` → `components.schemas`).
+
+use crate::error::{OpenApiToolsError, Result};
+use openapiv3::OpenAPI;
+use serde_json::{Map, Value, json};
+
+/// Side table holding the inline schemas of `OpenAPI` 3.1 documents.
+const INLINE_SCHEMAS_KEY: &str = "x-unrelated-inline-schemas";
+
+/// HTTP methods of a path item (Swagger 2.0 and `OpenAPI` 3.x).
+const METHODS: &[&str] = &[
+    "get", "put", "post", "delete", "options", "head", "patch", "trace",
+];
+
+/// Detected spec version family.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SpecVersion {
+    Swagger20,
+    OpenApi30,
+    OpenApi31,
+}
+
+impl SpecVersion {
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            SpecVersion::Swagger20 => "2.0",
+            SpecVersion::OpenApi30 => "3.0",
+            SpecVersion::OpenApi31 => "3.1",
+        }
+    }
+
+    /// Detect the version from the `swagger` / `openapi` field.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error for missing or unsupported versions.
+    pub fn detect(document: &Value) -> Result<Self> {
+        if let Some(v) = document.get("swagger") {
+            return match v.as_str() {
+                Some("2.0") => Ok(SpecVersion::Swagger20),
+                _ => Err(unsupported(&format!("swagger {v}"))),
+            };
+        }
+        match document.get("openapi").and_then(Value::as_str) {
+            Some(v) if v.starts_with("3.0") => Ok(SpecVersion::OpenApi30),
+            Some(v) if v.starts_with("3.1") => Ok(SpecVersion::OpenApi31),
+            Some(v) => Err(unsupported(&format!("openapi {v}"))),
+            None => Err(OpenApiToolsError::OpenApi(
+                "Spec has no 'openapi' or 'swagger' version field".to_string(),
+            )),
+        }
+    }
+}
+
+fn unsupported(version: &str) -> OpenApiToolsError {
+    OpenApiToolsError::OpenApi(format!(
+        "Unsupported spec version '{version}' (supported: swagger 2.0, openapi 3.0.x and 3.1.x)"
+    ))
+}
+
+/// A loaded spec: the 3.0 model used for tool generation plus the resolver root document.
+#[derive(Debug, Clone)]
+pub struct LoadedSpec {
+    pub version: SpecVersion,
+    /// Root document for `$ref` resolution (schemas are converted from here).
+    pub document: Value,
+    pub openapi: OpenAPI,
+}
+
+/// Detect the version of a raw spec document (loaded from `location`) and normalize it for tool
+/// generation.
+///
+/// # Errors
+///
+/// Returns an error if the version is unsupported or the (normalized) document is not a valid
+/// `OpenAPI` 3.0 model.
+pub fn load(location: &str, document: Value) -> Result<LoadedSpec> {
+    let version = SpecVersion::detect(&document).map_err(|e| match e {
+        OpenApiToolsError::OpenApi(msg) => {
+            OpenApiToolsError::OpenApi(format!("{msg} (spec '{location}')"))
+        }
+        other => other,
+    })?;
+    let (document, model) = match version {
+        SpecVersion::OpenApi30 => {
+            let model = document.clone();
+            (document, model)
+        }
+        SpecVersion::OpenApi31 => normalize_openapi31(document),
+        SpecVersion::Swagger20 => {
+            let converted = swagger20_to_openapi30(&document);
+            (converted.clone(), converted)
+        }
+    };
+    let openapi: OpenAPI = serde_json::from_value(model).map_err(|e| {
+        OpenApiToolsError::OpenApi(format!(
+            "Invalid OpenAPI {} spec '{location}': {e}",
+            version.as_str()
+        ))
+    })?;
+    Ok(LoadedSpec {
+        version,
+        document,
+        openapi,
+    })
+}
+
+// ============================================================================
+// OpenAPI 3.1
+// ============================================================================
+
+/// Returns `(resolver document, 3.0 model document)`.
+fn normalize_openapi31(mut document: Value) -> (Value, Value) {
+    let mut inline = Vec::new();
+    if let Some(root) = document.as_object_mut() {
+        for (key, value) in root.iter_mut() {
+            match key.as_str() {
+                "paths" => hoist_inline_schemas(value, &mut inline),
+                "components" => {
+                    for (section, items) in value.as_object_mut().into_iter().flatten() {
+                        if section != "schemas" {
+                            hoist_inline_schemas(items, &mut inline);
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+        root.insert(INLINE_SCHEMAS_KEY.to_string(), Value::Array(inline));
+    }
+
+    let mut model = document.clone();
+    if let Some(root) = model.as_object_mut() {
+        root.remove(INLINE_SCHEMAS_KEY);
+        root.remove("webhooks");
+        root.entry("paths").or_insert_with(|| json!({}));
+        if let Some(components) = root.get_mut("components").and_then(Value::as_object_mut) {
+            components.remove("schemas");
+            components.remove("pathItems");
+            // `mutualTLS` (3.1-only) has no 3.0 model; operations requiring it stay unsatisfied.
+            if let Some(schemes) = components
+                .get_mut("securitySchemes")
+                .and_then(Value::as_object_mut)
+            {
+                schemes.retain(|_, s| s.get("type").and_then(Value::as_str) != Some("mutualTLS"));
+            }
+        }
+    }
+    (document, model)
+}
+
+/// Move every `schema` value below `value` into `inline`, leaving a `$ref` to its new location.
+fn hoist_inline_schemas(value: &mut Value, inline: &mut Vec<Value>) {
+    match value {
+        Value::Object(map) => {
+            for (key, child) in map.iter_mut() {
+                if matches!(key.as_str(), "example" | "examples" | "default") {
+                    continue;
+                }
+                if key == "schema" && child.is_object() {
+                    let index = inline.len();
+                    inline.push(child.take());
+                    *child = json!({ "$ref": format!("#/{INLINE_SCHEMAS_KEY}/{index}") });
+                } else {
+                    hoist_inline_schemas(child, inline);
+                }
+            }
+        }
+        Value::Array(items) => {
+            for child in items {
+                hoist_inline_schemas(child, inline);
+            }
+        }
+        _ => {}
+    }
+}
+
+// ============================================================================
+// Swagger 2.0
+// ============================================================================
+
+/// Convert a Swagger 2.0 document into an equivalent `OpenAPI` 3.0 document.
+fn swagger20_to_openapi30(doc: &Value) -> Value {
+    let global_consumes = media_types(doc.get("consumes"));
+    let global_produces = media_types(doc.get("produces"));
+    let global_params = doc.get("parameters").and_then(Value::as_object);
+
+    let mut out = Map::new();
+    out.insert("openapi".to_string(), json!("3.0.3"));
+    for key in ["info", "tags", "externalDocs", "security"] {
+        if let Some(v) = doc.get(key) {
+            out.insert(key.to_string(), v.clone());
+        }
+    }
+    for (key, value) in doc.as_object().into_iter().flatten() {
+        if key.starts_with("x-") {
+            out.insert(key.clone(), value.clone());
+        }
+    }
+    out.insert("servers".to_string(), swagger_servers(doc));
+
+    let mut components = Map::new();
+    if let Some(definitions) = doc.get("definitions").and_then(Value::as_object) {
+        let schemas: Map<String, Value> = definitions
+            .iter()
+            .map(|(name, schema)| (name.clone(), swagger_schema(schema)))
+            .collect();
+        components.insert("schemas".to_string(), Value::Object(schemas));
+    }
+    if let Some(params) = global_params {
+        let mut parameters = Map::new();
+        let mut bodies = Map::new();
+        for (name, param) in params {
+            match param.get("in").and_then(Value::as_str) {
+                Some("body") => {
+                    bodies.insert(
+                        name.clone(),
+                        swagger_request_body(&[param], &global_consumes),
+                    );
+                }
+                // Form parameters are inlined into each operation's request body.
+                Some("formData") => {}
+                _ => {
+                    parameters.insert(name.clone(), swagger_parameter(param));
+                }
+            }
+        }
+        components.insert("parameters".to_string(), Value::Object(parameters));
+        components.insert("requestBodies".to_string(), Value::Object(bodies));
+    }
+    if let Some(responses) = doc.get("responses").and_then(Value::as_object) {
+        let converted: Map<String, Value> = responses
+            .iter()
+            .map(|(name, r)| (name.clone(), swagger_response(r, &global_produces)))
+            .collect();
+        components.insert("responses".to_string(), Value::Object(converted));
+    }
+    if let Some(defs) = doc.get("securityDefinitions").and_then(Value::as_object) {
+        let schemes: Map<String, Value> = defs
+            .iter()
+            .map(|(name, def)| (name.clone(), swagger_security_scheme(def)))
+            .collect();
+        components.insert("securitySchemes".to_string(), Value::Object(schemes));
+    }
+    out.insert("components".to_string(), Value::Object(components));
+
+    let mut paths = Map::new();
+    for (path, item) in doc
+        .get("paths")
+        .and_then(Value::as_object)
+        .into_iter()
+        .flatten()
+    {
+        if path.starts_with("x-") {
+            continue;
+        }
+        let Some(item) = item.as_object() else {
+            continue;
+        };
+        let path_params: Vec<&Value> = item
+            .get("parameters")
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+            .collect();
+
+        let mut converted = Map::new();
+        if let Some(r) = item.get("$ref") {
+            converted.insert("$ref".to_string(), r.clone());
+        }
+        let (non_body, _) = split_swagger_params(&path_params, global_params);
+        if !non_body.is_empty() {
+            converted.insert("parameters".to_string(), Value::Array(non_body));
+        }
+        for method in METHODS {
+            if let Some(op) = item.get(*method).and_then(Value::as_object) {
+                let op = swagger_operation(
+                    op,
+                    &path_params,
+                    global_params,
+                    &global_consumes,
+                    &global_produces,
+                );
+                converted.insert((*method).to_string(), op);
+            }
+        }
+        paths.insert(path.clone(), Value::Object(converted));
+    }
+    out.insert("paths".to_string(), Value::Object(paths));
+
+    let mut out = Value::Object(out);
+    rewrite_swagger_refs(&mut out);
+    out
+}
+
+fn media_types(v: Option<&Value>) -> Vec<String> {
+    v.and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(Value::as_str)
+        .map(str::to_string)
+        .collect()
+}
+
+fn swagger_servers(doc: &Value) -> Value {
+    let base_path = doc
+        .get("basePath")
+        .and_then(Value::as_str)
+        .unwrap_or("")
+        .trim_end_matches('/');
+    let Some(host) = doc.get("host").and_then(Value::as_str) else {
+        // Relative to the spec location.
+        let url = if base_path.is_empty() { "/" } else { base_path };
+        return json!([{ "url": url }]);
+    };
+    let mut schemes = media_types(doc.get("schemes"));
+    if schemes.is_empty() {
+        schemes.push("https".to_string());
+    }
+    Value::Array(
+        schemes
+            .iter()
+            .map(|scheme| json!({ "url": format!("{scheme}://{host}{base_path}") }))
+            .collect(),
+    )
+}
+
+/// Split operation/path parameters into 3.0 (non-body) parameters and body/form parameters.
+///
+/// `$ref`s to global body/form parameters are inlined; other `$ref`s are kept (and rewritten later).
+fn split_swagger_params<'a>(
+    params: &[&'a Value],
+    global_params: Option<&'a Map<String, Value>>,
+) -> (Vec<Value>, Vec<&'a Value>) {
+    let mut non_body = Vec::new();
+    let mut body = Vec::new();
+    for &param in params {
+        let resolved = match param.get("$ref").and_then(Value::as_str) {
+            Some(r) => r
+                .strip_prefix("#/parameters/")
+                .and_then(|name| global_params?.get(name)),
+            None => Some(param),
+        };
+        match resolved.and_then(|p| p.get("in")).and_then(Value::as_str) {
+            Some("body" | "formData") => body.extend(resolved),
+            _ if param.get("$ref").is_some() => non_body.push(param.clone()),
+            _ => non_body.push(swagger_parameter(param)),
+        }
+    }
+    (non_body, body)
+}
+
+fn param_key(p: &Value) -> (Option<&str>, Option<&str>) {
+    (
+        p.get("in").and_then(Value::as_str),
+        p.get("name").and_then(Value::as_str),
+    )
+}
+
+fn swagger_operation(
+    op: &Map<String, Value>,
+    path_params: &[&Value],
+    global_params: Option<&Map<String, Value>>,
+    global_consumes: &[String],
+    global_produces: &[String],
+) -> Value {
+    let consumes = op
+        .get("consumes")
+        .map_or_else(|| global_consumes.to_vec(), |v| media_types(Some(v)));
+    let produces = op
+        .get("produces")
+        .map_or_else(|| global_produces.to_vec(), |v| media_types(Some(v)));
+
+    let mut out = Map::new();
+    for (key, value) in op {
+        if matches!(
+            key.as_str(),
+            "tags"
+                | "summary"
+                | "description"
+                | "externalDocs"
+                | "operationId"
+                | "deprecated"
+                | "security"
+        ) || key.starts_with("x-")
+        {
+            out.insert(key.clone(), value.clone());
+        }
+    }
+
+    let op_params: Vec<&Value> = op
+        .get("parameters")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .collect();
+    let (non_body, mut body) = split_swagger_params(&op_params, global_params);
+    // Path-level body/form parameters apply unless the operation overrides them.
+    let (_, path_body) = split_swagger_params(path_params, global_params);
+    for p in path_body {
+        if !body.iter().any(|b| param_key(b) == param_key(p)) {
+            body.push(p);
+        }
+    }
+    if !non_body.is_empty() {
+        out.insert("parameters".to_string(), Value::Array(non_body));
+    }
+    if !body.is_empty() {
+        out.insert(
+            "requestBody".to_string(),
+            swagger_request_body(&body, &consumes),
+        );
+    }
+
+    let mut responses = Map::new();
+    for (code, resp) in op
+        .get("responses")
+        .and_then(Value::as_object)
+        .into_iter()
+        .flatten()
+    {
+        if code.starts_with("x-") {
+            continue;
+        }
+        let converted = if resp.get("$ref").is_some() {
+            resp.clone()
+        } else {
+            swagger_response(resp, &produces)
+        };
+        responses.insert(code.clone(), converted);
+    }
+    out.insert("responses".to_string(), Value::Object(responses));
+    Value::Object(out)
+}
+
+/// Build a 3.0 request body from a `body` parameter or a set of `formData` parameters.
+fn swagger_request_body(params: &[&Value], consumes: &[String]) -> Value {
+    if let Some(body) = params
+        .iter()
+        .find(|p| p.get("in").and_then(Value::as_str) == Some("body"))
+    {
+        let schema = body.get("schema").map_or_else(|| json!({}), swagger_schema);
+        let types: Vec<&str> = if consumes.is_empty() {
+            vec!["application/json"]
+        } else {
+            consumes.iter().map(String::as_str).collect()
+        };
+        let content: Map<String, Value> = types
+            .into_iter()
+            .map(|mt| (mt.to_string(), json!({ "schema": schema })))
+            .collect();
+        let mut out = json!({
+            "required": body.get("required").and_then(Value::as_bool).unwrap_or(false),
+            "content": content,
+        });
+        if let Some(desc) = body.get("description") {
+            out["description"] = desc.clone();
+        }
+        return out;
+    }
+
+    let mut properties = Map::new();
+    let mut required = Vec::new();
+    let mut has_file = false;
+    for p in params {
+        let Some(name) = p.get("name").and_then(Value::as_str) else {
+            continue;
+        };
+        has_file |= p.get("type").and_then(Value::as_str) == Some("file");
+        let mut schema = swagger_simple_schema(p);
+        if let Some(desc) = p.get("description") {
+            schema["description"] = desc.clone();
+        }
+        properties.insert(name.to_string(), schema);
+        if p.get("required").and_then(Value::as_bool) == Some(true) {
+            required.push(json!(name));
+        }
+    }
+    let media_type = if has_file || consumes.iter().any(|c| c == "multipart/form-data") {
+        "multipart/form-data"
+    } else {
+        "application/x-www-form-urlencoded"
+    };
+    let mut schema = json!({ "type": "object", "properties": properties });
+    if !required.is_empty() {
+        schema["required"] = Value::Array(required.clone());
+    }
+    json!({
+        "required": !required.is_empty(),
+        "content": { media_type: { "schema": schema } },
+    })
+}
+
+/// Convert a non-body Swagger parameter into a 3.0 parameter.
+fn swagger_parameter(p: &Value) -> Value {
+    let location = p.get("in").and_then(Value::as_str).unwrap_or("query");
+    let mut out = Map::new();
+    for key in ["name", "in", "description", "required", "allowEmptyValue"] {
+        if let Some(v) = p.get(key) {
+            out.insert(key.to_string(), v.clone());
+        }
+    }
+    if location == "path" {
+        out.insert("required".to_string(), json!(true));
+    }
+    out.insert("schema".to_string(), swagger_simple_schema(p));
+
+    if p.get("type").and_then(Value::as_str) == Some("array") {
+        let (style, explode) = match p.get("collectionFormat").and_then(Value::as_str) {
+            Some("multi") => ("form", true),
+            Some("ssv") => ("spaceDelimited", false),
+            Some("pipes") => ("pipeDelimited", false),
+            // `csv` (default) and `tsv` (no 3.0 equivalent).
+            _ if location == "query" => ("form", false),
+            _ => ("simple", false),
+        };
+        out.insert("style".to_string(), json!(style));
+        out.insert("explode".to_string(), json!(explode));
+    }
+    Value::Object(out)
+}
+
+/// Schema of a non-body parameter, header or `items` object.
+fn swagger_simple_schema(p: &Value) -> Value {
+    const KEYS: &[&str] = &[
+        "type",
+        "format",
+        "default",
+        "maximum",
+        "exclusiveMaximum",
+        "minimum",
+        "exclusiveMinimum",
+        "maxLength",
+        "minLength",
+        "pattern",
+        "maxItems",
+        "minItems",
+        "uniqueItems",
+        "enum",
+        "multipleOf",
+    ];
+    let mut out = Map::new();
+    for key in KEYS {
+        if let Some(v) = p.get(*key) {
+            out.insert((*key).to_string(), v.clone());
+        }
+    }
+    if let Some(items) = p.get("items") {
+        out.insert("items".to_string(), swagger_simple_schema(items));
+    }
+    fix_swagger_schema_keywords(&mut out);
+    Value::Object(out)
+}
+
+/// Convert a Swagger 2.0 Schema Object (recursively) to its 3.0 form.
+fn swagger_schema(schema: &Value) -> Value {
+    let Some(obj) = schema.as_object() else {
+        return schema.clone();
+    };
+    let mut out = Map::new();
+    for (key, value) in obj {
+        let converted = match key.as_str() {
+            "properties" => Value::Object(
+                value
+                    .as_object()
+                    .into_iter()
+                    .flatten()
+                    .map(|(k, v)| (k.clone(), swagger_schema(v)))
+                    .collect(),
+            ),
+            "items" | "additionalProperties" | "not" => swagger_schema(value),
+            "allOf" | "anyOf" | "oneOf" => Value::Array(
+                value
+                    .as_array()
+                    .into_iter()
+                    .flatten()
+                    .map(swagger_schema)
+                    .collect(),
+            ),
+            "discriminator" => match value {
+                Value::String(prop) => json!({ "propertyName": prop }),
+                other => other.clone(),
+            },
+            _ => value.clone(),
+        };
+        out.insert(key.clone(), converted);
+    }
+    fix_swagger_schema_keywords(&mut out);
+    Value::Object(out)
+}
+
+fn fix_swagger_schema_keywords(out: &mut Map<String, Value>) {
+    if out.get("type").and_then(Value::as_str) == Some("file") {
+        out.insert("type".to_string(), json!("string"));
+        out.insert("format".to_string(), json!("binary"));
+    }
+    if let Some(nullable) = out.remove("x-nullable") {
+        out.insert("nullable".to_string(), nullable);
+    }
+}
+
+fn swagger_response(resp: &Value, produces: &[String]) -> Value {
+    let mut out = Map::new();
+    out.insert(
+        "description".to_string(),
+        resp.get("description")
+            .cloned()
+            .unwrap_or_else(|| json!("")),
+    );
+    if let Some(headers) = resp.get("headers").and_then(Value::as_object) {
+        let converted: Map<String, Value> = headers
+            .iter()
+            .map(|(name, h)| {
+                let mut header = json!({ "schema": swagger_simple_schema(h) });
+                if let Some(desc) = h.get("description") {
+                    header["description"] = desc.clone();
+                }
+                (name.clone(), header)
+            })
+            .collect();
+        out.insert("headers".to_string(), Value::Object(converted));
+    }
+    if let Some(schema) = resp.get("schema") {
+        let schema = swagger_schema(schema);
+        let examples = resp.get("examples").and_then(Value::as_object);
+        let types: Vec<&str> = if produces.is_empty() {
+            vec!["application/json"]
+        } else {
+            produces.iter().map(String::as_str).collect()
+        };
+        let content: Map<String, Value> = types
+            .into_iter()
+            .map(|mt| {
+                let mut media = json!({ "schema": schema });
+                if let Some(example) = examples.and_then(|e| e.get(mt)) {
+                    media["example"] = example.clone();
+                }
+                (mt.to_string(), media)
+            })
+            .collect();
+        out.insert("content".to_string(), Value::Object(content));
+    }
+    Value::Object(out)
+}
+
+fn swagger_security_scheme(def: &Value) -> Value {
+    let description = def.get("description").cloned();
+    let mut out = match def.get("type").and_then(Value::as_str) {
+        Some("basic") => json!({ "type": "http", "scheme": "basic" }),
+        Some("apiKey") => json!({
+            "type": "apiKey",
+            "name": def.get("name").cloned().unwrap_or(Value::Null),
+            "in": def.get("in").cloned().unwrap_or(Value::Null),
+        }),
+        Some("oauth2") => {
+            let scopes = def.get("scopes").cloned().unwrap_or_else(|| json!({}));
+            let mut flow = json!({ "scopes": scopes });
+            for key in ["authorizationUrl", "tokenUrl"] {
+                if let Some(v) = def.get(key) {
+                    flow[key] = v.clone();
+                }
+            }
+            let name = match def.get("flow").and_then(Value::as_str) {
+                Some("implicit") => "implicit",
+                Some("password") => "password",
+                Some("application") => "clientCredentials",
+                _ => "authorizationCode",
+            };
+            json!({ "type": "oauth2", "flows": { name: flow } })
+        }
+        _ => def.clone(),
+    };
+    if let Some(desc) = description {
+        out["description"] = desc;
+    }
+    out
+}
+
+/// Rewrite local Swagger 2.0 `$ref`s to their 3.0 component locations.
+fn rewrite_swagger_refs(value: &mut Value) {
+    match value {
+        Value::Object(map) => {
+            if let Some(Value::String(r)) = map.get_mut("$ref") {
+                for (from, to) in [
+                    ("#/definitions/", "#/components/schemas/"),
+                    ("#/parameters/", "#/components/parameters/"),
+                    ("#/responses/", "#/components/responses/"),
+                ] {
+                    if let Some(rest) = r.strip_prefix(from) {
+                        *r = format!("{to}{rest}");
+                        break;
+                    }
+                }
+            }
+            map.values_mut().for_each(rewrite_swagger_refs);
+        }
+        Value::Array(items) => items.iter_mut().for_each(rewrite_swagger_refs),
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn converts_swagger20_to_openapi30() {
+        let doc: Value = serde_yaml::from_str(
+            r##"
+swagger: "2.0"
+info: { title: Pets, version: "1" }
+host: api.example.com
+basePath: /v1/
+schemes: [https]
+consumes: [application/json]
+produces: [application/json]
+securityDefinitions:
+  key: { type: apiKey, name: X-Key, in: header }
+  oauth: { type: oauth2, flow: application, tokenUrl: "https://auth.example.com/token", scopes: {} }
+parameters:
+  Limit: { name: limit, in: query, type: integer, maximum: 100 }
+definitions:
+  Pet:
+    type: object
+    discriminator: kind
+    required: [kind]
+    properties:
+      kind: { type: string }
+      photo: { type: file }
+      owner: { $ref: "#/definitions/Owner" }
+  Owner: { type: object, x-nullable: true }
+paths:
+  /pets:
+    get:
+      operationId: listPets
+      parameters:
+        - $ref: "#/parameters/Limit"
+        - { name: tags, in: query, type: array, items: { type: string }, collectionFormat: multi }
+        - { name: ids, in: query, type: array, items: { type: integer } }
+      responses:
+        "200":
+          description: ok
+          schema: { type: array, items: { $ref: "#/definitions/Pet" } }
+    post:
+      operationId: createPet
+      parameters:
+        - { name: body, in: body, required: true, schema: { $ref: "#/definitions/Pet" } }
+      responses:
+        default: { description: error }
+  /pets/{id}/photo:
+    parameters:
+      - { name: id, in: path, type: string }
+    put:
+      operationId: uploadPhoto
+      consumes: [multipart/form-data]
+      parameters:
+        - { name: file, in: formData, type: file, required: true }
+        - { name: note, in: formData, type: string }
+      responses:
+        "204": { description: done }
+"##,
+        )
+        .expect("yaml");
+
+        let loaded = load("spec.yaml", doc).expect("load");
+        assert_eq!(loaded.version, SpecVersion::Swagger20);
+        let d = &loaded.document;
+        assert_eq!(
+            d["servers"],
+            json!([{ "url": "https://api.example.com/v1" }])
+        );
+        assert_eq!(
+            d["components"]["securitySchemes"]["oauth"]["flows"]["clientCredentials"]["tokenUrl"],
+            json!("https://auth.example.com/token")
+        );
+        assert_eq!(
+            d["components"]["schemas"]["Pet"]["discriminator"],
+            json!({ "propertyName": "kind" })
+        );
+        assert_eq!(
+            d["components"]["schemas"]["Pet"]["properties"]["photo"],
+            json!({ "type": "string", "format": "binary" })
+        );
+        assert_eq!(
+            d["components"]["schemas"]["Pet"]["properties"]["owner"],
+            json!({ "$ref": "#/components/schemas/Owner" })
+        );
+        assert_eq!(d["components"]["schemas"]["Owner"]["nullable"], json!(true));
+
+        let list = &d["paths"]["/pets"]["get"];
+        assert_eq!(
+            list["parameters"],
+            json!([
+                { "$ref": "#/components/parameters/Limit" },
+                { "name": "tags", "in": "query", "schema": { "type": "array", "items": { "type": "string" } }, "style": "form", "explode": true },
+                { "name": "ids", "in": "query", "schema": { "type": "array", "items": { "type": "integer" } }, "style": "form", "explode": false }
+            ])
+        );
+        assert_eq!(
+            list["responses"]["200"]["content"]["application/json"]["schema"]["items"],
+            json!({ "$ref": "#/components/schemas/Pet" })
+        );
+        assert_eq!(
+            d["paths"]["/pets"]["post"]["requestBody"],
+            json!({
+                "required": true,
+                "content": { "application/json": { "schema": { "$ref": "#/components/schemas/Pet" } } }
+            })
+        );
+        let upload = &d["paths"]["/pets/{id}/photo"];
+        assert_eq!(upload["parameters"][0]["required"], json!(true));
+        assert_eq!(
+            upload["put"]["requestBody"]["content"]["multipart/form-data"]["schema"]["required"],
+            json!(["file"])
+        );
+
+        let op = loaded.openapi.paths.paths.get("/pets").expect("path");
+        assert!(op.as_item().and_then(|i| i.post.as_ref()).is_some());
+    }
+
+    #[test]
+    fn hoists_openapi31_inline_schemas_behind_refs() {
+        let doc: Value = serde_yaml::from_str(
+            r##"
+openapi: 3.1.0
+info: { title: t, version: "1" }
+webhooks:
+  ping: { post: { responses: { "200": { description: ok } } } }
+components:
+  schemas:
+    Id: { type: [string, "null"], const: abc }
+  parameters:
+    Q: { name: q, in: query, schema: { type: [string, "null"], examples: [x] } }
+paths:
+  /things/{id}:
+    get:
+      parameters:
+        - $ref: "#/components/parameters/Q"
+        - { name: id, in: path, required: true, schema: { $ref: "#/components/schemas/Id" } }
+      responses:
+        "200":
+          description: ok
+          content:
+            application/json:
+              schema: { type: object, properties: { id: { $ref: "#/components/schemas/Id" } } }
+"##,
+        )
+        .expect("yaml");
+
+        let loaded = load("spec.yaml", doc).expect("load");
+        assert_eq!(loaded.version, SpecVersion::OpenApi31);
+        let d = &loaded.document;
+        let q = &d["components"]["parameters"]["Q"]["schema"]["$ref"];
+        let pointer = q.as_str().expect("ref").trim_start_matches('#');
+        assert_eq!(
+            d.pointer(pointer),
+            Some(&json!({ "type": ["string", "null"], "examples": ["x"] }))
+        );
+        assert_eq!(d["components"]["schemas"]["Id"]["const"], json!("abc"));
+        let op = &d["paths"]["/things/{id}"]["get"];
+        assert!(
+            op["parameters"][1]["schema"]["$ref"]
+                .as_str()
+                .is_some_and(|r| r.starts_with(&format!("#/{INLINE_SCHEMAS_KEY}/")))
+        );
+        assert!(loaded.openapi.paths.paths.contains_key("/things/{id}"));
+    }
+
+    #[test]
+    fn rejects_unsupported_versions() {
+        assert!(SpecVersion::detect(&json!({ "openapi": "4.0.0" })).is_err());
+        assert!(SpecVersion::detect(&json!({ "swagger": "1.2" })).is_err());
+        assert!(SpecVersion::detect(&json!({ "info": {} })).is_err());
+        assert_eq!(
+            SpecVersion::detect(&json!({ "openapi": "3.0.3" })).ok(),
+            Some(SpecVersion::OpenApi30)
+        );
+    }
+}
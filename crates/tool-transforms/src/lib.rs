@@ -6,7 +6,7 @@
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::borrow::Cow;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 mod serde_helpers {
     // Serde's `default = "..."` expects helpers with the signature `fn() -> T`.
@@ -23,10 +23,10 @@ use serde_helpers::default_true;
 /// - the Gateway (per-tenant/per-profile policy)
 /// - the Adapter (standalone single-tenant config)
 ///
-/// The initial scope is intentionally small and only covers:
+/// The scope is intentionally small and covers:
 /// - tool name renames
-/// - top-level argument renames
-/// - top-level default argument injection
+/// - argument renames, hiding and default injection, for top-level or nested (JSON pointer)
+///   parameters
 #[derive(Debug, Clone, Default, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct TransformPipeline {
@@ -41,6 +41,8 @@ pub struct ToolOverride {
     pub rename: Option<String>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
+    /// Per-parameter overrides keyed by original param name, or by a JSON pointer to a nested
+    /// object property (e.g. `/body/options/region`).
     #[serde(default)]
     pub params: HashMap<String, ParamOverride>,
 }
@@ -68,6 +70,27 @@ pub struct ParamOverride {
         skip_serializing_if = "std::clone::Clone::clone"
     )]
     pub treat_null_as_missing: bool,
+    /// Force this value on every call; client-provided values are replaced.
+    ///
+    /// When visible, the param is exposed as `const` (and no longer `required`), so argument
+    /// validation rejects any other value.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pinned: Option<Value>,
+    /// Allowed values (a subset of the source schema's `enum`, if it has one).
+    #[serde(default, rename = "enum", skip_serializing_if = "Option::is_none")]
+    pub enum_values: Option<Vec<Value>>,
+    /// Lower bound; only narrows an existing `minimum`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub minimum: Option<serde_json::Number>,
+    /// Upper bound; only narrows an existing `maximum`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub maximum: Option<serde_json::Number>,
+    /// Regex (ECMA-262) the value must match, in addition to any source `pattern`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pattern: Option<String>,
+    /// Maximum string length; only narrows an existing `maxLength`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_length: Option<u64>,
 }
 
 impl Default for ParamOverride {
@@ -77,10 +100,191 @@ impl Default for ParamOverride {
             default: None,
             visible: true,
             treat_null_as_missing: true,
+            pinned: None,
+            enum_values: None,
+            minimum: None,
+            maximum: None,
+            pattern: None,
+            max_length: None,
+        }
+    }
+}
+
+impl ParamOverride {
+    fn validate(&self) -> Result<(), String> {
+        if self.rename.as_deref().is_some_and(str::is_empty) {
+            return Err("rename must not be empty".to_string());
+        }
+        if self.pinned.is_some() && self.default.is_some() {
+            return Err("pinned and default are mutually exclusive".to_string());
+        }
+        if let Some(values) = &self.enum_values {
+            if values.is_empty() {
+                return Err("enum must not be empty".to_string());
+            }
+            if let Some(p) = &self.pinned
+                && !values.contains(p)
+            {
+                return Err("pinned value is not in enum".to_string());
+            }
+        }
+        if let (Some(min), Some(max)) = (&self.minimum, &self.maximum)
+            && min.as_f64() > max.as_f64()
+        {
+            return Err("minimum must be <= maximum".to_string());
+        }
+        if let Some(pattern) = &self.pattern {
+            if pattern.is_empty() {
+                return Err("pattern must not be empty".to_string());
+            }
+            // An exposed schema whose `pattern` does not compile cannot validate arguments.
+            fancy_regex::Regex::new(pattern)
+                .map_err(|e| format!("pattern is not a valid regex: {e}"))?;
+        }
+        Ok(())
+    }
+
+    /// Write pinned value and constraints into the (exposed) property schema. Constraints only
+    /// narrow what the source schema already allows.
+    fn apply_schema_constraints(&self, prop: &mut serde_json::Map<String, Value>) {
+        if let Some(v) = &self.pinned {
+            prop.insert("const".to_string(), v.clone());
+            prop.insert("default".to_string(), v.clone());
+        }
+        if let Some(values) = &self.enum_values {
+            let narrowed: Vec<Value> = match prop.get("enum").and_then(Value::as_array) {
+                Some(existing) => values
+                    .iter()
+                    .filter(|v| existing.contains(v))
+                    .cloned()
+                    .collect(),
+                None => values.clone(),
+            };
+            prop.insert("enum".to_string(), Value::Array(narrowed));
+        }
+        let narrow = |prop: &mut serde_json::Map<String, Value>,
+                      key: &str,
+                      bound: &serde_json::Number,
+                      keep_existing: fn(f64, f64) -> bool| {
+            let keep = prop
+                .get(key)
+                .and_then(Value::as_f64)
+                .zip(bound.as_f64())
+                .is_some_and(|(cur, new)| keep_existing(cur, new));
+            if !keep {
+                prop.insert(key.to_string(), Value::Number(bound.clone()));
+            }
+        };
+        if let Some(min) = &self.minimum {
+            narrow(prop, "minimum", min, |cur, new| cur >= new);
+        }
+        if let Some(max) = &self.maximum {
+            narrow(prop, "maximum", max, |cur, new| cur <= new);
+        }
+        if let Some(n) = self.max_length {
+            narrow(prop, "maxLength", &n.into(), |cur, new| cur <= new);
+        }
+        if let Some(p) = &self.pattern {
+            match prop.get("pattern").and_then(Value::as_str) {
+                None => {
+                    prop.insert("pattern".to_string(), Value::String(p.clone()));
+                }
+                Some(cur) if cur == p => {}
+                Some(_) => {
+                    // JSON Schema allows one `pattern` per schema; require both via `allOf`.
+                    let all_of = prop
+                        .entry("allOf".to_string())
+                        .or_insert_with(|| Value::Array(Vec::new()));
+                    if let Some(arr) = all_of.as_array_mut() {
+                        arr.push(serde_json::json!({ "pattern": p }));
+                    }
+                }
+            }
         }
     }
 }
 
+/// Parse a `params` key into a property path.
+///
+/// Keys starting with `/` are JSON pointers (RFC 6901) addressing nested object properties, e.g.
+/// `/body/options/region`; any other key is a top-level argument name.
+fn param_path(key: &str) -> Result<Vec<String>, String> {
+    let Some(pointer) = key.strip_prefix('/') else {
+        if key.is_empty() {
+            return Err("empty param name".to_string());
+        }
+        return Ok(vec![key.to_string()]);
+    };
+    pointer
+        .split('/')
+        .map(|seg| {
+            if seg.is_empty() {
+                return Err(format!("invalid pointer '{key}': empty segment"));
+            }
+            let mut out = String::with_capacity(seg.len());
+            let mut chars = seg.chars();
+            while let Some(c) = chars.next() {
+                if c != '~' {
+                    out.push(c);
+                    continue;
+                }
+                match chars.next() {
+                    Some('0') => out.push('~'),
+                    Some('1') => out.push('/'),
+                    _ => return Err(format!("invalid pointer '{key}': bad '~' escape")),
+                }
+            }
+            Ok(out)
+        })
+        .collect()
+}
+
+/// Parsed param overrides of one tool. Keys that fail to parse are skipped (see `validate`).
+fn param_entries(tool: &ToolOverride) -> Vec<(Vec<String>, &ParamOverride)> {
+    let mut out: Vec<(Vec<String>, &ParamOverride)> = tool
+        .params
+        .iter()
+        .filter_map(|(k, o)| Some((param_path(k).ok()?, o)))
+        .collect();
+    // Shallowest first, then by path for determinism.
+    out.sort_by(|(a, _), (b, _)| a.len().cmp(&b.len()).then_with(|| a.cmp(b)));
+    out
+}
+
+/// The JSON Schema object at `path` (following `properties` at each level).
+fn schema_at<'a>(schema: &'a Value, path: &[String]) -> Option<&'a Value> {
+    path.iter()
+        .try_fold(schema, |cur, seg| cur.get("properties")?.get(seg))
+}
+
+fn schema_at_mut<'a>(schema: &'a mut Value, path: &[String]) -> Option<&'a mut Value> {
+    path.iter().try_fold(schema, |cur, seg| {
+        cur.get_mut("properties")?.as_object_mut()?.get_mut(seg)
+    })
+}
+
+/// The argument object at `path`. With `create`, missing (or `null`) intermediate values are
+/// created as empty objects.
+fn args_at_mut<'a>(
+    args: &'a mut serde_json::Map<String, Value>,
+    path: &[String],
+    create: bool,
+) -> Option<&'a mut serde_json::Map<String, Value>> {
+    path.iter().try_fold(args, |cur, seg| {
+        if create {
+            let v = cur
+                .entry(seg.clone())
+                .or_insert_with(|| Value::Object(serde_json::Map::new()));
+            if v.is_null() {
+                *v = Value::Object(serde_json::Map::new());
+            }
+            v.as_object_mut()
+        } else {
+            cur.get_mut(seg)?.as_object_mut()
+        }
+    })
+}
+
 impl TransformPipeline {
     /// Map a tool name through configured renames.
     #[must_use]
@@ -97,93 +301,153 @@ impl TransformPipeline {
         self.map_tool_name(original_tool_name)
     }
 
+    /// Validate the pipeline configuration (param keys and renames), independent of any schema.
+    ///
+    /// # Errors
+    ///
+    /// Returns a human-readable message for the first invalid override.
+    pub fn validate(&self) -> Result<(), String> {
+        let mut tools: Vec<_> = self.tool_overrides.iter().collect();
+        tools.sort_by(|a, b| a.0.cmp(b.0));
+        for (tool_name, tool) in tools {
+            let mut seen: HashSet<Vec<String>> = HashSet::new();
+            let mut keys: Vec<_> = tool.params.iter().collect();
+            keys.sort_by(|a, b| a.0.cmp(b.0));
+            for (key, o) in keys {
+                let path = param_path(key)
+                    .map_err(|e| format!("toolOverrides.{tool_name}.params: {e}"))?;
+                if !seen.insert(path) {
+                    return Err(format!(
+                        "toolOverrides.{tool_name}.params: '{key}' targets the same parameter as another entry"
+                    ));
+                }
+                o.validate()
+                    .map_err(|e| format!("toolOverrides.{tool_name}.params.{key}: {e}"))?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Check param overrides of `original_tool_name` against the tool's **source** input schema
+    /// (before transforms):
+    /// - every JSON pointer must resolve (top-level names are not checked: defaults and pins may
+    ///   intentionally target undeclared arguments),
+    /// - an `enum` override must be a subset of the source `enum`, if there is one.
+    ///
+    /// # Errors
+    ///
+    /// Returns a message listing the offending params.
+    pub fn validate_against_schema(
+        &self,
+        original_tool_name: &str,
+        schema: &Value,
+    ) -> Result<(), String> {
+        let Some(tool) = self.tool_overrides.get(original_tool_name) else {
+            return Ok(());
+        };
+        let mut problems: Vec<String> = Vec::new();
+        let mut keys: Vec<_> = tool.params.iter().collect();
+        keys.sort_by(|a, b| a.0.cmp(b.0));
+        for (key, o) in keys {
+            let prop = param_path(key)
+                .ok()
+                .and_then(|path| schema_at(schema, &path));
+            let Some(prop) = prop else {
+                if key.starts_with('/') {
+                    problems.push(format!("'{key}' not found in input schema"));
+                }
+                continue;
+            };
+            if let (Some(values), Some(allowed)) =
+                (&o.enum_values, prop.get("enum").and_then(Value::as_array))
+                && values.iter().any(|v| !allowed.contains(v))
+            {
+                problems.push(format!("'{key}' enum is not a subset of the source enum"));
+            }
+        }
+        if problems.is_empty() {
+            return Ok(());
+        }
+        Err(format!(
+            "tool '{original_tool_name}': {}",
+            problems.join("; ")
+        ))
+    }
+
     /// Apply transforms to a tool input schema for the exposed surface (`tools/list`).
     ///
-    /// This is intentionally limited to top-level JSON Schema rewrites:
-    /// - `properties` key rename
-    /// - `required` entries rename
+    /// Each param override targets an object property (top-level, or nested via JSON pointer) and
+    /// rewrites it within its parent schema:
+    /// - `properties` key rename (and matching `required` entries)
+    /// - hiding (removes the property and its `required` entry)
     /// - best-effort `default` injection into `properties.<name>.default`
+    /// - pinned values (`const`) and narrowing constraints (`enum`, `minimum`, `maximum`,
+    ///   `pattern`, `maxLength`)
+    ///
+    /// Pointers use original names; overrides are applied deepest first, so a nested override
+    /// still resolves when one of its parents is renamed.
     pub fn apply_schema_transforms(&self, original_tool_name: &str, schema: &mut Value) {
         let Some(tool) = self.tool_overrides.get(original_tool_name) else {
             return;
         };
 
-        // Rename `properties` keys.
-        if let Some(props) = schema.get_mut("properties").and_then(Value::as_object_mut) {
-            for (raw, o) in &tool.params {
-                let Some(to) = o.rename.as_ref() else {
-                    continue;
-                };
-                if raw == to {
-                    continue;
-                }
-                if let Some(v) = props.remove(raw) {
-                    // Do not override if destination exists.
-                    props.entry(to.clone()).or_insert(v);
-                }
+        for (path, o) in param_entries(tool).into_iter().rev() {
+            let Some((leaf, parents)) = path.split_last() else {
+                continue;
+            };
+            if let Some(parent) = schema_at_mut(schema, parents) {
+                Self::apply_schema_override(parent, leaf, o);
             }
         }
+    }
 
-        // Rename `required` entries.
-        if let Some(req) = schema.get_mut("required").and_then(Value::as_array_mut) {
-            for v in req.iter_mut() {
-                let Some(s) = v.as_str() else {
-                    continue;
-                };
-                if let Some(o) = tool.params.get(s)
-                    && let Some(to) = o.rename.as_ref()
-                {
-                    *v = Value::String(to.clone());
-                }
-            }
-        }
+    fn apply_schema_override(parent: &mut Value, raw: &str, o: &ParamOverride) {
+        let exposed = o.rename.as_deref().unwrap_or(raw);
 
-        // Hide params (after rename, since the exposed name is what exists in `properties`).
-        if let Some(props) = schema.get_mut("properties").and_then(Value::as_object_mut) {
-            for (raw, o) in &tool.params {
-                if o.visible {
-                    continue;
+        if o.visible && exposed != raw {
+            if let Some(props) = parent.get_mut("properties").and_then(Value::as_object_mut)
+                && let Some(v) = props.remove(raw)
+            {
+                // Do not override if destination exists.
+                props.entry(exposed.to_string()).or_insert(v);
+            }
+            if let Some(req) = parent.get_mut("required").and_then(Value::as_array_mut) {
+                for v in req.iter_mut() {
+                    if v.as_str() == Some(raw) {
+                        *v = Value::String(exposed.to_string());
+                    }
                 }
-                let exposed = o.rename.as_deref().unwrap_or(raw);
-                props.remove(exposed);
             }
         }
-        if let Some(req) = schema.get_mut("required").and_then(Value::as_array_mut) {
-            req.retain(|v| {
-                let Some(s) = v.as_str() else {
-                    return true;
-                };
-                let Some((raw, _)) = tool
-                    .params
-                    .iter()
-                    .find(|(raw, o)| o.rename.as_deref().unwrap_or(*raw) == s)
-                else {
-                    return true;
-                };
-                tool.params.get(raw).is_some_and(|o| o.visible)
-            });
-        }
-
-        Self::apply_schema_defaults(tool, schema);
-    }
 
-    fn apply_schema_defaults(tool: &ToolOverride, schema: &mut Value) {
-        let Some(props) = schema.get_mut("properties").and_then(Value::as_object_mut) else {
+        if !o.visible {
+            if let Some(props) = parent.get_mut("properties").and_then(Value::as_object_mut) {
+                props.remove(raw);
+            }
+            if let Some(req) = parent.get_mut("required").and_then(Value::as_array_mut) {
+                req.retain(|v| v.as_str() != Some(raw));
+            }
             return;
-        };
+        }
 
-        for (raw_param, o) in &tool.params {
-            let Some(default_value) = o.default.as_ref() else {
-                continue;
-            };
-            let exposed = o.rename.as_deref().unwrap_or(raw_param);
-            let Some(prop_schema) = props.get_mut(exposed) else {
-                continue;
-            };
-            if let Some(obj) = prop_schema.as_object_mut() {
+        if let Some(obj) = parent
+            .get_mut("properties")
+            .and_then(Value::as_object_mut)
+            .and_then(|props| props.get_mut(exposed))
+            .and_then(Value::as_object_mut)
+        {
+            if let Some(default_value) = o.default.as_ref() {
                 obj.entry("default".to_string())
                     .or_insert_with(|| default_value.clone());
             }
+            o.apply_schema_constraints(obj);
+        }
+
+        // A pinned param is always supplied by the pipeline.
+        if o.pinned.is_some()
+            && let Some(req) = parent.get_mut("required").and_then(Value::as_array_mut)
+        {
+            req.retain(|v| v.as_str() != Some(exposed));
         }
     }
 
@@ -191,55 +455,93 @@ impl TransformPipeline {
     ///
     /// The input `args` is assumed to be in **exposed** parameter names (post-rename).
     /// This function:
-    /// 1) rewrites exposed param names → original param names
-    /// 2) injects defaults for missing/`null` original params
+    /// 1) rewrites exposed param names → original param names (shallowest first, so nested
+    ///    pointers can be followed by original names)
+    /// 2) drops hidden params provided by the caller
+    /// 3) injects defaults for missing/`null` original params, creating missing parent objects
+    ///    for nested defaults
+    /// 4) forces pinned values, replacing whatever the client sent
     pub fn apply_call_transforms(
         &self,
         original_tool_name: &str,
         args: &mut serde_json::Map<String, Value>,
     ) {
+        let Some(tool) = self.tool_overrides.get(original_tool_name) else {
+            return;
+        };
+        let entries = param_entries(tool);
+
         // Invert rename map: original -> exposed  ==> exposed -> original
-        if let Some(tool) = self.tool_overrides.get(original_tool_name) {
-            for (raw, o) in &tool.params {
-                let Some(exposed) = o.rename.as_ref() else {
-                    continue;
-                };
-                if raw == exposed {
-                    continue;
-                }
-                if args.contains_key(raw) {
-                    // Destination already set: drop exposed to avoid ambiguity, but do not override.
-                    args.remove(exposed);
-                    continue;
-                }
-                if let Some(v) = args.remove(exposed) {
-                    args.insert(raw.clone(), v);
-                }
+        for (path, o) in &entries {
+            let Some((raw, parents)) = path.split_last() else {
+                continue;
+            };
+            let Some(exposed) = o.rename.as_ref() else {
+                continue;
+            };
+            if raw == exposed {
+                continue;
+            }
+            let Some(parent) = args_at_mut(args, parents, false) else {
+                continue;
+            };
+            if parent.contains_key(raw) {
+                // Destination already set: drop exposed to avoid ambiguity, but do not override.
+                parent.remove(exposed);
+                continue;
+            }
+            if let Some(v) = parent.remove(exposed) {
+                parent.insert(raw.clone(), v);
             }
+        }
 
-            // Drop hidden params provided by the caller (both raw and exposed keys).
-            for (raw, o) in &tool.params {
-                if o.visible {
-                    continue;
-                }
-                args.remove(raw);
-                if let Some(exposed) = o.rename.as_ref() {
-                    args.remove(exposed);
-                }
+        // Drop hidden params provided by the caller (both raw and exposed keys).
+        for (path, o) in &entries {
+            if o.visible {
+                continue;
+            }
+            let Some((raw, parents)) = path.split_last() else {
+                continue;
+            };
+            let Some(parent) = args_at_mut(args, parents, false) else {
+                continue;
+            };
+            parent.remove(raw);
+            if let Some(exposed) = o.rename.as_ref() {
+                parent.remove(exposed);
             }
+        }
 
-            // Defaults are configured by original param name.
-            for (k, o) in &tool.params {
-                let Some(v) = o.default.as_ref() else {
-                    continue;
-                };
-                let is_missing_or_null = match args.get(k) {
-                    None => true,
-                    Some(cur) => o.treat_null_as_missing && cur.is_null(),
-                };
-                if is_missing_or_null {
-                    args.insert(k.clone(), v.clone());
-                }
+        // Defaults are configured by original param name / pointer.
+        for (path, o) in &entries {
+            let Some(v) = o.default.as_ref() else {
+                continue;
+            };
+            let Some((raw, parents)) = path.split_last() else {
+                continue;
+            };
+            let Some(parent) = args_at_mut(args, parents, true) else {
+                continue;
+            };
+            let is_missing_or_null = match parent.get(raw) {
+                None => true,
+                Some(cur) => o.treat_null_as_missing && cur.is_null(),
+            };
+            if is_missing_or_null {
+                parent.insert(raw.clone(), v.clone());
+            }
+        }
+
+        // Pinned values always win over client input.
+        for (path, o) in &entries {
+            let Some(v) = o.pinned.as_ref() else {
+                continue;
+            };
+            let Some((raw, parents)) = path.split_last() else {
+                continue;
+            };
+            if let Some(parent) = args_at_mut(args, parents, true) {
+                parent.insert(raw.clone(), v.clone());
             }
         }
     }
@@ -391,4 +693,292 @@ mod tests {
             .expect("required");
         assert_eq!(required, &vec![json!("new")]);
     }
+
+    fn nested_pipeline() -> TransformPipeline {
+        let mut p = TransformPipeline::default();
+        p.tool_overrides.insert(
+            "tool".to_string(),
+            super::ToolOverride {
+                params: HashMap::from([
+                    (
+                        "body".to_string(),
+                        super::ParamOverride {
+                            rename: Some("payload".to_string()),
+                            ..Default::default()
+                        },
+                    ),
+                    (
+                        "/body/internal_flag".to_string(),
+                        super::ParamOverride {
+                            visible: false,
+                            default: Some(json!(true)),
+                            ..Default::default()
+                        },
+                    ),
+                    (
+                        "/body/options/region".to_string(),
+                        super::ParamOverride {
+                            rename: Some("zone".to_string()),
+                            default: Some(json!("eu-west-1")),
+                            ..Default::default()
+                        },
+                    ),
+                ]),
+                ..Default::default()
+            },
+        );
+        p
+    }
+
+    fn nested_schema() -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "body": {
+                    "type": "object",
+                    "properties": {
+                        "name": { "type": "string" },
+                        "internal_flag": { "type": "boolean" },
+                        "options": {
+                            "type": "object",
+                            "properties": { "region": { "type": "string" } },
+                            "required": ["region"]
+                        }
+                    },
+                    "required": ["name", "internal_flag"]
+                }
+            },
+            "required": ["body"]
+        })
+    }
+
+    #[test]
+    fn apply_schema_transforms_follows_json_pointers_into_nested_objects() {
+        let p = nested_pipeline();
+        let mut schema = nested_schema();
+        assert_eq!(p.validate(), Ok(()));
+        assert_eq!(p.validate_against_schema("tool", &schema), Ok(()));
+
+        p.apply_schema_transforms("tool", &mut schema);
+
+        assert_eq!(
+            schema,
+            json!({
+                "type": "object",
+                "properties": {
+                    "payload": {
+                        "type": "object",
+                        "properties": {
+                            "name": { "type": "string" },
+                            "options": {
+                                "type": "object",
+                                "properties": {
+                                    "zone": { "type": "string", "default": "eu-west-1" }
+                                },
+                                "required": ["zone"]
+                            }
+                        },
+                        "required": ["name"]
+                    }
+                },
+                "required": ["payload"]
+            })
+        );
+    }
+
+    #[test]
+    fn apply_call_transforms_rewrites_nested_args_and_injects_nested_defaults() {
+        let p = nested_pipeline();
+
+        let mut args = serde_json::Map::from_iter([(
+            "payload".to_string(),
+            json!({ "name": "n", "internal_flag": false, "options": { "zone": "us-east-1" } }),
+        )]);
+        p.apply_call_transforms("tool", &mut args);
+        assert_eq!(
+            Value::Object(args),
+            json!({ "body": {
+                "name": "n",
+                "internal_flag": true,
+                "options": { "region": "us-east-1" }
+            } })
+        );
+
+        // Missing parents are created for nested defaults.
+        let mut args = serde_json::Map::new();
+        p.apply_call_transforms("tool", &mut args);
+        assert_eq!(
+            Value::Object(args),
+            json!({ "body": { "internal_flag": true, "options": { "region": "eu-west-1" } } })
+        );
+    }
+
+    #[test]
+    fn validate_rejects_bad_pointers_and_reports_unresolved_ones() {
+        let mut p = nested_pipeline();
+        p.tool_overrides
+            .get_mut("tool")
+            .expect("tool")
+            .params
+            .insert("/body/missing".to_string(), super::ParamOverride::default());
+        assert_eq!(p.validate(), Ok(()));
+        let err = p
+            .validate_against_schema("tool", &nested_schema())
+            .expect_err("unresolved pointer");
+        assert!(err.contains("/body/missing"), "{err}");
+
+        let mut p = TransformPipeline::default();
+        p.tool_overrides.insert(
+            "tool".to_string(),
+            super::ToolOverride {
+                params: HashMap::from([
+                    ("a".to_string(), super::ParamOverride::default()),
+                    ("/a".to_string(), super::ParamOverride::default()),
+                ]),
+                ..Default::default()
+            },
+        );
+        assert!(p.validate().is_err());
+
+        let mut p = TransformPipeline::default();
+        p.tool_overrides.insert(
+            "tool".to_string(),
+            super::ToolOverride {
+                params: HashMap::from([("/a//b".to_string(), super::ParamOverride::default())]),
+                ..Default::default()
+            },
+        );
+        assert!(p.validate().is_err());
+
+        let mut p = TransformPipeline::default();
+        p.tool_overrides.insert(
+            "tool".to_string(),
+            super::ToolOverride {
+                params: HashMap::from([(
+                    "tag".to_string(),
+                    super::ParamOverride {
+                        pattern: Some("^(v".to_string()),
+                        ..Default::default()
+                    },
+                )]),
+                ..Default::default()
+            },
+        );
+        let err = p.validate().expect_err("invalid regex");
+        assert!(err.contains("pattern is not a valid regex"), "{err}");
+    }
+
+    #[test]
+    fn pinned_params_and_constraints_narrow_schema_and_force_values() {
+        let mut p = TransformPipeline::default();
+        p.tool_overrides.insert(
+            "deploy".to_string(),
+            super::ToolOverride {
+                params: HashMap::from([
+                    (
+                        "environment".to_string(),
+                        super::ParamOverride {
+                            enum_values: Some(vec![json!("staging")]),
+                            ..Default::default()
+                        },
+                    ),
+                    (
+                        "replicas".to_string(),
+                        super::ParamOverride {
+                            minimum: Some(1.into()),
+                            maximum: Some(3.into()),
+                            ..Default::default()
+                        },
+                    ),
+                    (
+                        "tag".to_string(),
+                        super::ParamOverride {
+                            pattern: Some("^v".to_string()),
+                            max_length: Some(64),
+                            ..Default::default()
+                        },
+                    ),
+                    (
+                        "region".to_string(),
+                        super::ParamOverride {
+                            pinned: Some(json!("eu-west-1")),
+                            ..Default::default()
+                        },
+                    ),
+                ]),
+                ..Default::default()
+            },
+        );
+        let mut schema = json!({
+            "type": "object",
+            "properties": {
+                "environment": { "type": "string", "enum": ["staging", "production"] },
+                "replicas": { "type": "integer", "minimum": 0, "maximum": 10 },
+                "tag": { "type": "string", "pattern": "^[a-z0-9.]+$", "maxLength": 128 },
+                "region": { "type": "string" }
+            },
+            "required": ["environment", "region"]
+        });
+        assert_eq!(p.validate(), Ok(()));
+        assert_eq!(p.validate_against_schema("deploy", &schema), Ok(()));
+
+        p.apply_schema_transforms("deploy", &mut schema);
+        assert_eq!(
+            schema,
+            json!({
+                "type": "object",
+                "properties": {
+                    "environment": { "type": "string", "enum": ["staging"] },
+                    "replicas": { "type": "integer", "minimum": 1, "maximum": 3 },
+                    "tag": {
+                        "type": "string",
+                        "pattern": "^[a-z0-9.]+$",
+                        "maxLength": 64,
+                        "allOf": [{ "pattern": "^v" }]
+                    },
+                    "region": { "type": "string", "const": "eu-west-1", "default": "eu-west-1" }
+                },
+                "required": ["environment"]
+            })
+        );
+
+        let mut args = serde_json::Map::from_iter([
+            ("environment".to_string(), json!("staging")),
+            ("region".to_string(), json!("us-east-1")),
+        ]);
+        p.apply_call_transforms("deploy", &mut args);
+        assert_eq!(args.get("region"), Some(&json!("eu-west-1")));
+
+        // Constraints may only narrow the source schema.
+        let mut widened = p.clone();
+        widened
+            .tool_overrides
+            .get_mut("deploy")
+            .expect("deploy")
+            .params
+            .get_mut("environment")
+            .expect("environment")
+            .enum_values = Some(vec![json!("dev")]);
+        let source = json!({
+            "properties": { "environment": { "type": "string", "enum": ["staging"] } }
+        });
+        assert!(widened.validate_against_schema("deploy", &source).is_err());
+
+        let mut conflicting = TransformPipeline::default();
+        conflicting.tool_overrides.insert(
+            "deploy".to_string(),
+            super::ToolOverride {
+                params: HashMap::from([(
+                    "region".to_string(),
+                    super::ParamOverride {
+                        pinned: Some(json!("a")),
+                        default: Some(json!("b")),
+                        ..Default::default()
+                    },
+                )]),
+                ..Default::default()
+            },
+        );
+        assert!(conflicting.validate().is_err());
+    }
 }
@@ -202,7 +202,11 @@ export default function NewOpenApiSourceWizardPage() {
                 {step === 2 && inspect && (
                   <>
                     <p className="mt-3 text-base text-zinc-400 max-w-2xl">
-                      We fetched and parsed your spec. Choose a source name.
+                      We fetched and parsed your spec
+                      {inspect.specVersion
+                        ? ` (${inspect.specVersion === "2.0" ? "Swagger" : "OpenAPI"} ${inspect.specVersion})`
+                        : ""}
+                      . Choose a source name.
                     </p>
 
                     <div className="mt-6 space-y-4">
@@ -63,6 +63,7 @@ export type ProfileSurface = {
 
 export type OpenApiInspectResponse = {
   title?: string | null;
+  specVersion?: "2.0" | "3.0" | "3.1" | null;
   inferredBaseUrl: string;
   suggestedId: string;
   tools: { name: string; description?: string | null }[];